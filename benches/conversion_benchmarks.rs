@@ -138,6 +138,14 @@ fn bench_html_converter_config(c: &mut Criterion) {
             remove_sidebars: black_box(true),
             remove_ads: black_box(true),
             max_blank_lines: black_box(2),
+            detect_soft_404: black_box(true),
+            detect_access_wall: black_box(true),
+            detect_login_walls: black_box(false),
+            escape_mode: markdowndown::converters::EscapeMode::Minimal,
+            passthrough_unconvertible: false,
+            summarize_forms: false,
+            detection_strategy: markdowndown::converters::DetectionStrategy::Url,
+            plain_text: markdowndown::converters::PlainTextHeuristics::default(),
         })
     });
 
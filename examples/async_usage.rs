@@ -133,6 +133,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let duration = start.elapsed();
     println!("      ⏱️  All three completed in {duration:?}");
 
+    #[allow(clippy::useless_vec)]
     let results = vec![result1, result2, result3];
     for (i, result) in results.iter().enumerate() {
         match result {
@@ -272,10 +272,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 MarkdownError::ContentError { .. } => "Content",
                 MarkdownError::ConverterError { .. } => "Converter",
                 MarkdownError::ConfigurationError { .. } => "Configuration",
+                MarkdownError::Skipped(_) => "Skipped",
                 MarkdownError::ParseError { .. } => "Parse",
                 MarkdownError::InvalidUrl { .. } => "Invalid URL",
                 MarkdownError::AuthError { .. } => "Auth (Legacy)",
                 MarkdownError::LegacyConfigurationError { .. } => "Config (Legacy)",
+                _ => "Other",
             };
             *error_types.entry(error_type).or_insert(0) += 1;
         }
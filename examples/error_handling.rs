@@ -145,6 +145,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         ValidationErrorKind::MissingParameter => {
                             println!("      📝 Missing required parameter");
                         }
+                        _ => println!("      ❓ Other validation error"),
                     },
                     MarkdownError::EnhancedNetworkError { kind, context } => {
                         match kind {
@@ -176,6 +177,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 println!("      🌐 DNS resolution failed");
                                 println!("         💡 Check domain name and DNS settings");
                             }
+                            _ => println!("      ❓ Other network error"),
                         }
                         println!("         🕐 Error occurred at: {}", context.timestamp);
                     }
@@ -196,6 +198,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             println!("      ⏰ Token expired");
                             println!("         💡 Refresh or regenerate authentication token");
                         }
+                        _ => println!("      ❓ Other authentication error"),
                     },
                     MarkdownError::ContentError { kind, context: _ } => match kind {
                         ContentErrorKind::EmptyContent => {
@@ -210,6 +213,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             println!("      🔧 Content parsing failed");
                             println!("         💡 Content may be corrupted or malformed");
                         }
+                        ContentErrorKind::SoftNotFound => {
+                            println!("      🚫 Page is a \"not found\" template");
+                            println!("         💡 The content may have moved or been removed");
+                        }
+                        _ => println!("      ❓ Other content error"),
                     },
                     // Legacy error types
                     MarkdownError::NetworkError { message } => {
@@ -389,6 +397,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             }
                         }
                         NetworkErrorKind::DnsResolution => "DNS_ERROR",
+                        _ => "NETWORK_ERROR",
                     },
                     MarkdownError::AuthenticationError { .. } => "AUTH_ERROR",
                     MarkdownError::ContentError { .. } => "CONTENT_ERROR",
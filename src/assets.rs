@@ -0,0 +1,471 @@
+//! Image downloading for converted documents.
+//!
+//! With [`crate::config::OutputConfig::download_images`] enabled, the images a
+//! converted document references are downloaded and their links rewritten to
+//! relative paths, so the markdown renders without reaching the original
+//! host. This runs after conversion on the output of every converter: images
+//! are found as `![alt](url)` links, which markdown sources already contain
+//! and which the HTML converter keeps `<img>` elements as while downloading
+//! is enabled.
+//!
+//! Images are named after a hash of their content, so an image referenced
+//! twice, or by two documents, is stored once. They are written to the
+//! configured directory, or kept in memory for the caller to store, and in
+//! either case returned by [`crate::MarkdownDown::convert_url_detailed`].
+//!
+//! Only `http`, `https`, and `data:` images are downloaded. An image that
+//! cannot be downloaded, or is not an image, keeps its original link and a
+//! warning is logged.
+//!
+//! # Usage Examples
+//!
+//! ```rust,no_run
+//! use markdowndown::config::ImageDownloads;
+//! use markdowndown::{Config, MarkdownDown};
+//!
+//! # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
+//! let config = Config::builder()
+//!     .download_images(ImageDownloads::InMemory)
+//!     .build();
+//! let result = MarkdownDown::with_config(config)
+//!     .convert_url_detailed("https://example.com/article.html")
+//!     .await?;
+//! for asset in &result.assets {
+//!     println!("{} -> {} ({} bytes)", asset.source_url, asset.path, asset.bytes.len());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::HttpClient;
+use crate::config::ImageDownloads;
+use crate::events;
+use crate::identity;
+use crate::types::{ConverterErrorKind, ErrorContext, MarkdownError};
+use bytes::Bytes;
+use data_url::DataUrl;
+use futures::stream::{self, StreamExt};
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+use tracing::{debug, warn};
+use url::Url;
+
+/// Matches inline markdown images: `![alt](target "title")`.
+const IMAGE_PATTERN: &str = r#"!\[((?:\\.|[^\]\\])*)\]\(\s*<?([^)\s>]+)>?(\s+"[^"]*")?\s*\)"#;
+
+/// How many images of one document are downloaded at once.
+const MAX_CONCURRENT_DOWNLOADS: usize = 4;
+
+/// Folder that links point into when images are kept in memory.
+const IN_MEMORY_FOLDER: &str = "assets";
+
+/// Image media types and the file extension each is stored with.
+const IMAGE_TYPES: [(&str, &str); 8] = [
+    ("image/png", "png"),
+    ("image/jpeg", "jpg"),
+    ("image/gif", "gif"),
+    ("image/webp", "webp"),
+    ("image/svg+xml", "svg"),
+    ("image/avif", "avif"),
+    ("image/bmp", "bmp"),
+    ("image/x-icon", "ico"),
+];
+
+/// An image downloaded for a converted document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Asset {
+    /// The image URL, resolved against the document URL
+    pub source_url: String,
+    /// The relative path the markdown links to, e.g. `assets/1f2e3d4c5b6a7980.png`
+    pub path: String,
+    /// The image media type, e.g. `image/png`
+    pub content_type: String,
+    /// The image data
+    pub bytes: Bytes,
+}
+
+/// A downloaded image before it is named and stored.
+struct DownloadedImage {
+    content_type: &'static str,
+    extension: &'static str,
+    bytes: Bytes,
+}
+
+/// Downloads the images of a converted document and rewrites their links.
+pub struct AssetDownloader<'a> {
+    client: &'a HttpClient,
+    mode: &'a ImageDownloads,
+}
+
+impl<'a> AssetDownloader<'a> {
+    /// Creates a downloader that fetches images with the given client.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The HTTP client used to download images
+    /// * `mode` - Where downloaded images are stored
+    pub fn new(client: &'a HttpClient, mode: &'a ImageDownloads) -> Self {
+        Self { client, mode }
+    }
+
+    /// Downloads the images a document links to and rewrites the links to the copies.
+    ///
+    /// # Arguments
+    ///
+    /// * `markdown` - The converted markdown
+    /// * `source_url` - The URL the document was converted from, used to
+    ///   resolve relative image links
+    ///
+    /// # Returns
+    ///
+    /// The rewritten markdown and the downloaded images, in order of first
+    /// reference. With `ImageDownloads::Off` the markdown is returned unchanged.
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ConverterError` - If an image cannot be written to the
+    ///   configured directory
+    pub async fn localize(
+        &self,
+        markdown: &str,
+        source_url: &str,
+    ) -> Result<(String, Vec<Asset>), MarkdownError> {
+        let folder = match self.mode {
+            ImageDownloads::Off => return Ok((markdown.to_string(), Vec::new())),
+            ImageDownloads::Directory(dir) => dir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            ImageDownloads::InMemory => IN_MEMORY_FOLDER.to_string(),
+        };
+
+        let pattern = Regex::new(IMAGE_PATTERN).expect("valid image pattern");
+        let base = Url::parse(source_url).ok();
+
+        // Collect the distinct image URLs in order of first reference
+        let mut image_urls: Vec<String> = Vec::new();
+        rewrite_images(markdown, &pattern, |caps| {
+            if let Some(url) = resolve(base.as_ref(), &caps[2]) {
+                if !image_urls.contains(&url) {
+                    image_urls.push(url);
+                }
+            }
+            None
+        });
+        if image_urls.is_empty() {
+            return Ok((markdown.to_string(), Vec::new()));
+        }
+
+        let downloads: Vec<(String, Option<DownloadedImage>)> = stream::iter(image_urls)
+            .map(|url| async move {
+                let image = self.fetch(&url).await;
+                (url, image)
+            })
+            .buffered(MAX_CONCURRENT_DOWNLOADS)
+            .collect()
+            .await;
+
+        let mut assets: Vec<Asset> = Vec::new();
+        let mut paths: HashMap<String, String> = HashMap::new();
+        for (url, image) in downloads {
+            let Some(DownloadedImage {
+                content_type,
+                extension,
+                bytes,
+            }) = image
+            else {
+                continue;
+            };
+            let file_name = format!("{}.{extension}", identity::fnv1a_hex(&bytes));
+            let path = if folder.is_empty() {
+                file_name.clone()
+            } else {
+                format!("{folder}/{file_name}")
+            };
+            if let ImageDownloads::Directory(dir) = self.mode {
+                self.write(dir, &file_name, &bytes, source_url).await?;
+            }
+            paths.insert(url.clone(), path.clone());
+            if !assets.iter().any(|asset| asset.path == path) {
+                assets.push(Asset {
+                    source_url: url,
+                    path,
+                    content_type: content_type.to_string(),
+                    bytes,
+                });
+            }
+        }
+        debug!("Downloaded {} images for {}", assets.len(), source_url);
+
+        let rewritten = rewrite_images(markdown, &pattern, |caps| {
+            let url = resolve(base.as_ref(), &caps[2])?;
+            let path = paths.get(&url)?;
+            let title = caps.get(3).map_or("", |title| title.as_str());
+            Some(format!("![{}]({path}{title})", &caps[1]))
+        });
+        Ok((rewritten, assets))
+    }
+
+    /// Downloads an image, returning its media type, file extension, and data.
+    ///
+    /// Returns `None`, after logging a warning, if the image cannot be
+    /// downloaded or is not a recognized image format.
+    async fn fetch(&self, url: &str) -> Option<DownloadedImage> {
+        let (bytes, declared_type) = if url.starts_with("data:") {
+            let decoded = DataUrl::process(url).ok().and_then(|data_url| {
+                let mime_type = data_url.mime_type();
+                let declared = format!("{}/{}", mime_type.type_, mime_type.subtype);
+                let (bytes, _fragment) = data_url.decode_to_vec().ok()?;
+                Some((Bytes::from(bytes), Some(declared)))
+            });
+            let Some(decoded) = decoded else {
+                warn!("Skipping image with malformed data URL");
+                return None;
+            };
+            decoded
+        } else {
+            match self.client.get_bytes(url).await {
+                Ok(bytes) => (bytes, None),
+                Err(e) => {
+                    warn!("Keeping original link to image {}: {}", url, e);
+                    events::warning(format!("Keeping original link to image {url}: {e}"));
+                    return None;
+                }
+            }
+        };
+
+        let image_type = sniff_image_type(&bytes)
+            .or_else(|| declared_type.as_deref().and_then(type_by_media_type))
+            .or_else(|| type_by_extension(url));
+        match image_type {
+            Some((content_type, extension)) => Some(DownloadedImage {
+                content_type,
+                extension,
+                bytes,
+            }),
+            None => {
+                warn!("Keeping original link to {}: not a recognized image", url);
+                events::warning(format!("Keeping original link to {url}: not a recognized image"));
+                None
+            }
+        }
+    }
+
+    /// Writes an image to the assets directory, unless a copy is already there.
+    async fn write(
+        &self,
+        dir: &std::path::Path,
+        file_name: &str,
+        bytes: &[u8],
+        source_url: &str,
+    ) -> Result<(), MarkdownError> {
+        let write_error = |e: std::io::Error| MarkdownError::ConverterError {
+            kind: ConverterErrorKind::ProcessingError,
+            context: ErrorContext::new(source_url, "Write image", "AssetDownloader")
+                .with_info(format!("IO error writing to {}: {e}", dir.display())),
+        };
+
+        let path = dir.join(file_name);
+        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            return Ok(());
+        }
+        tokio::fs::create_dir_all(dir).await.map_err(write_error)?;
+        tokio::fs::write(&path, bytes).await.map_err(write_error)
+    }
+}
+
+/// Resolves an image target against the document URL, keeping only images
+/// that can be downloaded.
+fn resolve(base: Option<&Url>, target: &str) -> Option<String> {
+    if target.starts_with("data:") {
+        return Some(target.to_string());
+    }
+    let resolved = match base {
+        Some(base) => base.join(target).ok()?,
+        None => Url::parse(target).ok()?,
+    };
+    matches!(resolved.scheme(), "http" | "https").then(|| resolved.to_string())
+}
+
+/// Replaces the markdown images outside code blocks for which `replace`
+/// returns a replacement.
+fn rewrite_images(
+    markdown: &str,
+    pattern: &Regex,
+    mut replace: impl FnMut(&Captures) -> Option<String>,
+) -> String {
+    let mut output = String::with_capacity(markdown.len());
+    let mut in_code_block = false;
+
+    for line in markdown.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            output.push_str(line);
+            continue;
+        }
+        if in_code_block {
+            output.push_str(line);
+            continue;
+        }
+        let rewritten = pattern.replace_all(line, |caps: &Captures| {
+            replace(caps).unwrap_or_else(|| caps[0].to_string())
+        });
+        output.push_str(&rewritten);
+    }
+    output
+}
+
+/// Recognizes an image format from the first bytes of its data.
+fn sniff_image_type(bytes: &[u8]) -> Option<(&'static str, &'static str)> {
+    let media_type = if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        "image/png"
+    } else if bytes.starts_with(&[0xff, 0xd8, 0xff]) {
+        "image/jpeg"
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        "image/gif"
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        "image/webp"
+    } else if bytes.len() >= 12 && &bytes[4..12] == b"ftypavif" {
+        "image/avif"
+    } else if bytes.starts_with(b"BM") {
+        "image/bmp"
+    } else if bytes.starts_with(&[0, 0, 1, 0]) {
+        "image/x-icon"
+    } else {
+        let head = String::from_utf8_lossy(&bytes[..bytes.len().min(1024)]).to_ascii_lowercase();
+        if head.contains("<svg") {
+            "image/svg+xml"
+        } else {
+            return None;
+        }
+    };
+    type_by_media_type(media_type)
+}
+
+/// Looks up a known image media type.
+fn type_by_media_type(media_type: &str) -> Option<(&'static str, &'static str)> {
+    IMAGE_TYPES
+        .iter()
+        .find(|(known, _)| *known == media_type)
+        .copied()
+}
+
+/// Guesses an image format from the file extension of its URL.
+fn type_by_extension(url: &str) -> Option<(&'static str, &'static str)> {
+    let path = Url::parse(url).ok()?.path().to_ascii_lowercase();
+    let extension = path.rsplit_once('.')?.1;
+    let extension = if extension == "jpeg" {
+        "jpg"
+    } else {
+        extension
+    };
+    IMAGE_TYPES
+        .iter()
+        .find(|(_, known)| *known == extension)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\nrest of the image";
+
+    async fn image_server() -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/img/logo.png"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(PNG))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/missing.png"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn test_localize_in_memory_rewrites_links() {
+        let server = image_server().await;
+        let client = HttpClient::new();
+        let mode = ImageDownloads::InMemory;
+        let page = format!("{}/docs/page.html", server.uri());
+        let markdown = format!(
+            "![Logo](/img/logo.png \"The logo\")\n![Again]({}/img/logo.png)\n![Gone](/missing.png)\n```\n![Code](/img/logo.png)\n```\n",
+            server.uri()
+        );
+
+        let (rewritten, assets) = AssetDownloader::new(&client, &mode)
+            .localize(&markdown, &page)
+            .await
+            .unwrap();
+
+        assert_eq!(assets.len(), 1);
+        let asset = &assets[0];
+        assert_eq!(asset.content_type, "image/png");
+        assert_eq!(asset.bytes.as_ref(), PNG);
+        assert!(asset.path.starts_with("assets/") && asset.path.ends_with(".png"));
+        assert_eq!(
+            rewritten,
+            format!(
+                "![Logo]({0} \"The logo\")\n![Again]({0})\n![Gone](/missing.png)\n```\n![Code](/img/logo.png)\n```\n",
+                asset.path
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_localize_writes_directory() {
+        let server = image_server().await;
+        let client = HttpClient::new();
+        let dir = tempfile::tempdir().unwrap();
+        let mode = ImageDownloads::Directory(dir.path().join("assets"));
+        let markdown =
+            "# Page\n\n![Logo](img/logo.png)\n![Dot](data:image/gif;base64,R0lGODlhAQABAAAAACw=)\n";
+
+        let (rewritten, assets) = AssetDownloader::new(&client, &mode)
+            .localize(markdown, &format!("{}/", server.uri()))
+            .await
+            .unwrap();
+
+        assert_eq!(assets.len(), 2);
+        assert_eq!(assets[1].content_type, "image/gif");
+        for asset in &assets {
+            assert!(rewritten.contains(&format!("]({})", asset.path)));
+            let written = std::fs::read(dir.path().join(&asset.path)).unwrap();
+            assert_eq!(written, asset.bytes.as_ref());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_localize_off_is_unchanged() {
+        let client = HttpClient::new();
+        let mode = ImageDownloads::Off;
+        let markdown = "![Logo](https://example.com/logo.png)\n";
+        let (rewritten, assets) = AssetDownloader::new(&client, &mode)
+            .localize(markdown, "https://example.com/")
+            .await
+            .unwrap();
+        assert_eq!(rewritten, markdown);
+        assert!(assets.is_empty());
+    }
+
+    #[test]
+    fn test_image_type_detection() {
+        assert_eq!(sniff_image_type(PNG), Some(("image/png", "png")));
+        assert_eq!(
+            sniff_image_type(b"<?xml version=\"1.0\"?><svg></svg>"),
+            Some(("image/svg+xml", "svg"))
+        );
+        assert_eq!(sniff_image_type(b"<html>"), None);
+        assert_eq!(
+            type_by_extension("https://example.com/photo.JPEG?w=200"),
+            Some(("image/jpeg", "jpg"))
+        );
+    }
+}
@@ -4,10 +4,31 @@
 //! using the markdowndown library. It supports single URL conversion, batch
 //! processing, and various output formats.
 
+use chrono::{DateTime, Utc};
 use clap::{Parser, Subcommand, ValueEnum};
+use markdowndown::chunks::{chunk_document, Chunk, ChunkOptions};
+use markdowndown::compression::{self, Compression};
+use markdowndown::concurrency::AdaptiveConcurrency;
+use markdowndown::config::{
+    ConfigBuilder, DateWindowAction, DirectionMarkers, HttpCacheConfig, ImageDownloads, LintMode,
+    Preset, RateLimit, RequestTemplate, SubDocumentMode,
+};
+use markdowndown::detection::RulePattern;
+use markdowndown::filter::ContentFilter;
+use markdowndown::frontmatter::FrontmatterFormat;
+use markdowndown::graph::GraphBuilder;
+use markdowndown::identity;
+use markdowndown::index::{render_index, IndexEntry, IndexGrouping};
+use markdowndown::progress::ProgressEvent;
+use markdowndown::retry::{ExponentialBackoff, FibonacciBackoff, FixedDelay, NoRetry};
+use markdowndown::search::SearchDocument;
+use markdowndown::stats::HostStats;
+use markdowndown::types::{ErrorReport, UrlType};
+use markdowndown::utils::parse_date;
 use markdowndown::{Config, MarkdownDown};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::process;
 use tracing::{debug, error, info};
 
@@ -45,7 +66,7 @@ struct Cli {
     #[arg(long)]
     no_frontmatter: bool,
 
-    /// Output only the frontmatter
+    /// Output only the frontmatter, skipping body conversion
     #[arg(long)]
     frontmatter_only: bool,
 
@@ -65,6 +86,30 @@ struct Cli {
     #[arg(long)]
     user_agent: Option<String>,
 
+    /// Skip documents last modified or published before this date (YYYY-MM-DD or RFC 3339)
+    #[arg(long)]
+    since: Option<String>,
+
+    /// Add an `excerpt` frontmatter field with the first N sentences of content
+    #[arg(long, value_name = "SENTENCES")]
+    excerpt: Option<usize>,
+
+    /// Add a `tags` frontmatter field with up to N extracted keywords
+    #[arg(long, value_name = "MAX")]
+    tags: Option<usize>,
+
+    /// Lint the converted markdown as a final step: report or fix problems
+    #[arg(long, value_name = "MODE")]
+    lint: Option<LintLevel>,
+
+    /// Write documents made of several parts, such as PDF chapters, as one file per part
+    #[arg(long, value_name = "MODE")]
+    sub_documents: Option<SubDocumentStyle>,
+
+    /// Start from a preset: archive, llm-ingest, site-migration or fast
+    #[arg(long, value_name = "PRESET")]
+    preset: Option<Preset>,
+
     /// Subcommand
     #[command(subcommand)]
     command: Option<Commands>,
@@ -81,6 +126,119 @@ enum OutputFormat {
     Yaml,
 }
 
+/// Lint modes for converted output
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum LintLevel {
+    /// Do not lint (default)
+    Off,
+    /// Log lint problems as warnings
+    Warn,
+    /// Fix lint problems in the output
+    Fix,
+}
+
+impl From<LintLevel> for LintMode {
+    fn from(level: LintLevel) -> Self {
+        match level {
+            LintLevel::Off => LintMode::Off,
+            LintLevel::Warn => LintMode::Warn,
+            LintLevel::Fix => LintMode::Fix,
+        }
+    }
+}
+
+/// How documents made of several parts are written
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum SubDocumentStyle {
+    /// One file, each part starting at its own heading (default)
+    Concatenate,
+    /// One file per part, named `{name}-{part_index}`
+    Split,
+}
+
+impl From<SubDocumentStyle> for SubDocumentMode {
+    fn from(style: SubDocumentStyle) -> Self {
+        match style {
+            SubDocumentStyle::Concatenate => SubDocumentMode::Concatenate,
+            SubDocumentStyle::Split => SubDocumentMode::Split,
+        }
+    }
+}
+
+/// Right-to-left paragraph markers in a config file
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum DirectionStyle {
+    /// Leave paragraphs unmarked (default)
+    Off,
+    /// Start right-to-left lines with a right-to-left mark
+    Unicode,
+    /// Wrap right-to-left paragraphs in `dir="rtl"` elements
+    Html,
+}
+
+impl From<DirectionStyle> for DirectionMarkers {
+    fn from(style: DirectionStyle) -> Self {
+        match style {
+            DirectionStyle::Off => DirectionMarkers::Off,
+            DirectionStyle::Unicode => DirectionMarkers::Unicode,
+            DirectionStyle::Html => DirectionMarkers::Html,
+        }
+    }
+}
+
+/// Retry delay policy in a config file
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum RetryStyle {
+    /// Double the delay on every retry (default)
+    Exponential,
+    /// Double the delay on every retry, randomized to spread retries out
+    Jitter,
+    /// Wait the same delay before every retry
+    Fixed,
+    /// Grow the delay along the Fibonacci sequence
+    Fibonacci,
+    /// Never retry
+    None,
+}
+
+impl RetryStyle {
+    /// Adds the policy to a configuration, with the delay before the first retry.
+    fn apply(self, builder: ConfigBuilder, base: std::time::Duration) -> ConfigBuilder {
+        match self {
+            RetryStyle::Exponential => builder.retry_policy(ExponentialBackoff::new(base)),
+            RetryStyle::Jitter => builder.retry_policy(ExponentialBackoff::new(base).jitter(true)),
+            RetryStyle::Fixed => builder.retry_policy(FixedDelay::new(base)),
+            RetryStyle::Fibonacci => builder.retry_policy(FibonacciBackoff::new(base)),
+            RetryStyle::None => builder.retry_policy(NoRetry),
+        }
+    }
+}
+
+/// Frontmatter format in a config file
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum FrontmatterStyle {
+    /// YAML between `---` lines (default)
+    Yaml,
+    /// TOML between `+++` lines
+    Toml,
+    /// A leading JSON object
+    Json,
+}
+
+impl From<FrontmatterStyle> for FrontmatterFormat {
+    fn from(style: FrontmatterStyle) -> Self {
+        match style {
+            FrontmatterStyle::Yaml => FrontmatterFormat::Yaml,
+            FrontmatterStyle::Toml => FrontmatterFormat::Toml,
+            FrontmatterStyle::Json => FrontmatterFormat::Json,
+        }
+    }
+}
+
 /// Available subcommands
 #[derive(Subcommand)]
 enum Commands {
@@ -91,12 +249,48 @@ enum Commands {
         /// Number of concurrent conversions
         #[arg(short, long, default_value = "5")]
         concurrency: usize,
+        /// Adapt concurrency to each host's errors and latency, up to --concurrency per host
+        #[arg(long)]
+        adaptive: bool,
         /// Output directory for converted files
         #[arg(long)]
         output_dir: Option<String>,
         /// Show conversion statistics
         #[arg(long)]
         stats: bool,
+        /// Output filename template without extension ({index}, {id}, {slug})
+        #[arg(long, default_value = "{index}")]
+        filename_template: String,
+        /// Compress output files (gzip or zstd)
+        #[arg(long)]
+        compress: Option<Compression>,
+        /// Write to a staging directory and move the files into --output-dir once the batch completes
+        #[arg(long, requires = "output_dir")]
+        staged: bool,
+        /// Write a .error.yaml file in place of each output that fails to convert
+        #[arg(long, requires = "output_dir")]
+        error_files: bool,
+        /// Write an index.md linking to every converted file, grouped by host or path
+        #[arg(long, value_name = "GROUPING", requires = "output_dir")]
+        index: Option<IndexGrouping>,
+        /// Write a graph.json of the converted files and the links between them
+        #[arg(long, requires = "output_dir")]
+        graph: bool,
+        /// Write a .search.json file next to each output, for search engine ingestion
+        #[arg(long, requires = "output_dir")]
+        search_json: bool,
+        /// Write a .chunks.jsonl file of overlapping text chunks next to each output
+        #[arg(long, requires = "output_dir")]
+        chunks: bool,
+        /// Maximum tokens per chunk
+        #[arg(long, default_value = "256", requires = "chunks")]
+        chunk_tokens: usize,
+        /// Tokens each chunk repeats from the previous one
+        #[arg(long, default_value = "32", requires = "chunks")]
+        chunk_overlap: usize,
+        /// Keep converted markdown in this directory, so re-runs skip unchanged URLs
+        #[arg(long, value_name = "DIR")]
+        cache_dir: Option<String>,
     },
     /// Detect URL type without conversion
     Detect {
@@ -105,6 +299,16 @@ enum Commands {
     },
     /// List supported URL types
     ListTypes,
+    /// Show what converting URLs will do, without fetching anything
+    Plan {
+        /// URL to plan, or with --batch, a file containing URLs
+        url: String,
+        /// Read URLs from a file (one per line) and summarize the batch
+        #[arg(long)]
+        batch: bool,
+    },
+    /// Check connectivity, configured tokens, and external tools
+    Doctor,
 }
 
 /// Configuration file structure for TOML files
@@ -129,23 +333,91 @@ struct ConfigFile {
     /// Logging configuration
     #[serde(default)]
     pub logging: LoggingConfig,
+
+    /// Allow/deny content filters
+    #[serde(default)]
+    pub filters: FiltersConfig,
+
+    /// Query parameters and headers for requests to specific services
+    #[serde(default)]
+    pub request_templates: Vec<RequestTemplateConfig>,
+
+    /// Request rates allowed for specific hosts
+    #[serde(default)]
+    pub rate_limits: Vec<RateLimitConfig>,
+
+    /// URL types assigned to matching hosts or URLs
+    #[serde(default)]
+    pub detection_rules: Vec<DetectionRuleConfig>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 struct HttpConfig {
     #[serde(default = "default_timeout")]
     pub timeout_seconds: u64,
+    pub total_timeout_seconds: Option<u64>,
+    #[serde(default)]
+    pub race_strategies: bool,
+    #[serde(default)]
+    pub sniff_content_type: bool,
+    #[serde(default)]
+    pub resolve_redirects: bool,
     pub user_agent: Option<String>,
     #[serde(default = "default_max_redirects")]
     pub max_redirects: u32,
+    pub ipfs_gateways: Option<Vec<String>>,
+    #[serde(default)]
+    pub wayback_fallback: bool,
+    pub dns_cache_ttl_seconds: Option<u64>,
+    pub max_retry_after_seconds: Option<u64>,
+    pub max_response_bytes: Option<u64>,
+    pub retry_policy: Option<RetryStyle>,
+    pub retry_delay_ms: Option<u64>,
+    pub connection_idle_ttl_seconds: Option<u64>,
+    pub proxy: Option<String>,
+    pub no_proxy: Option<Vec<String>>,
+    pub proxy_from_env: Option<bool>,
+    pub root_certificate_files: Option<Vec<String>>,
+    #[serde(default)]
+    pub danger_accept_invalid_certs: bool,
+    pub client_certificate_file: Option<String>,
+    pub client_key_file: Option<String>,
+    #[serde(default)]
+    pub default_headers: BTreeMap<String, String>,
+    pub cache_entries: Option<usize>,
+    pub cache_ttl_seconds: Option<u64>,
+    pub cache_dir: Option<String>,
 }
 
 impl Default for HttpConfig {
     fn default() -> Self {
         Self {
             timeout_seconds: default_timeout(),
+            total_timeout_seconds: None,
+            race_strategies: false,
+            sniff_content_type: false,
+            resolve_redirects: false,
             user_agent: None,
             max_redirects: default_max_redirects(),
+            ipfs_gateways: None,
+            wayback_fallback: false,
+            dns_cache_ttl_seconds: None,
+            max_retry_after_seconds: None,
+            max_response_bytes: None,
+            retry_policy: None,
+            retry_delay_ms: None,
+            connection_idle_ttl_seconds: None,
+            proxy: None,
+            no_proxy: None,
+            proxy_from_env: None,
+            root_certificate_files: None,
+            danger_accept_invalid_certs: false,
+            client_certificate_file: None,
+            client_key_file: None,
+            default_headers: BTreeMap::new(),
+            cache_entries: None,
+            cache_ttl_seconds: None,
+            cache_dir: None,
         }
     }
 }
@@ -153,8 +425,12 @@ impl Default for HttpConfig {
 #[derive(Debug, Deserialize, Serialize, Default)]
 struct AuthConfig {
     pub github_token: Option<String>,
+    pub github_api_base_url: Option<String>,
+    pub azure_devops_token: Option<String>,
     pub office365_token: Option<String>,
+    pub dropbox_token: Option<String>,
     pub google_api_key: Option<String>,
+    pub google_service_account_key_file: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -163,6 +439,20 @@ struct OutputConfig {
     pub include_frontmatter: bool,
     #[serde(default = "default_format")]
     pub format: String,
+    pub max_comments: Option<usize>,
+    #[serde(default)]
+    pub resolve_includes: bool,
+    pub extract_language: Option<String>,
+    pub direction_markers: Option<DirectionStyle>,
+    pub download_images: Option<PathBuf>,
+    #[serde(default)]
+    pub strip_tracking_params: bool,
+    #[serde(default)]
+    pub tracking_params: Vec<String>,
+    pub frontmatter_format: Option<FrontmatterStyle>,
+    #[serde(default = "default_true")]
+    pub notebook_outputs: bool,
+    pub sub_documents: Option<SubDocumentStyle>,
 }
 
 impl Default for OutputConfig {
@@ -170,6 +460,16 @@ impl Default for OutputConfig {
         Self {
             include_frontmatter: default_true(),
             format: default_format(),
+            max_comments: None,
+            resolve_includes: false,
+            extract_language: None,
+            direction_markers: None,
+            download_images: None,
+            strip_tracking_params: false,
+            tracking_params: Vec::new(),
+            frontmatter_format: None,
+            notebook_outputs: default_true(),
+            sub_documents: None,
         }
     }
 }
@@ -194,6 +494,53 @@ impl Default for BatchConfig {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, Default)]
+struct FiltersConfig {
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+    #[serde(default)]
+    pub denied_hosts: Vec<String>,
+    #[serde(default)]
+    pub allowed_paths: Vec<String>,
+    #[serde(default)]
+    pub denied_paths: Vec<String>,
+    #[serde(default)]
+    pub allowed_mime_types: Vec<String>,
+    #[serde(default)]
+    pub denied_mime_types: Vec<String>,
+    pub max_content_length: Option<u64>,
+    pub modified_after: Option<String>,
+    pub modified_before: Option<String>,
+    #[serde(default)]
+    pub flag_out_of_window: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct RequestTemplateConfig {
+    pub host: String,
+    pub path_prefix: Option<String>,
+    #[serde(default)]
+    pub query: BTreeMap<String, String>,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct RateLimitConfig {
+    pub host: String,
+    pub requests_per_second: f64,
+    pub burst: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct DetectionRuleConfig {
+    pub host: Option<String>,
+    pub url_regex: Option<String>,
+    pub url_type: String,
+    #[serde(default)]
+    pub priority: i32,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 struct LoggingConfig {
     #[serde(default = "default_log_level")]
@@ -284,27 +631,56 @@ async fn run_cli(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
 
     // Build configuration from CLI arguments
     let config = build_config(&cli)?;
-    let markdowndown = MarkdownDown::with_config(config);
+    // Invalid proxy or TLS settings stop the run here, before any request
+    let markdowndown = MarkdownDown::try_with_config(config)?;
 
     match &cli.command {
         Some(Commands::Batch {
             file,
             concurrency,
+            adaptive,
             output_dir,
             stats,
+            filename_template,
+            compress,
+            staged,
+            error_files,
+            index,
+            graph,
+            search_json,
+            chunks,
+            chunk_tokens,
+            chunk_overlap,
+            cache_dir,
         }) => {
             batch_convert(
                 &markdowndown,
                 file,
                 *concurrency,
+                adaptive.then(|| AdaptiveConcurrency::new(1, *concurrency)),
                 output_dir.as_deref(),
                 *stats,
+                filename_template,
+                compress.unwrap_or_default(),
+                *staged,
+                *error_files,
+                *index,
+                *graph,
+                *search_json,
+                chunks.then(|| {
+                    ChunkOptions::new()
+                        .max_tokens(*chunk_tokens)
+                        .overlap_tokens(*chunk_overlap)
+                }),
+                cache_dir.as_deref(),
                 &cli,
             )
             .await
         }
         Some(Commands::Detect { url }) => detect_url_type(url),
         Some(Commands::ListTypes) => list_supported_types(&markdowndown),
+        Some(Commands::Plan { url, batch }) => plan_conversion(&markdowndown, url, *batch, &cli),
+        Some(Commands::Doctor) => run_doctor(&markdowndown, &cli).await,
         None => {
             // Handle single URL conversion or show help if no URL provided
             if let Some(ref url) = cli.url {
@@ -323,10 +699,17 @@ fn build_config(cli: &Cli) -> Result<Config, Box<dyn std::error::Error>> {
     // First, load configuration from file (if specified or from default locations)
     let file_config = load_config_file(cli.config.as_deref())?;
 
-    // Start with config from file as base
-    let mut builder = Config::builder()
-        .timeout_seconds(file_config.http.timeout_seconds)
-        .include_frontmatter(file_config.output.include_frontmatter);
+    // Start with the preset, if any, then the config file on top of it
+    let mut builder = Config::builder();
+    if let Some(preset) = cli.preset {
+        builder = builder.preset(preset);
+    }
+    if file_config.http.timeout_seconds != default_timeout() {
+        builder = builder.timeout_seconds(file_config.http.timeout_seconds);
+    }
+    if !file_config.output.include_frontmatter {
+        builder = builder.include_frontmatter(false);
+    }
 
     // Apply CLI overrides (CLI arguments take precedence over config file)
     if cli.timeout != 30 {
@@ -338,6 +721,22 @@ fn build_config(cli: &Cli) -> Result<Config, Box<dyn std::error::Error>> {
         builder = builder.include_frontmatter(false);
     }
 
+    if cli.frontmatter_only {
+        builder = builder.frontmatter_only(true);
+    }
+
+    if let Some(sentences) = cli.excerpt {
+        builder = builder.excerpt_sentences(sentences);
+    }
+
+    if let Some(max) = cli.tags {
+        builder = builder.max_tags(max);
+    }
+
+    if let Some(level) = cli.lint {
+        builder = builder.lint_mode(level.into());
+    }
+
     // Authentication tokens - CLI takes precedence, then config file
     if let Some(token) = &cli.github_token {
         builder = builder.github_token(token);
@@ -345,14 +744,157 @@ fn build_config(cli: &Cli) -> Result<Config, Box<dyn std::error::Error>> {
         builder = builder.github_token(token);
     }
 
+    if let Some(base_url) = &file_config.authentication.github_api_base_url {
+        builder = builder.github_api_base_url(base_url);
+    }
+
+    if let Some(token) = &file_config.authentication.azure_devops_token {
+        builder = builder.azure_devops_token(token);
+    }
+
     if let Some(token) = &file_config.authentication.office365_token {
         builder = builder.office365_token(token);
     }
 
+    if let Some(token) = &file_config.authentication.dropbox_token {
+        builder = builder.dropbox_token(token);
+    }
+
     if let Some(key) = &file_config.authentication.google_api_key {
         builder = builder.google_api_key(key);
     }
 
+    if let Some(path) = &file_config.authentication.google_service_account_key_file {
+        let key = std::fs::read_to_string(path).map_err(|e| {
+            format!("Failed to read Google service account key '{path}': {e}")
+        })?;
+        builder = builder.google_service_account_key(key);
+    }
+
+    if let Some(seconds) = file_config.http.total_timeout_seconds {
+        builder = builder.total_timeout(std::time::Duration::from_secs(seconds));
+    }
+    if file_config.http.race_strategies {
+        builder = builder.race_strategies(true);
+    }
+    if file_config.http.sniff_content_type {
+        builder = builder.sniff_content_type(true);
+    }
+    if file_config.http.resolve_redirects {
+        builder = builder.resolve_redirects(true);
+    }
+    if let Some(gateways) = &file_config.http.ipfs_gateways {
+        builder = builder.ipfs_gateways(gateways.iter().cloned());
+    }
+    if file_config.http.wayback_fallback {
+        builder = builder.wayback_fallback(true);
+    }
+    if let Some(seconds) = file_config.http.dns_cache_ttl_seconds {
+        let ttl = (seconds > 0).then(|| std::time::Duration::from_secs(seconds));
+        builder = builder.dns_cache_ttl(ttl);
+    }
+    if let Some(seconds) = file_config.http.max_retry_after_seconds {
+        builder = builder.max_retry_after(std::time::Duration::from_secs(seconds));
+    }
+    if let Some(max_bytes) = file_config.http.max_response_bytes {
+        builder = builder.max_response_bytes(Some(max_bytes));
+    }
+    let retry_delay = file_config
+        .http
+        .retry_delay_ms
+        .map(std::time::Duration::from_millis);
+    if let Some(delay) = retry_delay {
+        builder = builder.retry_delay(delay);
+    }
+    if let Some(style) = file_config.http.retry_policy {
+        let base = retry_delay.unwrap_or(std::time::Duration::from_secs(1));
+        builder = style.apply(builder, base);
+    }
+    if let Some(seconds) = file_config.http.connection_idle_ttl_seconds {
+        builder = builder.connection_idle_ttl(std::time::Duration::from_secs(seconds));
+    }
+    if let Some(proxy) = &file_config.http.proxy {
+        builder = builder.proxy(proxy);
+    }
+    if let Some(hosts) = &file_config.http.no_proxy {
+        builder = builder.no_proxy(hosts.iter().cloned());
+    }
+    if let Some(enabled) = file_config.http.proxy_from_env {
+        builder = builder.proxy_from_env(enabled);
+    }
+    for path in file_config.http.root_certificate_files.iter().flatten() {
+        let pem = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read root certificate '{path}': {e}"))?;
+        builder = builder.root_certificate(pem);
+    }
+    if file_config.http.danger_accept_invalid_certs {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+    for (name, value) in &file_config.http.default_headers {
+        builder = builder.default_header(name, value);
+    }
+    let http = &file_config.http;
+    if http.cache_entries.is_some() || http.cache_ttl_seconds.is_some() || http.cache_dir.is_some()
+    {
+        let mut cache = HttpCacheConfig::new();
+        if let Some(entries) = http.cache_entries {
+            cache = cache.max_entries(entries);
+        }
+        if let Some(seconds) = http.cache_ttl_seconds {
+            cache = cache.ttl(std::time::Duration::from_secs(seconds));
+        }
+        if let Some(dir) = &http.cache_dir {
+            cache = cache.disk_dir(dir);
+        }
+        builder = builder.http_cache(cache);
+    }
+    match (
+        &file_config.http.client_certificate_file,
+        &file_config.http.client_key_file,
+    ) {
+        (Some(certificate_path), Some(key_path)) => {
+            let certificate = std::fs::read_to_string(certificate_path).map_err(|e| {
+                format!("Failed to read client certificate '{certificate_path}': {e}")
+            })?;
+            let key = std::fs::read_to_string(key_path)
+                .map_err(|e| format!("Failed to read client key '{key_path}': {e}"))?;
+            builder = builder.client_certificate(certificate, key);
+        }
+        (None, None) => {}
+        _ => return Err("client_certificate_file and client_key_file must be set together".into()),
+    }
+    if let Some(max) = file_config.output.max_comments {
+        builder = builder.max_comments(max);
+    }
+    if file_config.output.resolve_includes {
+        builder = builder.resolve_includes(true);
+    }
+    if !file_config.output.notebook_outputs {
+        builder = builder.notebook_outputs(false);
+    }
+    if let Some(language) = &file_config.output.extract_language {
+        builder = builder.extract_language(language);
+    }
+    if let Some(style) = file_config.output.direction_markers {
+        builder = builder.direction_markers(style.into());
+    }
+    if let Some(dir) = &file_config.output.download_images {
+        builder = builder.download_images(ImageDownloads::Directory(dir.clone()));
+    }
+    if file_config.output.strip_tracking_params {
+        builder = builder.strip_tracking_params(true);
+    }
+    for param in &file_config.output.tracking_params {
+        builder = builder.tracking_param(param);
+    }
+    if let Some(style) = file_config.output.frontmatter_format {
+        builder = builder.frontmatter_format(style.into());
+    }
+    // CLI --sub-documents takes precedence over the config file
+    if let Some(style) = cli.sub_documents.or(file_config.output.sub_documents) {
+        builder = builder.sub_documents(style.into());
+    }
+
     // User agent - CLI takes precedence
     if let Some(ua) = &cli.user_agent {
         builder = builder.user_agent(ua);
@@ -360,7 +902,103 @@ fn build_config(cli: &Cli) -> Result<Config, Box<dyn std::error::Error>> {
         builder = builder.user_agent(ua);
     }
 
-    Ok(builder.build())
+    // Content filters come from the config file only
+    let filters = &file_config.filters;
+    for pattern in &filters.allowed_hosts {
+        builder = builder.allow_host(pattern);
+    }
+    for pattern in &filters.denied_hosts {
+        builder = builder.deny_host(pattern);
+    }
+    for pattern in &filters.allowed_paths {
+        builder = builder.allow_path(pattern);
+    }
+    for pattern in &filters.denied_paths {
+        builder = builder.deny_path(pattern);
+    }
+    for pattern in &filters.allowed_mime_types {
+        builder = builder.allow_mime_type(pattern);
+    }
+    for pattern in &filters.denied_mime_types {
+        builder = builder.deny_mime_type(pattern);
+    }
+    if let Some(bytes) = filters.max_content_length {
+        builder = builder.max_content_length(bytes);
+    }
+
+    // Date window - CLI --since takes precedence over the config file
+    if let Some(date) = cli.since.as_deref().or(filters.modified_after.as_deref()) {
+        builder = builder.modified_after(parse_date_option(date)?);
+    }
+    if let Some(date) = &filters.modified_before {
+        builder = builder.modified_before(parse_date_option(date)?);
+    }
+    if filters.flag_out_of_window {
+        builder = builder.date_window_action(DateWindowAction::Flag);
+    }
+
+    // Request templates come from the config file only
+    for entry in &file_config.request_templates {
+        let mut template = RequestTemplate::new(&entry.host);
+        if let Some(prefix) = &entry.path_prefix {
+            template = template.path_prefix(prefix);
+        }
+        for (key, value) in &entry.query {
+            template = template.query_param(key, value);
+        }
+        for (name, value) in &entry.headers {
+            template = template.header(name, value);
+        }
+        builder = builder.request_template(template);
+    }
+    for entry in &file_config.rate_limits {
+        let mut limit = RateLimit::new(&entry.host, entry.requests_per_second);
+        if let Some(burst) = entry.burst {
+            limit = limit.burst(burst);
+        }
+        builder = builder.rate_limit(limit);
+    }
+    for entry in &file_config.detection_rules {
+        let pattern = match (&entry.host, &entry.url_regex) {
+            (Some(host), None) => RulePattern::host(host),
+            (None, Some(regex)) => RulePattern::regex(regex)?,
+            _ => return Err("each detection rule needs exactly one of host and url_regex".into()),
+        };
+        let url_type = url_type_from_name(&entry.url_type);
+        builder = builder.detection_rule(pattern.priority(entry.priority), url_type);
+    }
+
+    // Report invalid path patterns before any URL is converted
+    let config = builder.build();
+    ContentFilter::new(&config.filters)?;
+    Ok(config)
+}
+
+/// Map a URL type name from the config file to a URL type
+///
+/// Names that are not built-in types become custom types.
+fn url_type_from_name(name: &str) -> UrlType {
+    match name {
+        "html" => UrlType::Html,
+        "pdf" => UrlType::Pdf,
+        "notebook" => UrlType::Notebook,
+        "markup" => UrlType::Markup,
+        "email" => UrlType::Email,
+        "markdown" => UrlType::Markdown,
+        "google_docs" => UrlType::GoogleDocs,
+        "github_issue" => UrlType::GitHubIssue,
+        "azure_devops" => UrlType::AzureDevOps,
+        "office365" => UrlType::Office365,
+        "dropbox" => UrlType::Dropbox,
+        name => UrlType::Custom(name.to_string()),
+    }
+}
+
+/// Parse a date given on the command line or in the config file
+fn parse_date_option(value: &str) -> Result<DateTime<Utc>, Box<dyn std::error::Error>> {
+    parse_date(value).ok_or_else(|| {
+        format!("Invalid date '{value}': expected YYYY-MM-DD or an RFC 3339 timestamp").into()
+    })
 }
 
 /// Load configuration from file
@@ -422,30 +1060,53 @@ async fn single_convert(
 ) -> Result<(), Box<dyn std::error::Error>> {
     info!("Converting single URL: {}", url);
 
+    if markdowndown.config().output.sub_documents == SubDocumentMode::Split {
+        let parts = markdowndown.convert_url_parts(url).await?;
+        for part in &parts {
+            let output = format_output(&part.markdown, cli)?;
+            let path = cli
+                .output
+                .as_deref()
+                .map(|path| part_path(path, part.index, parts.len()));
+            write_output(&output, path.as_deref()).await?;
+        }
+        return Ok(());
+    }
+
     let result = markdowndown.convert_url(url).await?;
 
     // Format output based on CLI options
     let output = format_output(&result, cli)?;
 
     // Write output to file or stdout
-    write_output(&output, cli.output.as_deref())?;
+    write_output(&output, cli.output.as_deref()).await?;
 
     Ok(())
 }
 
 /// Convert multiple URLs from a file
+#[allow(clippy::too_many_arguments)]
 async fn batch_convert(
     markdowndown: &MarkdownDown,
     file: &str,
     concurrency: usize,
+    adaptive: Option<AdaptiveConcurrency>,
     output_dir: Option<&str>,
     stats: bool,
+    filename_template: &str,
+    compression: Compression,
+    staged: bool,
+    error_files: bool,
+    index_grouping: Option<IndexGrouping>,
+    write_graph: bool,
+    search_json: bool,
+    chunk_options: Option<ChunkOptions>,
+    cache_dir: Option<&str>,
     cli: &Cli,
 ) -> Result<(), Box<dyn std::error::Error>> {
     use indicatif::{ProgressBar, ProgressStyle};
-    use std::path::Path;
     use std::sync::atomic::{AtomicUsize, Ordering};
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
     use tokio::fs;
     use tokio::io::{AsyncBufReadExt, BufReader};
     use tokio::sync::Semaphore;
@@ -472,10 +1133,24 @@ async fn batch_convert(
 
     println!("Found {} URLs to process", urls.len());
 
-    // Create output directory if specified
-    if let Some(dir) = output_dir {
+    // Staged batches write elsewhere until the whole batch has completed
+    let staging = output_dir
+        .filter(|_| staged)
+        .map(|dir| staging_dir(Path::new(dir)));
+    if let Some(staging) = &staging {
+        // A leftover staging directory holds the output of an interrupted run
+        if fs::metadata(staging).await.is_ok() {
+            fs::remove_dir_all(staging).await?;
+        }
+        fs::create_dir_all(staging).await?;
+    } else if let Some(dir) = output_dir {
+        // Create output directory if specified
         fs::create_dir_all(dir).await?;
     }
+    let write_dir = staging
+        .as_deref()
+        .map(|staging| staging.to_string_lossy().into_owned())
+        .or(output_dir.map(String::from));
 
     // Set up progress bar if not in quiet mode
     let pb = if !cli.quiet {
@@ -494,23 +1169,52 @@ async fn batch_convert(
     // Statistics tracking
     let success_count = Arc::new(AtomicUsize::new(0));
     let error_count = Arc::new(AtomicUsize::new(0));
+    let skipped_count = Arc::new(AtomicUsize::new(0));
+    let dead_link_count = Arc::new(AtomicUsize::new(0));
+    let index_entries = Arc::new(Mutex::new(Vec::new()));
+    let graph_builder = Arc::new(Mutex::new(GraphBuilder::new()));
+    let stale_error_files = Arc::new(Mutex::new(Vec::new()));
     let semaphore = Arc::new(Semaphore::new(concurrency));
 
-    // Get the configuration to create new instances in tasks
+    // Get the configuration to create new instances in tasks, sharing host statistics
     let config = markdowndown.config().clone();
+    let host_stats = markdowndown.host_stats().clone();
 
     // Process URLs concurrently
     let mut tasks = Vec::new();
 
     for (index, url) in urls.into_iter().enumerate() {
         let config = config.clone();
-        let output_dir = output_dir.map(String::from);
+        let host_stats = host_stats.clone();
+        let cache_dir = cache_dir.map(str::to_string);
+        let filename = render_filename(filename_template, index + 1, &url);
+        let stem = filename
+            .strip_suffix(".md")
+            .unwrap_or(&filename)
+            .to_string();
+        let error_filename = format!("{stem}.error.yaml");
+        let error_file = write_dir
+            .as_ref()
+            .filter(|_| error_files)
+            .map(|dir| Path::new(dir).join(&error_filename));
+        // Error files from earlier runs are removed with the batch's output
+        // published, once the document converts
+        let stale_error_file = output_dir
+            .filter(|_| error_files)
+            .map(|dir| Path::new(dir).join(&error_filename));
+        let output_dir = write_dir.clone();
         let cli_format = cli.format;
         let include_frontmatter = !cli.no_frontmatter;
         let pb = pb.clone();
         let success_count = success_count.clone();
         let error_count = error_count.clone();
+        let skipped_count = skipped_count.clone();
+        let dead_link_count = dead_link_count.clone();
+        let index_entries = index_entries.clone();
+        let graph_builder = graph_builder.clone();
+        let stale_error_files = stale_error_files.clone();
         let semaphore = semaphore.clone();
+        let adaptive = adaptive.clone();
 
         let task = tokio::spawn(async move {
             let _permit = match semaphore.acquire().await {
@@ -528,9 +1232,27 @@ async fn batch_convert(
                     return;
                 }
             };
+            // Adaptive batches also wait for a slot in the host's current limit
+            let host_permit = match &adaptive {
+                Some(adaptive) => Some(adaptive.acquire(&url).await),
+                None => None,
+            };
 
             // Create a new MarkdownDown instance for this task
-            let markdowndown = MarkdownDown::with_config(config);
+            let mut markdowndown = MarkdownDown::with_host_stats(config, host_stats);
+            if let Some(dir) = cache_dir {
+                markdowndown = markdowndown.with_cache(dir);
+            }
+            if let Some(pb) = pb.clone() {
+                // Show retries of slow or flaky hosts on the progress bar
+                markdowndown = markdowndown.with_progress_observer(Box::new(
+                    move |url: &str, event: &ProgressEvent| {
+                        if let ProgressEvent::Retrying { attempt, .. } = event {
+                            pb.set_message(format!("Retrying ({attempt}): {url}"));
+                        }
+                    },
+                ));
+            }
 
             if let Some(ref pb) = pb {
                 pb.set_message(format!("Converting: {url}"));
@@ -543,42 +1265,106 @@ async fn batch_convert(
                 convert_single_url(&markdowndown, &url, cli_format, include_frontmatter),
             )
             .await;
+            if let Some(permit) = host_permit {
+                match &conversion_result {
+                    Ok(Ok(_)) => permit.record_error(None),
+                    Ok(Err(e)) => permit.record_error(as_markdown_error(e.as_ref())),
+                    Err(_timeout) => permit.record_timeout(),
+                }
+            }
 
             match conversion_result {
-                Ok(Ok(content)) => {
+                Ok(Ok(parts)) => {
                     // Save to file if output directory specified
                     if let Some(ref dir) = output_dir {
-                        let filename = format!("{:03}.md", index + 1);
-                        let filepath = Path::new(dir).join(filename);
-                        if let Err(e) = fs::write(&filepath, &content).await {
-                            if let Some(ref pb) = pb {
-                                pb.println(format!(
-                                    "❌ Failed to write {}: {}",
-                                    filepath.display(),
-                                    e
-                                ));
+                        let mut written = true;
+                        for (part, content) in parts.iter().enumerate() {
+                            // Split documents get one set of files per part
+                            let stem = match parts.len() {
+                                1 => stem.clone(),
+                                _ => format!("{stem}-{}", part + 1),
+                            };
+                            let filename = match compression.extension() {
+                                Some(extension) => format!("{stem}.md.{extension}"),
+                                None => format!("{stem}.md"),
+                            };
+                            let filepath = Path::new(dir).join(&filename);
+                            if let Err(e) = compression::write_file_atomic(&filepath, content).await
+                            {
+                                if let Some(ref pb) = pb {
+                                    pb.println(format!(
+                                        "❌ Failed to write {}: {}",
+                                        filepath.display(),
+                                        e
+                                    ));
+                                }
+                                written = false;
+                                break;
                             }
-                            error_count.fetch_add(1, Ordering::Relaxed);
-                        } else {
                             if let Some(ref pb) = pb {
                                 pb.println(format!("✅ {} -> {}", url, filepath.display()));
                             }
+                            if index_grouping.is_some() {
+                                let entry = IndexEntry::from_markdown(&url, &filename, content);
+                                index_entries.lock().unwrap().push(entry);
+                            }
+                            if write_graph {
+                                let mut graph = graph_builder.lock().unwrap();
+                                graph.add_document(&url, &filename, content);
+                            }
+                            if search_json {
+                                let path = Path::new(dir).join(format!("{stem}.search.json"));
+                                let document = SearchDocument::from_markdown(&url, content);
+                                write_search_file(&path, &document, pb.as_ref()).await;
+                            }
+                            if let Some(ref options) = chunk_options {
+                                let path = Path::new(dir).join(format!("{stem}.chunks.jsonl"));
+                                let chunks = chunk_document(&url, content, options);
+                                write_chunks_file(&path, &chunks, pb.as_ref()).await;
+                            }
+                        }
+                        if written {
+                            if let Some(path) = stale_error_file {
+                                stale_error_files.lock().unwrap().push(path);
+                            }
                             success_count.fetch_add(1, Ordering::Relaxed);
+                        } else {
+                            error_count.fetch_add(1, Ordering::Relaxed);
                         }
                     } else {
                         // Output to stdout with separator
                         println!("=== {url} ===");
-                        println!("{content}");
-                        println!();
+                        for content in &parts {
+                            println!("{content}");
+                            println!();
+                        }
                         success_count.fetch_add(1, Ordering::Relaxed);
                     }
                 }
+                Ok(Err(e)) if as_markdown_error(e.as_ref()).is_some_and(|e| e.is_skip()) => {
+                    if let Some(ref pb) = pb {
+                        pb.println(format!("⏭️  {url}: {e}"));
+                    } else {
+                        eprintln!("⏭️  {url}: {e}");
+                    }
+                    skipped_count.fetch_add(1, Ordering::Relaxed);
+                }
                 Ok(Err(e)) => {
+                    if as_markdown_error(e.as_ref()).is_some_and(|e| e.is_dead_link()) {
+                        dead_link_count.fetch_add(1, Ordering::Relaxed);
+                    }
                     if let Some(ref pb) = pb {
                         pb.println(format!("❌ {url}: {e}"));
                     } else {
                         eprintln!("❌ {url}: {e}");
                     }
+                    if let Some(ref path) = error_file {
+                        let report = match as_markdown_error(e.as_ref()) {
+                            Some(e) => e.report(&url),
+                            None => failure_report(&url, "output", e.to_string(), false),
+                        };
+                        write_error_file(path, &report, pb.as_ref()).await;
+                    }
                     error_count.fetch_add(1, Ordering::Relaxed);
                 }
                 Err(_timeout) => {
@@ -589,6 +1375,10 @@ async fn batch_convert(
                     } else {
                         eprintln!("❌ {url}: {timeout_msg}");
                     }
+                    if let Some(ref path) = error_file {
+                        let report = failure_report(&url, "timeout", timeout_msg, true);
+                        write_error_file(path, &report, pb.as_ref()).await;
+                    }
                     error_count.fetch_add(1, Ordering::Relaxed);
                 }
             }
@@ -610,53 +1400,280 @@ async fn batch_convert(
         pb.finish_with_message("Batch conversion complete");
     }
 
+    if let (Some(grouping), Some(dir)) = (index_grouping, &write_dir) {
+        let entries = std::mem::take(&mut *index_entries.lock().unwrap());
+        let index_path = Path::new(dir).join("index.md");
+        compression::write_file_atomic(&index_path, &render_index(&entries, grouping)).await?;
+        info!("Wrote index to {}", index_path.display());
+    }
+
+    if let Some(dir) = write_dir.as_ref().filter(|_| write_graph) {
+        let graph = std::mem::take(&mut *graph_builder.lock().unwrap()).build();
+        let graph_path = Path::new(dir).join("graph.json");
+        compression::write_file_atomic(&graph_path, &serde_json::to_string_pretty(&graph)?).await?;
+        info!("Wrote link graph to {}", graph_path.display());
+    }
+
+    let stale_error_files = std::mem::take(&mut *stale_error_files.lock().unwrap());
+    if let (Some(staging), Some(dir)) = (&staging, output_dir) {
+        publish_staged(staging, Path::new(dir), &stale_error_files).await?;
+        info!("Moved staged output into {}", dir);
+    } else {
+        remove_stale_files(&stale_error_files).await?;
+    }
+
     // Print statistics if requested
     let successes = success_count.load(Ordering::Relaxed);
     let errors = error_count.load(Ordering::Relaxed);
+    let skipped = skipped_count.load(Ordering::Relaxed);
+    let dead_links = dead_link_count.load(Ordering::Relaxed);
 
     if stats || cli.verbose {
         println!();
         println!("Conversion Statistics:");
         println!("  Successful: {successes}");
-        println!("  Failed: {errors}");
-        println!("  Total: {}", successes + errors);
-        println!(
-            "  Success rate: {:.1}%",
-            (successes as f64 / (successes + errors) as f64) * 100.0
-        );
+        println!("  Failed: {errors} ({dead_links} dead links)");
+        println!("  Skipped: {skipped}");
+        println!("  Total: {}", successes + errors + skipped);
+        match success_rate(successes, errors) {
+            Some(rate) => println!("  Success rate: {rate:.1}%"),
+            None => println!("  Success rate: n/a (nothing converted)"),
+        }
+
+        let hosts = host_stats.worst_hosts();
+        if !hosts.is_empty() {
+            println!();
+            println!("Host Statistics (worst first):");
+            for line in format_host_stats(&hosts) {
+                println!("  {line}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Build the error record for a batch failure that is not a conversion error
+fn failure_report(url: &str, category: &str, message: String, retryable: bool) -> ErrorReport {
+    ErrorReport {
+        url: url.to_string(),
+        category: category.to_string(),
+        kind: None,
+        operation: None,
+        message,
+        retryable,
+        dead_link: false,
+        suggestions: Vec::new(),
+        failed_at: Utc::now(),
+    }
+}
+
+/// Write the error record for a failed document where its output would have gone
+async fn write_error_file(path: &Path, report: &ErrorReport, pb: Option<&indicatif::ProgressBar>) {
+    let written = match serde_yaml::to_string(report) {
+        Ok(yaml) => compression::write_file_atomic(path, &yaml)
+            .await
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    if let Err(e) = written {
+        let message = format!("❌ Failed to write {}: {}", path.display(), e);
+        match pb {
+            Some(pb) => pb.println(message),
+            None => eprintln!("{message}"),
+        }
+    }
+}
+
+/// Write the search-index document for a converted document next to its output
+async fn write_search_file(
+    path: &Path,
+    document: &SearchDocument,
+    pb: Option<&indicatif::ProgressBar>,
+) {
+    let written = match serde_json::to_string_pretty(document) {
+        Ok(json) => compression::write_file_atomic(path, &json)
+            .await
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    if let Err(e) = written {
+        let message = format!("❌ Failed to write {}: {}", path.display(), e);
+        match pb {
+            Some(pb) => pb.println(message),
+            None => eprintln!("{message}"),
+        }
+    }
+}
+
+/// Write the chunks of a converted document next to its output, one JSON object per line
+async fn write_chunks_file(path: &Path, chunks: &[Chunk], pb: Option<&indicatif::ProgressBar>) {
+    let lines: Result<Vec<String>, _> = chunks.iter().map(serde_json::to_string).collect();
+    let written = match lines {
+        Ok(lines) => compression::write_file_atomic(path, &(lines.join("\n") + "\n"))
+            .await
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    if let Err(e) = written {
+        let message = format!("❌ Failed to write {}: {}", path.display(), e);
+        match pb {
+            Some(pb) => pb.println(message),
+            None => eprintln!("{message}"),
+        }
+    }
+}
+
+/// Staging directory for a batch, next to the output directory so renames stay on one filesystem
+fn staging_dir(output_dir: &Path) -> PathBuf {
+    match output_dir.file_name() {
+        Some(name) => {
+            let mut staging = std::ffi::OsString::from(".");
+            staging.push(name);
+            staging.push(".staging");
+            output_dir.with_file_name(staging)
+        }
+        None => output_dir.join(".markdowndown-staging"),
     }
+}
+
+/// Move the files of a completed staged batch into the output directory,
+/// removing the stale files it replaces
+///
+/// A new output directory is swapped in whole with a single rename; otherwise
+/// the files are moved in one at a time, each with an atomic rename, and the
+/// stale files are removed only once every file has moved.
+async fn publish_staged(
+    staging: &Path,
+    output_dir: &Path,
+    stale: &[PathBuf],
+) -> std::io::Result<()> {
+    if tokio::fs::metadata(output_dir).await.is_err() {
+        return tokio::fs::rename(staging, output_dir).await;
+    }
+    let mut entries = tokio::fs::read_dir(staging).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        tokio::fs::rename(entry.path(), output_dir.join(entry.file_name())).await?;
+    }
+    tokio::fs::remove_dir(staging).await?;
+    remove_stale_files(stale).await
+}
 
+/// Remove files left by an earlier run, ignoring those already gone
+async fn remove_stale_files(paths: &[PathBuf]) -> std::io::Result<()> {
+    for path in paths {
+        match tokio::fs::remove_file(path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+    }
     Ok(())
 }
 
+/// Percentage of attempted conversions that succeeded, or `None` if every URL was skipped
+fn success_rate(successes: usize, errors: usize) -> Option<f64> {
+    let attempted = successes + errors;
+    (attempted > 0).then(|| successes as f64 / attempted as f64 * 100.0)
+}
+
+/// Format one summary line per host for the batch statistics report
+fn format_host_stats(hosts: &[HostStats]) -> Vec<String> {
+    hosts
+        .iter()
+        .map(|stats| {
+            format!(
+                "{}: {}/{} ok ({:.1}%), median {}ms, {} retries, {} throttled",
+                stats.host,
+                stats.successes,
+                stats.requests,
+                stats.success_rate() * 100.0,
+                stats.median_latency.as_millis(),
+                stats.retries,
+                stats.throttled
+            )
+        })
+        .collect()
+}
+
+/// Render a batch output filename from a template
+///
+/// Supports `{index}` (1-based, zero-padded to 3 digits), `{id}` (stable document ID),
+/// and `{slug}` (filesystem-safe slug). The `.md` extension is always appended.
+fn render_filename(template: &str, index: usize, url: &str) -> String {
+    let name = template
+        .replace("{index}", &format!("{index:03}"))
+        .replace("{id}", &identity::document_id(url))
+        .replace("{slug}", &identity::document_slug(url));
+    format!("{name}.md")
+}
+
+/// Name the output file for one part of a split document
+///
+/// The part index goes before the extension, so the second part of
+/// `out/guide.md.gz` is written to `out/guide-2.md.gz`. Documents with a
+/// single part keep the name as given.
+fn part_path(path: &str, index: usize, count: usize) -> String {
+    if count <= 1 {
+        return path.to_string();
+    }
+    let (path, compressed) = match Compression::from_path(path).extension() {
+        Some(extension) => path.split_at(path.len() - extension.len() - 1),
+        None => (path, ""),
+    };
+    let name_start = path.rfind(['/', '\\']).map_or(0, |i| i + 1);
+    let (stem, extension) = match path[name_start..].rfind('.') {
+        Some(dot) => path.split_at(name_start + dot),
+        None => (path, ""),
+    };
+    format!("{stem}-{index}{extension}{compressed}")
+}
+
+/// Returns the library error behind a boxed conversion error, if there is one
+fn as_markdown_error<'a>(
+    error: &'a (dyn std::error::Error + Send + Sync + 'static),
+) -> Option<&'a markdowndown::types::MarkdownError> {
+    error.downcast_ref::<markdowndown::types::MarkdownError>()
+}
+
 /// Helper function to convert a single URL with specified options
+///
+/// Returns one output per part when documents are split, and a single output otherwise.
 async fn convert_single_url(
     markdowndown: &MarkdownDown,
     url: &str,
     format: OutputFormat,
     _include_frontmatter: bool,
-) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-    let result = markdowndown
-        .convert_url(url)
-        .await
-        .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+    let documents = if markdowndown.config().output.sub_documents == SubDocumentMode::Split {
+        markdowndown
+            .convert_url_parts(url)
+            .await?
+            .into_iter()
+            .map(|part| part.markdown)
+            .collect()
+    } else {
+        vec![markdowndown.convert_url(url).await?]
+    };
 
     // For batch processing, we'll use a simpler format output
-    match format {
-        OutputFormat::Markdown => Ok(result.as_str().to_string()),
-        OutputFormat::Json => {
-            let json_output = serde_json::json!({
-                "url": url,
-                "content": result.as_str(),
-                "format": "markdown"
-            });
-            Ok(serde_json::to_string_pretty(&json_output)?)
-        }
-        OutputFormat::Yaml => {
-            let yaml_data = serde_yaml::Value::String(result.as_str().to_string());
-            Ok(serde_yaml::to_string(&yaml_data)?)
-        }
-    }
+    documents
+        .iter()
+        .map(|result| match format {
+            OutputFormat::Markdown => Ok(result.as_str().to_string()),
+            OutputFormat::Json => {
+                let json_output = serde_json::json!({
+                    "url": url,
+                    "content": result.as_str(),
+                    "format": "markdown"
+                });
+                Ok(serde_json::to_string_pretty(&json_output)?)
+            }
+            OutputFormat::Yaml => {
+                let yaml_data = serde_yaml::Value::String(result.as_str().to_string());
+                Ok(serde_yaml::to_string(&yaml_data)?)
+            }
+        })
+        .collect()
 }
 
 /// Detect and display URL type
@@ -666,6 +1683,65 @@ fn detect_url_type(url: &str) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Print the conversion plan for a URL, or a summary for a file of URLs
+fn plan_conversion(
+    markdowndown: &MarkdownDown,
+    target: &str,
+    batch: bool,
+    cli: &Cli,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if !batch {
+        let plan = markdowndown.plan(target)?;
+        match cli.format {
+            OutputFormat::Markdown => println!("{plan}"),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&plan)?),
+            OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&plan)?),
+        }
+        return Ok(());
+    }
+
+    let content = std::fs::read_to_string(target)?;
+    let urls: Vec<&str> = content
+        .lines()
+        .map(str::trim)
+        .filter(|url| !url.is_empty() && !url.starts_with('#'))
+        .collect();
+
+    let mut plans = Vec::new();
+    let mut failures = Vec::new();
+    for url in urls {
+        match markdowndown.plan(url) {
+            Ok(plan) => plans.push(plan),
+            Err(e) => failures.push((url, e)),
+        }
+    }
+
+    match cli.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&plans)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&plans)?),
+        OutputFormat::Markdown => {
+            let mut converters: BTreeMap<&str, usize> = BTreeMap::new();
+            for plan in plans.iter().filter(|plan| plan.skipped.is_none()) {
+                *converters.entry(plan.converter.as_str()).or_default() += 1;
+            }
+            let skipped = plans.iter().filter(|plan| plan.skipped.is_some()).count();
+            let requests: usize = plans.iter().map(|plan| plan.estimated_requests).sum();
+
+            println!("Planned {} URLs", plans.len() + failures.len());
+            for (converter, count) in &converters {
+                println!("  {converter}: {count}");
+            }
+            println!("Skipped by filters: {skipped}");
+            println!("Cannot be converted: {}", failures.len());
+            println!("Estimated requests: {requests}");
+        }
+    }
+    for (url, e) in &failures {
+        eprintln!("Cannot convert {url}: {e}");
+    }
+    Ok(())
+}
+
 /// List all supported URL types
 fn list_supported_types(markdowndown: &MarkdownDown) -> Result<(), Box<dyn std::error::Error>> {
     let types = markdowndown.supported_types();
@@ -676,6 +1752,23 @@ fn list_supported_types(markdowndown: &MarkdownDown) -> Result<(), Box<dyn std::
     Ok(())
 }
 
+/// Diagnose the environment, exiting with an error if any check failed
+async fn run_doctor(
+    markdowndown: &MarkdownDown,
+    cli: &Cli,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let report = markdowndown.doctor().await;
+    match cli.format {
+        OutputFormat::Markdown => print!("{report}"),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(&report)?),
+    }
+    if !report.is_healthy() {
+        process::exit(1);
+    }
+    Ok(())
+}
+
 /// Format output based CLI options
 fn format_output(
     markdown: &markdowndown::types::Markdown,
@@ -705,13 +1798,15 @@ fn format_output(
 }
 
 /// Write output to file or stdout
-fn write_output(
+///
+/// Files ending in `.gz` or `.zst` are compressed.
+async fn write_output(
     content: &str,
     output_file: Option<&str>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     match output_file {
         Some(file_path) => {
-            std::fs::write(file_path, content)?;
+            compression::write_file(file_path, content).await?;
             debug!("Output written to: {}", file_path);
         }
         None => {
@@ -826,6 +1921,27 @@ mod tests {
         matches!(cli.command, Some(Commands::ListTypes));
     }
 
+    #[test]
+    fn test_cli_parsing_plan_command() {
+        let args = vec!["markdowndown", "plan", "--batch", "urls.txt"];
+        let cli = Cli::try_parse_from(args).unwrap();
+
+        if let Some(Commands::Plan { url, batch }) = cli.command {
+            assert_eq!(url, "urls.txt");
+            assert!(batch);
+        } else {
+            panic!("Expected plan command");
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_doctor_command() {
+        let cli = Cli::try_parse_from(["markdowndown", "--format", "json", "doctor"]).unwrap();
+
+        assert!(matches!(cli.command, Some(Commands::Doctor)));
+        assert_eq!(cli.format, OutputFormat::Json);
+    }
+
     #[test]
     fn test_config_file_defaults() {
         let config = ConfigFile::default();
@@ -879,6 +1995,12 @@ mod tests {
             quiet: false,
             debug: false,
             user_agent: None,
+            since: None,
+            excerpt: None,
+            tags: None,
+            lint: None,
+            sub_documents: None,
+            preset: None,
             command: None,
         };
 
@@ -901,11 +2023,17 @@ mod tests {
             timeout: 60, // Override default
             format: OutputFormat::Markdown,
             no_frontmatter: true, // Override default
-            frontmatter_only: false,
+            frontmatter_only: true,
             verbose: false,
             quiet: false,
             debug: false,
             user_agent: Some("cli-agent".to_string()),
+            since: Some("2024-06-01".to_string()),
+            excerpt: Some(2),
+            tags: Some(5),
+            lint: Some(LintLevel::Fix),
+            sub_documents: Some(SubDocumentStyle::Split),
+            preset: None,
             command: None,
         };
 
@@ -914,8 +2042,36 @@ mod tests {
         // Should use CLI overrides
         assert_eq!(config.http.timeout.as_secs(), 60);
         assert!(!config.output.include_frontmatter); // Overridden by no_frontmatter
+        assert!(config.output.frontmatter_only);
+        assert_eq!(config.output.excerpt_sentences, Some(2));
+        assert_eq!(config.output.max_tags, Some(5));
+        assert_eq!(config.output.lint_mode, LintMode::Fix);
+        assert_eq!(config.output.sub_documents, SubDocumentMode::Split);
         assert_eq!(config.auth.github_token, Some("cli-token".to_string()));
         assert_eq!(config.http.user_agent, "cli-agent");
+        assert_eq!(config.filters.modified_after, parse_date("2024-06-01"));
+    }
+
+    #[test]
+    fn test_build_config_with_preset() {
+        let cli = Cli::try_parse_from([
+            "markdowndown",
+            "--preset",
+            "fast",
+            "--timeout",
+            "5",
+            "https://example.com",
+        ])
+        .expect("Should parse --preset");
+        assert_eq!(cli.preset, Some(Preset::Fast));
+
+        let config = build_config(&cli).expect("Should build config from preset");
+
+        // Preset settings apply, CLI options override them
+        assert_eq!(config.http.timeout.as_secs(), 5);
+        assert_eq!(config.http.max_retries, 1);
+        assert!(config.http.race_strategies);
+        assert!(Cli::try_parse_from(["markdowndown", "--preset", "slow"]).is_err());
     }
 
     #[test]
@@ -925,20 +2081,87 @@ mod tests {
         
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
         let config_path = temp_dir.path().join("test.toml");
-        
+        let key_path = temp_dir.path().join("service-account.json");
+        fs::write(&key_path, r#"{"type": "service_account"}"#).expect("Failed to write key file");
+        let ca_path = temp_dir.path().join("ca.pem");
+        fs::write(&ca_path, "ca-pem").expect("Failed to write certificate file");
+
         let config_content = r#"
 [http]
 timeout_seconds = 45
+total_timeout_seconds = 120
+race_strategies = true
+sniff_content_type = true
+resolve_redirects = true
+ipfs_gateways = ["http://127.0.0.1:8080"]
+wayback_fallback = true
+dns_cache_ttl_seconds = 0
+max_retry_after_seconds = 15
+max_response_bytes = 1048576
+retry_policy = "none"
+connection_idle_ttl_seconds = 300
+proxy = "socks5h://127.0.0.1:1080"
+no_proxy = ["localhost", ".internal.example.com"]
+proxy_from_env = false
+root_certificate_files = ["CA_PATH"]
+danger_accept_invalid_certs = true
+client_certificate_file = "CA_PATH"
+client_key_file = "KEY_PATH"
 user_agent = "file-agent"
+default_headers = { "X-Org-Token" = "intranet-token" }
+cache_ttl_seconds = 600
+cache_dir = "http-cache"
 
 [authentication]
 github_token = "file-token"
+github_api_base_url = "https://github.example.com/api/v3"
+azure_devops_token = "ado-token"
 office365_token = "office-token"
+dropbox_token = "dropbox-token"
 google_api_key = "google-key"
+google_service_account_key_file = "KEY_PATH"
 
 [output]
 include_frontmatter = false
-"#;
+max_comments = 250
+resolve_includes = true
+notebook_outputs = false
+extract_language = "fr"
+direction_markers = "html"
+download_images = "out/assets"
+tracking_params = ["mkt_*"]
+frontmatter_format = "toml"
+sub_documents = "split"
+
+[filters]
+denied_hosts = ["*.ads.example.com"]
+allowed_mime_types = ["text/*"]
+max_content_length = 1048576
+modified_after = "2024-01-01"
+flag_out_of_window = true
+
+[[request_templates]]
+host = "*.atlassian.net"
+path_prefix = "/wiki/rest/api/"
+query = { expand = "body.storage" }
+headers = { Accept = "application/json" }
+
+[[rate_limits]]
+host = "api.github.com"
+requests_per_second = 1.0
+burst = 5
+
+[[detection_rules]]
+host = "*.corp.example"
+url_type = "wiki"
+
+[[detection_rules]]
+url_regex = "/exports/.+\\.pdf$"
+url_type = "pdf"
+priority = 10
+"#
+        .replace("KEY_PATH", &key_path.to_string_lossy().replace('\\', "/"))
+        .replace("CA_PATH", &ca_path.to_string_lossy().replace('\\', "/"));
         
         fs::write(&config_path, config_content).expect("Failed to write config file");
         
@@ -955,18 +2178,144 @@ include_frontmatter = false
             quiet: false,
             debug: false,
             user_agent: None,
+            since: None,
+            excerpt: None,
+            tags: None,
+            lint: None,
+            sub_documents: None,
+            preset: None,
             command: None,
         };
 
-        let config = build_config(&cli).expect("Should build config from file");
+        let mut config = build_config(&cli).expect("Should build config from file");
         
         // Should use values from config file
         assert_eq!(config.http.timeout.as_secs(), 45);
+        assert_eq!(
+            config.http.total_timeout,
+            Some(std::time::Duration::from_secs(120))
+        );
+        assert!(config.http.race_strategies);
+        assert!(config.http.sniff_content_type);
+        assert!(config.http.resolve_redirects);
+        assert_eq!(config.http.ipfs_gateways, vec!["http://127.0.0.1:8080"]);
+        assert!(config.http.wayback_api_url.is_some());
+        assert_eq!(config.http.dns_cache_ttl, None);
+        assert_eq!(
+            config.http.max_retry_after,
+            std::time::Duration::from_secs(15)
+        );
+        assert_eq!(config.http.max_response_bytes, Some(1048576));
+        let policy = config.http.retry_policy.as_ref().expect("retry policy");
+        assert_eq!(policy.delay(0), None);
+        assert_eq!(
+            config.http.connection_idle_ttl,
+            std::time::Duration::from_secs(300)
+        );
+        assert_eq!(
+            config.http.proxy.as_deref(),
+            Some("socks5h://127.0.0.1:1080")
+        );
+        assert_eq!(
+            config.http.no_proxy,
+            vec!["localhost", ".internal.example.com"]
+        );
+        assert!(!config.http.proxy_from_env);
+        assert_eq!(config.http.root_certificates, vec!["ca-pem"]);
+        assert!(config.http.danger_accept_invalid_certs);
+        let cache = config.http.cache.as_ref().expect("cache should be enabled");
+        assert_eq!(cache.ttl, std::time::Duration::from_secs(600));
+        assert_eq!(cache.max_entries, 256);
+        assert_eq!(
+            cache.disk_dir.as_deref(),
+            Some(std::path::Path::new("http-cache"))
+        );
+        assert_eq!(
+            config.http.default_headers,
+            vec![("X-Org-Token".to_string(), "intranet-token".to_string())]
+        );
+        assert_eq!(
+            config
+                .http
+                .client_certificate
+                .as_ref()
+                .map(|c| c.certificate_pem.as_str()),
+            Some("ca-pem")
+        );
         assert!(!config.output.include_frontmatter);
+        assert_eq!(config.output.max_comments, Some(250));
+        assert!(config.output.resolve_includes);
+        assert!(!config.output.notebook_outputs);
+        assert_eq!(config.output.extract_language.as_deref(), Some("fr"));
+        assert_eq!(config.output.direction_markers, DirectionMarkers::Html);
+        assert_eq!(
+            config.output.download_images,
+            ImageDownloads::Directory(PathBuf::from("out/assets"))
+        );
+        assert!(config.output.strip_tracking_params);
+        assert_eq!(config.output.tracking_params, vec!["mkt_*"]);
+        assert_eq!(config.output.frontmatter_format, FrontmatterFormat::Toml);
+        assert_eq!(config.output.sub_documents, SubDocumentMode::Split);
         assert_eq!(config.auth.github_token, Some("file-token".to_string()));
+        assert_eq!(
+            config.auth.github_api_base_url.as_deref(),
+            Some("https://github.example.com/api/v3")
+        );
+        assert_eq!(config.auth.azure_devops_token.as_deref(), Some("ado-token"));
         assert_eq!(config.auth.office365_token, Some("office-token".to_string()));
+        assert_eq!(config.auth.dropbox_token.as_deref(), Some("dropbox-token"));
         assert_eq!(config.auth.google_api_key, Some("google-key".to_string()));
+        assert_eq!(
+            config.auth.google_service_account_key.as_deref(),
+            Some(r#"{"type": "service_account"}"#)
+        );
         assert_eq!(config.http.user_agent, "file-agent");
+        assert_eq!(config.filters.denied_hosts, vec!["*.ads.example.com"]);
+        assert_eq!(config.filters.allowed_mime_types, vec!["text/*"]);
+        assert_eq!(config.filters.max_content_length, Some(1048576));
+        assert_eq!(config.filters.modified_after, parse_date("2024-01-01"));
+        assert_eq!(config.filters.date_window_action, DateWindowAction::Flag);
+        assert_eq!(
+            config.http.request_templates,
+            vec![RequestTemplate::new("*.atlassian.net")
+                .path_prefix("/wiki/rest/api/")
+                .query_param("expand", "body.storage")
+                .header("Accept", "application/json")]
+        );
+        assert_eq!(
+            config.http.rate_limits,
+            vec![RateLimit::new("api.github.com", 1.0).burst(5)]
+        );
+        // The placeholder certificates are rejected once a client is built
+        assert!(MarkdownDown::try_with_config(config.clone()).is_err());
+        config.http.root_certificates.clear();
+        config.http.client_certificate = None;
+        let md = MarkdownDown::with_config(config);
+        assert_eq!(
+            md.detector().detect_type("https://wiki.corp.example/page").unwrap(),
+            UrlType::Custom("wiki".into())
+        );
+        assert_eq!(
+            md.detector().detect_type("https://wiki.corp.example/exports/q3.pdf").unwrap(),
+            UrlType::Pdf
+        );
+    }
+
+    #[test]
+    fn test_build_config_rejects_invalid_path_filter() {
+        let temp_dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+        let config_path = temp_dir.path().join("test.toml");
+        std::fs::write(&config_path, "[filters]\ndenied_paths = [\"(unclosed\"]\n")
+            .expect("Failed to write config file");
+
+        let cli = Cli::try_parse_from([
+            "markdowndown",
+            "--config",
+            &config_path.to_string_lossy(),
+            "https://example.com",
+        ])
+        .unwrap();
+        assert!(build_config(&cli).is_err());
     }
 
     #[test]
@@ -1013,6 +2362,12 @@ include_frontmatter = false
             quiet: false,
             debug: false,
             user_agent: None,
+            since: None,
+            excerpt: None,
+            tags: None,
+            lint: None,
+            sub_documents: None,
+            preset: None,
             command: None,
         };
 
@@ -1039,6 +2394,12 @@ include_frontmatter = false
             quiet: false,
             debug: false,
             user_agent: None,
+            since: None,
+            excerpt: None,
+            tags: None,
+            lint: None,
+            sub_documents: None,
+            preset: None,
             command: None,
         };
 
@@ -1068,6 +2429,12 @@ include_frontmatter = false
             quiet: false,
             debug: false,
             user_agent: None,
+            since: None,
+            excerpt: None,
+            tags: None,
+            lint: None,
+            sub_documents: None,
+            preset: None,
             command: None,
         };
 
@@ -1097,6 +2464,12 @@ include_frontmatter = false
             quiet: false,
             debug: false,
             user_agent: None,
+            since: None,
+            excerpt: None,
+            tags: None,
+            lint: None,
+            sub_documents: None,
+            preset: None,
             command: None,
         };
 
@@ -1107,8 +2480,8 @@ include_frontmatter = false
         assert!(output.contains("No frontmatter found"));
     }
 
-    #[test]
-    fn test_write_output_to_file() {
+    #[tokio::test]
+    async fn test_write_output_to_file() {
         use tempfile::TempDir;
         use std::fs;
         
@@ -1117,21 +2490,34 @@ include_frontmatter = false
         
         let content = "# Test Output\n\nThis is test content.";
         
-        write_output(content, Some(output_file.to_str().unwrap())).expect("Should write to file");
+        write_output(content, Some(output_file.to_str().unwrap()))
+            .await
+            .expect("Should write to file");
         
         // File should exist and contain expected content
         assert!(output_file.exists());
         let file_content = fs::read_to_string(&output_file).expect("Should read file");
         assert_eq!(file_content, content);
+
+        // A compressed extension compresses the file
+        let compressed_file = temp_dir.path().join("test-output.md.gz");
+        write_output(content, Some(compressed_file.to_str().unwrap()))
+            .await
+            .expect("Should write compressed file");
+        assert_ne!(fs::read(&compressed_file).unwrap(), content.as_bytes());
+        assert_eq!(
+            compression::read_file(&compressed_file).await.unwrap(),
+            content
+        );
     }
 
-    #[test]
-    fn test_write_output_to_stdout() {
+    #[tokio::test]
+    async fn test_write_output_to_stdout() {
         // Test writing to stdout (no file specified)
         let content = "Test stdout content";
         
         // This should not panic or return error
-        let result = write_output(content, None);
+        let result = write_output(content, None).await;
         assert!(result.is_ok());
     }
 
@@ -1218,25 +2604,150 @@ include_frontmatter = false
             "urls.txt",
             "--concurrency",
             "10",
+            "--adaptive",
             "--output-dir",
             "output",
             "--stats",
+            "--compress",
+            "zstd",
+            "--staged",
+            "--error-files",
+            "--index",
+            "path",
+            "--graph",
+            "--search-json",
+            "--chunks",
+            "--chunk-tokens",
+            "128",
+            "--cache-dir",
+            "cache",
         ];
         let cli = Cli::try_parse_from(args).unwrap();
 
         if let Some(Commands::Batch {
             file,
             concurrency,
+            adaptive,
             output_dir,
             stats,
+            filename_template,
+            compress,
+            staged,
+            error_files,
+            index,
+            graph,
+            search_json,
+            chunks,
+            chunk_tokens,
+            chunk_overlap,
+            cache_dir,
         }) = cli.command
         {
             assert_eq!(file, "urls.txt");
             assert_eq!(concurrency, 10);
+            assert!(adaptive);
             assert_eq!(output_dir, Some("output".to_string()));
             assert!(stats);
+            assert_eq!(filename_template, "{index}");
+            assert_eq!(compress, Some(Compression::Zstd));
+            assert!(staged);
+            assert!(error_files);
+            assert_eq!(index, Some(IndexGrouping::Path));
+            assert!(graph);
+            assert!(search_json);
+            assert!(chunks);
+            assert_eq!(chunk_tokens, 128);
+            assert_eq!(chunk_overlap, 32);
+            assert_eq!(cache_dir, Some("cache".to_string()));
         } else {
             panic!("Expected batch command");
         }
     }
+
+    #[tokio::test]
+    async fn test_publish_staged() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let output_dir = temp_dir.path().join("archive");
+        let staging = staging_dir(&output_dir);
+        assert_eq!(staging, temp_dir.path().join(".archive.staging"));
+
+        // A new output directory is swapped in whole
+        fs::create_dir_all(&staging).unwrap();
+        fs::write(staging.join("001.md"), "# One").unwrap();
+        publish_staged(&staging, &output_dir, &[]).await.unwrap();
+        assert!(!staging.exists());
+        assert_eq!(
+            fs::read_to_string(output_dir.join("001.md")).unwrap(),
+            "# One"
+        );
+
+        // An existing one keeps its other files, losing only the stale ones
+        let stale = output_dir.join("002.error.yaml");
+        fs::write(&stale, "error: timeout").unwrap();
+        fs::create_dir_all(&staging).unwrap();
+        fs::write(staging.join("002.md"), "# Two").unwrap();
+        let gone = output_dir.join("003.error.yaml");
+        publish_staged(&staging, &output_dir, &[stale.clone(), gone])
+            .await
+            .unwrap();
+        assert!(!staging.exists());
+        assert!(!stale.exists());
+        assert!(output_dir.join("001.md").exists());
+        assert_eq!(
+            fs::read_to_string(output_dir.join("002.md")).unwrap(),
+            "# Two"
+        );
+    }
+
+    #[test]
+    fn test_render_filename() {
+        let url = "https://example.com/blog/post";
+
+        assert_eq!(render_filename("{index}", 7, url), "007.md");
+        assert_eq!(
+            render_filename("{slug}", 1, url),
+            "example-com-blog-post.md"
+        );
+        assert_eq!(
+            render_filename("{index}-{id}", 12, url),
+            format!("012-{}.md", identity::document_id(url))
+        );
+    }
+
+    #[test]
+    fn test_part_path() {
+        assert_eq!(part_path("out/guide.md", 2, 3), "out/guide-2.md");
+        assert_eq!(part_path("out/guide.md.gz", 1, 3), "out/guide-1.md.gz");
+        assert_eq!(part_path("v1.2/guide", 3, 3), "v1.2/guide-3");
+        // Documents with a single part keep their name
+        assert_eq!(part_path("out/guide.md", 1, 1), "out/guide.md");
+    }
+
+    #[test]
+    fn test_success_rate() {
+        assert_eq!(success_rate(3, 1), Some(75.0));
+        assert_eq!(success_rate(0, 0), None);
+    }
+
+    #[test]
+    fn test_format_host_stats() {
+        let hosts = vec![HostStats {
+            host: "example.com".to_string(),
+            requests: 4,
+            successes: 3,
+            failures: 1,
+            retries: 2,
+            throttled: 1,
+            median_latency: std::time::Duration::from_millis(250),
+            max_latency: std::time::Duration::from_millis(900),
+        }];
+
+        assert_eq!(
+            format_host_stats(&hosts),
+            vec!["example.com: 3/4 ok (75.0%), median 250ms, 2 retries, 1 throttled"]
+        );
+    }
 }
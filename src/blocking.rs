@@ -0,0 +1,198 @@
+//! A synchronous API for callers without an async runtime.
+//!
+//! Enabled with the `blocking` feature. Like reqwest's blocking client, the
+//! types here wrap the async API and run it on a runtime of their own, so
+//! synchronous command line tools can convert URLs without setting up tokio.
+//!
+//! A [`MarkdownDown`] owns its runtime, so keep one for many conversions
+//! rather than creating one per call. Do not use this module from async code:
+//! blocking on a conversion inside a runtime panics. Call the async
+//! [`crate::MarkdownDown`] there instead.
+//!
+//! # Usage Examples
+//!
+//! ```rust,no_run
+//! use markdowndown::blocking;
+//!
+//! let markdown = blocking::convert_url("https://example.com/article.html")?;
+//! println!("{markdown}");
+//! # Ok::<(), markdowndown::types::MarkdownError>(())
+//! ```
+//!
+//! ```rust,no_run
+//! use markdowndown::blocking::MarkdownDown;
+//! use markdowndown::Config;
+//!
+//! let md = MarkdownDown::with_config(Config::builder().timeout_seconds(60).build());
+//! for url in ["https://example.com/one", "https://example.com/two"] {
+//!     let result = md.convert_url_detailed(url)?;
+//!     println!("{}: {} words", url, result.word_count);
+//! }
+//! # Ok::<(), markdowndown::types::MarkdownError>(())
+//! ```
+
+use crate::config::{Config, ConversionOptions};
+use crate::types::{ConversionResult, Markdown, MarkdownError};
+use tokio::runtime::{Builder, Runtime};
+
+/// A synchronous wrapper around [`crate::MarkdownDown`].
+///
+/// Each method blocks the calling thread until the conversion finishes.
+pub struct MarkdownDown {
+    inner: crate::MarkdownDown,
+    runtime: Runtime,
+}
+
+impl MarkdownDown {
+    /// Creates a new instance with default configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime cannot be created.
+    pub fn new() -> Self {
+        Self::from(crate::MarkdownDown::new())
+    }
+
+    /// Creates a new instance with custom configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration to use
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime cannot be created.
+    pub fn with_config(config: Config) -> Self {
+        Self::from(crate::MarkdownDown::with_config(config))
+    }
+
+    /// Converts content from a URL to markdown.
+    ///
+    /// See [`crate::MarkdownDown::convert_url`].
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to fetch and convert
+    pub fn convert_url(&self, url: &str) -> Result<Markdown, MarkdownError> {
+        self.runtime.block_on(self.inner.convert_url(url))
+    }
+
+    /// Converts content from a URL to markdown, overriding settings for this call.
+    ///
+    /// See [`crate::MarkdownDown::convert_url_with_options`].
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to fetch and convert
+    /// * `options` - The settings to override
+    pub fn convert_url_with_options(
+        &self,
+        url: &str,
+        options: &ConversionOptions,
+    ) -> Result<Markdown, MarkdownError> {
+        self.runtime
+            .block_on(self.inner.convert_url_with_options(url, options))
+    }
+
+    /// Converts content from a URL and returns it with document metadata.
+    ///
+    /// See [`crate::MarkdownDown::convert_url_detailed`].
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to fetch and convert
+    pub fn convert_url_detailed(&self, url: &str) -> Result<ConversionResult, MarkdownError> {
+        self.runtime.block_on(self.inner.convert_url_detailed(url))
+    }
+
+    /// Converts HTML the caller already has, without fetching anything.
+    ///
+    /// See [`crate::MarkdownDown::convert_html`].
+    ///
+    /// # Arguments
+    ///
+    /// * `html` - The HTML document
+    /// * `base_url` - The URL the document was fetched from, if known
+    pub fn convert_html(
+        &self,
+        html: &str,
+        base_url: Option<&str>,
+    ) -> Result<Markdown, MarkdownError> {
+        self.inner.convert_html(html, base_url)
+    }
+
+    /// Returns the configuration being used.
+    pub fn config(&self) -> &Config {
+        self.inner.config()
+    }
+}
+
+impl Default for MarkdownDown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wraps an async instance, keeping its converters, link rewriters, and observers.
+///
+/// # Panics
+///
+/// Panics if the runtime cannot be created.
+impl From<crate::MarkdownDown> for MarkdownDown {
+    fn from(inner: crate::MarkdownDown) -> Self {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to create the markdowndown blocking runtime");
+        Self { inner, runtime }
+    }
+}
+
+/// Converts a URL to markdown with default configuration, blocking until done.
+///
+/// A convenience for one-off conversions; it creates a runtime for the call.
+/// Use a [`MarkdownDown`] to convert several URLs.
+///
+/// # Arguments
+///
+/// * `url` - The URL to fetch and convert
+///
+/// # Panics
+///
+/// Panics if called from async code, or if the runtime cannot be created.
+pub fn convert_url(url: &str) -> Result<Markdown, MarkdownError> {
+    MarkdownDown::new().convert_url(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_blocking_convert_url() {
+        // The mock server runs on a runtime of its own, as a remote host would
+        let server_runtime = Runtime::new().unwrap();
+        let mock_server = server_runtime.block_on(async {
+            let mock_server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/page"))
+                .respond_with(ResponseTemplate::new(200).set_body_string("<h1>Blocking</h1>"))
+                .mount(&mock_server)
+                .await;
+            mock_server
+        });
+        let url = format!("{}/page", mock_server.uri());
+
+        let md = MarkdownDown::new();
+        let markdown = md.convert_url(&url).unwrap();
+        assert!(markdown.content_only().contains("# Blocking"));
+
+        let result = md.convert_url_detailed(&url).unwrap();
+        assert_eq!(result.http_status, Some(200));
+
+        let markdown = convert_url(&url).unwrap();
+        assert!(markdown.frontmatter().is_some());
+    }
+}
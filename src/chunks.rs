@@ -0,0 +1,230 @@
+//! Overlapping text chunks of converted documents for embedding.
+//!
+//! Vector databases store embeddings of short passages, not whole documents.
+//! [`chunk_document`] splits a converted document into plain-text chunks of
+//! at most [`ChunkOptions::max_tokens`] tokens, with consecutive chunks
+//! sharing [`ChunkOptions::overlap_tokens`] tokens so that no passage loses
+//! its context at a boundary. Chunks never span two sections, so each one
+//! links back to the heading it came from.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::chunks::{chunk_document, ChunkOptions};
+//!
+//! let markdown = "## Install\n\none two three four five six\n";
+//! let options = ChunkOptions::new().max_tokens(4).overlap_tokens(1);
+//! let chunks = chunk_document("https://example.com/guide", markdown, &options);
+//!
+//! assert_eq!(chunks.len(), 2);
+//! assert_eq!(chunks[0].text, "Install\n\none two three");
+//! assert_eq!(chunks[1].text, "three four five six");
+//! assert_eq!(chunks[1].source, "https://example.com/guide#install");
+//! assert_eq!(chunks[1].token_count, 4);
+//! ```
+
+use crate::identity::document_id;
+use crate::search::plain_text;
+use crate::sections::extract_sections;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// How documents are split into chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkOptions {
+    /// Maximum number of tokens in a chunk
+    pub max_tokens: usize,
+    /// Number of tokens a chunk repeats from the end of the previous one
+    pub overlap_tokens: usize,
+}
+
+impl ChunkOptions {
+    /// Creates options for chunks of up to 256 tokens, overlapping by 32.
+    pub fn new() -> Self {
+        Self {
+            max_tokens: 256,
+            overlap_tokens: 32,
+        }
+    }
+
+    /// Sets the maximum number of tokens in a chunk.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - Maximum tokens per chunk, at least 1
+    pub fn max_tokens(mut self, max: usize) -> Self {
+        self.max_tokens = max.max(1);
+        self
+    }
+
+    /// Sets how many tokens consecutive chunks of a section share.
+    ///
+    /// An overlap of `max_tokens` or more is reduced to one less than
+    /// `max_tokens`, so every chunk moves forward.
+    ///
+    /// # Arguments
+    ///
+    /// * `overlap` - Tokens repeated from the previous chunk; zero disables overlap
+    pub fn overlap_tokens(mut self, overlap: usize) -> Self {
+        self.overlap_tokens = overlap;
+        self
+    }
+}
+
+impl Default for ChunkOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A passage of a converted document, ready for embedding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Chunk {
+    /// Stable chunk ID: the document ID and the chunk's position in the document
+    pub id: String,
+    /// The stable document ID, as stamped into the frontmatter
+    pub document_id: String,
+    /// The URL the document was converted from
+    pub url: String,
+    /// Position of the chunk in the document, from 0
+    pub index: usize,
+    /// The document URL with the anchor of the chunk's section, if any
+    pub source: String,
+    /// Texts of the chunk's section heading and the headings enclosing it, outermost first
+    pub headings: Vec<String>,
+    /// The chunk as plain text
+    pub text: String,
+    /// Number of tokens in the chunk
+    pub token_count: usize,
+}
+
+/// Splits a converted document into overlapping plain-text chunks.
+///
+/// Each section, from its heading up to the next heading, is converted to
+/// plain text and split separately; text before the first heading forms a
+/// section of its own. Tokens are runs of non-whitespace characters, a
+/// close estimate of model tokens for sizing embedding inputs. Chunk IDs
+/// depend only on the document URL and the chunk position, so reconverting
+/// an unchanged document replaces the same chunks.
+///
+/// # Arguments
+///
+/// * `url` - The URL the document was converted from
+/// * `markdown` - The converted output, with or without frontmatter
+/// * `options` - Chunk size and overlap
+pub fn chunk_document(url: &str, markdown: &str, options: &ChunkOptions) -> Vec<Chunk> {
+    let token = Regex::new(r"\S+").expect("valid token pattern");
+    let doc_id = document_id(url);
+    let max_tokens = options.max_tokens.max(1);
+    let step = max_tokens - options.overlap_tokens.min(max_tokens - 1);
+
+    let sections = extract_sections(markdown);
+    // Each span is the section's own text, up to the next heading
+    let mut spans: Vec<(Option<usize>, usize, usize)> = Vec::new();
+    let first_heading = sections.first().map_or(markdown.len(), |s| s.start);
+    spans.push((None, 0, first_heading));
+    for (index, section) in sections.iter().enumerate() {
+        let end = sections
+            .get(index + 1)
+            .map_or(markdown.len(), |next| next.start);
+        spans.push((Some(index), section.start, end));
+    }
+
+    let mut chunks = Vec::new();
+    for (section, start, end) in spans {
+        let text = plain_text(&markdown[start..end]);
+        let tokens: Vec<(usize, usize)> = token
+            .find_iter(&text)
+            .map(|m| (m.start(), m.end()))
+            .collect();
+        if tokens.is_empty() {
+            continue;
+        }
+
+        let source = match section {
+            Some(index) => format!("{url}#{}", sections[index].anchor),
+            None => url.to_string(),
+        };
+        let mut headings = Vec::new();
+        let mut enclosing = section;
+        while let Some(index) = enclosing {
+            headings.insert(0, sections[index].text.clone());
+            enclosing = sections[index].parent;
+        }
+
+        let mut first = 0;
+        loop {
+            let last = (first + max_tokens).min(tokens.len());
+            let index = chunks.len();
+            chunks.push(Chunk {
+                id: format!("{doc_id}-{index:04}"),
+                document_id: doc_id.clone(),
+                url: url.to_string(),
+                index,
+                source: source.clone(),
+                headings: headings.clone(),
+                text: text[tokens[first].0..tokens[last - 1].1].to_string(),
+                token_count: last - first,
+            });
+            if last == tokens.len() {
+                break;
+            }
+            first += step;
+        }
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunks_follow_sections_with_stable_ids() {
+        let markdown = "---\ntitle: Guide\n---\n\nIntro words.\n\n# Guide\n\n## Install\n\n\
+                        Run the installer.\n\n## Usage\n\nCall **convert**.\n";
+        let url = "https://example.com/guide";
+        let chunks = chunk_document(url, markdown, &ChunkOptions::new());
+
+        let summary: Vec<(&str, &str)> = chunks
+            .iter()
+            .map(|chunk| (chunk.source.as_str(), chunk.text.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (url, "Intro words."),
+                ("https://example.com/guide#guide", "Guide"),
+                (
+                    "https://example.com/guide#install",
+                    "Install\n\nRun the installer."
+                ),
+                ("https://example.com/guide#usage", "Usage\n\nCall convert."),
+            ]
+        );
+        assert_eq!(chunks[2].headings, vec!["Guide", "Install"]);
+        assert_eq!(chunks[3].id, format!("{}-0003", document_id(url)));
+        assert_eq!(
+            chunks,
+            chunk_document(url, markdown, &ChunkOptions::new()),
+            "chunking is deterministic"
+        );
+    }
+
+    #[test]
+    fn test_chunk_overlap_is_clamped() {
+        let markdown = "a b c d e";
+        let options = ChunkOptions::new().max_tokens(2).overlap_tokens(5);
+        let texts: Vec<String> = chunk_document("https://example.com/", markdown, &options)
+            .into_iter()
+            .map(|chunk| chunk.text)
+            .collect();
+        assert_eq!(texts, vec!["a b", "b c", "c d", "d e"]);
+
+        let options = ChunkOptions::new().max_tokens(2).overlap_tokens(0);
+        assert_eq!(
+            chunk_document("https://example.com/", markdown, &options).len(),
+            3
+        );
+    }
+}
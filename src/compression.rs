@@ -0,0 +1,343 @@
+//! Optional compression of stored markdown output.
+//!
+//! Converted documents compress well, so archives can store them as
+//! `.md.gz` or `.md.zst`. Writers compress on the fly, and readers detect a
+//! compressed file from its first bytes, so stored output can be read back
+//! without knowing how it was written.
+
+use async_compression::tokio::bufread::{GzipDecoder, ZstdDecoder};
+use async_compression::tokio::write::{GzipEncoder, ZstdEncoder};
+use std::path::Path;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+
+/// Magic bytes at the start of a gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Magic bytes at the start of a zstd frame.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Compression applied to written output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Compression {
+    /// Write plain text
+    #[default]
+    None,
+    /// Compress with gzip
+    Gzip,
+    /// Compress with zstd
+    Zstd,
+}
+
+impl Compression {
+    /// Returns the file extension added for this compression, without the dot.
+    pub fn extension(&self) -> Option<&'static str> {
+        match self {
+            Compression::None => None,
+            Compression::Gzip => Some("gz"),
+            Compression::Zstd => Some("zst"),
+        }
+    }
+
+    /// Chooses the compression for a file from its extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The output file path, such as `article.md.zst`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::compression::Compression;
+    ///
+    /// assert_eq!(Compression::from_path("out/article.md.zst"), Compression::Zstd);
+    /// assert_eq!(Compression::from_path("out/article.md"), Compression::None);
+    /// ```
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+            Some("gz") => Compression::Gzip,
+            Some("zst") => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+
+    /// Detects the compression of stored content from its first bytes.
+    fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(&GZIP_MAGIC) {
+            Compression::Gzip
+        } else if bytes.starts_with(&ZSTD_MAGIC) {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+}
+
+impl FromStr for Compression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Ok(Compression::None),
+            "gzip" | "gz" => Ok(Compression::Gzip),
+            "zstd" | "zst" => Ok(Compression::Zstd),
+            other => Err(format!(
+                "unknown compression '{other}' (expected none, gzip, or zstd)"
+            )),
+        }
+    }
+}
+
+/// A writer that compresses what is written to it.
+///
+/// Call [`CompressedWriter::finish`] once everything is written; a compressed
+/// stream is incomplete until then.
+pub enum CompressedWriter<W> {
+    /// Passes output through unchanged
+    Plain(W),
+    /// Compresses output with gzip
+    Gzip(GzipEncoder<W>),
+    /// Compresses output with zstd
+    Zstd(ZstdEncoder<W>),
+}
+
+impl<W: AsyncWrite + Unpin> CompressedWriter<W> {
+    /// Wraps `writer` so output is compressed with `compression`.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where the compressed output goes
+    /// * `compression` - How to compress it
+    pub fn new(writer: W, compression: Compression) -> Self {
+        match compression {
+            Compression::None => CompressedWriter::Plain(writer),
+            Compression::Gzip => CompressedWriter::Gzip(GzipEncoder::new(writer)),
+            Compression::Zstd => CompressedWriter::Zstd(ZstdEncoder::new(writer)),
+        }
+    }
+
+    /// Finishes the output.
+    ///
+    /// Compressed streams are finished by shutting the inner writer down;
+    /// plain output is only flushed, so the writer stays usable.
+    pub async fn finish(&mut self) -> std::io::Result<()> {
+        match self {
+            CompressedWriter::Plain(writer) => writer.flush().await,
+            CompressedWriter::Gzip(encoder) => encoder.shutdown().await,
+            CompressedWriter::Zstd(encoder) => encoder.shutdown().await,
+        }
+    }
+
+    /// Returns the inner writer.
+    ///
+    /// Call [`CompressedWriter::finish`] first, or compressed output is incomplete.
+    pub fn into_inner(self) -> W {
+        match self {
+            CompressedWriter::Plain(writer) => writer,
+            CompressedWriter::Gzip(encoder) => encoder.into_inner(),
+            CompressedWriter::Zstd(encoder) => encoder.into_inner(),
+        }
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for CompressedWriter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            CompressedWriter::Plain(writer) => Pin::new(writer).poll_write(cx, buf),
+            CompressedWriter::Gzip(encoder) => Pin::new(encoder).poll_write(cx, buf),
+            CompressedWriter::Zstd(encoder) => Pin::new(encoder).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            CompressedWriter::Plain(writer) => Pin::new(writer).poll_flush(cx),
+            CompressedWriter::Gzip(encoder) => Pin::new(encoder).poll_flush(cx),
+            CompressedWriter::Zstd(encoder) => Pin::new(encoder).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            CompressedWriter::Plain(writer) => Pin::new(writer).poll_shutdown(cx),
+            CompressedWriter::Gzip(encoder) => Pin::new(encoder).poll_shutdown(cx),
+            CompressedWriter::Zstd(encoder) => Pin::new(encoder).poll_shutdown(cx),
+        }
+    }
+}
+
+/// Writes `content` to a file, compressing it according to the file extension.
+///
+/// # Arguments
+///
+/// * `path` - The output file; `.gz` and `.zst` extensions select compression
+/// * `content` - The output to write
+pub async fn write_file(path: impl AsRef<Path>, content: &str) -> std::io::Result<()> {
+    let path = path.as_ref();
+    let file = tokio::fs::File::create(path).await?;
+    let mut writer = CompressedWriter::new(file, Compression::from_path(path));
+    writer.write_all(content.as_bytes()).await?;
+    writer.finish().await
+}
+
+/// Writes `content` to a file atomically, compressing it according to the file extension.
+///
+/// The output is written to a hidden `.partial` file next to `path`, synced
+/// to disk, and renamed over `path`, so a crash leaves either the previous
+/// file or the complete new one, never a truncated file.
+///
+/// # Arguments
+///
+/// * `path` - The output file; `.gz` and `.zst` extensions select compression
+/// * `content` - The output to write
+pub async fn write_file_atomic(path: impl AsRef<Path>, content: &str) -> std::io::Result<()> {
+    let path = path.as_ref();
+    let file_name = path.file_name().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "output path has no file name",
+        )
+    })?;
+    let mut partial_name = std::ffi::OsString::from(".");
+    partial_name.push(file_name);
+    partial_name.push(".partial");
+    let partial = path.with_file_name(partial_name);
+
+    let written = async {
+        let file = tokio::fs::File::create(&partial).await?;
+        let mut writer = CompressedWriter::new(file, Compression::from_path(path));
+        writer.write_all(content.as_bytes()).await?;
+        writer.finish().await?;
+        writer.into_inner().sync_all().await?;
+        tokio::fs::rename(&partial, path).await
+    }
+    .await;
+    if written.is_err() {
+        let _ = tokio::fs::remove_file(&partial).await;
+    }
+    written?;
+    sync_parent(path).await
+}
+
+/// Syncs the directory containing `path`, so a rename into it survives a crash.
+#[cfg(unix)]
+async fn sync_parent(path: &Path) -> std::io::Result<()> {
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    tokio::fs::File::open(parent).await?.sync_all().await
+}
+
+/// Directories cannot be opened for syncing on this platform.
+#[cfg(not(unix))]
+async fn sync_parent(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Reads stored output, decompressing it if it is gzip or zstd compressed.
+///
+/// # Arguments
+///
+/// * `reader` - The stored output
+pub async fn read_to_string<R>(reader: R) -> std::io::Result<String>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut reader = BufReader::new(reader);
+    let head = tokio::io::AsyncBufReadExt::fill_buf(&mut reader).await?;
+
+    let mut content = String::new();
+    match Compression::detect(head) {
+        Compression::None => reader.read_to_string(&mut content).await?,
+        Compression::Gzip => {
+            let mut decoder = GzipDecoder::new(reader);
+            decoder.multiple_members(true);
+            decoder.read_to_string(&mut content).await?
+        }
+        Compression::Zstd => {
+            let mut decoder = ZstdDecoder::new(reader);
+            decoder.multiple_members(true);
+            decoder.read_to_string(&mut content).await?
+        }
+    };
+    Ok(content)
+}
+
+/// Reads a stored output file, decompressing it if needed.
+///
+/// # Arguments
+///
+/// * `path` - The file to read
+pub async fn read_file(path: impl AsRef<Path>) -> std::io::Result<String> {
+    read_to_string(tokio::fs::File::open(path).await?).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_round_trip_each_compression() {
+        let content = "# Title\n\nSome text. ".repeat(200);
+
+        for compression in [Compression::None, Compression::Gzip, Compression::Zstd] {
+            let mut writer = CompressedWriter::new(Vec::new(), compression);
+            writer.write_all(content.as_bytes()).await.unwrap();
+            writer.finish().await.unwrap();
+            let stored = match writer {
+                CompressedWriter::Plain(stored) => stored,
+                CompressedWriter::Gzip(encoder) => encoder.into_inner(),
+                CompressedWriter::Zstd(encoder) => encoder.into_inner(),
+            };
+            if compression != Compression::None {
+                assert!(stored.len() < content.len() / 4);
+            }
+
+            let read = read_to_string(stored.as_slice()).await.unwrap();
+            assert_eq!(read, content);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_file_compresses_by_extension() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("article.md.zst");
+
+        write_file(&path, "# Stored\n").await.unwrap();
+
+        let raw = std::fs::read(&path).unwrap();
+        assert!(raw.starts_with(&ZSTD_MAGIC));
+        assert_eq!(read_file(&path).await.unwrap(), "# Stored\n");
+    }
+
+    #[tokio::test]
+    async fn test_write_file_atomic_replaces_whole_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("article.md.gz");
+        std::fs::write(&path, "old").unwrap();
+
+        write_file_atomic(&path, "# Replaced\n").await.unwrap();
+
+        assert_eq!(read_file(&path).await.unwrap(), "# Replaced\n");
+        let names: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(names, ["article.md.gz"]);
+    }
+
+    #[test]
+    fn test_parse_compression() {
+        assert_eq!("gzip".parse(), Ok(Compression::Gzip));
+        assert_eq!("ZST".parse(), Ok(Compression::Zstd));
+        assert!("brotli".parse::<Compression>().is_err());
+    }
+}
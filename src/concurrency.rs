@@ -0,0 +1,393 @@
+//! Adaptive per-host concurrency for batch conversions.
+//!
+//! A fixed concurrency is a compromise: too low for hosts that answer
+//! quickly, too high for hosts that throttle. [`AdaptiveConcurrency`] keeps a
+//! concurrency limit for each host and adjusts it the way TCP adjusts its
+//! congestion window (additive increase, multiplicative decrease): every
+//! healthy conversion raises the host's limit a little, and a conversion that
+//! was throttled, timed out, failed with a server error, or took much longer
+//! than the host's usual latency halves it. Limits stay within the bounds the
+//! caller sets.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::concurrency::AdaptiveConcurrency;
+//!
+//! # async fn example() {
+//! let controller = AdaptiveConcurrency::new(1, 8);
+//! let url = "https://example.com/docs/install";
+//!
+//! let permit = controller.acquire(url).await;
+//! let result: Result<(), markdowndown::types::MarkdownError> = Ok(());
+//! permit.record(&result);
+//!
+//! assert_eq!(controller.limit("example.com"), 2);
+//! # }
+//! ```
+
+use crate::types::{MarkdownError, NetworkErrorKind};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+use tracing::debug;
+
+/// Default ratio of smoothed to best latency above which a host counts as overloaded.
+const DEFAULT_LATENCY_TOLERANCE: f64 = 4.0;
+
+/// Weight of the newest latency sample in a host's smoothed latency.
+const LATENCY_SMOOTHING: f64 = 0.2;
+
+/// Concurrency state of one host.
+#[derive(Debug)]
+struct HostLimit {
+    /// Current limit; fractional so increases can be smaller than one
+    limit: f64,
+    /// Conversions currently holding a permit
+    in_flight: usize,
+    /// Lowest latency seen, taken as the host's unloaded latency
+    best_latency: Option<Duration>,
+    /// Exponentially smoothed latency
+    smoothed_latency: Option<Duration>,
+    /// When the limit was last decreased
+    last_decrease: Option<Instant>,
+}
+
+/// Shared state of a controller.
+#[derive(Debug)]
+struct ControllerState {
+    min: usize,
+    max: usize,
+    latency_tolerance: f64,
+    hosts: Mutex<HashMap<String, HostLimit>>,
+    /// Woken whenever a permit is released or a limit grows
+    released: Notify,
+}
+
+/// AIMD concurrency limits kept per host, within fixed bounds.
+///
+/// Cloning a controller shares its limits, so the tasks of one batch can
+/// each hold a clone.
+#[derive(Debug, Clone)]
+pub struct AdaptiveConcurrency {
+    state: Arc<ControllerState>,
+}
+
+impl AdaptiveConcurrency {
+    /// Creates a controller whose per-host limits start at `min` and stay within `min..=max`.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - Lowest limit of a host, at least 1
+    /// * `max` - Highest limit of a host, at least `min`
+    pub fn new(min: usize, max: usize) -> Self {
+        let min = min.max(1);
+        Self {
+            state: Arc::new(ControllerState {
+                min,
+                max: max.max(min),
+                latency_tolerance: DEFAULT_LATENCY_TOLERANCE,
+                hosts: Mutex::new(HashMap::new()),
+                released: Notify::new(),
+            }),
+        }
+    }
+
+    /// Sets how much slower than its best latency a host may get before its
+    /// limit is decreased.
+    ///
+    /// Must be called before the controller is cloned or used.
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - Ratio of smoothed to best latency, at least 1.0 (default 4.0)
+    pub fn latency_tolerance(mut self, tolerance: f64) -> Self {
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.latency_tolerance = tolerance.max(1.0);
+        }
+        self
+    }
+
+    /// Returns the bounds limits are kept within.
+    pub fn bounds(&self) -> (usize, usize) {
+        (self.state.min, self.state.max)
+    }
+
+    /// Returns the current limit of a host; hosts not seen yet are at the minimum.
+    ///
+    /// # Arguments
+    ///
+    /// * `host` - The host name, such as `example.com`
+    pub fn limit(&self, host: &str) -> usize {
+        let hosts = self.hosts();
+        hosts
+            .get(host)
+            .map_or(self.state.min, |host| host.limit as usize)
+    }
+
+    /// Waits until the URL's host is below its limit, and takes a slot.
+    ///
+    /// URLs without a host share one limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL about to be converted
+    pub async fn acquire(&self, url: &str) -> ConcurrencyPermit {
+        let host = url::Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default();
+        loop {
+            let released = self.state.released.notified();
+            {
+                let mut hosts = self.hosts();
+                let entry = hosts.entry(host.clone()).or_insert_with(|| HostLimit {
+                    limit: self.state.min as f64,
+                    in_flight: 0,
+                    best_latency: None,
+                    smoothed_latency: None,
+                    last_decrease: None,
+                });
+                if entry.in_flight < entry.limit as usize {
+                    entry.in_flight += 1;
+                    return ConcurrencyPermit {
+                        controller: self.clone(),
+                        host,
+                        started: Instant::now(),
+                        recorded: false,
+                    };
+                }
+            }
+            released.await;
+        }
+    }
+
+    /// Releases a slot and adjusts the host's limit for the outcome.
+    fn release(&self, host: &str, started: Instant, outcome: Option<Outcome>) {
+        let mut hosts = self.hosts();
+        if let Some(entry) = hosts.get_mut(host) {
+            entry.in_flight = entry.in_flight.saturating_sub(1);
+            let latency = started.elapsed();
+            match outcome {
+                Some(Outcome::Healthy) => {
+                    let best = entry.best_latency.map_or(latency, |best| best.min(latency));
+                    let smoothed = entry.smoothed_latency.map_or(latency, |smoothed| {
+                        smoothed.mul_f64(1.0 - LATENCY_SMOOTHING)
+                            + latency.mul_f64(LATENCY_SMOOTHING)
+                    });
+                    entry.best_latency = Some(best);
+                    entry.smoothed_latency = Some(smoothed);
+                    if smoothed.as_secs_f64() > best.as_secs_f64() * self.state.latency_tolerance {
+                        self.decrease(host, entry, started, "latency");
+                    } else {
+                        entry.limit = (entry.limit + 1.0 / entry.limit).min(self.state.max as f64);
+                    }
+                }
+                Some(Outcome::Overloaded) => self.decrease(host, entry, started, "errors"),
+                None => {}
+            }
+        }
+        drop(hosts);
+        self.state.released.notify_waiters();
+    }
+
+    /// Halves a host's limit, once per round of conversions started before the last decrease.
+    fn decrease(&self, host: &str, entry: &mut HostLimit, started: Instant, reason: &str) {
+        if entry.last_decrease.is_some_and(|last| started < last) {
+            return;
+        }
+        entry.limit = (entry.limit / 2.0).max(self.state.min as f64);
+        entry.last_decrease = Some(Instant::now());
+        debug!(
+            "Lowered concurrency for {} to {} ({})",
+            host, entry.limit as usize, reason
+        );
+    }
+
+    fn hosts(&self) -> std::sync::MutexGuard<'_, HashMap<String, HostLimit>> {
+        self.state.hosts.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// How a conversion holding a permit went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    /// Finished, successfully or with an error unrelated to load
+    Healthy,
+    /// Throttled, timed out, or failed with a server error
+    Overloaded,
+}
+
+/// A slot in a host's concurrency limit, released when dropped.
+///
+/// Call [`ConcurrencyPermit::record`] with the conversion's result so the
+/// host's limit is adjusted; a permit dropped without a result only frees
+/// its slot.
+#[derive(Debug)]
+pub struct ConcurrencyPermit {
+    controller: AdaptiveConcurrency,
+    host: String,
+    started: Instant,
+    recorded: bool,
+}
+
+impl ConcurrencyPermit {
+    /// Releases the slot, adjusting the host's limit for the conversion's result.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The conversion's result
+    pub fn record<T>(self, result: &Result<T, MarkdownError>) {
+        self.record_error(result.as_ref().err());
+    }
+
+    /// Releases the slot, adjusting the host's limit for the conversion's error, if any.
+    ///
+    /// Errors that mean the host is overloaded (throttling, timeouts,
+    /// dropped connections, and 5xx responses) decrease the limit. Other
+    /// errors, such as missing pages, count like successes.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The error the conversion failed with, or None if it succeeded
+    pub fn record_error(self, error: Option<&MarkdownError>) {
+        let outcome = match error {
+            Some(e) if signals_overload(e) => Outcome::Overloaded,
+            _ => Outcome::Healthy,
+        };
+        self.finish(outcome);
+    }
+
+    /// Releases the slot after the caller gave up waiting for the conversion,
+    /// decreasing the host's limit.
+    pub fn record_timeout(self) {
+        self.finish(Outcome::Overloaded);
+    }
+
+    fn finish(mut self, outcome: Outcome) {
+        self.recorded = true;
+        self.controller
+            .release(&self.host, self.started, Some(outcome));
+    }
+}
+
+impl Drop for ConcurrencyPermit {
+    fn drop(&mut self) {
+        if !self.recorded {
+            self.controller.release(&self.host, self.started, None);
+        }
+    }
+}
+
+/// Returns true if an error suggests the host is overloaded.
+fn signals_overload(error: &MarkdownError) -> bool {
+    match error {
+        MarkdownError::EnhancedNetworkError { kind, .. } => match kind {
+            NetworkErrorKind::Timeout
+            | NetworkErrorKind::ConnectionFailed
+            | NetworkErrorKind::RateLimited => true,
+            NetworkErrorKind::ServerError(status) => *status == 429 || *status >= 500,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ErrorContext;
+
+    const URL: &str = "https://example.com/page";
+
+    fn rate_limited() -> Result<(), MarkdownError> {
+        Err(MarkdownError::EnhancedNetworkError {
+            kind: NetworkErrorKind::RateLimited,
+            context: ErrorContext::new(URL, "fetch", "test"),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_successes_increase_limit_up_to_max() {
+        let controller = AdaptiveConcurrency::new(1, 3);
+        for _ in 0..20 {
+            controller
+                .acquire(URL)
+                .await
+                .record(&Ok::<(), MarkdownError>(()));
+        }
+        assert_eq!(controller.limit("example.com"), 3);
+        assert_eq!(controller.limit("other.example"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_overload_halves_limit_once_per_round() {
+        let controller = AdaptiveConcurrency::new(1, 16);
+        for _ in 0..60 {
+            controller
+                .acquire(URL)
+                .await
+                .record(&Ok::<(), MarkdownError>(()));
+        }
+        let before = controller.limit("example.com");
+        assert!(before >= 8);
+
+        // Conversions started before the decrease do not decrease it again
+        let first = controller.acquire(URL).await;
+        let second = controller.acquire(URL).await;
+        first.record(&rate_limited());
+        second.record(&rate_limited());
+        assert_eq!(controller.limit("example.com"), before / 2);
+
+        // Missing pages are not a sign of overload
+        let not_found: Result<(), MarkdownError> = Err(MarkdownError::EnhancedNetworkError {
+            kind: NetworkErrorKind::ServerError(404),
+            context: ErrorContext::new(URL, "fetch", "test"),
+        });
+        controller.acquire(URL).await.record(&not_found);
+        assert!(controller.limit("example.com") >= before / 2);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_for_free_slot() {
+        let controller = AdaptiveConcurrency::new(1, 1);
+        let permit = controller.acquire(URL).await;
+
+        let waiting = tokio::spawn({
+            let controller = controller.clone();
+            async move { controller.acquire(URL).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiting.is_finished());
+
+        // Other hosts are not held up
+        let _other = controller.acquire("https://other.example/").await;
+
+        drop(permit);
+        tokio::time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .expect("slot should be released")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_slow_responses_decrease_limit() {
+        let controller = AdaptiveConcurrency::new(1, 8).latency_tolerance(3.0);
+        for _ in 0..10 {
+            let permit = controller.acquire(URL).await;
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            permit.record(&Ok::<(), MarkdownError>(()));
+        }
+        let before = controller.limit("example.com");
+        assert!(before > 1);
+
+        for _ in 0..2 {
+            let permit = controller.acquire(URL).await;
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            permit.record(&Ok::<(), MarkdownError>(()));
+        }
+        assert!(controller.limit("example.com") < before);
+    }
+}
@@ -0,0 +1,187 @@
+//! Paywall and login-wall detection for pages that hide their content.
+//! Signals come from paywall metadata (`og:type=paywall`, a locked
+//! `article:content_tier`, schema.org `isAccessibleForFree: false`), from
+//! "subscribe to continue reading" style prompts, and, when asked for, from
+//! sign-in forms. A short body is required so that full articles that merely
+//! carry paywall metadata, or a login box in the sidebar, are still converted.
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Pages with more words than this are treated as real content.
+const MAX_ACCESS_WALL_WORDS: usize = 500;
+
+/// Pages with a sign-in form and more words than this are treated as real content.
+const MAX_LOGIN_WALL_WORDS: usize = 150;
+
+/// Prompts that ask the reader to subscribe or sign in before reading on.
+const PROMPT_PATTERN: &str = r"(?i)\b(?:subscribe|sign\s+up|register)\s+(?:now\s+)?to\s+(?:continue|keep)\s+reading\b|\b(?:sign|log)\s*in\s+to\s+(?:continue|keep\s+reading|read\s+(?:the|this)\s+(?:full\s+)?(?:article|story|post))\b|\b(?:this|the)\s+(?:article|content|story|post)\s+is\s+(?:only\s+)?(?:available\s+)?(?:exclusively\s+)?(?:for|to)\s+(?:paying\s+)?(?:subscribers|members)\b|\bsubscribers[\s-]+only\s+(?:content|article|story)\b|\bcreate\s+a\s+free\s+account\s+to\s+(?:continue|keep\s+reading|read)\b|\byou\s+(?:have|'ve|’ve)\s+reached\s+your\s+(?:free\s+)?(?:article|story)\s+limit\b";
+
+/// Detects paywalls and login walls from a page's HTML and converted markdown.
+pub struct AccessWallDetector {
+    meta: Regex,
+    attribute: Regex,
+    free_access: Regex,
+    prompt: Regex,
+    password_input: Regex,
+}
+
+impl AccessWallDetector {
+    /// Creates a new access wall detector.
+    pub fn new() -> Self {
+        Self {
+            meta: Regex::new(r"(?is)<meta\b[^>]*>").expect("valid meta pattern"),
+            attribute: Regex::new(r#"(?is)\b([a-z:_-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#)
+                .expect("valid attribute pattern"),
+            free_access: Regex::new(r#"(?i)"isAccessibleForFree"\s*:\s*(?:false|"false")"#)
+                .expect("valid isAccessibleForFree pattern"),
+            prompt: Regex::new(PROMPT_PATTERN).expect("valid access wall prompt pattern"),
+            password_input: Regex::new(r#"(?is)<input\b[^>]*\btype\s*=\s*["']?password\b"#)
+                .expect("valid password input pattern"),
+        }
+    }
+
+    /// Returns a detector shared by all conversions, compiled on first use.
+    pub fn shared() -> &'static Self {
+        static DETECTOR: OnceLock<AccessWallDetector> = OnceLock::new();
+        DETECTOR.get_or_init(Self::new)
+    }
+
+    /// Checks whether a page shows a paywall instead of its content.
+    ///
+    /// # Arguments
+    ///
+    /// * `html` - The raw HTML of the page
+    /// * `markdown` - The converted markdown, used to measure how much content the page has
+    ///
+    /// # Returns
+    ///
+    /// A short description of the matching signal, or `None` if the page looks readable.
+    pub fn detect(&self, html: &str, markdown: &str) -> Option<String> {
+        let words = markdown.split_whitespace().count();
+        if words > MAX_ACCESS_WALL_WORDS {
+            return None;
+        }
+
+        if let Some(signal) = self.meta_signal(html) {
+            return Some(signal);
+        }
+
+        if self.free_access.is_match(html) {
+            return Some("structured data marks the page as not accessible for free".to_string());
+        }
+
+        if let Some(prompt) = self.prompt.find(markdown) {
+            let prompt = prompt.as_str().split_whitespace().collect::<Vec<_>>();
+            return Some(format!("page asks to '{}'", prompt.join(" ")));
+        }
+
+        None
+    }
+
+    /// Checks whether a page is little more than a sign-in form.
+    ///
+    /// Kept separate from [`detect`](Self::detect) because short pages with a
+    /// password field are not always walls, such as account settings pages.
+    ///
+    /// # Arguments
+    ///
+    /// * `html` - The raw HTML of the page
+    /// * `markdown` - The converted markdown, used to measure how much content the page has
+    ///
+    /// # Returns
+    ///
+    /// A short description of the matching signal, or `None` if the page has content.
+    pub fn detect_login_wall(&self, html: &str, markdown: &str) -> Option<String> {
+        let words = markdown.split_whitespace().count();
+        (words <= MAX_LOGIN_WALL_WORDS && self.password_input.is_match(html))
+            .then(|| "page shows a sign-in form instead of content".to_string())
+    }
+
+    /// Returns the first meta tag that marks the page as paywalled.
+    fn meta_signal(&self, html: &str) -> Option<String> {
+        self.meta.find_iter(html).find_map(|tag| {
+            let mut name = String::new();
+            let mut content = String::new();
+            for attribute in self.attribute.captures_iter(tag.as_str()) {
+                let value = attribute
+                    .get(2)
+                    .or_else(|| attribute.get(3))
+                    .map_or("", |value| value.as_str())
+                    .trim()
+                    .to_ascii_lowercase();
+                match attribute[1].to_ascii_lowercase().as_str() {
+                    "property" | "name" => name = value,
+                    "content" => content = value,
+                    _ => {}
+                }
+            }
+            match (name.as_str(), content.as_str()) {
+                ("og:type", "paywall") => Some("meta tag og:type is 'paywall'".to_string()),
+                ("article:content_tier", "locked" | "metered") => {
+                    Some(format!("meta tag article:content_tier is '{content}'"))
+                }
+                _ => None,
+            }
+        })
+    }
+}
+
+impl Default for AccessWallDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_paywall_metadata() {
+        let detector = AccessWallDetector::new();
+        let html = "<head><meta property='og:type' content='paywall'></head><p>Teaser.</p>";
+        assert!(detector
+            .detect(html, "Teaser.")
+            .unwrap()
+            .contains("og:type"));
+
+        let html = r#"<meta content="locked" property="article:content_tier"><p>Teaser.</p>"#;
+        assert!(detector.detect(html, "Teaser.").is_some());
+
+        let html = r#"<script type="application/ld+json">{"@type": "NewsArticle",
+            "isAccessibleForFree": "False"}</script><p>Teaser.</p>"#;
+        assert!(detector.detect(html, "Teaser.").is_some());
+    }
+
+    #[test]
+    fn test_detects_prompts_and_login_forms() {
+        let detector = AccessWallDetector::new();
+        let markdown = "# Market update\n\nStocks rose.\n\nSubscribe now to continue reading.";
+        let signal = detector.detect("<p>Stocks rose.</p>", markdown).unwrap();
+        assert!(signal.contains("Subscribe now to continue reading"));
+
+        let html =
+            "<form action='/login'><input name='user'><input type=\"password\" name='pw'></form>";
+        assert!(detector.detect(html, "Username Password Sign in").is_none());
+        assert!(detector
+            .detect_login_wall(html, "Username Password Sign in")
+            .is_some());
+    }
+
+    #[test]
+    fn test_ignores_readable_pages() {
+        let detector = AccessWallDetector::new();
+        let html = "<meta property='og:type' content='article'><p>Free to read.</p>";
+        assert!(detector.detect(html, "Free to read.").is_none());
+
+        // Full articles with paywall metadata or a sidebar login box are kept
+        let article = "word ".repeat(MAX_ACCESS_WALL_WORDS + 1);
+        let html = "<meta property='og:type' content='paywall'>";
+        assert!(detector.detect(html, &article).is_none());
+        let article = "word ".repeat(MAX_LOGIN_WALL_WORDS + 1);
+        assert!(detector
+            .detect_login_wall("<input type='password'>", &article)
+            .is_none());
+    }
+}
@@ -0,0 +1,800 @@
+//! Azure DevOps work items to markdown conversion with REST API integration.
+//!
+//! Work items are read from the Azure Boards REST API: the work item's fields
+//! give the title and metadata, its HTML fields (description, repro steps,
+//! acceptance criteria) are converted to markdown, and the discussion is read
+//! from the comments endpoint, following continuation tokens across pages.
+//!
+//! Requests are authenticated with a personal access token, configured with
+//! `Config::builder().azure_devops_token(...)` or the `AZURE_DEVOPS_EXT_PAT`
+//! environment variable used by the Azure DevOps CLI.
+//!
+//! # Supported URLs
+//!
+//! - `https://dev.azure.com/{organization}/{project}/_workitems/edit/{id}`
+//! - `https://{organization}.visualstudio.com/{project}/_workitems/edit/{id}`
+//!
+//! # Usage Examples
+//!
+//! ```rust,no_run
+//! use markdowndown::converters::AzureDevOpsConverter;
+//!
+//! # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
+//! let converter = AzureDevOpsConverter::from_env();
+//! let url = "https://dev.azure.com/contoso/Fabrikam/_workitems/edit/42";
+//! let markdown = converter.convert(url).await?;
+//! println!("{}", markdown);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::HttpClient;
+use crate::frontmatter::FrontmatterBuilder;
+use crate::types::{
+    AuthErrorKind, ContentErrorKind, ErrorContext, Markdown, MarkdownError, ValidationErrorKind,
+};
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use tracing::{debug, instrument, warn};
+use url::Url as ParsedUrl;
+
+use super::html::HtmlConverter;
+
+/// Default Azure DevOps Services API base URL
+const DEFAULT_AZURE_DEVOPS_API_BASE_URL: &str = "https://dev.azure.com";
+
+/// Work item API version
+const WORK_ITEM_API_VERSION: &str = "7.1";
+
+/// Comments API version (the comments endpoint is still in preview)
+const COMMENTS_API_VERSION: &str = "7.1-preview.4";
+
+/// Comments requested per page (the API's maximum)
+const COMMENTS_PAGE_SIZE: usize = 200;
+
+/// Upper bound on comment pages fetched, as a guard against continuation loops
+const MAX_COMMENT_PAGES: usize = 100;
+
+/// Environment variable holding a personal access token, as used by the Azure DevOps CLI
+const TOKEN_ENV_VAR: &str = "AZURE_DEVOPS_EXT_PAT";
+
+/// HTML fields rendered as sections, with their headings, in display order.
+const HTML_SECTIONS: [(&str, &str); 4] = [
+    ("System.Description", "Description"),
+    ("Microsoft.VSTS.TCM.ReproSteps", "Repro Steps"),
+    ("Microsoft.VSTS.TCM.SystemInfo", "System Info"),
+    (
+        "Microsoft.VSTS.Common.AcceptanceCriteria",
+        "Acceptance Criteria",
+    ),
+];
+
+/// Represents a parsed Azure DevOps work item URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkItemResource {
+    /// Organization name
+    pub organization: String,
+    /// Project name, percent-encoded as in the URL
+    pub project: String,
+    /// Work item ID
+    pub id: u32,
+    /// Original URL for reference
+    pub original_url: String,
+}
+
+/// Azure DevOps work item data from the API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkItem {
+    /// Work item ID
+    pub id: u32,
+    /// Revision number
+    #[serde(default)]
+    pub rev: u32,
+    /// Field values by reference name, such as `System.Title`
+    pub fields: HashMap<String, Value>,
+}
+
+impl WorkItem {
+    /// Returns a field as text, for string and number fields.
+    pub fn text(&self, field: &str) -> Option<String> {
+        match self.fields.get(field)? {
+            Value::String(text) if !text.trim().is_empty() => Some(text.trim().to_string()),
+            Value::Number(number) => Some(number.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Returns the display name of an identity field, such as `System.AssignedTo`.
+    pub fn person(&self, field: &str) -> Option<String> {
+        match self.fields.get(field)? {
+            Value::Object(identity) => identity
+                .get("displayName")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            // Older API versions return identities as "Name <email>"
+            Value::String(identity) => identity
+                .split(" <")
+                .next()
+                .map(|name| name.trim().to_string()),
+            _ => None,
+        }
+    }
+
+    /// Returns a date field.
+    pub fn date(&self, field: &str) -> Option<DateTime<Utc>> {
+        self.fields
+            .get(field)?
+            .as_str()
+            .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+            .map(|date| date.with_timezone(&Utc))
+    }
+
+    /// Returns the work item's tags.
+    pub fn tags(&self) -> Vec<String> {
+        self.text("System.Tags")
+            .map(|tags| {
+                tags.split(';')
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// An identity in a comment.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentityRef {
+    /// Display name
+    pub display_name: String,
+}
+
+/// A comment in a work item's discussion.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkItemComment {
+    /// Comment HTML
+    #[serde(default)]
+    pub text: String,
+    /// Comment author
+    pub created_by: IdentityRef,
+    /// When the comment was posted
+    pub created_date: DateTime<Utc>,
+}
+
+/// One page of a work item's comments.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CommentPage {
+    #[serde(default)]
+    comments: Vec<WorkItemComment>,
+    #[serde(default)]
+    total_count: usize,
+    continuation_token: Option<String>,
+}
+
+/// Azure DevOps work item to markdown converter.
+///
+/// Fetches the work item and its discussion from the REST API and renders
+/// them as markdown with frontmatter metadata.
+#[derive(Debug, Clone)]
+pub struct AzureDevOpsConverter {
+    /// HTTP client for making requests to the Azure DevOps API
+    client: HttpClient,
+    /// Converter for the HTML in work item fields and comments
+    html_converter: HtmlConverter,
+    /// Optional personal access token for authentication
+    auth_token: Option<String>,
+    /// Base URL of the API, below which organizations are addressed
+    api_base_url: String,
+    /// Maximum number of comments to fetch (None fetches all)
+    max_comments: Option<usize>,
+}
+
+impl AzureDevOpsConverter {
+    /// Creates a new Azure DevOps converter without authentication.
+    ///
+    /// Only work items in public projects can be read without a token.
+    pub fn new() -> Self {
+        Self {
+            client: HttpClient::new(),
+            html_converter: HtmlConverter::new(),
+            auth_token: None,
+            api_base_url: DEFAULT_AZURE_DEVOPS_API_BASE_URL.to_string(),
+            max_comments: None,
+        }
+    }
+
+    /// Creates a new Azure DevOps converter with a personal access token.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Personal access token with the Work Items (Read) scope
+    pub fn new_with_token(token: String) -> Self {
+        Self {
+            auth_token: Some(token),
+            ..Self::new()
+        }
+    }
+
+    /// Creates an Azure DevOps converter with the token in `AZURE_DEVOPS_EXT_PAT`.
+    ///
+    /// Falls back to unauthenticated mode if the variable is not set.
+    pub fn from_env() -> Self {
+        Self {
+            auth_token: token_from_env(),
+            ..Self::new()
+        }
+    }
+
+    /// Creates an Azure DevOps converter with a configured client and HTML converter.
+    ///
+    /// The token configured on the client is used, or the one in
+    /// `AZURE_DEVOPS_EXT_PAT` if the client has none.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client used for API requests
+    /// * `html_converter` - The configured converter used for HTML fields and comments
+    pub fn with_config(client: HttpClient, html_converter: HtmlConverter) -> Self {
+        let auth_token = client
+            .azure_devops_token()
+            .map(str::to_string)
+            .or_else(token_from_env);
+        Self {
+            client,
+            html_converter,
+            auth_token,
+            api_base_url: DEFAULT_AZURE_DEVOPS_API_BASE_URL.to_string(),
+            max_comments: None,
+        }
+    }
+
+    /// Fetches from a different API base URL (primarily for testing).
+    ///
+    /// # Arguments
+    ///
+    /// * `api_base_url` - The URL organizations are addressed below, in place of `https://dev.azure.com`
+    pub fn with_api_base_url<T: Into<String>>(mut self, api_base_url: T) -> Self {
+        self.api_base_url = api_base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Limits how many comments are fetched and rendered.
+    ///
+    /// Comments are fetched oldest first, so the limit keeps the start of the
+    /// discussion. A note below the comments says how many were left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - The maximum number of comments to include
+    pub fn with_max_comments(mut self, max: usize) -> Self {
+        self.max_comments = Some(max);
+        self
+    }
+
+    /// Converts an Azure DevOps work item URL to markdown with frontmatter.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The work item URL to convert
+    ///
+    /// # Returns
+    ///
+    /// Returns the work item and its discussion as markdown, or an error.
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ValidationError` - If the URL is not a work item URL
+    /// * `MarkdownError::AuthenticationError` - If the token is missing or not accepted
+    /// * `MarkdownError::EnhancedNetworkError` - For API errors or network failures
+    /// * `MarkdownError::ContentError` - If an API response cannot be parsed
+    #[instrument(skip(self))]
+    pub async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+        let resource = self.parse_work_item_url(url)?;
+        let (work_item, (comments, total_comments)) = tokio::try_join!(
+            self.fetch_work_item(&resource),
+            self.fetch_comments(&resource)
+        )?;
+
+        let content = self.render_markdown(&work_item, &comments, total_comments);
+        let frontmatter = self.build_frontmatter(&resource, &work_item, comments.len())?;
+        Markdown::new(format!("{frontmatter}\n{content}"))
+    }
+
+    /// Parses an Azure DevOps work item URL.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The work item URL to parse
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::converters::AzureDevOpsConverter;
+    ///
+    /// let converter = AzureDevOpsConverter::new();
+    /// let resource = converter
+    ///     .parse_work_item_url("https://dev.azure.com/contoso/Fabrikam/_workitems/edit/42")?;
+    /// assert_eq!(resource.organization, "contoso");
+    /// assert_eq!(resource.project, "Fabrikam");
+    /// assert_eq!(resource.id, 42);
+    /// # Ok::<(), markdowndown::types::MarkdownError>(())
+    /// ```
+    pub fn parse_work_item_url(&self, url: &str) -> Result<WorkItemResource, MarkdownError> {
+        let invalid = |info: &str| {
+            let context = ErrorContext::new(url, "URL parsing", "AzureDevOpsConverter")
+                .with_info(info.to_string());
+            MarkdownError::ValidationError {
+                kind: ValidationErrorKind::InvalidUrl,
+                context,
+            }
+        };
+
+        let parsed_url = ParsedUrl::parse(url.trim()).map_err(|_| invalid("Invalid URL"))?;
+        let segments: Vec<&str> = parsed_url
+            .path_segments()
+            .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default();
+        let (organization, rest) = match parsed_url.host_str() {
+            Some("dev.azure.com") => match segments.split_first() {
+                Some((organization, rest)) => (organization.to_string(), rest),
+                None => return Err(invalid("Missing organization")),
+            },
+            Some(host) if host.ends_with(".visualstudio.com") => (
+                host.trim_end_matches(".visualstudio.com").to_string(),
+                &segments[..],
+            ),
+            _ => return Err(invalid("Not an Azure DevOps URL")),
+        };
+
+        match rest {
+            [project, "_workitems", "edit", id, ..] => Ok(WorkItemResource {
+                organization,
+                project: project.to_string(),
+                id: id.parse().map_err(|_| invalid("Invalid work item ID"))?,
+                original_url: url.trim().to_string(),
+            }),
+            _ => Err(invalid(
+                "Expected a URL like https://dev.azure.com/{organization}/{project}/_workitems/edit/{id}",
+            )),
+        }
+    }
+
+    /// Fetches a work item with all of its fields.
+    pub async fn fetch_work_item(
+        &self,
+        resource: &WorkItemResource,
+    ) -> Result<WorkItem, MarkdownError> {
+        let url = format!(
+            "{}/_apis/wit/workitems/{}?api-version={WORK_ITEM_API_VERSION}",
+            self.project_url(resource),
+            resource.id
+        );
+        let response = self.make_api_request(&url).await?;
+        self.parse_response(&url, &response, "work item")
+    }
+
+    /// Fetches a work item's comments, oldest first, with the total comment count.
+    ///
+    /// Stops early once the comment limit is reached.
+    pub async fn fetch_comments(
+        &self,
+        resource: &WorkItemResource,
+    ) -> Result<(Vec<WorkItemComment>, usize), MarkdownError> {
+        let page_size = self
+            .max_comments
+            .map_or(COMMENTS_PAGE_SIZE, |max| max.clamp(1, COMMENTS_PAGE_SIZE));
+        let first_page_url = format!(
+            "{}/_apis/wit/workItems/{}/comments?api-version={COMMENTS_API_VERSION}&order=asc&$top={page_size}",
+            self.project_url(resource),
+            resource.id
+        );
+
+        let mut comments = Vec::new();
+        let mut total = 0;
+        let mut continuation: Option<String> = None;
+        for _ in 0..MAX_COMMENT_PAGES {
+            let url = match &continuation {
+                Some(token) => format!(
+                    "{first_page_url}&continuationToken={}",
+                    url::form_urlencoded::byte_serialize(token.as_bytes()).collect::<String>()
+                ),
+                None => first_page_url.clone(),
+            };
+            let response = self.make_api_request(&url).await?;
+            let page: CommentPage = self.parse_response(&url, &response, "comments")?;
+            total = total.max(page.total_count);
+            comments.extend(page.comments);
+
+            if let Some(max) = self.max_comments {
+                if comments.len() >= max {
+                    comments.truncate(max);
+                    break;
+                }
+            }
+            match page.continuation_token {
+                Some(token) if continuation.as_ref() != Some(&token) => continuation = Some(token),
+                _ => break,
+            }
+        }
+
+        debug!("Fetched {} of {} comments", comments.len(), total);
+        let total = total.max(comments.len());
+        Ok((comments, total))
+    }
+
+    /// Returns the API URL of the resource's project.
+    fn project_url(&self, resource: &WorkItemResource) -> String {
+        format!(
+            "{}/{}/{}",
+            self.api_base_url, resource.organization, resource.project
+        )
+    }
+
+    /// Makes an API request, authenticated with the personal access token if set.
+    async fn make_api_request(&self, url: &str) -> Result<String, MarkdownError> {
+        let mut headers = HashMap::new();
+        headers.insert("Accept".to_string(), "application/json".to_string());
+        if let Some(token) = &self.auth_token {
+            // Personal access tokens are sent as the password of basic auth
+            headers.insert(
+                "Authorization".to_string(),
+                format!("Basic {}", BASE64.encode(format!(":{token}"))),
+            );
+        }
+        self.client.get_text_with_headers(url, &headers).await
+    }
+
+    /// Parses an API response.
+    ///
+    /// Without valid credentials, Azure DevOps answers with a sign-in page
+    /// rather than an error status, which is reported as missing credentials.
+    fn parse_response<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        response: &str,
+        what: &str,
+    ) -> Result<T, MarkdownError> {
+        serde_json::from_str(response).map_err(|e| {
+            if response.trim_start().starts_with('<') {
+                warn!("Azure DevOps returned a sign-in page for {}", url);
+                let context = ErrorContext::new(url, "API request", "AzureDevOpsConverter")
+                    .with_info("Azure DevOps asked to sign in; set a personal access token with azure_devops_token or AZURE_DEVOPS_EXT_PAT");
+                MarkdownError::AuthenticationError {
+                    kind: if self.auth_token.is_some() {
+                        AuthErrorKind::InvalidToken
+                    } else {
+                        AuthErrorKind::MissingToken
+                    },
+                    context,
+                }
+            } else {
+                let context = ErrorContext::new(url, "Response parsing", "AzureDevOpsConverter")
+                    .with_info(format!("Failed to parse Azure DevOps {what} response: {e}"));
+                MarkdownError::ContentError {
+                    kind: ContentErrorKind::ParsingFailed,
+                    context,
+                }
+            }
+        })
+    }
+
+    /// Converts HTML from a field or comment, keeping the text if conversion fails.
+    fn html_to_markdown(&self, html: &str) -> String {
+        self.html_converter
+            .convert_html(html)
+            .map(|markdown| markdown.trim().to_string())
+            .unwrap_or_else(|_| html.trim().to_string())
+    }
+
+    /// Renders the work item and its comments as markdown.
+    fn render_markdown(
+        &self,
+        work_item: &WorkItem,
+        comments: &[WorkItemComment],
+        total_comments: usize,
+    ) -> String {
+        let mut markdown = String::new();
+        let work_item_type = work_item
+            .text("System.WorkItemType")
+            .unwrap_or_else(|| "Work Item".to_string());
+        let title = work_item.text("System.Title").unwrap_or_default();
+        markdown.push_str(&format!("# {work_item_type} {}: {title}\n\n", work_item.id));
+
+        if let Some(state) = work_item.text("System.State") {
+            match work_item.text("System.Reason") {
+                Some(reason) => markdown.push_str(&format!("**State:** {state} ({reason})  \n")),
+                None => markdown.push_str(&format!("**State:** {state}  \n")),
+            }
+        }
+        for (label, value) in [
+            ("Assigned To", work_item.person("System.AssignedTo")),
+            ("Created By", work_item.person("System.CreatedBy")),
+            (
+                "Created",
+                work_item
+                    .date("System.CreatedDate")
+                    .map(|date| date.format("%Y-%m-%d %H:%M:%S UTC").to_string()),
+            ),
+            ("Area", work_item.text("System.AreaPath")),
+            ("Iteration", work_item.text("System.IterationPath")),
+            ("Priority", work_item.text("Microsoft.VSTS.Common.Priority")),
+            ("Severity", work_item.text("Microsoft.VSTS.Common.Severity")),
+        ] {
+            if let Some(value) = value {
+                markdown.push_str(&format!("**{label}:** {value}  \n"));
+            }
+        }
+        let tags = work_item.tags();
+        if !tags.is_empty() {
+            markdown.push_str(&format!("**Tags:** {}  \n", tags.join(", ")));
+        }
+        markdown.push('\n');
+
+        for (field, heading) in HTML_SECTIONS {
+            if let Some(html) = work_item.text(field) {
+                markdown.push_str(&format!("## {heading}\n\n"));
+                markdown.push_str(&self.html_to_markdown(&html));
+                markdown.push_str("\n\n");
+            }
+        }
+
+        if !comments.is_empty() {
+            markdown.push_str("## Discussion\n\n");
+            for comment in comments {
+                markdown.push_str(&format!(
+                    "### Comment by {} ({})\n\n",
+                    comment.created_by.display_name,
+                    comment.created_date.format("%Y-%m-%d %H:%M:%S UTC")
+                ));
+                if !comment.text.trim().is_empty() {
+                    markdown.push_str(&self.html_to_markdown(&comment.text));
+                    markdown.push_str("\n\n");
+                }
+            }
+            if total_comments > comments.len() {
+                markdown.push_str(&format!(
+                    "*Showing the first {} of {} comments.*\n\n",
+                    comments.len(),
+                    total_comments
+                ));
+            }
+        }
+
+        markdown.trim().to_string()
+    }
+
+    /// Builds frontmatter for the work item.
+    fn build_frontmatter(
+        &self,
+        resource: &WorkItemResource,
+        work_item: &WorkItem,
+        comment_count: usize,
+    ) -> Result<String, MarkdownError> {
+        let now = Utc::now();
+        let project = work_item
+            .text("System.TeamProject")
+            .unwrap_or_else(|| resource.project.clone());
+        let mut builder = FrontmatterBuilder::new(resource.original_url.clone())
+            .exporter(format!(
+                "markdowndown-azure-devops-{}",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .download_date(now)
+            .additional_field(
+                "title".to_string(),
+                work_item.text("System.Title").unwrap_or_default(),
+            )
+            .additional_field("url".to_string(), resource.original_url.clone())
+            .additional_field("converter".to_string(), "AzureDevOpsConverter".to_string())
+            .additional_field("converted_at".to_string(), now.to_rfc3339())
+            .additional_field("conversion_type".to_string(), "azure_devops".to_string())
+            .additional_field(
+                "azure_devops_work_item_id".to_string(),
+                work_item.id.to_string(),
+            )
+            .additional_field(
+                "azure_devops_project".to_string(),
+                format!("{}/{project}", resource.organization),
+            )
+            .additional_field(
+                "azure_devops_revision".to_string(),
+                work_item.rev.to_string(),
+            )
+            .additional_field(
+                "azure_devops_comment_count".to_string(),
+                comment_count.to_string(),
+            );
+
+        for (name, value) in [
+            ("azure_devops_type", work_item.text("System.WorkItemType")),
+            ("azure_devops_state", work_item.text("System.State")),
+            (
+                "azure_devops_assigned_to",
+                work_item.person("System.AssignedTo"),
+            ),
+        ] {
+            if let Some(value) = value {
+                builder = builder.additional_field(name.to_string(), value);
+            }
+        }
+        let tags = work_item.tags();
+        if !tags.is_empty() {
+            builder = builder.additional_field("azure_devops_tags".to_string(), tags.join(", "));
+        }
+
+        builder.build()
+    }
+}
+
+/// Reads a personal access token from `AZURE_DEVOPS_EXT_PAT`.
+fn token_from_env() -> Option<String> {
+    std::env::var(TOKEN_ENV_VAR)
+        .ok()
+        .filter(|token| !token.trim().is_empty())
+}
+
+impl Default for AzureDevOpsConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl super::Converter for AzureDevOpsConverter {
+    async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+        self.convert(url).await
+    }
+
+    /// The work item and its first page of comments are fetched separately.
+    fn estimated_requests(&self, _url: &str) -> usize {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        "Azure DevOps Work Item"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const URL: &str = "https://dev.azure.com/contoso/Fabrikam%20Web/_workitems/edit/42";
+
+    fn work_item_json() -> serde_json::Value {
+        serde_json::json!({
+            "id": 42,
+            "rev": 7,
+            "fields": {
+                "System.TeamProject": "Fabrikam Web",
+                "System.WorkItemType": "Bug",
+                "System.Title": "Login fails on Safari",
+                "System.State": "Active",
+                "System.Reason": "Approved",
+                "System.AssignedTo": {"displayName": "Jamie Rivera", "uniqueName": "jamie@contoso.com"},
+                "System.CreatedBy": {"displayName": "Sam Lee"},
+                "System.CreatedDate": "2024-03-01T09:30:00.000Z",
+                "System.AreaPath": "Fabrikam Web\\Auth",
+                "Microsoft.VSTS.Common.Priority": 1,
+                "System.Tags": "safari; login",
+                "Microsoft.VSTS.TCM.ReproSteps": "<ol><li>Open the login page</li><li>Sign in</li></ol>",
+                "System.Description": ""
+            }
+        })
+    }
+
+    #[test]
+    fn test_parse_work_item_url() {
+        let converter = AzureDevOpsConverter::new();
+
+        let resource = converter.parse_work_item_url(URL).unwrap();
+        assert_eq!(resource.organization, "contoso");
+        assert_eq!(resource.project, "Fabrikam%20Web");
+        assert_eq!(resource.id, 42);
+
+        let legacy = converter
+            .parse_work_item_url("https://contoso.visualstudio.com/Fabrikam/_workitems/edit/7/")
+            .unwrap();
+        assert_eq!(legacy.organization, "contoso");
+        assert_eq!(legacy.project, "Fabrikam");
+        assert_eq!(legacy.id, 7);
+
+        assert!(converter
+            .parse_work_item_url("https://dev.azure.com/contoso/Fabrikam/_git/web")
+            .is_err());
+        assert!(converter
+            .parse_work_item_url("https://github.com/contoso/Fabrikam/_workitems/edit/42")
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_convert_work_item_with_paginated_comments() {
+        let mock_server = MockServer::start().await;
+        let project_path = "/contoso/Fabrikam%20Web/_apis/wit";
+        // The token is sent as the basic auth password: base64(":secret-pat")
+        Mock::given(method("GET"))
+            .and(path(format!("{project_path}/workitems/42")))
+            .and(header("Authorization", "Basic OnNlY3JldC1wYXQ="))
+            .respond_with(ResponseTemplate::new(200).set_body_json(work_item_json()))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("{project_path}/workItems/42/comments")))
+            .and(query_param("continuationToken", "page 2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalCount": 2,
+                "comments": [{
+                    "text": "<p>Fixed in <b>build 1.2</b>.</p>",
+                    "createdBy": {"displayName": "Jamie Rivera"},
+                    "createdDate": "2024-03-03T08:00:00Z"
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("{project_path}/workItems/42/comments")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalCount": 2,
+                "continuationToken": "page 2",
+                "comments": [{
+                    "text": "<p>Reproduced on Safari 17.</p>",
+                    "createdBy": {"displayName": "Sam Lee"},
+                    "createdDate": "2024-03-02T10:00:00Z"
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let converter = AzureDevOpsConverter::new_with_token("secret-pat".to_string())
+            .with_api_base_url(mock_server.uri());
+        let markdown = converter.convert(URL).await.unwrap();
+        let content = markdown.as_str();
+
+        assert!(content.contains("# Bug 42: Login fails on Safari"));
+        assert!(content.contains("**State:** Active (Approved)"));
+        assert!(content.contains("**Assigned To:** Jamie Rivera"));
+        assert!(content.contains("**Priority:** 1"));
+        assert!(content.contains("**Tags:** safari, login"));
+        assert!(content.contains("## Repro Steps"));
+        assert!(content.contains("Open the login page"));
+        assert!(!content.contains("## Description"));
+        let first = content.find("Reproduced on Safari 17.").unwrap();
+        let second = content.find("Fixed in").unwrap();
+        assert!(first < second);
+        assert!(content.contains("azure_devops_project: contoso/Fabrikam Web"));
+        assert!(content.contains("azure_devops_comment_count: '2'"));
+    }
+
+    #[tokio::test]
+    async fn test_convert_sign_in_page_requires_token() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(203)
+                    .set_body_string("<html><body>Sign in to your account</body></html>"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let converter = AzureDevOpsConverter::new().with_api_base_url(mock_server.uri());
+        let result = converter.convert(URL).await;
+        assert!(matches!(
+            result,
+            Err(MarkdownError::AuthenticationError {
+                kind: AuthErrorKind::MissingToken,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,285 @@
+//! Cloud object storage converter.
+//!
+//! Objects in Amazon S3 (`s3://bucket/key`), Google Cloud Storage
+//! (`gs://bucket/object`), and Azure Blob Storage (`az://container/blob`) are
+//! read through each store's API with the credentials found in the
+//! environment: the standard `AWS_*`, `GOOGLE_*`, and `AZURE_*` variables,
+//! falling back to instance metadata and workload identities. The object's
+//! content type decides how it is converted, as it does for fetched pages.
+//!
+//! Reading objects needs the `cloud` feature. Without it, cloud storage URIs
+//! are still recognized, but converting one fails with a configuration error.
+//! Pre-signed HTTPS URLs need no credentials and are fetched like any page.
+
+use crate::client::{is_text_media_type, ResponseContent};
+use crate::types::{ContentErrorKind, ErrorContext, Markdown, MarkdownError};
+use async_trait::async_trait;
+use bytes::Bytes;
+use tracing::{debug, info, instrument};
+
+use super::html::HtmlConverter;
+
+/// Media types that are already markdown, or plain text used as markdown.
+const MARKDOWN_TYPES: [&str; 3] = ["text/markdown", "text/x-markdown", "text/plain"];
+
+/// Media types stores report for objects uploaded without a content type.
+const GENERIC_TYPES: [&str; 2] = ["application/octet-stream", "binary/octet-stream"];
+
+/// Guesses the media type of an object from the extension of its name.
+fn media_type_from_extension(url: &str) -> &'static str {
+    let name = url.rsplit('/').next().unwrap_or_default();
+    let extension = name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "md" | "markdown" => "text/markdown",
+        "txt" => "text/plain",
+        "html" | "htm" => "text/html",
+        "xhtml" => "application/xhtml+xml",
+        "pdf" => "application/pdf",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Converter for objects in S3, Google Cloud Storage, and Azure Blob Storage.
+///
+/// Objects are routed by content type:
+/// - markdown and plain text are used unchanged
+/// - HTML, PDF, and Word documents are converted like fetched pages
+///
+/// Objects stored without a content type are routed by their extension.
+#[derive(Debug, Clone)]
+pub struct CloudStorageConverter {
+    html_converter: HtmlConverter,
+}
+
+impl CloudStorageConverter {
+    /// Creates a new cloud storage converter with default conversion settings.
+    pub fn new() -> Self {
+        Self {
+            html_converter: HtmlConverter::new(),
+        }
+    }
+
+    /// Creates a cloud storage converter that converts documents with `html_converter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `html_converter` - The configured converter used for HTML, PDF, and Word objects
+    pub fn with_html_converter(html_converter: HtmlConverter) -> Self {
+        Self { html_converter }
+    }
+
+    /// Converts an object that has already been downloaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The object URI
+    /// * `content_type` - The content type the store reported, if any
+    /// * `bytes` - The object contents
+    async fn convert_object(
+        &self,
+        url: &str,
+        content_type: Option<&str>,
+        bytes: Bytes,
+    ) -> Result<Markdown, MarkdownError> {
+        let media_type = content_type
+            .and_then(|content_type| content_type.split(';').next())
+            .map(|media_type| media_type.trim().to_ascii_lowercase())
+            .filter(|media_type| {
+                !media_type.is_empty() && !GENERIC_TYPES.contains(&media_type.as_str())
+            })
+            .unwrap_or_else(|| media_type_from_extension(url).to_string());
+        info!("Converting {} object ({} bytes)", media_type, bytes.len());
+
+        if MARKDOWN_TYPES.contains(&media_type.as_str()) {
+            debug!("Using object as markdown");
+            let text = String::from_utf8_lossy(&bytes).into_owned();
+            if text.trim().is_empty() {
+                let context = ErrorContext::new(url, "Content validation", "CloudStorageConverter")
+                    .with_info("Object is empty");
+                return Err(MarkdownError::ContentError {
+                    kind: ContentErrorKind::EmptyContent,
+                    context,
+                });
+            }
+            return Markdown::new(text);
+        }
+
+        let content = if is_text_media_type(&media_type) {
+            ResponseContent::Text(String::from_utf8_lossy(&bytes).into_owned())
+        } else {
+            ResponseContent::Binary {
+                content_type: media_type,
+                bytes,
+            }
+        };
+        self.html_converter.convert_content(url, content).await
+    }
+
+    /// Downloads an object, returning its reported content type and contents.
+    #[cfg(feature = "cloud")]
+    async fn fetch(&self, url: &str) -> Result<(Option<String>, Bytes), MarkdownError> {
+        use crate::types::{ConfigErrorKind, ValidationErrorKind};
+        use object_store::aws::AmazonS3Builder;
+        use object_store::azure::MicrosoftAzureBuilder;
+        use object_store::gcp::GoogleCloudStorageBuilder;
+        use object_store::{Attribute, ObjectStore, ObjectStoreScheme};
+
+        let config_error = |info: String| MarkdownError::ConfigurationError {
+            kind: ConfigErrorKind::InvalidConfig,
+            context: ErrorContext::new(url, "Object store setup", "CloudStorageConverter")
+                .with_info(info),
+        };
+
+        let parsed = url::Url::parse(url).map_err(|e| MarkdownError::ValidationError {
+            kind: ValidationErrorKind::InvalidUrl,
+            context: ErrorContext::new(url, "URL parsing", "CloudStorageConverter")
+                .with_info(format!("Parse error: {e}")),
+        })?;
+        let (scheme, path) =
+            ObjectStoreScheme::parse(&parsed).map_err(|e| config_error(e.to_string()))?;
+        let store: Box<dyn ObjectStore> = match scheme {
+            ObjectStoreScheme::AmazonS3 => AmazonS3Builder::from_env()
+                .with_url(url)
+                .build()
+                .map(|store| Box::new(store) as _),
+            ObjectStoreScheme::GoogleCloudStorage => GoogleCloudStorageBuilder::from_env()
+                .with_url(url)
+                .build()
+                .map(|store| Box::new(store) as _),
+            ObjectStoreScheme::MicrosoftAzure => MicrosoftAzureBuilder::from_env()
+                .with_url(url)
+                .build()
+                .map(|store| Box::new(store) as _),
+            other => return Err(config_error(format!("Unsupported object store: {other:?}"))),
+        }
+        .map_err(|e| config_error(e.to_string()))?;
+
+        debug!("Reading object {} from {:?}", path, scheme);
+        let result = store.get(&path).await.map_err(|e| store_error(url, e))?;
+        let content_type = result
+            .attributes
+            .get(&Attribute::ContentType)
+            .map(|value| value.to_string());
+        let bytes = result.bytes().await.map_err(|e| store_error(url, e))?;
+        Ok((content_type, bytes))
+    }
+
+    /// Without the `cloud` feature, objects cannot be read.
+    #[cfg(not(feature = "cloud"))]
+    async fn fetch(&self, url: &str) -> Result<(Option<String>, Bytes), MarkdownError> {
+        Err(crate::features::missing_feature_error(
+            "cloud",
+            url,
+            "Object store setup",
+            "CloudStorageConverter",
+        ))
+    }
+}
+
+/// Maps an object store error to the matching markdowndown error.
+#[cfg(feature = "cloud")]
+fn store_error(url: &str, error: object_store::Error) -> MarkdownError {
+    use crate::types::{AuthErrorKind, NetworkErrorKind};
+
+    let context = ErrorContext::new(url, "Object download", "CloudStorageConverter")
+        .with_info(error.to_string());
+    match error {
+        object_store::Error::NotFound { .. } => MarkdownError::EnhancedNetworkError {
+            kind: NetworkErrorKind::ServerError(404),
+            context,
+        },
+        object_store::Error::PermissionDenied { .. }
+        | object_store::Error::Unauthenticated { .. } => MarkdownError::AuthenticationError {
+            kind: AuthErrorKind::PermissionDenied,
+            context,
+        },
+        _ => MarkdownError::EnhancedNetworkError {
+            kind: NetworkErrorKind::ConnectionFailed,
+            context,
+        },
+    }
+}
+
+impl Default for CloudStorageConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl super::Converter for CloudStorageConverter {
+    /// Downloads an object from cloud storage and converts it to markdown.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The `s3://`, `gs://`, or `az://` URI of the object
+    ///
+    /// # Returns
+    ///
+    /// Returns the converted markdown or an error.
+    #[instrument(skip(self))]
+    async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+        let url = url.trim();
+        let (content_type, bytes) = self.fetch(url).await?;
+        self.convert_object(url, content_type.as_deref(), bytes)
+            .await
+    }
+
+    fn name(&self) -> &'static str {
+        "Cloud Storage Converter"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_convert_object_routes_by_content_type() {
+        let converter = CloudStorageConverter::new();
+
+        let markdown = converter
+            .convert_object(
+                "s3://notes/todo",
+                Some("text/markdown; charset=utf-8"),
+                Bytes::from_static(b"# Todo\n\n- Ship it"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(markdown.as_str(), "# Todo\n\n- Ship it");
+
+        // Uploaded without a content type, so the extension decides
+        let html = converter
+            .convert_object(
+                "gs://site/index.html",
+                Some("application/octet-stream"),
+                Bytes::from_static(b"<h1>Stored</h1><p>Served from a bucket.</p>"),
+            )
+            .await
+            .unwrap();
+        assert!(html.as_str().contains("# Stored"));
+        assert!(html.as_str().contains("Served from a bucket."));
+    }
+
+    #[cfg(not(feature = "cloud"))]
+    #[tokio::test]
+    async fn test_convert_requires_cloud_feature() {
+        use crate::converters::converter::Converter;
+        use crate::types::ConfigErrorKind;
+
+        let result = CloudStorageConverter::new()
+            .convert("s3://reports/q1.pdf")
+            .await;
+        match result {
+            Err(MarkdownError::ConfigurationError {
+                kind: ConfigErrorKind::MissingDependency,
+                context,
+            }) => assert!(context.additional_info.unwrap().contains("--features cloud")),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+}
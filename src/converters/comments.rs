@@ -0,0 +1,289 @@
+//! Comment preservation for exported documents.
+//! Google Docs HTML exports mark each comment with a `[a]` reference anchor in
+//! the body and list the comment text at the end of the document; this module
+//! lifts those comments out of the HTML and re-attaches them to the markdown.
+//! Word documents are read by the DOCX parser, which places the same markers.
+
+use crate::config::CommentMode;
+use regex::Regex;
+
+/// A comment attached to a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentComment {
+    /// Sequential comment number, in document order starting at 1
+    pub number: usize,
+    /// The plain text of the comment and any replies
+    pub text: String,
+}
+
+/// Extracts comments from Google Docs HTML exports and renders them as markdown.
+pub struct CommentExtractor {
+    reference: Regex,
+    comment_start: Regex,
+    div_tag: Regex,
+    tags: Regex,
+}
+
+/// A comment's `<div>` in an exported document.
+struct CommentBlock<'a> {
+    /// Byte range of the whole `<div>`, including its closing tag
+    range: std::ops::Range<usize>,
+    /// The anchor id the body references the comment with
+    id: &'a str,
+    /// The HTML after the comment's back-reference anchor
+    body: &'a str,
+}
+
+impl CommentExtractor {
+    /// Creates a new comment extractor.
+    pub fn new() -> Self {
+        Self {
+            reference: Regex::new(
+                r##"(?s)(?:<sup>\s*)?<a[^>]*href="#cmnt(\d+)"[^>]*>\[[^\]]*\]</a>(?:\s*</sup>)?"##,
+            )
+            .expect("valid comment reference pattern"),
+            comment_start: Regex::new(
+                r##"(?s)<div[^>]*>\s*<p[^>]*>\s*<a[^>]*href="#cmnt_ref(\d+)"[^>]*>\[[^\]]*\]</a>"##,
+            )
+            .expect("valid comment start pattern"),
+            div_tag: Regex::new(r"(?i)<(/?)div\b[^>]*>").expect("valid div pattern"),
+            tags: Regex::new(r"<[^>]+>").expect("valid tag pattern"),
+        }
+    }
+
+    /// Lifts comments out of exported HTML.
+    ///
+    /// Comment references in the body are replaced with plain-text markers
+    /// that survive HTML conversion, and the comment list is removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `html` - The exported HTML document
+    ///
+    /// # Returns
+    ///
+    /// The HTML with markers in place of references, and the referenced comments.
+    pub fn extract(&self, html: &str) -> (String, Vec<DocumentComment>) {
+        let blocks = self.comment_blocks(html);
+        let mut bodies = std::collections::HashMap::new();
+        let mut without_bodies = String::with_capacity(html.len());
+        let mut last = 0;
+        for block in &blocks {
+            let text = self.tags.replace_all(block.body, " ");
+            let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            bodies.insert(block.id.to_string(), text);
+            without_bodies.push_str(&html[last..block.range.start]);
+            last = block.range.end;
+        }
+        without_bodies.push_str(&html[last..]);
+
+        let mut comments = Vec::new();
+        let marked = self.reference.replace_all(&without_bodies, |captures: &regex::Captures| {
+            match bodies.remove(&captures[1]) {
+                Some(text) => {
+                    comments.push(DocumentComment {
+                        number: comments.len() + 1,
+                        text,
+                    });
+                    marker(comments.len())
+                }
+                None => String::new(),
+            }
+        });
+
+        (marked.into_owned(), comments)
+    }
+
+    /// Finds the comment `<div>`s, matching each to its own closing tag so
+    /// replies nested in further `<div>`s stay part of the comment.
+    fn comment_blocks<'a>(&self, html: &'a str) -> Vec<CommentBlock<'a>> {
+        let mut blocks = Vec::new();
+        let mut from = 0;
+        while let Some(start) = self.comment_start.captures_at(html, from) {
+            let (opening, id) = (start.get(0).expect("whole match"), start.get(1));
+            let mut depth = 1usize;
+            let mut tags = self.div_tag.captures_iter(&html[opening.end()..]);
+            let closing = tags.find(|tag| {
+                if tag[1].is_empty() {
+                    depth += 1;
+                } else {
+                    depth -= 1;
+                }
+                depth == 0
+            });
+            let (Some(closing), Some(id)) = (closing, id) else {
+                break;
+            };
+            let closing = closing.get(0).expect("whole match");
+            let end = opening.end() + closing.end();
+            blocks.push(CommentBlock {
+                range: opening.start()..end,
+                id: id.as_str(),
+                body: &html[opening.end()..opening.end() + closing.start()],
+            });
+            from = end;
+        }
+        blocks
+    }
+
+    /// Replaces comment markers in converted markdown with rendered comments.
+    ///
+    /// # Arguments
+    ///
+    /// * `markdown` - Markdown converted from the marked HTML
+    /// * `comments` - The comments returned by [`CommentExtractor::extract`]
+    /// * `mode` - How comments should be rendered
+    pub fn render(&self, markdown: &str, comments: &[DocumentComment], mode: CommentMode) -> String {
+        match mode {
+            CommentMode::Omit => comments
+                .iter()
+                .fold(markdown.to_string(), |text, comment| {
+                    text.replace(&marker(comment.number), "")
+                }),
+            CommentMode::Footnotes => {
+                let mut rendered = comments.iter().fold(markdown.to_string(), |text, comment| {
+                    text.replace(&marker(comment.number), &format!("[^c{}]", comment.number))
+                });
+                if !comments.is_empty() {
+                    rendered = rendered.trim_end().to_string();
+                    rendered.push('\n');
+                    for comment in comments {
+                        rendered.push_str(&format!("\n[^c{}]: {}", comment.number, comment.text));
+                    }
+                    rendered.push('\n');
+                }
+                rendered
+            }
+            CommentMode::Blockquotes => self.render_blockquotes(markdown, comments),
+        }
+    }
+
+    /// Places each comment in a blockquote after the paragraph that references it.
+    fn render_blockquotes(&self, markdown: &str, comments: &[DocumentComment]) -> String {
+        let mut output: Vec<String> = Vec::new();
+        let mut pending: Vec<&DocumentComment> = Vec::new();
+
+        let flush = |output: &mut Vec<String>, pending: &mut Vec<&DocumentComment>| {
+            for comment in pending.drain(..) {
+                output.push(String::new());
+                output.push(format!("> **Comment:** {}", comment.text));
+            }
+        };
+
+        for line in markdown.lines() {
+            if line.trim().is_empty() {
+                flush(&mut output, &mut pending);
+                output.push(line.to_string());
+                continue;
+            }
+
+            let mut text = line.to_string();
+            for comment in comments {
+                let comment_marker = marker(comment.number);
+                if text.contains(&comment_marker) {
+                    text = text.replace(&comment_marker, "");
+                    pending.push(comment);
+                }
+            }
+            output.push(text.trim_end().to_string());
+        }
+        flush(&mut output, &mut pending);
+
+        output.join("\n")
+    }
+}
+
+impl Default for CommentExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the plain-text marker for a comment reference.
+pub(crate) fn marker(number: usize) -> String {
+    format!("@@comment{number}@@")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HTML: &str = r##"<html><body>
+<p><span>The term is five years.</span><sup><a href="#cmnt1" id="cmnt_ref1">[a]</a></sup></p>
+<p><span>Payment is monthly.</span><sup><a href="#cmnt2" id="cmnt_ref2">[b]</a></sup></p>
+<div style="border:1px"><p><a href="#cmnt_ref1" id="cmnt1">[a]</a><span>Should this be three?</span></p><p><span>Agreed.</span></p></div>
+<div style="border:1px"><p><a href="#cmnt_ref2" id="cmnt2">[b]</a><span>Confirm with finance</span></p></div>
+</body></html>"##;
+
+    #[test]
+    fn test_extract_comments() {
+        let extractor = CommentExtractor::new();
+        let (marked, comments) = extractor.extract(HTML);
+
+        assert_eq!(
+            comments,
+            vec![
+                DocumentComment {
+                    number: 1,
+                    text: "Should this be three? Agreed.".to_string()
+                },
+                DocumentComment {
+                    number: 2,
+                    text: "Confirm with finance".to_string()
+                },
+            ]
+        );
+        assert!(marked.contains("five years.</span>@@comment1@@"));
+        assert!(!marked.contains("cmnt_ref"));
+        assert!(!marked.contains("Confirm with finance"));
+    }
+
+    #[test]
+    fn test_extract_keeps_nested_replies() {
+        let html = r##"<p>Clause.<sup><a href="#cmnt1" id="cmnt_ref1">[a]</a></sup></p>
+<div><p><a href="#cmnt_ref1" id="cmnt1">[a]</a><span>Too broad.</span></p><div><p><span>Narrowed.</span></p></div><p><span>Resolved.</span></p></div>
+<div><p>Footer after the comments</p></div>"##;
+        let (marked, comments) = CommentExtractor::new().extract(html);
+
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].text, "Too broad. Narrowed. Resolved.");
+        assert!(!marked.contains("Resolved."));
+        assert!(marked.contains("Footer after the comments"));
+    }
+
+    #[test]
+    fn test_render_footnotes() {
+        let extractor = CommentExtractor::new();
+        let comments = vec![DocumentComment {
+            number: 1,
+            text: "Check this".to_string(),
+        }];
+
+        assert_eq!(
+            extractor.render("Claim.@@comment1@@\n\nMore.\n", &comments, CommentMode::Footnotes),
+            "Claim.[^c1]\n\nMore.\n\n[^c1]: Check this\n"
+        );
+        assert_eq!(
+            extractor.render("Claim.@@comment1@@", &comments, CommentMode::Omit),
+            "Claim."
+        );
+    }
+
+    #[test]
+    fn test_render_blockquotes() {
+        let extractor = CommentExtractor::new();
+        let comments = vec![DocumentComment {
+            number: 1,
+            text: "Check this".to_string(),
+        }];
+
+        assert_eq!(
+            extractor.render(
+                "Claim.@@comment1@@\ncontinued\n\nMore.",
+                &comments,
+                CommentMode::Blockquotes
+            ),
+            "Claim.\ncontinued\n\n> **Comment:** Check this\n\nMore."
+        );
+    }
+}
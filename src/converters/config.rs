@@ -15,6 +15,103 @@ pub struct HtmlConverterConfig {
     pub remove_ads: bool,
     /// Maximum consecutive blank lines allowed
     pub max_blank_lines: usize,
+    /// Whether to reject pages that look like "not found" templates (off by default)
+    pub detect_soft_404: bool,
+    /// Whether to reject pages that show a paywall or login wall instead of
+    /// their content
+    pub detect_access_wall: bool,
+    /// Whether access wall detection also rejects short pages that are little
+    /// more than a sign-in form (off by default)
+    pub detect_login_walls: bool,
+    /// How markdown special characters in page text are escaped
+    pub escape_mode: EscapeMode,
+    /// Whether to keep unconvertible elements (complex tables, forms, custom
+    /// elements) as sanitized raw HTML instead of flattening them
+    pub passthrough_unconvertible: bool,
+    /// Whether to replace forms and controls with a summary of their fields
+    /// instead of dropping them
+    pub summarize_forms: bool,
+    /// How responses to URLs without a specialized converter are routed
+    pub detection_strategy: DetectionStrategy,
+    /// Which structures are recognized in plain text responses
+    pub plain_text: PlainTextHeuristics,
+}
+
+/// How the responses of URLs without a specialized converter are routed.
+///
+/// URL detection only sees the URL's shape, so a URL with no recognizable
+/// pattern goes to the HTML converter, which receives whatever the server
+/// returns. PDF and Word documents are always recognized by their contents,
+/// JSON and XML responses are always shown as code blocks, and plain text
+/// responses are always given structure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum DetectionStrategy {
+    /// Convert every other text response as HTML
+    #[default]
+    Url,
+    /// Route text responses by their `Content-Type`: markdown is kept and
+    /// normalized, and HTML and unknown types are converted as HTML
+    ContentTypeFallback,
+}
+
+/// Which structures are recognized when converting plain text.
+///
+/// Plain text has no markup, so its structure is guessed from how the
+/// lines are laid out. Turning every heuristic off keeps the text as it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlainTextHeuristics {
+    /// Whether lines underlined with `=`, `-`, or `~` become headings
+    pub underlined_headings: bool,
+    /// Whether short numbered lines on their own, such as `2.1 Scope`,
+    /// become headings
+    pub numbered_sections: bool,
+    /// Whether lines starting with `•`, `*`, `-`, `o`, or a similar bullet
+    /// become list items
+    pub bullets: bool,
+    /// Whether formatted man pages are recognized, so their section names
+    /// become headings and their indented body is kept as prose
+    pub man_pages: bool,
+}
+
+impl PlainTextHeuristics {
+    /// Heuristics with everything turned off, keeping text unchanged.
+    pub fn none() -> Self {
+        Self {
+            underlined_headings: false,
+            numbered_sections: false,
+            bullets: false,
+            man_pages: false,
+        }
+    }
+}
+
+impl Default for PlainTextHeuristics {
+    fn default() -> Self {
+        Self {
+            underlined_headings: true,
+            numbered_sections: true,
+            bullets: true,
+            man_pages: true,
+        }
+    }
+}
+
+/// How markdown special characters in converted text are escaped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum EscapeMode {
+    /// Leave text unchanged
+    Off,
+    /// Escape only characters that would otherwise change the formatting:
+    /// emphasis markers outside words, backticks, tag-like `<`, and pipes
+    /// in table cells
+    #[default]
+    Minimal,
+    /// Escape every markdown special character
+    All,
+    /// Like `Minimal`, but leave `<` unescaped so HTML in the text is kept as raw HTML
+    PreserveHtml,
 }
 
 impl Default for HtmlConverterConfig {
@@ -26,6 +123,14 @@ impl Default for HtmlConverterConfig {
             remove_sidebars: true,
             remove_ads: true,
             max_blank_lines: 2,
+            detect_soft_404: false,
+            detect_access_wall: true,
+            detect_login_walls: false,
+            escape_mode: EscapeMode::Minimal,
+            passthrough_unconvertible: false,
+            summarize_forms: false,
+            detection_strategy: DetectionStrategy::Url,
+            plain_text: PlainTextHeuristics::default(),
         }
     }
 }
@@ -43,5 +148,14 @@ mod tests {
         assert!(config.remove_sidebars);
         assert!(config.remove_ads);
         assert_eq!(config.max_blank_lines, 2);
+        assert!(!config.detect_soft_404);
+        assert!(config.detect_access_wall);
+        assert!(!config.detect_login_walls);
+        assert_eq!(config.escape_mode, EscapeMode::Minimal);
+        assert!(!config.passthrough_unconvertible);
+        assert!(!config.summarize_forms);
+        assert_eq!(config.detection_strategy, DetectionStrategy::Url);
+        assert_eq!(config.plain_text, PlainTextHeuristics::default());
+        assert!(config.plain_text.man_pages);
     }
 }
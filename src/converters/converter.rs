@@ -4,8 +4,12 @@
 //! must implement, and the `ConverterRegistry` that manages converter routing based
 //! on URL type detection.
 
-use crate::types::{Markdown, MarkdownError, UrlType};
+use crate::types::{Markdown, MarkdownError, SubDocument, UrlType};
 use async_trait::async_trait;
+use futures::stream::{self, BoxStream, StreamExt};
+
+/// A stream of markdown chunks that together form one converted document.
+pub type MarkdownStream<'a> = BoxStream<'a, Result<String, MarkdownError>>;
 
 /// Trait for converting URLs to markdown.
 ///
@@ -24,6 +28,78 @@ pub trait Converter: Send + Sync {
     /// Returns the converted markdown content or an error.
     async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError>;
 
+    /// Extracts only the YAML frontmatter for a URL.
+    ///
+    /// Converters that can read metadata without converting the full body
+    /// should override this. The default converts the document and discards
+    /// everything after the frontmatter.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to fetch metadata for
+    ///
+    /// # Returns
+    ///
+    /// Returns markdown containing only the frontmatter block, or an error.
+    async fn convert_frontmatter(&self, url: &str) -> Result<Markdown, MarkdownError> {
+        let markdown = self.convert(url).await?;
+        Ok(Markdown::from(markdown.frontmatter().unwrap_or_default()))
+    }
+
+    /// Converts content from a URL to markdown, yielding it in chunks.
+    ///
+    /// Converters that can convert a document incrementally should override
+    /// this so large documents are never held in memory whole. The default
+    /// converts the document and yields it as a single chunk.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to fetch and convert
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream whose chunks concatenate to the markdown document, or an error.
+    async fn convert_stream(&self, url: &str) -> Result<MarkdownStream<'_>, MarkdownError> {
+        let markdown = self.convert(url).await?;
+        Ok(stream::once(async move { Ok(String::from(markdown)) }).boxed())
+    }
+
+    /// Converts content from a URL to markdown, one document per part.
+    ///
+    /// Converters for sources made of several logical parts, such as the
+    /// chapters of a PDF, should override this. The default converts the
+    /// document as a single part.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to fetch and convert
+    ///
+    /// # Returns
+    ///
+    /// Returns the parts in document order, or an error.
+    async fn convert_parts(&self, url: &str) -> Result<Vec<SubDocument>, MarkdownError> {
+        let markdown = self.convert(url).await?;
+        Ok(vec![SubDocument {
+            index: 1,
+            title: None,
+            markdown,
+        }])
+    }
+
+    /// Estimates how many HTTP requests converting a URL makes.
+    ///
+    /// This is used to plan conversions without fetching anything, and counts
+    /// the requests of a conversion that succeeds first time. Converters that
+    /// make more than one request, or none, should override this. The default
+    /// is a single request.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL that would be converted
+    fn estimated_requests(&self, _url: &str) -> usize {
+        1
+    }
+
     /// Returns the human-readable name of this converter.
     fn name(&self) -> &'static str;
 }
@@ -60,10 +136,33 @@ impl ConverterRegistry {
             UrlType::GitHubIssue,
             Box::new(super::GitHubConverter::from_env()),
         );
+        registry.register(
+            UrlType::AzureDevOps,
+            Box::new(super::AzureDevOpsConverter::from_env()),
+        );
+        registry.register(
+            UrlType::Office365,
+            Box::new(super::Office365Converter::new()),
+        );
+        registry.register(UrlType::Dropbox, Box::new(super::DropboxConverter::new()));
         registry.register(
             UrlType::LocalFile,
             Box::new(super::local::LocalFileConverter::new()),
         );
+        registry.register(UrlType::DataUri, Box::new(super::DataUriConverter::new()));
+        registry.register(
+            UrlType::CloudStorage,
+            Box::new(super::CloudStorageConverter::new()),
+        );
+        registry.register(UrlType::Ipfs, Box::new(super::IpfsConverter::new()));
+        registry.register(UrlType::Pdf, Box::new(super::PdfConverter::new()));
+        registry.register(UrlType::Notebook, Box::new(super::NotebookConverter::new()));
+        registry.register(UrlType::Markup, Box::new(super::MarkupConverter::new()));
+        registry.register(UrlType::Email, Box::new(super::EmailConverter::new()));
+        registry.register(
+            UrlType::Markdown,
+            Box::new(super::MarkdownSourceConverter::new()),
+        );
 
         registry
     }
@@ -79,25 +178,109 @@ impl ConverterRegistry {
         };
 
         // Register configured converters
+        let notebook_converter = super::NotebookConverter::with_config(
+            http_client.clone(),
+            html_config.clone(),
+            output_config.clone(),
+        );
+        let markup_converter =
+            super::MarkupConverter::with_config(http_client.clone(), output_config.clone());
+        let html_converter = super::HtmlConverter::with_config(
+            http_client.clone(),
+            html_config,
+            output_config.clone(),
+        );
+        registry.register(UrlType::Html, Box::new(html_converter.clone()));
         registry.register(
-            UrlType::Html,
-            Box::new(super::HtmlConverter::with_config(
+            UrlType::GoogleDocs,
+            // The configured client carries the Google API token for Drive lookups
+            Box::new(
+                super::GoogleDocsConverter::with_client(http_client.clone())
+                .with_comment_mode(output_config.comment_mode)
+                .with_export_format(output_config.google_docs_export_format),
+            ),
+        );
+        let mut github_converter = super::GitHubConverter::from_env()
+            .with_stats(http_client.stats().clone())
+            .with_request_templates(http_client.request_templates().to_vec());
+        if let Some(base_url) = http_client.github_api_base_url() {
+            github_converter = github_converter.with_api_base_url(base_url);
+        }
+        if let Some(max) = output_config.max_comments {
+            github_converter = github_converter.with_max_comments(max);
+        }
+        registry.register(UrlType::GitHubIssue, Box::new(github_converter));
+        let mut azure_devops_converter =
+            super::AzureDevOpsConverter::with_config(http_client.clone(), html_converter.clone());
+        if let Some(max) = output_config.max_comments {
+            azure_devops_converter = azure_devops_converter.with_max_comments(max);
+        }
+        registry.register(UrlType::AzureDevOps, Box::new(azure_devops_converter));
+        registry.register(
+            UrlType::Office365,
+            Box::new(super::Office365Converter::with_config(
                 http_client.clone(),
-                html_config,
-                output_config.clone(),
+                html_converter.clone(),
             )),
         );
         registry.register(
-            UrlType::GoogleDocs,
-            Box::new(super::GoogleDocsConverter::new()), // GoogleDocs converter manages its own HttpClient
+            UrlType::Dropbox,
+            Box::new(super::DropboxConverter::with_config(
+                http_client.clone(),
+                html_converter.clone(),
+            )),
         );
-        registry.register(
-            UrlType::GitHubIssue,
-            Box::new(super::GitHubConverter::from_env()),
+        let email_converter = super::EmailConverter::with_config(
+            http_client.clone(),
+            html_converter.clone(),
+            output_config.clone(),
         );
+        // Local files, inline HTML, stored objects, and IPFS content are
+        // converted with the same settings as fetched pages
         registry.register(
             UrlType::LocalFile,
-            Box::new(super::local::LocalFileConverter::new()),
+            Box::new(
+                super::local::LocalFileConverter::with_html_converter(html_converter.clone())
+                    .with_notebook_converter(notebook_converter.clone())
+                    .with_markup_converter(markup_converter.clone())
+                    .with_email_converter(email_converter.clone()),
+            ),
+        );
+        registry.register(
+            UrlType::DataUri,
+            Box::new(super::DataUriConverter::with_html_converter(
+                html_converter.clone(),
+            )),
+        );
+        registry.register(
+            UrlType::CloudStorage,
+            Box::new(super::CloudStorageConverter::with_html_converter(
+                html_converter.clone(),
+            )),
+        );
+        registry.register(
+            UrlType::Ipfs,
+            Box::new(super::IpfsConverter::with_config(
+                http_client.clone(),
+                html_converter,
+            )),
+        );
+        registry.register(
+            UrlType::Pdf,
+            Box::new(super::PdfConverter::with_config(
+                http_client.clone(),
+                output_config.clone(),
+            )),
+        );
+        registry.register(UrlType::Notebook, Box::new(notebook_converter));
+        registry.register(UrlType::Markup, Box::new(markup_converter));
+        registry.register(UrlType::Email, Box::new(email_converter));
+        registry.register(
+            UrlType::Markdown,
+            Box::new(super::MarkdownSourceConverter::with_config(
+                http_client.clone(),
+                output_config.clone(),
+            )),
         );
 
         registry
@@ -105,12 +288,39 @@ impl ConverterRegistry {
 
     /// Registers a converter for a specific URL type.
     ///
+    /// Registering a second converter for the same URL type replaces the
+    /// first, so built-in converters can be overridden.
+    ///
     /// # Arguments
     ///
     /// * `url_type` - The URL type this converter handles
     /// * `converter` - The converter implementation
-    pub fn register(&mut self, url_type: UrlType, converter: Box<dyn Converter>) {
-        self.converters.insert(url_type, converter);
+    ///
+    /// # Returns
+    ///
+    /// The converter previously registered for the URL type, if any.
+    pub fn register(
+        &mut self,
+        url_type: UrlType,
+        converter: Box<dyn Converter>,
+    ) -> Option<Box<dyn Converter>> {
+        self.converters.insert(url_type, converter)
+    }
+
+    /// Removes the converter for a URL type.
+    ///
+    /// URLs of that type then fail with a configuration error, or fall back
+    /// to the HTML converter where fallback applies.
+    ///
+    /// # Arguments
+    ///
+    /// * `url_type` - The URL type to stop handling
+    ///
+    /// # Returns
+    ///
+    /// The removed converter, if one was registered.
+    pub fn unregister(&mut self, url_type: &UrlType) -> Option<Box<dyn Converter>> {
+        self.converters.remove(url_type)
     }
 
     /// Gets a converter for the specified URL type.
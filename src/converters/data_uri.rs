@@ -0,0 +1,218 @@
+//! Data URI converter for documents passed inline.
+//!
+//! Small documents can be handed over as `data:` URIs instead of being
+//! written to a file or served over HTTP. The payload is decoded (percent- or
+//! base64-encoded) and routed by its media type: HTML goes through the HTML
+//! conversion pipeline, while markdown and plain text are used as they are.
+
+use crate::types::{ContentErrorKind, ErrorContext, Markdown, MarkdownError, ValidationErrorKind};
+use async_trait::async_trait;
+use data_url::DataUrl;
+use tracing::{debug, info, instrument};
+
+use super::html::HtmlConverter;
+
+/// Media types converted from HTML.
+const HTML_TYPES: [&str; 2] = ["text/html", "application/xhtml+xml"];
+
+/// Media types that are already markdown, or plain text used as markdown.
+const MARKDOWN_TYPES: [&str; 3] = ["text/markdown", "text/x-markdown", "text/plain"];
+
+/// Converter for documents embedded in `data:` URIs.
+///
+/// Supported media types:
+/// - `text/html` and `application/xhtml+xml`, converted like fetched pages
+/// - `text/markdown`, `text/x-markdown` and `text/plain`, used unchanged
+///
+/// A URI without a media type is `text/plain`, as the data URI scheme defines.
+#[derive(Debug, Clone)]
+pub struct DataUriConverter {
+    html_converter: HtmlConverter,
+}
+
+impl DataUriConverter {
+    /// Creates a new data URI converter with default HTML conversion settings.
+    pub fn new() -> Self {
+        Self {
+            html_converter: HtmlConverter::new(),
+        }
+    }
+
+    /// Creates a data URI converter that converts HTML payloads with `html_converter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `html_converter` - The configured converter used for HTML payloads
+    pub fn with_html_converter(html_converter: HtmlConverter) -> Self {
+        Self { html_converter }
+    }
+
+    /// Decodes a data URI into its media type and text payload.
+    ///
+    /// Fails for media types this converter does not handle.
+    fn decode(&self, url: &str) -> Result<(String, String), MarkdownError> {
+        let data_url = DataUrl::process(url.trim()).map_err(|e| {
+            let context = ErrorContext::new(url, "Data URI parsing", "DataUriConverter")
+                .with_info(format!("Invalid data URI: {e:?}"));
+            MarkdownError::ValidationError {
+                kind: ValidationErrorKind::InvalidUrl,
+                context,
+            }
+        })?;
+
+        let mime_type = data_url.mime_type();
+        let essence = format!("{}/{}", mime_type.type_, mime_type.subtype);
+        if !HTML_TYPES.contains(&essence.as_str()) && !MARKDOWN_TYPES.contains(&essence.as_str()) {
+            let context = ErrorContext::new(url, "Content validation", "DataUriConverter")
+                .with_info(format!("Unsupported media type: {essence}"));
+            return Err(MarkdownError::ContentError {
+                kind: ContentErrorKind::UnsupportedFormat,
+                context,
+            });
+        }
+        let charset = mime_type
+            .get_parameter("charset")
+            .unwrap_or("utf-8")
+            .to_ascii_lowercase();
+        if !matches!(charset.as_str(), "utf-8" | "utf8" | "us-ascii") {
+            let context = ErrorContext::new(url, "Data URI decoding", "DataUriConverter")
+                .with_info(format!("Unsupported charset: {charset}"));
+            return Err(MarkdownError::ContentError {
+                kind: ContentErrorKind::UnsupportedFormat,
+                context,
+            });
+        }
+
+        let (bytes, _fragment) = data_url.decode_to_vec().map_err(|e| {
+            let context = ErrorContext::new(url, "Data URI decoding", "DataUriConverter")
+                .with_info(format!("Invalid base64 payload: {e:?}"));
+            MarkdownError::ContentError {
+                kind: ContentErrorKind::ParsingFailed,
+                context,
+            }
+        })?;
+        let text = String::from_utf8(bytes).map_err(|e| {
+            let context = ErrorContext::new(url, "Data URI decoding", "DataUriConverter")
+                .with_info(format!("Payload is not valid UTF-8: {e}"));
+            MarkdownError::ContentError {
+                kind: ContentErrorKind::ParsingFailed,
+                context,
+            }
+        })?;
+
+        Ok((essence, text))
+    }
+}
+
+impl Default for DataUriConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl super::Converter for DataUriConverter {
+    /// Converts a data URI to markdown.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The `data:` URI holding the document
+    ///
+    /// # Returns
+    ///
+    /// Returns the converted markdown or an error.
+    #[instrument(skip(self, url))]
+    async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+        let (media_type, text) = self.decode(url)?;
+        info!(
+            "Converting inline {} document ({} bytes)",
+            media_type,
+            text.len()
+        );
+
+        if text.trim().is_empty() {
+            let context = ErrorContext::new(url, "Content validation", "DataUriConverter")
+                .with_info("Data URI payload is empty");
+            return Err(MarkdownError::ContentError {
+                kind: ContentErrorKind::EmptyContent,
+                context,
+            });
+        }
+
+        if HTML_TYPES.contains(&media_type.as_str()) {
+            debug!("Routing data URI to HTML conversion");
+            self.html_converter.convert_document(&text, Some(url))
+        } else {
+            debug!("Using data URI payload as markdown");
+            Markdown::new(text)
+        }
+    }
+
+    /// The document is carried in the URI itself.
+    fn estimated_requests(&self, _url: &str) -> usize {
+        0
+    }
+
+    fn name(&self) -> &'static str {
+        "Data URI Converter"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converters::converter::Converter;
+
+    #[tokio::test]
+    async fn test_convert_markdown_and_html_payloads() {
+        let converter = DataUriConverter::new();
+
+        let markdown = converter
+            .convert("data:text/markdown,%23%20Inline%0A%0ASent%20by%20message.")
+            .await
+            .unwrap();
+        assert_eq!(markdown.as_str(), "# Inline\n\nSent by message.");
+
+        // "<h1>Encoded</h1><p>Base64 body</p>"
+        let html = converter
+            .convert("data:text/html;base64,PGgxPkVuY29kZWQ8L2gxPjxwPkJhc2U2NCBib2R5PC9wPg==")
+            .await
+            .unwrap();
+        assert!(html.as_str().contains("# Encoded"));
+        assert!(html.as_str().contains("Base64 body"));
+    }
+
+    #[tokio::test]
+    async fn test_convert_rejects_unsupported_payloads() {
+        let converter = DataUriConverter::new();
+
+        let image = converter
+            .convert("data:image/png;base64,iVBORw0KGgo=")
+            .await;
+        assert!(matches!(
+            image,
+            Err(MarkdownError::ContentError {
+                kind: ContentErrorKind::UnsupportedFormat,
+                ..
+            })
+        ));
+
+        let bad_base64 = converter.convert("data:text/plain;base64,@@@").await;
+        assert!(matches!(
+            bad_base64,
+            Err(MarkdownError::ContentError {
+                kind: ContentErrorKind::ParsingFailed,
+                ..
+            })
+        ));
+
+        let empty = converter.convert("data:text/plain,").await;
+        assert!(matches!(
+            empty,
+            Err(MarkdownError::ContentError {
+                kind: ContentErrorKind::EmptyContent,
+                ..
+            })
+        ));
+    }
+}
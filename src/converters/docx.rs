@@ -0,0 +1,914 @@
+//! DOCX (Office Open XML) to markdown conversion.
+//!
+//! Office 365 and SharePoint links often download a Word document rather
+//! than a web page. A DOCX file is a zip archive whose text lives in
+//! `word/document.xml`. The built-in parser reads headings, paragraphs,
+//! bulleted and numbered lists, tables, bold and italic runs, and hyperlinks,
+//! renders them as HTML, and converts that HTML like any other page.
+//! Comments from `word/comments.xml` are rendered per the configured
+//! [`CommentMode`], and tracked insertions and deletions are accepted,
+//! rejected, or preserved per the [`TrackedChangeMode`].
+//!
+//! With the `pandoc` feature enabled, documents are converted by running
+//! `pandoc` instead, falling back to the built-in parser if `pandoc` is not
+//! installed. Documents whose comments are rendered, or whose tracked changes
+//! are preserved, always use the built-in parser.
+
+use crate::client::HttpClient;
+use crate::config::{CommentMode, TrackedChangeMode};
+use crate::frontmatter::FrontmatterBuilder;
+use crate::types::{ContentErrorKind, ErrorContext, Markdown, MarkdownError};
+use async_trait::async_trait;
+use chrono::Utc;
+use regex::Regex;
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+use zip::result::ZipError;
+use zip::ZipArchive;
+
+use super::comments::{marker, CommentExtractor, DocumentComment};
+use super::config::HtmlConverterConfig;
+use super::converter::Converter;
+use super::html::HtmlConverter;
+
+/// Path of the main document part inside the archive.
+const DOCUMENT_PART: &str = "word/document.xml";
+
+/// Path of the comments part inside the archive.
+const COMMENTS_PART: &str = "word/comments.xml";
+
+/// Plain-text markers placed around preserved insertions and deletions; they
+/// survive HTML conversion and are replaced with CriticMarkup afterwards.
+const INSERT_START: &str = "@@insert@@";
+const INSERT_END: &str = "@@endinsert@@";
+const DELETE_START: &str = "@@delete@@";
+const DELETE_END: &str = "@@enddelete@@";
+
+/// Largest decompressed archive entry read, to guard against zip bombs.
+const MAX_ENTRY_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Signature of a zip local file header.
+const LOCAL_HEADER_SIGNATURE: &[u8] = b"PK\x03\x04";
+
+/// An opened DOCX archive.
+type Archive<'a> = ZipArchive<Cursor<&'a [u8]>>;
+
+/// Returns true if the bytes are a zip archive containing a Word document.
+pub fn is_docx(bytes: &[u8]) -> bool {
+    bytes.starts_with(LOCAL_HEADER_SIGNATURE)
+        && ZipArchive::new(Cursor::new(bytes))
+            .is_ok_and(|archive| archive.index_for_name(DOCUMENT_PART).is_some())
+}
+
+/// DOCX to markdown converter.
+#[derive(Debug, Clone)]
+pub struct DocxConverter {
+    client: HttpClient,
+    html_config: HtmlConverterConfig,
+    output_config: crate::config::OutputConfig,
+}
+
+impl DocxConverter {
+    /// Creates a new DOCX converter with default configuration.
+    pub fn new() -> Self {
+        Self {
+            client: HttpClient::new(),
+            html_config: HtmlConverterConfig::default(),
+            output_config: crate::config::OutputConfig::default(),
+        }
+    }
+
+    /// Creates a new DOCX converter with custom configuration and HTTP client.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Configured HTTP client to use for requests
+    /// * `html_config` - Options for converting the rendered document HTML
+    /// * `output_config` - Output configuration including custom frontmatter fields
+    pub fn with_config(
+        client: HttpClient,
+        html_config: HtmlConverterConfig,
+        output_config: crate::config::OutputConfig,
+    ) -> Self {
+        Self {
+            client,
+            html_config,
+            output_config,
+        }
+    }
+
+    /// Converts DOCX bytes that have already been downloaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL the document was fetched from
+    /// * `bytes` - The DOCX file contents
+    ///
+    /// # Returns
+    ///
+    /// Returns the converted markdown on success, or a `MarkdownError` on failure.
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ContentError` - If the bytes are not a Word document
+    ///   (`UnsupportedFormat`), cannot be read (`ParsingFailed`), or have no
+    ///   text (`EmptyContent`)
+    pub async fn convert_docx(&self, url: &str, bytes: &[u8]) -> Result<Markdown, MarkdownError> {
+        let content_error = |kind, info: String| MarkdownError::ContentError {
+            kind,
+            context: ErrorContext::new(url, "DOCX conversion", self.name()).with_info(info),
+        };
+
+        if !is_docx(bytes) {
+            return Err(content_error(
+                ContentErrorKind::UnsupportedFormat,
+                "Response is not a Word document".to_string(),
+            ));
+        }
+        let mut archive = ZipArchive::new(Cursor::new(bytes)).map_err(|e| {
+            content_error(
+                ContentErrorKind::ParsingFailed,
+                format!("Malformed zip archive: {e}"),
+            )
+        })?;
+        let mut part = |name: &str| {
+            read_entry(&mut archive, name)
+                .map(|data| data.map(|data| String::from_utf8_lossy(&data).into_owned()))
+                .map_err(|e| content_error(ContentErrorKind::ParsingFailed, e))
+        };
+
+        let comment_mode = self.output_config.comment_mode;
+        let tracked_changes = self.output_config.tracked_changes;
+        // pandoc cannot place comments or keep both sides of a change as markdown
+        let pandoc = if comment_mode == CommentMode::Omit
+            && tracked_changes != TrackedChangeMode::Preserve
+        {
+            self.pandoc_markdown(url, bytes).await?
+        } else {
+            None
+        };
+        let body = match pandoc {
+            Some(markdown) => markdown,
+            None => {
+                let parser = DocxParser::new();
+                let document = part(DOCUMENT_PART)?.unwrap_or_default();
+                let relationships = part("word/_rels/document.xml.rels")?.unwrap_or_default();
+                let numbering = part("word/numbering.xml")?.unwrap_or_default();
+                let comment_bodies = if comment_mode == CommentMode::Omit {
+                    HashMap::new()
+                } else {
+                    parser.comments(&part(COMMENTS_PART)?.unwrap_or_default())
+                };
+                let (html, comments) = parser.to_html(
+                    &document,
+                    &relationships,
+                    &numbering,
+                    &comment_bodies,
+                    tracked_changes,
+                );
+                if html.is_empty() {
+                    String::new()
+                } else {
+                    let markdown = HtmlConverter::with_config(
+                        self.client.clone(),
+                        self.html_config.clone(),
+                        self.output_config.clone(),
+                    )
+                    .convert_html(&html)?;
+                    let markdown =
+                        CommentExtractor::new().render(&markdown, &comments, comment_mode);
+                    render_tracked_changes(&markdown)
+                }
+            }
+        };
+        if body.trim().is_empty() {
+            return Err(content_error(
+                ContentErrorKind::EmptyContent,
+                "Word document has no text".to_string(),
+            ));
+        }
+
+        if !self.output_config.include_frontmatter {
+            return Markdown::new(body);
+        }
+
+        let now = Utc::now();
+        let mut builder = FrontmatterBuilder::new(url.to_string())
+            .exporter(format!("markdowndown-docx-{}", env!("CARGO_PKG_VERSION")))
+            .download_date(now)
+            .additional_field("converted_at".to_string(), now.to_rfc3339())
+            .additional_field("conversion_type".to_string(), "docx".to_string())
+            .additional_field("url".to_string(), url.to_string());
+        if let Some(title) = part("docProps/core.xml")?.and_then(|core| document_title(&core)) {
+            builder = builder.additional_field("title".to_string(), title);
+        }
+
+        // Add custom frontmatter fields from configuration
+        for (key, value) in &self.output_config.custom_frontmatter_fields {
+            builder = builder.additional_field(key.clone(), value.clone());
+        }
+
+        let frontmatter = builder.build()?;
+        Markdown::new(format!("{frontmatter}\n{body}"))
+    }
+
+    /// Converts the document with `pandoc`, or returns `None` if it is not installed.
+    ///
+    /// `pandoc` runs as a child process the runtime waits on, so a slow
+    /// conversion does not block other tasks.
+    #[cfg(feature = "pandoc")]
+    async fn pandoc_markdown(
+        &self,
+        url: &str,
+        bytes: &[u8],
+    ) -> Result<Option<String>, MarkdownError> {
+        use crate::types::ConverterErrorKind;
+        use std::process::Stdio;
+        use tokio::io::AsyncWriteExt;
+        use tokio::process::Command;
+
+        let tool_error = |info: String| MarkdownError::ConverterError {
+            kind: ConverterErrorKind::ExternalToolFailed,
+            context: ErrorContext::new(url, "pandoc conversion", self.name()).with_info(info),
+        };
+
+        let track_changes = match self.output_config.tracked_changes {
+            TrackedChangeMode::Reject => "--track-changes=reject",
+            _ => "--track-changes=accept",
+        };
+        let child = Command::new("pandoc")
+            .args(["--from", "docx", "--to", "gfm", "--wrap=none"])
+            .arg(track_changes)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn();
+        let mut child = match child {
+            Ok(child) => child,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                tracing::warn!("pandoc is not installed; using the built-in DOCX parser");
+                return Ok(None);
+            }
+            Err(e) => return Err(tool_error(format!("Failed to start pandoc: {e}"))),
+        };
+
+        // pandoc reads the whole archive before writing, so stdin can be written
+        // first; dropping it closes the pipe
+        let mut stdin = child.stdin.take().expect("pandoc stdin is piped");
+        stdin
+            .write_all(bytes)
+            .await
+            .map_err(|e| tool_error(format!("Failed to send document to pandoc: {e}")))?;
+        drop(stdin);
+        let output = child
+            .wait_with_output()
+            .await
+            .map_err(|e| tool_error(format!("Failed to read pandoc output: {e}")))?;
+        if !output.status.success() {
+            return Err(tool_error(format!(
+                "pandoc exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        Ok(Some(String::from_utf8_lossy(&output.stdout).into_owned()))
+    }
+
+    /// Without the `pandoc` feature, documents always use the built-in parser.
+    ///
+    /// If `pandoc` is installed anyway, the first conversion warns that it is
+    /// not being used.
+    #[cfg(not(feature = "pandoc"))]
+    async fn pandoc_markdown(
+        &self,
+        _url: &str,
+        _bytes: &[u8],
+    ) -> Result<Option<String>, MarkdownError> {
+        static PANDOC_HINT: std::sync::Once = std::sync::Once::new();
+        PANDOC_HINT.call_once(|| {
+            if crate::utils::find_in_path("pandoc").is_some() {
+                let hint = crate::features::feature("pandoc").map(|feature| feature.hint());
+                tracing::warn!(
+                    "pandoc is installed, but DOCX documents use the built-in parser. {}",
+                    hint.unwrap_or_default()
+                );
+            }
+        });
+        Ok(None)
+    }
+}
+
+impl Default for DocxConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Converter for DocxConverter {
+    async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+        let bytes = self.client.get_bytes(url).await?;
+        self.convert_docx(url, &bytes).await
+    }
+
+    fn name(&self) -> &'static str {
+        "DOCX"
+    }
+}
+
+/// Reads and decompresses a file from a DOCX archive.
+///
+/// Returns `Ok(None)` if the archive has no such file.
+fn read_entry(archive: &mut Archive<'_>, name: &str) -> Result<Option<Vec<u8>>, String> {
+    let entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(format!("Failed to read {name}: {e}")),
+    };
+
+    let mut output = Vec::new();
+    entry
+        .take(MAX_ENTRY_SIZE + 1)
+        .read_to_end(&mut output)
+        .map_err(|e| format!("Failed to decompress {name}: {e}"))?;
+    if output.len() as u64 > MAX_ENTRY_SIZE {
+        return Err(format!("{name} is larger than {MAX_ENTRY_SIZE} bytes"));
+    }
+    Ok(Some(output))
+}
+
+/// Reads the title from the `docProps/core.xml` part.
+fn document_title(core: &str) -> Option<String> {
+    let start = core.find("<dc:title>")? + "<dc:title>".len();
+    let end = start + core[start..].find("</dc:title>")?;
+    let title = decode_xml_entities(core[start..end].trim());
+    (!title.is_empty()).then_some(title)
+}
+
+/// Decodes the predefined XML entities.
+pub(crate) fn decode_xml_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// A list opened while rendering list paragraphs.
+struct OpenList {
+    tag: &'static str,
+    item_open: bool,
+}
+
+/// The paragraph being read.
+#[derive(Default)]
+struct Paragraph {
+    style: Option<String>,
+    /// Numbering definition id and nesting level, for list items
+    numbering: Option<(String, usize)>,
+    html: String,
+}
+
+/// Renders the main document part of a DOCX file as HTML.
+struct DocxParser {
+    token: Regex,
+    attribute: Regex,
+    heading_style: Regex,
+}
+
+impl DocxParser {
+    fn new() -> Self {
+        Self {
+            token: Regex::new(r"<[?!][^>]*>|<(/?)([A-Za-z_][\w:.-]*)([^>]*)>|([^<]+)")
+                .expect("valid token pattern"),
+            attribute: Regex::new(r#"([\w:.-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#)
+                .expect("valid attribute pattern"),
+            heading_style: Regex::new(r"(?i)^heading\s*([1-9])$").expect("valid heading pattern"),
+        }
+    }
+
+    /// Renders `document.xml` as HTML, resolving links and list types.
+    ///
+    /// References to the given comments are replaced with comment markers,
+    /// and tracked changes are applied per `tracked_changes`. Returns the
+    /// HTML and the referenced comments, numbered in document order.
+    fn to_html(
+        &self,
+        document: &str,
+        relationships: &str,
+        numbering: &str,
+        comment_bodies: &HashMap<String, String>,
+        tracked_changes: TrackedChangeMode,
+    ) -> (String, Vec<DocumentComment>) {
+        let links = self.hyperlink_targets(relationships);
+        let ordered_lists = self.ordered_lists(numbering);
+        let preserve = tracked_changes == TrackedChangeMode::Preserve;
+
+        let mut output = String::new();
+        let mut comments: Vec<DocumentComment> = Vec::new();
+        let mut lists: Vec<OpenList> = Vec::new();
+        let mut paragraph: Option<Paragraph> = None;
+        let mut table_depth = 0usize;
+        let (mut bold, mut italic, mut in_text) = (false, false, false);
+        let (mut in_insert, mut in_delete) = (false, false);
+
+        for caps in self.token.captures_iter(document) {
+            if let Some(text) = caps.get(4) {
+                let hidden = match tracked_changes {
+                    TrackedChangeMode::Reject => in_insert,
+                    TrackedChangeMode::Accept => in_delete,
+                    _ => false,
+                };
+                if in_text && !hidden {
+                    if let Some(paragraph) = paragraph.as_mut() {
+                        paragraph.html.push_str(&format_run(text.as_str(), bold, italic));
+                    }
+                }
+                continue;
+            }
+            let Some(name) = caps.get(2).map(|m| m.as_str()) else {
+                continue;
+            };
+            let closing = !caps[1].is_empty();
+            let attributes = &caps[3];
+            let self_closing = attributes.ends_with('/');
+
+            match (name, closing) {
+                ("w:p", false) => paragraph = (!self_closing).then(Paragraph::default),
+                ("w:p", true) => {
+                    if let Some(paragraph) = paragraph.take() {
+                        self.render_paragraph(
+                            paragraph,
+                            table_depth > 0,
+                            &ordered_lists,
+                            &mut lists,
+                            &mut output,
+                        );
+                    }
+                }
+                ("w:pStyle", false) => {
+                    if let Some(paragraph) = paragraph.as_mut() {
+                        paragraph.style = self.value(attributes, "w:val");
+                    }
+                }
+                ("w:ilvl", false) | ("w:numId", false) => {
+                    if let (Some(paragraph), Some(value)) =
+                        (paragraph.as_mut(), self.value(attributes, "w:val"))
+                    {
+                        let numbering = paragraph.numbering.get_or_insert((String::new(), 0));
+                        if name == "w:ilvl" {
+                            numbering.1 = value.parse().unwrap_or(0);
+                        } else {
+                            numbering.0 = value;
+                        }
+                    }
+                }
+                ("w:r", false) => (bold, italic) = (false, false),
+                ("w:b", false) => bold = is_enabled(self.value(attributes, "w:val")),
+                ("w:i", false) => italic = is_enabled(self.value(attributes, "w:val")),
+                ("w:t" | "w:delText", false) => in_text = !self_closing,
+                ("w:t" | "w:delText", true) => in_text = false,
+                // Self-closing marks in run properties only track formatting changes
+                ("w:ins", false) if !self_closing => {
+                    in_insert = true;
+                    if preserve {
+                        push_html(&mut paragraph, INSERT_START);
+                    }
+                }
+                ("w:ins", true) => {
+                    in_insert = false;
+                    if preserve {
+                        push_html(&mut paragraph, INSERT_END);
+                    }
+                }
+                ("w:del", false) if !self_closing => {
+                    in_delete = true;
+                    if preserve {
+                        push_html(&mut paragraph, DELETE_START);
+                    }
+                }
+                ("w:del", true) => {
+                    in_delete = false;
+                    if preserve {
+                        push_html(&mut paragraph, DELETE_END);
+                    }
+                }
+                ("w:commentReference", false) => {
+                    let body = self
+                        .value(attributes, "w:id")
+                        .and_then(|id| comment_bodies.get(&id));
+                    if let (Some(body), Some(paragraph)) = (body, paragraph.as_mut()) {
+                        comments.push(DocumentComment {
+                            number: comments.len() + 1,
+                            text: body.clone(),
+                        });
+                        paragraph.html.push_str(&marker(comments.len()));
+                    }
+                }
+                ("w:tab", false) => push_html(&mut paragraph, " "),
+                ("w:br", false) => push_html(&mut paragraph, "<br>"),
+                ("w:hyperlink", false) => {
+                    let target = self
+                        .value(attributes, "r:id")
+                        .and_then(|id| links.get(&id).cloned())
+                        .or_else(|| {
+                            self.value(attributes, "w:anchor")
+                                .map(|anchor| format!("#{anchor}"))
+                        });
+                    if let Some(target) = target {
+                        push_html(&mut paragraph, &format!("<a href=\"{target}\">"));
+                    } else {
+                        push_html(&mut paragraph, "<a>");
+                    }
+                }
+                ("w:hyperlink", true) => push_html(&mut paragraph, "</a>"),
+                ("w:tbl", false) => {
+                    if table_depth == 0 {
+                        close_lists(&mut lists, 0, &mut output);
+                    }
+                    table_depth += 1;
+                    output.push_str("<table>");
+                }
+                ("w:tbl", true) => {
+                    table_depth = table_depth.saturating_sub(1);
+                    output.push_str("</table>");
+                }
+                ("w:tr", false) => output.push_str("<tr>"),
+                ("w:tr", true) => output.push_str("</tr>"),
+                ("w:tc", false) => output.push_str("<td>"),
+                ("w:tc", true) => output.push_str("</td>"),
+                _ => {}
+            }
+        }
+        close_lists(&mut lists, 0, &mut output);
+
+        (output, comments)
+    }
+
+    /// Reads the text of each comment in `comments.xml`, keyed by comment id.
+    ///
+    /// Paragraphs of a comment are joined with spaces.
+    fn comments(&self, comments_xml: &str) -> HashMap<String, String> {
+        let mut comments = HashMap::new();
+        let mut current: Option<(String, String)> = None;
+        let mut in_text = false;
+
+        for caps in self.token.captures_iter(comments_xml) {
+            if let Some(text) = caps.get(4) {
+                if let (true, Some((_, body))) = (in_text, current.as_mut()) {
+                    body.push_str(&decode_xml_entities(text.as_str()));
+                }
+                continue;
+            }
+            let Some(name) = caps.get(2).map(|m| m.as_str()) else {
+                continue;
+            };
+            let closing = !caps[1].is_empty();
+            match (name, closing) {
+                ("w:comment", false) => {
+                    current = self.value(&caps[3], "w:id").map(|id| (id, String::new()));
+                }
+                ("w:comment", true) => {
+                    if let Some((id, body)) = current.take() {
+                        let body = body.split_whitespace().collect::<Vec<_>>().join(" ");
+                        if !body.is_empty() {
+                            comments.insert(id, body);
+                        }
+                    }
+                }
+                ("w:t", false) => in_text = !caps[3].ends_with('/'),
+                ("w:t", true) => in_text = false,
+                ("w:p", true) => {
+                    if let Some((_, body)) = current.as_mut() {
+                        body.push(' ');
+                    }
+                }
+                _ => {}
+            }
+        }
+        comments
+    }
+
+    /// Writes a finished paragraph as a heading, list item, or paragraph.
+    fn render_paragraph(
+        &self,
+        paragraph: Paragraph,
+        in_table: bool,
+        ordered_lists: &HashMap<(String, usize), bool>,
+        lists: &mut Vec<OpenList>,
+        output: &mut String,
+    ) {
+        if paragraph.html.trim().is_empty() {
+            return;
+        }
+        if in_table {
+            output.push_str(&format!("<p>{}</p>", paragraph.html));
+            return;
+        }
+
+        if let Some((num_id, level)) = paragraph.numbering.filter(|(id, _)| id != "0") {
+            let ordered = ordered_lists
+                .get(&(num_id, level))
+                .copied()
+                .unwrap_or(false);
+            let tag = if ordered { "ol" } else { "ul" };
+
+            close_lists(lists, level + 1, output);
+            if lists.len() == level + 1 && lists[level].tag != tag {
+                close_lists(lists, level, output);
+            }
+            if let Some(list) = lists.get_mut(level) {
+                if list.item_open {
+                    output.push_str("</li>");
+                }
+            }
+            while lists.len() <= level {
+                output.push_str(&format!("<{tag}>"));
+                lists.push(OpenList {
+                    tag,
+                    item_open: false,
+                });
+            }
+            output.push_str(&format!("<li>{}", paragraph.html));
+            lists[level].item_open = true;
+            return;
+        }
+
+        close_lists(lists, 0, output);
+        let level = paragraph.style.as_deref().and_then(|style| {
+            if style.eq_ignore_ascii_case("title") {
+                Some(1)
+            } else {
+                self.heading_style
+                    .captures(style)
+                    .and_then(|caps| caps[1].parse::<usize>().ok())
+                    .map(|level| level.min(6))
+            }
+        });
+        match level {
+            Some(level) => output.push_str(&format!("<h{level}>{}</h{level}>", paragraph.html)),
+            None => output.push_str(&format!("<p>{}</p>", paragraph.html)),
+        }
+    }
+
+    /// Maps relationship ids to hyperlink targets.
+    fn hyperlink_targets(&self, relationships: &str) -> HashMap<String, String> {
+        self.token
+            .captures_iter(relationships)
+            .filter(|caps| caps.get(2).is_some_and(|name| name.as_str() == "Relationship"))
+            .filter_map(|caps| {
+                let id = self.value(&caps[3], "Id")?;
+                let target = self.value(&caps[3], "Target")?;
+                Some((id, target))
+            })
+            .collect()
+    }
+
+    /// Maps each numbering id and level to whether its list is ordered.
+    fn ordered_lists(&self, numbering: &str) -> HashMap<(String, usize), bool> {
+        let abstract_num = Regex::new(r"(?s)<w:abstractNum\b([^>]*)>(.*?)</w:abstractNum>")
+            .expect("valid abstract numbering pattern");
+        let level = Regex::new(r#"(?s)<w:lvl\b([^>]*)>.*?<w:numFmt\s+w:val="([^"]*)""#)
+            .expect("valid level pattern");
+        let num = Regex::new(r#"(?s)<w:num\b([^>]*)>.*?<w:abstractNumId\s+w:val="([^"]*)""#)
+            .expect("valid numbering pattern");
+
+        let mut formats: HashMap<String, Vec<(usize, bool)>> = HashMap::new();
+        for caps in abstract_num.captures_iter(numbering) {
+            let Some(id) = self.value(&caps[1], "w:abstractNumId") else {
+                continue;
+            };
+            let levels = level
+                .captures_iter(&caps[2])
+                .filter_map(|lvl| {
+                    let index = self.value(&lvl[1], "w:ilvl")?.parse().ok()?;
+                    Some((index, !matches!(&lvl[2], "bullet" | "none")))
+                })
+                .collect();
+            formats.insert(id, levels);
+        }
+
+        let mut ordered = HashMap::new();
+        for caps in num.captures_iter(numbering) {
+            let Some(num_id) = self.value(&caps[1], "w:numId") else {
+                continue;
+            };
+            for &(index, is_ordered) in formats.get(&caps[2]).into_iter().flatten() {
+                ordered.insert((num_id.clone(), index), is_ordered);
+            }
+        }
+        ordered
+    }
+
+    /// Returns the value of an attribute in a tag's attribute text.
+    fn value(&self, attributes: &str, name: &str) -> Option<String> {
+        self.attribute
+            .captures_iter(attributes)
+            .find(|caps| &caps[1] == name)
+            .and_then(|caps| caps.get(2).or_else(|| caps.get(3)))
+            .map(|value| value.as_str().to_string())
+    }
+}
+
+/// Closes open lists until `depth` remain.
+fn close_lists(lists: &mut Vec<OpenList>, depth: usize, output: &mut String) {
+    while lists.len() > depth {
+        let list = lists.pop().expect("list is open");
+        if list.item_open {
+            output.push_str("</li>");
+        }
+        output.push_str(&format!("</{}>", list.tag));
+    }
+}
+
+/// Replaces preserved change markers with CriticMarkup, dropping changes
+/// that lost all their text.
+fn render_tracked_changes(markdown: &str) -> String {
+    markdown
+        .replace(&format!("{INSERT_START}{INSERT_END}"), "")
+        .replace(&format!("{DELETE_START}{DELETE_END}"), "")
+        .replace(INSERT_START, "{++")
+        .replace(INSERT_END, "++}")
+        .replace(DELETE_START, "{--")
+        .replace(DELETE_END, "--}")
+}
+
+/// Appends HTML to the current paragraph, if there is one.
+fn push_html(paragraph: &mut Option<Paragraph>, html: &str) {
+    if let Some(paragraph) = paragraph.as_mut() {
+        paragraph.html.push_str(html);
+    }
+}
+
+/// Wraps run text in emphasis tags. The text is already XML-escaped.
+fn format_run(text: &str, bold: bool, italic: bool) -> String {
+    match (bold, italic) {
+        (true, true) => format!("<strong><em>{text}</em></strong>"),
+        (true, false) => format!("<strong>{text}</strong>"),
+        (false, true) => format!("<em>{text}</em>"),
+        (false, false) => text.to_string(),
+    }
+}
+
+/// Reads an on/off property such as `<w:b w:val="0"/>`, which is on when the value is absent.
+fn is_enabled(value: Option<String>) -> bool {
+    !matches!(value.as_deref(), Some("0" | "false" | "off"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+    use zip::{CompressionMethod, ZipWriter};
+
+    /// Builds a zip archive, deflating every entry except the first.
+    fn build_zip(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (index, (name, content)) in files.iter().enumerate() {
+            let method = if index == 0 {
+                CompressionMethod::Stored
+            } else {
+                CompressionMethod::Deflated
+            };
+            let options = SimpleFileOptions::default().compression_method(method);
+            writer.start_file(*name, options).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    const DOCUMENT: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+<w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t>Quarterly Plan</w:t></w:r></w:p>
+<w:p><w:r><w:t xml:space="preserve">Read the </w:t></w:r><w:r><w:rPr><w:b/></w:rPr><w:t>summary</w:t></w:r><w:r><w:t xml:space="preserve"> at </w:t></w:r><w:hyperlink r:id="rId9"><w:r><w:t>the wiki</w:t></w:r></w:hyperlink><w:r><w:t>.</w:t></w:r></w:p>
+<w:p><w:pPr><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t>First step</w:t></w:r></w:p>
+<w:p><w:pPr><w:numPr><w:ilvl w:val="1"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t>Detail</w:t></w:r></w:p>
+<w:p><w:pPr><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t>Second step</w:t></w:r></w:p>
+<w:tbl><w:tr><w:tc><w:p><w:r><w:t>Owner</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>Due</w:t></w:r></w:p></w:tc></w:tr></w:tbl>
+</w:body></w:document>"#;
+
+    const RELATIONSHIPS: &str = r#"<Relationships><Relationship Id="rId9" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="https://wiki.example.com/plan" TargetMode="External"/></Relationships>"#;
+
+    const NUMBERING: &str = r#"<w:numbering><w:abstractNum w:abstractNumId="4"><w:lvl w:ilvl="0"><w:numFmt w:val="decimal"/></w:lvl><w:lvl w:ilvl="1"><w:numFmt w:val="bullet"/></w:lvl></w:abstractNum><w:num w:numId="1"><w:abstractNumId w:val="4"/></w:num></w:numbering>"#;
+
+    #[test]
+    fn test_renders_document_structure_as_html() {
+        let (html, comments) = DocxParser::new().to_html(
+            DOCUMENT,
+            RELATIONSHIPS,
+            NUMBERING,
+            &HashMap::new(),
+            TrackedChangeMode::Accept,
+        );
+        assert!(comments.is_empty());
+
+        assert_eq!(
+            html,
+            "<h1>Quarterly Plan</h1>\
+             <p>Read the <strong>summary</strong> at <a href=\"https://wiki.example.com/plan\">the wiki</a>.</p>\
+             <ol><li>First step<ul><li>Detail</li></ul></li><li>Second step</li></ol>\
+             <table><tr><td><p>Owner</p></td><td><p>Due</p></td></tr></table>"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_convert_docx_reads_archive_and_title() {
+        let docx = build_zip(&[
+            (DOCUMENT_PART, DOCUMENT),
+            ("word/_rels/document.xml.rels", RELATIONSHIPS),
+            ("word/numbering.xml", NUMBERING),
+            (
+                "docProps/core.xml",
+                "<cp:coreProperties><dc:title>Plan &amp; Goals</dc:title></cp:coreProperties>",
+            ),
+        ]);
+        assert!(is_docx(&docx));
+
+        let markdown = DocxConverter::new()
+            .convert_docx("https://contoso.sharepoint.com/plan.docx", &docx)
+            .await
+            .unwrap();
+        let text = markdown.as_str();
+        assert!(text.contains("conversion_type: docx"));
+        assert!(text.contains("title: Plan & Goals"));
+        assert!(text.contains("Quarterly Plan"));
+        assert!(text.contains("Second step"));
+        assert!(text.contains("https://wiki.example.com/plan"));
+    }
+
+    #[tokio::test]
+    async fn test_renders_comments_and_tracked_changes() {
+        let document = r#"<w:document><w:body>
+<w:p><w:r><w:t xml:space="preserve">The term is </w:t></w:r><w:del w:id="1" w:author="A"><w:r><w:delText>five</w:delText></w:r></w:del><w:ins w:id="2" w:author="B"><w:r><w:t>three</w:t></w:r></w:ins><w:r><w:t xml:space="preserve"> years.</w:t></w:r><w:r><w:commentReference w:id="0"/></w:r></w:p>
+</w:body></w:document>"#;
+        let comments = r#"<w:comments><w:comment w:id="0" w:author="Legal"><w:p><w:r><w:t>Confirm with</w:t></w:r></w:p><w:p><w:r><w:t>finance &amp; ops</w:t></w:r></w:p></w:comment></w:comments>"#;
+        let docx = build_zip(&[(DOCUMENT_PART, document), (COMMENTS_PART, comments)]);
+        let convert = |comment_mode, tracked_changes| {
+            let output_config = crate::config::OutputConfig {
+                include_frontmatter: false,
+                comment_mode,
+                tracked_changes,
+                ..Default::default()
+            };
+            let converter = DocxConverter::with_config(
+                HttpClient::new(),
+                HtmlConverterConfig::default(),
+                output_config,
+            );
+            let docx = docx.clone();
+            async move {
+                let markdown = converter
+                    .convert_docx("https://example.com/contract.docx", &docx)
+                    .await
+                    .unwrap();
+                markdown.as_str().trim().to_string()
+            }
+        };
+
+        assert_eq!(
+            convert(CommentMode::Omit, TrackedChangeMode::Accept).await,
+            "The term is three years."
+        );
+        assert_eq!(
+            convert(CommentMode::Omit, TrackedChangeMode::Reject).await,
+            "The term is five years."
+        );
+        assert_eq!(
+            convert(CommentMode::Footnotes, TrackedChangeMode::Preserve).await,
+            "The term is {--five--}{++three++} years.[^c1]\n\n[^c1]: Confirm with finance & ops"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejects_other_archives_and_empty_documents() {
+        let other = build_zip(&[("content.xml", "<office:document/>")]);
+        assert!(!is_docx(&other));
+        let error = DocxConverter::new()
+            .convert_docx("https://example.com/a.odt", &other)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            MarkdownError::ContentError {
+                kind: ContentErrorKind::UnsupportedFormat,
+                ..
+            }
+        ));
+
+        let empty = build_zip(&[(DOCUMENT_PART, "<w:document><w:body><w:p/></w:body></w:document>")]);
+        let error = DocxConverter::new()
+            .convert_docx("https://example.com/a.docx", &empty)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            MarkdownError::ContentError {
+                kind: ContentErrorKind::EmptyContent,
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,369 @@
+//! Dropbox Paper documents and Dropbox shared files to markdown conversion.
+//!
+//! Paper documents are exported as markdown through the Dropbox API, which
+//! needs an access token, set with `Config::builder().dropbox_token(...)`.
+//! Shared file links open a preview page, so they are instead fetched with
+//! `dl=1`, which downloads the file itself, and the download is converted by
+//! its content type like any fetched document: PDF, DOCX, or HTML.
+//!
+//! # Supported URLs
+//!
+//! - Paper documents, such as `https://paper.dropbox.com/doc/Release-Notes--AbCdEf-qRsTuVwXyZ`
+//! - Shared file links, such as `https://www.dropbox.com/s/a1b2c3d4e5/report.pdf?dl=0`
+//! - Newer shared file links, such as `https://www.dropbox.com/scl/fi/a1b2c3/plan.docx?rlkey=xyz`
+//!
+//! # Usage Examples
+//!
+//! ```rust,no_run
+//! use markdowndown::{Config, MarkdownDown};
+//!
+//! # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
+//! let config = Config::builder().dropbox_token("sl.B1a2...").build();
+//! let md = MarkdownDown::with_config(config);
+//! let markdown = md
+//!     .convert_url("https://paper.dropbox.com/doc/Release-Notes--AbCdEf-qRsTuVwXyZ")
+//!     .await?;
+//! println!("{}", markdown);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::HttpClient;
+use crate::frontmatter::FrontmatterBuilder;
+use crate::types::{AuthErrorKind, ErrorContext, Markdown, MarkdownError, ValidationErrorKind};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::{debug, instrument};
+use url::Url;
+
+use super::converter::Converter;
+use super::html::HtmlConverter;
+
+/// Default Dropbox API base URL
+const DEFAULT_DROPBOX_API_BASE_URL: &str = "https://api.dropboxapi.com";
+
+/// Host of Dropbox Paper documents
+const PAPER_HOST: &str = "paper.dropbox.com";
+
+/// Metadata Dropbox returns in the `Dropbox-API-Result` header of a Paper export.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PaperExportResult {
+    /// The document's title
+    #[serde(default)]
+    pub title: Option<String>,
+    /// The exported revision of the document
+    #[serde(default)]
+    pub revision: Option<u64>,
+}
+
+/// Dropbox Paper and shared file to markdown converter.
+#[derive(Debug, Clone)]
+pub struct DropboxConverter {
+    /// HTTP client for API requests and file downloads
+    client: HttpClient,
+    /// Converter for downloaded files
+    html_converter: HtmlConverter,
+    /// Optional Dropbox access token, needed for Paper documents
+    auth_token: Option<String>,
+    /// Base URL of the Dropbox API
+    api_base_url: String,
+}
+
+impl DropboxConverter {
+    /// Creates a new Dropbox converter without a token.
+    ///
+    /// Shared files can be converted; Paper documents need a token.
+    pub fn new() -> Self {
+        Self {
+            client: HttpClient::new(),
+            html_converter: HtmlConverter::new(),
+            auth_token: None,
+            api_base_url: DEFAULT_DROPBOX_API_BASE_URL.to_string(),
+        }
+    }
+
+    /// Creates a new Dropbox converter with an access token.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Dropbox access token with the `files.content.read` scope
+    pub fn new_with_token(token: String) -> Self {
+        Self {
+            auth_token: Some(token),
+            ..Self::new()
+        }
+    }
+
+    /// Creates a Dropbox converter with a configured client and HTML converter.
+    ///
+    /// The token configured on the client is used, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client used for API requests and downloads
+    /// * `html_converter` - The configured converter used for downloaded files
+    pub fn with_config(client: HttpClient, html_converter: HtmlConverter) -> Self {
+        let auth_token = client.dropbox_token().map(str::to_string);
+        Self {
+            client,
+            html_converter,
+            auth_token,
+            api_base_url: DEFAULT_DROPBOX_API_BASE_URL.to_string(),
+        }
+    }
+
+    /// Exports Paper documents from a different API base URL (primarily for testing).
+    ///
+    /// # Arguments
+    ///
+    /// * `api_base_url` - The URL used in place of `https://api.dropboxapi.com`
+    pub fn with_api_base_url<T: Into<String>>(mut self, api_base_url: T) -> Self {
+        self.api_base_url = api_base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Converts a Dropbox Paper or shared file URL to markdown with frontmatter.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The Paper document or shared file URL to convert
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ValidationError` - If the URL is malformed or names no Paper document
+    /// * `MarkdownError::AuthenticationError` - If a Paper document is converted
+    ///   without a token, or the token is not accepted
+    /// * `MarkdownError::EnhancedNetworkError` - For API errors or network failures
+    #[instrument(skip(self))]
+    pub async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+        let url = url.trim();
+        let parsed = Url::parse(url).map_err(|e| MarkdownError::ValidationError {
+            kind: ValidationErrorKind::InvalidUrl,
+            context: ErrorContext::new(url, "URL parsing", "DropboxConverter")
+                .with_info(e.to_string()),
+        })?;
+
+        if parsed.host_str() == Some(PAPER_HOST) {
+            self.convert_paper(url, &parsed).await
+        } else {
+            let download_url = download_url(&parsed);
+            debug!("Downloading Dropbox shared file from {}", download_url);
+            let content = self
+                .client
+                .get_content_with_headers(&download_url, &HashMap::new())
+                .await?;
+            self.html_converter.convert_content(url, content).await
+        }
+    }
+
+    /// Exports a Paper document as markdown.
+    async fn convert_paper(&self, url: &str, parsed: &Url) -> Result<Markdown, MarkdownError> {
+        let Some(doc_id) = paper_doc_id(parsed) else {
+            return Err(MarkdownError::ValidationError {
+                kind: ValidationErrorKind::InvalidUrl,
+                context: ErrorContext::new(url, "Paper document ID extraction", "DropboxConverter")
+                    .with_info("Expected a URL like https://paper.dropbox.com/doc/Title--Id"),
+            });
+        };
+        let Some(token) = &self.auth_token else {
+            return Err(MarkdownError::AuthenticationError {
+                kind: AuthErrorKind::MissingToken,
+                context: ErrorContext::new(url, "Paper export", "DropboxConverter").with_info(
+                    "Exporting Paper documents needs a Dropbox token; set one with `dropbox_token`",
+                ),
+            });
+        };
+
+        let arg = serde_json::json!({"doc_id": doc_id, "export_format": "markdown"});
+        let headers = HashMap::from([
+            ("Authorization".to_string(), format!("Bearer {token}")),
+            ("Dropbox-API-Arg".to_string(), arg.to_string()),
+        ]);
+        let export_url = format!("{}/2/paper/docs/download", self.api_base_url);
+        debug!("Exporting Paper document {} as markdown", doc_id);
+        let (content, metadata) = self
+            .client
+            .get_text_and_metadata_with_headers(&export_url, &headers)
+            .await?;
+        let result: PaperExportResult = metadata
+            .header("dropbox-api-result")
+            .and_then(|header| serde_json::from_str(header).ok())
+            .unwrap_or_default();
+
+        let now = Utc::now();
+        let mut builder = FrontmatterBuilder::new(url.to_string())
+            .exporter(format!(
+                "markdowndown-dropbox-{}",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .download_date(now)
+            .additional_field("converted_at".to_string(), now.to_rfc3339())
+            .additional_field("conversion_type".to_string(), "dropbox_paper".to_string())
+            .additional_field("document_id".to_string(), doc_id.to_string());
+        if let Some(title) = result.title {
+            builder = builder.additional_field("title".to_string(), title);
+        }
+        if let Some(revision) = result.revision {
+            builder = builder.additional_field("revision".to_string(), revision.to_string());
+        }
+        let frontmatter = builder.build()?;
+
+        Markdown::new(format!("{frontmatter}\n{}", content.trim()))
+    }
+}
+
+/// Returns the ID of a Paper document, the last `-`-separated part of its
+/// `/doc/` path segment.
+fn paper_doc_id(url: &Url) -> Option<&str> {
+    let slug = url.path().strip_prefix("/doc/")?.split('/').next()?;
+    slug.rsplit('-')
+        .next()
+        .filter(|id| !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+/// Returns a shared file URL that downloads the file instead of previewing it.
+fn download_url(url: &Url) -> String {
+    let mut download = url.clone();
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| key != "dl" && key != "raw")
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    download
+        .query_pairs_mut()
+        .clear()
+        .extend_pairs(pairs)
+        .append_pair("dl", "1");
+    download.to_string()
+}
+
+impl Default for DropboxConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Converter for DropboxConverter {
+    async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+        self.convert(url).await
+    }
+
+    fn name(&self) -> &'static str {
+        "Dropbox"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MarkdownError;
+    use wiremock::matchers::{header, header_exists, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const PAPER_URL: &str = "https://paper.dropbox.com/doc/Release-Notes--AbCdEfGh-qRsTuVwXyZ";
+
+    #[test]
+    fn test_paper_doc_id() {
+        let id = |url: &str| paper_doc_id(&Url::parse(url).unwrap()).map(str::to_string);
+        assert_eq!(id(PAPER_URL), Some("qRsTuVwXyZ".to_string()));
+        assert_eq!(
+            id("https://paper.dropbox.com/doc/uaSvRuxvnkFa12PTkBv5q"),
+            Some("uaSvRuxvnkFa12PTkBv5q".to_string())
+        );
+        assert_eq!(id("https://paper.dropbox.com/doc/"), None);
+        assert_eq!(id("https://paper.dropbox.com/folder/show/Team"), None);
+    }
+
+    #[test]
+    fn test_download_url_sets_dl() {
+        let download = |url: &str| download_url(&Url::parse(url).unwrap());
+        assert_eq!(
+            download("https://www.dropbox.com/s/a1b2c3/report.pdf?dl=0"),
+            "https://www.dropbox.com/s/a1b2c3/report.pdf?dl=1"
+        );
+        assert_eq!(
+            download("https://www.dropbox.com/scl/fi/a1b2c3/plan.docx?rlkey=xyz&dl=0"),
+            "https://www.dropbox.com/scl/fi/a1b2c3/plan.docx?rlkey=xyz&dl=1"
+        );
+        assert_eq!(
+            download("https://www.dropbox.com/s/a1b2c3/notes.html"),
+            "https://www.dropbox.com/s/a1b2c3/notes.html?dl=1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_convert_paper_exports_markdown() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/paper/docs/download"))
+            .and(header("Authorization", "Bearer dropbox-token"))
+            .and(header_exists("Dropbox-API-Arg"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(
+                        "Dropbox-API-Result",
+                        r#"{"owner":"jamie@example.com","title":"Release Notes","revision":7,"mime_type":"text/x-markdown"}"#,
+                    )
+                    .set_body_string("# Release Notes\n\n- Faster exports\n"),
+            )
+            .mount(&server)
+            .await;
+
+        let converter = DropboxConverter::new_with_token("dropbox-token".to_string())
+            .with_api_base_url(server.uri());
+        let markdown = converter.convert(PAPER_URL).await.unwrap();
+        assert!(markdown.as_str().contains("- Faster exports"));
+        let frontmatter = markdown.frontmatter().unwrap();
+        assert!(frontmatter.contains("title: Release Notes"));
+        assert!(frontmatter.contains("document_id: qRsTuVwXyZ"));
+        assert!(frontmatter.contains("revision: '7'"));
+
+        // The mock server splits header values at commas
+        let requests = server.received_requests().await.unwrap();
+        let arg: Vec<&str> = requests[0].headers[&"Dropbox-API-Arg".into()]
+            .iter()
+            .map(|value| value.as_str())
+            .collect();
+        assert_eq!(
+            arg.join(","),
+            r#"{"doc_id":"qRsTuVwXyZ","export_format":"markdown"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_convert_paper_without_token_fails() {
+        let result = DropboxConverter::new().convert(PAPER_URL).await;
+        assert!(matches!(
+            result,
+            Err(MarkdownError::AuthenticationError {
+                kind: AuthErrorKind::MissingToken,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_convert_shared_file_downloads_with_dl() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/s/a1b2c3/notes.html"))
+            .and(query_param("dl", "1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Type", "text/html")
+                    .set_body_string("<html><body><h1>Notes</h1><p>Shared file.</p></body></html>"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let markdown = DropboxConverter::new()
+            .convert(&format!("{}/s/a1b2c3/notes.html?dl=0", server.uri()))
+            .await
+            .unwrap();
+        assert!(markdown.as_str().contains("Shared file."));
+    }
+}
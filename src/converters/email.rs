@@ -0,0 +1,710 @@
+//! Email message (`.eml`) and web archive (`.mhtml`) to markdown conversion.
+//!
+//! Both formats are MIME messages: a block of headers followed by a body
+//! that may be split into parts. The message headers become frontmatter
+//! fields (`from`, `to`, `cc`, `subject`, and `date`), the HTML body is
+//! converted with the HTML pipeline, and a plain text body is used as it is.
+//! When a message offers both, the HTML alternative is preferred. Attached
+//! files are listed at the end with their type and size.
+//!
+//! Web archives saved by a browser hold the page as the first part of a
+//! `multipart/related` body, followed by its images and stylesheets. Only
+//! the page is converted; the original address it was saved from is kept as
+//! the `original_url` field.
+
+use crate::client::HttpClient;
+use crate::frontmatter::FrontmatterBuilder;
+use crate::types::{ContentErrorKind, ErrorContext, Markdown, MarkdownError};
+use async_trait::async_trait;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use encoding_rs::Encoding;
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use super::converter::Converter;
+use super::html::HtmlConverter;
+
+/// A MIME entity: the message itself or one part of a multipart body.
+#[derive(Debug, Default)]
+struct MimePart {
+    /// Header names, lowercased, with their unfolded and decoded values
+    headers: Vec<(String, String)>,
+    /// The body with its transfer encoding removed; empty for multipart entities
+    body: Vec<u8>,
+    /// The parts of a multipart body
+    children: Vec<MimePart>,
+}
+
+/// A text body found in a message.
+enum MessageBody {
+    Html(String),
+    Text(String),
+}
+
+/// A file attached to a message.
+struct Attachment {
+    name: String,
+    media_type: String,
+    size: usize,
+}
+
+impl MimePart {
+    /// Parses an entity and, for multipart bodies, its parts.
+    fn parse(bytes: &[u8]) -> Self {
+        let (head, body) = split_head(bytes);
+        let mut part = MimePart {
+            headers: parse_headers(&String::from_utf8_lossy(head)),
+            ..Default::default()
+        };
+
+        let (media_type, parameters) = part.content_type();
+        match parameters.get("boundary") {
+            Some(boundary) if media_type.starts_with("multipart/") => {
+                part.children = split_multipart(body, boundary)
+                    .into_iter()
+                    .map(MimePart::parse)
+                    .collect();
+            }
+            _ => {
+                let encoding = part
+                    .header("content-transfer-encoding")
+                    .unwrap_or_default()
+                    .to_ascii_lowercase();
+                part.body = match encoding.trim() {
+                    "base64" => decode_base64(body),
+                    "quoted-printable" => decode_quoted_printable(body),
+                    _ => body.to_vec(),
+                };
+            }
+        }
+        part
+    }
+
+    /// The first value of a header.
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The media type, lowercased, and its parameters; `text/plain` when not given.
+    fn content_type(&self) -> (String, HashMap<String, String>) {
+        let (media_type, parameters) =
+            parse_parameters(self.header("content-type").unwrap_or_default());
+        if media_type.is_empty() {
+            ("text/plain".to_string(), parameters)
+        } else {
+            (media_type, parameters)
+        }
+    }
+
+    /// The file name from the disposition or, failing that, the content type.
+    fn file_name(&self) -> Option<String> {
+        let (_, disposition) =
+            parse_parameters(self.header("content-disposition").unwrap_or_default());
+        disposition
+            .get("filename")
+            .cloned()
+            .or_else(|| self.content_type().1.get("name").cloned())
+            .filter(|name| !name.trim().is_empty())
+    }
+
+    /// Whether the part is marked as an attachment rather than inline content.
+    fn is_attachment(&self) -> bool {
+        let (disposition, _) =
+            parse_parameters(self.header("content-disposition").unwrap_or_default());
+        disposition == "attachment"
+    }
+
+    /// Decodes a text body with the charset it declares.
+    fn text(&self) -> String {
+        let (_, parameters) = self.content_type();
+        let encoding = parameters
+            .get("charset")
+            .and_then(|charset| Encoding::for_label(charset.as_bytes()))
+            .unwrap_or(encoding_rs::UTF_8);
+        encoding.decode(&self.body).0.into_owned()
+    }
+
+    /// Whether this part is, or holds, an HTML body.
+    fn has_html(&self) -> bool {
+        self.content_type().0 == "text/html" || self.children.iter().any(MimePart::has_html)
+    }
+
+    /// Collects the text bodies to convert and the attached files.
+    ///
+    /// Of the alternatives in a `multipart/alternative` body, the HTML one is
+    /// used. Only the root of a `multipart/related` body is read, since the
+    /// other parts are resources it refers to.
+    fn collect(&self, bodies: &mut Vec<MessageBody>, attachments: &mut Vec<Attachment>) {
+        let (media_type, parameters) = self.content_type();
+        if !self.children.is_empty() {
+            match media_type.as_str() {
+                "multipart/alternative" => {
+                    let preferred = self
+                        .children
+                        .iter()
+                        .rev()
+                        .find(|child| child.has_html())
+                        .or_else(|| self.children.last());
+                    if let Some(child) = preferred {
+                        child.collect(bodies, attachments);
+                    }
+                }
+                "multipart/related" => {
+                    let start = parameters
+                        .get("start")
+                        .map(|id| id.trim_matches(['<', '>']));
+                    let root = start
+                        .and_then(|start| {
+                            self.children.iter().find(|child| {
+                                child
+                                    .header("content-id")
+                                    .is_some_and(|id| id.trim_matches(['<', '>']) == start)
+                            })
+                        })
+                        .or_else(|| self.children.first());
+                    if let Some(root) = root {
+                        root.collect(bodies, attachments);
+                    }
+                }
+                _ => {
+                    for child in &self.children {
+                        child.collect(bodies, attachments);
+                    }
+                }
+            }
+            return;
+        }
+
+        let file_name = self.file_name();
+        match media_type.as_str() {
+            "text/html" if !self.is_attachment() && file_name.is_none() => {
+                bodies.push(MessageBody::Html(self.text()))
+            }
+            "text/plain" if !self.is_attachment() && file_name.is_none() => {
+                bodies.push(MessageBody::Text(self.text()))
+            }
+            _ if self.body.is_empty() && file_name.is_none() => {}
+            _ => attachments.push(Attachment {
+                name: file_name.unwrap_or_else(|| format!("Untitled ({media_type})")),
+                size: self.body.len(),
+                media_type,
+            }),
+        }
+    }
+}
+
+/// Email message and web archive to markdown converter.
+#[derive(Debug, Clone)]
+pub struct EmailConverter {
+    client: HttpClient,
+    html_converter: HtmlConverter,
+    output_config: crate::config::OutputConfig,
+}
+
+impl EmailConverter {
+    /// Creates a new email converter with default configuration.
+    pub fn new() -> Self {
+        Self {
+            client: HttpClient::new(),
+            html_converter: HtmlConverter::new(),
+            output_config: crate::config::OutputConfig::default(),
+        }
+    }
+
+    /// Creates a new email converter with custom configuration and HTTP client.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Configured HTTP client to use for requests
+    /// * `html_converter` - The configured converter used for HTML bodies
+    /// * `output_config` - Output configuration for frontmatter
+    pub fn with_config(
+        client: HttpClient,
+        html_converter: HtmlConverter,
+        output_config: crate::config::OutputConfig,
+    ) -> Self {
+        Self {
+            client,
+            html_converter,
+            output_config,
+        }
+    }
+
+    /// Converts an email message or web archive that has already been read.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL or path the message was read from
+    /// * `bytes` - The `.eml` or `.mhtml` file contents
+    ///
+    /// # Returns
+    ///
+    /// Returns the converted markdown on success, or a `MarkdownError` on failure.
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ContentError` - If the bytes do not start with MIME
+    ///   headers (`ParsingFailed`), or the message has neither a body nor
+    ///   attachments (`EmptyContent`)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::converters::EmailConverter;
+    ///
+    /// let message = "From: Ann <ann@example.com>\r\nTo: bo@example.com\r\nSubject: Launch\r\n\
+    ///                Content-Type: text/html; charset=utf-8\r\n\r\n<p>Ship it today.</p>\r\n";
+    /// let markdown = EmailConverter::new().convert_message("launch.eml", message.as_bytes())?;
+    /// assert!(markdown.as_str().contains("subject: Launch"));
+    /// assert!(markdown.as_str().contains("Ship it today."));
+    /// # Ok::<(), markdowndown::types::MarkdownError>(())
+    /// ```
+    pub fn convert_message(&self, url: &str, bytes: &[u8]) -> Result<Markdown, MarkdownError> {
+        let content_error = |kind, info: &str| MarkdownError::ContentError {
+            kind,
+            context: ErrorContext::new(url, "Email conversion", self.name()).with_info(info),
+        };
+
+        let message = MimePart::parse(bytes);
+        if message.headers.is_empty() {
+            return Err(content_error(
+                ContentErrorKind::ParsingFailed,
+                "Content does not start with MIME headers",
+            ));
+        }
+
+        let mut bodies = Vec::new();
+        let mut attachments = Vec::new();
+        message.collect(&mut bodies, &mut attachments);
+
+        let mut sections = Vec::new();
+        for body in bodies {
+            let markdown = match body {
+                MessageBody::Html(html) if html.trim().is_empty() => continue,
+                MessageBody::Html(html) => self.html_converter.convert_html(&html)?,
+                MessageBody::Text(text) => text,
+            };
+            let markdown = markdown.trim();
+            if !markdown.is_empty() {
+                sections.push(markdown.to_string());
+            }
+        }
+        if !attachments.is_empty() {
+            let list = attachments
+                .iter()
+                .map(|a| format!("- {} ({}, {})", a.name, a.media_type, format_size(a.size)))
+                .collect::<Vec<_>>()
+                .join("\n");
+            sections.push(format!("## Attachments\n\n{list}"));
+        }
+        if sections.is_empty() {
+            return Err(content_error(
+                ContentErrorKind::EmptyContent,
+                "Message has no body or attachments",
+            ));
+        }
+        let body = format!("{}\n", sections.join("\n\n"));
+
+        if !self.output_config.include_frontmatter {
+            return Markdown::new(body);
+        }
+
+        let now = Utc::now();
+        let mut builder = FrontmatterBuilder::new(url.to_string())
+            .exporter(format!("markdowndown-email-{}", env!("CARGO_PKG_VERSION")))
+            .download_date(now)
+            .additional_field("converted_at".to_string(), now.to_rfc3339())
+            .additional_field("conversion_type".to_string(), "email".to_string())
+            .additional_field("url".to_string(), url.to_string());
+        for field in ["from", "to", "cc", "subject"] {
+            if let Some(value) = message.header(field).filter(|v| !v.is_empty()) {
+                builder = builder.additional_field(field.to_string(), value.to_string());
+            }
+        }
+        if let Some(date) = message.header("date") {
+            let date = DateTime::parse_from_rfc2822(date)
+                .map(|date| date.to_rfc3339())
+                .unwrap_or_else(|_| date.to_string());
+            builder = builder.additional_field("date".to_string(), date);
+        }
+        if let Some(original) = message.header("snapshot-content-location") {
+            builder = builder.additional_field("original_url".to_string(), original.to_string());
+        }
+
+        // Add custom frontmatter fields from configuration
+        for (key, value) in &self.output_config.custom_frontmatter_fields {
+            builder = builder.additional_field(key.clone(), value.clone());
+        }
+
+        let frontmatter = builder.build()?;
+        Markdown::new(format!("{frontmatter}\n{body}"))
+    }
+}
+
+impl Default for EmailConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Converter for EmailConverter {
+    async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+        let bytes = self.client.get_bytes(url).await?;
+        self.convert_message(url, &bytes)
+    }
+
+    fn name(&self) -> &'static str {
+        "Email Message"
+    }
+}
+
+/// Splits an entity at the first blank line into its headers and body.
+fn split_head(bytes: &[u8]) -> (&[u8], &[u8]) {
+    let mut position = 0;
+    while position < bytes.len() {
+        let end = bytes[position..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(bytes.len(), |p| position + p);
+        let line = &bytes[position..end];
+        if line.is_empty() || line == b"\r" {
+            return (&bytes[..position], bytes.get(end + 1..).unwrap_or_default());
+        }
+        position = end + 1;
+    }
+    (bytes, &[])
+}
+
+/// Parses header lines, joining folded continuation lines and decoding encoded words.
+fn parse_headers(head: &str) -> Vec<(String, String)> {
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for line in head.lines() {
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            let name = name.trim();
+            if !name.is_empty() && !name.contains(' ') {
+                headers.push((name.to_ascii_lowercase(), value.trim().to_string()));
+            }
+        }
+    }
+    headers
+        .into_iter()
+        .map(|(name, value)| (name, decode_encoded_words(&value)))
+        .collect()
+}
+
+/// Decodes RFC 2047 encoded words, such as `=?utf-8?B?...?=`, in a header value.
+fn decode_encoded_words(value: &str) -> String {
+    static ADJACENT: OnceLock<Regex> = OnceLock::new();
+    static ENCODED_WORD: OnceLock<Regex> = OnceLock::new();
+    let adjacent = ADJACENT.get_or_init(|| Regex::new(r"\?=\s+=\?").expect("valid pattern"));
+    let encoded_word = ENCODED_WORD.get_or_init(|| {
+        Regex::new(r"=\?([^?\s]+)\?([bBqQ])\?([^?\s]*)\?=").expect("valid encoded word pattern")
+    });
+
+    // Whitespace between two encoded words is not part of the text
+    let value = adjacent.replace_all(value, "?==?");
+    encoded_word
+        .replace_all(&value, |caps: &Captures| {
+            let bytes = if caps[2].eq_ignore_ascii_case("b") {
+                decode_base64(caps[3].as_bytes())
+            } else {
+                decode_quoted_printable(caps[3].replace('_', " ").as_bytes())
+            };
+            let charset = caps[1].split('*').next().unwrap_or_default();
+            let encoding = Encoding::for_label(charset.as_bytes()).unwrap_or(encoding_rs::UTF_8);
+            encoding.decode(&bytes).0.into_owned()
+        })
+        .into_owned()
+}
+
+/// Parses a header value such as `text/html; charset="utf-8"` into its
+/// lowercased first item and its parameters.
+///
+/// Parameter names are lowercased, and RFC 2231 values such as
+/// `filename*=UTF-8''report%20v2.pdf` are decoded.
+fn parse_parameters(value: &str) -> (String, HashMap<String, String>) {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in value.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.push(c);
+            }
+            ';' if !quoted => items.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    items.push(current);
+
+    let first = items[0].trim().to_ascii_lowercase();
+    let parameters = items[1..]
+        .iter()
+        .filter_map(|item| {
+            let (name, value) = item.split_once('=')?;
+            let name = name.trim().to_ascii_lowercase();
+            let value = value.trim().trim_matches('"');
+            match name.strip_suffix('*') {
+                Some(name) => Some((name.to_string(), decode_extended_value(value))),
+                None => Some((name, value.to_string())),
+            }
+        })
+        .collect();
+    (first, parameters)
+}
+
+/// Decodes an RFC 2231 parameter value: a charset, a language, and percent-encoded text.
+fn decode_extended_value(value: &str) -> String {
+    let mut fields = value.splitn(3, '\'');
+    let (Some(charset), Some(_language), Some(text)) =
+        (fields.next(), fields.next(), fields.next())
+    else {
+        return value.to_string();
+    };
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let escaped = (byte == b'%')
+            .then(|| tail.get(..2))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(decoded) => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            None => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    let encoding = Encoding::for_label(charset.as_bytes()).unwrap_or(encoding_rs::UTF_8);
+    encoding.decode(&bytes).0.into_owned()
+}
+
+/// Splits a multipart body into its parts at the boundary delimiter lines.
+///
+/// The preamble before the first delimiter and the epilogue after the
+/// closing one are dropped.
+fn split_multipart<'a>(body: &'a [u8], boundary: &str) -> Vec<&'a [u8]> {
+    let delimiter = format!("--{boundary}");
+    let mut parts = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut position = 0;
+    while position < body.len() {
+        let end = body[position..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(body.len(), |p| position + p);
+        if let Some(rest) = body[position..end].strip_prefix(delimiter.as_bytes()) {
+            if let Some(start) = start {
+                // The line break before a delimiter belongs to the delimiter
+                let mut stop = position.max(start);
+                if stop > start && body[stop - 1] == b'\n' {
+                    stop -= 1;
+                }
+                if stop > start && body[stop - 1] == b'\r' {
+                    stop -= 1;
+                }
+                parts.push(&body[start..stop]);
+            }
+            if rest.starts_with(b"--") {
+                return parts;
+            }
+            start = Some((end + 1).min(body.len()));
+        }
+        position = end + 1;
+    }
+    // A message cut short of its closing delimiter keeps its last part
+    if let Some(start) = start {
+        parts.push(&body[start..]);
+    }
+    parts
+}
+
+/// Decodes a base64 body, ignoring line breaks; undecodable bodies are kept as they are.
+fn decode_base64(body: &[u8]) -> Vec<u8> {
+    let compact: Vec<u8> = body
+        .iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+    base64::engine::general_purpose::STANDARD
+        .decode(&compact)
+        .unwrap_or_else(|_| body.to_vec())
+}
+
+/// Decodes a quoted-printable body, removing soft line breaks.
+fn decode_quoted_printable(body: &[u8]) -> Vec<u8> {
+    let mut decoded = Vec::with_capacity(body.len());
+    let mut i = 0;
+    while i < body.len() {
+        if body[i] != b'=' {
+            decoded.push(body[i]);
+            i += 1;
+            continue;
+        }
+        let rest = &body[i + 1..];
+        if rest.starts_with(b"\r\n") {
+            i += 3;
+        } else if rest.starts_with(b"\n") {
+            i += 2;
+        } else if let Some(byte) = rest
+            .get(..2)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(b'=');
+            i += 1;
+        }
+    }
+    decoded
+}
+
+/// Formats a size in bytes for the attachment list.
+fn format_size(bytes: usize) -> String {
+    const UNITS: [&str; 3] = ["KB", "MB", "GB"];
+    if bytes < 1024 {
+        return format!("{bytes} bytes");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE: &str = "From: =?utf-8?Q?Jos=C3=A9_Garc=C3=ADa?= <jose@example.com>\r\n\
+To: team@example.com\r\n\
+Cc: lead@example.com\r\n\
+Subject: =?utf-8?B?UTMgcmVwb3J0?=\r\n \
+ and notes\r\n\
+Date: Tue, 1 Oct 2024 09:30:00 +0200\r\n\
+MIME-Version: 1.0\r\n\
+Content-Type: multipart/mixed; boundary=\"outer\"\r\n\
+\r\n\
+This is a multi-part message in MIME format.\r\n\
+--outer\r\n\
+Content-Type: multipart/alternative; boundary=inner\r\n\
+\r\n\
+--inner\r\n\
+Content-Type: text/plain; charset=utf-8\r\n\
+\r\n\
+Plain version\r\n\
+--inner\r\n\
+Content-Type: text/html; charset=iso-8859-1\r\n\
+Content-Transfer-Encoding: quoted-printable\r\n\
+\r\n\
+<h2>Results</h2><p>Caf=E9 sales are up this quarter, as =\r\n\
+planned.</p>\r\n\
+--inner--\r\n\
+--outer\r\n\
+Content-Type: application/pdf; name=\"report.pdf\"\r\n\
+Content-Disposition: attachment; filename*=UTF-8''Q3%20report.pdf\r\n\
+Content-Transfer-Encoding: base64\r\n\
+\r\n\
+JVBERi0xLjcK\r\n\
+--outer--\r\n";
+
+    #[test]
+    fn test_convert_message() {
+        let markdown = EmailConverter::new()
+            .convert_message("q3.eml", MESSAGE.as_bytes())
+            .unwrap();
+        let markdown = markdown.as_str();
+
+        assert!(markdown.contains("from: José García <jose@example.com>"));
+        assert!(markdown.contains("to: team@example.com"));
+        assert!(markdown.contains("cc: lead@example.com"));
+        assert!(markdown.contains("subject: Q3 report and notes"));
+        assert!(markdown.contains("date: 2024-10-01T09:30:00+02:00"));
+        assert!(markdown.contains("# Results"));
+        assert!(markdown.contains("Café sales are up this quarter, as planned."));
+        assert!(!markdown.contains("Plain version"));
+        assert!(
+            markdown.ends_with("## Attachments\n\n- Q3 report.pdf (application/pdf, 9 bytes)\n")
+        );
+    }
+
+    #[test]
+    fn test_convert_web_archive() {
+        let archive = "From: <Saved by Blink>\n\
+Snapshot-Content-Location: https://example.com/post\n\
+Subject: A Post\n\
+MIME-Version: 1.0\n\
+Content-Type: multipart/related; type=\"text/html\"; boundary=\"----MultipartBoundary--abc\"\n\
+\n\
+------MultipartBoundary--abc\n\
+Content-Type: text/html\n\
+Content-Location: https://example.com/post\n\
+\n\
+<html><body><h1>A Post</h1><p>Body text.</p><img src=\"https://example.com/a.png\" alt=\"Chart\"></body></html>\n\
+------MultipartBoundary--abc\n\
+Content-Type: image/png\n\
+Content-Transfer-Encoding: base64\n\
+Content-Location: https://example.com/a.png\n\
+\n\
+iVBORw0KGgo=\n\
+------MultipartBoundary--abc--\n";
+        let markdown = EmailConverter::new()
+            .convert_message("post.mhtml", archive.as_bytes())
+            .unwrap();
+        let markdown = markdown.as_str();
+
+        assert!(markdown.contains("original_url: https://example.com/post"));
+        assert!(markdown.contains("# A Post"));
+        assert!(markdown.contains("Body text."));
+        assert!(!markdown.contains("Attachments"));
+    }
+
+    #[test]
+    fn test_convert_message_errors() {
+        let converter = EmailConverter::new();
+        for (bytes, expected) in [
+            (&b"\nno headers here"[..], ContentErrorKind::ParsingFailed),
+            (
+                &b"Subject: Empty\n\n   \n"[..],
+                ContentErrorKind::EmptyContent,
+            ),
+        ] {
+            match converter.convert_message("a.eml", bytes) {
+                Err(MarkdownError::ContentError { kind, .. }) => assert_eq!(kind, expected),
+                other => panic!("Expected ContentError, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_decoding_helpers() {
+        assert_eq!(decode_quoted_printable(b"a=3Db=\nc"), b"a=bc");
+        assert_eq!(
+            decode_encoded_words("=?ISO-8859-1?Q?Andr=E9?= =?utf-8?B?IFBpcnJl?="),
+            "André Pirre"
+        );
+        assert_eq!(format_size(512), "512 bytes");
+        assert_eq!(format_size(1536), "1.5 KB");
+    }
+}
@@ -0,0 +1,291 @@
+//! Escaping of markdown special characters in HTML text.
+//!
+//! Text content is escaped before HTML-to-markdown conversion, so only
+//! characters that came from the page's text are escaped and the markup
+//! produced by the converter is left alone. Text inside `<pre>`, `<code>`,
+//! `<kbd>`, `<samp>`, `<script>`, and `<style>` elements is never escaped.
+
+use super::config::EscapeMode;
+
+/// Elements whose text is rendered verbatim.
+const RAW_TEXT_ELEMENTS: [&str; 6] = ["pre", "code", "kbd", "samp", "script", "style"];
+
+/// Elements whose text becomes a table cell.
+const TABLE_CELL_ELEMENTS: [&str; 2] = ["td", "th"];
+
+/// Characters escaped by `EscapeMode::All`.
+const ALL_SPECIAL_CHARS: &str = "\\`*_[]<>|";
+
+/// A piece of HTML text: a character, or an entity passed through unchanged.
+#[derive(Clone, Copy)]
+enum Piece<'a> {
+    Char(char),
+    Entity(&'a str),
+}
+
+impl Piece<'_> {
+    /// Returns the character used when judging a neighbour's context.
+    fn context_char(&self) -> char {
+        match self {
+            Piece::Char(c) => *c,
+            Piece::Entity(entity) if entity.eq_ignore_ascii_case("&nbsp;") => ' ',
+            Piece::Entity(_) => 'x',
+        }
+    }
+}
+
+/// Escapes markdown special characters in the text nodes of an HTML document.
+pub struct MarkdownEscaper {
+    mode: EscapeMode,
+}
+
+impl MarkdownEscaper {
+    /// Creates a new escaper for the given mode.
+    pub fn new(mode: EscapeMode) -> Self {
+        Self { mode }
+    }
+
+    /// Escapes the text nodes of an HTML document.
+    ///
+    /// Tags, comments, and raw text elements are copied unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `html` - The HTML document
+    pub fn escape_html(&self, html: &str) -> String {
+        if self.mode == EscapeMode::Off {
+            return html.to_string();
+        }
+
+        let mut output = String::with_capacity(html.len() + html.len() / 16);
+        let mut text = String::new();
+        let mut raw_depth = 0usize;
+        let mut cell_depth = 0usize;
+        let mut rest = html;
+
+        while let Some(position) = rest.find('<') {
+            let after = &rest[position..];
+            let Some(tag_len) = tag_length(after) else {
+                // A '<' that does not start a tag is text
+                text.push_str(&rest[..=position]);
+                rest = &rest[position + 1..];
+                continue;
+            };
+
+            text.push_str(&rest[..position]);
+            output.push_str(&self.escape_text(&text, raw_depth > 0, cell_depth > 0));
+            text.clear();
+
+            let tag = &after[..tag_len];
+            if let Some((name, closing)) = tag_name(tag) {
+                let depth = if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+                    Some(&mut raw_depth)
+                } else if TABLE_CELL_ELEMENTS.contains(&name.as_str()) {
+                    Some(&mut cell_depth)
+                } else {
+                    None
+                };
+                if let Some(depth) = depth {
+                    if closing {
+                        *depth = depth.saturating_sub(1);
+                    } else if !tag.ends_with("/>") {
+                        *depth += 1;
+                    }
+                }
+            }
+            output.push_str(tag);
+            rest = &after[tag_len..];
+        }
+        text.push_str(rest);
+        output.push_str(&self.escape_text(&text, raw_depth > 0, cell_depth > 0));
+
+        output
+    }
+
+    /// Escapes a run of HTML text.
+    fn escape_text(&self, text: &str, raw: bool, in_cell: bool) -> String {
+        if raw || text.is_empty() {
+            return text.to_string();
+        }
+
+        let pieces = split_pieces(text);
+        let mut output = String::with_capacity(text.len());
+        for (index, piece) in pieces.iter().enumerate() {
+            let c = match piece {
+                Piece::Entity(entity) => {
+                    output.push_str(entity);
+                    continue;
+                }
+                Piece::Char(c) => *c,
+            };
+
+            let prev = index.checked_sub(1).map(|i| pieces[i].context_char());
+            let next = pieces.get(index + 1).map(Piece::context_char);
+            if self.needs_escape(c, prev, next, in_cell) {
+                output.push('\\');
+            }
+            // Keep markup characters encoded so the HTML parser reads them as text
+            match c {
+                '<' => output.push_str("&lt;"),
+                '>' => output.push_str("&gt;"),
+                '&' => output.push_str("&amp;"),
+                c => output.push(c),
+            }
+        }
+        output
+    }
+
+    /// Decides whether a character needs a backslash in its context.
+    fn needs_escape(&self, c: char, prev: Option<char>, next: Option<char>, in_cell: bool) -> bool {
+        let is_space = |c: Option<char>| c.is_none_or(char::is_whitespace);
+        let is_word = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+
+        match self.mode {
+            EscapeMode::Off => false,
+            EscapeMode::All => ALL_SPECIAL_CHARS.contains(c),
+            EscapeMode::Minimal | EscapeMode::PreserveHtml => match c {
+                '`' => true,
+                // Emphasis needs a non-space character on at least one side
+                '*' => !(is_space(prev) && is_space(next)),
+                // Underscores inside words never start emphasis
+                '_' => !(is_word(prev) && is_word(next) || is_space(prev) && is_space(next)),
+                '<' => {
+                    self.mode == EscapeMode::Minimal
+                        && next.is_some_and(|n| n.is_ascii_alphabetic() || "/!?".contains(n))
+                }
+                '|' => in_cell,
+                '\\' => next.is_some_and(|n| n.is_ascii_punctuation()),
+                _ => false,
+            },
+        }
+    }
+}
+
+/// Returns the length of the tag or comment at the start of `html`, if it starts one.
+fn tag_length(html: &str) -> Option<usize> {
+    if html.starts_with("<!--") {
+        return html.find("-->").map(|end| end + 3);
+    }
+    let next = html[1..].chars().next()?;
+    if !(next.is_ascii_alphabetic() || "/!?".contains(next)) {
+        return None;
+    }
+    html.find('>').map(|end| end + 1)
+}
+
+/// Returns the lowercase element name of a tag and whether it is a closing tag.
+fn tag_name(tag: &str) -> Option<(String, bool)> {
+    let inner = tag.strip_prefix('<')?;
+    let (closing, inner) = match inner.strip_prefix('/') {
+        Some(inner) => (true, inner),
+        None => (false, inner),
+    };
+    let name: String = inner
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect();
+    (!name.is_empty()).then(|| (name.to_ascii_lowercase(), closing))
+}
+
+/// Splits HTML text into characters, decoding the entities that matter for escaping.
+fn split_pieces(text: &str) -> Vec<Piece<'_>> {
+    let mut pieces = Vec::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '&' {
+            if let Some(end) = rest.find(';').filter(|&end| end <= 10) {
+                let entity = &rest[..=end];
+                let piece = match decode_entity(entity) {
+                    Some(decoded) => Piece::Char(decoded),
+                    None => Piece::Entity(entity),
+                };
+                pieces.push(piece);
+                rest = &rest[end + 1..];
+                continue;
+            }
+        }
+        pieces.push(Piece::Char(c));
+        rest = &rest[c.len_utf8()..];
+    }
+
+    pieces
+}
+
+/// Decodes entities for characters that markdown treats specially.
+fn decode_entity(entity: &str) -> Option<char> {
+    let name = entity[1..entity.len() - 1].to_ascii_lowercase();
+    let decoded = match name.as_str() {
+        "lt" => '<',
+        "gt" => '>',
+        "amp" => '&',
+        "ast" | "midast" => '*',
+        "lowbar" => '_',
+        "grave" => '`',
+        "vert" | "verbar" => '|',
+        "bsol" => '\\',
+        _ => {
+            let code = match name.strip_prefix("#x") {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => name.strip_prefix('#')?.parse().ok()?,
+            };
+            char::from_u32(code).filter(|c| "<>&*_`|\\".contains(*c))?
+        }
+    };
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn escape(mode: EscapeMode, html: &str) -> String {
+        MarkdownEscaper::new(mode).escape_html(html)
+    }
+
+    #[test]
+    fn test_minimal_escapes_only_ambiguous_characters() {
+        let html =
+            "<p>a*b*c, 5 * 3, snake_case, _under_, `tick`, a &lt; b, &lt;div&gt;, C:\\dir \\*</p>";
+
+        assert_eq!(
+            escape(EscapeMode::Minimal, html),
+            "<p>a\\*b\\*c, 5 * 3, snake_case, \\_under\\_, \\`tick\\`, a &lt; b, \\&lt;div&gt;, C:\\dir \\\\\\*</p>"
+        );
+    }
+
+    #[test]
+    fn test_minimal_escapes_pipes_in_table_cells() {
+        let html = "<p>a | b</p><table><tr><td>x | y</td><td><code>p|q</code></td></tr></table>";
+
+        assert_eq!(
+            escape(EscapeMode::Minimal, html),
+            "<p>a | b</p><table><tr><td>x \\| y</td><td><code>p|q</code></td></tr></table>"
+        );
+    }
+
+    #[test]
+    fn test_escape_all_and_preserve_html() {
+        let html = "<p>5 * 3 | &lt;b&gt;</p><pre>a*b</pre>";
+
+        assert_eq!(
+            escape(EscapeMode::All, html),
+            "<p>5 \\* 3 \\| \\&lt;b\\&gt;</p><pre>a*b</pre>"
+        );
+        assert_eq!(
+            escape(EscapeMode::PreserveHtml, html),
+            "<p>5 * 3 | &lt;b&gt;</p><pre>a*b</pre>"
+        );
+        assert_eq!(escape(EscapeMode::Off, "<p>a*b</p>"), "<p>a*b</p>");
+    }
+
+    #[test]
+    fn test_comments_entities_and_stray_brackets() {
+        let html = "<!-- a*b --><p>1 < 2 &nbsp;*&nbsp; &copy; x&#95;</p>";
+
+        assert_eq!(
+            escape(EscapeMode::Minimal, html),
+            "<!-- a*b --><p>1 &lt; 2 &nbsp;*&nbsp; &copy; x\\_</p>"
+        );
+    }
+}
@@ -0,0 +1,273 @@
+//! Excerpt generation from HTML paragraphs or converted markdown.
+//! The excerpt is the first few sentences of the main prose, skipping headings,
+//! lists, tables, quotes and code so that previews read like the article itself.
+//! HTML is read before conversion, where paragraph boundaries are still exact.
+
+use regex::Regex;
+
+/// Builds short plain-text excerpts from markdown content.
+pub struct ExcerptExtractor {
+    main_content: Regex,
+    page_chrome: Regex,
+    paragraph: Regex,
+    tag: Regex,
+    image: Regex,
+    link: Regex,
+    emphasis: Regex,
+    sentence_end: Regex,
+}
+
+impl ExcerptExtractor {
+    /// Creates a new excerpt extractor.
+    pub fn new() -> Self {
+        Self {
+            main_content: Regex::new(r"(?is)<(?:main|article)\b[^>]*>(.*)</(?:main|article)>")
+                .expect("valid main content pattern"),
+            page_chrome: Regex::new(
+                r"(?is)<(?:nav|header|footer|aside)\b.*?</(?:nav|header|footer|aside)>",
+            )
+            .expect("valid page chrome pattern"),
+            paragraph: Regex::new(r"(?is)<p\b[^>]*>(.*?)</p>").expect("valid paragraph pattern"),
+            tag: Regex::new(r"(?s)<[^>]*>").expect("valid tag pattern"),
+            image: Regex::new(r"!\[[^\]]*\]\([^)]*\)").expect("valid image pattern"),
+            link: Regex::new(r"\[([^\]]*)\]\([^)]*\)").expect("valid link pattern"),
+            emphasis: Regex::new(r"[*_`~]+").expect("valid emphasis pattern"),
+            sentence_end: Regex::new(r#"[.!?]["')\]]*\s+"#).expect("valid sentence pattern"),
+        }
+    }
+
+    /// Returns the first `sentences` sentences of the markdown's prose.
+    ///
+    /// # Arguments
+    ///
+    /// * `markdown` - The converted markdown, without frontmatter
+    /// * `sentences` - The maximum number of sentences to include
+    ///
+    /// # Returns
+    ///
+    /// The excerpt as plain text, or `None` if the document has no prose paragraphs.
+    pub fn extract(&self, markdown: &str, sentences: usize) -> Option<String> {
+        first_sentences(
+            self.prose_paragraphs(markdown),
+            &self.sentence_end,
+            sentences,
+        )
+    }
+
+    /// Returns the first `sentences` sentences of the HTML's `<p>` paragraphs.
+    ///
+    /// Paragraphs are taken from the `<main>` or `<article>` element when the
+    /// page has one, skipping navigation, headers, footers and sidebars.
+    ///
+    /// # Arguments
+    ///
+    /// * `html` - The HTML document, before conversion
+    /// * `sentences` - The maximum number of sentences to include
+    ///
+    /// # Returns
+    ///
+    /// The excerpt as plain text, or `None` if the page has no paragraphs.
+    pub fn extract_html(&self, html: &str, sentences: usize) -> Option<String> {
+        let content = self
+            .main_content
+            .captures(html)
+            .and_then(|captures| captures.get(1))
+            .map_or(html, |content| content.as_str());
+        let content = self.page_chrome.replace_all(content, "");
+
+        let paragraphs = self
+            .paragraph
+            .captures_iter(&content)
+            .map(|captures| {
+                // Inline tags join their text; only line breaks separate words
+                let text = self.tag.replace_all(&captures[1], |tag: &regex::Captures| {
+                    if tag[0].to_ascii_lowercase().starts_with("<br") {
+                        " "
+                    } else {
+                        ""
+                    }
+                });
+                decode_entities(&text)
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .filter(|text| !text.is_empty())
+            .collect();
+        first_sentences(paragraphs, &self.sentence_end, sentences)
+    }
+
+    /// Returns the plain text of each prose paragraph, in document order.
+    fn prose_paragraphs(&self, markdown: &str) -> Vec<String> {
+        let mut paragraphs = Vec::new();
+        let mut current: Vec<&str> = Vec::new();
+        let mut in_code_block = false;
+
+        for line in markdown.lines() {
+            let trimmed = line.trim();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_code_block = !in_code_block;
+                self.flush_paragraph(&mut current, &mut paragraphs);
+                continue;
+            }
+
+            if in_code_block || trimmed.is_empty() || !is_prose_line(line) {
+                self.flush_paragraph(&mut current, &mut paragraphs);
+                continue;
+            }
+
+            current.push(trimmed);
+        }
+        self.flush_paragraph(&mut current, &mut paragraphs);
+
+        paragraphs
+    }
+
+    /// Strips inline formatting from the buffered lines and stores the paragraph.
+    fn flush_paragraph(&self, lines: &mut Vec<&str>, paragraphs: &mut Vec<String>) {
+        if lines.is_empty() {
+            return;
+        }
+
+        let joined = lines.join(" ");
+        lines.clear();
+
+        let text = self.image.replace_all(&joined, "");
+        let text = self.link.replace_all(&text, "$1");
+        let text = self.emphasis.replace_all(&text, "");
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !text.is_empty() {
+            paragraphs.push(text);
+        }
+    }
+}
+
+impl Default for ExcerptExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Joins the first `sentences` sentences of the paragraphs.
+fn first_sentences(
+    paragraphs: Vec<String>,
+    sentence_end: &Regex,
+    sentences: usize,
+) -> Option<String> {
+    if sentences == 0 {
+        return None;
+    }
+
+    let mut excerpt: Vec<String> = Vec::new();
+    for paragraph in paragraphs {
+        let mut start = 0;
+        for boundary in sentence_end.find_iter(&paragraph) {
+            excerpt.push(paragraph[start..boundary.end()].trim().to_string());
+            start = boundary.end();
+            if excerpt.len() == sentences {
+                return Some(excerpt.join(" "));
+            }
+        }
+
+        let rest = paragraph[start..].trim();
+        if !rest.is_empty() {
+            excerpt.push(rest.to_string());
+            if excerpt.len() == sentences {
+                break;
+            }
+        }
+    }
+
+    (!excerpt.is_empty()).then(|| excerpt.join(" "))
+}
+
+/// Decodes the HTML entities common in paragraph text.
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+/// Returns false for headings, list items, quotes, tables, rules and indented code.
+fn is_prose_line(line: &str) -> bool {
+    if line.starts_with("    ") || line.starts_with('\t') {
+        return false;
+    }
+
+    let trimmed = line.trim_start();
+    let is_list_item = trimmed.starts_with("- ")
+        || trimmed.starts_with("* ")
+        || trimmed.starts_with("+ ")
+        || trimmed
+            .split_once(". ")
+            .is_some_and(|(number, _)| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()));
+    let is_rule = trimmed.chars().all(|c| matches!(c, '-' | '*' | '_' | '=' | ' '));
+
+    !(is_list_item
+        || is_rule
+        || trimmed.starts_with('#')
+        || trimmed.starts_with('>')
+        || trimmed.starts_with('|')
+        || trimmed.starts_with("<!--"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extracts_first_sentences() {
+        let extractor = ExcerptExtractor::new();
+        let markdown = "# Title\n\nFirst sentence here. Second one? Third!\n\nAnother paragraph.";
+
+        assert_eq!(
+            extractor.extract(markdown, 2),
+            Some("First sentence here. Second one?".to_string())
+        );
+        assert_eq!(
+            extractor.extract(markdown, 4),
+            Some("First sentence here. Second one? Third! Another paragraph.".to_string())
+        );
+        assert_eq!(extractor.extract(markdown, 0), None);
+    }
+
+    #[test]
+    fn test_keeps_decimal_numbers_and_strips_formatting() {
+        let extractor = ExcerptExtractor::new();
+        let markdown = "Version 4.2 ships **today** with [new docs](https://example.com).\nIt wraps lines. Done.";
+
+        assert_eq!(
+            extractor.extract(markdown, 2),
+            Some("Version 4.2 ships today with new docs. It wraps lines.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_skips_non_prose_blocks() {
+        let extractor = ExcerptExtractor::new();
+        let markdown = "## Contents\n\n- Item one.\n1. Numbered.\n\n> Quoted.\n\n```\ncode. here.\n```\n\n| a | b |\n\n---\n\nThe real text.";
+
+        assert_eq!(
+            extractor.extract(markdown, 3),
+            Some("The real text.".to_string())
+        );
+        assert_eq!(extractor.extract("# Only a heading", 3), None);
+    }
+
+    #[test]
+    fn test_extracts_html_paragraphs() {
+        let extractor = ExcerptExtractor::new();
+        let html = "<nav><p>Home. About.</p></nav><main><h1>Title</h1>\
+            <p>Version 4.2 ships <b>today</b>\n with <a href=\"/docs\">new docs</a>.<br>Fish &amp; chips</p>\
+            <pre>code. here.</pre><p>Last one.</p></main><footer><p>Copyright.</p></footer>";
+
+        assert_eq!(
+            extractor.extract_html(html, 3),
+            Some("Version 4.2 ships today with new docs. Fish & chips Last one.".to_string())
+        );
+        assert_eq!(extractor.extract_html("<div>No paragraphs.</div>", 2), None);
+    }
+}
@@ -0,0 +1,353 @@
+//! Summaries of forms and interactive controls.
+//!
+//! html2text drops form controls and keeps only stray label text, which hides
+//! what a page lets the reader do. When summarization is enabled, each form
+//! is replaced with a short note listing its fields and buttons, and controls
+//! outside forms are replaced with an inline `[Button: ...]` style marker.
+
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+
+/// Maximum number of select options listed for a field.
+const MAX_LISTED_OPTIONS: usize = 10;
+
+/// Input types that render as buttons rather than fields.
+const BUTTON_INPUT_TYPES: [&str; 4] = ["submit", "button", "reset", "image"];
+
+/// A control found in a form.
+enum Control {
+    Field(Field),
+    Button(String),
+    Hidden,
+}
+
+/// A form field, described by its label and kind.
+struct Field {
+    /// The `name` attribute, used to group radio buttons
+    name: Option<String>,
+    label: String,
+    kind: String,
+    required: bool,
+    /// Choices for selects and radio groups
+    options: Vec<String>,
+}
+
+impl Field {
+    fn describe(&self) -> String {
+        let mut details = self.kind.clone();
+        if !self.options.is_empty() {
+            let shown = self.options.len().min(MAX_LISTED_OPTIONS);
+            details.push_str(": ");
+            details.push_str(&self.options[..shown].join(", "));
+            if self.options.len() > shown {
+                details.push_str(&format!(", and {} more", self.options.len() - shown));
+            }
+        }
+        if self.required {
+            details.push_str(", required");
+        }
+        format!("{} ({details})", self.label)
+    }
+}
+
+/// Replaces forms and interactive controls with readable summaries.
+pub struct FormSummarizer {
+    form: Regex,
+    control: Regex,
+    label: Regex,
+    legend: Regex,
+    option: Regex,
+    attribute: Regex,
+    tag: Regex,
+}
+
+impl FormSummarizer {
+    /// Creates a new form summarizer.
+    pub fn new() -> Self {
+        Self {
+            form: Regex::new(r"(?is)<form\b([^>]*)>(.*?)</form\s*>").expect("valid form pattern"),
+            control: Regex::new(
+                r"(?is)<input\b([^>]*)>|<select\b([^>]*)>(.*?)</select\s*>|<textarea\b([^>]*)>.*?</textarea\s*>|<button\b([^>]*)>(.*?)</button\s*>",
+            )
+            .expect("valid control pattern"),
+            label: Regex::new(r"(?is)<label\b([^>]*)>(.*?)</label\s*>").expect("valid label pattern"),
+            legend: Regex::new(r"(?is)<legend\b[^>]*>(.*?)</legend\s*>")
+                .expect("valid legend pattern"),
+            option: Regex::new(r"(?is)<option\b[^>]*>(.*?)</option\s*>")
+                .expect("valid option pattern"),
+            attribute: Regex::new(
+                r#"([a-zA-Z_:][-a-zA-Z0-9_:.]*)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+)))?"#,
+            )
+            .expect("valid attribute pattern"),
+            tag: Regex::new(r"<[^>]*>").expect("valid tag pattern"),
+        }
+    }
+
+    /// Replaces every form with a summary note and every other control with a marker.
+    ///
+    /// # Arguments
+    ///
+    /// * `html` - The HTML document
+    pub fn summarize(&self, html: &str) -> String {
+        let with_forms = self.form.replace_all(html, |caps: &Captures| {
+            self.summarize_form(&caps[1], &caps[2])
+        });
+
+        self.control
+            .replace_all(&with_forms, |caps: &Captures| {
+                let labels = HashMap::new();
+                match self.parse_control(caps, &labels) {
+                    Control::Button(text) => format!("[Button: {text}]"),
+                    Control::Field(field) => format!("[Field: {}]", field.describe()),
+                    Control::Hidden => String::new(),
+                }
+            })
+            .into_owned()
+    }
+
+    /// Renders a form as a blockquote listing its fields and buttons.
+    fn summarize_form(&self, attributes: &str, body: &str) -> String {
+        let attributes = self.parse_attributes(attributes);
+        let labels = self.collect_labels(body);
+
+        let mut fields: Vec<Field> = Vec::new();
+        let mut buttons = Vec::new();
+        for caps in self.control.captures_iter(body) {
+            match self.parse_control(&caps, &labels) {
+                Control::Button(text) => buttons.push(text),
+                Control::Hidden => {}
+                Control::Field(field) if field.kind == "radio" => {
+                    // Radio buttons sharing a name are one field with several choices
+                    let group = fields
+                        .iter_mut()
+                        .find(|f| f.kind == "radio" && f.name.is_some() && f.name == field.name);
+                    match group {
+                        Some(group) => group.options.push(field.label),
+                        None => fields.push(Field {
+                            label: field.name.clone().unwrap_or_else(|| field.label.clone()),
+                            options: vec![field.label],
+                            ..field
+                        }),
+                    }
+                }
+                Control::Field(field) => fields.push(field),
+            }
+        }
+
+        let title = attributes
+            .get("aria-label")
+            .cloned()
+            .or_else(|| {
+                self.legend
+                    .captures(body)
+                    .map(|caps| self.plain_text(&caps[1]))
+            })
+            .or_else(|| attributes.get("name").cloned())
+            .filter(|title| !title.is_empty());
+        let mut heading = match title {
+            Some(title) => format!("<strong>Form: {title}</strong>"),
+            None => "<strong>Form</strong>".to_string(),
+        };
+        if let Some(action) = attributes.get("action") {
+            let method = attributes
+                .get("method")
+                .map(|method| method.to_ascii_uppercase())
+                .unwrap_or_else(|| "GET".to_string());
+            heading.push_str(&format!(" ({method} {action})"));
+        }
+
+        let mut summary = format!("<blockquote><p>{heading}</p>");
+        if !fields.is_empty() {
+            summary.push_str("<ul>");
+            for field in &fields {
+                summary.push_str(&format!("<li>{}</li>", field.describe()));
+            }
+            summary.push_str("</ul>");
+        }
+        if !buttons.is_empty() {
+            summary.push_str(&format!("<p>Buttons: {}</p>", buttons.join(", ")));
+        }
+        summary.push_str("</blockquote>");
+        summary
+    }
+
+    /// Describes the control matched by `caps`, using `labels` to name it.
+    fn parse_control(&self, caps: &Captures, labels: &HashMap<String, String>) -> Control {
+        let (attributes, kind) = if let Some(attributes) = caps.get(1) {
+            let attributes = self.parse_attributes(attributes.as_str());
+            let kind = attributes
+                .get("type")
+                .map(|kind| kind.to_ascii_lowercase())
+                .unwrap_or_else(|| "text".to_string());
+            if BUTTON_INPUT_TYPES.contains(&kind.as_str()) {
+                let text = attributes
+                    .get("value")
+                    .or_else(|| attributes.get("alt"))
+                    .cloned()
+                    .unwrap_or_else(|| "Submit".to_string());
+                return Control::Button(text);
+            }
+            if kind == "hidden" {
+                return Control::Hidden;
+            }
+            (attributes, kind)
+        } else if let Some(attributes) = caps.get(2) {
+            (
+                self.parse_attributes(attributes.as_str()),
+                "select".to_string(),
+            )
+        } else if let Some(attributes) = caps.get(4) {
+            (
+                self.parse_attributes(attributes.as_str()),
+                "text area".to_string(),
+            )
+        } else {
+            let attributes = self.parse_attributes(caps.get(5).map_or("", |m| m.as_str()));
+            let text = self.plain_text(caps.get(6).map_or("", |m| m.as_str()));
+            let text = Some(text)
+                .filter(|text| !text.is_empty())
+                .or_else(|| attributes.get("aria-label").cloned())
+                .unwrap_or_else(|| "Button".to_string());
+            return Control::Button(text);
+        };
+
+        let options = caps
+            .get(3)
+            .map(|body| {
+                self.option
+                    .captures_iter(body.as_str())
+                    .map(|option| self.plain_text(&option[1]))
+                    .filter(|option| !option.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let name = attributes.get("name").cloned();
+        let label = attributes
+            .get("id")
+            .and_then(|id| labels.get(id))
+            .or_else(|| name.as_ref().and_then(|name| labels.get(name)))
+            .or_else(|| attributes.get("aria-label"))
+            .or_else(|| attributes.get("placeholder"))
+            .or_else(|| attributes.get("title"))
+            .or(name.as_ref())
+            .cloned()
+            .unwrap_or_else(|| kind.clone());
+
+        Control::Field(Field {
+            name,
+            label,
+            kind,
+            required: attributes.contains_key("required"),
+            options,
+        })
+    }
+
+    /// Maps control ids (from `for`) and wrapped control ids or names to label text.
+    fn collect_labels(&self, body: &str) -> HashMap<String, String> {
+        let mut labels = HashMap::new();
+        for caps in self.label.captures_iter(body) {
+            let attributes = self.parse_attributes(&caps[1]);
+            let content = &caps[2];
+            let text = self.plain_text(&self.control.replace_all(content, ""));
+            if text.is_empty() {
+                continue;
+            }
+
+            if let Some(target) = attributes.get("for") {
+                labels.insert(target.clone(), text);
+            } else if let Some(wrapped) = self.control.captures(content) {
+                let attrs = [1, 2, 4, 5]
+                    .into_iter()
+                    .find_map(|group| wrapped.get(group))
+                    .map(|m| self.parse_attributes(m.as_str()))
+                    .unwrap_or_default();
+                if let Some(key) = attrs.get("id").or_else(|| attrs.get("name")) {
+                    labels.insert(key.clone(), text);
+                }
+            }
+        }
+        labels
+    }
+
+    /// Parses tag attributes into a map keyed by lowercase attribute name.
+    fn parse_attributes(&self, attributes: &str) -> HashMap<String, String> {
+        self.attribute
+            .captures_iter(attributes)
+            .map(|caps| {
+                let value = caps
+                    .get(2)
+                    .or_else(|| caps.get(3))
+                    .or_else(|| caps.get(4))
+                    .map_or("", |m| m.as_str());
+                (
+                    caps[1].to_ascii_lowercase(),
+                    value.trim().replace('<', "&lt;").replace('>', "&gt;"),
+                )
+            })
+            .collect()
+    }
+
+    /// Strips tags and collapses whitespace, dropping a trailing colon.
+    fn plain_text(&self, html: &str) -> String {
+        let text = self.tag.replace_all(html, " ");
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        text.trim_end_matches(':').trim_end().to_string()
+    }
+}
+
+impl Default for FormSummarizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarizes_form_fields_and_buttons() {
+        let html = r#"<form action="/login" method="post" aria-label="Sign in">
+            <label for="email">Email address:</label>
+            <input type="email" id="email" name="email" required>
+            <label>Password <input type="password" name="password"></label>
+            <input type="hidden" name="csrf" value="x">
+            <select name="region"><option>EU</option><option>US</option></select>
+            <button type="submit">Sign in</button>
+        </form>"#;
+
+        let summary = FormSummarizer::new().summarize(html);
+        assert_eq!(
+            summary,
+            "<blockquote><p><strong>Form: Sign in</strong> (POST /login)</p><ul>\
+             <li>Email address (email, required)</li>\
+             <li>Password (password)</li>\
+             <li>region (select: EU, US)</li>\
+             </ul><p>Buttons: Sign in</p></blockquote>"
+        );
+    }
+
+    #[test]
+    fn test_groups_radio_buttons() {
+        let html = r#"<form><fieldset><legend>Plan</legend>
+            <label><input type="radio" name="plan" id="free"> Free</label>
+            <label><input type="radio" name="plan" id="pro"> Pro</label>
+            <input type="submit"></fieldset></form>"#;
+
+        let summary = FormSummarizer::new().summarize(html);
+        assert!(summary.contains("<strong>Form: Plan</strong></p>"));
+        assert!(summary.contains("<li>plan (radio: Free, Pro)</li>"));
+        assert!(summary.contains("<p>Buttons: Submit</p>"));
+    }
+
+    #[test]
+    fn test_marks_controls_outside_forms() {
+        let html = r#"<p>Stay informed <input type="email" placeholder="Your email"> <button>Subscribe</button></p>"#;
+
+        assert_eq!(
+            FormSummarizer::new().summarize(html),
+            "<p>Stay informed [Field: Your email (email)] [Button: Subscribe]</p>"
+        );
+    }
+}
@@ -4,11 +4,22 @@
 //! by leveraging the GitHub REST API. It handles issue fetching, comment retrieval,
 //! authentication, and proper markdown rendering.
 //!
+//! For pull requests the converter also fetches submitted reviews and all inline
+//! review comments (including resolved threads that the web UI collapses behind
+//! "hidden items"), so the converted discussion is complete.
+//!
+//! List endpoints are paginated by following the `Link` headers GitHub sends,
+//! so issues with hundreds of comments are converted completely unless a
+//! comment limit is set with [`GitHubConverter::with_max_comments`].
+//!
 //! # Supported URLs
 //!
 //! - Issues: `https://github.com/{owner}/{repo}/issues/{number}`
 //! - Pull Requests: `https://github.com/{owner}/{repo}/pull/{number}`
 //!
+//! With a GitHub Enterprise Server API base URL set, the same URLs on the
+//! server's host are accepted too.
+//!
 //! # Usage Examples
 //!
 //! ## Basic Conversion (Public Repository)
@@ -45,6 +56,7 @@ use crate::frontmatter::FrontmatterBuilder;
 use crate::types::{Markdown, MarkdownError};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use std::collections::HashMap;
 use url::Url as ParsedUrl;
@@ -58,6 +70,15 @@ const GITHUB_API_VERSION: &str = "application/vnd.github.v3+json";
 /// User-Agent string prefix for GitHub API requests
 const USER_AGENT_PREFIX: &str = "markdowndown";
 
+/// Page size requested from list endpoints (GitHub's maximum)
+const API_PAGE_SIZE: u32 = 100;
+
+/// Upper bound on pages fetched from one list endpoint, as a guard against link loops
+const MAX_API_PAGES: usize = 100;
+
+/// Number of trailing diff hunk lines shown above each review thread
+const DIFF_HUNK_CONTEXT_LINES: usize = 6;
+
 /// GitHub resource types supported for conversion.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ResourceType {
@@ -125,6 +146,9 @@ pub struct Issue {
     pub labels: Vec<Label>,
     /// Whether this is a pull request
     pub pull_request: Option<PullRequestRef>,
+    /// Total number of comments on the issue
+    #[serde(default)]
+    pub comments: u32,
 }
 
 /// GitHub user information.
@@ -170,6 +194,114 @@ pub struct Comment {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Pull request review data from API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Review {
+    /// Review ID
+    pub id: u64,
+    /// User who submitted the review
+    pub user: User,
+    /// Review summary body (markdown)
+    #[serde(default)]
+    pub body: Option<String>,
+    /// Review state (APPROVED, CHANGES_REQUESTED, COMMENTED, DISMISSED, PENDING)
+    pub state: String,
+    /// Review submission timestamp (absent for pending reviews)
+    #[serde(default)]
+    pub submitted_at: Option<DateTime<Utc>>,
+}
+
+/// Inline pull request review comment from API.
+///
+/// Review comments are returned by the API regardless of whether their thread
+/// has been resolved or collapsed in the web UI.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReviewComment {
+    /// Comment ID
+    pub id: u64,
+    /// Comment body content (markdown)
+    #[serde(default)]
+    pub body: Option<String>,
+    /// User who created the comment
+    pub user: User,
+    /// Comment creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Comment update timestamp
+    pub updated_at: DateTime<Utc>,
+    /// Path of the file the comment refers to
+    pub path: String,
+    /// Line in the file the comment refers to (absent for outdated comments)
+    #[serde(default)]
+    pub line: Option<u32>,
+    /// Line in the original diff the comment was made on
+    #[serde(default)]
+    pub original_line: Option<u32>,
+    /// Diff hunk surrounding the commented line
+    #[serde(default)]
+    pub diff_hunk: Option<String>,
+    /// ID of the comment this one replies to (thread root for replies)
+    #[serde(default)]
+    pub in_reply_to_id: Option<u64>,
+    /// ID of the review this comment belongs to
+    #[serde(default)]
+    pub pull_request_review_id: Option<u64>,
+}
+
+/// An inline review thread: a root comment and its replies in chronological order.
+#[derive(Debug, Clone)]
+pub struct ReviewThread {
+    /// The comment that started the thread
+    pub root: ReviewComment,
+    /// Replies to the root comment
+    pub replies: Vec<ReviewComment>,
+}
+
+impl ReviewThread {
+    /// Groups flat review comments into threads keyed by their root comment.
+    ///
+    /// Replies whose root comment is missing from the list are promoted to
+    /// thread roots so no comment is ever dropped.
+    pub fn group(comments: &[ReviewComment]) -> Vec<ReviewThread> {
+        let mut sorted: Vec<&ReviewComment> = comments.iter().collect();
+        sorted.sort_by_key(|c| (c.created_at, c.id));
+
+        let mut threads: Vec<ReviewThread> = Vec::new();
+        let mut thread_index_by_comment: HashMap<u64, usize> = HashMap::new();
+
+        for comment in sorted {
+            let parent_index = comment
+                .in_reply_to_id
+                .and_then(|id| thread_index_by_comment.get(&id).copied());
+
+            let index = match parent_index {
+                Some(index) => {
+                    threads[index].replies.push(comment.clone());
+                    index
+                }
+                None => {
+                    threads.push(ReviewThread {
+                        root: comment.clone(),
+                        replies: Vec::new(),
+                    });
+                    threads.len() - 1
+                }
+            };
+            thread_index_by_comment.insert(comment.id, index);
+        }
+
+        threads
+    }
+}
+
+/// Review data fetched for a pull request.
+#[derive(Debug, Clone, Default)]
+pub struct PullRequestReviews {
+    /// Submitted reviews
+    pub reviews: Vec<Review>,
+    /// All inline review comments, including resolved and outdated threads
+    pub comments: Vec<ReviewComment>,
+}
+
 /// GitHub reaction data.
 #[derive(Debug, Clone, Deserialize)]
 pub struct Reaction {
@@ -238,6 +370,8 @@ pub struct GitHubConverter {
     auth_token: Option<String>,
     /// Base URL for GitHub API (allows for GitHub Enterprise)
     api_base_url: String,
+    /// Maximum number of issue comments to fetch (None fetches all)
+    max_comments: Option<usize>,
 }
 
 impl GitHubConverter {
@@ -258,6 +392,7 @@ impl GitHubConverter {
             client: HttpClient::new(),
             auth_token: None,
             api_base_url: DEFAULT_GITHUB_API_BASE_URL.to_string(),
+            max_comments: None,
         }
     }
 
@@ -283,6 +418,7 @@ impl GitHubConverter {
             client: HttpClient::new(),
             auth_token: Some(token),
             api_base_url: DEFAULT_GITHUB_API_BASE_URL.to_string(),
+            max_comments: None,
         }
     }
 
@@ -309,6 +445,7 @@ impl GitHubConverter {
             client: HttpClient::new(),
             auth_token: token,
             api_base_url,
+            max_comments: None,
         }
     }
 
@@ -332,15 +469,82 @@ impl GitHubConverter {
         }
     }
 
+    /// Records this converter's GitHub API requests in a shared statistics recorder.
+    ///
+    /// # Arguments
+    ///
+    /// * `stats` - The per-host statistics recorder to share
+    pub fn with_stats(mut self, stats: crate::stats::HostStatsRecorder) -> Self {
+        self.client = self.client.with_stats(stats);
+        self
+    }
+
+    /// Applies request templates to this converter's GitHub API requests.
+    ///
+    /// Template headers replace the converter's own, so a template can ask
+    /// for a preview media type in place of the default `Accept` header.
+    ///
+    /// # Arguments
+    ///
+    /// * `templates` - Query parameters and headers for matching services
+    pub fn with_request_templates(mut self, templates: Vec<crate::config::RequestTemplate>) -> Self {
+        self.client = self.client.with_request_templates(templates);
+        self
+    }
+
+    /// Fetches from the API of a GitHub Enterprise Server.
+    ///
+    /// Issue and pull request URLs on the server's host are accepted in
+    /// addition to github.com URLs.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_base_url` - The REST API root, usually `https://<host>/api/v3`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::converters::GitHubConverter;
+    ///
+    /// let converter = GitHubConverter::from_env()
+    ///     .with_api_base_url("https://github.example.com/api/v3");
+    /// ```
+    pub fn with_api_base_url<T: Into<String>>(mut self, api_base_url: T) -> Self {
+        self.api_base_url = api_base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Limits how many issue comments are fetched and rendered.
+    ///
+    /// Comments are fetched oldest first, so the limit keeps the start of the
+    /// discussion. A note below the comments says how many were left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - The maximum number of comments to include
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::converters::GitHubConverter;
+    ///
+    /// let converter = GitHubConverter::new().with_max_comments(200);
+    /// ```
+    pub fn with_max_comments(mut self, max: usize) -> Self {
+        self.max_comments = Some(max);
+        self
+    }
+
     /// Converts a GitHub issue or pull request URL to markdown with frontmatter.
     ///
     /// This method performs the complete conversion workflow:
     /// 1. Parse and validate the GitHub URL
     /// 2. Fetch issue/PR data from GitHub API
     /// 3. Fetch all comments and reactions
-    /// 4. Render issue and comments as markdown
-    /// 5. Generate frontmatter with metadata
-    /// 6. Combine frontmatter with content
+    /// 4. For pull requests, fetch reviews and inline review threads
+    /// 5. Render issue, comments and reviews as markdown
+    /// 6. Generate frontmatter with metadata
+    /// 7. Combine frontmatter with content
     ///
     /// # Arguments
     ///
@@ -381,8 +585,15 @@ impl GitHubConverter {
         // Step 2-3: Fetch issue/PR data and comments from GitHub API
         let (issue, comments) = self.fetch_issue_and_comments(&resource).await?;
 
-        // Step 4-6: Render content and create final markdown
-        self.create_markdown_document(&resource, &issue, &comments)
+        // Step 4: Pull requests carry review discussion outside the issue comments
+        let reviews = if issue.pull_request.is_some() {
+            Some(self.fetch_pull_request_reviews(&resource).await?)
+        } else {
+            None
+        };
+
+        // Step 5-7: Render content and create final markdown
+        self.create_markdown_document(&resource, &issue, &comments, reviews.as_ref())
     }
 
     /// Fetches issue/PR data and comments in parallel for better performance.
@@ -398,18 +609,41 @@ impl GitHubConverter {
         Ok((issue, comments))
     }
 
+    /// Fetches reviews and inline review comments for a pull request concurrently.
+    async fn fetch_pull_request_reviews(
+        &self,
+        resource: &GitHubResource,
+    ) -> Result<PullRequestReviews, MarkdownError> {
+        let reviews_future = self.fetch_reviews(&resource.owner, &resource.repo, resource.number);
+        let comments_future =
+            self.fetch_review_comments(&resource.owner, &resource.repo, resource.number);
+
+        let (reviews, comments) = tokio::try_join!(reviews_future, comments_future)?;
+        Ok(PullRequestReviews { reviews, comments })
+    }
+
     /// Creates the final markdown document with frontmatter and content.
     fn create_markdown_document(
         &self,
         resource: &GitHubResource,
         issue: &Issue,
         comments: &[Comment],
+        reviews: Option<&PullRequestReviews>,
     ) -> Result<Markdown, MarkdownError> {
         // Render issue and comments as markdown
-        let content = self.render_markdown(issue, comments);
+        let mut content = self.render_markdown(issue, comments);
+
+        // Append review discussion for pull requests
+        if let Some(reviews) = reviews {
+            let review_section = self.render_reviews(reviews);
+            if !review_section.is_empty() {
+                content.push_str("\n\n");
+                content.push_str(&review_section);
+            }
+        }
 
         // Generate frontmatter with metadata
-        let frontmatter = self.build_frontmatter(resource, issue)?;
+        let frontmatter = self.build_frontmatter(resource, issue, reviews)?;
 
         // Combine frontmatter with content
         let markdown_with_frontmatter = format!("{frontmatter}\n{content}");
@@ -452,7 +686,8 @@ impl GitHubConverter {
                 url: url.to_string(),
             })?;
 
-        if host != "github.com" {
+        let enterprise_host = crate::utils::github_enterprise_host(&self.api_base_url);
+        if host != "github.com" && enterprise_host.as_deref() != Some(host) {
             return Err(MarkdownError::InvalidUrl {
                 url: url.to_string(),
             });
@@ -520,6 +755,8 @@ impl GitHubConverter {
     }
 
     /// Fetches all comments for an issue or pull request from GitHub API.
+    ///
+    /// Every page is fetched, up to the converter's comment limit if one is set.
     pub async fn fetch_comments(
         &self,
         owner: &str,
@@ -527,21 +764,95 @@ impl GitHubConverter {
         number: u32,
     ) -> Result<Vec<Comment>, MarkdownError> {
         let url = format!(
-            "{}/repos/{}/{}/issues/{}/comments",
+            "{}/repos/{}/{}/issues/{}/comments?per_page={API_PAGE_SIZE}",
             self.api_base_url, owner, repo, number
         );
 
-        let response_text = self.make_api_request(&url).await?;
+        self.fetch_all_pages(url, self.max_comments, "comments")
+            .await
+    }
+
+    /// Fetches all submitted reviews for a pull request from GitHub API.
+    pub async fn fetch_reviews(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u32,
+    ) -> Result<Vec<Review>, MarkdownError> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/reviews?per_page={API_PAGE_SIZE}",
+            self.api_base_url, owner, repo, number
+        );
+
+        self.fetch_all_pages(url, None, "reviews").await
+    }
+
+    /// Fetches all inline review comments for a pull request from GitHub API.
+    ///
+    /// This includes comments in resolved and outdated threads, which the web UI
+    /// hides behind "Show resolved" and "hidden items" toggles.
+    pub async fn fetch_review_comments(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u32,
+    ) -> Result<Vec<ReviewComment>, MarkdownError> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/comments?per_page={API_PAGE_SIZE}",
+            self.api_base_url, owner, repo, number
+        );
+
+        self.fetch_all_pages(url, None, "review comments").await
+    }
+
+    /// Fetches every page of a list endpoint, following `rel="next"` links.
+    ///
+    /// Stops early once `limit` items have been collected.
+    async fn fetch_all_pages<T: DeserializeOwned>(
+        &self,
+        first_page_url: String,
+        limit: Option<usize>,
+        what: &str,
+    ) -> Result<Vec<T>, MarkdownError> {
+        let mut items = Vec::new();
+        let mut next_url = Some(first_page_url);
+
+        for _ in 0..MAX_API_PAGES {
+            let Some(url) = next_url.take() else {
+                break;
+            };
+            let (response_text, next) = self.make_api_page_request(&url).await?;
+            let page = serde_json::from_str::<Vec<T>>(&response_text).map_err(|e| {
+                MarkdownError::ParseError {
+                    message: format!("Failed to parse GitHub {what} response: {e}"),
+                }
+            })?;
+            items.extend(page);
 
-        serde_json::from_str::<Vec<Comment>>(&response_text).map_err(|e| {
-            MarkdownError::ParseError {
-                message: format!("Failed to parse GitHub comments response: {e}"),
+            if let Some(limit) = limit {
+                if items.len() >= limit {
+                    items.truncate(limit);
+                    break;
+                }
             }
-        })
+            next_url = next.filter(|next| *next != url);
+        }
+
+        Ok(items)
     }
 
     /// Makes an authenticated API request to GitHub.
     async fn make_api_request(&self, url: &str) -> Result<String, MarkdownError> {
+        self.make_api_page_request(url)
+            .await
+            .map(|(response_text, _next)| response_text)
+    }
+
+    /// Makes an authenticated API request to GitHub, returning the link to the next page.
+    async fn make_api_page_request(
+        &self,
+        url: &str,
+    ) -> Result<(String, Option<String>), MarkdownError> {
         // Create HTTP client with proper headers
         let mut headers = HashMap::new();
         headers.insert(
@@ -556,8 +867,12 @@ impl GitHubConverter {
         }
 
         // Make the request using the HttpClient with header support
-        match self.client.get_text_with_headers(url, &headers).await {
-            Ok(response) => Ok(response),
+        match self
+            .client
+            .get_text_and_metadata_with_headers(url, &headers)
+            .await
+        {
+            Ok((response, metadata)) => Ok((response, metadata.link("next"))),
             Err(MarkdownError::AuthError { message }) => {
                 Err(MarkdownError::AuthError {
                     message: format!("GitHub API authentication failed: {message}. Consider setting GITHUB_TOKEN environment variable.")
@@ -636,16 +951,113 @@ impl GitHubConverter {
                 // but the framework is in place via ReactionCounts struct
                 markdown.push('\n');
             }
+
+            let total = issue.comments as usize;
+            let limited = self.max_comments.is_some_and(|max| comments.len() >= max);
+            if limited && total > comments.len() {
+                markdown.push_str(&format!(
+                    "*Showing the first {} of {} comments.*\n\n",
+                    comments.len(),
+                    total
+                ));
+            }
         }
 
         markdown.trim().to_string()
     }
 
+    /// Renders pull request reviews and inline review threads as markdown.
+    ///
+    /// Returns an empty string when the pull request has no review discussion.
+    fn render_reviews(&self, reviews: &PullRequestReviews) -> String {
+        let mut markdown = String::new();
+
+        // Review summaries (skip pending reviews and bodiless reviews that only wrap inline comments)
+        let submitted: Vec<&Review> = reviews
+            .reviews
+            .iter()
+            .filter(|r| r.submitted_at.is_some())
+            .filter(|r| {
+                r.body.as_deref().is_some_and(|b| !b.trim().is_empty()) || r.state != "COMMENTED"
+            })
+            .collect();
+
+        if !submitted.is_empty() {
+            markdown.push_str("## Reviews\n\n");
+
+            for review in submitted {
+                let submitted_at = review
+                    .submitted_at
+                    .map(|d| d.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                    .unwrap_or_default();
+                markdown.push_str(&format!(
+                    "### Review by @{} ({}, {})\n\n",
+                    review.user.login,
+                    self.format_review_state(&review.state),
+                    submitted_at
+                ));
+
+                if let Some(ref body) = review.body {
+                    if !body.trim().is_empty() {
+                        markdown.push_str(body.trim());
+                        markdown.push_str("\n\n");
+                    }
+                }
+            }
+        }
+
+        // Inline review threads, including resolved and outdated ones
+        let threads = ReviewThread::group(&reviews.comments);
+        if !threads.is_empty() {
+            markdown.push_str("## Review Threads\n\n");
+
+            for thread in &threads {
+                let root = &thread.root;
+                match root.line.or(root.original_line) {
+                    Some(line) => {
+                        markdown.push_str(&format!("### `{}` (line {})\n\n", root.path, line))
+                    }
+                    None => markdown.push_str(&format!("### `{}`\n\n", root.path)),
+                }
+
+                if let Some(ref hunk) = root.diff_hunk {
+                    let lines: Vec<&str> = hunk.lines().collect();
+                    let start = lines.len().saturating_sub(DIFF_HUNK_CONTEXT_LINES);
+                    markdown.push_str("```diff\n");
+                    markdown.push_str(&lines[start..].join("\n"));
+                    markdown.push_str("\n```\n\n");
+                }
+
+                for comment in std::iter::once(root).chain(thread.replies.iter()) {
+                    markdown.push_str(&format!(
+                        "**@{}** ({}):\n\n",
+                        comment.user.login,
+                        comment.created_at.format("%Y-%m-%d %H:%M:%S UTC")
+                    ));
+                    if let Some(ref body) = comment.body {
+                        if !body.trim().is_empty() {
+                            markdown.push_str(body.trim());
+                            markdown.push_str("\n\n");
+                        }
+                    }
+                }
+            }
+        }
+
+        markdown.trim().to_string()
+    }
+
+    /// Converts an API review state (e.g. `CHANGES_REQUESTED`) to display text.
+    fn format_review_state(&self, state: &str) -> String {
+        self.capitalize_first(&state.replace('_', " ").to_lowercase())
+    }
+
     /// Builds frontmatter for the GitHub issue/PR.
     fn build_frontmatter(
         &self,
         resource: &GitHubResource,
         issue: &Issue,
+        reviews: Option<&PullRequestReviews>,
     ) -> Result<String, MarkdownError> {
         let now = Utc::now();
         let mut builder = FrontmatterBuilder::new(resource.original_url.clone())
@@ -674,6 +1086,19 @@ impl GitHubConverter {
             builder = builder.additional_field("github_labels".to_string(), labels.join(", "));
         }
 
+        // Add review counts for pull requests
+        if let Some(reviews) = reviews {
+            builder = builder
+                .additional_field(
+                    "github_review_count".to_string(),
+                    reviews.reviews.len().to_string(),
+                )
+                .additional_field(
+                    "github_review_thread_count".to_string(),
+                    ReviewThread::group(&reviews.comments).len().to_string(),
+                );
+        }
+
         builder.build()
     }
 
@@ -699,6 +1124,18 @@ impl super::Converter for GitHubConverter {
         self.convert(url).await
     }
 
+    /// Issues fetch the issue and its comments; pull requests also fetch
+    /// reviews and review comments.
+    fn estimated_requests(&self, url: &str) -> usize {
+        match self
+            .parse_github_url(url)
+            .map(|resource| resource.resource_type)
+        {
+            Ok(ResourceType::PullRequest) => 4,
+            _ => 2,
+        }
+    }
+
     fn name(&self) -> &'static str {
         "GitHub Issue"
     }
@@ -740,6 +1177,7 @@ mod tests {
                 .with_timezone(&Utc),
             labels,
             pull_request: None,
+            comments: 0,
         }
     }
 
@@ -790,6 +1228,23 @@ mod tests {
         assert_eq!(converter.api_base_url, DEFAULT_GITHUB_API_BASE_URL);
     }
 
+    #[test]
+    fn test_parse_github_enterprise_url() {
+        let url = "https://github.example.com/platform/api/pull/17";
+        assert!(GitHubConverter::new().parse_github_url(url).is_err());
+
+        let converter =
+            GitHubConverter::new().with_api_base_url("https://github.example.com/api/v3/");
+        assert_eq!(converter.api_base_url, "https://github.example.com/api/v3");
+        let resource = converter.parse_github_url(url).unwrap();
+        assert_eq!(resource.owner, "platform");
+        assert_eq!(resource.number, 17);
+        assert_eq!(resource.resource_type, ResourceType::PullRequest);
+        assert!(converter
+            .parse_github_url("https://github.com/owner/repo/issues/1")
+            .is_ok());
+    }
+
     #[test]
     fn test_github_converter_with_token() {
         let token = "ghp_test_token".to_string();
@@ -998,6 +1453,231 @@ mod tests {
         assert_eq!(result.resource_type, ResourceType::PullRequest);
     }
 
+    /// Creates a test review comment on `src/lib.rs`.
+    fn create_test_review_comment(
+        id: u64,
+        in_reply_to_id: Option<u64>,
+        body: &str,
+        user_login: &str,
+        created_at: &str,
+    ) -> ReviewComment {
+        ReviewComment {
+            id,
+            body: Some(body.to_string()),
+            user: create_test_user(user_login, id),
+            created_at: DateTime::parse_from_rfc3339(created_at)
+                .unwrap()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(created_at)
+                .unwrap()
+                .with_timezone(&Utc),
+            path: "src/lib.rs".to_string(),
+            line: Some(42),
+            original_line: Some(40),
+            diff_hunk: Some("@@ -1,3 +1,4 @@\n fn main() {\n+    run();\n }".to_string()),
+            in_reply_to_id,
+            pull_request_review_id: Some(1),
+        }
+    }
+
+    #[test]
+    fn test_review_thread_grouping() {
+        let comments = vec![
+            create_test_review_comment(3, Some(1), "Reply two", "alice", "2023-01-15T12:00:00Z"),
+            create_test_review_comment(1, None, "Root one", "bob", "2023-01-15T10:00:00Z"),
+            create_test_review_comment(2, None, "Root two", "carol", "2023-01-15T11:00:00Z"),
+            create_test_review_comment(4, Some(3), "Nested reply", "bob", "2023-01-15T13:00:00Z"),
+            // Reply to a comment we never received is kept as its own thread
+            create_test_review_comment(5, Some(99), "Orphan", "dave", "2023-01-15T14:00:00Z"),
+        ];
+
+        let threads = ReviewThread::group(&comments);
+
+        assert_eq!(threads.len(), 3);
+        assert_eq!(threads[0].root.id, 1);
+        let reply_ids: Vec<u64> = threads[0].replies.iter().map(|c| c.id).collect();
+        assert_eq!(reply_ids, vec![3, 4]);
+        assert_eq!(threads[1].root.id, 2);
+        assert!(threads[1].replies.is_empty());
+        assert_eq!(threads[2].root.id, 5);
+    }
+
+    #[test]
+    fn test_render_reviews() {
+        let converter = GitHubConverter::new();
+        let submitted_at = DateTime::parse_from_rfc3339("2023-01-16T09:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let reviews = PullRequestReviews {
+            reviews: vec![
+                Review {
+                    id: 1,
+                    user: create_test_user("bob", 2),
+                    body: Some("Needs a test.".to_string()),
+                    state: "CHANGES_REQUESTED".to_string(),
+                    submitted_at: Some(submitted_at),
+                },
+                Review {
+                    id: 2,
+                    user: create_test_user("carol", 3),
+                    body: Some(String::new()),
+                    state: "COMMENTED".to_string(),
+                    submitted_at: Some(submitted_at),
+                },
+            ],
+            comments: vec![
+                create_test_review_comment(1, None, "Why run here?", "bob", "2023-01-15T10:00:00Z"),
+                create_test_review_comment(
+                    2,
+                    Some(1),
+                    "Resolved: moved to init.",
+                    "alice",
+                    "2023-01-15T11:00:00Z",
+                ),
+            ],
+        };
+
+        let markdown = converter.render_reviews(&reviews);
+
+        assert!(markdown.contains("## Reviews"));
+        assert!(
+            markdown.contains("### Review by @bob (Changes requested, 2023-01-16 09:00:00 UTC)")
+        );
+        assert!(markdown.contains("Needs a test."));
+        // Bodiless comment-only reviews are just containers for inline comments
+        assert!(!markdown.contains("@carol"));
+        assert!(markdown.contains("## Review Threads"));
+        assert!(markdown.contains("### `src/lib.rs` (line 42)"));
+        assert!(markdown.contains("```diff\n@@ -1,3 +1,4 @@"));
+        assert!(markdown.contains("**@bob** (2023-01-15 10:00:00 UTC):\n\nWhy run here?"));
+        assert!(markdown.contains("Resolved: moved to init."));
+    }
+
+    #[test]
+    fn test_render_reviews_empty() {
+        let converter = GitHubConverter::new();
+        let markdown = converter.render_reviews(&PullRequestReviews::default());
+        assert!(markdown.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_convert_pull_request_includes_review_threads() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        let issue_json = serde_json::json!({
+            "id": 1, "number": 7, "title": "Add feature", "body": "PR body",
+            "state": "open", "user": {"login": "alice", "id": 1},
+            "created_at": "2023-01-15T10:00:00Z", "updated_at": "2023-01-15T10:00:00Z",
+            "labels": [],
+            "pull_request": {"url": "u", "html_url": "h"}
+        });
+        let review_comments_json = serde_json::json!([{
+            "id": 10, "body": "Resolved design discussion", "user": {"login": "bob", "id": 2},
+            "created_at": "2023-01-15T11:00:00Z", "updated_at": "2023-01-15T11:00:00Z",
+            "path": "src/main.rs", "line": null, "original_line": 3,
+            "diff_hunk": "@@ -1 +1 @@\n-a\n+b", "pull_request_review_id": 5
+        }]);
+
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/issues/7"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(issue_json))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/issues/7/comments"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/pulls/7/reviews"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/pulls/7/comments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(review_comments_json))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let converter = GitHubConverter::new_with_config(None, mock_server.uri());
+        let markdown = converter
+            .convert("https://github.com/owner/repo/pull/7")
+            .await
+            .unwrap();
+
+        assert!(markdown
+            .as_str()
+            .contains("github_review_thread_count: '1'"));
+        assert!(markdown.as_str().contains("### `src/main.rs` (line 3)"));
+        assert!(markdown.as_str().contains("Resolved design discussion"));
+    }
+
+    #[tokio::test]
+    async fn test_convert_follows_comment_pagination() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let comments_path = "/repos/owner/repo/issues/9/comments";
+        let comment = |id: u64| {
+            serde_json::json!({
+                "id": id, "body": format!("Comment number {id}"), "user": {"login": "bob", "id": 2},
+                "created_at": "2023-01-15T11:00:00Z", "updated_at": "2023-01-15T11:00:00Z"
+            })
+        };
+
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/issues/9"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": 1, "number": 9, "title": "Long thread", "body": "Body",
+                "state": "open", "user": {"login": "alice", "id": 1},
+                "created_at": "2023-01-15T10:00:00Z", "updated_at": "2023-01-15T10:00:00Z",
+                "labels": [], "pull_request": null, "comments": 3
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(comments_path))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([comment(3)])))
+            .mount(&mock_server)
+            .await;
+        let next_link = format!(
+            r#"<{}{comments_path}?page=2>; rel="next""#,
+            mock_server.uri()
+        );
+        Mock::given(method("GET"))
+            .and(path(comments_path))
+            .and(query_param("per_page", "100"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Link", next_link.as_str())
+                    .set_body_json(serde_json::json!([comment(1), comment(2)])),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let url = "https://github.com/owner/repo/issues/9";
+        let converter = GitHubConverter::new_with_config(None, mock_server.uri());
+        let markdown = converter.convert(url).await.unwrap();
+        assert!(markdown.as_str().contains("Comment number 1"));
+        assert!(markdown.as_str().contains("Comment number 3"));
+        assert!(!markdown.as_str().contains("Showing the first"));
+
+        let limited = converter.with_max_comments(2).convert(url).await.unwrap();
+        assert!(limited.as_str().contains("Comment number 2"));
+        assert!(!limited.as_str().contains("Comment number 3"));
+        assert!(limited
+            .as_str()
+            .contains("*Showing the first 2 of 3 comments.*"));
+    }
+
     #[test]
     fn test_render_markdown_empty_body() {
         let converter = GitHubConverter::new();
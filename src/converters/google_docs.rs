@@ -10,6 +10,24 @@
 //! - View URLs: `https://docs.google.com/document/d/{id}/view`
 //! - Share URLs: `https://docs.google.com/document/d/{id}/edit?usp=sharing`
 //! - Drive URLs: `https://drive.google.com/file/d/{id}/view`
+//! - Drive shared links: `https://drive.google.com/open?id={id}` and
+//!   `https://drive.google.com/uc?id={id}`
+//!
+//! # Google Drive Files
+//!
+//! Drive links can point at any kind of file. Their type is looked up with
+//! the Drive API first: Google Docs are exported as usual, while PDF, Word,
+//! HTML, and plain text files are downloaded and converted by type. If the
+//! lookup fails, for example without a Google API token for a private file,
+//! the link is treated as a Google Doc.
+//!
+//! # Export Formats
+//!
+//! By default the markdown or plain text export is used, which loses most
+//! structure. [`GoogleDocsExportFormat::Html`] and
+//! [`GoogleDocsExportFormat::Docx`] fetch the HTML or Word export instead and
+//! convert it with the HTML or DOCX pipeline, keeping headings, links, and
+//! tables.
 //!
 //! # Usage Examples
 //!
@@ -28,10 +46,43 @@
 //! ```
 
 use crate::client::HttpClient;
+use crate::config::{CommentMode, GoogleDocsExportFormat, OutputConfig};
 use crate::frontmatter::FrontmatterBuilder;
-use crate::types::{Markdown, MarkdownError};
+use crate::types::{ContentErrorKind, ErrorContext, Markdown, MarkdownError};
 use async_trait::async_trait;
 use chrono::Utc;
+use futures::stream::{FuturesUnordered, StreamExt};
+use serde::Deserialize;
+use url::Url as ParsedUrl;
+
+use super::comments::CommentExtractor;
+use super::config::HtmlConverterConfig;
+use super::docx::DocxConverter;
+use super::html::HtmlConverter;
+use super::pdf::PdfConverter;
+use tracing::debug;
+
+/// Default base URL of the Google Drive API
+const DRIVE_API_BASE_URL: &str = "https://www.googleapis.com/drive/v3";
+
+/// MIME type of native Google Docs documents
+const GOOGLE_DOCS_MIME_TYPE: &str = "application/vnd.google-apps.document";
+
+/// MIME type of Word documents
+const DOCX_MIME_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+
+/// A Google Drive file's metadata, as returned by the Drive API.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DriveFile {
+    /// Drive file ID
+    pub id: String,
+    /// File name
+    pub name: String,
+    /// MIME type, such as `application/pdf`
+    pub mime_type: String,
+}
 
 /// Google Docs to markdown converter with intelligent URL handling.
 ///
@@ -44,6 +95,12 @@ pub struct GoogleDocsConverter {
     client: HttpClient,
     /// Set of supported export formats in preference order
     export_formats: Vec<String>,
+    /// How comments in the document are rendered
+    comment_mode: CommentMode,
+    /// Which export the document is converted from
+    export_format: GoogleDocsExportFormat,
+    /// Base URL of the Google Drive API, used to look up Drive files
+    drive_api_base_url: String,
 }
 
 impl GoogleDocsConverter {
@@ -68,6 +125,9 @@ impl GoogleDocsConverter {
                 "txt".to_string(),  // Plain text (fallback)
                 "html".to_string(), // HTML (can be converted)
             ],
+            comment_mode: CommentMode::Omit,
+            export_format: GoogleDocsExportFormat::Text,
+            drive_api_base_url: DRIVE_API_BASE_URL.to_string(),
         }
     }
 
@@ -96,9 +156,67 @@ impl GoogleDocsConverter {
                 "txt".to_string(),  // Plain text (fallback)
                 "html".to_string(), // HTML (can be converted)
             ],
+            comment_mode: CommentMode::Omit,
+            export_format: GoogleDocsExportFormat::Text,
+            drive_api_base_url: DRIVE_API_BASE_URL.to_string(),
         }
     }
 
+    /// Sets how document comments are rendered.
+    ///
+    /// Comments are only present in Google's HTML export, so any mode other
+    /// than [`CommentMode::Omit`] fetches the HTML export first and converts it.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - How comments should appear in the markdown
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::config::CommentMode;
+    /// use markdowndown::converters::GoogleDocsConverter;
+    ///
+    /// let converter = GoogleDocsConverter::new().with_comment_mode(CommentMode::Footnotes);
+    /// ```
+    pub fn with_comment_mode(mut self, mode: CommentMode) -> Self {
+        self.comment_mode = mode;
+        self
+    }
+
+    /// Sets which export the document is converted from.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - The export to fetch; HTML and DOCX exports keep headings,
+    ///   links, and tables that the text export loses
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::config::GoogleDocsExportFormat;
+    /// use markdowndown::converters::GoogleDocsConverter;
+    ///
+    /// let converter =
+    ///     GoogleDocsConverter::new().with_export_format(GoogleDocsExportFormat::Html);
+    /// ```
+    pub fn with_export_format(mut self, format: GoogleDocsExportFormat) -> Self {
+        self.export_format = format;
+        self
+    }
+
+    /// Sets the base URL of the Google Drive API.
+    ///
+    /// This is useful for testing with mock servers.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The API base URL, such as `https://www.googleapis.com/drive/v3`
+    pub fn with_drive_api_base_url<T: Into<String>>(mut self, base_url: T) -> Self {
+        self.drive_api_base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
     /// Converts a Google Docs URL to markdown with frontmatter.
     ///
     /// This method performs the complete conversion workflow:
@@ -149,11 +267,30 @@ impl GoogleDocsConverter {
         // Step 1: Extract and validate document ID
         let document_id = self.extract_document_id(url)?;
 
+        // Drive links may point at any kind of file, not just a Google Doc
+        if self.is_drive_url(url) {
+            match self.resolve_drive_file(&document_id).await {
+                Ok(file) if file.mime_type != GOOGLE_DOCS_MIME_TYPE => {
+                    return self.convert_drive_file(url, &file).await;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    debug!("Could not look up Drive file {document_id}, treating it as a Google Doc: {e}");
+                }
+            }
+        }
+
         // Step 2: Validate document access
         self.validate_access(url).await?;
 
         // Step 3: Try export formats in preference order
-        let content = self.fetch_content_with_fallback(&document_id).await?;
+        let content = match self.fetch_docx(url, &document_id).await {
+            Some(markdown) => markdown,
+            None => {
+                let content = self.fetch_content_with_fallback(&document_id).await?;
+                self.render_html(&content)?
+            }
+        };
 
         // Step 4: Post-process the content
         let processed_content = self.post_process_content(&content)?;
@@ -189,7 +326,13 @@ impl GoogleDocsConverter {
         let document_id = self.extract_document_id(url)?;
 
         // Fetch content directly from the export URL
-        let content = self.client.get_text(url).await?;
+        let content = if self.export_format == GoogleDocsExportFormat::Docx {
+            let bytes = self.client.get_bytes(url).await?;
+            self.docx_to_markdown(url, &bytes).await?
+        } else {
+            let content = self.client.get_text(url).await?;
+            self.render_html(&content)?
+        };
 
         // Post-process the content
         let processed_content = self.post_process_content(&content)?;
@@ -221,6 +364,7 @@ impl GoogleDocsConverter {
     /// - `https://docs.google.com/document/d/{id}/view*`
     /// - `https://drive.google.com/file/d/{id}/view*`
     /// - `https://drive.google.com/open?id={id}`
+    /// - `https://drive.google.com/uc?id={id}`
     ///
     /// # Arguments
     ///
@@ -332,27 +476,56 @@ impl GoogleDocsConverter {
     /// Fetches document content with format fallback strategy.
     ///
     /// Tries export formats in preference order (markdown → text → HTML)
-    /// until one succeeds or all fail.
+    /// until one succeeds or all fail. When the client races strategies, all
+    /// formats are requested at once and the first valid export is used,
+    /// unless comments or the HTML format require the HTML export first.
     async fn fetch_content_with_fallback(
         &self,
         document_id: &str,
     ) -> Result<String, MarkdownError> {
         let mut last_error = None;
 
-        for format in &self.export_formats {
-            let export_url = self.build_export_url(document_id, format);
+        // Comments are only included in the HTML export
+        let preferred = self.prefers_html().then_some("html");
+        let formats = preferred.into_iter().chain(
+            self.export_formats
+                .iter()
+                .map(String::as_str)
+                .filter(|format| Some(*format) != preferred),
+        );
 
-            match self.client.get_text(&export_url).await {
-                Ok(content) => {
-                    // Verify we got actual content, not an error page
-                    if self.is_valid_content(&content, format) {
-                        return Ok(content);
-                    }
-                    // Continue to next format if content seems invalid
+        if self.client.race_strategies() && preferred.is_none() {
+            let mut exports: FuturesUnordered<_> = formats
+                .map(|format| async move {
+                    let export_url = self.build_export_url(document_id, format);
+                    (format, self.client.get_text(&export_url).await)
+                })
+                .collect();
+
+            // Dropping the remaining requests cancels them
+            while let Some((format, result)) = exports.next().await {
+                match result {
+                    Ok(content) if self.is_valid_content(&content, format) => return Ok(content),
+                    Ok(_) => {}
+                    Err(e) => last_error = Some(e),
                 }
-                Err(e) => {
-                    last_error = Some(e);
-                    // Continue to next format
+            }
+        } else {
+            for format in formats {
+                let export_url = self.build_export_url(document_id, format);
+
+                match self.client.get_text(&export_url).await {
+                    Ok(content) => {
+                        // Verify we got actual content, not an error page
+                        if self.is_valid_content(&content, format) {
+                            return Ok(content);
+                        }
+                        // Continue to next format if content seems invalid
+                    }
+                    Err(e) => {
+                        last_error = Some(e);
+                        // Continue to next format
+                    }
                 }
             }
         }
@@ -407,6 +580,64 @@ impl GoogleDocsConverter {
         }
     }
 
+    /// Returns true if the HTML export is fetched first, for its comments or
+    /// its structure.
+    fn prefers_html(&self) -> bool {
+        self.comment_mode != CommentMode::Omit || self.export_format == GoogleDocsExportFormat::Html
+    }
+
+    /// Fetches and converts the Word export when the DOCX format is selected.
+    ///
+    /// Returns `None` for other formats, and when the Word export fails so
+    /// the text exports are tried instead.
+    async fn fetch_docx(&self, url: &str, document_id: &str) -> Option<String> {
+        if self.export_format != GoogleDocsExportFormat::Docx {
+            return None;
+        }
+
+        let export_url = self.build_export_url(document_id, "docx");
+        let result = match self.client.get_bytes(&export_url).await {
+            Ok(bytes) => self.docx_to_markdown(url, &bytes).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(markdown) => Some(markdown),
+            Err(e) => {
+                debug!("DOCX export of {document_id} failed, using text exports: {e}");
+                None
+            }
+        }
+    }
+
+    /// Converts a Word export to markdown, without frontmatter.
+    async fn docx_to_markdown(&self, url: &str, bytes: &[u8]) -> Result<String, MarkdownError> {
+        let output_config = OutputConfig {
+            include_frontmatter: false,
+            ..OutputConfig::default()
+        };
+        let converter = DocxConverter::with_config(
+            self.client.clone(),
+            HtmlConverterConfig::default(),
+            output_config,
+        );
+        Ok(converter.convert_docx(url, bytes).await?.content_only())
+    }
+
+    /// Converts an HTML export to markdown with comments rendered per the comment mode.
+    ///
+    /// Content from other export formats, and HTML exports when neither
+    /// comments nor the HTML format were asked for, is returned unchanged.
+    fn render_html(&self, content: &str) -> Result<String, MarkdownError> {
+        if !self.prefers_html() || !self.is_valid_content(content, "html") {
+            return Ok(content.to_string());
+        }
+
+        let extractor = CommentExtractor::new();
+        let (marked_html, comments) = extractor.extract(content);
+        let markdown = HtmlConverter::new().convert_html(&marked_html)?;
+        Ok(extractor.render(&markdown, &comments, self.comment_mode))
+    }
+
     /// Post-processes the fetched content to clean it up.
     fn post_process_content(&self, content: &str) -> Result<String, MarkdownError> {
         if content.trim().is_empty() {
@@ -489,10 +720,10 @@ impl GoogleDocsConverter {
         None
     }
 
-    /// Helper function to extract document ID from drive.google.com open URLs.
+    /// Helper function to extract document ID from drive.google.com open and download URLs.
     fn extract_from_drive_open_url(&self, url: &str) -> Option<String> {
-        // Pattern: https://drive.google.com/open?id={id}
-        if url.contains("drive.google.com/open") {
+        // Pattern: https://drive.google.com/open?id={id} or /uc?id={id}
+        if url.contains("drive.google.com/open") || url.contains("drive.google.com/uc") {
             if let Some(id_start) = url.find("id=") {
                 let after_id = &url[id_start + 3..]; // "id=" is 3 chars
                                                      // Find end of ID (next & or end of string)
@@ -532,6 +763,100 @@ impl GoogleDocsConverter {
         None
     }
 
+    /// Returns true if the URL is a Google Drive link rather than a Docs URL.
+    fn is_drive_url(&self, url: &str) -> bool {
+        ParsedUrl::parse(url)
+            .ok()
+            .is_some_and(|parsed| parsed.host_str() == Some("drive.google.com"))
+    }
+
+    /// Looks up a Google Drive file's name and type with the Drive API.
+    ///
+    /// The configured Google API token is sent with the request; public
+    /// files may be looked up without one.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_id` - The Drive file ID
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::AuthError` - If the file is private and no valid token is configured
+    /// * `MarkdownError::NetworkError` - For network-related failures
+    /// * `MarkdownError::ParseError` - If the API response cannot be read
+    pub async fn resolve_drive_file(&self, file_id: &str) -> Result<DriveFile, MarkdownError> {
+        let metadata_url = format!(
+            "{}/files/{file_id}?fields=id,name,mimeType&supportsAllDrives=true",
+            self.drive_api_base_url
+        );
+        let response = self.client.get_text(&metadata_url).await?;
+        serde_json::from_str(&response).map_err(|e| MarkdownError::ParseError {
+            message: format!("Failed to parse Google Drive file response: {e}"),
+        })
+    }
+
+    /// Downloads a Drive file that is not a Google Doc and converts it by type.
+    async fn convert_drive_file(
+        &self,
+        url: &str,
+        file: &DriveFile,
+    ) -> Result<Markdown, MarkdownError> {
+        let content_type = match file.mime_type.as_str() {
+            "application/pdf" => "pdf",
+            DOCX_MIME_TYPE => "docx",
+            "text/html" => "html",
+            mime if mime.starts_with("text/") => "text",
+            mime => {
+                return Err(MarkdownError::ContentError {
+                    kind: ContentErrorKind::UnsupportedFormat,
+                    context: ErrorContext::new(url, "Google Drive conversion", "Google Docs")
+                        .with_info(format!(
+                            "Drive file '{}' has unsupported type {mime}",
+                            file.name
+                        )),
+                })
+            }
+        };
+
+        let download_url = format!(
+            "{}/files/{}?alt=media&supportsAllDrives=true",
+            self.drive_api_base_url, file.id
+        );
+        let bytes = self.client.get_bytes(&download_url).await?;
+        let content = match content_type {
+            "pdf" => {
+                let output_config = OutputConfig {
+                    include_frontmatter: false,
+                    ..OutputConfig::default()
+                };
+                PdfConverter::with_config(self.client.clone(), output_config)
+                    .convert_pdf(url, &bytes)?
+                    .content_only()
+            }
+            "docx" => self.docx_to_markdown(url, &bytes).await?,
+            "html" => HtmlConverter::new().convert_html(&String::from_utf8_lossy(&bytes))?,
+            _ => String::from_utf8_lossy(&bytes).into_owned(),
+        };
+        let processed_content = self.post_process_content(&content)?;
+
+        let now = Utc::now();
+        let frontmatter = FrontmatterBuilder::new(url.to_string())
+            .exporter(format!(
+                "markdowndown-googledocs-{}",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .download_date(now)
+            .additional_field("converted_at".to_string(), now.to_rfc3339())
+            .additional_field("conversion_type".to_string(), "google_drive".to_string())
+            .additional_field("document_id".to_string(), file.id.clone())
+            .additional_field("document_type".to_string(), content_type.to_string())
+            .additional_field("title".to_string(), file.name.clone())
+            .additional_field("mime_type".to_string(), file.mime_type.clone())
+            .build()?;
+
+        Markdown::new(format!("{frontmatter}\n{processed_content}"))
+    }
+
     /// Validates that a string looks like a valid Google Docs document ID.
     fn is_valid_document_id(&self, id: &str) -> bool {
         // Google Docs IDs are typically alphanumeric with some special chars
@@ -549,6 +874,22 @@ impl super::Converter for GoogleDocsConverter {
         self.convert(url).await
     }
 
+    /// Access is checked before the export is fetched; racing strategies
+    /// requests every export format at once. Drive links are looked up first.
+    fn estimated_requests(&self, url: &str) -> usize {
+        let lookup = usize::from(self.is_drive_url(url));
+        if self.is_export_url(url) {
+            1
+        } else if self.client.race_strategies()
+            && self.export_format == GoogleDocsExportFormat::Text
+            && self.comment_mode == CommentMode::Omit
+        {
+            lookup + 1 + self.export_formats.len()
+        } else {
+            lookup + 2
+        }
+    }
+
     fn name(&self) -> &'static str {
         "Google Docs"
     }
@@ -717,6 +1058,30 @@ mod tests {
         assert_eq!(empty_result.unwrap(), "_[Empty document]_");
     }
 
+    #[tokio::test]
+    async fn test_export_format_prefers_html() {
+        let converter = GoogleDocsConverter::new();
+        assert!(!converter.prefers_html());
+        assert!(converter
+            .clone()
+            .with_export_format(GoogleDocsExportFormat::Html)
+            .prefers_html());
+        assert!(!converter
+            .clone()
+            .with_export_format(GoogleDocsExportFormat::Docx)
+            .prefers_html());
+        assert!(converter
+            .with_comment_mode(CommentMode::Footnotes)
+            .prefers_html());
+
+        // Word exports that are not Word documents are rejected, so text exports are tried
+        let docx = GoogleDocsConverter::new().with_export_format(GoogleDocsExportFormat::Docx);
+        assert!(docx
+            .docx_to_markdown("https://docs.google.com/document/d/x", b"<html></html>")
+            .await
+            .is_err());
+    }
+
     #[test]
     fn test_default_implementation() {
         let converter = GoogleDocsConverter::default();
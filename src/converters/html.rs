@@ -4,18 +4,42 @@
 //! with intelligent preprocessing to remove unwanted elements and postprocessing
 //! to clean up the markdown output.
 
-use crate::client::HttpClient;
-use crate::frontmatter::FrontmatterBuilder;
-use crate::types::{Markdown, MarkdownError};
+use crate::client::{HttpClient, ResponseContent};
+use crate::filter;
+use crate::frontmatter::{frontmatter_field, set_frontmatter_field, FrontmatterBuilder};
+use crate::profiling::{self, Stage};
+use crate::types::{ContentErrorKind, ErrorContext, Markdown, MarkdownError};
+use crate::utils::parse_date;
 use async_trait::async_trait;
-use chrono::Utc;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, BoxStream, StreamExt};
 use html2text::from_read;
+use regex::Regex;
+use std::collections::{HashMap, VecDeque};
 use std::io::Cursor;
+use std::time::Instant;
+use tracing::{debug, warn};
 
+use super::config::DetectionStrategy;
 pub use super::config::HtmlConverterConfig;
-use super::converter::Converter;
+use super::converter::{Converter, MarkdownStream};
+use super::docx::{is_docx, DocxConverter};
+use super::escaping::MarkdownEscaper;
+use super::passthrough::RawHtmlPassthrough;
+use super::markdown_source::MarkdownSourceConverter;
+use super::pdf::PdfConverter;
+use super::plaintext;
+use super::excerpt::ExcerptExtractor;
+use super::forms::FormSummarizer;
+use super::images::ImagePreserver;
+use super::keywords::KeywordExtractor;
 use super::postprocessor::MarkdownPostprocessor;
 use super::preprocessor::HtmlPreprocessor;
+use super::access_wall::AccessWallDetector;
+use super::soft404::Soft404Detector;
+use super::streaming::HtmlChunker;
+use super::structured::StructuredFormat;
 
 /// HTML to markdown converter with intelligent preprocessing and cleanup.
 #[derive(Debug, Clone)]
@@ -92,8 +116,14 @@ impl HtmlConverter {
     ///
     /// This method implements a complete pipeline:
     /// 1. Preprocess HTML to remove unwanted elements
-    /// 2. Convert HTML to markdown using html2text
-    /// 3. Postprocess markdown to clean up formatting
+    /// 2. Set aside unconvertible elements, if passthrough is enabled
+    /// 3. Set aside images, if they are downloaded
+    /// 4. Summarize forms and controls, if enabled
+    /// 5. Escape markdown special characters in the page text
+    /// 6. Convert HTML to markdown using html2text
+    /// 7. Postprocess markdown to clean up formatting
+    /// 8. Restore set-aside elements as fenced raw HTML
+    /// 9. Restore set-aside images as markdown image links
     ///
     /// # Arguments
     ///
@@ -130,11 +160,47 @@ impl HtmlConverter {
         }
 
         // Step 1: Preprocess HTML
+        let preprocess_started = Instant::now();
         let preprocessor = HtmlPreprocessor::new(&self.config);
         let cleaned_html = preprocessor.preprocess(html);
 
-        // Step 2: Convert to markdown
-        let markdown = self.html_to_markdown(&cleaned_html).map_err(|e| {
+        // Step 2: Set aside elements that cannot be converted faithfully
+        let passthrough = self
+            .config
+            .passthrough_unconvertible
+            .then(RawHtmlPassthrough::new);
+        let (cleaned_html, passthrough_blocks) = match &passthrough {
+            Some(passthrough) => passthrough.extract(&cleaned_html),
+            None => (cleaned_html, Vec::new()),
+        };
+        for block in &passthrough_blocks {
+            warn!("Keeping {} as raw HTML", block.reason);
+        }
+
+        // Step 3: Set aside images, whose sources html2text would drop
+        let images = self
+            .output_config
+            .download_images
+            .is_enabled()
+            .then(ImagePreserver::new);
+        let (cleaned_html, image_links) = match &images {
+            Some(images) => images.extract(&cleaned_html),
+            None => (cleaned_html, Vec::new()),
+        };
+
+        // Step 4: Summarize forms and controls that html2text would drop
+        let cleaned_html = if self.config.summarize_forms {
+            FormSummarizer::new().summarize(&cleaned_html)
+        } else {
+            cleaned_html
+        };
+
+        // Step 5: Escape markdown special characters in the page text
+        let escaped_html = MarkdownEscaper::new(self.config.escape_mode).escape_html(&cleaned_html);
+        profiling::record(Stage::Preprocess, preprocess_started, escaped_html.len());
+
+        // Step 6: Convert to markdown
+        let markdown = self.html_to_markdown(&escaped_html).map_err(|e| {
             if let MarkdownError::ParseError { message } = e {
                 MarkdownError::ParseError {
                     message: format!(
@@ -148,11 +214,237 @@ impl HtmlConverter {
             }
         })?;
 
-        // Step 3: Postprocess markdown
+        // Step 7: Postprocess markdown
+        let postprocess_started = Instant::now();
         let postprocessor = MarkdownPostprocessor::new(&self.config);
         let cleaned_markdown = postprocessor.postprocess(&markdown);
 
-        Ok(cleaned_markdown)
+        // Step 8: Restore set-aside elements as fenced raw HTML
+        let result = match passthrough {
+            Some(passthrough) => passthrough.restore(&cleaned_markdown, &passthrough_blocks),
+            None => cleaned_markdown,
+        };
+
+        // Step 9: Restore set-aside images as markdown image links
+        let result = match images {
+            Some(images) => images.restore(&result, &image_links),
+            None => result,
+        };
+        profiling::record(Stage::Postprocess, postprocess_started, result.len());
+        Ok(result)
+    }
+
+    /// Converts an HTML document to markdown with frontmatter, without fetching it.
+    ///
+    /// This runs the same steps as [`Converter::convert`] on HTML the caller
+    /// already has. Frontmatter records where the document came from, so it
+    /// is only added when `url` is given.
+    ///
+    /// # Arguments
+    ///
+    /// * `html` - The HTML document
+    /// * `url` - The URL the document was fetched from, if known
+    ///
+    /// # Returns
+    ///
+    /// Returns the converted markdown on success, or a `MarkdownError` on failure.
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ParseError` - If the HTML is empty or cannot be converted
+    /// * `MarkdownError::ContentError` - If the page looks like a "not found" template,
+    ///   or shows a paywall or login wall instead of its content
+    pub fn convert_document(&self, html: &str, url: Option<&str>) -> Result<Markdown, MarkdownError> {
+        // Convert HTML to markdown string
+        let markdown_string = self.convert_html(html)?;
+
+        // Reject pages that return success but render a "not found" template
+        if self.config.detect_soft_404 {
+            if let Some(signal) = Soft404Detector::shared().detect(html, &markdown_string) {
+                return Err(MarkdownError::ContentError {
+                    kind: ContentErrorKind::SoftNotFound,
+                    context: ErrorContext::new(
+                        url.unwrap_or_default(),
+                        "Soft-404 detection",
+                        "HtmlConverter",
+                    )
+                    .with_info(signal),
+                });
+            }
+        }
+
+        // Reject pages that hide their content behind a paywall or sign-in
+        if self.config.detect_access_wall {
+            let detector = AccessWallDetector::shared();
+            let signal = detector.detect(html, &markdown_string).or_else(|| {
+                self.config
+                    .detect_login_walls
+                    .then(|| detector.detect_login_wall(html, &markdown_string))
+                    .flatten()
+            });
+            if let Some(signal) = signal {
+                return Err(MarkdownError::ContentError {
+                    kind: ContentErrorKind::AccessWall,
+                    context: ErrorContext::new(
+                        url.unwrap_or_default(),
+                        "Access wall detection",
+                        "HtmlConverter",
+                    )
+                    .with_info(signal),
+                });
+            }
+        }
+
+        // Carry the published date out for the date window, with or without frontmatter
+        filter::record_published_date(|| self.extract_published_date(html));
+
+        // Handle empty content case - provide minimal markdown for empty HTML
+        let markdown_content = if markdown_string.trim().is_empty() {
+            "<!-- Empty HTML document -->".to_string()
+        } else {
+            markdown_string
+        };
+
+        // Only generate frontmatter if configured to include it and the source is known
+        match url.filter(|_| self.output_config.include_frontmatter) {
+            Some(url) => {
+                let frontmatter = self.build_frontmatter(url, html, Some(&markdown_content))?;
+
+                // Combine frontmatter with content
+                let markdown_with_frontmatter = format!("{frontmatter}\n{markdown_content}");
+
+                // Wrap in Markdown type with validation
+                Markdown::new(markdown_with_frontmatter)
+            }
+            // No frontmatter - just return the markdown content
+            None => Markdown::new(markdown_content),
+        }
+    }
+
+    /// Converts a fetched response body, routing it by content type.
+    ///
+    /// Text is converted as HTML; PDF and Word documents are handed to their
+    /// converters, and other binary bodies are read as lossy UTF-8 HTML.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL the content was fetched from
+    /// * `content` - The response body
+    pub async fn convert_content(
+        &self,
+        url: &str,
+        content: ResponseContent,
+    ) -> Result<Markdown, MarkdownError> {
+        match content {
+            ResponseContent::Text(html) => self.convert_document(&html, Some(url)),
+            // URLs without a .pdf extension can still serve PDF documents
+            ResponseContent::Binary {
+                content_type,
+                bytes,
+            } if content_type == "application/pdf" => {
+                PdfConverter::with_config(self.client.clone(), self.output_config.clone())
+                    .convert_pdf(url, &bytes)
+            }
+            // Office 365 links often download Word documents
+            ResponseContent::Binary { bytes, .. } if is_docx(&bytes) => DocxConverter::with_config(
+                self.client.clone(),
+                self.config.clone(),
+                self.output_config.clone(),
+            )
+            .convert_docx(url, &bytes)
+            .await,
+            ResponseContent::Binary { bytes, .. } => {
+                self.convert_document(&String::from_utf8_lossy(&bytes), Some(url))
+            }
+        }
+    }
+
+    /// Converts a fetched response body, routing text bodies by their content
+    /// type.
+    ///
+    /// JSON and XML are always pretty-printed into a code block, with their
+    /// top-level scalar values added to the frontmatter, and plain text that
+    /// does not start with a tag is given structure by
+    /// [`plaintext::render`]. When the detection strategy is
+    /// `ContentTypeFallback`, markdown is normalized by
+    /// [`MarkdownSourceConverter::convert_source`] too; everything else is
+    /// converted by [`HtmlConverter::convert_content`].
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL the content was fetched from
+    /// * `content_type` - The media type from the `Content-Type` header,
+    ///   lowercased, without parameters
+    /// * `content` - The response body
+    pub async fn convert_response(
+        &self,
+        url: &str,
+        content_type: Option<&str>,
+        content: ResponseContent,
+    ) -> Result<Markdown, MarkdownError> {
+        let (Some(media_type), ResponseContent::Text(text)) = (content_type, &content) else {
+            return self.convert_content(url, content).await;
+        };
+        let strategy = self.config.detection_strategy;
+        if strategy == DetectionStrategy::ContentTypeFallback
+            && matches!(media_type, "text/markdown" | "text/x-markdown")
+        {
+            return MarkdownSourceConverter::with_config(
+                self.client.clone(),
+                self.output_config.clone(),
+            )
+            .convert_source(url, text);
+        }
+        let (body, conversion_type, fields) = match (
+            StructuredFormat::from_media_type(media_type),
+            strategy,
+            media_type,
+        ) {
+            (Some(format), _, _) => {
+                let document = format.render(text);
+                (document.body, format.name(), document.fields)
+            }
+            // Test servers and misconfigured hosts label HTML as plain text too
+            (None, _, "text/plain") if !text.trim().is_empty() && !looks_like_html(text) => (
+                plaintext::render(text, &self.config.plain_text),
+                "text",
+                Vec::new(),
+            ),
+            _ => return self.convert_content(url, content).await,
+        };
+        debug!("Using {} response without HTML conversion", media_type);
+
+        if text.trim().is_empty() {
+            return Err(MarkdownError::ContentError {
+                kind: ContentErrorKind::EmptyContent,
+                context: ErrorContext::new(url, "Content validation", "HtmlConverter")
+                    .with_info(format!("Empty {media_type} response")),
+            });
+        }
+        if !self.output_config.include_frontmatter {
+            return Markdown::new(body);
+        }
+
+        let now = Utc::now();
+        let mut builder = FrontmatterBuilder::new(url.to_string());
+        // Payload fields go first, so the standard fields below take precedence
+        for (key, value) in fields {
+            builder = builder.additional_field(key, value);
+        }
+        let mut builder = builder
+            .exporter(format!(
+                "markdowndown-{conversion_type}-{}",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .download_date(now)
+            .additional_field("converted_at".to_string(), now.to_rfc3339())
+            .additional_field("conversion_type".to_string(), conversion_type.to_string())
+            .additional_field("url".to_string(), url.to_string());
+        for (key, value) in &self.output_config.custom_frontmatter_fields {
+            builder = builder.additional_field(key.clone(), value.clone());
+        }
+        let frontmatter = builder.build()?;
+        Markdown::new(format!("{frontmatter}\n{body}"))
     }
 
     /// Converts preprocessed HTML to markdown using html2text.
@@ -173,61 +465,343 @@ impl HtmlConverter {
         }
         None
     }
+
+    /// Extracts the published date from HTML metadata.
+    ///
+    /// Looks at common `<meta>` tags (Open Graph, Dublin Core, schema.org
+    /// microdata) and JSON-LD `datePublished` values, in that order.
+    fn extract_published_date(&self, html: &str) -> Option<DateTime<Utc>> {
+        self.extract_meta_values(
+            html,
+            "article:published_time|og:published_time|datepublished|date|pubdate|publish_date|publish-date|dc\\.date|dcterms\\.created",
+        )
+        .into_iter()
+        .chain(self.extract_json_ld_values(html, "datePublished"))
+        .find_map(|value| parse_date(&value))
+    }
+
+    /// Extracts the page's `<link rel="canonical">` URL, resolved against the page URL.
+    ///
+    /// Returns `None` when the page has no canonical link, or it is the page itself.
+    fn extract_canonical_url(&self, html: &str, url: &str) -> Option<String> {
+        let patterns = [
+            r#"(?is)<link[^>]+rel\s*=\s*["']canonical["'][^>]*href\s*=\s*["']([^"']+)["']"#,
+            r#"(?is)<link[^>]+href\s*=\s*["']([^"']+)["'][^>]*rel\s*=\s*["']canonical["']"#,
+        ];
+        let href = patterns.iter().find_map(|pattern| {
+            Regex::new(pattern)
+                .ok()?
+                .captures(html)
+                .map(|captures| captures[1].trim().to_string())
+        })?;
+        let canonical = url::Url::parse(url).ok()?.join(&href).ok()?;
+        (matches!(canonical.scheme(), "http" | "https") && canonical.as_str() != url)
+            .then(|| canonical.to_string())
+    }
+
+    /// Extracts the page description from `<meta>` tags.
+    fn extract_description(&self, html: &str) -> Option<String> {
+        self.extract_meta_values(html, "description|og:description|twitter:description")
+            .into_iter()
+            .next()
+    }
+
+    /// Extracts the author from `<meta>` tags or JSON-LD.
+    fn extract_author(&self, html: &str) -> Option<String> {
+        self.extract_meta_values(html, "author|article:author|dc\\.creator")
+            .into_iter()
+            .next()
+            .or_else(|| {
+                // JSON-LD authors are usually objects; take the first name inside them
+                Regex::new(r#"(?s)"author"\s*:\s*(?:\[\s*)?\{[^}]*?"name"\s*:\s*"([^"]+)""#)
+                    .ok()?
+                    .captures(html)
+                    .map(|captures| captures[1].trim().to_string())
+            })
+    }
+
+    /// Extracts the content type (e.g. `article`, `website`) from Open Graph metadata.
+    fn extract_content_type(&self, html: &str) -> Option<String> {
+        self.extract_meta_values(html, "og:type").into_iter().next()
+    }
+
+    /// Returns the `content` values of `<meta>` tags whose name matches the pattern.
+    ///
+    /// Both attribute orders (`name` before `content` and after) are supported.
+    fn extract_meta_values(&self, html: &str, names: &str) -> Vec<String> {
+        let patterns = [
+            format!(
+                r#"(?is)<meta[^>]+(?:property|name|itemprop)\s*=\s*["'](?:{names})["'][^>]*content\s*=\s*["']([^"']+)["']"#
+            ),
+            format!(
+                r#"(?is)<meta[^>]+content\s*=\s*["']([^"']+)["'][^>]*(?:property|name|itemprop)\s*=\s*["'](?:{names})["']"#
+            ),
+        ];
+
+        patterns
+            .iter()
+            .filter_map(|pattern| Regex::new(pattern).ok())
+            .flat_map(|re| {
+                re.captures_iter(html)
+                    .map(|captures| captures[1].trim().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|value| !value.is_empty())
+            .collect()
+    }
+
+    /// Returns the string values of a key in embedded JSON-LD.
+    fn extract_json_ld_values(&self, html: &str, key: &str) -> Vec<String> {
+        Regex::new(&format!(r#""{key}"\s*:\s*"([^"]+)""#))
+            .map(|re| {
+                re.captures_iter(html)
+                    .map(|captures| captures[1].to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Fetches the HTML for a URL with HTML-specific headers.
+    async fn fetch_html(&self, url: &str) -> Result<String, MarkdownError> {
+        self.client
+            .get_text_with_headers(url, &html_request_headers())
+            .await
+    }
+
+    /// Builds the YAML frontmatter for a page from its HTML metadata.
+    ///
+    /// `body` is the converted markdown, if any, used to pick tags. The
+    /// excerpt is taken from the HTML's paragraphs, whose boundaries are exact.
+    fn build_frontmatter(
+        &self,
+        url: &str,
+        html: &str,
+        body: Option<&str>,
+    ) -> Result<String, MarkdownError> {
+        let now = Utc::now();
+        let mut builder = FrontmatterBuilder::new(url.to_string())
+            .exporter(format!("markdowndown-html-{}", env!("CARGO_PKG_VERSION")))
+            .download_date(now)
+            .additional_field("converted_at".to_string(), now.to_rfc3339())
+            .additional_field("conversion_type".to_string(), "html".to_string())
+            .additional_field("url".to_string(), url.to_string());
+
+        // Try to extract title from HTML
+        if let Some(title) = self.extract_title(html) {
+            builder = builder.additional_field("title".to_string(), title);
+        }
+
+        if let Some(canonical) = self.extract_canonical_url(html, url) {
+            builder = builder.additional_field("canonical_url".to_string(), canonical);
+        }
+
+        let description = self.extract_description(html);
+        if let Some(sentences) = self.output_config.excerpt_sentences {
+            let excerpt = ExcerptExtractor::new()
+                .extract_html(html, sentences)
+                .or_else(|| description.clone());
+            if let Some(excerpt) = excerpt {
+                builder = builder.additional_field("excerpt".to_string(), excerpt);
+            }
+        }
+
+        if let Some(description) = description {
+            builder = builder.additional_field("description".to_string(), description);
+        }
+
+        if let Some(author) = self.extract_author(html) {
+            builder = builder.additional_field("author".to_string(), author);
+        }
+
+        if let Some(content_type) = self.extract_content_type(html) {
+            builder = builder.additional_field("type".to_string(), content_type);
+        }
+
+        if let Some(published) = self.extract_published_date(html) {
+            builder =
+                builder.additional_field("published_date".to_string(), published.to_rfc3339());
+        }
+
+        if let Some(max_tags) = self.output_config.max_tags {
+            let declared = self.extract_meta_values(html, "keywords|news_keywords|article:tag");
+            let tags = KeywordExtractor::new().extract(&declared, body, max_tags);
+            if !tags.is_empty() {
+                builder = builder.list_field("tags".to_string(), tags);
+            }
+        }
+
+        // Add custom frontmatter fields from configuration
+        for (key, value) in &self.output_config.custom_frontmatter_fields {
+            builder = builder.additional_field(key.clone(), value.clone());
+        }
+
+        builder.build()
+    }
 }
 
-#[async_trait]
-impl Converter for HtmlConverter {
-    /// Converts content from a URL to markdown by fetching HTML and converting it.
-    async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
-        // Fetch HTML content from URL with HTML-specific headers
-        let headers = std::collections::HashMap::from([(
-            "Accept".to_string(),
-            "text/html,application/xhtml+xml".to_string(),
-        )]);
-        let html_content = self.client.get_text_with_headers(url, &headers).await?;
+/// Returns the request headers used when fetching HTML pages.
+fn html_request_headers() -> HashMap<String, String> {
+    HashMap::from([(
+        "Accept".to_string(),
+        "text/html,application/xhtml+xml".to_string(),
+    )])
+}
 
-        // Convert HTML to markdown string
-        let markdown_string = self.convert_html(&html_content)?;
+/// Whether a body labelled as plain text starts like an HTML document or fragment.
+fn looks_like_html(text: &str) -> bool {
+    text.trim_start_matches('\u{feff}')
+        .trim_start()
+        .starts_with('<')
+}
 
-        // Handle empty content case - provide minimal markdown for empty HTML
-        let markdown_content = if markdown_string.trim().is_empty() {
-            "<!-- Empty HTML document -->".to_string()
-        } else {
-            markdown_string
-        };
+/// State of an in-progress streaming conversion.
+struct HtmlStream<'a> {
+    converter: &'a HtmlConverter,
+    url: String,
+    body: BoxStream<'static, Result<Bytes, MarkdownError>>,
+    chunker: HtmlChunker,
+    segments: VecDeque<String>,
+    body_done: bool,
+    failed: bool,
+    wrote_frontmatter: bool,
+    wrote_body: bool,
+}
 
-        // Only generate frontmatter if configured to include it
-        if self.output_config.include_frontmatter {
-            // Generate frontmatter
-            let now = Utc::now();
-            let mut builder = FrontmatterBuilder::new(url.to_string())
-                .exporter(format!("markdowndown-html-{}", env!("CARGO_PKG_VERSION")))
-                .download_date(now)
-                .additional_field("converted_at".to_string(), now.to_rfc3339())
-                .additional_field("conversion_type".to_string(), "html".to_string())
-                .additional_field("url".to_string(), url.to_string());
-
-            // Try to extract title from HTML
-            if let Some(title) = self.extract_title(&html_content) {
-                builder = builder.additional_field("title".to_string(), title);
+impl HtmlStream<'_> {
+    /// Returns the next markdown chunk, reading more of the body as needed.
+    async fn next_chunk(&mut self) -> Option<Result<String, MarkdownError>> {
+        if self.failed {
+            return None;
+        }
+        loop {
+            if let Some(segment) = self.segments.pop_front() {
+                match self.render_segment(&segment) {
+                    Ok(Some(chunk)) => return Some(Ok(chunk)),
+                    Ok(None) => continue,
+                    Err(e) => {
+                        self.failed = true;
+                        return Some(Err(e));
+                    }
+                }
+            }
+            if self.body_done {
+                return self.render_empty_document();
             }
 
-            // Add custom frontmatter fields from configuration
-            for (key, value) in &self.output_config.custom_frontmatter_fields {
-                builder = builder.additional_field(key.clone(), value.clone());
+            match self.body.next().await {
+                Some(Ok(bytes)) => self.segments.extend(self.chunker.push(&bytes)),
+                Some(Err(e)) => {
+                    self.failed = true;
+                    return Some(Err(e));
+                }
+                None => {
+                    self.body_done = true;
+                    self.segments.extend(self.chunker.finish());
+                }
             }
+        }
+    }
 
-            let frontmatter = builder.build()?;
+    /// Converts one HTML segment, prefixing the frontmatter to the first chunk.
+    fn render_segment(&mut self, segment: &str) -> Result<Option<String>, MarkdownError> {
+        let mut chunk = String::new();
+        if !self.wrote_frontmatter {
+            self.wrote_frontmatter = true;
+            if self.converter.output_config.include_frontmatter {
+                let frontmatter = self.converter.build_frontmatter(&self.url, segment, None)?;
+                chunk.push_str(&frontmatter);
+                chunk.push('\n');
+            }
+        }
 
-            // Combine frontmatter with content
-            let markdown_with_frontmatter = format!("{frontmatter}\n{markdown_content}");
+        let markdown = self.converter.convert_html(segment)?;
+        let body = markdown.trim();
+        if !body.is_empty() {
+            // Separate the blocks of consecutive segments with a blank line
+            if self.wrote_body {
+                chunk.push('\n');
+            }
+            chunk.push_str(body);
+            chunk.push('\n');
+            self.wrote_body = true;
+        }
 
-            // Wrap in Markdown type with validation
-            Markdown::new(markdown_with_frontmatter)
-        } else {
-            // No frontmatter - just return the markdown content
-            Markdown::new(markdown_content)
+        Ok((!chunk.is_empty()).then_some(chunk))
+    }
+
+    /// Ends a document that produced no markdown, as whole-page conversion does.
+    fn render_empty_document(&mut self) -> Option<Result<String, MarkdownError>> {
+        if self.wrote_body {
+            return None;
+        }
+        self.wrote_body = true;
+
+        if !self.wrote_frontmatter {
+            // Nothing but whitespace was received
+            return self.converter.convert_html("").err().map(Err);
+        }
+        Some(Ok("<!-- Empty HTML document -->".to_string()))
+    }
+}
+
+#[async_trait]
+impl Converter for HtmlConverter {
+    /// Converts content from a URL to markdown by fetching HTML and converting it.
+    async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+        // Fetch HTML content from URL with HTML-specific headers
+        let (content, metadata) = self
+            .client
+            .get_content_and_metadata_with_headers(url, &html_request_headers())
+            .await?;
+        let markdown = self
+            .convert_response(url, metadata.content_type().as_deref(), content)
+            .await?;
+
+        // Pages reached through a redirect live at the final URL
+        if metadata.url == url || frontmatter_field(markdown.as_str(), "canonical_url").is_some() {
+            return Ok(markdown);
         }
+        Ok(Markdown::from(set_frontmatter_field(
+            markdown.as_str(),
+            "canonical_url",
+            &metadata.url,
+        )))
+    }
+
+    /// Converts a page a segment at a time as its HTML is received.
+    ///
+    /// The first chunk holds the frontmatter, read from the page's `<head>`;
+    /// the excerpt and tags are taken from page metadata only, and soft-404
+    /// detection is skipped because it needs the whole page.
+    async fn convert_stream(&self, url: &str) -> Result<MarkdownStream<'_>, MarkdownError> {
+        let body = self
+            .client
+            .get_stream_with_headers(url, &html_request_headers())
+            .await?;
+
+        let state = HtmlStream {
+            converter: self,
+            url: url.to_string(),
+            body,
+            chunker: HtmlChunker::default(),
+            segments: VecDeque::new(),
+            body_done: false,
+            failed: false,
+            wrote_frontmatter: false,
+            wrote_body: false,
+        };
+        let chunks = stream::unfold(state, |mut state| async move {
+            let chunk = state.next_chunk().await?;
+            Some((chunk, state))
+        });
+        Ok(chunks.boxed())
+    }
+
+    /// Extracts frontmatter from the page metadata without converting the body.
+    async fn convert_frontmatter(&self, url: &str) -> Result<Markdown, MarkdownError> {
+        let html_content = self.fetch_html(url).await?;
+        let frontmatter = self.build_frontmatter(url, &html_content, None)?;
+        Ok(Markdown::from(frontmatter))
     }
 
     /// Returns the name of this converter.
@@ -246,6 +820,7 @@ impl Default for HtmlConverter {
 mod tests {
     use super::*;
     use crate::config::{AuthConfig, HttpConfig, OutputConfig};
+    use crate::frontmatter::{frontmatter_field, frontmatter_list};
     use std::time::Duration;
     use wiremock::matchers::{method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -311,6 +886,7 @@ mod tests {
     }
 
     /// Comprehensive tests for improved coverage
+    #[allow(clippy::field_reassign_with_default)]
     mod comprehensive_coverage_tests {
         use super::*;
 
@@ -319,15 +895,41 @@ mod tests {
             // Test `with_config` method (covers constructor path)
             let http_config = HttpConfig {
                 timeout: Duration::from_secs(30),
+                total_timeout: None,
                 user_agent: "test-agent".to_string(),
                 max_retries: 3,
                 retry_delay: Duration::from_secs(1),
+                retry_policy: None,
+                max_retry_after: Duration::from_secs(60),
                 max_redirects: 10,
+                request_templates: Vec::new(),
+                race_strategies: false,
+                sniff_content_type: false,
+                resolve_redirects: false,
+                ipfs_gateways: Vec::new(),
+                wayback_api_url: None,
+                dns_cache_ttl: None,
+                connection_idle_ttl: Duration::from_secs(90),
+                proxy: None,
+                no_proxy: Vec::new(),
+                proxy_from_env: true,
+                root_certificates: Vec::new(),
+                danger_accept_invalid_certs: false,
+                client_certificate: None,
+                default_headers: Vec::new(),
+                cache: None,
+                rate_limits: Vec::new(),
+                max_response_bytes: None,
             };
             let auth_config = AuthConfig {
                 github_token: None,
+                github_api_base_url: None,
+                azure_devops_token: None,
                 office365_token: None,
+                dropbox_token: None,
                 google_api_key: None,
+                google_service_account_key: None,
+                oauth_sessions: Vec::new(),
             };
             let client = HttpClient::with_config(&http_config, &auth_config);
             
@@ -338,6 +940,14 @@ mod tests {
                 remove_sidebars: true,
                 remove_ads: false,
                 max_blank_lines: 3,
+                detect_soft_404: true,
+                detect_access_wall: true,
+                detect_login_walls: false,
+                escape_mode: crate::converters::EscapeMode::Minimal,
+                passthrough_unconvertible: false,
+                summarize_forms: false,
+                detection_strategy: crate::converters::DetectionStrategy::Url,
+                plain_text: crate::converters::PlainTextHeuristics::default(),
             };
             
             let output_config = OutputConfig {
@@ -347,6 +957,26 @@ mod tests {
                 ],
                 normalize_whitespace: true,
                 max_consecutive_blank_lines: 2,
+                frontmatter_only: false,
+                excerpt_sentences: None,
+                max_tags: None,
+                comment_mode: crate::config::CommentMode::Omit,
+                tracked_changes: crate::config::TrackedChangeMode::Accept,
+                sub_documents: crate::config::SubDocumentMode::Concatenate,
+                max_comments: None,
+                lint_mode: crate::config::LintMode::Off,
+                lint_rules: crate::config::LintRule::all(),
+                compression: crate::compression::Compression::None,
+                resolve_includes: false,
+                extract_language: None,
+                direction_markers: crate::config::DirectionMarkers::Off,
+                google_docs_export_format: crate::config::GoogleDocsExportFormat::Text,
+                profiling: false,
+                download_images: crate::config::ImageDownloads::Off,
+                strip_tracking_params: false,
+                tracking_params: Vec::new(),
+                frontmatter_format: crate::frontmatter::FrontmatterFormat::Yaml,
+                notebook_outputs: true,
             };
 
             let converter = HtmlConverter::with_config(client, html_config.clone(), output_config.clone());
@@ -396,6 +1026,401 @@ mod tests {
             assert_eq!(title.unwrap(), "Trimmed Title");
         }
 
+        #[test]
+        fn test_extract_published_date_from_meta() {
+            let converter = HtmlConverter::new();
+            let html = r#"<head><meta property="article:published_time" content="2024-03-05T08:00:00+00:00"></head>"#;
+
+            let date = converter.extract_published_date(html);
+            assert_eq!(
+                date.map(|d| d.to_rfc3339()),
+                Some("2024-03-05T08:00:00+00:00".to_string())
+            );
+
+            // Attribute order should not matter
+            let html = r#"<meta content="2024-03-05" name="date">"#;
+            assert!(converter.extract_published_date(html).is_some());
+        }
+
+        #[test]
+        fn test_extract_published_date_from_json_ld() {
+            let converter = HtmlConverter::new();
+            let html = r#"<script type="application/ld+json">{"@type":"Article","datePublished":"2023-11-20"}</script>"#;
+
+            let date = converter.extract_published_date(html);
+            assert_eq!(
+                date.map(|d| d.to_rfc3339()),
+                Some("2023-11-20T00:00:00+00:00".to_string())
+            );
+            assert!(converter
+                .extract_published_date("<p>No dates here</p>")
+                .is_none());
+        }
+
+        #[test]
+        fn test_extract_page_metadata() {
+            let converter = HtmlConverter::new();
+            let html = r#"<head>
+                <meta name="description" content="A short summary">
+                <meta content="Jane Doe" name="author">
+                <meta property="og:type" content="article">
+            </head>"#;
+
+            assert_eq!(
+                converter.extract_description(html),
+                Some("A short summary".to_string())
+            );
+            assert_eq!(converter.extract_author(html), Some("Jane Doe".to_string()));
+            assert_eq!(
+                converter.extract_content_type(html),
+                Some("article".to_string())
+            );
+
+            let json_ld = r#"<script type="application/ld+json">{"author":{"@type":"Person","name":"John Roe"}}</script>"#;
+            assert_eq!(
+                converter.extract_author(json_ld),
+                Some("John Roe".to_string())
+            );
+            assert!(converter.extract_description("<p>Plain</p>").is_none());
+        }
+
+        #[test]
+        fn test_extract_canonical_url() {
+            let converter = HtmlConverter::new();
+            let url = "https://mirror.example.com/posts/42";
+
+            let html = r#"<link href="/canonical/42" rel="canonical">"#;
+            assert_eq!(
+                converter.extract_canonical_url(html, url),
+                Some("https://mirror.example.com/canonical/42".to_string())
+            );
+            let html = r#"<link rel="canonical" href="https://github.com/o/r/issues/42">"#;
+            assert_eq!(
+                converter.extract_canonical_url(html, url),
+                Some("https://github.com/o/r/issues/42".to_string())
+            );
+
+            // Self-references and non-web URLs are ignored
+            let html = format!(r#"<link rel="canonical" href="{url}">"#);
+            assert!(converter.extract_canonical_url(&html, url).is_none());
+            let html = r#"<link rel="canonical" href="file:///etc/hosts">"#;
+            assert!(converter.extract_canonical_url(html, url).is_none());
+            assert!(converter
+                .extract_canonical_url("<p>Plain</p>", url)
+                .is_none());
+        }
+
+        #[tokio::test]
+        async fn test_convert_frontmatter_skips_body() {
+            let mock_server = MockServer::start().await;
+
+            let html_content = r#"<html><head><title>Metadata Page</title><meta name="description" content="Just the facts"></head><body><h1>Body Heading</h1><p>Body text.</p></body></html>"#;
+
+            Mock::given(method("GET"))
+                .and(path("/meta"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(html_content))
+                .mount(&mock_server)
+                .await;
+
+            let url = format!("{}/meta", mock_server.uri());
+            let markdown = HtmlConverter::new()
+                .convert_frontmatter(&url)
+                .await
+                .unwrap();
+
+            let frontmatter = markdown.frontmatter().unwrap();
+            assert!(frontmatter.contains("title: Metadata Page"));
+            assert!(frontmatter.contains("description: Just the facts"));
+            assert!(markdown.content_only().trim().is_empty());
+            assert!(!markdown.as_str().contains("Body Heading"));
+        }
+
+        #[tokio::test]
+        async fn test_converter_adds_excerpt() {
+            let mock_server = MockServer::start().await;
+
+            let html_content = r#"<html><head><title>Essay</title><meta name="description" content="Meta summary."></head><body><h1>Essay</h1><p>First point is made. Second point follows. Third is cut.</p></body></html>"#;
+
+            Mock::given(method("GET"))
+                .and(path("/essay"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(html_content))
+                .mount(&mock_server)
+                .await;
+
+            let output_config = OutputConfig {
+                excerpt_sentences: Some(2),
+                ..Default::default()
+            };
+            let converter = HtmlConverter::with_config(
+                HttpClient::new(),
+                HtmlConverterConfig::default(),
+                output_config,
+            );
+            let url = format!("{}/essay", mock_server.uri());
+
+            let markdown = converter.convert(&url).await.unwrap();
+            assert_eq!(
+                frontmatter_field(markdown.as_str(), "excerpt"),
+                Some("First point is made. Second point follows.".to_string())
+            );
+
+            // The excerpt comes from the HTML, so frontmatter-only output has it too
+            let markdown = converter.convert_frontmatter(&url).await.unwrap();
+            assert_eq!(
+                frontmatter_field(markdown.as_str(), "excerpt"),
+                Some("First point is made. Second point follows.".to_string())
+            );
+
+            // Without paragraphs, the meta description is used instead
+            Mock::given(method("GET"))
+                .and(path("/gallery"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    r#"<html><head><meta name="description" content="Meta summary."></head><body><img src="a.png"></body></html>"#,
+                ))
+                .mount(&mock_server)
+                .await;
+            let gallery_url = format!("{}/gallery", mock_server.uri());
+            let markdown = converter.convert(&gallery_url).await.unwrap();
+            assert_eq!(
+                frontmatter_field(markdown.as_str(), "excerpt"),
+                Some("Meta summary.".to_string())
+            );
+
+            // Excerpts are off by default
+            let markdown = HtmlConverter::new().convert(&url).await.unwrap();
+            assert_eq!(frontmatter_field(markdown.as_str(), "excerpt"), None);
+        }
+
+        #[tokio::test]
+        async fn test_converter_adds_tags() {
+            let mock_server = MockServer::start().await;
+
+            let html_content = r#"<html><head><title>Tagged</title><meta name="keywords" content="Web, Scraping"><meta property="article:tag" content="markdown"></head><body><p>Crawlers fetch pages. Crawlers parse pages. Crawlers store pages.</p></body></html>"#;
+
+            Mock::given(method("GET"))
+                .and(path("/tagged"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(html_content))
+                .mount(&mock_server)
+                .await;
+
+            let output_config = OutputConfig {
+                max_tags: Some(4),
+                ..Default::default()
+            };
+            let converter = HtmlConverter::with_config(
+                HttpClient::new(),
+                HtmlConverterConfig::default(),
+                output_config,
+            );
+            let url = format!("{}/tagged", mock_server.uri());
+
+            let markdown = converter.convert(&url).await.unwrap();
+            assert_eq!(
+                frontmatter_list(markdown.as_str(), "tags"),
+                Some(vec![
+                    "web".to_string(),
+                    "scraping".to_string(),
+                    "markdown".to_string(),
+                    "crawlers".to_string(),
+                ])
+            );
+
+            // Tags are off by default
+            let markdown = HtmlConverter::new().convert(&url).await.unwrap();
+            assert_eq!(frontmatter_list(markdown.as_str(), "tags"), None);
+        }
+
+        #[tokio::test]
+        async fn test_converter_rejects_soft_404() {
+            let mock_server = MockServer::start().await;
+
+            let html_content = r#"<html><head><title>Page Not Found</title></head><body><h1>Oops!</h1><p>We couldn't find that page.</p></body></html>"#;
+
+            Mock::given(method("GET"))
+                .and(path("/missing"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(html_content))
+                .mount(&mock_server)
+                .await;
+
+            let url = format!("{}/missing", mock_server.uri());
+            let config = HtmlConverterConfig {
+                detect_soft_404: true,
+                ..Default::default()
+            };
+            let result = HtmlConverter::with_config_only(config).convert(&url).await;
+
+            match result {
+                Err(error @ MarkdownError::ContentError { .. }) => {
+                    assert!(error.is_dead_link());
+                    assert!(!error.is_recoverable());
+                }
+                other => panic!("Expected soft-404 ContentError, got: {other:?}"),
+            }
+
+            // Detection is opt-in
+            assert!(HtmlConverter::new().convert(&url).await.is_ok());
+        }
+
+        #[tokio::test]
+        async fn test_converter_rejects_access_wall() {
+            let mock_server = MockServer::start().await;
+
+            let html_content = r#"<html><head><title>Rates rise again</title><meta property="og:type" content="paywall"></head><body><h1>Rates rise again</h1><p>The central bank raised rates.</p><p>Subscribe now to continue reading.</p></body></html>"#;
+
+            Mock::given(method("GET"))
+                .and(path("/article"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(html_content))
+                .mount(&mock_server)
+                .await;
+
+            let url = format!("{}/article", mock_server.uri());
+            match HtmlConverter::new().convert(&url).await {
+                Err(error @ MarkdownError::ContentError { .. }) => {
+                    assert!(matches!(
+                        error,
+                        MarkdownError::ContentError {
+                            kind: ContentErrorKind::AccessWall,
+                            ..
+                        }
+                    ));
+                    assert!(!error.is_dead_link());
+                    assert!(!error.suggestions().is_empty());
+                }
+                other => panic!("Expected access wall ContentError, got: {other:?}"),
+            }
+
+            // Detection can be disabled to keep the teaser
+            let config = HtmlConverterConfig {
+                detect_access_wall: false,
+                ..Default::default()
+            };
+            let converter = HtmlConverter::with_config_only(config);
+            let markdown = converter.convert(&url).await.unwrap();
+            assert!(markdown.as_str().contains("The central bank raised rates."));
+        }
+
+        #[tokio::test]
+        async fn test_content_type_fallback_routes_text_responses() {
+            let mock_server = MockServer::start().await;
+
+            Mock::given(method("GET"))
+                .and(path("/notes"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_raw("# Notes\n\n* one\n* two\n", "text/markdown; charset=utf-8"),
+                )
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/status"))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_raw(r#"{"ok":true}"#, "application/json"),
+                )
+                .mount(&mock_server)
+                .await;
+
+            let notes = format!("{}/notes", mock_server.uri());
+            let status = format!("{}/status", mock_server.uri());
+            let config = HtmlConverterConfig {
+                detection_strategy: DetectionStrategy::ContentTypeFallback,
+                ..Default::default()
+            };
+            let converter = HtmlConverter::with_config(
+                HttpClient::new(),
+                config,
+                OutputConfig {
+                    include_frontmatter: false,
+                    ..Default::default()
+                },
+            );
+
+            let markdown = converter.convert(&notes).await.unwrap();
+            assert_eq!(markdown.as_str(), "# Notes\n\n* one\n* two\n");
+            let markdown = converter.convert(&status).await.unwrap();
+            assert_eq!(markdown.as_str(), "```json\n{\n  \"ok\": true\n}\n```\n");
+
+            // JSON is a code block whatever the strategy
+            let markdown = HtmlConverter::with_config(
+                HttpClient::new(),
+                HtmlConverterConfig::default(),
+                OutputConfig {
+                    include_frontmatter: false,
+                    ..Default::default()
+                },
+            )
+            .convert(&status)
+            .await
+            .unwrap();
+            assert_eq!(markdown.as_str(), "```json\n{\n  \"ok\": true\n}\n```\n");
+        }
+
+        #[tokio::test]
+        async fn test_convert_response_structured_payloads() {
+            let converter = HtmlConverter::new();
+            let xml = "<repo><id>7</id><name>widgets</name><url>x</url></repo>";
+            let markdown = converter
+                .convert_response(
+                    "https://api.example.com/repos/7",
+                    Some("application/xml"),
+                    ResponseContent::Text(xml.to_string()),
+                )
+                .await
+                .unwrap();
+            let markdown = markdown.as_str();
+
+            assert!(markdown.contains("conversion_type: xml"));
+            assert!(markdown.contains("name: widgets"));
+            assert_eq!(
+                frontmatter_field(markdown, "url").as_deref(),
+                Some("https://api.example.com/repos/7")
+            );
+            assert!(markdown.contains("```xml\n<repo>\n  <id>7</id>\n"));
+
+            let result = converter
+                .convert_response(
+                    "https://api.example.com/empty",
+                    Some("application/json"),
+                    ResponseContent::Text("  ".to_string()),
+                )
+                .await;
+            assert!(matches!(
+                result,
+                Err(MarkdownError::ContentError {
+                    kind: ContentErrorKind::EmptyContent,
+                    ..
+                })
+            ));
+        }
+
+        #[tokio::test]
+        async fn test_convert_response_plain_text() {
+            let converter = HtmlConverter::new();
+            let text = "Changelog\n=========\n\n* Faster\n* Smaller\n";
+            let markdown = converter
+                .convert_response(
+                    "https://example.com/CHANGES.txt",
+                    Some("text/plain"),
+                    ResponseContent::Text(text.to_string()),
+                )
+                .await
+                .unwrap();
+            let markdown = markdown.as_str();
+            assert!(markdown.contains("conversion_type: text"));
+            assert!(markdown.ends_with("\n# Changelog\n\n- Faster\n- Smaller\n"));
+
+            // HTML labelled as plain text is still converted as HTML
+            let markdown = converter
+                .convert_response(
+                    "https://example.com/page",
+                    Some("text/plain"),
+                    ResponseContent::Text("<h1>Hi</h1><p>There</p>".to_string()),
+                )
+                .await
+                .unwrap();
+            assert!(!markdown.as_str().contains("conversion_type: text"));
+            assert!(markdown.as_str().contains("# Hi"));
+        }
+
         #[tokio::test]
         async fn test_converter_async_with_frontmatter() {
             // Test the async convert method with frontmatter enabled
@@ -611,5 +1636,47 @@ mod tests {
             // but we can verify the conversion succeeded
             assert!(markdown.contains("very long paragraph"));
         }
+
+        #[test]
+        fn test_convert_html_summarizes_forms() {
+            let html = r#"<p>Request access below.</p><form action="/access" method="post"><label for="team">Team</label><input id="team" name="team" required><button>Request</button></form>"#;
+
+            let stripped = HtmlConverter::new().convert_html(html).unwrap();
+            assert!(!stripped.contains("Team (text, required)"));
+
+            let config = HtmlConverterConfig {
+                summarize_forms: true,
+                ..Default::default()
+            };
+            let markdown = HtmlConverter::with_config_only(config)
+                .convert_html(html)
+                .unwrap();
+            assert!(markdown.contains("Form"));
+            assert!(markdown.contains("POST /access"));
+            assert!(markdown.contains("Team (text, required)"));
+            assert!(markdown.contains("Buttons: Request"));
+        }
+
+        #[test]
+        fn test_convert_html_passes_unconvertible_elements_through() {
+            let html = r#"<h1>Survey</h1><p>Please answer below.</p><form action="/submit"><input name="answer"></form><p>Thanks.</p>"#;
+
+            let flattened = HtmlConverter::new().convert_html(html).unwrap();
+            assert!(!flattened.contains("<form"));
+
+            let config = HtmlConverterConfig {
+                passthrough_unconvertible: true,
+                ..Default::default()
+            };
+            let markdown = HtmlConverter::with_config_only(config)
+                .convert_html(html)
+                .unwrap();
+            assert!(markdown.contains("> **Warning:** form could not be converted to markdown"));
+            assert!(markdown.contains(
+                "```html\n<form action=\"/submit\"><input name=\"answer\"></form>\n```"
+            ));
+            assert!(markdown.contains("Please answer below."));
+            assert!(markdown.contains("Thanks."));
+        }
     }
 }
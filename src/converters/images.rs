@@ -0,0 +1,171 @@
+//! Image elements kept through HTML conversion.
+//!
+//! html2text renders an image as its alt text in brackets and drops the
+//! source. When images are downloaded, each `<img>` is replaced by a
+//! placeholder word before conversion and put back afterwards as a markdown
+//! image link, which [`crate::assets`] then downloads.
+
+use regex::Regex;
+
+/// Marker text that stands in for an image during conversion.
+const PLACEHOLDER_PREFIX: &str = "MARKDOWNDOWNIMAGE";
+
+/// An image element set aside during conversion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageLink {
+    /// The `src` attribute, with HTML entities decoded
+    pub src: String,
+    /// The `alt` attribute, with HTML entities decoded
+    pub alt: String,
+}
+
+impl ImageLink {
+    /// Renders the image as a markdown image link.
+    ///
+    /// Spaces and parentheses in the source are percent-encoded so the link
+    /// parses without angle brackets.
+    pub fn to_markdown(&self) -> String {
+        let alt = self
+            .alt
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .replace('[', "\\[")
+            .replace(']', "\\]");
+        let src = self
+            .src
+            .trim()
+            .replace(' ', "%20")
+            .replace('(', "%28")
+            .replace(')', "%29");
+        format!("![{alt}]({src})")
+    }
+}
+
+/// Replaces `<img>` elements with placeholders and restores them as markdown images.
+pub struct ImagePreserver {
+    img: Regex,
+    src: Regex,
+    alt: Regex,
+    placeholder: Regex,
+}
+
+impl ImagePreserver {
+    /// Creates a new image preserver.
+    pub fn new() -> Self {
+        Self {
+            img: Regex::new(r"(?is)<img\b[^>]*>").expect("valid image pattern"),
+            src: Regex::new(r#"(?is)\ssrc\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+))"#)
+                .expect("valid src pattern"),
+            alt: Regex::new(r#"(?is)\salt\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+))"#)
+                .expect("valid alt pattern"),
+            placeholder: Regex::new(&format!("{PLACEHOLDER_PREFIX}([0-9]+)END"))
+                .expect("valid placeholder pattern"),
+        }
+    }
+
+    /// Replaces image elements with placeholders.
+    ///
+    /// Images without a `src` attribute are left in place.
+    ///
+    /// # Arguments
+    ///
+    /// * `html` - The HTML document
+    ///
+    /// # Returns
+    ///
+    /// The document with each image replaced by a placeholder word, and the
+    /// images in document order.
+    pub fn extract(&self, html: &str) -> (String, Vec<ImageLink>) {
+        let mut images = Vec::new();
+        let output = self.img.replace_all(html, |caps: &regex::Captures| {
+            let tag = &caps[0];
+            let Some(src) = attribute(&self.src, tag).filter(|src| !src.trim().is_empty()) else {
+                return tag.to_string();
+            };
+            images.push(ImageLink {
+                src,
+                alt: attribute(&self.alt, tag).unwrap_or_default(),
+            });
+            format!("{PLACEHOLDER_PREFIX}{}END", images.len() - 1)
+        });
+        (output.into_owned(), images)
+    }
+
+    /// Replaces the placeholders in converted markdown with markdown image links.
+    ///
+    /// # Arguments
+    ///
+    /// * `markdown` - The converted markdown
+    /// * `images` - The images returned by [`ImagePreserver::extract`]
+    pub fn restore(&self, markdown: &str, images: &[ImageLink]) -> String {
+        if images.is_empty() {
+            return markdown.to_string();
+        }
+        self.placeholder
+            .replace_all(markdown, |caps: &regex::Captures| {
+                caps[1]
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| images.get(index))
+                    .map(ImageLink::to_markdown)
+                    .unwrap_or_default()
+            })
+            .into_owned()
+    }
+}
+
+impl Default for ImagePreserver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the decoded value of an attribute matched by `pattern` in a tag.
+fn attribute(pattern: &Regex, tag: &str) -> Option<String> {
+    let caps = pattern.captures(tag)?;
+    let value = caps.get(1).or(caps.get(2)).or(caps.get(3))?.as_str();
+    Some(decode_entities(value))
+}
+
+/// Decodes the HTML entities common in attribute values.
+fn decode_entities(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_and_restore_images() {
+        let preserver = ImagePreserver::new();
+        let html = r#"<p>Logo <img src="/logo.png?a=1&amp;b=2" alt="Our [logo]"> and <img alt="none"></p><img src='my pic.jpg'>"#;
+
+        let (extracted, images) = preserver.extract(html);
+        assert_eq!(images.len(), 2);
+        assert_eq!(images[0].src, "/logo.png?a=1&b=2");
+        assert!(extracted.contains("MARKDOWNDOWNIMAGE0END"));
+        assert!(extracted.contains(r#"<img alt="none">"#));
+
+        let restored = preserver.restore(
+            "Logo MARKDOWNDOWNIMAGE0END\n\nMARKDOWNDOWNIMAGE1END\n",
+            &images,
+        );
+        assert_eq!(
+            restored,
+            "Logo ![Our \\[logo\\]](/logo.png?a=1&b=2)\n\n![](my%20pic.jpg)\n"
+        );
+    }
+
+    #[test]
+    fn test_restore_without_images_is_unchanged() {
+        let preserver = ImagePreserver::new();
+        assert_eq!(preserver.restore("# Title\n", &[]), "# Title\n");
+    }
+}
@@ -0,0 +1,172 @@
+//! IPFS converter for content-addressed documents.
+//!
+//! `ipfs://<cid>/<path>` URIs are fetched through HTTP gateways, which serve
+//! IPFS content at `<gateway>/ipfs/<cid>/<path>`. The configured gateways are
+//! tried in order, so an unavailable or failing gateway is skipped, and the
+//! response is converted by its content type like any fetched page.
+
+use crate::client::HttpClient;
+use crate::types::{ConfigErrorKind, ErrorContext, Markdown, MarkdownError};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use tracing::{info, instrument, warn};
+
+use super::html::HtmlConverter;
+
+/// Converter for documents published on IPFS.
+///
+/// Gateways are read from the HTTP client, as configured with
+/// `Config::builder().ipfs_gateways(...)`.
+#[derive(Debug, Clone)]
+pub struct IpfsConverter {
+    client: HttpClient,
+    html_converter: HtmlConverter,
+}
+
+impl IpfsConverter {
+    /// Creates a new IPFS converter using the default public gateways.
+    pub fn new() -> Self {
+        Self {
+            client: HttpClient::new(),
+            html_converter: HtmlConverter::new(),
+        }
+    }
+
+    /// Creates an IPFS converter with a configured client and HTML converter.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client used to fetch from gateways, and whose gateway list is used
+    /// * `html_converter` - The configured converter used for fetched documents
+    pub fn with_config(client: HttpClient, html_converter: HtmlConverter) -> Self {
+        Self {
+            client,
+            html_converter,
+        }
+    }
+
+    /// Returns the gateway URLs for an `ipfs://` URI, in the order to try them.
+    fn gateway_urls(&self, url: &str) -> Vec<String> {
+        let content_path = url.trim()[7..].split('#').next().unwrap_or_default();
+        self.client
+            .ipfs_gateways()
+            .iter()
+            .map(|gateway| format!("{}/ipfs/{content_path}", gateway.trim_end_matches('/')))
+            .collect()
+    }
+}
+
+impl Default for IpfsConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl super::Converter for IpfsConverter {
+    /// Fetches an `ipfs://` URI through the configured gateways and converts it.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The `ipfs://` URI of the document
+    ///
+    /// # Returns
+    ///
+    /// Returns the converted markdown, or the last gateway's error if every gateway failed.
+    #[instrument(skip(self))]
+    async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+        let url = url.trim();
+        let mut last_error = None;
+        for gateway_url in self.gateway_urls(url) {
+            info!("Fetching IPFS content from {}", gateway_url);
+            match self
+                .client
+                .get_content_with_headers(&gateway_url, &HashMap::new())
+                .await
+            {
+                Ok(content) => return self.html_converter.convert_content(url, content).await,
+                Err(e) => {
+                    warn!("IPFS gateway failed, trying the next one: {}", e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            let context = ErrorContext::new(url, "Gateway selection", "IpfsConverter")
+                .with_info("No IPFS gateways are configured");
+            MarkdownError::ConfigurationError {
+                kind: ConfigErrorKind::InvalidConfig,
+                context,
+            }
+        }))
+    }
+
+    fn name(&self) -> &'static str {
+        "IPFS Converter"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::converters::converter::Converter;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const CID: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+
+    fn converter_for(gateways: Vec<String>) -> IpfsConverter {
+        let config = Config::builder()
+            .max_retries(0)
+            .ipfs_gateways(gateways)
+            .build();
+        let client = HttpClient::with_config(&config.http, &config.auth);
+        IpfsConverter::with_config(client, HtmlConverter::new())
+    }
+
+    #[tokio::test]
+    async fn test_convert_fails_over_to_next_gateway() {
+        let broken = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(504))
+            .mount(&broken)
+            .await;
+        let working = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("/ipfs/{CID}/wiki/Rust.html")))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Type", "text/html")
+                    .set_body_string("<h1>Rust</h1><p>Archived on IPFS.</p>"),
+            )
+            .mount(&working)
+            .await;
+
+        let converter = converter_for(vec![broken.uri(), working.uri()]);
+        let markdown = converter
+            .convert(&format!("ipfs://{CID}/wiki/Rust.html"))
+            .await
+            .unwrap();
+        assert!(markdown.as_str().contains("# Rust"));
+        assert!(markdown.as_str().contains("Archived on IPFS."));
+        assert!(markdown
+            .as_str()
+            .contains(&format!("ipfs://{CID}/wiki/Rust.html")));
+    }
+
+    #[tokio::test]
+    async fn test_convert_without_gateways() {
+        let result = converter_for(Vec::new())
+            .convert(&format!("ipfs://{CID}"))
+            .await;
+        assert!(matches!(
+            result,
+            Err(MarkdownError::ConfigurationError {
+                kind: ConfigErrorKind::InvalidConfig,
+                ..
+            })
+        ));
+    }
+}
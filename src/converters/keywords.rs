@@ -0,0 +1,126 @@
+//! Keyword extraction for frontmatter tags.
+//! Tags declared by the page (meta keywords, `article:tag`) come first; any
+//! remaining slots are filled with the most frequent content words of the body.
+
+use std::collections::HashMap;
+
+/// Words shorter than this are never used as tags.
+const MIN_KEYWORD_LENGTH: usize = 4;
+
+/// A body word must appear at least this many times to become a tag.
+const MIN_KEYWORD_FREQUENCY: usize = 2;
+
+/// Common English words that carry no topical meaning.
+const STOPWORDS: &[&str] = &[
+    "about", "above", "after", "again", "also", "been", "before", "being", "below", "between",
+    "both", "came", "come", "could", "does", "doing", "down", "each", "even", "every", "from",
+    "further", "have", "having", "here", "into", "just", "know", "like", "made", "make", "many",
+    "more", "most", "much", "must", "only", "other", "over", "said", "same", "should", "some",
+    "such", "take", "than", "that", "their", "them", "then", "there", "these", "they", "this",
+    "those", "through", "under", "until", "very", "want", "well", "were", "what", "when",
+    "where", "which", "while", "will", "with", "would", "your", "yours",
+];
+
+/// Extracts tags from declared page keywords and body term frequency.
+pub struct KeywordExtractor;
+
+impl KeywordExtractor {
+    /// Creates a new keyword extractor.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns up to `max_tags` lowercase tags.
+    ///
+    /// # Arguments
+    ///
+    /// * `declared` - Raw keyword values from the page; comma-separated lists are split
+    /// * `body` - The converted markdown used for term-frequency keywords, if available
+    /// * `max_tags` - The maximum number of tags to return
+    pub fn extract(&self, declared: &[String], body: Option<&str>, max_tags: usize) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        let declared_tags = declared
+            .iter()
+            .flat_map(|value| value.split(','))
+            .map(|tag| tag.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase());
+
+        for tag in declared_tags.chain(self.frequent_terms(body.unwrap_or_default())) {
+            if tags.len() == max_tags {
+                break;
+            }
+            if !tag.is_empty() && !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+
+        tags
+    }
+
+    /// Returns content words ordered by descending frequency, then alphabetically.
+    fn frequent_terms(&self, body: &str) -> Vec<String> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for word in body.split(|c: char| !c.is_alphanumeric() && c != '-') {
+            let word = word.trim_matches('-').to_lowercase();
+            if word.chars().count() >= MIN_KEYWORD_LENGTH
+                && word.chars().any(char::is_alphabetic)
+                && !STOPWORDS.contains(&word.as_str())
+            {
+                *counts.entry(word).or_default() += 1;
+            }
+        }
+
+        let mut terms: Vec<(String, usize)> = counts
+            .into_iter()
+            .filter(|(_, count)| *count >= MIN_KEYWORD_FREQUENCY)
+            .collect();
+        terms.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        terms.into_iter().map(|(term, _)| term).collect()
+    }
+}
+
+impl Default for KeywordExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declared_keywords_come_first() {
+        let extractor = KeywordExtractor::new();
+        let declared = vec!["Rust, Async  IO".to_string(), "rust".to_string()];
+
+        assert_eq!(
+            extractor.extract(&declared, None, 5),
+            vec!["rust".to_string(), "async io".to_string()]
+        );
+        assert_eq!(extractor.extract(&declared, None, 1), vec!["rust".to_string()]);
+    }
+
+    #[test]
+    fn test_frequent_terms_fill_remaining_slots() {
+        let extractor = KeywordExtractor::new();
+        let body = "Tokio runtime. The tokio runtime schedules tasks; tasks yield. \
+                    Tokio is fast. This text mentions other words once.";
+
+        assert_eq!(
+            extractor.extract(&[], Some(body), 3),
+            vec!["tokio".to_string(), "runtime".to_string(), "tasks".to_string()]
+        );
+        assert_eq!(
+            extractor.extract(&["async".to_string()], Some(body), 2),
+            vec!["async".to_string(), "tokio".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_ignores_stopwords_and_short_words() {
+        let extractor = KeywordExtractor::new();
+        let body = "this this that that with with the the 2024 2024";
+
+        assert!(extractor.extract(&[], Some(body), 5).is_empty());
+    }
+}
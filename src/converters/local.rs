@@ -1,13 +1,180 @@
 //! Local file converter for reading markdown files from the filesystem.
 //!
 //! This converter handles local file paths and file:// URLs by reading markdown content
-//! directly from the local filesystem.
+//! directly from the local filesystem. Gzip and zstd compressed files, such as
+//! stored `.md.zst` output, are decompressed transparently.
+//!
+//! Files that are not markdown are routed by their contents and extension:
+//! HTML pages go through the HTML pipeline, and PDF documents, Word
+//! documents, Jupyter notebooks, AsciiDoc and reStructuredText sources, and
+//! saved emails and web archives are handed to their converters. `.txt` files
+//! are given structure as plain text, and everything else is read as
+//! markdown text.
+//!
+//! Whole directories are converted with
+//! [`LocalFileConverter::convert_directory`], optionally writing the results
+//! to a mirrored output tree.
 
-use crate::types::{ContentErrorKind, ErrorContext, Markdown, MarkdownError};
+use crate::client::ResponseContent;
+use crate::types::{ContentErrorKind, ConverterErrorKind, ErrorContext, Markdown, MarkdownError};
 use async_trait::async_trait;
-use std::path::Path;
-use tokio::fs;
-use tracing::{debug, info, instrument};
+use bytes::Bytes;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, instrument, warn};
+
+use super::docx::is_docx;
+use super::email::EmailConverter;
+use super::html::HtmlConverter;
+use super::markup::{MarkupConverter, MarkupSyntax};
+use super::notebook::NotebookConverter;
+use super::pdf::PDF_MAGIC;
+
+/// Media type Word documents are handed to the HTML converter with.
+const DOCX_MEDIA_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+
+/// How a local file is converted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LocalFormat {
+    /// Markdown or other text, used as it is
+    Text,
+    /// A `.txt` file, given structure by the plain text heuristics
+    PlainText,
+    /// An HTML page, converted with the HTML pipeline
+    Html,
+    /// A PDF document
+    Pdf,
+    /// A Word document
+    Docx,
+    /// A Jupyter notebook
+    Notebook,
+    /// An AsciiDoc or reStructuredText source
+    Markup(MarkupSyntax),
+    /// An email message or web archive
+    Email,
+}
+
+impl LocalFormat {
+    /// Chooses the format of a file from its first bytes and its extension.
+    ///
+    /// Magic bytes win over the extension, so a misnamed PDF is still
+    /// converted as one. Notebooks, markup sources, emails, web archives,
+    /// and `.txt` files are recognized by their extension, and HTML by a
+    /// `.html`, `.htm`, or `.xhtml` extension or a leading doctype or
+    /// `<html>` tag.
+    fn detect(path: &str, bytes: &[u8]) -> Self {
+        if bytes.starts_with(PDF_MAGIC) {
+            return LocalFormat::Pdf;
+        }
+        if is_docx(bytes) {
+            return LocalFormat::Docx;
+        }
+
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("ipynb") => return LocalFormat::Notebook,
+            Some("eml" | "mht" | "mhtml") => return LocalFormat::Email,
+            Some("html" | "htm" | "xhtml") => return LocalFormat::Html,
+            Some("txt") => return LocalFormat::PlainText,
+            _ => {}
+        }
+        if let Some(syntax) = MarkupSyntax::from_path(path) {
+            return LocalFormat::Markup(syntax);
+        }
+        let head = String::from_utf8_lossy(&bytes[..bytes.len().min(256)]);
+        let head = head
+            .trim_start_matches('\u{feff}')
+            .trim_start()
+            .to_ascii_lowercase();
+        if head.starts_with("<!doctype html") || head.starts_with("<html") {
+            LocalFormat::Html
+        } else {
+            LocalFormat::Text
+        }
+    }
+}
+
+/// Files converted when no directory pattern is given.
+pub const DEFAULT_DIRECTORY_PATTERN: &str = "*.md";
+
+/// Options for converting the files in a local directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectoryOptions {
+    /// Pattern selecting the files to convert, relative to the directory
+    pub pattern: String,
+    /// Whether files in subdirectories are converted too
+    pub recursive: bool,
+    /// Directory to write the converted files to, mirroring the input tree
+    pub output_dir: Option<PathBuf>,
+}
+
+impl DirectoryOptions {
+    /// Creates options converting every `*.md` file, recursively, without writing output.
+    pub fn new() -> Self {
+        Self {
+            pattern: DEFAULT_DIRECTORY_PATTERN.to_string(),
+            recursive: true,
+            output_dir: None,
+        }
+    }
+
+    /// Sets the pattern selecting the files to convert.
+    ///
+    /// `*` matches within a path segment, `?` matches one character, and `**`
+    /// matches any number of directories. When converting recursively, a
+    /// pattern without a `/`, such as `*.txt`, matches files at any depth.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The pattern, such as `*.md` or `guide/**/*.md`
+    pub fn pattern<T: Into<String>>(mut self, pattern: T) -> Self {
+        self.pattern = pattern.into();
+        self
+    }
+
+    /// Sets whether files in subdirectories are converted.
+    ///
+    /// # Arguments
+    ///
+    /// * `recursive` - Whether to descend into subdirectories
+    pub fn recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Sets a directory to write each converted file to.
+    ///
+    /// Files keep their path relative to the input directory, with a `.md`
+    /// extension, and missing folders are created.
+    ///
+    /// # Arguments
+    ///
+    /// * `output_dir` - The root of the mirrored output tree
+    pub fn output_dir<P: Into<PathBuf>>(mut self, output_dir: P) -> Self {
+        self.output_dir = Some(output_dir.into());
+        self
+    }
+
+    /// The pattern to match relative paths against.
+    fn effective_pattern(&self) -> String {
+        let pattern = self.pattern.trim().trim_start_matches("./");
+        if self.recursive && !pattern.contains('/') {
+            format!("**/{pattern}")
+        } else {
+            pattern.to_string()
+        }
+    }
+}
+
+impl Default for DirectoryOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 /// Converter for reading markdown files from the local filesystem.
 ///
@@ -17,13 +184,70 @@ use tracing::{debug, info, instrument};
 /// - `../parent/relative/path.md`
 /// - `file:///absolute/path/to/file.md`
 /// - `file://./relative/path.md`
+///
+/// HTML, PDF, Word, notebook, AsciiDoc, reStructuredText, email, web
+/// archive, and plain text files are converted rather than read as they are.
 #[derive(Debug, Clone, Default)]
-pub struct LocalFileConverter;
+pub struct LocalFileConverter {
+    html_converter: HtmlConverter,
+    notebook_converter: NotebookConverter,
+    markup_converter: MarkupConverter,
+    email_converter: EmailConverter,
+}
 
 impl LocalFileConverter {
     /// Creates a new LocalFileConverter instance.
     pub fn new() -> Self {
-        LocalFileConverter
+        Self {
+            html_converter: HtmlConverter::new(),
+            notebook_converter: NotebookConverter::new(),
+            markup_converter: MarkupConverter::new(),
+            email_converter: EmailConverter::new(),
+        }
+    }
+
+    /// Creates a local file converter that converts documents with `html_converter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `html_converter` - The configured converter used for HTML, PDF, and Word files
+    pub fn with_html_converter(html_converter: HtmlConverter) -> Self {
+        Self {
+            html_converter,
+            notebook_converter: NotebookConverter::new(),
+            markup_converter: MarkupConverter::new(),
+            email_converter: EmailConverter::new(),
+        }
+    }
+
+    /// Sets the converter used for Jupyter notebooks.
+    ///
+    /// # Arguments
+    ///
+    /// * `notebook_converter` - The configured notebook converter
+    pub fn with_notebook_converter(mut self, notebook_converter: NotebookConverter) -> Self {
+        self.notebook_converter = notebook_converter;
+        self
+    }
+
+    /// Sets the converter used for AsciiDoc and reStructuredText files.
+    ///
+    /// # Arguments
+    ///
+    /// * `markup_converter` - The configured markup converter
+    pub fn with_markup_converter(mut self, markup_converter: MarkupConverter) -> Self {
+        self.markup_converter = markup_converter;
+        self
+    }
+
+    /// Sets the converter used for email messages and web archives.
+    ///
+    /// # Arguments
+    ///
+    /// * `email_converter` - The configured email converter
+    pub fn with_email_converter(mut self, email_converter: EmailConverter) -> Self {
+        self.email_converter = email_converter;
+        self
     }
 
     /// Converts a file path or file:// URL to a standard file path.
@@ -80,33 +304,155 @@ impl LocalFileConverter {
         Ok(())
     }
 
-    /// Reads the file content as a UTF-8 string.
-    async fn read_file_content(&self, path: &str) -> Result<String, MarkdownError> {
-        match fs::read_to_string(path).await {
-            Ok(content) => Ok(content),
-            Err(e) => {
-                let context = ErrorContext::new(path, "File reading", "LocalFileConverter")
-                    .with_info(format!("IO error: {e}"));
-
-                match e.kind() {
-                    std::io::ErrorKind::NotFound => Err(MarkdownError::ContentError {
-                        kind: ContentErrorKind::EmptyContent,
-                        context,
-                    }),
-                    std::io::ErrorKind::PermissionDenied => Err(MarkdownError::ContentError {
-                        kind: ContentErrorKind::ParsingFailed,
-                        context,
-                    }),
-                    _ => Err(MarkdownError::ContentError {
-                        kind: ContentErrorKind::ParsingFailed,
-                        context,
-                    }),
+    /// Reads the file content as bytes.
+    async fn read_file_bytes(&self, path: &str) -> Result<Vec<u8>, MarkdownError> {
+        tokio::fs::read(path)
+            .await
+            .map_err(|e| self.read_error(path, e))
+    }
+
+    /// Decodes file content as a UTF-8 string, decompressing it if needed.
+    async fn read_file_content(&self, path: &str, bytes: &[u8]) -> Result<String, MarkdownError> {
+        crate::compression::read_to_string(bytes)
+            .await
+            .map_err(|e| self.read_error(path, e))
+    }
+
+    /// Maps an IO error reading `path` to a content error.
+    fn read_error(&self, path: &str, e: std::io::Error) -> MarkdownError {
+        let context = ErrorContext::new(path, "File reading", "LocalFileConverter")
+            .with_info(format!("IO error: {e}"));
+
+        match e.kind() {
+            std::io::ErrorKind::NotFound => MarkdownError::ContentError {
+                kind: ContentErrorKind::EmptyContent,
+                context,
+            },
+            std::io::ErrorKind::PermissionDenied => MarkdownError::ContentError {
+                kind: ContentErrorKind::ParsingFailed,
+                context,
+            },
+            _ => MarkdownError::ContentError {
+                kind: ContentErrorKind::ParsingFailed,
+                context,
+            },
+        }
+    }
+
+    /// Converts every file in a directory that matches the options.
+    ///
+    /// Files that cannot be converted, such as empty files, are logged and
+    /// left out of the result. Hidden `.git` folders are skipped and symbolic
+    /// links are not followed.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory path or file:// URL
+    /// * `options` - Which files to convert and where to write them
+    ///
+    /// # Returns
+    ///
+    /// Returns the converted files keyed by their path relative to `dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ContentError` if `dir` is not a directory, or a
+    /// `ConverterError` if the output tree cannot be written.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use markdowndown::converters::local::{DirectoryOptions, LocalFileConverter};
+    ///
+    /// # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
+    /// let converter = LocalFileConverter::new();
+    /// let options = DirectoryOptions::new().pattern("*.txt").output_dir("out");
+    /// let files = converter.convert_directory("./notes", &options).await?;
+    /// for (path, markdown) in &files {
+    ///     println!("{}: {} chars", path.display(), markdown.as_str().len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, options))]
+    pub async fn convert_directory(
+        &self,
+        dir: &str,
+        options: &DirectoryOptions,
+    ) -> Result<BTreeMap<PathBuf, Markdown>, MarkdownError> {
+        let dir_path = self.normalize_path(dir);
+        if !Path::new(&dir_path).is_dir() {
+            let context =
+                ErrorContext::new(&dir_path, "Directory validation", "LocalFileConverter")
+                    .with_info("Path is not a directory");
+            return Err(MarkdownError::ContentError {
+                kind: ContentErrorKind::UnsupportedFormat,
+                context,
+            });
+        }
+
+        let root = Path::new(&dir_path);
+        let pattern = options.effective_pattern();
+        let files = crate::repository::matching_files(root, &pattern);
+        info!(
+            "Converting {} files matching {} in {}",
+            files.len(),
+            pattern,
+            dir_path
+        );
+
+        let mut converted = BTreeMap::new();
+        for file in files {
+            let path = root.join(&file);
+            let markdown = match super::Converter::convert(self, &path.to_string_lossy()).await {
+                Ok(markdown) => markdown,
+                Err(e) => {
+                    warn!("Skipping {}: {}", file, e);
+                    continue;
                 }
+            };
+            let relative = PathBuf::from(file);
+            if let Some(output_dir) = &options.output_dir {
+                write_mirrored(output_dir, &relative, &markdown).await?;
             }
+            converted.insert(relative, markdown);
         }
+        Ok(converted)
     }
 }
 
+/// Writes a converted file below `output_dir` at its relative path, as `.md`.
+async fn write_mirrored(
+    output_dir: &Path,
+    relative: &Path,
+    markdown: &Markdown,
+) -> Result<(), MarkdownError> {
+    let mut target = output_dir.join(relative);
+    if matches!(
+        target.extension().and_then(|ext| ext.to_str()),
+        Some("gz" | "zst")
+    ) {
+        target.set_extension("");
+    }
+    target.set_extension("md");
+
+    let write = async {
+        if let Some(parent) = target.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&target, markdown.as_str()).await
+    };
+    write.await.map_err(|e| MarkdownError::ConverterError {
+        kind: ConverterErrorKind::ProcessingError,
+        context: ErrorContext::new(
+            target.display().to_string(),
+            "Write converted file",
+            "LocalFileConverter",
+        )
+        .with_info(format!("IO error: {e}")),
+    })
+}
+
 #[async_trait]
 impl super::Converter for LocalFileConverter {
     /// Converts a local file path or file:// URL to markdown.
@@ -131,9 +477,61 @@ impl super::Converter for LocalFileConverter {
         debug!("Validating file path");
         self.validate_file_path(&file_path).await?;
 
-        // Read file content
+        // Read file content, handing documents to their converters
         debug!("Reading file content");
-        let content = self.read_file_content(&file_path).await?;
+        let bytes = self.read_file_bytes(&file_path).await?;
+        let format = LocalFormat::detect(&file_path, &bytes);
+        debug!("Detected {:?} file", format);
+        match format {
+            LocalFormat::Pdf => {
+                let content = ResponseContent::Binary {
+                    content_type: "application/pdf".to_string(),
+                    bytes: Bytes::from(bytes),
+                };
+                return self
+                    .html_converter
+                    .convert_content(&file_path, content)
+                    .await;
+            }
+            LocalFormat::Docx => {
+                let content = ResponseContent::Binary {
+                    content_type: DOCX_MEDIA_TYPE.to_string(),
+                    bytes: Bytes::from(bytes),
+                };
+                return self
+                    .html_converter
+                    .convert_content(&file_path, content)
+                    .await;
+            }
+            LocalFormat::Notebook => {
+                return self.notebook_converter.convert_notebook(&file_path, &bytes);
+            }
+            LocalFormat::Email => {
+                return self.email_converter.convert_message(&file_path, &bytes);
+            }
+            LocalFormat::Markup(syntax) => {
+                let text = self.read_file_content(&file_path, &bytes).await?;
+                return self
+                    .markup_converter
+                    .convert_markup(&file_path, syntax, &text);
+            }
+            LocalFormat::Html => {
+                let html = self.read_file_content(&file_path, &bytes).await?;
+                return self
+                    .html_converter
+                    .convert_content(&file_path, ResponseContent::Text(html))
+                    .await;
+            }
+            LocalFormat::PlainText => {
+                let text = self.read_file_content(&file_path, &bytes).await?;
+                return self
+                    .html_converter
+                    .convert_response(&file_path, Some("text/plain"), ResponseContent::Text(text))
+                    .await;
+            }
+            LocalFormat::Text => {}
+        }
+        let content = self.read_file_content(&file_path, &bytes).await?;
 
         // Validate content is not empty
         if content.trim().is_empty() {
@@ -164,6 +562,11 @@ impl super::Converter for LocalFileConverter {
         Ok(markdown)
     }
 
+    /// Local files are read from disk.
+    fn estimated_requests(&self, _url: &str) -> usize {
+        0
+    }
+
     fn name(&self) -> &'static str {
         "Local File Converter"
     }
@@ -242,6 +645,24 @@ mod tests {
         assert!(markdown.as_str().contains("This is a test markdown file."));
     }
 
+    #[tokio::test]
+    async fn test_convert_compressed_file() {
+        let converter = LocalFileConverter::new();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("stored.md.zst");
+        crate::compression::write_file(&file_path, "# Stored Document\n\nArchived output.")
+            .await
+            .unwrap();
+
+        let markdown = converter
+            .convert(file_path.to_str().unwrap())
+            .await
+            .unwrap();
+
+        assert!(markdown.as_str().contains("# Stored Document"));
+        assert!(markdown.as_str().contains("Archived output."));
+    }
+
     #[tokio::test]
     async fn test_convert_file_url() {
         let converter = LocalFileConverter::new();
@@ -320,6 +741,121 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_local_format_detection() {
+        assert_eq!(LocalFormat::detect("a.md", b"# Notes"), LocalFormat::Text);
+        assert_eq!(LocalFormat::detect("a.ipynb", b"{}"), LocalFormat::Notebook);
+        assert_eq!(
+            LocalFormat::detect("docs/index.rst", b"Title\n====="),
+            LocalFormat::Markup(MarkupSyntax::ReStructuredText)
+        );
+        assert_eq!(
+            LocalFormat::detect("saved.MHTML", b"From: <Saved by Blink>"),
+            LocalFormat::Email
+        );
+        assert_eq!(
+            LocalFormat::detect("notes.TXT", b"Notes\n====="),
+            LocalFormat::PlainText
+        );
+        assert_eq!(
+            LocalFormat::detect("a.HTM", b"<p>Hi</p>"),
+            LocalFormat::Html
+        );
+        assert_eq!(
+            LocalFormat::detect("page", b"\n<!DOCTYPE html><html></html>"),
+            LocalFormat::Html
+        );
+        assert_eq!(
+            LocalFormat::detect("a.txt", b"%PDF-1.7\n"),
+            LocalFormat::Pdf
+        );
+        assert_eq!(
+            LocalFormat::detect("a.docx", b"PK\x03\x04"),
+            LocalFormat::Text
+        );
+    }
+
+    #[tokio::test]
+    async fn test_convert_routes_html_and_pdf_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let converter = LocalFileConverter::new();
+
+        let page = dir.path().join("page.html");
+        std::fs::write(
+            &page,
+            "<html><head><title>Saved</title></head><body><h1>Saved Page</h1><p>Kept offline.</p></body></html>",
+        )
+        .unwrap();
+        let markdown = converter.convert(page.to_str().unwrap()).await.unwrap();
+        assert!(markdown.as_str().contains("# Saved Page"));
+        assert!(!markdown.as_str().contains("<h1>"));
+
+        // A truncated PDF reaches the PDF converter instead of being read as text
+        let pdf = dir.path().join("report.pdf");
+        std::fs::write(&pdf, b"%PDF-1.4\n").unwrap();
+        let result = converter.convert(pdf.to_str().unwrap()).await;
+        match result.unwrap_err() {
+            MarkdownError::ContentError { kind, context } => {
+                assert_eq!(kind, ContentErrorKind::ParsingFailed);
+                assert_eq!(context.operation, "PDF conversion");
+            }
+            other => panic!("Expected ContentError, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_convert_directory_tree() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let out = tempfile::TempDir::new().unwrap();
+        for (file, content) in [
+            ("index.md", "# Home\n"),
+            ("guide/install.md", "# Install\n"),
+            ("guide/notes.txt", "Plain notes\n"),
+            ("guide/empty.md", "   \n"),
+        ] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        let converter = LocalFileConverter::new();
+        let root = dir.path().to_str().unwrap();
+
+        let files = converter
+            .convert_directory(root, &DirectoryOptions::new())
+            .await
+            .unwrap();
+        let paths: Vec<&Path> = files.keys().map(PathBuf::as_path).collect();
+        assert_eq!(
+            paths,
+            vec![Path::new("guide/install.md"), Path::new("index.md")]
+        );
+
+        let options = DirectoryOptions::new().recursive(false);
+        let files = converter.convert_directory(root, &options).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[Path::new("index.md")].as_str().contains("# Home"));
+
+        let options = DirectoryOptions::new()
+            .pattern("guide/*.txt")
+            .output_dir(out.path());
+        let files = converter.convert_directory(root, &options).await.unwrap();
+        assert_eq!(files.len(), 1);
+        let written = std::fs::read_to_string(out.path().join("guide/notes.md")).unwrap();
+        assert!(written.contains("Plain notes"));
+
+        let file = dir.path().join("index.md");
+        let result = converter
+            .convert_directory(file.to_str().unwrap(), &DirectoryOptions::new())
+            .await;
+        assert!(matches!(
+            result,
+            Err(MarkdownError::ContentError {
+                kind: ContentErrorKind::UnsupportedFormat,
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn test_converter_name() {
         let converter = LocalFileConverter::new();
@@ -328,7 +864,7 @@ mod tests {
 
     #[test]
     fn test_default_implementation() {
-        let converter = LocalFileConverter;
+        let converter = LocalFileConverter::default();
         assert_eq!(converter.name(), "Local File Converter");
     }
 
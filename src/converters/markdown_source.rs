@@ -0,0 +1,317 @@
+//! Markdown source documents, passed through with normalization.
+//!
+//! URLs that already serve markdown, such as `.md` files and READMEs linked
+//! on GitHub, would be mangled by the HTML pipeline, which escapes their
+//! syntax as page text. The source is kept instead and normalized: its own
+//! frontmatter is merged with the generated fields, relative links and
+//! images are resolved against the URL, and whitespace follows the output
+//! configuration.
+//!
+//! Files linked on GitHub (`github.com/owner/repo/blob/...`) are downloaded
+//! from `raw.githubusercontent.com` rather than as the rendered page.
+
+use crate::client::HttpClient;
+use crate::frontmatter::{
+    frontmatter_field, merge_frontmatter, strip_frontmatter, FrontmatterBuilder,
+};
+use crate::links::resolve_links;
+use crate::types::{ContentErrorKind, ErrorContext, Markdown, MarkdownError};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::path::Path;
+
+use super::converter::Converter;
+use super::notebook::raw_github_url;
+
+/// File extensions of markdown sources.
+const MARKDOWN_EXTENSIONS: [&str; 5] = ["md", "markdown", "mdown", "mkd", "mkdn"];
+
+/// Returns true if a URL or path names a markdown file.
+///
+/// The query string and fragment are ignored, and extensions match in any case.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdowndown::converters::markdown_source::is_markdown_path;
+///
+/// assert!(is_markdown_path("https://example.com/docs/README.md?plain=1"));
+/// assert!(is_markdown_path("/CHANGELOG.markdown"));
+/// assert!(!is_markdown_path("https://example.com/md"));
+/// ```
+pub fn is_markdown_path(path: &str) -> bool {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            MARKDOWN_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+        })
+}
+
+/// Markdown source to markdown converter.
+#[derive(Debug, Clone)]
+pub struct MarkdownSourceConverter {
+    client: HttpClient,
+    output_config: crate::config::OutputConfig,
+}
+
+impl MarkdownSourceConverter {
+    /// Creates a new markdown source converter with default configuration.
+    pub fn new() -> Self {
+        Self {
+            client: HttpClient::new(),
+            output_config: crate::config::OutputConfig::default(),
+        }
+    }
+
+    /// Creates a new markdown source converter with custom configuration and HTTP client.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Configured HTTP client to use for requests
+    /// * `output_config` - Output configuration for frontmatter and whitespace
+    pub fn with_config(client: HttpClient, output_config: crate::config::OutputConfig) -> Self {
+        Self {
+            client,
+            output_config,
+        }
+    }
+
+    /// Normalizes markdown source that has already been fetched.
+    ///
+    /// The source's own frontmatter is merged into the generated frontmatter,
+    /// whose standard fields take precedence; without a `title` field, the
+    /// first top-level heading is used. Relative link and image targets are
+    /// resolved against `url`. When
+    /// [`crate::config::OutputConfig::normalize_whitespace`] is on, trailing
+    /// whitespace is removed, except for two-space line breaks, and runs of
+    /// blank lines outside code blocks are cut to
+    /// [`crate::config::OutputConfig::max_consecutive_blank_lines`].
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL the source was read from
+    /// * `text` - The markdown source
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ContentError` - If the source has no content besides
+    ///   its frontmatter (`EmptyContent`)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::converters::MarkdownSourceConverter;
+    /// use markdowndown::frontmatter::frontmatter_field;
+    ///
+    /// let source = "---\ntags: [guide]\n---\n# Setup\n\nSee [install](install.md).\n\n\n\n";
+    /// let markdown = MarkdownSourceConverter::new()
+    ///     .convert_source("https://example.com/docs/setup.md", source)?;
+    /// assert_eq!(frontmatter_field(markdown.as_str(), "title").as_deref(), Some("Setup"));
+    /// assert!(markdown.as_str().ends_with(
+    ///     "\n# Setup\n\nSee [install](https://example.com/docs/install.md).\n"
+    /// ));
+    /// # Ok::<(), markdowndown::types::MarkdownError>(())
+    /// ```
+    pub fn convert_source(&self, url: &str, text: &str) -> Result<Markdown, MarkdownError> {
+        let source = text.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+        let body = resolve_links(&strip_frontmatter(&source), url);
+        let body = if self.output_config.normalize_whitespace {
+            normalize_whitespace(&body, self.output_config.max_consecutive_blank_lines)
+        } else {
+            body
+        };
+        if body.trim().is_empty() {
+            return Err(MarkdownError::ContentError {
+                kind: ContentErrorKind::EmptyContent,
+                context: ErrorContext::new(url, "Markdown normalization", self.name())
+                    .with_info("Document has no content"),
+            });
+        }
+
+        if !self.output_config.include_frontmatter {
+            return Markdown::new(body);
+        }
+
+        let now = Utc::now();
+        let mut builder = FrontmatterBuilder::new(url.to_string())
+            .exporter(format!(
+                "markdowndown-markdown-{}",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .download_date(now)
+            .additional_field("converted_at".to_string(), now.to_rfc3339())
+            .additional_field("conversion_type".to_string(), "markdown".to_string())
+            .additional_field("url".to_string(), url.to_string());
+        if frontmatter_field(&source, "title").is_none() {
+            if let Some(title) = first_heading(&body) {
+                builder = builder.additional_field("title".to_string(), title);
+            }
+        }
+
+        // Add custom frontmatter fields from configuration
+        for (key, value) in &self.output_config.custom_frontmatter_fields {
+            builder = builder.additional_field(key.clone(), value.clone());
+        }
+
+        let frontmatter = builder.build()?;
+        Markdown::new(merge_frontmatter(
+            &format!("{frontmatter}\n{body}"),
+            &source,
+        ))
+    }
+}
+
+impl Default for MarkdownSourceConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Converter for MarkdownSourceConverter {
+    async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+        let text = self.client.get_text(&raw_github_url(url)).await?;
+        self.convert_source(url, &text)
+    }
+
+    fn name(&self) -> &'static str {
+        "Markdown Source"
+    }
+}
+
+/// Returns whether a line opens or closes a fenced code block.
+fn is_fence(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+/// Returns the text of the first `#` heading outside code blocks.
+fn first_heading(markdown: &str) -> Option<String> {
+    let mut in_code_block = false;
+    for line in markdown.lines() {
+        if is_fence(line) {
+            in_code_block = !in_code_block;
+        } else if !in_code_block {
+            if let Some(title) = line.strip_prefix("# ") {
+                let title = title.trim().trim_end_matches('#').trim();
+                if !title.is_empty() {
+                    return Some(title.to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Removes trailing whitespace and extra blank lines outside code blocks.
+///
+/// Lines ending in two or more spaces keep exactly two, since that is a
+/// markdown line break. The result ends with a single newline.
+fn normalize_whitespace(markdown: &str, max_blank_lines: usize) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut blank_run = 0;
+    let mut in_code_block = false;
+    for line in markdown.lines() {
+        if is_fence(line) {
+            in_code_block = !in_code_block;
+        } else if in_code_block {
+            lines.push(line.to_string());
+            continue;
+        }
+
+        let trimmed = line.trim_end();
+        if trimmed.is_empty() {
+            blank_run += 1;
+            if blank_run <= max_blank_lines {
+                lines.push(String::new());
+            }
+            continue;
+        }
+        blank_run = 0;
+        if line.ends_with("  ") && !is_fence(line) {
+            lines.push(format!("{trimmed}  "));
+        } else {
+            lines.push(trimmed.to_string());
+        }
+    }
+    let text = lines.join("\n");
+    format!("{}\n", text.trim_matches('\n'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontmatter::frontmatter_list;
+
+    #[test]
+    fn test_convert_source_merges_frontmatter() {
+        let source = "\u{feff}---\r\ntitle: Guide\r\ntags:\r\n- docs\r\nurl: ignored\r\n---\r\n\r\n# Heading\r\n\r\n![Diagram](img/flow.png)\r\n";
+        let markdown = MarkdownSourceConverter::new()
+            .convert_source("https://example.com/docs/guide.md", source)
+            .unwrap();
+        let markdown = markdown.as_str();
+
+        assert_eq!(
+            frontmatter_field(markdown, "title").as_deref(),
+            Some("Guide")
+        );
+        assert_eq!(
+            frontmatter_field(markdown, "url").as_deref(),
+            Some("https://example.com/docs/guide.md")
+        );
+        assert_eq!(
+            frontmatter_field(markdown, "conversion_type").as_deref(),
+            Some("markdown")
+        );
+        assert_eq!(
+            frontmatter_list(markdown, "tags"),
+            Some(vec!["docs".to_string()])
+        );
+        assert!(markdown
+            .ends_with("\n# Heading\n\n![Diagram](https://example.com/docs/img/flow.png)\n"));
+
+        let result =
+            MarkdownSourceConverter::new().convert_source("a.md", "---\ntitle: x\n---\n\n");
+        assert!(matches!(
+            result,
+            Err(MarkdownError::ContentError {
+                kind: ContentErrorKind::EmptyContent,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_normalize_whitespace() {
+        let markdown = "\n\nLine one  \nLine two\t\n\n\n\n```\ncode  \n\n\n\nmore\n```\n\n";
+        assert_eq!(
+            normalize_whitespace(markdown, 1),
+            "Line one  \nLine two\n\n```\ncode  \n\n\n\nmore\n```\n"
+        );
+
+        // Escaped markdown syntax is not escaped again
+        let output_config = crate::config::OutputConfig {
+            include_frontmatter: false,
+            normalize_whitespace: false,
+            ..Default::default()
+        };
+        let markdown = MarkdownSourceConverter::with_config(HttpClient::new(), output_config)
+            .convert_source("notes.md", "Use \\*literal\\* stars and `a_b`.\n\n\n")
+            .unwrap();
+        assert_eq!(
+            markdown.as_str(),
+            "Use \\*literal\\* stars and `a_b`.\n\n\n"
+        );
+    }
+
+    #[test]
+    fn test_is_markdown_path() {
+        assert!(is_markdown_path(
+            "https://github.com/o/r/blob/main/docs/INDEX.MD"
+        ));
+        assert!(is_markdown_path("notes.mkd#intro"));
+        assert!(!is_markdown_path("https://example.com/readme.txt"));
+    }
+}
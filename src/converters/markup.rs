@@ -0,0 +1,1332 @@
+//! AsciiDoc and reStructuredText to markdown conversion.
+//!
+//! Much open-source documentation is written in AsciiDoc (`.adoc`) or
+//! reStructuredText (`.rst`) rather than markdown. Both are translated line
+//! by line: headings keep their levels, admonitions such as `NOTE:` and
+//! `.. warning::` become block quotes led by their label, source and literal
+//! blocks become fenced code blocks, and tables become pipe tables. Links,
+//! images, and inline markup are rewritten to their markdown forms.
+//!
+//! The syntax is chosen from the file extension. Files linked on GitHub are
+//! downloaded from `raw.githubusercontent.com` rather than as the rendered
+//! page. Constructs without a markdown equivalent, such as comments,
+//! `include::` directives, and Sphinx `toctree` entries, are left out.
+
+use crate::client::HttpClient;
+use crate::frontmatter::FrontmatterBuilder;
+use crate::types::{ContentErrorKind, ErrorContext, Markdown, MarkdownError};
+use async_trait::async_trait;
+use chrono::Utc;
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use super::converter::Converter;
+use super::notebook::{code_block, raw_github_url};
+
+/// A lightweight markup language translated to markdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkupSyntax {
+    /// AsciiDoc (`.adoc`, `.asciidoc`)
+    AsciiDoc,
+    /// reStructuredText (`.rst`, `.rest`)
+    ReStructuredText,
+}
+
+impl MarkupSyntax {
+    /// Chooses the syntax from the file extension of a path or URL.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - A file path or URL; any query string or fragment is ignored
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::converters::markup::MarkupSyntax;
+    ///
+    /// assert_eq!(
+    ///     MarkupSyntax::from_path("https://example.com/docs/index.rst?plain=1"),
+    ///     Some(MarkupSyntax::ReStructuredText)
+    /// );
+    /// assert_eq!(MarkupSyntax::from_path("guide.ADOC"), Some(MarkupSyntax::AsciiDoc));
+    /// assert_eq!(MarkupSyntax::from_path("notes.md"), None);
+    /// ```
+    pub fn from_path(path: &str) -> Option<Self> {
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let file_name = path.rsplit('/').next().unwrap_or_default();
+        let (_, extension) = file_name.rsplit_once('.')?;
+        match extension.to_ascii_lowercase().as_str() {
+            "adoc" | "asciidoc" => Some(MarkupSyntax::AsciiDoc),
+            "rst" | "rest" => Some(MarkupSyntax::ReStructuredText),
+            _ => None,
+        }
+    }
+
+    /// The name used for the `conversion_type` frontmatter field.
+    pub fn name(&self) -> &'static str {
+        match self {
+            MarkupSyntax::AsciiDoc => "asciidoc",
+            MarkupSyntax::ReStructuredText => "rst",
+        }
+    }
+}
+
+/// AsciiDoc and reStructuredText to markdown converter.
+#[derive(Debug, Clone)]
+pub struct MarkupConverter {
+    client: HttpClient,
+    output_config: crate::config::OutputConfig,
+}
+
+impl MarkupConverter {
+    /// Creates a new markup converter with default configuration.
+    pub fn new() -> Self {
+        Self {
+            client: HttpClient::new(),
+            output_config: crate::config::OutputConfig::default(),
+        }
+    }
+
+    /// Creates a new markup converter with custom configuration and HTTP client.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Configured HTTP client to use for requests
+    /// * `output_config` - Output configuration for frontmatter
+    pub fn with_config(client: HttpClient, output_config: crate::config::OutputConfig) -> Self {
+        Self {
+            client,
+            output_config,
+        }
+    }
+
+    /// Converts AsciiDoc or reStructuredText source that has already been read.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL or path the source was read from
+    /// * `syntax` - The markup language of the source
+    /// * `text` - The source text
+    ///
+    /// # Returns
+    ///
+    /// Returns the converted markdown on success, or a `MarkdownError` on failure.
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ContentError` - If the source has no content (`EmptyContent`)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::converters::markup::{MarkupConverter, MarkupSyntax};
+    ///
+    /// let source = "Install\n=======\n\n.. note:: Requires Python 3.\n\n.. code-block:: bash\n\n   pip install tool\n";
+    /// let markdown = MarkupConverter::new().convert_markup("install.rst", MarkupSyntax::ReStructuredText, source)?;
+    /// assert!(markdown.as_str().contains("# Install"));
+    /// assert!(markdown.as_str().contains("> **Note:** Requires Python 3."));
+    /// assert!(markdown.as_str().contains("```bash\npip install tool\n```"));
+    /// # Ok::<(), markdowndown::types::MarkdownError>(())
+    /// ```
+    pub fn convert_markup(
+        &self,
+        url: &str,
+        syntax: MarkupSyntax,
+        text: &str,
+    ) -> Result<Markdown, MarkdownError> {
+        let lines: Vec<&str> = text.trim_start_matches('\u{feff}').lines().collect();
+        let (title, body) = match syntax {
+            MarkupSyntax::AsciiDoc => {
+                let mut renderer = AsciiDocRenderer::new(&lines);
+                let body = renderer.render(&lines);
+                (renderer.title, body)
+            }
+            MarkupSyntax::ReStructuredText => {
+                let mut renderer = RstRenderer::default();
+                let body = renderer.render(&lines);
+                (renderer.title, body)
+            }
+        };
+        if body.trim().is_empty() {
+            return Err(MarkdownError::ContentError {
+                kind: ContentErrorKind::EmptyContent,
+                context: ErrorContext::new(url, "Markup conversion", self.name())
+                    .with_info("Document has no content"),
+            });
+        }
+        let body = format!("{body}\n");
+
+        if !self.output_config.include_frontmatter {
+            return Markdown::new(body);
+        }
+
+        let now = Utc::now();
+        let mut builder = FrontmatterBuilder::new(url.to_string())
+            .exporter(format!("markdowndown-markup-{}", env!("CARGO_PKG_VERSION")))
+            .download_date(now)
+            .additional_field("converted_at".to_string(), now.to_rfc3339())
+            .additional_field("conversion_type".to_string(), syntax.name().to_string())
+            .additional_field("url".to_string(), url.to_string());
+        if let Some(title) = title {
+            builder = builder.additional_field("title".to_string(), title);
+        }
+
+        // Add custom frontmatter fields from configuration
+        for (key, value) in &self.output_config.custom_frontmatter_fields {
+            builder = builder.additional_field(key.clone(), value.clone());
+        }
+
+        let frontmatter = builder.build()?;
+        Markdown::new(format!("{frontmatter}\n{body}"))
+    }
+}
+
+impl Default for MarkupConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Converter for MarkupConverter {
+    async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+        let syntax = MarkupSyntax::from_path(url).ok_or_else(|| MarkdownError::ContentError {
+            kind: ContentErrorKind::UnsupportedFormat,
+            context: ErrorContext::new(url, "Markup conversion", self.name())
+                .with_info("Expected an .adoc, .asciidoc, .rst, or .rest file"),
+        })?;
+        let text = self.client.get_text(&raw_github_url(url)).await?;
+        self.convert_markup(url, syntax, &text)
+    }
+
+    fn name(&self) -> &'static str {
+        "Markup Document"
+    }
+}
+
+/// Compiles a pattern on first use.
+fn regex(cell: &'static OnceLock<Regex>, pattern: &str) -> &'static Regex {
+    cell.get_or_init(|| Regex::new(pattern).expect("valid markup pattern"))
+}
+
+/// The label of an admonition, from its AsciiDoc or reStructuredText name.
+fn admonition_label(name: &str) -> Option<&'static str> {
+    let label = match name.to_ascii_lowercase().as_str() {
+        "note" => "Note",
+        "tip" => "Tip",
+        "hint" => "Hint",
+        "important" => "Important",
+        "warning" => "Warning",
+        "caution" => "Caution",
+        "danger" => "Danger",
+        "attention" => "Attention",
+        "error" => "Error",
+        "seealso" => "See also",
+        _ => return None,
+    };
+    Some(label)
+}
+
+/// Renders an admonition as a block quote led by its bold label.
+fn admonition(label: &str, body: &str) -> String {
+    let body = body.trim();
+    let starts_with_text = body
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphanumeric() || c == '*' && !body.starts_with("* "));
+    let text = if body.is_empty() {
+        format!("**{label}:**")
+    } else if starts_with_text {
+        format!("**{label}:** {body}")
+    } else {
+        format!("**{label}:**\n\n{body}")
+    };
+    quote(&text)
+}
+
+/// Prefixes every line with a block quote marker.
+fn quote(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            if line.is_empty() {
+                ">".to_string()
+            } else {
+                format!("> {line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders rows as a pipe table, using the first row as the header.
+fn pipe_table(rows: &[Vec<String>]) -> Option<String> {
+    let columns = rows.iter().map(Vec::len).max().filter(|&n| n > 0)?;
+    let row = |cells: &Vec<String>| {
+        let cells: Vec<String> = (0..columns)
+            .map(|i| {
+                cells
+                    .get(i)
+                    .map_or(String::new(), |c| c.replace('|', "\\|"))
+            })
+            .collect();
+        format!("| {} |", cells.join(" | "))
+    };
+    let mut lines = vec![
+        row(&rows[0]),
+        format!("|{}|", vec![" --- "; columns].join("|")),
+    ];
+    lines.extend(rows[1..].iter().map(row));
+    Some(lines.join("\n"))
+}
+
+/// The number of leading whitespace characters on a line.
+fn indentation(line: &str) -> usize {
+    line.chars().take_while(|c| c.is_whitespace()).count()
+}
+
+/// Removes the indentation shared by all non-blank lines.
+fn dedent(lines: &[&str]) -> String {
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| indentation(line))
+        .min()
+        .unwrap_or_default();
+    lines
+        .iter()
+        .map(|line| line.chars().skip(indent).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_matches('\n')
+        .to_string()
+}
+
+/// Appends a blank line unless the output is empty or already ends with one.
+fn push_blank(out: &mut Vec<String>) {
+    if out.last().is_some_and(|line| !line.is_empty()) {
+        out.push(String::new());
+    }
+}
+
+/// Appends a block separated from its neighbours by blank lines.
+fn push_block(out: &mut Vec<String>, block: String) {
+    push_blank(out);
+    out.push(block);
+    out.push(String::new());
+}
+
+/// Joins output lines into the markdown body.
+fn finish(out: &[String]) -> String {
+    out.join("\n").trim_matches('\n').to_string()
+}
+
+/// Whether markup spanning `start..end` sits between non-word characters.
+fn at_word_boundaries(text: &str, start: usize, end: usize) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    !text[..start].chars().next_back().is_some_and(is_word)
+        && !text[end..].chars().next().is_some_and(is_word)
+}
+
+/// Whether a line opens or closes an AsciiDoc delimited block.
+fn is_asciidoc_delimiter(line: &str) -> bool {
+    if line == "--" || line.starts_with("```") {
+        return true;
+    }
+    if let Some(rest) = line.strip_prefix("|==") {
+        return rest.chars().all(|c| c == '=');
+    }
+    let Some(first) = line.chars().next() else {
+        return false;
+    };
+    matches!(first, '-' | '.' | '=' | '_' | '*' | '/' | '+')
+        && line.len() >= 4
+        && line.chars().all(|c| c == first)
+}
+
+/// The column count given by an AsciiDoc table's `cols` attribute.
+fn column_count(attributes: &str) -> Option<usize> {
+    let value = attributes.split_once("cols=")?.1;
+    let value = match value.strip_prefix('"') {
+        Some(quoted) => quoted.split('"').next().unwrap_or_default(),
+        None => value.split(',').next().unwrap_or_default(),
+    };
+    if let Some((count, _)) = value.split_once('*') {
+        return count.trim().parse().ok();
+    }
+    Some(value.split([',', ';']).count())
+}
+
+/// Renders an AsciiDoc link target and its text.
+fn asciidoc_link(target: &str, text: &str) -> String {
+    let text = text
+        .split(",window=")
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches('^')
+        .trim_matches('"')
+        .trim();
+    if text.is_empty() {
+        format!("[{target}]({target})")
+    } else {
+        format!("[{text}]({target})")
+    }
+}
+
+/// Renders an image from its target and attribute list, whose first entry is the alt text.
+fn image(target: &str, attributes: &str) -> String {
+    let alt = attributes.split(',').next().unwrap_or_default().trim();
+    format!("![{}]({target})", alt.trim_matches('"'))
+}
+
+/// Translates AsciiDoc to markdown.
+struct AsciiDocRenderer {
+    /// Document attributes, substituted for `{name}` references
+    attributes: HashMap<String, String>,
+    /// The document title, from the level 0 heading
+    title: Option<String>,
+}
+
+impl AsciiDocRenderer {
+    /// Creates a renderer with the attributes defined anywhere in the document.
+    fn new(lines: &[&str]) -> Self {
+        static ENTRY: OnceLock<Regex> = OnceLock::new();
+        let entry = regex(&ENTRY, r"^:([\w][\w-]*):(?:\s+(.*))?$");
+        let attributes = lines
+            .iter()
+            .filter_map(|line| {
+                let caps = entry.captures(line.trim_end())?;
+                let value = caps.get(2).map_or("", |m| m.as_str()).trim();
+                Some((caps[1].to_string(), value.to_string()))
+            })
+            .collect();
+        Self {
+            attributes,
+            title: None,
+        }
+    }
+
+    fn render(&mut self, lines: &[&str]) -> String {
+        static ATTRIBUTE_ENTRY: OnceLock<Regex> = OnceLock::new();
+        static HEADING: OnceLock<Regex> = OnceLock::new();
+        static BLOCK_MACRO: OnceLock<Regex> = OnceLock::new();
+        static ADMONITION: OnceLock<Regex> = OnceLock::new();
+        static LIST_ITEM: OnceLock<Regex> = OnceLock::new();
+        static DESCRIPTION: OnceLock<Regex> = OnceLock::new();
+        let attribute_entry = regex(&ATTRIBUTE_ENTRY, r"^:!?[\w][\w-]*!?:(\s|$)");
+        let heading = regex(&HEADING, r"^(={1,6})\s+(\S.*?)(?:\s+=+)?$");
+        let block_macro = regex(&BLOCK_MACRO, r"^([a-z][\w-]*)::(\S*?)\[(.*)\]$");
+        let admonition_paragraph = regex(
+            &ADMONITION,
+            r"^(NOTE|TIP|IMPORTANT|WARNING|CAUTION):\s+(.*)$",
+        );
+        let list_item = regex(&LIST_ITEM, r"^(\*{1,5}|-|\.{1,5})\s+(.*)$");
+        let description = regex(&DESCRIPTION, r"^(\S.*?)(?:::|;;)(?:\s+(.*))?$");
+
+        let mut out = Vec::new();
+        let mut block_attributes: Option<String> = None;
+        let mut i = 0;
+        while i < lines.len() {
+            let line = lines[i].trim_end();
+            let after_blank = i == 0 || lines[i - 1].trim().is_empty();
+            i += 1;
+            if line.trim().is_empty() {
+                push_blank(&mut out);
+                continue;
+            }
+
+            if is_asciidoc_delimiter(line) {
+                let closing = if line.starts_with("```") { "```" } else { line };
+                let end = lines[i..]
+                    .iter()
+                    .position(|l| l.trim_end() == closing)
+                    .map_or(lines.len(), |p| i + p);
+                let attributes = block_attributes.take().unwrap_or_default();
+                if let Some(block) = self.delimited_block(line, &attributes, &lines[i..end]) {
+                    push_block(&mut out, block);
+                }
+                i = (end + 1).min(lines.len());
+                continue;
+            }
+            if line.starts_with("//") || attribute_entry.is_match(line) {
+                continue;
+            }
+            if line.starts_with("[[") && line.ends_with("]]") {
+                continue;
+            }
+            if line.starts_with('[') && line.ends_with(']') {
+                block_attributes = Some(line[1..line.len() - 1].to_string());
+                continue;
+            }
+            let attributes = block_attributes.take();
+
+            if let Some(caps) = heading.captures(line) {
+                let level = caps[1].len();
+                let text = self.inline(caps[2].trim());
+                if level == 1 && self.title.is_none() {
+                    self.title = Some(text.clone());
+                    // The author and revision lines of the document header
+                    if out.is_empty() {
+                        while i < lines.len() && !lines[i].trim().is_empty() {
+                            i += 1;
+                        }
+                    }
+                }
+                push_block(&mut out, format!("{} {text}", "#".repeat(level)));
+                continue;
+            }
+            if line.starts_with("'''") {
+                push_block(&mut out, "---".to_string());
+                continue;
+            }
+            if line.starts_with("<<<") || line == "+" {
+                continue;
+            }
+            if line.starts_with('.')
+                && line[1..].starts_with(|c: char| c != '.' && !c.is_whitespace())
+            {
+                push_block(&mut out, format!("**{}**", self.inline(&line[1..])));
+                continue;
+            }
+            if let Some(caps) = block_macro.captures(line) {
+                if &caps[1] == "image" {
+                    push_block(&mut out, image(&caps[2], &caps[3]));
+                }
+                continue;
+            }
+
+            // Admonition paragraphs, written `NOTE: text` or after a `[NOTE]` line
+            let style = attributes
+                .as_deref()
+                .and_then(|a| a.split(',').next())
+                .and_then(|style| admonition_label(style.trim()));
+            let admonition_start = match admonition_paragraph.captures(line) {
+                Some(caps) => admonition_label(&caps[1]).map(|label| (label, caps[2].to_string())),
+                None => style.map(|label| (label, line.to_string())),
+            };
+            if let Some((label, first)) = admonition_start {
+                let mut paragraph = vec![self.inline(&first)];
+                while i < lines.len()
+                    && !lines[i].trim().is_empty()
+                    && !is_asciidoc_delimiter(lines[i].trim_end())
+                {
+                    paragraph.push(self.inline(lines[i].trim()));
+                    i += 1;
+                }
+                push_block(&mut out, admonition(label, &paragraph.join("\n")));
+                continue;
+            }
+
+            if let Some(caps) = list_item.captures(line) {
+                let marker = &caps[1];
+                let text = self.inline(&caps[2]);
+                let item = if marker.starts_with('.') {
+                    format!("{}1. {text}", "   ".repeat(marker.len() - 1))
+                } else {
+                    let level = if marker == "-" { 1 } else { marker.len() };
+                    format!("{}- {text}", "  ".repeat(level - 1))
+                };
+                out.push(item);
+                continue;
+            }
+            if let Some(caps) = description.captures(line) {
+                let term = self.inline(&caps[1]);
+                match caps.get(2) {
+                    Some(definition) => out.push(format!(
+                        "- **{term}**: {}",
+                        self.inline(definition.as_str())
+                    )),
+                    None => out.push(format!("- **{term}**:")),
+                }
+                continue;
+            }
+
+            // Indented paragraphs are literal text
+            if after_blank && line.starts_with(char::is_whitespace) {
+                let mut end = i;
+                while end < lines.len() && !lines[end].trim().is_empty() {
+                    end += 1;
+                }
+                push_block(&mut out, code_block("", &dedent(&lines[i - 1..end])));
+                i = end;
+                continue;
+            }
+
+            let text = self.inline(line.trim_start());
+            match text.strip_suffix(" +") {
+                Some(text) => out.push(format!("{text}\\")),
+                None => out.push(text),
+            }
+        }
+        finish(&out)
+    }
+
+    /// Renders a delimited block given its opening delimiter and attribute list.
+    fn delimited_block(
+        &mut self,
+        delimiter: &str,
+        attributes: &str,
+        inner: &[&str],
+    ) -> Option<String> {
+        let mut attribute_list = attributes.split(',').map(str::trim);
+        let style = attribute_list
+            .next()
+            .unwrap_or_default()
+            .split('%')
+            .next()
+            .unwrap_or_default();
+        let source = inner.join("\n");
+
+        if let Some(language) = delimiter.strip_prefix("```") {
+            return Some(code_block(language.trim(), &source));
+        }
+        if delimiter.starts_with("|=") {
+            return self.table(attributes, inner);
+        }
+        match delimiter.chars().next() {
+            Some('-') if delimiter != "--" => {
+                let language = match style {
+                    "source" | "" => attribute_list.next().unwrap_or_default(),
+                    _ => "",
+                };
+                Some(code_block(language, &source))
+            }
+            Some('.') => Some(code_block("", &source)),
+            Some('+') => Some(source.trim_matches('\n').to_string()),
+            Some('/') => None,
+            Some('_') => {
+                let mut quoted = quote(&self.render(inner));
+                if style == "quote" {
+                    if let Some(author) = attribute_list.next().filter(|a| !a.is_empty()) {
+                        quoted.push_str(&format!("\n>\n> — {author}"));
+                    }
+                }
+                Some(quoted)
+            }
+            _ => {
+                let body = self.render(inner);
+                match admonition_label(style) {
+                    Some(label) => Some(admonition(label, &body)),
+                    None => Some(body).filter(|body| !body.is_empty()),
+                }
+            }
+        }
+    }
+
+    /// Renders a `|===` table, taking the column count from `cols` or the first row.
+    fn table(&self, attributes: &str, inner: &[&str]) -> Option<String> {
+        static CELL_SPEC: OnceLock<Regex> = OnceLock::new();
+        let cell_spec = regex(
+            &CELL_SPEC,
+            r"^\d*(?:\.\d+)?[+*]?[<^>]?(?:\.[<^>])?[adehlmsv]?$",
+        );
+
+        let mut columns = column_count(attributes);
+        let mut cells: Vec<String> = Vec::new();
+        for line in inner.iter().map(|line| line.trim()) {
+            if line.is_empty() {
+                continue;
+            }
+            let line = line.replace("\\|", "\u{0}");
+            let mut parts = line.split('|').map(|part| part.replace('\u{0}', "|"));
+            let lead = parts.next().unwrap_or_default();
+            // Text before the first separator is a cell specifier or a continued cell
+            if !cell_spec.is_match(lead.trim()) {
+                if let Some(last) = cells.last_mut() {
+                    last.push(' ');
+                    last.push_str(lead.trim());
+                }
+            }
+            let parts: Vec<String> = parts.map(|part| part.trim().to_string()).collect();
+            if columns.is_none() && !parts.is_empty() {
+                columns = Some(parts.len());
+            }
+            cells.extend(parts);
+        }
+        let rows: Vec<Vec<String>> = cells
+            .chunks(columns.unwrap_or(1).max(1))
+            .map(|row| row.iter().map(|cell| self.inline(cell)).collect())
+            .collect();
+        pipe_table(&rows)
+    }
+
+    /// Rewrites inline markup, links, and attribute references.
+    fn inline(&self, text: &str) -> String {
+        static REFERENCE: OnceLock<Regex> = OnceLock::new();
+        static INLINE: OnceLock<Regex> = OnceLock::new();
+        let reference = regex(&REFERENCE, r"\{([\w][\w-]*)\}");
+        let inline = regex(
+            &INLINE,
+            concat!(
+                r"(?P<code>`[^`]+`)",
+                r"|link:(?P<link>[^\[\s]+)\[(?P<link_text>[^\]]*)\]",
+                r"|(?P<url>https?://[^\s\[\]]+)\[(?P<url_text>[^\]]*)\]",
+                r"|image:(?P<image>[^\[\s:][^\[\s]*)\[(?P<alt>[^\]]*)\]",
+                r"|xref:(?P<xref>[^\[\s]+)\[(?P<xref_text>[^\]]*)\]",
+                r"|<<(?P<anchor>[^,>]+)(?:,\s*(?P<anchor_text>[^>]+))?>>",
+                r"|footnote:\[(?P<footnote>[^\]]*)\]",
+                r"|(?P<strong>\*\*.+?\*\*)",
+                r"|\*(?P<bold>[^*\s](?:[^*\n]*?[^*\s])?)\*",
+                r"|__(?P<em>.+?)__",
+                r"|_(?P<italic>[^_\s](?:[^_\n]*?[^_\s])?)_",
+            ),
+        );
+
+        let text = reference.replace_all(text, |caps: &Captures| match &caps[1] {
+            "nbsp" | "sp" => " ".to_string(),
+            "empty" => String::new(),
+            name => self
+                .attributes
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| caps[0].to_string()),
+        });
+        inline
+            .replace_all(&text, |caps: &Captures| {
+                let whole = caps.get(0).expect("match");
+                let group = |name: &str| caps.name(name).map(|m| m.as_str());
+                if let Some(target) = group("link") {
+                    asciidoc_link(target, &caps["link_text"])
+                } else if let Some(target) = group("url") {
+                    asciidoc_link(target, &caps["url_text"])
+                } else if let Some(target) = group("image") {
+                    image(target, &caps["alt"])
+                } else if let Some(target) = group("xref") {
+                    asciidoc_link(target, &caps["xref_text"])
+                } else if let Some(anchor) = group("anchor") {
+                    group("anchor_text").unwrap_or(anchor).trim().to_string()
+                } else if let Some(note) = group("footnote") {
+                    format!(" ({})", note.trim())
+                } else if let Some(bold) =
+                    group("bold").filter(|_| at_word_boundaries(&text, whole.start(), whole.end()))
+                {
+                    format!("**{bold}**")
+                } else if let Some(italic) = group("em").or_else(|| {
+                    group("italic")
+                        .filter(|_| at_word_boundaries(&text, whole.start(), whole.end()))
+                }) {
+                    format!("*{italic}*")
+                } else {
+                    whole.as_str().to_string()
+                }
+            })
+            .into_owned()
+    }
+}
+
+/// Translates reStructuredText to markdown.
+#[derive(Default)]
+struct RstRenderer {
+    /// Heading adornments in order of first use; the position is the heading level
+    styles: Vec<(char, bool)>,
+    /// The document title, from the first heading
+    title: Option<String>,
+}
+
+/// Whether a line is a section adornment: one punctuation character repeated.
+fn is_adornment(line: &str) -> bool {
+    let Some(first) = line.chars().next() else {
+        return false;
+    };
+    first.is_ascii_punctuation() && line.len() >= 2 && line.chars().all(|c| c == first)
+}
+
+/// Whether a line is the `=====  =====` border of a simple table.
+fn is_simple_table_border(line: &str) -> bool {
+    line.starts_with('=') && line.contains(' ') && line.chars().all(|c| c == '=' || c == ' ')
+}
+
+/// The end of the block indented deeper than `indent` that starts at `start`.
+///
+/// Trailing blank lines are not part of the block.
+fn indented_block_end(lines: &[&str], start: usize, indent: usize) -> usize {
+    let mut end = start;
+    for (j, line) in lines.iter().enumerate().skip(start) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if indentation(line) <= indent {
+            break;
+        }
+        end = j + 1;
+    }
+    end
+}
+
+impl RstRenderer {
+    fn render(&mut self, lines: &[&str]) -> String {
+        static DIRECTIVE: OnceLock<Regex> = OnceLock::new();
+        static GRID_BORDER: OnceLock<Regex> = OnceLock::new();
+        static LIST_ITEM: OnceLock<Regex> = OnceLock::new();
+        static FIELD: OnceLock<Regex> = OnceLock::new();
+        let directive = regex(&DIRECTIVE, r"^\.\.\s+([\w-]+(?::[\w-]+)*)::(?:\s+(.*))?$");
+        let grid_border = regex(&GRID_BORDER, r"^\+([-=]+\+)+$");
+        let list_item = regex(&LIST_ITEM, r"^(\s*)(?:([-*+])|(#|\d+)\.)\s+(.*)$");
+        let field = regex(&FIELD, r"^:([^:`]+):(?:\s+(.*))?$");
+
+        let mut out = Vec::new();
+        let mut in_list = false;
+        let mut i = 0;
+        while i < lines.len() {
+            let line = lines[i].trim_end();
+            if line.trim().is_empty() {
+                push_blank(&mut out);
+                i += 1;
+                continue;
+            }
+            let indent = indentation(line);
+            let next = lines.get(i + 1).map_or("", |l| l.trim_end());
+
+            // Section headings, underlined or with a matching overline
+            if indent == 0
+                && is_adornment(line)
+                && !next.trim().is_empty()
+                && lines.get(i + 2).map(|l| l.trim_end()) == Some(line)
+            {
+                let heading = self.heading(next.trim(), line, true);
+                push_block(&mut out, heading);
+                i += 3;
+                continue;
+            }
+            if indent == 0
+                && !is_adornment(line)
+                && is_adornment(next)
+                && (next.len() >= 4 || next.chars().count() >= line.chars().count())
+            {
+                let heading = self.heading(line, next, false);
+                push_block(&mut out, heading);
+                i += 2;
+                continue;
+            }
+            if indent == 0 && is_adornment(line) && line.len() >= 4 {
+                push_block(&mut out, "---".to_string());
+                i += 1;
+                continue;
+            }
+
+            // Directives, comments, and link targets, each with an indented body
+            if let Some(rest) = line.trim_start().strip_prefix("..") {
+                let end = indented_block_end(lines, i + 1, indent);
+                if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+                    if let Some(caps) = directive.captures(line.trim_start()) {
+                        let argument = caps.get(2).map_or("", |m| m.as_str()).trim();
+                        if let Some(block) = self.directive(&caps[1], argument, &lines[i + 1..end])
+                        {
+                            push_block(&mut out, block);
+                        }
+                    }
+                    i = end;
+                    continue;
+                }
+            }
+
+            // Grid and simple tables
+            if grid_border.is_match(line.trim()) {
+                let end = lines[i..]
+                    .iter()
+                    .position(|l| !l.trim_start().starts_with(['+', '|']))
+                    .map_or(lines.len(), |p| i + p);
+                if let Some(table) = self.grid_table(&lines[i..end]) {
+                    push_block(&mut out, table);
+                }
+                i = end;
+                continue;
+            }
+            if indent == 0 && is_simple_table_border(line) {
+                let (table, end) = self.simple_table(lines, i);
+                if let Some(table) = table {
+                    push_block(&mut out, table);
+                }
+                i = end;
+                continue;
+            }
+
+            if let Some(caps) = list_item.captures(line) {
+                let marker = match caps.get(3).map(|m| m.as_str()) {
+                    None => "-".to_string(),
+                    Some("#") => "1.".to_string(),
+                    Some(number) => format!("{number}."),
+                };
+                out.push(format!("{}{marker} {}", &caps[1], rst_inline(&caps[4])));
+                in_list = true;
+                i += 1;
+                continue;
+            }
+
+            // Indented text continues a list item, or else is a block quote
+            if indent > 0 && !in_list {
+                let end = indented_block_end(lines, i, 0);
+                let body = self.render_text(&dedent(&lines[i..end]));
+                push_block(&mut out, quote(&body));
+                i = end;
+                continue;
+            }
+            if indent == 0 {
+                in_list = false;
+            }
+
+            // A paragraph ending in `::` introduces an indented literal block
+            if let Some(text) = line.strip_suffix("::") {
+                let text = if text.trim().is_empty() {
+                    String::new()
+                } else if text.ends_with(char::is_whitespace) {
+                    rst_inline(text.trim_end())
+                } else {
+                    format!("{}:", rst_inline(text))
+                };
+                if !text.is_empty() {
+                    out.push(text);
+                }
+                let start = lines[i + 1..]
+                    .iter()
+                    .position(|l| !l.trim().is_empty())
+                    .map_or(lines.len(), |p| i + 1 + p);
+                if start < lines.len() && indentation(lines[start]) > indent {
+                    let end = indented_block_end(lines, start, indent);
+                    push_block(&mut out, code_block("", &dedent(&lines[start..end])));
+                    i = end;
+                } else {
+                    i += 1;
+                }
+                continue;
+            }
+
+            if let Some(caps) = field.captures(line) {
+                if let Some(value) = caps.get(2) {
+                    out.push(format!("**{}:** {}", &caps[1], rst_inline(value.as_str())));
+                }
+                i += 1;
+                continue;
+            }
+
+            out.push(rst_inline(line));
+            i += 1;
+        }
+        finish(&out)
+    }
+
+    fn render_text(&mut self, text: &str) -> String {
+        let lines: Vec<&str> = text.lines().collect();
+        self.render(&lines)
+    }
+
+    /// Renders a section heading, assigning levels to adornment styles in order of use.
+    fn heading(&mut self, text: &str, adornment: &str, overline: bool) -> String {
+        let style = (adornment.chars().next().unwrap_or('='), overline);
+        let level = match self.styles.iter().position(|s| *s == style) {
+            Some(position) => position + 1,
+            None => {
+                self.styles.push(style);
+                self.styles.len()
+            }
+        };
+        let text = rst_inline(text.trim());
+        if self.title.is_none() {
+            self.title = Some(text.clone());
+        }
+        format!("{} {text}", "#".repeat(level.min(6)))
+    }
+
+    /// Renders a directive from its name, argument, and indented body.
+    fn directive(&mut self, name: &str, argument: &str, body: &[&str]) -> Option<String> {
+        static OPTION: OnceLock<Regex> = OnceLock::new();
+        let option = regex(&OPTION, r"^:([\w-]+):(?:\s+(.*))?$");
+
+        // Options are the field list at the top of the body
+        let mut options = HashMap::new();
+        let mut rest = body;
+        while let Some((line, tail)) = rest.split_first() {
+            let Some(caps) = option.captures(line.trim()) else {
+                break;
+            };
+            let value = caps.get(2).map_or("", |m| m.as_str()).trim();
+            options.insert(caps[1].to_string(), value.to_string());
+            rest = tail;
+        }
+        let content = dedent(rest);
+
+        if let Some(label) = admonition_label(name) {
+            let body = self.render_text(&format!("{argument}\n{content}"));
+            return Some(admonition(label, &body));
+        }
+        match name {
+            "code-block" | "code" | "sourcecode" | "prompt" | "doctest" | "testcode"
+            | "parsed-literal" | "testoutput" => {
+                let language = match name {
+                    "doctest" | "testcode" => "python",
+                    "parsed-literal" | "testoutput" => "",
+                    _ => argument,
+                };
+                Some(code_block(language, &content)).filter(|_| !content.is_empty())
+            }
+            "image" | "figure" => {
+                let alt = options.get("alt").map_or("", String::as_str);
+                let mut markdown = format!("![{alt}]({argument})");
+                if !content.is_empty() {
+                    markdown.push_str("\n\n");
+                    markdown.push_str(&self.render_text(&content));
+                }
+                Some(markdown)
+            }
+            "list-table" => {
+                let table = pipe_table(&rst_list_table(&content))?;
+                match argument {
+                    "" => Some(table),
+                    title => Some(format!("**{}**\n\n{table}", rst_inline(title))),
+                }
+            }
+            "admonition" => Some(admonition(argument, &self.render_text(&content))),
+            "versionadded" | "versionchanged" | "deprecated" => {
+                let (version, text) = argument.split_once(' ').unwrap_or((argument, ""));
+                let label = match name {
+                    "versionadded" => format!("Added in version {version}"),
+                    "versionchanged" => format!("Changed in version {version}"),
+                    _ => format!("Deprecated since version {version}"),
+                };
+                Some(admonition(
+                    &label,
+                    &self.render_text(&format!("{text}\n{content}")),
+                ))
+            }
+            "rubric" => Some(format!("**{}**", rst_inline(argument))),
+            "toctree" | "contents" | "include" | "literalinclude" | "highlight" | "index"
+            | "raw" | "meta" | "sectnum" | "currentmodule" | "module" => None,
+            name if name.starts_with("auto") => None,
+            // Domain directives, such as `py:function`, document a signature
+            name if name.contains(':') => {
+                let signature = format!("`{argument}`");
+                match self.render_text(&content) {
+                    body if body.is_empty() => Some(signature),
+                    body => Some(format!("{signature}\n\n{body}")),
+                }
+            }
+            _ => {
+                let body = self.render_text(&content);
+                let heading =
+                    (!argument.is_empty()).then(|| format!("**{}**", rst_inline(argument)));
+                let block = [heading, Some(body).filter(|b| !b.is_empty())]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+                Some(block).filter(|b| !b.is_empty())
+            }
+        }
+    }
+
+    /// Renders a grid table, joining the lines of multi-line cells.
+    fn grid_table(&self, lines: &[&str]) -> Option<String> {
+        let border: Vec<char> = lines.first()?.trim().chars().collect();
+        let boundaries: Vec<usize> = border
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| **c == '+')
+            .map(|(i, _)| i)
+            .collect();
+        let empty_row = vec![String::new(); boundaries.len().saturating_sub(1)];
+
+        let mut rows = Vec::new();
+        let mut row = empty_row.clone();
+        for line in &lines[1..] {
+            let line: Vec<char> = line.trim().chars().collect();
+            if line.first() == Some(&'+') {
+                if row.iter().any(|cell| !cell.is_empty()) {
+                    rows.push(std::mem::replace(&mut row, empty_row.clone()));
+                }
+                continue;
+            }
+            for (cell, bounds) in row.iter_mut().zip(boundaries.windows(2)) {
+                let end = bounds[1].min(line.len());
+                let start = (bounds[0] + 1).min(end);
+                let text: String = line[start..end].iter().collect();
+                let text = text.trim();
+                if !text.is_empty() {
+                    if !cell.is_empty() {
+                        cell.push(' ');
+                    }
+                    cell.push_str(text);
+                }
+            }
+        }
+        let rows: Vec<Vec<String>> = rows
+            .into_iter()
+            .map(|row| row.iter().map(|cell| rst_inline(cell)).collect())
+            .collect();
+        pipe_table(&rows)
+    }
+
+    /// Renders the simple table starting at `start`, returning it and the line after it.
+    fn simple_table(&self, lines: &[&str], start: usize) -> (Option<String>, usize) {
+        let border: Vec<char> = lines[start].trim_end().chars().collect();
+        let column_starts: Vec<usize> = (0..border.len())
+            .filter(|&i| border[i] == '=' && (i == 0 || border[i - 1] == ' '))
+            .collect();
+
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        let mut end = lines.len();
+        let mut j = start + 1;
+        while j < lines.len() {
+            let line = lines[j].trim_end();
+            j += 1;
+            if is_simple_table_border(line) {
+                if lines.get(j).is_none_or(|l| l.trim().is_empty()) {
+                    end = j;
+                    break;
+                }
+                continue;
+            }
+            if line.trim().is_empty() || line.chars().all(|c| c == '-' || c == ' ') {
+                continue;
+            }
+            let chars: Vec<char> = line.chars().collect();
+            let cells: Vec<String> = column_starts
+                .iter()
+                .enumerate()
+                .map(|(k, &from)| {
+                    // The last column runs to the end of the line
+                    let to = match column_starts.get(k + 1) {
+                        Some(&next) => next.min(chars.len()),
+                        None => chars.len(),
+                    };
+                    let from = from.min(to);
+                    chars[from..to]
+                        .iter()
+                        .collect::<String>()
+                        .trim()
+                        .to_string()
+                })
+                .collect();
+            // Rows whose first cell is blank continue the row above
+            match rows.last_mut() {
+                Some(previous) if cells[0].is_empty() => {
+                    for (cell, text) in previous.iter_mut().zip(cells) {
+                        if !text.is_empty() {
+                            cell.push(' ');
+                            cell.push_str(&text);
+                        }
+                    }
+                }
+                _ => rows.push(cells),
+            }
+        }
+        let rows: Vec<Vec<String>> = rows
+            .into_iter()
+            .map(|row| row.iter().map(|cell| rst_inline(cell)).collect())
+            .collect();
+        (pipe_table(&rows), end)
+    }
+}
+
+/// Parses the body of a `list-table` directive into rows of cells.
+fn rst_list_table(content: &str) -> Vec<Vec<String>> {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() {
+            continue;
+        }
+        let row_start = line
+            .strip_prefix('*')
+            .map(str::trim_start)
+            .and_then(|rest| rest.strip_prefix('-'));
+        if let Some(cell) = row_start {
+            rows.push(vec![cell.trim().to_string()]);
+        } else if let (Some(cell), Some(row)) = (line.strip_prefix('-'), rows.last_mut()) {
+            row.push(cell.trim().to_string());
+        } else if let Some(cell) = rows.last_mut().and_then(|row| row.last_mut()) {
+            cell.push(' ');
+            cell.push_str(line);
+        }
+    }
+    rows.into_iter()
+        .map(|row| row.iter().map(|cell| rst_inline(cell)).collect())
+        .collect()
+}
+
+/// Rewrites reStructuredText inline literals, roles, and links.
+///
+/// Emphasis and strong emphasis are written the same way in markdown.
+fn rst_inline(text: &str) -> String {
+    static INLINE: OnceLock<Regex> = OnceLock::new();
+    let inline = regex(
+        &INLINE,
+        concat!(
+            r"``(?P<literal>.+?)``",
+            r"|:(?P<role>[\w:.+-]+):`(?P<role_text>[^`]+)`",
+            r"|`(?P<link_text>[^`<]*?)\s*<(?P<link>[^`>]+)>`__?",
+            r"|`(?P<reference>[^`]+)`__?",
+            r"|`(?P<interpreted>[^`]+)`",
+            r"|\[(?P<footnote>[#*]?[\w-]*)\]_",
+        ),
+    );
+    inline
+        .replace_all(text, |caps: &Captures| {
+            let group = |name: &str| caps.name(name).map(|m| m.as_str());
+            if let Some(literal) = group("literal") {
+                format!("`{literal}`")
+            } else if let (Some(role), Some(text)) = (group("role"), group("role_text")) {
+                rst_role(role, text)
+            } else if let Some(target) = group("link") {
+                match group("link_text").map(str::trim) {
+                    Some(text) if !text.is_empty() => format!("[{text}]({target})"),
+                    _ => format!("<{target}>"),
+                }
+            } else if let Some(reference) = group("reference") {
+                reference.to_string()
+            } else if let Some(text) = group("interpreted") {
+                format!("*{text}*")
+            } else {
+                format!("[{}]", group("footnote").unwrap_or_default())
+            }
+        })
+        .into_owned()
+}
+
+/// Renders an interpreted text role, such as `:func:` or `:ref:`.
+///
+/// Cross-references to other pages become their plain title, and roles
+/// naming code objects become code spans.
+fn rst_role(role: &str, content: &str) -> String {
+    let text = match content.trim_end().strip_suffix('>') {
+        Some(titled) if titled.contains('<') => titled
+            .rsplit_once('<')
+            .map_or(content, |(title, _)| title)
+            .trim()
+            .to_string(),
+        // A leading `~` shortens a dotted name to its last part
+        _ => match content.strip_prefix('~') {
+            Some(name) => name.rsplit('.').next().unwrap_or(name).to_string(),
+            None => content.to_string(),
+        },
+    };
+    match role {
+        "ref" | "doc" | "term" | "abbr" | "numref" | "download" | "title-reference" => text,
+        "emphasis" => format!("*{text}*"),
+        "strong" => format!("**{text}**"),
+        "math" => format!("${text}$"),
+        _ => format!("`{text}`"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(syntax: MarkupSyntax, source: &str) -> String {
+        let output_config = crate::config::OutputConfig {
+            include_frontmatter: false,
+            ..Default::default()
+        };
+        MarkupConverter::with_config(HttpClient::new(), output_config)
+            .convert_markup("doc", syntax, source)
+            .unwrap()
+            .as_str()
+            .to_string()
+    }
+
+    #[test]
+    fn test_convert_asciidoc() {
+        let markdown = convert(
+            MarkupSyntax::AsciiDoc,
+            "= Widget Guide\nJane Doe <jane@example.com>\n:product: Widget\n\n\
+             // A comment\n\
+             Welcome to *{product}*, a _fast_ tool. See link:https://example.com[the site].\n\
+             Use `snake_case_name` and <<install,Installation>>.\n\n\
+             [[install]]\n== Installation\n\n\
+             NOTE: Requires Rust.\n\n\
+             [WARNING]\n====\nBack up first.\n====\n\n\
+             .Build steps\n[source,rust]\n----\nfn main() {}\n----\n\n\
+             * First\n** Nested\n. Step\n\n\
+             image::diagram.png[Architecture,600]\n",
+        );
+
+        assert_eq!(
+            markdown,
+            "# Widget Guide\n\n\
+             Welcome to **Widget**, a *fast* tool. See [the site](https://example.com).\n\
+             Use `snake_case_name` and Installation.\n\n\
+             ## Installation\n\n\
+             > **Note:** Requires Rust.\n\n\
+             > **Warning:** Back up first.\n\n\
+             **Build steps**\n\n\
+             ```rust\nfn main() {}\n```\n\n\
+             - First\n  - Nested\n1. Step\n\n\
+             ![Architecture](diagram.png)\n"
+        );
+    }
+
+    #[test]
+    fn test_convert_asciidoc_table() {
+        let markdown = convert(
+            MarkupSyntax::AsciiDoc,
+            "[cols=\"1,2\"]\n|===\n|Name |Description\n\n|alpha\n|First *letter*\n\n|beta |Pipe \\| here\n|===\n",
+        );
+
+        assert_eq!(
+            markdown,
+            "| Name | Description |\n| --- | --- |\n| alpha | First **letter** |\n| beta | Pipe \\| here |\n"
+        );
+    }
+
+    #[test]
+    fn test_convert_rst() {
+        let markdown = convert(
+            MarkupSyntax::ReStructuredText,
+            "==========\nUser Guide\n==========\n\n\
+             .. contents::\n   :depth: 2\n\n\
+             Intro with ``literal``, :func:`~pkg.mod.run`, :ref:`Setup <setup>`, and `Example <https://example.com>`_.\n\n\
+             .. _setup:\n\n\
+             Setup\n=====\n\n\
+             .. note::\n   Requires Python 3.\n\n   Second paragraph.\n\n\
+             Example::\n\n    $ tool --run\n\n\
+             .. code-block:: python\n   :linenos:\n\n   def f():\n       return 1\n\n\
+             Details\n-------\n\n\
+             - one\n- two\n\n#. numbered\n",
+        );
+
+        assert_eq!(
+            markdown,
+            "# User Guide\n\n\
+             Intro with `literal`, `run`, Setup, and [Example](https://example.com).\n\n\
+             ## Setup\n\n\
+             > **Note:** Requires Python 3.\n>\n> Second paragraph.\n\n\
+             Example:\n\n```\n$ tool --run\n```\n\n\
+             ```python\ndef f():\n    return 1\n```\n\n\
+             ### Details\n\n\
+             - one\n- two\n\n1. numbered\n"
+        );
+    }
+
+    #[test]
+    fn test_convert_rst_tables() {
+        let expected = "| Name | Value |\n| --- | --- |\n| a | 1 more |\n| b | 2 |\n";
+        let grid = "+------+-------+\n| Name | Value |\n+======+=======+\n| a    | 1     |\n|      | more  |\n+------+-------+\n| b    | 2     |\n+------+-------+\n";
+        let simple =
+            "====  =====\nName  Value\n====  =====\na     1\n      more\nb     2\n====  =====\n";
+        let list = ".. list-table::\n   :header-rows: 1\n\n   * - Name\n     - Value\n   * - a\n     - 1\n       more\n   * - b\n     - 2\n";
+
+        for source in [grid, simple, list] {
+            assert_eq!(convert(MarkupSyntax::ReStructuredText, source), expected);
+        }
+    }
+
+    #[test]
+    fn test_convert_markup_frontmatter_and_errors() {
+        let converter = MarkupConverter::new();
+        let markdown = converter
+            .convert_markup(
+                "https://example.com/guide.adoc",
+                MarkupSyntax::AsciiDoc,
+                "= Guide\n\nText.\n",
+            )
+            .unwrap();
+        assert!(markdown.as_str().contains("title: Guide"));
+        assert!(markdown.as_str().contains("conversion_type: asciidoc"));
+
+        match converter.convert_markup("a.rst", MarkupSyntax::ReStructuredText, ".. comment\n") {
+            Err(MarkdownError::ContentError { kind, .. }) => {
+                assert_eq!(kind, ContentErrorKind::EmptyContent)
+            }
+            other => panic!("Expected ContentError, got {other:?}"),
+        }
+    }
+}
@@ -15,6 +15,36 @@ pub mod preprocessor;
 /// Markdown postprocessing utilities
 pub mod postprocessor;
 
+/// Soft-404 page detection
+pub mod soft404;
+
+/// Paywall and login-wall detection
+pub mod access_wall;
+
+/// Excerpt generation from converted content
+pub mod excerpt;
+
+/// Keyword extraction for frontmatter tags
+pub mod keywords;
+
+/// Comment preservation for exported documents
+pub mod comments;
+
+/// Escaping of markdown special characters in page text
+pub mod escaping;
+
+/// Raw HTML passthrough for unconvertible elements
+pub mod passthrough;
+
+/// Summaries of forms and interactive controls
+pub mod forms;
+
+/// Image elements kept as markdown image links through conversion
+pub mod images;
+
+/// Incremental HTML splitting for streaming conversion
+pub mod streaming;
+
 /// HTML to markdown converter
 pub mod html;
 
@@ -24,13 +54,70 @@ pub mod google_docs;
 /// GitHub Issues to markdown converter
 pub mod github;
 
+/// Azure DevOps work item to markdown converter
+pub mod azure_devops;
+
+/// Office 365 document to markdown converter
+pub mod office365;
+
+/// Dropbox Paper and shared file to markdown converter
+pub mod dropbox;
+
 /// Local file to markdown converter
 pub mod local;
 
+/// Data URI to markdown converter
+pub mod data_uri;
+
+/// Cloud object storage to markdown converter
+pub mod cloud;
+
+/// IPFS to markdown converter
+pub mod ipfs;
+
+/// PDF to markdown converter
+pub mod pdf;
+
+/// DOCX to markdown converter
+pub mod docx;
+
+/// Jupyter notebook to markdown converter
+pub mod notebook;
+
+/// AsciiDoc and reStructuredText to markdown converter
+pub mod markup;
+
+/// Email message and web archive to markdown converter
+pub mod email;
+
+/// Markdown source passthrough with normalization
+pub mod markdown_source;
+
+/// JSON and XML rendering as code blocks
+pub mod structured;
+
+/// Plain text and man page rendering as structured markdown
+pub mod plaintext;
+
+/// Converters registered by plugin crates
+pub mod plugin;
+
 // Re-export main converter types for convenience
-pub use config::HtmlConverterConfig;
-pub use converter::{Converter, ConverterRegistry};
+pub use azure_devops::AzureDevOpsConverter;
+pub use cloud::CloudStorageConverter;
+pub use config::{DetectionStrategy, EscapeMode, HtmlConverterConfig, PlainTextHeuristics};
+pub use converter::{Converter, ConverterRegistry, MarkdownStream};
+pub use data_uri::DataUriConverter;
+pub use docx::DocxConverter;
+pub use dropbox::DropboxConverter;
+pub use email::EmailConverter;
 pub use github::GitHubConverter;
 pub use google_docs::GoogleDocsConverter;
 pub use html::HtmlConverter;
+pub use ipfs::IpfsConverter;
 pub use local::LocalFileConverter;
+pub use markdown_source::MarkdownSourceConverter;
+pub use markup::MarkupConverter;
+pub use notebook::NotebookConverter;
+pub use office365::Office365Converter;
+pub use pdf::PdfConverter;
@@ -0,0 +1,539 @@
+//! Jupyter notebook (`.ipynb`) to markdown conversion.
+//!
+//! Notebooks are JSON documents holding a list of cells. Markdown cells are
+//! kept as they are and code cells become fenced blocks tagged with the
+//! notebook's language. Cell outputs are rendered beneath their code:
+//! printed text and results as plain text blocks, HTML results such as
+//! dataframe tables through the HTML pipeline, and images as inline image
+//! references. Outputs can be left out with
+//! [`crate::config::ConfigBuilder::notebook_outputs`].
+//!
+//! Notebooks linked on GitHub (`github.com/owner/repo/blob/...`) are
+//! downloaded from `raw.githubusercontent.com` rather than as the rendered
+//! page. Only the current notebook format (nbformat 4) is supported.
+
+use crate::client::HttpClient;
+use crate::frontmatter::FrontmatterBuilder;
+use crate::types::{ContentErrorKind, ErrorContext, Markdown, MarkdownError};
+use async_trait::async_trait;
+use base64::Engine;
+use chrono::Utc;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use super::config::HtmlConverterConfig;
+use super::converter::Converter;
+use super::html::HtmlConverter;
+
+/// Image types rendered as inline image references, in order of preference.
+const IMAGE_TYPES: [&str; 4] = ["image/png", "image/jpeg", "image/gif", "image/svg+xml"];
+
+/// Text stored either as one string or as a list of lines.
+#[derive(Debug, Default, Deserialize)]
+#[serde(untagged)]
+enum MultilineText {
+    #[default]
+    Empty,
+    Text(String),
+    Lines(Vec<String>),
+}
+
+impl MultilineText {
+    fn text(&self) -> String {
+        match self {
+            MultilineText::Empty => String::new(),
+            MultilineText::Text(text) => text.clone(),
+            MultilineText::Lines(lines) => lines.concat(),
+        }
+    }
+}
+
+/// A parsed notebook.
+#[derive(Debug, Deserialize)]
+struct Notebook {
+    /// Missing from notebooks older than nbformat 4, which used worksheets
+    cells: Option<Vec<Cell>>,
+    #[serde(default)]
+    metadata: NotebookMetadata,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct NotebookMetadata {
+    title: Option<String>,
+    kernelspec: Option<KernelSpec>,
+    language_info: Option<LanguageInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KernelSpec {
+    language: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LanguageInfo {
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Cell {
+    cell_type: String,
+    #[serde(default)]
+    source: MultilineText,
+    #[serde(default)]
+    outputs: Vec<Output>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Output {
+    output_type: String,
+    /// Stream output text
+    #[serde(default)]
+    text: MultilineText,
+    /// Result and display data, keyed by media type
+    #[serde(default)]
+    data: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    ename: String,
+    #[serde(default)]
+    evalue: String,
+}
+
+impl Notebook {
+    /// The language code cells are written in, from the kernel metadata.
+    fn language(&self) -> Option<&str> {
+        let metadata = &self.metadata;
+        metadata
+            .language_info
+            .as_ref()
+            .and_then(|info| info.name.as_deref())
+            .or_else(|| {
+                metadata
+                    .kernelspec
+                    .as_ref()
+                    .and_then(|kernel| kernel.language.as_deref())
+            })
+            .filter(|language| !language.is_empty())
+    }
+
+    /// The title from the metadata, or else the first top-level heading.
+    fn title(&self, cells: &[Cell]) -> Option<String> {
+        if let Some(title) = self.metadata.title.as_deref().map(str::trim) {
+            if !title.is_empty() {
+                return Some(title.to_string());
+            }
+        }
+        cells
+            .iter()
+            .filter(|cell| cell.cell_type == "markdown")
+            .filter_map(|cell| {
+                cell.source
+                    .text()
+                    .lines()
+                    .find_map(|line| line.strip_prefix("# "))
+                    .map(|heading| heading.trim().to_string())
+            })
+            .find(|heading| !heading.is_empty())
+    }
+}
+
+/// Jupyter notebook to markdown converter.
+#[derive(Debug, Clone)]
+pub struct NotebookConverter {
+    client: HttpClient,
+    html_config: HtmlConverterConfig,
+    output_config: crate::config::OutputConfig,
+}
+
+impl NotebookConverter {
+    /// Creates a new notebook converter with default configuration.
+    pub fn new() -> Self {
+        Self {
+            client: HttpClient::new(),
+            html_config: HtmlConverterConfig::default(),
+            output_config: crate::config::OutputConfig::default(),
+        }
+    }
+
+    /// Creates a new notebook converter with custom configuration and HTTP client.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Configured HTTP client to use for requests
+    /// * `html_config` - Options for converting HTML cell outputs
+    /// * `output_config` - Output configuration, including whether cell outputs are rendered
+    pub fn with_config(
+        client: HttpClient,
+        html_config: HtmlConverterConfig,
+        output_config: crate::config::OutputConfig,
+    ) -> Self {
+        Self {
+            client,
+            html_config,
+            output_config,
+        }
+    }
+
+    /// Converts notebook JSON that has already been downloaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL the notebook was fetched from
+    /// * `bytes` - The `.ipynb` file contents
+    ///
+    /// # Returns
+    ///
+    /// Returns the converted markdown on success, or a `MarkdownError` on failure.
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ContentError` - If the bytes are not notebook JSON
+    ///   (`ParsingFailed`), use a notebook format older than version 4
+    ///   (`UnsupportedFormat`), or have no cells with content (`EmptyContent`)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::converters::NotebookConverter;
+    ///
+    /// let notebook = r##"{
+    ///     "cells": [
+    ///         {"cell_type": "markdown", "source": ["# Analysis\n"]},
+    ///         {"cell_type": "code", "source": "print(1 + 1)",
+    ///          "outputs": [{"output_type": "stream", "name": "stdout", "text": ["2\n"]}]}
+    ///     ],
+    ///     "metadata": {"language_info": {"name": "python"}},
+    ///     "nbformat": 4
+    /// }"##;
+    /// let markdown = NotebookConverter::new().convert_notebook("analysis.ipynb", notebook.as_bytes())?;
+    /// assert!(markdown.as_str().contains("```python\nprint(1 + 1)\n```"));
+    /// assert!(markdown.as_str().contains("```text\n2\n```"));
+    /// # Ok::<(), markdowndown::types::MarkdownError>(())
+    /// ```
+    pub fn convert_notebook(&self, url: &str, bytes: &[u8]) -> Result<Markdown, MarkdownError> {
+        let content_error = |kind, info: String| MarkdownError::ContentError {
+            kind,
+            context: ErrorContext::new(url, "Notebook conversion", self.name()).with_info(info),
+        };
+
+        let notebook: Notebook = serde_json::from_slice(bytes).map_err(|e| {
+            content_error(
+                ContentErrorKind::ParsingFailed,
+                format!("Response is not a Jupyter notebook: {e}"),
+            )
+        })?;
+        let Some(cells) = &notebook.cells else {
+            return Err(content_error(
+                ContentErrorKind::UnsupportedFormat,
+                "Only nbformat 4 notebooks are supported".to_string(),
+            ));
+        };
+
+        let body = self.render_cells(cells, notebook.language());
+        if body.trim().is_empty() {
+            return Err(content_error(
+                ContentErrorKind::EmptyContent,
+                "Notebook has no cells with content".to_string(),
+            ));
+        }
+
+        if !self.output_config.include_frontmatter {
+            return Markdown::new(body);
+        }
+
+        let now = Utc::now();
+        let mut builder = FrontmatterBuilder::new(url.to_string())
+            .exporter(format!(
+                "markdowndown-notebook-{}",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .download_date(now)
+            .additional_field("converted_at".to_string(), now.to_rfc3339())
+            .additional_field("conversion_type".to_string(), "notebook".to_string())
+            .additional_field("url".to_string(), url.to_string());
+        if let Some(title) = notebook.title(cells) {
+            builder = builder.additional_field("title".to_string(), title);
+        }
+        if let Some(language) = notebook.language() {
+            builder = builder.additional_field("language".to_string(), language.to_string());
+        }
+        builder = builder.additional_field("cell_count".to_string(), cells.len().to_string());
+
+        // Add custom frontmatter fields from configuration
+        for (key, value) in &self.output_config.custom_frontmatter_fields {
+            builder = builder.additional_field(key.clone(), value.clone());
+        }
+
+        let frontmatter = builder.build()?;
+        Markdown::new(format!("{frontmatter}\n{body}"))
+    }
+
+    /// Renders cells in order, each followed by its outputs when enabled.
+    ///
+    /// Raw cells hold input for other export tools and are skipped.
+    fn render_cells(&self, cells: &[Cell], language: Option<&str>) -> String {
+        let mut blocks = Vec::new();
+        for cell in cells {
+            let source = cell.source.text();
+            match cell.cell_type.as_str() {
+                "markdown" if !source.trim().is_empty() => {
+                    blocks.push(source.trim_end().to_string());
+                }
+                "code" => {
+                    if !source.trim().is_empty() {
+                        blocks.push(code_block(language.unwrap_or_default(), &source));
+                    }
+                    if self.output_config.notebook_outputs {
+                        blocks.extend(cell.outputs.iter().filter_map(|o| self.render_output(o)));
+                    }
+                }
+                _ => {}
+            }
+        }
+        let mut markdown = blocks.join("\n\n");
+        markdown.push('\n');
+        markdown
+    }
+
+    /// Renders one cell output, preferring markdown, then HTML, images, and plain text.
+    fn render_output(&self, output: &Output) -> Option<String> {
+        match output.output_type.as_str() {
+            "stream" => {
+                let text = strip_ansi(&output.text.text());
+                (!text.trim().is_empty()).then(|| code_block("text", &text))
+            }
+            "execute_result" | "display_data" => {
+                let data = |media_type: &str| output.data.get(media_type).and_then(value_text);
+                if let Some(markdown) = data("text/markdown") {
+                    return Some(markdown.trim_end().to_string());
+                }
+                if let Some(markdown) = data("text/html").and_then(|html| self.convert_html(&html))
+                {
+                    return Some(markdown);
+                }
+                if let Some((media_type, image)) = IMAGE_TYPES
+                    .iter()
+                    .find_map(|media_type| data(media_type).map(|image| (media_type, image)))
+                {
+                    return Some(image_reference(media_type, &image));
+                }
+                data("text/plain")
+                    .filter(|text| !text.trim().is_empty())
+                    .map(|text| code_block("text", &strip_ansi(&text)))
+            }
+            "error" => Some(code_block(
+                "text",
+                &format!("{}: {}", output.ename, output.evalue),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Converts an HTML output, such as a dataframe table, to markdown.
+    fn convert_html(&self, html: &str) -> Option<String> {
+        HtmlConverter::with_config(
+            self.client.clone(),
+            self.html_config.clone(),
+            self.output_config.clone(),
+        )
+        .convert_html(html)
+        .ok()
+        .map(|markdown| markdown.trim().to_string())
+        .filter(|markdown| !markdown.is_empty())
+    }
+}
+
+impl Default for NotebookConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Converter for NotebookConverter {
+    async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+        let bytes = self.client.get_bytes(&raw_github_url(url)).await?;
+        self.convert_notebook(url, &bytes)
+    }
+
+    fn name(&self) -> &'static str {
+        "Jupyter Notebook"
+    }
+}
+
+/// Rewrites a GitHub file page URL to the raw file it shows.
+pub(crate) fn raw_github_url(url: &str) -> String {
+    let Ok(parsed) = url::Url::parse(url) else {
+        return url.to_string();
+    };
+    if parsed.host_str() != Some("github.com") {
+        return url.to_string();
+    }
+    let segments: Vec<&str> = parsed.path().trim_matches('/').split('/').collect();
+    match segments.as_slice() {
+        [owner, repo, "blob", rest @ ..] if !rest.is_empty() => format!(
+            "https://raw.githubusercontent.com/{owner}/{repo}/{}",
+            rest.join("/")
+        ),
+        _ => url.to_string(),
+    }
+}
+
+/// Reads an output value stored as a string or a list of lines.
+fn value_text(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(text) => Some(text.clone()),
+        serde_json::Value::Array(lines) => Some(
+            lines
+                .iter()
+                .filter_map(serde_json::Value::as_str)
+                .collect::<String>(),
+        ),
+        _ => None,
+    }
+}
+
+/// Renders text as a fenced code block, lengthening the fence if the text holds backticks.
+pub(crate) fn code_block(language: &str, text: &str) -> String {
+    let longest_run = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!(
+        "{fence}{language}\n{}\n{fence}",
+        text.trim_end_matches('\n')
+    )
+}
+
+/// Renders an image output as an inline image reference.
+///
+/// PNG, JPEG, and GIF outputs are already base64 encoded; SVG outputs are
+/// stored as markup and encoded here.
+fn image_reference(media_type: &str, image: &str) -> String {
+    let data = if media_type == "image/svg+xml" {
+        base64::engine::general_purpose::STANDARD.encode(image)
+    } else {
+        image.split_whitespace().collect()
+    };
+    format!("![Output](data:{media_type};base64,{data})")
+}
+
+/// Removes the terminal color codes found in tracebacks and printed output.
+fn strip_ansi(text: &str) -> String {
+    static ANSI: OnceLock<Regex> = OnceLock::new();
+    ANSI.get_or_init(|| Regex::new(r"\x1b\[[0-9;]*[A-Za-z]").expect("valid ANSI pattern"))
+        .replace_all(text, "")
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTEBOOK: &str = r##"{
+        "cells": [
+            {"cell_type": "markdown", "source": ["# Sales Report\n", "\n", "Quarterly totals."]},
+            {"cell_type": "code", "execution_count": 1, "source": ["import pandas as pd\n", "df.head()"],
+             "outputs": [
+                {"output_type": "stream", "name": "stdout", "text": ["\u001b[1mloaded\u001b[0m 3 rows\n"]},
+                {"output_type": "execute_result", "execution_count": 1, "metadata": {},
+                 "data": {"text/plain": ["   region  total"],
+                          "text/html": ["<table><tr><th>region</th><th>total</th></tr>",
+                                        "<tr><td>West</td><td>120</td></tr></table>"]}}
+             ]},
+            {"cell_type": "code", "source": "plot()",
+             "outputs": [{"output_type": "display_data", "metadata": {},
+                          "data": {"image/png": "iVBORw0KGgo=\n", "text/plain": ["<Figure>"]}}]},
+            {"cell_type": "code", "source": "1 / 0",
+             "outputs": [{"output_type": "error", "ename": "ZeroDivisionError",
+                          "evalue": "division by zero", "traceback": []}]},
+            {"cell_type": "raw", "source": "\\newpage"}
+        ],
+        "metadata": {"kernelspec": {"name": "python3", "language": "python"}},
+        "nbformat": 4,
+        "nbformat_minor": 5
+    }"##;
+
+    #[test]
+    fn test_convert_notebook_cells_and_outputs() {
+        let markdown = NotebookConverter::new()
+            .convert_notebook("https://example.com/sales.ipynb", NOTEBOOK.as_bytes())
+            .unwrap();
+        let markdown = markdown.as_str();
+
+        assert!(markdown.contains("title: Sales Report"));
+        assert!(markdown.contains("language: python"));
+        assert!(markdown.contains(
+            "# Sales Report\n\nQuarterly totals.\n\n```python\nimport pandas as pd\ndf.head()\n```"
+        ));
+        assert!(markdown.contains("```text\nloaded 3 rows\n```"));
+        assert!(markdown.contains("West"));
+        assert!(!markdown.contains("<table>"));
+        assert!(markdown.contains("![Output](data:image/png;base64,iVBORw0KGgo=)"));
+        assert!(markdown.contains("```text\nZeroDivisionError: division by zero\n```"));
+        assert!(!markdown.contains("newpage"));
+    }
+
+    #[test]
+    fn test_convert_notebook_without_outputs() {
+        let output_config = crate::config::OutputConfig {
+            include_frontmatter: false,
+            notebook_outputs: false,
+            ..Default::default()
+        };
+        let converter = NotebookConverter::with_config(
+            HttpClient::new(),
+            HtmlConverterConfig::default(),
+            output_config,
+        );
+        let markdown = converter
+            .convert_notebook("sales.ipynb", NOTEBOOK.as_bytes())
+            .unwrap();
+
+        assert!(markdown.as_str().starts_with("# Sales Report"));
+        assert!(markdown.as_str().contains("```python\n1 / 0\n```"));
+        assert!(!markdown.as_str().contains("loaded"));
+        assert!(!markdown.as_str().contains("ZeroDivisionError"));
+    }
+
+    #[test]
+    fn test_convert_notebook_rejects_other_json() {
+        let converter = NotebookConverter::new();
+        for (bytes, expected) in [
+            (&b"not json"[..], ContentErrorKind::ParsingFailed),
+            (
+                br#"{"worksheets": [], "nbformat": 3}"#,
+                ContentErrorKind::UnsupportedFormat,
+            ),
+            (
+                br#"{"cells": [], "nbformat": 4}"#,
+                ContentErrorKind::EmptyContent,
+            ),
+        ] {
+            match converter.convert_notebook("a.ipynb", bytes) {
+                Err(MarkdownError::ContentError { kind, .. }) => assert_eq!(kind, expected),
+                other => panic!("Expected ContentError, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_code_block_and_raw_urls() {
+        assert_eq!(code_block("python", "x = 1\n"), "```python\nx = 1\n```");
+        assert_eq!(
+            code_block("markdown", "```\ncode\n```"),
+            "````markdown\n```\ncode\n```\n````"
+        );
+        assert_eq!(
+            raw_github_url("https://github.com/owner/repo/blob/main/docs/intro.ipynb"),
+            "https://raw.githubusercontent.com/owner/repo/main/docs/intro.ipynb"
+        );
+        assert_eq!(
+            raw_github_url("https://example.com/intro.ipynb"),
+            "https://example.com/intro.ipynb"
+        );
+    }
+}
@@ -0,0 +1,472 @@
+//! Office 365 documents to markdown conversion through the Microsoft Graph API.
+//!
+//! SharePoint and OneDrive links open a web viewer, so fetching them returns
+//! the viewer's HTML rather than the document. With an Office 365 access
+//! token, the document is instead located with the Graph shares API and
+//! downloaded: Word documents as DOCX, and PowerPoint, Excel, and other
+//! Office formats converted to PDF by Graph. Either is then converted like a
+//! downloaded file of that type.
+//!
+//! Without a token, the link is fetched and converted as HTML, as before.
+//! The token, set with `Config::builder().office365_token(...)`, needs the
+//! `Files.Read.All` or `Sites.Read.All` permission. A Microsoft
+//! [`crate::oauth::OAuthSession`] can be configured instead, so tokens are
+//! refreshed rather than pasted in again when they expire.
+//!
+//! # Supported URLs
+//!
+//! - SharePoint sharing links, such as `https://contoso.sharepoint.com/:w:/s/team/EaBcD...`
+//! - SharePoint file URLs, such as `https://contoso.sharepoint.com/sites/team/Shared%20Documents/plan.docx`
+//! - OneDrive short links, such as `https://1drv.ms/w/s!AbCd...`
+//! - OneDrive file links with a `resid` parameter
+//!
+//! # Usage Examples
+//!
+//! ```rust,no_run
+//! use markdowndown::{Config, MarkdownDown};
+//!
+//! # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
+//! let config = Config::builder().office365_token("eyJ0eXAi...").build();
+//! let md = MarkdownDown::with_config(config);
+//! let markdown = md
+//!     .convert_url("https://contoso.sharepoint.com/:w:/s/team/EaBcDeFgHiJk")
+//!     .await?;
+//! println!("{}", markdown);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::HttpClient;
+use crate::types::{ContentErrorKind, ErrorContext, Markdown, MarkdownError};
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::{debug, instrument};
+
+use super::converter::Converter;
+use super::html::HtmlConverter;
+
+/// Default Microsoft Graph API base URL
+const DEFAULT_GRAPH_API_BASE_URL: &str = "https://graph.microsoft.com/v1.0";
+
+/// Extensions of files Graph converts to PDF, since there is no better
+/// converter for their native format.
+const PDF_EXPORT_EXTENSIONS: [&str; 13] = [
+    "doc", "dot", "odt", "rtf", "ppt", "pptx", "pps", "ppsx", "odp", "xls", "xlsx", "xlsm", "ods",
+];
+
+/// A file or folder in OneDrive or SharePoint, as described by Graph.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DriveItem {
+    /// File name, including its extension
+    pub name: String,
+    /// Present if the item is a file
+    pub file: Option<FileFacet>,
+}
+
+/// The file-specific properties of a drive item.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileFacet {
+    /// The file's MIME type
+    #[serde(default)]
+    pub mime_type: Option<String>,
+}
+
+impl DriveItem {
+    /// Returns true if Graph should convert the file to PDF for download.
+    pub fn exports_as_pdf(&self) -> bool {
+        self.name
+            .rsplit_once('.')
+            .map(|(_, extension)| extension.to_ascii_lowercase())
+            .is_some_and(|extension| PDF_EXPORT_EXTENSIONS.contains(&extension.as_str()))
+    }
+}
+
+/// Office 365 document to markdown converter.
+///
+/// Downloads SharePoint and OneDrive documents through the Microsoft Graph
+/// API when a token is configured, and converts the links as HTML otherwise.
+#[derive(Debug, Clone)]
+pub struct Office365Converter {
+    /// HTTP client for making requests to the Graph API
+    client: HttpClient,
+    /// Converter for downloaded documents, and for links when no token is set
+    html_converter: HtmlConverter,
+    /// Optional Office 365 access token
+    auth_token: Option<String>,
+    /// Base URL of the Graph API
+    graph_api_base_url: String,
+}
+
+impl Office365Converter {
+    /// Creates a new Office 365 converter without a token.
+    ///
+    /// Links are converted as HTML until a token is set.
+    pub fn new() -> Self {
+        Self {
+            client: HttpClient::new(),
+            html_converter: HtmlConverter::new(),
+            auth_token: None,
+            graph_api_base_url: DEFAULT_GRAPH_API_BASE_URL.to_string(),
+        }
+    }
+
+    /// Creates a new Office 365 converter with an access token.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - Microsoft Graph access token with permission to read files
+    pub fn new_with_token(token: String) -> Self {
+        Self {
+            auth_token: Some(token),
+            ..Self::new()
+        }
+    }
+
+    /// Creates an Office 365 converter with a configured client and HTML converter.
+    ///
+    /// The token configured on the client is used, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client used for API requests
+    /// * `html_converter` - The configured converter used for downloaded documents
+    pub fn with_config(client: HttpClient, html_converter: HtmlConverter) -> Self {
+        let auth_token = client.office365_token().map(str::to_string);
+        Self {
+            client,
+            html_converter,
+            auth_token,
+            graph_api_base_url: DEFAULT_GRAPH_API_BASE_URL.to_string(),
+        }
+    }
+
+    /// Fetches from a different Graph API base URL (primarily for testing).
+    ///
+    /// # Arguments
+    ///
+    /// * `graph_api_base_url` - The URL used in place of `https://graph.microsoft.com/v1.0`
+    pub fn with_graph_api_base_url<T: Into<String>>(mut self, graph_api_base_url: T) -> Self {
+        self.graph_api_base_url = graph_api_base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Converts a SharePoint or OneDrive document URL to markdown with frontmatter.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The document URL to convert
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::AuthenticationError` - If the token is not accepted
+    /// * `MarkdownError::EnhancedNetworkError` - For API errors or network failures
+    /// * `MarkdownError::ContentError` - If the URL points at a folder, or the
+    ///   API response cannot be parsed
+    #[instrument(skip(self))]
+    pub async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+        let url = url.trim();
+        let Some(headers) = self.graph_headers() else {
+            debug!("No Office 365 token configured; converting {} as HTML", url);
+            return self.html_converter.convert(url).await;
+        };
+
+        let item_url = format!(
+            "{}/shares/{}/driveItem",
+            self.graph_api_base_url,
+            share_id(url)
+        );
+        let item = self.fetch_drive_item(url, &item_url, &headers).await?;
+        if item.file.is_none() {
+            let context = ErrorContext::new(url, "Drive item lookup", "Office365Converter")
+                .with_info(format!("'{}' is a folder, not a document", item.name));
+            return Err(MarkdownError::ContentError {
+                kind: ContentErrorKind::UnsupportedFormat,
+                context,
+            });
+        }
+
+        let content_url = if item.exports_as_pdf() {
+            format!("{item_url}/content?format=pdf")
+        } else {
+            format!("{item_url}/content")
+        };
+        debug!("Downloading {} from {}", item.name, content_url);
+        let content = self
+            .client
+            .get_content_with_headers(&content_url, &headers)
+            .await?;
+        self.html_converter.convert_content(url, content).await
+    }
+
+    /// Returns the headers authenticating Graph requests, or None if there
+    /// are no credentials for Graph.
+    ///
+    /// A configured token is sent as is; otherwise the client adds the token
+    /// of a Microsoft OAuth session, if one was configured.
+    fn graph_headers(&self) -> Option<HashMap<String, String>> {
+        match &self.auth_token {
+            Some(token) => Some(auth_headers(token)),
+            None if self.client.has_oauth_session(&self.graph_api_base_url) => Some(HashMap::new()),
+            None => None,
+        }
+    }
+
+    /// Fetches the drive item a sharing URL points at.
+    async fn fetch_drive_item(
+        &self,
+        url: &str,
+        item_url: &str,
+        headers: &HashMap<String, String>,
+    ) -> Result<DriveItem, MarkdownError> {
+        let mut headers = headers.clone();
+        headers.insert("Accept".to_string(), "application/json".to_string());
+        let response = self
+            .client
+            .get_text_with_headers(&format!("{item_url}?$select=name,file"), &headers)
+            .await?;
+        serde_json::from_str(&response).map_err(|e| {
+            let context = ErrorContext::new(url, "Response parsing", "Office365Converter")
+                .with_info(format!("Failed to parse drive item: {e}"));
+            MarkdownError::ContentError {
+                kind: ContentErrorKind::ParsingFailed,
+                context,
+            }
+        })
+    }
+}
+
+/// Encodes a sharing URL as a Graph share ID: `u!` followed by the URL in
+/// unpadded base64url.
+fn share_id(url: &str) -> String {
+    format!("u!{}", URL_SAFE_NO_PAD.encode(url))
+}
+
+/// Returns the headers authenticating a Graph request.
+fn auth_headers(token: &str) -> HashMap<String, String> {
+    HashMap::from([("Authorization".to_string(), format!("Bearer {token}"))])
+}
+
+impl Default for Office365Converter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Converter for Office365Converter {
+    async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+        self.convert(url).await
+    }
+
+    /// The drive item is looked up before its content is downloaded.
+    fn estimated_requests(&self, _url: &str) -> usize {
+        if self.graph_headers().is_some() {
+            2
+        } else {
+            1
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "Office 365"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const URL: &str = "https://contoso.sharepoint.com/:w:/s/team/EaBcDeFgHiJk";
+
+    fn converter(server: &MockServer) -> Office365Converter {
+        Office365Converter::new_with_token("graph-token".to_string())
+            .with_graph_api_base_url(server.uri())
+    }
+
+    async fn mount_item(server: &MockServer, item: serde_json::Value) {
+        Mock::given(method("GET"))
+            .and(path(format!("/shares/{}/driveItem", share_id(URL))))
+            .and(header("Authorization", "Bearer graph-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(item))
+            .mount(server)
+            .await;
+    }
+
+    #[test]
+    fn test_share_id_encoding() {
+        // The example from the Graph documentation
+        assert_eq!(
+            share_id("https://onedrive.live.com/redir?resid=1231244193912!12&authKey=1201919!12921!1"),
+            "u!aHR0cHM6Ly9vbmVkcml2ZS5saXZlLmNvbS9yZWRpcj9yZXNpZD0xMjMxMjQ0MTkzOTEyITEyJmF1dGhLZXk9MTIwMTkxOSExMjkyMSEx"
+        );
+    }
+
+    #[test]
+    fn test_exports_as_pdf() {
+        let item = |name: &str| DriveItem {
+            name: name.to_string(),
+            file: None,
+        };
+        assert!(item("Roadmap.PPTX").exports_as_pdf());
+        assert!(item("budget.xlsx").exports_as_pdf());
+        assert!(!item("plan.docx").exports_as_pdf());
+        assert!(!item("report.pdf").exports_as_pdf());
+        assert!(!item("README").exports_as_pdf());
+    }
+
+    #[tokio::test]
+    async fn test_convert_downloads_native_content() {
+        let server = MockServer::start().await;
+        mount_item(
+            &server,
+            serde_json::json!({"name": "notes.html", "file": {"mimeType": "text/html"}}),
+        )
+        .await;
+        Mock::given(method("GET"))
+            .and(path(format!("/shares/{}/driveItem/content", share_id(URL))))
+            .and(header("Authorization", "Bearer graph-token"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Type", "text/html")
+                    .set_body_string(
+                        "<html><body><h1>Team Notes</h1><p>Ship it.</p></body></html>",
+                    ),
+            )
+            .mount(&server)
+            .await;
+
+        let markdown = converter(&server).convert(URL).await.unwrap();
+        assert!(markdown.as_str().contains("Team Notes"));
+        assert!(markdown.as_str().contains("Ship it."));
+    }
+
+    #[tokio::test]
+    async fn test_convert_presentation_requests_pdf_export() {
+        let server = MockServer::start().await;
+        mount_item(
+            &server,
+            serde_json::json!({"name": "Roadmap.pptx", "file": {}}),
+        )
+        .await;
+        Mock::given(method("GET"))
+            .and(path(format!("/shares/{}/driveItem/content", share_id(URL))))
+            .and(query_param("format", "pdf"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Type", "text/plain")
+                    .set_body_string("Exported slides"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let markdown = converter(&server).convert(URL).await.unwrap();
+        assert!(markdown.as_str().contains("Exported slides"));
+    }
+
+    #[tokio::test]
+    async fn test_convert_folder_is_unsupported() {
+        let server = MockServer::start().await;
+        mount_item(
+            &server,
+            serde_json::json!({"name": "Shared Documents", "folder": {"childCount": 3}}),
+        )
+        .await;
+
+        let result = converter(&server).convert(URL).await;
+        assert!(matches!(
+            result,
+            Err(MarkdownError::ContentError {
+                kind: ContentErrorKind::UnsupportedFormat,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_token_is_authentication_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let result = converter(&server).convert(URL).await;
+        assert!(matches!(
+            result,
+            Err(MarkdownError::AuthenticationError { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_oauth_session_used_without_token() {
+        use crate::oauth::{MemoryTokenStore, OAuthClient, OAuthSession, OAuthToken};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("/shares/{}/driveItem", share_id(URL))))
+            .and(header("Authorization", "Bearer oauth-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({"name": "notes.html", "file": {"mimeType": "text/html"}}),
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("/shares/{}/driveItem/content", share_id(URL))))
+            .and(header("Authorization", "Bearer oauth-token"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Type", "text/html")
+                    .set_body_string("<html><body><p>Signed in</p></body></html>"),
+            )
+            .mount(&server)
+            .await;
+
+        let session = OAuthSession::new(
+            OAuthClient::microsoft("common", "app-id").hosts(["127.0.0.1"]),
+            MemoryTokenStore::with_token(OAuthToken {
+                access_token: "oauth-token".to_string(),
+                refresh_token: None,
+                expires_at: None,
+            }),
+        );
+        let config = crate::config::Config::builder()
+            .oauth_session(session)
+            .build();
+        let client = HttpClient::with_config(&config.http, &config.auth);
+        let converter = Office365Converter::with_config(client, HtmlConverter::new())
+            .with_graph_api_base_url(server.uri());
+
+        assert_eq!(converter.estimated_requests(URL), 2);
+        let markdown = converter.convert(URL).await.unwrap();
+        assert!(markdown.as_str().contains("Signed in"));
+    }
+
+    #[tokio::test]
+    async fn test_without_token_converts_as_html() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/sites/team/viewer"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Type", "text/html")
+                    .set_body_string("<html><body><p>Viewer page</p></body></html>"),
+            )
+            .mount(&server)
+            .await;
+
+        let converter = Office365Converter::new();
+        assert_eq!(converter.estimated_requests(URL), 1);
+        let markdown = converter
+            .convert(&format!("{}/sites/team/viewer", server.uri()))
+            .await
+            .unwrap();
+        assert!(markdown.as_str().contains("Viewer page"));
+    }
+}
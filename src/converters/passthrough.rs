@@ -0,0 +1,288 @@
+//! Raw HTML passthrough for elements that cannot be converted faithfully.
+//!
+//! Tables with merged cells or nested tables, forms, and custom elements lose
+//! their structure when flattened to markdown. When passthrough is enabled,
+//! these elements are cut out before conversion and put back afterwards as
+//! sanitized HTML in a fenced `html` block, preceded by a warning.
+
+use regex::Regex;
+
+/// Marker text that stands in for an extracted element during conversion.
+const PLACEHOLDER_PREFIX: &str = "MARKDOWNDOWNPASSTHROUGH";
+
+/// An element kept as raw HTML.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassthroughBlock {
+    /// Why the element could not be converted, e.g. "form"
+    pub reason: String,
+    /// The sanitized HTML of the element
+    pub html: String,
+}
+
+/// A tag found in the document.
+struct Tag {
+    start: usize,
+    end: usize,
+    name: String,
+    closing: bool,
+}
+
+/// Extracts unconvertible elements from HTML and restores them after conversion.
+pub struct RawHtmlPassthrough {
+    tag: Regex,
+    merged_cell: Regex,
+    nested_table: Regex,
+    raw_element: Regex,
+    comment: Regex,
+    event_attribute: Regex,
+    script_url: Regex,
+}
+
+impl RawHtmlPassthrough {
+    /// Creates a new passthrough extractor.
+    pub fn new() -> Self {
+        Self {
+            tag: Regex::new(r"<(/?)([a-zA-Z][a-zA-Z0-9-]*)[^>]*>").expect("valid tag pattern"),
+            merged_cell: Regex::new(r#"(?i)\b(?:col|row)span\s*=\s*["']?\s*0*([2-9]|[1-9][0-9]+)"#)
+                .expect("valid merged cell pattern"),
+            nested_table: Regex::new(r"(?i)<table\b").expect("valid nested table pattern"),
+            raw_element: Regex::new(r"(?is)<script\b.*?</script\s*>|<style\b.*?</style\s*>")
+                .expect("valid script pattern"),
+            comment: Regex::new(r"(?s)<!--.*?-->").expect("valid comment pattern"),
+            event_attribute: Regex::new(r#"(?i)\s+on[a-z]+\s*=\s*(?:"[^"]*"|'[^']*'|[^\s>]+)"#)
+                .expect("valid event attribute pattern"),
+            script_url: Regex::new(
+                r#"(?i)\b(href|src|action|formaction)\s*=\s*(?:"\s*javascript:[^"]*"|'\s*javascript:[^']*'|javascript:[^\s>]*)"#,
+            )
+            .expect("valid script URL pattern"),
+        }
+    }
+
+    /// Replaces unconvertible elements with placeholders.
+    ///
+    /// # Arguments
+    ///
+    /// * `html` - The HTML document
+    ///
+    /// # Returns
+    ///
+    /// The document with each unconvertible element replaced by a placeholder
+    /// paragraph, and the extracted elements in document order.
+    pub fn extract(&self, html: &str) -> (String, Vec<PassthroughBlock>) {
+        let tags: Vec<Tag> = self
+            .tag
+            .captures_iter(html)
+            .map(|caps| {
+                let whole = caps.get(0).expect("match has a whole group");
+                Tag {
+                    start: whole.start(),
+                    end: whole.end(),
+                    name: caps[2].to_ascii_lowercase(),
+                    closing: !caps[1].is_empty(),
+                }
+            })
+            .collect();
+
+        let mut output = String::with_capacity(html.len());
+        let mut blocks = Vec::new();
+        let mut cursor = 0;
+        let mut index = 0;
+
+        while index < tags.len() {
+            let open = &tags[index];
+            let candidate = open.name == "table" || open.name == "form" || open.name.contains('-');
+            if open.closing || !candidate || html[open.start..open.end].ends_with("/>") {
+                index += 1;
+                continue;
+            }
+            let Some(close) = matching_close(&tags, index) else {
+                index += 1;
+                continue;
+            };
+
+            let element = &html[open.start..tags[close].end];
+            match self.unconvertible_reason(&open.name, element) {
+                Some(reason) => {
+                    output.push_str(&html[cursor..open.start]);
+                    output.push_str(&format!("<p>{PLACEHOLDER_PREFIX}{}END</p>", blocks.len()));
+                    blocks.push(PassthroughBlock {
+                        reason,
+                        html: self.sanitize(element),
+                    });
+                    cursor = tags[close].end;
+                    index = close + 1;
+                }
+                None => index += 1,
+            }
+        }
+        output.push_str(&html[cursor..]);
+
+        (output, blocks)
+    }
+
+    /// Replaces the placeholders in converted markdown with fenced HTML blocks.
+    ///
+    /// # Arguments
+    ///
+    /// * `markdown` - The converted markdown
+    /// * `blocks` - The elements returned by [`RawHtmlPassthrough::extract`]
+    pub fn restore(&self, markdown: &str, blocks: &[PassthroughBlock]) -> String {
+        if blocks.is_empty() {
+            return markdown.to_string();
+        }
+
+        let mut output = String::with_capacity(markdown.len());
+        for line in markdown.split_inclusive('\n') {
+            let block = blocks.iter().enumerate().find_map(|(index, block)| {
+                line.contains(&format!("{PLACEHOLDER_PREFIX}{index}END"))
+                    .then_some(block)
+            });
+            match block {
+                Some(block) => output.push_str(&render_block(block)),
+                None => output.push_str(line),
+            }
+        }
+        output
+    }
+
+    /// Returns why an element cannot be converted, or `None` if it can.
+    fn unconvertible_reason(&self, name: &str, element: &str) -> Option<String> {
+        match name {
+            "form" => Some("form".to_string()),
+            "table" => {
+                let inner = &element[1..];
+                if self.nested_table.is_match(inner) {
+                    Some("nested table".to_string())
+                } else if self.merged_cell.is_match(element) {
+                    Some("table with merged cells".to_string())
+                } else {
+                    None
+                }
+            }
+            _ => Some(format!("custom element <{name}>")),
+        }
+    }
+
+    /// Removes scripts, styles, comments, event handlers, and `javascript:` URLs.
+    fn sanitize(&self, element: &str) -> String {
+        let cleaned = self.raw_element.replace_all(element, "");
+        let cleaned = self.comment.replace_all(&cleaned, "");
+        let cleaned = self.event_attribute.replace_all(&cleaned, "");
+        let cleaned = self.script_url.replace_all(&cleaned, "$1=\"#\"");
+        cleaned.trim().to_string()
+    }
+}
+
+impl Default for RawHtmlPassthrough {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the index of the tag that closes the element opened at `open`.
+fn matching_close(tags: &[Tag], open: usize) -> Option<usize> {
+    let name = &tags[open].name;
+    let mut depth = 0usize;
+    for (index, tag) in tags.iter().enumerate().skip(open) {
+        if tag.name != *name {
+            continue;
+        }
+        if tag.closing {
+            depth -= 1;
+            if depth == 0 {
+                return Some(index);
+            }
+        } else {
+            depth += 1;
+        }
+    }
+    None
+}
+
+/// Renders a block as a warning followed by a fenced `html` code block.
+fn render_block(block: &PassthroughBlock) -> String {
+    // The fence must be longer than any backtick run in the HTML
+    let longest_run = block
+        .html
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+
+    format!(
+        "\n> **Warning:** {} could not be converted to markdown; the original HTML is kept below.\n\n{fence}html\n{}\n{fence}\n\n",
+        block.reason, block.html
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extracts_only_unconvertible_elements() {
+        let passthrough = RawHtmlPassthrough::new();
+        let html = r#"<table><tr><td>simple</td></tr></table>
+<table><tr><td colspan="2">merged</td></tr></table>
+<form action="/search"><input name="q"></form>
+<date-picker value="today"></date-picker>"#;
+
+        let (cleaned, blocks) = passthrough.extract(html);
+        let reasons: Vec<&str> = blocks.iter().map(|b| b.reason.as_str()).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                "table with merged cells",
+                "form",
+                "custom element <date-picker>"
+            ]
+        );
+        assert!(cleaned.starts_with("<table><tr><td>simple</td></tr></table>"));
+        assert!(cleaned.contains("<p>MARKDOWNDOWNPASSTHROUGH1END</p>"));
+        assert!(!cleaned.contains("colspan"));
+    }
+
+    #[test]
+    fn test_nested_table_is_extracted_whole() {
+        let passthrough = RawHtmlPassthrough::new();
+        let html = "<p>Before</p><table><tr><td><table><tr><td>inner</td></tr></table></td></tr></table><p>After</p>";
+
+        let (cleaned, blocks) = passthrough.extract(html);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].reason, "nested table");
+        assert!(blocks[0].html.ends_with("</table></td></tr></table>"));
+        assert_eq!(
+            cleaned,
+            "<p>Before</p><p>MARKDOWNDOWNPASSTHROUGH0END</p><p>After</p>"
+        );
+    }
+
+    #[test]
+    fn test_sanitizes_extracted_html() {
+        let passthrough = RawHtmlPassthrough::new();
+        let html = r#"<form onsubmit="steal()" action="javascript:alert('x')"><!-- note --><script>evil()</script><button onclick='go()'>Go</button></form>"#;
+
+        let (_, blocks) = passthrough.extract(html);
+        assert_eq!(
+            blocks[0].html,
+            r##"<form action="#"><button>Go</button></form>"##
+        );
+    }
+
+    #[test]
+    fn test_restore_renders_fenced_block_with_warning() {
+        let passthrough = RawHtmlPassthrough::new();
+        let blocks = vec![PassthroughBlock {
+            reason: "form".to_string(),
+            html: "<form><input name=\"q\"></form>".to_string(),
+        }];
+
+        let restored =
+            passthrough.restore("Intro\n\nMARKDOWNDOWNPASSTHROUGH0END\n\nOutro\n", &blocks);
+        assert!(restored.contains("> **Warning:** form could not be converted to markdown"));
+        assert!(restored.contains("```html\n<form><input name=\"q\"></form>\n```\n"));
+        assert!(!restored.contains(PLACEHOLDER_PREFIX));
+        assert!(restored.ends_with("Outro\n"));
+    }
+}
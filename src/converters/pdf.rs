@@ -0,0 +1,635 @@
+//! PDF to markdown conversion with headings and page anchors.
+//!
+//! Text is extracted page by page with `pdf-extract`. Each page is introduced
+//! by an HTML anchor (`<a id="page-3"></a>`) so links like `#page-3` can
+//! point into the converted document. Headings come from the document
+//! outline (bookmarks) when it has one, and otherwise from numbered section
+//! lines such as "2.1 Installation". Lines within a paragraph are joined and
+//! words hyphenated across line breaks are rejoined.
+//!
+//! Documents whose outline has several top-level entries can also be
+//! converted one chapter per entry with [`Converter::convert_parts`].
+//!
+//! PDFs without a text layer, such as scanned documents, cannot be converted
+//! and are reported as empty content.
+
+use crate::client::HttpClient;
+use crate::frontmatter::FrontmatterBuilder;
+use crate::types::{ContentErrorKind, ErrorContext, Markdown, MarkdownError, SubDocument};
+use async_trait::async_trait;
+use chrono::Utc;
+use pdf_extract::{Document, Object};
+use regex::Regex;
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+
+use super::converter::Converter;
+
+/// Bytes every PDF file starts with.
+pub(crate) const PDF_MAGIC: &[u8] = b"%PDF-";
+
+/// Longest line treated as a numbered section heading.
+const MAX_HEADING_LEN: usize = 80;
+
+/// An outline entry pointing at a page.
+struct OutlineEntry {
+    /// Nesting depth, starting at 1 for top-level entries
+    level: usize,
+    title: String,
+    /// 1-based page number
+    page: usize,
+}
+
+/// Text and structure extracted from a PDF.
+struct PdfContent {
+    title: Option<String>,
+    /// Text of each page, in order
+    pages: Vec<String>,
+    outline: Vec<OutlineEntry>,
+}
+
+/// PDF to markdown converter.
+#[derive(Debug, Clone)]
+pub struct PdfConverter {
+    client: HttpClient,
+    output_config: crate::config::OutputConfig,
+}
+
+impl PdfConverter {
+    /// Creates a new PDF converter with default configuration.
+    pub fn new() -> Self {
+        Self {
+            client: HttpClient::new(),
+            output_config: crate::config::OutputConfig::default(),
+        }
+    }
+
+    /// Creates a new PDF converter with a configured HTTP client and output options.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Configured HTTP client to use for requests
+    /// * `output_config` - Output configuration including custom frontmatter fields
+    pub fn with_config(client: HttpClient, output_config: crate::config::OutputConfig) -> Self {
+        Self {
+            client,
+            output_config,
+        }
+    }
+
+    /// Converts PDF bytes that have already been downloaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL the PDF was fetched from
+    /// * `bytes` - The PDF file contents
+    ///
+    /// # Returns
+    ///
+    /// Returns the converted markdown on success, or a `MarkdownError` on failure.
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ContentError` - If the bytes are not a PDF
+    ///   (`UnsupportedFormat`), cannot be parsed (`ParsingFailed`), or have no
+    ///   text layer (`EmptyContent`)
+    pub fn convert_pdf(&self, url: &str, bytes: &[u8]) -> Result<Markdown, MarkdownError> {
+        let content = self.extract(url, bytes)?;
+        let body = render_markdown(&content);
+        self.with_frontmatter(url, content.title.as_deref(), content.pages.len(), body)
+    }
+
+    /// Converts PDF bytes that have already been downloaded, one document per chapter.
+    ///
+    /// Chapters start at the top-level entries of the document outline, and
+    /// pages before the first entry belong to the first chapter. Each chapter
+    /// is titled with its outline entry and keeps the page anchors of the
+    /// whole document. PDFs with fewer than two top-level entries are
+    /// returned as a single part.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL the PDF was fetched from
+    /// * `bytes` - The PDF file contents
+    ///
+    /// # Returns
+    ///
+    /// Returns the chapters in page order on success, or a `MarkdownError` on failure.
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ContentError` - As for [`PdfConverter::convert_pdf`]
+    pub fn convert_pdf_parts(
+        &self,
+        url: &str,
+        bytes: &[u8],
+    ) -> Result<Vec<SubDocument>, MarkdownError> {
+        let content = self.extract(url, bytes)?;
+        let chapters = chapters(&content);
+        if chapters.len() < 2 {
+            let body = render_markdown(&content);
+            let markdown =
+                self.with_frontmatter(url, content.title.as_deref(), content.pages.len(), body)?;
+            return Ok(vec![SubDocument {
+                index: 1,
+                title: content.title,
+                markdown,
+            }]);
+        }
+
+        chapters
+            .into_iter()
+            .enumerate()
+            .map(|(index, (title, pages))| {
+                let page_count = pages.len();
+                let body = render_pages(&content, None, pages);
+                Ok(SubDocument {
+                    index: index + 1,
+                    markdown: self.with_frontmatter(url, Some(&title), page_count, body)?,
+                    title: Some(title),
+                })
+            })
+            .collect()
+    }
+
+    /// Checks that the bytes are a PDF with a text layer and extracts its content.
+    fn extract(&self, url: &str, bytes: &[u8]) -> Result<PdfContent, MarkdownError> {
+        let content_error = |kind, info: String| MarkdownError::ContentError {
+            kind,
+            context: ErrorContext::new(url, "PDF conversion", self.name()).with_info(info),
+        };
+
+        if !bytes.starts_with(PDF_MAGIC) {
+            return Err(content_error(
+                ContentErrorKind::UnsupportedFormat,
+                "Response is not a PDF document".to_string(),
+            ));
+        }
+
+        let content = extract_content(bytes)
+            .map_err(|e| content_error(ContentErrorKind::ParsingFailed, e))?;
+        if content.pages.iter().all(|page| page.trim().is_empty()) {
+            return Err(content_error(
+                ContentErrorKind::EmptyContent,
+                "PDF has no extractable text; it may be a scanned document".to_string(),
+            ));
+        }
+        Ok(content)
+    }
+
+    /// Prepends the frontmatter to a rendered body, when frontmatter is enabled.
+    fn with_frontmatter(
+        &self,
+        url: &str,
+        title: Option<&str>,
+        page_count: usize,
+        body: String,
+    ) -> Result<Markdown, MarkdownError> {
+        if !self.output_config.include_frontmatter {
+            return Markdown::new(body);
+        }
+
+        let now = Utc::now();
+        let mut builder = FrontmatterBuilder::new(url.to_string())
+            .exporter(format!("markdowndown-pdf-{}", env!("CARGO_PKG_VERSION")))
+            .download_date(now)
+            .additional_field("converted_at".to_string(), now.to_rfc3339())
+            .additional_field("conversion_type".to_string(), "pdf".to_string())
+            .additional_field("url".to_string(), url.to_string());
+        if let Some(title) = title {
+            builder = builder.additional_field("title".to_string(), title.to_string());
+        }
+        builder = builder.additional_field("page_count".to_string(), page_count.to_string());
+
+        // Add custom frontmatter fields from configuration
+        for (key, value) in &self.output_config.custom_frontmatter_fields {
+            builder = builder.additional_field(key.clone(), value.clone());
+        }
+
+        let frontmatter = builder.build()?;
+        Markdown::new(format!("{frontmatter}\n{body}"))
+    }
+}
+
+impl Default for PdfConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Converter for PdfConverter {
+    async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+        let bytes = self.client.get_bytes(url).await?;
+        self.convert_pdf(url, &bytes)
+    }
+
+    async fn convert_parts(&self, url: &str) -> Result<Vec<SubDocument>, MarkdownError> {
+        let bytes = self.client.get_bytes(url).await?;
+        self.convert_pdf_parts(url, &bytes)
+    }
+
+    fn name(&self) -> &'static str {
+        "PDF"
+    }
+}
+
+/// Extracts page text, the outline, and the title from a PDF.
+///
+/// The PDF libraries panic on some malformed files, so panics are reported
+/// as parse errors rather than unwinding into the caller.
+fn extract_content(bytes: &[u8]) -> Result<PdfContent, String> {
+    let pages = panic::catch_unwind(|| pdf_extract::extract_text_from_mem_by_pages(bytes))
+        .map_err(|_| "PDF text extraction failed".to_string())?
+        .map_err(|e| format!("PDF text extraction failed: {e}"))?;
+
+    // The outline and title are optional, so failing to read them is not an error
+    let (title, outline) = panic::catch_unwind(AssertUnwindSafe(|| {
+        let Ok(document) = Document::load_mem(bytes) else {
+            return (None, Vec::new());
+        };
+        let outline = document
+            .get_toc()
+            .map(|toc| {
+                toc.toc
+                    .into_iter()
+                    .map(|entry| OutlineEntry {
+                        level: entry.level,
+                        title: collapse_whitespace(&entry.title),
+                        page: entry.page,
+                    })
+                    .filter(|entry| !entry.title.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        (document_title(&document), outline)
+    }))
+    .unwrap_or_default();
+
+    Ok(PdfContent {
+        title,
+        pages,
+        outline,
+    })
+}
+
+/// Reads the title from the document information dictionary.
+fn document_title(document: &Document) -> Option<String> {
+    let info = match document.trailer.get(b"Info").ok()? {
+        Object::Reference(id) => document.get_dictionary(*id).ok()?,
+        Object::Dictionary(info) => info,
+        _ => return None,
+    };
+    let Object::String(bytes, _) = info.get(b"Title").ok()? else {
+        return None;
+    };
+    let title = collapse_whitespace(&decode_text_string(bytes));
+    (!title.is_empty()).then_some(title)
+}
+
+/// Decodes a PDF text string, which is UTF-16BE with a byte order mark or
+/// PDFDocEncoding (treated here as Latin-1).
+fn decode_text_string(bytes: &[u8]) -> String {
+    match bytes.strip_prefix(&[0xfe, 0xff]) {
+        Some(utf16) => {
+            let units: Vec<u16> = utf16
+                .chunks_exact(2)
+                .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        None => bytes.iter().map(|&b| char::from(b)).collect(),
+    }
+}
+
+/// Renders extracted PDF content as markdown.
+fn render_markdown(content: &PdfContent) -> String {
+    render_pages(content, content.title.as_deref(), 0..content.pages.len())
+}
+
+/// Splits a PDF into chapters at the top-level outline entries.
+///
+/// Returns each chapter's title and page indexes, in page order.
+fn chapters(content: &PdfContent) -> Vec<(String, Range<usize>)> {
+    let mut starts: Vec<(usize, &str)> = Vec::new();
+    for entry in content.outline.iter().filter(|entry| entry.level == 1) {
+        let in_range = (1..=content.pages.len()).contains(&entry.page);
+        if in_range && !starts.iter().any(|&(page, _)| page == entry.page) {
+            starts.push((entry.page, &entry.title));
+        }
+    }
+    starts.sort_by_key(|&(page, _)| page);
+
+    let ends = starts
+        .iter()
+        .skip(1)
+        .map(|&(page, _)| page - 1)
+        .chain([content.pages.len()]);
+    starts
+        .iter()
+        .zip(ends)
+        .enumerate()
+        .map(|(index, (&(page, title), end))| {
+            // Pages before the first chapter, such as a cover, belong to it
+            let start = if index == 0 { 0 } else { page - 1 };
+            (title.to_string(), start..end)
+        })
+        .collect()
+}
+
+/// Renders a range of pages as markdown, under a top-level title if given.
+fn render_pages(content: &PdfContent, title: Option<&str>, pages: Range<usize>) -> String {
+    // The document title is the only top-level heading when there is one
+    let offset = usize::from(title.is_some());
+    let numbered_heading =
+        Regex::new(r"^(\d+(?:\.\d+)*)\.?\s+\p{Lu}").expect("valid numbered heading pattern");
+
+    let mut output = String::new();
+    if let Some(title) = title {
+        output.push_str(&format!("# {title}\n\n"));
+    }
+
+    for (index, text) in content.pages[pages.clone()].iter().enumerate() {
+        let page = pages.start + index + 1;
+        output.push_str(&format!("<a id=\"page-{page}\"></a>\n\n"));
+
+        let mut blocks: Vec<String> = paragraphs(text);
+        if content.outline.is_empty() {
+            for block in &mut blocks {
+                if let Some(caps) = numbered_heading.captures(block) {
+                    if block.len() <= MAX_HEADING_LEN && !block.ends_with('.') {
+                        let depth = caps[1].split('.').count();
+                        *block = heading(depth + offset, block);
+                    }
+                }
+            }
+        } else {
+            // Outline entries replace the paragraph with the same text, or lead the page
+            let mut leading = Vec::new();
+            for entry in content.outline.iter().filter(|entry| entry.page == page) {
+                let level = entry.level + offset;
+                let found = blocks
+                    .iter_mut()
+                    .find(|block| block.eq_ignore_ascii_case(&entry.title));
+                match found {
+                    Some(block) => *block = heading(level, &entry.title),
+                    None => leading.push(heading(level, &entry.title)),
+                }
+            }
+            blocks.splice(0..0, leading);
+        }
+
+        for block in blocks {
+            output.push_str(&block);
+            output.push_str("\n\n");
+        }
+    }
+
+    output.truncate(output.trim_end().len());
+    output.push('\n');
+    output
+}
+
+/// Formats a heading, capping the level at 6.
+fn heading(level: usize, text: &str) -> String {
+    format!("{} {text}", "#".repeat(level.clamp(1, 6)))
+}
+
+/// Splits page text into paragraphs at blank lines, joining the lines of each.
+fn paragraphs(text: &str) -> Vec<String> {
+    let mut paragraphs = Vec::new();
+    let mut current = String::new();
+
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        let hyphenated = current
+            .strip_suffix('-')
+            .filter(|rest| rest.ends_with(|c: char| c.is_alphabetic()))
+            .is_some();
+        if hyphenated && line.starts_with(|c: char| c.is_lowercase()) {
+            // Rejoin a word broken across lines
+            current.pop();
+        } else if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(&collapse_whitespace(line));
+    }
+    if !current.is_empty() {
+        paragraphs.push(current);
+    }
+
+    paragraphs
+}
+
+/// Collapses runs of whitespace to single spaces and trims the ends.
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pdf_extract::{dictionary, Stream};
+
+    /// Builds a PDF with one page per entry in `pages`, each a list of text lines.
+    fn build_pdf(title: Option<&str>, pages: &[&[&str]], outline: &[(&str, usize)]) -> Vec<u8> {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let font_id = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+        });
+        let resources_id = doc.add_object(dictionary! {
+            "Font" => dictionary! { "F1" => font_id },
+        });
+
+        let mut page_ids = Vec::new();
+        for lines in pages {
+            let text: Vec<String> = lines.iter().map(|line| format!("({line}) Tj T*")).collect();
+            let content = format!("BT /F1 12 Tf 72 720 Td 14 TL {} ET", text.join(" "));
+            let content_id = doc.add_object(Stream::new(dictionary! {}, content.into_bytes()));
+            page_ids.push(doc.add_object(dictionary! {
+                "Type" => "Page",
+                "Parent" => pages_id,
+                "Contents" => content_id,
+                "Resources" => resources_id,
+                "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+            }));
+        }
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => page_ids.iter().map(|&id| id.into()).collect::<Vec<Object>>(),
+                "Count" => page_ids.len() as i64,
+            }),
+        );
+
+        let mut catalog = dictionary! { "Type" => "Catalog", "Pages" => pages_id };
+        if !outline.is_empty() {
+            let outlines_id = doc.new_object_id();
+            let item_ids: Vec<_> = outline.iter().map(|_| doc.new_object_id()).collect();
+            for (index, (text, page)) in outline.iter().enumerate() {
+                let mut item = dictionary! {
+                    "Title" => Object::string_literal(*text),
+                    "Parent" => outlines_id,
+                    "Dest" => vec![page_ids[page - 1].into(), "XYZ".into(), Object::Null, Object::Null, Object::Null],
+                };
+                if index > 0 {
+                    item.set("Prev", item_ids[index - 1]);
+                }
+                if index + 1 < item_ids.len() {
+                    item.set("Next", item_ids[index + 1]);
+                }
+                doc.objects.insert(item_ids[index], Object::Dictionary(item));
+            }
+            doc.objects.insert(
+                outlines_id,
+                Object::Dictionary(dictionary! {
+                    "Type" => "Outlines",
+                    "First" => item_ids[0],
+                    "Last" => item_ids[item_ids.len() - 1],
+                    "Count" => item_ids.len() as i64,
+                }),
+            );
+            catalog.set("Outlines", outlines_id);
+        }
+        let catalog_id = doc.add_object(catalog);
+        doc.trailer.set("Root", catalog_id);
+        if let Some(title) = title {
+            let info_id = doc.add_object(dictionary! { "Title" => Object::string_literal(title) });
+            doc.trailer.set("Info", info_id);
+        }
+
+        let mut bytes = Vec::new();
+        doc.save_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    fn converter() -> PdfConverter {
+        let output_config = crate::config::OutputConfig {
+            include_frontmatter: false,
+            ..Default::default()
+        };
+        PdfConverter::with_config(HttpClient::new(), output_config)
+    }
+
+    #[test]
+    fn test_outline_headings_and_page_anchors() {
+        let pdf = build_pdf(
+            Some("User Guide"),
+            &[&["Introduction", "", "Welcome to the guide."], &["Details follow here."]],
+            &[("Introduction", 1), ("Reference", 2)],
+        );
+
+        let markdown = converter().convert_pdf("https://example.com/guide.pdf", &pdf).unwrap();
+        let text = markdown.as_str();
+        assert!(text.starts_with("# User Guide\n\n<a id=\"page-1\"></a>\n\n## Introduction\n"));
+        assert!(text.contains("Welcome to the guide."));
+        assert!(text.contains("<a id=\"page-2\"></a>\n\n## Reference\n\nDetails follow here."));
+    }
+
+    #[test]
+    fn test_parts_split_at_top_level_outline_entries() {
+        let pdf = build_pdf(
+            Some("Handbook"),
+            &[
+                &["Cover"],
+                &["Setup", "", "Install it."],
+                &["Usage", "", "Run it."],
+                &["More usage."],
+            ],
+            &[("Setup", 2), ("Usage", 3)],
+        );
+
+        let parts = converter()
+            .convert_pdf_parts("https://example.com/handbook.pdf", &pdf)
+            .unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].index, 1);
+        assert_eq!(parts[0].title.as_deref(), Some("Setup"));
+        assert_eq!(
+            parts[0].markdown.as_str(),
+            "<a id=\"page-1\"></a>\n\nCover\n\n<a id=\"page-2\"></a>\n\n# Setup\n\nInstall it.\n"
+        );
+        assert_eq!(parts[1].index, 2);
+        assert_eq!(parts[1].title.as_deref(), Some("Usage"));
+        let second = parts[1].markdown.as_str();
+        assert!(second.starts_with("<a id=\"page-3\"></a>\n\n# Usage\n"));
+        assert!(second.contains("<a id=\"page-4\"></a>\n\nMore usage."));
+    }
+
+    #[test]
+    fn test_parts_without_chapters_are_one_document() {
+        let pdf = build_pdf(Some("Memo"), &[&["One"], &["Two"]], &[("One", 1)]);
+
+        let parts = PdfConverter::new()
+            .convert_pdf_parts("https://example.com/memo.pdf", &pdf)
+            .unwrap();
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].title.as_deref(), Some("Memo"));
+        assert!(parts[0].markdown.as_str().contains("# Memo"));
+        assert!(parts[0].markdown.as_str().contains("page_count"));
+    }
+
+    #[test]
+    fn test_numbered_lines_become_headings_without_outline() {
+        let pdf = build_pdf(None, &[&["2.1 Installation", "", "Run the installer."]], &[]);
+
+        let markdown = converter().convert_pdf("https://example.com/a.pdf", &pdf).unwrap();
+        assert!(markdown.as_str().contains("## 2.1 Installation\n\nRun the installer."));
+    }
+
+    #[test]
+    fn test_frontmatter_records_page_count() {
+        let pdf = build_pdf(Some("Report"), &[&["One"], &["Two"]], &[]);
+
+        let markdown = PdfConverter::new()
+            .convert_pdf("https://example.com/report.pdf", &pdf)
+            .unwrap();
+        let text = markdown.as_str();
+        assert!(text.contains("conversion_type: pdf"));
+        assert!(text.contains("page_count: '2'") || text.contains("page_count: \"2\""));
+        assert!(text.contains("title: Report"));
+    }
+
+    #[test]
+    fn test_rejects_non_pdf_and_empty_documents() {
+        let error = converter()
+            .convert_pdf("https://example.com/a.pdf", b"<html></html>")
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            MarkdownError::ContentError {
+                kind: ContentErrorKind::UnsupportedFormat,
+                ..
+            }
+        ));
+        assert!(error.is_recoverable());
+
+        let blank = build_pdf(None, &[&[]], &[]);
+        let error = converter().convert_pdf("https://example.com/a.pdf", &blank).unwrap_err();
+        assert!(matches!(
+            error,
+            MarkdownError::ContentError {
+                kind: ContentErrorKind::EmptyContent,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_paragraphs_join_lines_and_hyphenated_words() {
+        assert_eq!(
+            paragraphs("The conver-\nsion keeps\nlines.\n\nCo-\nOperative"),
+            vec!["The conversion keeps lines.", "Co- Operative"]
+        );
+    }
+}
@@ -0,0 +1,451 @@
+//! Rendering of plain text documents as structured markdown.
+//!
+//! A `text/plain` response has no markup, so as markdown it comes out as
+//! one run of paragraphs. Its structure is guessed from the layout instead:
+//! underlined titles and short numbered lines become headings, bullet
+//! characters become list items, and formatted man pages have their section
+//! names turned into headings. Fenced code blocks are left alone, as
+//! markdown files are often served as plain text. Each heuristic can be
+//! turned off through [`PlainTextHeuristics`].
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+use super::config::PlainTextHeuristics;
+
+/// Longest line treated as a heading, in characters.
+const MAX_HEADING_LEN: usize = 80;
+
+/// Shortest underline or rule, in characters.
+const MIN_RULE_LEN: usize = 3;
+
+/// Renders plain text as markdown, using the heuristics that are turned on.
+///
+/// # Arguments
+///
+/// * `text` - The plain text document
+/// * `heuristics` - Which structures to look for
+///
+/// # Examples
+///
+/// ```rust
+/// use markdowndown::converters::plaintext;
+/// use markdowndown::converters::PlainTextHeuristics;
+///
+/// let text = "Release Notes\n=============\n\n1. Changes\n\n• Faster startup\n• Smaller binary\n";
+/// assert_eq!(
+///     plaintext::render(text, &PlainTextHeuristics::default()),
+///     "# Release Notes\n\n## 1. Changes\n\n- Faster startup\n- Smaller binary\n"
+/// );
+/// assert_eq!(plaintext::render(text, &PlainTextHeuristics::none()), text);
+/// ```
+pub fn render(text: &str, heuristics: &PlainTextHeuristics) -> String {
+    if *heuristics == PlainTextHeuristics::none() {
+        return text.to_string();
+    }
+    let text = text.replace("\r\n", "\n");
+    let lines = if heuristics.man_pages {
+        let text = strip_overstrike(&text);
+        let lines: Vec<&str> = text.lines().collect();
+        if is_man_page(&lines) {
+            return finish(render_man_page(&lines));
+        }
+        render_lines(&lines, heuristics)
+    } else {
+        render_lines(&text.lines().collect::<Vec<_>>(), heuristics)
+    };
+    finish(lines)
+}
+
+/// Joins rendered lines, dropping trailing whitespace and repeated blank lines.
+fn finish(lines: Vec<String>) -> String {
+    let mut kept: Vec<&str> = Vec::with_capacity(lines.len());
+    for line in lines.iter().map(|line| line.trim_end()) {
+        if !(line.is_empty() && kept.last().is_some_and(|last| last.is_empty())) {
+            kept.push(line);
+        }
+    }
+    let text = kept.join("\n");
+    let text = text.trim_matches('\n');
+    if text.is_empty() {
+        String::new()
+    } else {
+        format!("{text}\n")
+    }
+}
+
+/// Renders the lines of an ordinary document.
+fn render_lines(lines: &[&str], heuristics: &PlainTextHeuristics) -> Vec<String> {
+    let blank = |index: usize| lines.get(index).is_none_or(|line| line.trim().is_empty());
+    let mut out = Vec::new();
+    // Indents of the bullets enclosing the current line, outermost first
+    let mut list: Vec<usize> = Vec::new();
+    let mut in_fence = false;
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        let trimmed = line.trim();
+        let starts_block = index == 0 || blank(index - 1);
+
+        // Markdown served as plain text keeps its code blocks as they are
+        if trimmed.starts_with("```") {
+            in_fence = !in_fence;
+            list.clear();
+        }
+        if in_fence || trimmed.starts_with("```") || trimmed.is_empty() {
+            out.push(line.to_string());
+            index += 1;
+            continue;
+        }
+
+        if heuristics.underlined_headings && starts_block {
+            // A title between two matching rules
+            if let (Some(rule), Some(title), Some(under)) = (
+                rule_char(trimmed),
+                lines.get(index + 1),
+                lines.get(index + 2),
+            ) {
+                if rule_char(under.trim()) == Some(rule) && is_heading_text(title.trim()) {
+                    push_heading(&mut out, rule_level(rule), title.trim());
+                    list.clear();
+                    index += 3;
+                    continue;
+                }
+            }
+            if let Some(under) = lines.get(index + 1) {
+                let under = under.trim();
+                if let Some(rule) = rule_char(under) {
+                    let long_enough = under.chars().count() * 3 >= trimmed.chars().count() * 2;
+                    if rule_char(trimmed).is_none() && is_heading_text(trimmed) && long_enough {
+                        push_heading(&mut out, rule_level(rule), trimmed);
+                        list.clear();
+                        index += 2;
+                        continue;
+                    }
+                }
+            }
+            if rule_char(trimmed).is_some() && blank(index + 1) {
+                out.push("---".to_string());
+                list.clear();
+                index += 1;
+                continue;
+            }
+        }
+
+        if heuristics.numbered_sections && starts_block && blank(index + 1) {
+            if let Some((level, heading)) = numbered_section(trimmed) {
+                push_heading(&mut out, level, &heading);
+                list.clear();
+                index += 1;
+                continue;
+            }
+        }
+
+        if heuristics.bullets {
+            if let Some((indent, item)) = bullet(line) {
+                while list.last().is_some_and(|&outer| outer >= indent) {
+                    list.pop();
+                }
+                out.push(format!("{}- {item}", "  ".repeat(list.len())));
+                list.push(indent);
+                index += 1;
+                continue;
+            }
+            if !list.is_empty() && !starts_block {
+                // Wrapped text of the item above
+                out.push(format!("{}{trimmed}", "  ".repeat(list.len())));
+                index += 1;
+                continue;
+            }
+            list.clear();
+        }
+
+        out.push(line.to_string());
+        index += 1;
+    }
+    out
+}
+
+/// Adds an ATX heading, separated from the lines around it by blank lines.
+fn push_heading(out: &mut Vec<String>, level: usize, text: &str) {
+    if out.last().is_some_and(|line| !line.is_empty()) {
+        out.push(String::new());
+    }
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    out.push(format!("{} {text}", "#".repeat(level.clamp(1, 6))));
+    out.push(String::new());
+}
+
+/// Returns the character of a line made of one repeated `=`, `-`, `~`, `*`, or `_`.
+fn rule_char(line: &str) -> Option<char> {
+    let first = line.chars().next()?;
+    (matches!(first, '=' | '-' | '~' | '*' | '_')
+        && line.chars().count() >= MIN_RULE_LEN
+        && line.chars().all(|c| c == first))
+    .then_some(first)
+}
+
+/// The heading level an underline character stands for.
+fn rule_level(rule: char) -> usize {
+    match rule {
+        '=' => 1,
+        '-' => 2,
+        _ => 3,
+    }
+}
+
+/// Whether a line is short enough, and not punctuated like prose, to be a heading.
+fn is_heading_text(line: &str) -> bool {
+    !line.is_empty()
+        && line.chars().count() <= MAX_HEADING_LEN
+        && !line.ends_with(['.', ',', ';'])
+        && bullet(line).is_none()
+}
+
+/// Parses a section heading such as `2.`, `2.1`, or `2.1.3. Scope`.
+///
+/// Returns the heading level, one deeper than the number of parts, and the
+/// heading text. Numbers without a dot, such as a year, are not sections.
+fn numbered_section(line: &str) -> Option<(usize, String)> {
+    static SECTION: OnceLock<Regex> = OnceLock::new();
+    let section = SECTION.get_or_init(|| {
+        Regex::new(r"^(\d{1,2}(?:\.\d{1,2})*)(\.?)\s+(\p{Lu}.*)$").expect("valid section pattern")
+    });
+    let captures = section.captures(line)?;
+    let number = &captures[1];
+    let parts = number.split('.').count();
+    let title = &captures[3];
+    if (parts == 1 && captures[2].is_empty())
+        || !is_heading_text(line)
+        || title.ends_with(':')
+        || title.split_whitespace().count() > 10
+    {
+        return None;
+    }
+    Some((parts + 1, format!("{number}{} {title}", &captures[2])))
+}
+
+/// Parses a bullet line, returning its indent and the item text.
+fn bullet(line: &str) -> Option<(usize, &str)> {
+    static BULLET: OnceLock<Regex> = OnceLock::new();
+    let bullet = BULLET.get_or_init(|| {
+        Regex::new(r"^(\s*)[•◦▪▫‣⁃∙●○■□*+o-]\s+(\S.*)$").expect("valid bullet pattern")
+    });
+    let captures = bullet.captures(line)?;
+    let indent = captures[1].chars().count();
+    Some((indent, captures.get(2)?.as_str().trim_end()))
+}
+
+/// Removes the backspace overstriking `man` uses for bold and underlined text.
+fn strip_overstrike(text: &str) -> String {
+    if !text.contains('\u{8}') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c == '\u{8}' {
+            out.pop();
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Whether a line is a man page section name, such as `NAME` or `SEE ALSO`.
+fn is_man_section(line: &str) -> bool {
+    line.starts_with(|c: char| c.is_ascii_uppercase())
+        && line.trim_end().chars().count() <= 40
+        && !line.chars().any(char::is_lowercase)
+}
+
+/// Whether the lines are a formatted man page, with `NAME` followed by
+/// `SYNOPSIS` or `DESCRIPTION` sections.
+fn is_man_page(lines: &[&str]) -> bool {
+    let mut sections = lines
+        .iter()
+        .map(|line| line.trim_end())
+        .filter(|line| is_man_section(line));
+    sections.any(|line| line == "NAME")
+        && sections.any(|line| line == "SYNOPSIS" || line == "DESCRIPTION")
+}
+
+/// Returns the `name(section)` a man page header or footer line ends with.
+fn man_title(line: &str) -> Option<&str> {
+    static TITLE: OnceLock<Regex> = OnceLock::new();
+    let title = TITLE.get_or_init(|| {
+        Regex::new(r"(\S+\([0-9][0-9a-zA-Z]*\))\s*$").expect("valid man title pattern")
+    });
+    Some(title.captures(line)?.get(1)?.as_str())
+}
+
+/// Renders a formatted man page.
+///
+/// The running header becomes the title, the footer is dropped, section
+/// names become headings, and the indented body is moved to the left so it
+/// is not read as code. Option lines starting with `-` become list items,
+/// with the more deeply indented description below them as the item text.
+fn render_man_page(lines: &[&str]) -> Vec<String> {
+    let mut out = Vec::new();
+    let first = lines.iter().position(|line| !line.trim().is_empty());
+    let last = lines.iter().rposition(|line| !line.trim().is_empty());
+    let (Some(mut start), Some(mut end)) = (first, last) else {
+        return out;
+    };
+    if let Some(title) = man_title(lines[start]).filter(|_| !is_man_section(lines[start])) {
+        out.push(format!("# {title}"));
+        if end > start && man_title(lines[end]) == Some(title) {
+            end -= 1;
+        }
+        start += 1;
+    }
+
+    let mut base = 0;
+    let mut in_option = false;
+    for (index, line) in lines.iter().enumerate().take(end + 1).skip(start) {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            out.push(String::new());
+            in_option = false;
+            continue;
+        }
+        if is_man_section(line) {
+            push_heading(&mut out, 2, trimmed);
+            // The section body is indented the same throughout
+            base = lines[index + 1..=end]
+                .iter()
+                .find(|line| !line.trim().is_empty())
+                .map_or(0, |line| line.len() - line.trim_start().len());
+            in_option = false;
+            continue;
+        }
+
+        let indent = line.len() - line.trim_start().len();
+        if indent <= base && trimmed.starts_with('-') {
+            // `-a, --all   description` keeps its description on the same line
+            let (option, description) = match trimmed.find("  ") {
+                Some(at) => (&trimmed[..at], trimmed[at..].trim()),
+                None => (trimmed, ""),
+            };
+            let item = format!("- `{option}`");
+            out.push(if description.is_empty() {
+                item
+            } else {
+                format!("{item}: {description}")
+            });
+            in_option = true;
+        } else if in_option && indent > base {
+            out.push(format!("  {trimmed}"));
+        } else {
+            in_option = false;
+            out.push(line.get(base.min(indent)..).unwrap_or(trimmed).to_string());
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_document_structure() {
+        let text = "\
+==========
+User Guide
+==========
+
+Overview
+--------
+This tool converts
+documents.
+
+1.2 Getting Started
+
+Requirements:
+  * a terminal
+    with a shell
+      o bash or zsh
+  * network access
+
+2024 was a good year.
+
+----
+
+Setup Steps
+~~~~~~~~~~~
+";
+        assert_eq!(
+            render(text, &PlainTextHeuristics::default()),
+            "# User Guide\n\n## Overview\n\nThis tool converts\ndocuments.\n\n\
+             ### 1.2 Getting Started\n\nRequirements:\n- a terminal\n  with a shell\n  \
+             - bash or zsh\n- network access\n\n2024 was a good year.\n\n---\n\n### Setup Steps\n"
+        );
+
+        // Each heuristic can be turned off on its own
+        let heuristics = PlainTextHeuristics {
+            numbered_sections: false,
+            bullets: false,
+            ..Default::default()
+        };
+        let rendered = render(text, &heuristics);
+        assert!(rendered.contains("\n1.2 Getting Started\n"));
+        assert!(rendered.contains("\n  * a terminal\n"));
+        assert!(rendered.starts_with("# User Guide\n"));
+    }
+
+    #[test]
+    fn test_numbered_sections() {
+        assert_eq!(
+            numbered_section("3. Results"),
+            Some((2, "3. Results".to_string()))
+        );
+        assert_eq!(
+            numbered_section("2.1.4 Error   Handling"),
+            Some((4, "2.1.4 Error   Handling".to_string()))
+        );
+        assert_eq!(numbered_section("1999 Annual Report"), None);
+        assert_eq!(numbered_section("1. Buy milk and eggs."), None);
+        assert_eq!(numbered_section("2. lowercase start"), None);
+    }
+
+    #[test]
+    fn test_render_man_page() {
+        let text = "\
+LS(1)                       User Commands                      LS(1)
+
+N\u{8}NA\u{8}AM\u{8}ME\u{8}E
+       ls - list directory contents
+
+SYNOPSIS
+       ls [OPTION]... [FILE]...
+
+DESCRIPTION
+       List information about the FILEs.
+
+       -a, --all
+              do not ignore entries starting with .
+
+       -l     use a long listing format
+
+SEE ALSO
+       dir(1)
+
+GNU coreutils 9.1              September 2022                  LS(1)
+";
+        assert_eq!(
+            render(text, &PlainTextHeuristics::default()),
+            "# LS(1)\n\n## NAME\n\nls - list directory contents\n\n## SYNOPSIS\n\n\
+             ls [OPTION]... [FILE]...\n\n## DESCRIPTION\n\nList information about the FILEs.\n\n\
+             - `-a, --all`\n  do not ignore entries starting with .\n\n\
+             - `-l`: use a long listing format\n\n## SEE ALSO\n\ndir(1)\n"
+        );
+
+        // Without man page recognition the indented body stays as it is
+        let heuristics = PlainTextHeuristics {
+            man_pages: false,
+            ..Default::default()
+        };
+        assert!(render(text, &heuristics).contains("\n       ls [OPTION]... [FILE]...\n"));
+    }
+}
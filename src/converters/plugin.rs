@@ -0,0 +1,164 @@
+//! Converters registered by plugin crates at link time.
+//!
+//! A crate that provides a converter registers it with
+//! [`register_converter!`](crate::register_converter), together with the
+//! detection patterns that route URLs to it. Every [`crate::MarkdownDown`]
+//! created afterwards installs the registered converters, so binaries only
+//! need to link the plugin crate, not wire it into a registry by hand.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use async_trait::async_trait;
+//! use markdowndown::converters::Converter;
+//! use markdowndown::types::{Markdown, MarkdownError, UrlType};
+//! use markdowndown::{register_converter, MarkdownDown};
+//!
+//! struct WikiConverter;
+//!
+//! #[async_trait]
+//! impl Converter for WikiConverter {
+//!     async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+//!         Markdown::new(format!("# Wiki page\n\nFetched from {url}"))
+//!     }
+//!
+//!     fn name(&self) -> &'static str {
+//!         "Wiki"
+//!     }
+//! }
+//!
+//! register_converter! {
+//!     url_type: UrlType::Custom("wiki".into()),
+//!     converter: WikiConverter,
+//!     patterns: [("wiki.example.com", None)],
+//! }
+//!
+//! let md = MarkdownDown::new();
+//! assert_eq!(
+//!     md.detector().detect_type("https://wiki.example.com/Home").unwrap(),
+//!     UrlType::Custom("wiki".into())
+//! );
+//! assert_eq!(
+//!     md.registry().get_converter(&UrlType::Custom("wiki".into())).map(|c| c.name()),
+//!     Some("Wiki")
+//! );
+//! ```
+
+use super::{Converter, ConverterRegistry};
+use crate::config::Config;
+use crate::detection::UrlDetector;
+use crate::types::UrlType;
+use tracing::debug;
+
+/// A converter registered with [`register_converter!`](crate::register_converter).
+///
+/// Plugins are collected at link time, so their fields are functions that
+/// build the URL type and converter when a [`crate::MarkdownDown`] is created.
+pub struct ConverterPlugin {
+    /// Builds the URL type the converter handles
+    pub url_type: fn() -> UrlType,
+    /// Builds the converter from the configuration of the instance installing it
+    pub converter: fn(&Config) -> Box<dyn Converter>,
+    /// Domain and optional path patterns detected as the plugin's URL type
+    pub patterns: &'static [(&'static str, Option<&'static str>)],
+}
+
+inventory::collect!(ConverterPlugin);
+
+/// Returns every converter plugin linked into the binary.
+pub fn plugins() -> impl Iterator<Item = &'static ConverterPlugin> {
+    inventory::iter::<ConverterPlugin>.into_iter()
+}
+
+/// Installs every linked plugin's converter and detection patterns.
+///
+/// Plugin converters replace built-in converters for the same URL type.
+pub(crate) fn install_plugins(
+    config: &Config,
+    registry: &mut ConverterRegistry,
+    detector: &mut UrlDetector,
+) {
+    for plugin in plugins() {
+        let url_type = (plugin.url_type)();
+        let converter = (plugin.converter)(config);
+        debug!(
+            "Installing plugin converter {} for {}",
+            converter.name(),
+            url_type
+        );
+        for (domain, path) in plugin.patterns {
+            detector.add_pattern(domain, *path, url_type.clone());
+        }
+        registry.register(url_type, converter);
+    }
+}
+
+/// Registers a converter, and the URL patterns routed to it, at link time.
+///
+/// Every [`MarkdownDown`](crate::MarkdownDown) created in a binary that links
+/// the registering crate installs the converter. `converter` is either an
+/// expression building the converter, or a closure taking the instance's
+/// [`Config`](crate::Config). `patterns` lists `(domain, path)` detection
+/// patterns as accepted by
+/// [`UrlDetector::add_pattern`](crate::detection::UrlDetector::add_pattern),
+/// and may be left out.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_trait::async_trait;
+/// use markdowndown::converters::Converter;
+/// use markdowndown::types::{Markdown, MarkdownError, UrlType};
+/// use markdowndown::register_converter;
+/// use std::time::Duration;
+///
+/// struct ConfluenceConverter {
+///     timeout: Duration,
+/// }
+///
+/// #[async_trait]
+/// impl Converter for ConfluenceConverter {
+///     async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+///         Markdown::new(format!("Fetched {url} within {:?}", self.timeout))
+///     }
+///
+///     fn name(&self) -> &'static str {
+///         "Confluence"
+///     }
+/// }
+///
+/// register_converter! {
+///     url_type: UrlType::Custom("confluence".into()),
+///     converter: |config| ConfluenceConverter { timeout: config.http.timeout },
+///     patterns: [("*.atlassian.net", Some("/wiki/"))],
+/// }
+/// ```
+#[macro_export]
+macro_rules! register_converter {
+    (
+        url_type: $url_type:expr,
+        converter: |$config:ident| $converter:expr
+        $(, patterns: [$(($domain:expr, $path:expr)),* $(,)?])? $(,)?
+    ) => {
+        $crate::inventory::submit! {
+            $crate::converters::plugin::ConverterPlugin {
+                url_type: || $url_type,
+                converter: |$config: &$crate::Config| -> ::std::boxed::Box<dyn $crate::Converter> {
+                    ::std::boxed::Box::new($converter)
+                },
+                patterns: &[$($(($domain, $path)),*)?],
+            }
+        }
+    };
+    (
+        url_type: $url_type:expr,
+        converter: $converter:expr
+        $(, patterns: [$(($domain:expr, $path:expr)),* $(,)?])? $(,)?
+    ) => {
+        $crate::register_converter! {
+            url_type: $url_type,
+            converter: |_config| $converter
+            $(, patterns: [$(($domain, $path)),*])?
+        }
+    };
+}
@@ -0,0 +1,126 @@
+//! Soft-404 detection for pages that return 200 but render a "not found" template.
+//! Signals come from the page title and first heading; a short body is required
+//! so that articles *about* 404 errors are not misclassified.
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Pages with more words than this are treated as real content.
+const MAX_SOFT_404_WORDS: usize = 500;
+
+/// Phrases that mark a "not found" template when they appear in a title or heading.
+const NOT_FOUND_PATTERN: &str = r"(?i)^\s*(?:error\s*)?404\b|\b404\b.*\bnot\s+found\b|\b(?:page|file|document|resource|article|post)\s+(?:was\s+)?not\s+found\b|^\s*not\s+found\b|\bpage\s+(?:does\s+not|doesn't|doesn’t)\s+exist\b|\bpage\s+(?:cannot|can't|can’t|could\s+not|couldn't)\s+be\s+found\b|\bpage\s+(?:is\s+)?no\s+longer\s+(?:exists|available)\b";
+
+/// Detects soft-404 pages from their HTML and converted markdown.
+pub struct Soft404Detector {
+    not_found: Regex,
+    title: Regex,
+    heading: Regex,
+    tags: Regex,
+}
+
+impl Soft404Detector {
+    /// Creates a new soft-404 detector.
+    pub fn new() -> Self {
+        Self {
+            not_found: Regex::new(NOT_FOUND_PATTERN).expect("valid soft-404 pattern"),
+            title: Regex::new(r"(?is)<title[^>]*>(.*?)</title>").expect("valid title pattern"),
+            heading: Regex::new(r"(?is)<h1[^>]*>(.*?)</h1>").expect("valid heading pattern"),
+            tags: Regex::new(r"<[^>]+>").expect("valid tag pattern"),
+        }
+    }
+
+    /// Returns a detector shared by all conversions, compiled on first use.
+    pub fn shared() -> &'static Self {
+        static DETECTOR: OnceLock<Soft404Detector> = OnceLock::new();
+        DETECTOR.get_or_init(Self::new)
+    }
+
+    /// Checks whether a page looks like a "not found" template.
+    ///
+    /// # Arguments
+    ///
+    /// * `html` - The raw HTML of the page
+    /// * `markdown` - The converted markdown, used to measure how much content the page has
+    ///
+    /// # Returns
+    ///
+    /// A short description of the matching signal, or `None` if the page looks genuine.
+    pub fn detect(&self, html: &str, markdown: &str) -> Option<String> {
+        if markdown.split_whitespace().count() > MAX_SOFT_404_WORDS {
+            return None;
+        }
+
+        if let Some(title) = self.first_match_text(&self.title, html) {
+            if self.not_found.is_match(&title) {
+                return Some(format!("title '{title}' matches a not-found page"));
+            }
+        }
+
+        if let Some(heading) = self.first_match_text(&self.heading, html) {
+            if self.not_found.is_match(&heading) {
+                return Some(format!("heading '{heading}' matches a not-found page"));
+            }
+        }
+
+        None
+    }
+
+    /// Returns the tag-stripped, whitespace-collapsed text of the first match.
+    fn first_match_text(&self, pattern: &Regex, html: &str) -> Option<String> {
+        let inner = pattern.captures(html)?.get(1)?.as_str();
+        let text = self.tags.replace_all(inner, " ");
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        (!text.is_empty()).then_some(text)
+    }
+}
+
+impl Default for Soft404Detector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_not_found_title() {
+        let detector = Soft404Detector::new();
+        let html = "<html><head><title>Page Not Found | Example</title></head><body><p>Sorry!</p></body></html>";
+
+        let signal = detector.detect(html, "Sorry!");
+        assert!(signal.unwrap().contains("Page Not Found"));
+    }
+
+    #[test]
+    fn test_detects_not_found_heading() {
+        let detector = Soft404Detector::new();
+        let html = "<title>Example Site</title><h1><span>404</span> - Oops</h1><p>We looked everywhere.</p>";
+
+        assert!(detector
+            .detect(html, "# 404 - Oops\n\nWe looked everywhere.")
+            .is_some());
+        let html = "<title>Example</title><h1>This page doesn't exist</h1>";
+        assert!(detector.detect(html, "# This page doesn't exist").is_some());
+    }
+
+    #[test]
+    fn test_ignores_regular_pages() {
+        let detector = Soft404Detector::new();
+        let html = "<title>Release Notes</title><h1>Version 4.0.4</h1><p>Bug fixes.</p>";
+
+        assert!(detector.detect(html, "# Version 4.0.4\n\nBug fixes.").is_none());
+        assert!(detector.detect("<p>No title at all</p>", "No title at all").is_none());
+    }
+
+    #[test]
+    fn test_ignores_long_articles_about_404s() {
+        let detector = Soft404Detector::new();
+        let html = "<title>404 Not Found: what it means</title><h1>Fixing 404 errors</h1>";
+        let markdown = "word ".repeat(MAX_SOFT_404_WORDS + 1);
+
+        assert!(detector.detect(html, &markdown).is_none());
+    }
+}
@@ -0,0 +1,210 @@
+//! Incremental splitting of HTML for streaming conversion.
+//!
+//! Large pages are converted a segment at a time so the whole document never
+//! has to be held in memory. Segments are cut only after the end tag of a
+//! block element, and never inside a `<pre>`, list, table, or other element
+//! whose markdown depends on seeing it whole. The first segment always
+//! contains the complete `<head>`, so page metadata can be read from it.
+
+use regex::Regex;
+
+/// Default minimum size of a segment in bytes.
+pub const DEFAULT_MIN_SEGMENT_LEN: usize = 16 * 1024;
+
+/// Block elements after whose end tag a segment may be cut.
+const BREAK_ELEMENTS: [&str; 14] = [
+    "p", "h1", "h2", "h3", "h4", "h5", "h6", "div", "section", "article", "pre", "table", "ul",
+    "ol",
+];
+
+/// Elements that must not be split across segments.
+const UNSPLITTABLE_ELEMENTS: [&str; 10] = [
+    "pre",
+    "table",
+    "ul",
+    "ol",
+    "dl",
+    "blockquote",
+    "figure",
+    "script",
+    "style",
+    "textarea",
+];
+
+/// Splits a stream of HTML bytes into independently convertible segments.
+pub struct HtmlChunker {
+    min_segment_len: usize,
+    tag: Regex,
+    /// Decoded HTML not yet returned in a segment
+    buffer: String,
+    /// Trailing bytes of an incomplete UTF-8 sequence
+    partial: Vec<u8>,
+    /// Offset in `buffer` up to which tags have been scanned
+    scanned: usize,
+    /// Number of open unsplittable elements at `scanned`
+    depth: usize,
+    /// Whether the end of the `<head>` has been scanned
+    head_done: bool,
+}
+
+impl HtmlChunker {
+    /// Creates a chunker that returns segments of at least `min_segment_len` bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_segment_len` - Minimum segment size; the last segment may be shorter
+    pub fn new(min_segment_len: usize) -> Self {
+        Self {
+            min_segment_len,
+            tag: Regex::new(r"<(/?)([a-zA-Z][a-zA-Z0-9]*)[^<>]*>").expect("valid tag pattern"),
+            buffer: String::new(),
+            partial: Vec::new(),
+            scanned: 0,
+            depth: 0,
+            head_done: false,
+        }
+    }
+
+    /// Adds bytes to the chunker and returns any segments that are complete.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The next bytes of the UTF-8 encoded document
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.partial.extend_from_slice(bytes);
+        match std::str::from_utf8(&self.partial) {
+            Ok(text) => {
+                self.buffer.push_str(text);
+                self.partial.clear();
+            }
+            Err(e) if e.error_len().is_none() => {
+                // Keep an incomplete character for the next push
+                let valid = e.valid_up_to();
+                let text = std::str::from_utf8(&self.partial[..valid]).expect("valid prefix");
+                self.buffer.push_str(text);
+                self.partial.drain(..valid);
+            }
+            Err(_) => {
+                self.buffer
+                    .push_str(&String::from_utf8_lossy(&self.partial));
+                self.partial.clear();
+            }
+        }
+
+        let mut segments = Vec::new();
+        while let Some(end) = self.next_break() {
+            segments.push(self.buffer.drain(..end).collect());
+            self.scanned = 0;
+        }
+        segments
+    }
+
+    /// Returns the rest of the document once all bytes have been pushed.
+    pub fn finish(&mut self) -> Option<String> {
+        if !self.partial.is_empty() {
+            self.buffer
+                .push_str(&String::from_utf8_lossy(&self.partial));
+            self.partial.clear();
+        }
+        self.scanned = 0;
+        let rest = std::mem::take(&mut self.buffer);
+        (!rest.trim().is_empty()).then_some(rest)
+    }
+
+    /// Scans new tags in the buffer and returns the end of the first safe cut point.
+    fn next_break(&mut self) -> Option<usize> {
+        while let Some(caps) = self.tag.captures(&self.buffer[self.scanned..]) {
+            let whole = caps.get(0).expect("match has a whole group");
+            let end = self.scanned + whole.end();
+            let closing = !caps[1].is_empty();
+            let name = caps[2].to_ascii_lowercase();
+            self.scanned = end;
+
+            if UNSPLITTABLE_ELEMENTS.contains(&name.as_str()) && !whole.as_str().ends_with("/>") {
+                if closing {
+                    self.depth = self.depth.saturating_sub(1);
+                } else {
+                    self.depth += 1;
+                }
+            }
+            if (name == "head" && closing) || (name == "body" && !closing) {
+                self.head_done = true;
+            }
+
+            if closing
+                && self.head_done
+                && self.depth == 0
+                && end >= self.min_segment_len
+                && BREAK_ELEMENTS.contains(&name.as_str())
+            {
+                return Some(end);
+            }
+        }
+        None
+    }
+}
+
+impl Default for HtmlChunker {
+    fn default() -> Self {
+        Self::new(DEFAULT_MIN_SEGMENT_LEN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segments_break_after_block_elements() {
+        let mut chunker = HtmlChunker::new(10);
+        let html = "<html><head><title>T</title></head><body><p>First paragraph</p><p>Second paragraph</p></body></html>";
+
+        let segments = chunker.push(html.as_bytes());
+        assert_eq!(
+            segments,
+            vec![
+                "<html><head><title>T</title></head><body><p>First paragraph</p>",
+                "<p>Second paragraph</p>"
+            ]
+        );
+        assert_eq!(chunker.finish(), Some("</body></html>".to_string()));
+    }
+
+    #[test]
+    fn test_unsplittable_elements_stay_whole() {
+        let mut chunker = HtmlChunker::new(1);
+        let html = "<body><ul><li><p>a</p></li><li><p>b</p></li></ul><pre><p>x</p></pre>";
+
+        let segments = chunker.push(html.as_bytes());
+        assert_eq!(
+            segments,
+            vec![
+                "<body><ul><li><p>a</p></li><li><p>b</p></li></ul>",
+                "<pre><p>x</p></pre>"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_tags_and_characters_across_pushes() {
+        let mut chunker = HtmlChunker::new(1);
+        let html = "<body><p>caf\u{e9}</p><p>end</p>".as_bytes();
+        let mut segments = Vec::new();
+
+        // Feed one byte at a time, splitting tags and the two-byte 'é'
+        for byte in html {
+            segments.extend(chunker.push(std::slice::from_ref(byte)));
+        }
+
+        assert_eq!(segments, vec!["<body><p>caf\u{e9}</p>", "<p>end</p>"]);
+        assert_eq!(chunker.finish(), None);
+    }
+
+    #[test]
+    fn test_no_segment_before_head_ends() {
+        let mut chunker = HtmlChunker::new(1);
+
+        assert!(chunker.push(b"<html><head><div>x</div>").is_empty());
+        assert_eq!(chunker.push(b"</head><p>y</p>").len(), 1);
+    }
+}
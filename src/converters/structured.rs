@@ -0,0 +1,313 @@
+//! Rendering of JSON and XML responses as markdown.
+//!
+//! API URLs pasted in place of a page return JSON or XML, which the HTML
+//! pipeline would flatten into a run of text. These payloads are instead
+//! pretty-printed into a fenced code block, and the scalar values at the top
+//! level of the document, such as an API object's `id` and `name`, are
+//! copied into the frontmatter.
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+use super::docx::decode_xml_entities;
+
+/// Most payload fields copied into the frontmatter.
+const MAX_FIELDS: usize = 20;
+
+/// Longest value copied into the frontmatter, in characters.
+const MAX_FIELD_LEN: usize = 200;
+
+/// Frontmatter keys written by the builder itself, which payload fields may not replace.
+const RESERVED_FIELDS: [&str; 6] = [
+    "source_url",
+    "exporter",
+    "date_downloaded",
+    "converted_at",
+    "conversion_type",
+    "url",
+];
+
+/// A structured data format rendered as a code block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructuredFormat {
+    /// `application/json` and `+json` media types
+    Json,
+    /// `application/xml`, `text/xml`, and `+xml` media types other than XHTML and SVG
+    Xml,
+}
+
+/// A rendered payload: the code block and the fields for the frontmatter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructuredDocument {
+    /// The pretty-printed payload in a fenced code block
+    pub body: String,
+    /// Top-level scalar values, in key order for JSON and document order for XML
+    pub fields: Vec<(String, String)>,
+}
+
+impl StructuredFormat {
+    /// Chooses the format for a media type, if it is JSON or XML.
+    ///
+    /// # Arguments
+    ///
+    /// * `media_type` - The media type, lowercased, without parameters
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::converters::structured::StructuredFormat;
+    ///
+    /// assert_eq!(
+    ///     StructuredFormat::from_media_type("application/vnd.github+json"),
+    ///     Some(StructuredFormat::Json)
+    /// );
+    /// assert_eq!(StructuredFormat::from_media_type("text/xml"), Some(StructuredFormat::Xml));
+    /// assert_eq!(StructuredFormat::from_media_type("application/xhtml+xml"), None);
+    /// ```
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        match media_type {
+            "application/json" => Some(StructuredFormat::Json),
+            media_type if media_type.ends_with("+json") => Some(StructuredFormat::Json),
+            "application/xhtml+xml" | "image/svg+xml" => None,
+            "application/xml" | "text/xml" => Some(StructuredFormat::Xml),
+            media_type if media_type.ends_with("+xml") => Some(StructuredFormat::Xml),
+            _ => None,
+        }
+    }
+
+    /// The name used for the code block language and the `conversion_type` field.
+    pub fn name(&self) -> &'static str {
+        match self {
+            StructuredFormat::Json => "json",
+            StructuredFormat::Xml => "xml",
+        }
+    }
+
+    /// Pretty-prints a payload and extracts its top-level fields.
+    ///
+    /// JSON object members are printed in key order. Payloads that do not
+    /// parse are shown as they are, without fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The response body
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::converters::structured::StructuredFormat;
+    ///
+    /// let document = StructuredFormat::Json.render(r#"{"id":7,"name":"Ann","tags":["a"]}"#);
+    /// assert!(document.body.starts_with("```json\n{\n  \"id\": 7,"));
+    /// assert_eq!(
+    ///     document.fields,
+    ///     vec![("id".to_string(), "7".to_string()), ("name".to_string(), "Ann".to_string())]
+    /// );
+    /// ```
+    pub fn render(&self, text: &str) -> StructuredDocument {
+        let (pretty, fields) = match self {
+            StructuredFormat::Json => render_json(text),
+            StructuredFormat::Xml => render_xml(text),
+        };
+        let pretty = pretty.unwrap_or_else(|| text.trim().to_string());
+        let fields = fields
+            .into_iter()
+            .filter(|(key, value)| {
+                !key.is_empty()
+                    && !RESERVED_FIELDS.contains(&key.as_str())
+                    && !value.contains('\n')
+                    && value.chars().count() <= MAX_FIELD_LEN
+            })
+            .take(MAX_FIELDS)
+            .collect();
+        StructuredDocument {
+            body: format!("```{}\n{pretty}\n```\n", self.name()),
+            fields,
+        }
+    }
+}
+
+/// Pretty-prints JSON and collects the scalar members of a top-level object.
+fn render_json(text: &str) -> (Option<String>, Vec<(String, String)>) {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(text) else {
+        return (None, Vec::new());
+    };
+    let fields = match &value {
+        serde_json::Value::Object(members) => members
+            .iter()
+            .filter_map(|(key, value)| {
+                let value = match value {
+                    serde_json::Value::String(text) => text.clone(),
+                    serde_json::Value::Number(number) => number.to_string(),
+                    serde_json::Value::Bool(flag) => flag.to_string(),
+                    _ => return None,
+                };
+                Some((key.clone(), value))
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    (serde_json::to_string_pretty(&value).ok(), fields)
+}
+
+/// A piece of an XML document.
+enum XmlToken<'a> {
+    Open(&'a str),
+    Close,
+    /// Self-closing elements, declarations, comments, and processing instructions
+    Standalone(&'a str),
+    Text(&'a str),
+}
+
+/// Splits XML into tags and text, or returns `None` if it is not well formed.
+fn tokenize_xml(text: &str) -> Option<Vec<XmlToken<'_>>> {
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    let tag = TAG.get_or_init(|| {
+        Regex::new(r"(?s)<!\[CDATA\[.*?\]\]>|<!--.*?-->|<[^>]*>").expect("valid XML tag pattern")
+    });
+
+    let mut tokens = Vec::new();
+    let mut depth = 0usize;
+    let mut last = 0;
+    for found in tag.find_iter(text) {
+        let between = text[last..found.start()].trim();
+        if !between.is_empty() {
+            tokens.push(XmlToken::Text(between));
+        }
+        last = found.end();
+        let markup = found.as_str();
+        if markup.starts_with("<![CDATA[") {
+            tokens.push(XmlToken::Text(markup));
+        } else if markup.starts_with("</") {
+            depth = depth.checked_sub(1)?;
+            tokens.push(XmlToken::Close);
+        } else if markup.ends_with("/>") || markup.starts_with("<?") || markup.starts_with("<!") {
+            tokens.push(XmlToken::Standalone(markup));
+        } else {
+            depth += 1;
+            tokens.push(XmlToken::Open(markup));
+        }
+    }
+    let rest = text[last..].trim();
+    if !rest.is_empty() {
+        tokens.push(XmlToken::Text(rest));
+    }
+    (depth == 0
+        && tokens
+            .iter()
+            .any(|token| matches!(token, XmlToken::Open(_))))
+    .then_some(tokens)
+}
+
+/// Returns the element name of an opening tag.
+fn element_name(tag: &str) -> &str {
+    tag.trim_start_matches('<')
+        .trim_end_matches('>')
+        .split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or_default()
+}
+
+/// Re-indents XML and collects the text-only children of the root element.
+fn render_xml(text: &str) -> (Option<String>, Vec<(String, String)>) {
+    let Some(tokens) = tokenize_xml(text) else {
+        return (None, Vec::new());
+    };
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut fields = Vec::new();
+    let mut open: Vec<&str> = Vec::new();
+    let mut index = 0;
+    while index < tokens.len() {
+        let indent = "  ".repeat(open.len());
+        match tokens[index] {
+            // Elements holding only text stay on one line
+            XmlToken::Open(tag) => match (tokens.get(index + 1), tokens.get(index + 2)) {
+                (Some(XmlToken::Text(value)), Some(XmlToken::Close)) => {
+                    let name = element_name(tag);
+                    lines.push(format!("{indent}{tag}{value}</{name}>"));
+                    if open.len() == 1 && !fields.iter().any(|(key, _)| key == name) {
+                        let value = value
+                            .strip_prefix("<![CDATA[")
+                            .and_then(|value| value.strip_suffix("]]>"))
+                            .map(str::to_string)
+                            .unwrap_or_else(|| decode_xml_entities(value));
+                        fields.push((name.to_string(), value.trim().to_string()));
+                    }
+                    index += 2;
+                }
+                (Some(XmlToken::Close), _) => {
+                    lines.push(format!("{indent}{tag}</{}>", element_name(tag)));
+                    index += 1;
+                }
+                _ => {
+                    lines.push(format!("{indent}{tag}"));
+                    open.push(element_name(tag));
+                }
+            },
+            XmlToken::Close => {
+                let name = open.pop().unwrap_or_default();
+                lines.push(format!("{}</{name}>", "  ".repeat(open.len())));
+            }
+            XmlToken::Standalone(markup) => lines.push(format!("{indent}{markup}")),
+            XmlToken::Text(value) => lines.push(format!("{indent}{value}")),
+        }
+        index += 1;
+    }
+    (Some(lines.join("\n")), fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_json() {
+        let document = StructuredFormat::Json.render(
+            r#"{"id": 42, "login": "octocat", "url": "https://api.example.com/u/42",
+                "site_admin": false, "bio": null, "plan": {"name": "pro"}}"#,
+        );
+        assert!(document
+            .body
+            .starts_with("```json\n{\n  \"bio\": null,\n  \"id\": 42,\n"));
+        assert!(document.body.ends_with("}\n```\n"));
+        assert_eq!(
+            document.fields,
+            vec![
+                ("id".to_string(), "42".to_string()),
+                ("login".to_string(), "octocat".to_string()),
+                ("site_admin".to_string(), "false".to_string()),
+            ]
+        );
+
+        // Arrays have no fields, and invalid JSON is shown as it is
+        assert!(StructuredFormat::Json.render("[1, 2]").fields.is_empty());
+        let document = StructuredFormat::Json.render("{oops ");
+        assert_eq!(document.body, "```json\n{oops\n```\n");
+        assert!(document.fields.is_empty());
+    }
+
+    #[test]
+    fn test_render_xml() {
+        let document = StructuredFormat::Xml.render(
+            "<?xml version=\"1.0\"?><user id=\"7\"><name>Ann &amp; Bo</name><empty/><note><![CDATA[<b>hi</b>]]></note>\
+             <roles><role>admin</role><role>dev</role></roles><name>Second</name></user>",
+        );
+        assert_eq!(
+            document.body,
+            "```xml\n<?xml version=\"1.0\"?>\n<user id=\"7\">\n  <name>Ann &amp; Bo</name>\n  <empty/>\n  \
+             <note><![CDATA[<b>hi</b>]]></note>\n  <roles>\n    <role>admin</role>\n    <role>dev</role>\n  \
+             </roles>\n  <name>Second</name>\n</user>\n```\n"
+        );
+        assert_eq!(
+            document.fields,
+            vec![
+                ("name".to_string(), "Ann & Bo".to_string()),
+                ("note".to_string(), "<b>hi</b>".to_string()),
+            ]
+        );
+
+        let document = StructuredFormat::Xml.render("<a><b></a>");
+        assert_eq!(document.body, "```xml\n<a><b></a>\n```\n");
+    }
+}
@@ -0,0 +1,406 @@
+//! Recursive crawling from a seed URL.
+//!
+//! [`crate::MarkdownDown::crawl`] converts a seed page, follows the links in
+//! the converted markdown, and converts the linked pages, up to
+//! [`CrawlOptions::max_depth`] links from the seed. Pages are taken from a
+//! [`crate::frontier::CrawlFrontier`] in priority order, so a page budget is
+//! spent on the most important pages, and a frontier saved with
+//! [`CrawlOptions::frontier`] makes later crawls incremental. Each URL is
+//! converted at most once per crawl, links can be restricted to the seed's
+//! domain, and `robots.txt` rules are respected unless turned off.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::crawl::RobotsRules;
+//!
+//! let robots = "User-agent: *\nDisallow: /private/\nAllow: /private/press\n";
+//! let rules = RobotsRules::parse(robots, "markdowndown/1.0");
+//!
+//! assert!(rules.is_allowed("/docs/intro"));
+//! assert!(!rules.is_allowed("/private/notes"));
+//! assert!(rules.is_allowed("/private/press-kit"));
+//! ```
+
+use crate::identity::canonical_url;
+use crate::links::extract_links;
+use crate::types::{Markdown, MarkdownError};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
+use url::Url;
+
+/// Options for crawling a site from a seed URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrawlOptions {
+    /// Links followed from the seed; 0 converts only the seed
+    pub max_depth: u32,
+    /// Whether only links to the seed's domain are followed
+    pub same_domain: bool,
+    /// Whether `robots.txt` rules are respected
+    pub respect_robots: bool,
+    /// Maximum number of pages to convert (None converts every page found)
+    pub max_pages: Option<usize>,
+    /// Number of pages converted at the same time, or the most per host when adaptive
+    pub concurrency: usize,
+    /// Whether concurrency adapts to each host's errors and latency
+    pub adaptive_concurrency: bool,
+    /// Minimum time between the start of two page conversions
+    pub delay: Duration,
+    /// Directory each converted page is written to (None only returns them)
+    pub output_dir: Option<PathBuf>,
+    /// File the crawl frontier is loaded from and saved to (None keeps it in memory)
+    pub frontier: Option<PathBuf>,
+}
+
+impl CrawlOptions {
+    /// Creates options following links two deep within the seed's domain,
+    /// respecting `robots.txt`, four pages at a time, 500ms apart.
+    pub fn new() -> Self {
+        Self {
+            max_depth: 2,
+            same_domain: true,
+            respect_robots: true,
+            max_pages: None,
+            concurrency: 4,
+            adaptive_concurrency: false,
+            delay: Duration::from_millis(500),
+            output_dir: None,
+            frontier: None,
+        }
+    }
+
+    /// Sets how many links deep the crawl goes.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - Links followed from the seed; 0 converts only the seed
+    pub fn max_depth(mut self, depth: u32) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Sets whether only links to the seed's domain are followed.
+    ///
+    /// The domain comparison ignores a leading `www.`, so `example.com` and
+    /// `www.example.com` count as the same domain.
+    ///
+    /// # Arguments
+    ///
+    /// * `same_domain` - Whether links to other domains are ignored
+    pub fn same_domain(mut self, same_domain: bool) -> Self {
+        self.same_domain = same_domain;
+        self
+    }
+
+    /// Sets whether `robots.txt` rules are respected.
+    ///
+    /// # Arguments
+    ///
+    /// * `respect` - Whether disallowed pages are skipped and `Crawl-delay` is honored
+    pub fn respect_robots(mut self, respect: bool) -> Self {
+        self.respect_robots = respect;
+        self
+    }
+
+    /// Limits how many pages are converted.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - Maximum number of pages, taken highest frontier priority first
+    pub fn max_pages(mut self, max: usize) -> Self {
+        self.max_pages = Some(max);
+        self
+    }
+
+    /// Sets how many pages are converted at the same time.
+    ///
+    /// # Arguments
+    ///
+    /// * `concurrency` - Number of concurrent conversions, at least 1
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets whether concurrency adapts to each host instead of staying fixed.
+    ///
+    /// Each host starts at one conversion at a time. Healthy responses
+    /// raise its limit up to [`Self::concurrency`], and throttling, timeouts,
+    /// server errors, or slowing responses halve it. See
+    /// [`crate::concurrency::AdaptiveConcurrency`].
+    ///
+    /// # Arguments
+    ///
+    /// * `adaptive` - Whether per-host limits adapt to the host's responses
+    pub fn adaptive_concurrency(mut self, adaptive: bool) -> Self {
+        self.adaptive_concurrency = adaptive;
+        self
+    }
+
+    /// Sets the politeness delay between the start of two page conversions.
+    ///
+    /// A longer `Crawl-delay` in the seed site's `robots.txt` takes precedence.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - Minimum time between conversions; zero disables the delay
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Writes each converted page to a directory, named after its URL slug.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The output directory, created if missing
+    pub fn output_dir<T: Into<PathBuf>>(mut self, dir: T) -> Self {
+        self.output_dir = Some(dir.into());
+        self
+    }
+
+    /// Loads the crawl frontier from a file before crawling and saves it afterwards.
+    ///
+    /// The frontier remembers every URL found and how often its content
+    /// changed, so a later crawl with a page budget fetches new and
+    /// frequently changing pages ahead of static ones. A missing file starts
+    /// an empty frontier.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The frontier file, written as JSON
+    pub fn frontier<T: Into<PathBuf>>(mut self, path: T) -> Self {
+        self.frontier = Some(path.into());
+        self
+    }
+}
+
+impl Default for CrawlOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A page converted during a crawl.
+#[derive(Debug)]
+pub struct CrawledPage {
+    /// The page URL
+    pub url: String,
+    /// Links followed from the seed to reach the page
+    pub depth: u32,
+    /// The converted markdown, or why the page could not be converted
+    pub result: Result<Markdown, MarkdownError>,
+}
+
+/// The `robots.txt` rules that apply to one user agent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RobotsRules {
+    /// `Allow` (true) and `Disallow` (false) path patterns
+    rules: Vec<(bool, String)>,
+    /// The `Crawl-delay` requested for the user agent
+    pub crawl_delay: Option<Duration>,
+}
+
+impl RobotsRules {
+    /// Parses the rules of a `robots.txt` file for a user agent.
+    ///
+    /// The group naming the user agent's product token (such as
+    /// `markdowndown` for `markdowndown/1.0`) is used, falling back to the
+    /// `*` group. Paths may use `*` wildcards and a `$` end anchor, and the
+    /// longest matching rule wins, with `Allow` winning ties.
+    ///
+    /// # Arguments
+    ///
+    /// * `robots` - The contents of the `robots.txt` file
+    /// * `user_agent` - The `User-Agent` header sent with requests
+    pub fn parse(robots: &str, user_agent: &str) -> Self {
+        let token = user_agent
+            .split(['/', ' '])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        // Each group is its user agents, its rules, and its crawl delay
+        let mut groups: Vec<(Vec<String>, RobotsRules)> = Vec::new();
+        let mut in_agents = false;
+        for line in robots.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((field, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match field.trim().to_ascii_lowercase().as_str() {
+                "user-agent" => {
+                    if !in_agents {
+                        groups.push((Vec::new(), RobotsRules::default()));
+                        in_agents = true;
+                    }
+                    if let Some((agents, _)) = groups.last_mut() {
+                        agents.push(value.to_ascii_lowercase());
+                    }
+                }
+                field => {
+                    in_agents = false;
+                    let Some((_, rules)) = groups.last_mut() else {
+                        continue;
+                    };
+                    match field {
+                        "allow" if !value.is_empty() => rules.rules.push((true, value.to_string())),
+                        // An empty Disallow allows everything
+                        "disallow" if !value.is_empty() => {
+                            rules.rules.push((false, value.to_string()))
+                        }
+                        "crawl-delay" => {
+                            rules.crawl_delay = value
+                                .parse::<f64>()
+                                .ok()
+                                .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+                                .map(Duration::from_secs_f64);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        let named = groups.iter().find(|(agents, _)| {
+            agents
+                .iter()
+                .any(|agent| agent != "*" && !token.is_empty() && token.contains(agent.as_str()))
+        });
+        let wildcard = || {
+            groups
+                .iter()
+                .find(|(agents, _)| agents.iter().any(|a| a == "*"))
+        };
+        named
+            .or_else(wildcard)
+            .map(|(_, rules)| rules.clone())
+            .unwrap_or_default()
+    }
+
+    /// Checks whether a path may be crawled.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The URL path, with its query string if any
+    pub fn is_allowed(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|(_, pattern)| robots_match(pattern.as_bytes(), path.as_bytes()))
+            .max_by_key(|(allow, pattern)| (pattern.len(), *allow))
+            .is_none_or(|(allow, _)| *allow)
+    }
+}
+
+/// Matches a path against a `robots.txt` pattern, as a prefix unless it ends in `$`.
+fn robots_match(pattern: &[u8], path: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => true,
+        Some((b'$', [])) => path.is_empty(),
+        Some((b'*', rest)) => (0..=path.len()).any(|skip| robots_match(rest, &path[skip..])),
+        Some((c, rest)) => path.first() == Some(c) && robots_match(rest, &path[1..]),
+    }
+}
+
+/// Returns the host without a leading `www.`, for same-domain checks.
+fn domain(url: &Url) -> Option<&str> {
+    url.host_str()
+        .map(|host| host.strip_prefix("www.").unwrap_or(host))
+}
+
+/// Checks whether the crawl may follow a link to `target`.
+///
+/// Only `http` and `https` URLs are followed, restricted to the seed's domain
+/// when [`CrawlOptions::same_domain`] is set.
+pub(crate) fn in_scope(target: &Url, seed: &Url, options: &CrawlOptions) -> bool {
+    matches!(target.scheme(), "http" | "https")
+        && (!options.same_domain || domain(target) == domain(seed))
+}
+
+/// Collects the links of a converted page that the crawl should follow.
+///
+/// Links are resolved against the page URL and stripped of fragments, links
+/// [`in_scope`] rejects are dropped, and links with the same canonical URL
+/// are listed once.
+pub(crate) fn links_to_follow(
+    markdown: &str,
+    page_url: &str,
+    seed: &Url,
+    options: &CrawlOptions,
+) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut follow = Vec::new();
+    for link in extract_links(markdown, page_url) {
+        let Ok(mut target) = Url::parse(&link.url) else {
+            continue;
+        };
+        target.set_fragment(None);
+        if !in_scope(&target, seed, options) {
+            continue;
+        }
+        if seen.insert(canonical_url(target.as_str())) {
+            follow.push(target.to_string());
+        }
+    }
+    follow
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_robots_rules_pick_group_and_longest_match() {
+        let robots = "# comment\n\
+                      User-agent: *\n\
+                      Disallow: /\n\
+                      \n\
+                      User-agent: otherbot\n\
+                      User-agent: MarkdownDown\n\
+                      Disallow: /drafts/\n\
+                      Disallow: /*.pdf$\n\
+                      Allow: /drafts/public\n\
+                      Crawl-delay: 2.5\n";
+
+        let rules = RobotsRules::parse(robots, "markdowndown/0.1.0");
+        assert!(rules.is_allowed("/"));
+        assert!(!rules.is_allowed("/drafts/next"));
+        assert!(rules.is_allowed("/drafts/public/a"));
+        assert!(!rules.is_allowed("/files/report.pdf"));
+        assert!(rules.is_allowed("/files/report.pdf?download=1"));
+        assert_eq!(rules.crawl_delay, Some(Duration::from_millis(2500)));
+
+        let other = RobotsRules::parse(robots, "curl/8.0");
+        assert!(!other.is_allowed("/anything"));
+        assert!(RobotsRules::parse("", "curl/8.0").is_allowed("/anything"));
+    }
+
+    #[test]
+    fn test_links_to_follow() {
+        let seed = Url::parse("https://www.example.com/").unwrap();
+        let markdown = "[Home](/#top) [Docs](/docs/) [Docs again](https://example.com/docs)\n\
+                        [Other](https://other.org/) [Mail](mailto:a@example.com)";
+
+        let follow = links_to_follow(
+            markdown,
+            "https://www.example.com/",
+            &seed,
+            &CrawlOptions::new(),
+        );
+        assert_eq!(
+            follow,
+            vec![
+                "https://www.example.com/",
+                "https://www.example.com/docs/",
+                "https://example.com/docs"
+            ]
+        );
+
+        let options = CrawlOptions::new().same_domain(false);
+        let follow = links_to_follow(markdown, "https://www.example.com/", &seed, &options);
+        assert_eq!(follow.len(), 4);
+        assert_eq!(follow[3], "https://other.org/");
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! This module provides intelligent URL type detection to route different URL types
 //! to appropriate handlers. It supports detection of Google Docs, Office 365,
-//! GitHub Issues, and generic HTML URLs.
+//! GitHub Issues, PDF documents, and generic HTML URLs.
 //!
 //! # Examples
 //!
@@ -40,10 +40,22 @@
 //! # Ok::<(), markdowndown::types::MarkdownError>(())
 //! ```
 
-use crate::types::{MarkdownError, UrlType};
+use crate::converters::markdown_source::is_markdown_path;
+use crate::converters::markup::MarkupSyntax;
+use crate::types::{ConfigErrorKind, ErrorContext, MarkdownError, UrlType};
+use regex::Regex;
 use std::collections::HashSet;
 use url::Url as ParsedUrl;
 
+/// Query parameters that carry the signature of a pre-signed storage URL.
+///
+/// S3, Google Cloud Storage, and Azure shared access signatures sign the
+/// query string as sent, so URLs carrying one are never rewritten.
+const SIGNATURE_PARAMS: [&str; 3] = ["x-amz-signature", "x-goog-signature", "sig"];
+
+/// Hosts of URL shorteners, whose links redirect to the real document.
+const SHORTENER_HOSTS: [&str; 4] = ["bit.ly", "t.co", "goo.gl", "tinyurl.com"];
+
 /// URL pattern configuration for different URL types.
 #[derive(Debug, Clone)]
 struct Pattern {
@@ -57,9 +69,12 @@ struct Pattern {
 
 impl Pattern {
     /// Creates a new pattern configuration.
+    ///
+    /// Internationalized domains are stored in ASCII form to match parsed hosts.
     fn new(domain_pattern: &str, path_pattern: Option<&str>, url_type: UrlType) -> Self {
         Self {
-            domain_pattern: domain_pattern.to_string(),
+            domain_pattern: crate::utils::host_to_ascii(domain_pattern)
+                .unwrap_or_else(|| domain_pattern.to_string()),
             path_pattern: path_pattern.map(|s| s.to_string()),
             url_type,
         }
@@ -124,11 +139,117 @@ impl Pattern {
     }
 }
 
+/// A host glob or URL regex that assigns a URL type to matching URLs.
+///
+/// Rules are registered with [`UrlDetector::register_rule`] and checked
+/// before every other pattern, highest priority first. Rules with the same
+/// priority are checked in the order they were registered.
+#[derive(Debug, Clone)]
+pub struct RulePattern {
+    /// Compiled form of the glob or regex
+    regex: Regex,
+    /// Whether the regex is matched against the host rather than the whole URL
+    host_only: bool,
+    /// Rules with higher priorities are checked first
+    priority: i32,
+}
+
+impl RulePattern {
+    /// Creates a rule matching hosts against a glob.
+    ///
+    /// `*` matches any run of characters and `?` any single character. A
+    /// leading `*.` also matches the bare domain, so `*.corp.example`
+    /// matches both `corp.example` and `wiki.corp.example`.
+    ///
+    /// # Arguments
+    ///
+    /// * `glob` - Host glob, such as `wiki.corp.example` or `*.corp.example`
+    pub fn host(glob: &str) -> Self {
+        let glob = glob.trim().trim_end_matches('.').to_ascii_lowercase();
+        let (prefix, rest) = match glob.strip_prefix("*.") {
+            Some(rest) => ("(?:.*\\.)?", rest),
+            None => ("", glob.as_str()),
+        };
+        let body = rest
+            .split('.')
+            .map(|label| {
+                let label = if label.contains(['*', '?']) {
+                    label.to_string()
+                } else {
+                    crate::utils::host_to_ascii(label).unwrap_or_else(|| label.to_string())
+                };
+                label
+                    .chars()
+                    .map(|c| match c {
+                        '*' => ".*".to_string(),
+                        '?' => ".".to_string(),
+                        c => regex::escape(c.encode_utf8(&mut [0; 4])),
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\\.");
+        Self {
+            regex: Regex::new(&format!("^{prefix}{body}$")).expect("escaped host glob"),
+            host_only: true,
+            priority: 0,
+        }
+    }
+
+    /// Creates a rule matching whole URLs against a regular expression.
+    ///
+    /// The regex is unanchored and matched against the parsed URL, whose
+    /// scheme and host are lowercase.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Regular expression, such as `^https://git\.corp\.example/.+/wiki/`
+    ///
+    /// # Errors
+    ///
+    /// Returns `MarkdownError::ConfigurationError` if the pattern is not a valid regex.
+    pub fn regex(pattern: &str) -> Result<Self, MarkdownError> {
+        let regex = Regex::new(pattern).map_err(|e| MarkdownError::ConfigurationError {
+            kind: ConfigErrorKind::InvalidValue,
+            context: ErrorContext::new(pattern, "Compile detection rule", "UrlDetector")
+                .with_info(format!("Invalid regex: {e}")),
+        })?;
+        Ok(Self {
+            regex,
+            host_only: false,
+            priority: 0,
+        })
+    }
+
+    /// Sets the rule's priority; higher priorities are checked first (default 0).
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Checks if a URL matches this rule.
+    fn matches(&self, parsed_url: &ParsedUrl) -> bool {
+        if self.host_only {
+            parsed_url
+                .host_str()
+                .is_some_and(|host| self.regex.is_match(host))
+        } else {
+            self.regex.is_match(parsed_url.as_str())
+        }
+    }
+}
+
 /// URL detector for intelligent URL type classification.
 #[derive(Debug)]
 pub struct UrlDetector {
+    /// Rules registered by the deployment, highest priority first
+    rules: Vec<(RulePattern, UrlType)>,
+    /// Patterns added by the application, checked before the built-in patterns
+    custom_patterns: Vec<Pattern>,
     /// Configured URL patterns for detection
     patterns: Vec<Pattern>,
+    /// GitHub Enterprise Server hosts whose issue URLs are GitHub issues
+    github_hosts: Vec<String>,
     /// Tracking parameters to remove during normalization
     tracking_params: HashSet<String>,
 }
@@ -140,6 +261,8 @@ impl UrlDetector {
             // Google Docs patterns
             Pattern::new("docs.google.com", Some("/document/"), UrlType::GoogleDocs),
             Pattern::new("drive.google.com", Some("/file/"), UrlType::GoogleDocs),
+            Pattern::new("drive.google.com", Some("/open"), UrlType::GoogleDocs),
+            Pattern::new("drive.google.com", Some("/uc"), UrlType::GoogleDocs),
             // GitHub patterns (handled separately due to complexity)
         ];
 
@@ -167,11 +290,117 @@ impl UrlDetector {
         .collect();
 
         Self {
+            rules: Vec::new(),
+            custom_patterns: Vec::new(),
             patterns,
+            github_hosts: Vec::new(),
             tracking_params,
         }
     }
 
+    /// Adds a pattern that assigns a URL type to matching URLs.
+    ///
+    /// Added patterns are checked in the order they were added, before the
+    /// built-in patterns, so they can claim URLs that would otherwise be
+    /// detected as GitHub issues, Google Docs, or HTML. Local file paths are
+    /// always detected as `UrlType::LocalFile`, `data:` URIs as `UrlType::DataUri`,
+    /// `s3://`, `gs://`, and `az://` URIs as `UrlType::CloudStorage`, and
+    /// `ipfs://` URIs as `UrlType::Ipfs`.
+    ///
+    /// # Arguments
+    ///
+    /// * `domain_pattern` - Host name, or `*.example.com` to match any subdomain
+    /// * `path_pattern` - Optional path prefix; `*` matches any prefix or suffix
+    /// * `url_type` - The URL type to assign, usually `UrlType::Custom`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::detection::UrlDetector;
+    /// use markdowndown::types::UrlType;
+    ///
+    /// let mut detector = UrlDetector::new();
+    /// detector.add_pattern("*.atlassian.net", Some("/wiki/"), UrlType::Custom("confluence".into()));
+    ///
+    /// let url_type = detector.detect_type("https://acme.atlassian.net/wiki/spaces/ENG")?;
+    /// assert_eq!(url_type, UrlType::Custom("confluence".into()));
+    /// # Ok::<(), markdowndown::types::MarkdownError>(())
+    /// ```
+    pub fn add_pattern(&mut self, domain_pattern: &str, path_pattern: Option<&str>, url_type: UrlType) {
+        self.custom_patterns
+            .push(Pattern::new(domain_pattern, path_pattern, url_type));
+    }
+
+    /// Registers a rule that assigns a URL type to matching URLs.
+    ///
+    /// Rules map internal domains, such as a corporate wiki, to a specific
+    /// converter. They are checked before patterns added with
+    /// [`UrlDetector::add_pattern`] and the built-in patterns, highest
+    /// priority first, then in the order they were registered. Like added
+    /// patterns, they never apply to local files or non-HTTP URIs.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Host glob or URL regex, with its priority
+    /// * `url_type` - The URL type to assign
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::detection::{RulePattern, UrlDetector};
+    /// use markdowndown::types::UrlType;
+    ///
+    /// let mut detector = UrlDetector::new();
+    /// let wiki = UrlType::Custom("wiki".into());
+    /// detector.register_rule(RulePattern::host("*.corp.example"), wiki.clone());
+    /// detector.register_rule(
+    ///     RulePattern::regex(r"^https://wiki\.corp\.example/.*\.pdf$")?.priority(10),
+    ///     UrlType::Pdf,
+    /// );
+    ///
+    /// let url_type = detector.detect_type("https://wiki.corp.example/spec.pdf")?;
+    /// assert_eq!(url_type, UrlType::Pdf);
+    /// let url_type = detector.detect_type("https://wiki.corp.example/spec")?;
+    /// assert_eq!(url_type, wiki);
+    /// # Ok::<(), markdowndown::types::MarkdownError>(())
+    /// ```
+    pub fn register_rule(&mut self, pattern: RulePattern, url_type: UrlType) {
+        let index = self
+            .rules
+            .partition_point(|(rule, _)| rule.priority >= pattern.priority);
+        self.rules.insert(index, (pattern, url_type));
+    }
+
+    /// Adds a GitHub Enterprise Server host.
+    ///
+    /// Issue and pull request URLs on the host are then detected as
+    /// `UrlType::GitHubIssue`, like those on github.com.
+    ///
+    /// # Arguments
+    ///
+    /// * `host` - The server's web host, such as `github.example.com`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::detection::UrlDetector;
+    /// use markdowndown::types::UrlType;
+    ///
+    /// let mut detector = UrlDetector::new();
+    /// detector.add_github_host("github.example.com");
+    ///
+    /// let url_type = detector.detect_type("https://github.example.com/platform/api/issues/7")?;
+    /// assert_eq!(url_type, UrlType::GitHubIssue);
+    /// # Ok::<(), markdowndown::types::MarkdownError>(())
+    /// ```
+    pub fn add_github_host(&mut self, host: &str) {
+        let host = host.trim().trim_end_matches('.');
+        let host = crate::utils::host_to_ascii(host).unwrap_or_else(|| host.to_ascii_lowercase());
+        if !self.github_hosts.contains(&host) {
+            self.github_hosts.push(host);
+        }
+    }
+
     /// Detects the URL type for a given URL string.
     ///
     /// # Arguments
@@ -194,30 +423,204 @@ impl UrlDetector {
     /// # Ok::<(), markdowndown::types::MarkdownError>(())
     /// ```
     pub fn detect_type(&self, url: &str) -> Result<UrlType, MarkdownError> {
+        let mut candidates = self.detect_with_confidence(url)?;
+        Ok(candidates.swap_remove(0).0)
+    }
+
+    /// Detects every URL type a URL may have, with a confidence for each.
+    ///
+    /// Candidates are ordered from most to least likely, and the first is
+    /// the type [`UrlDetector::detect_type`] returns. Web URLs always end with
+    /// `UrlType::Html`, so a caller whose converter fails can try the next
+    /// candidate before falling back to HTML. Confidences range from 0.0 to
+    /// 1.0: registered rules score 1.0, service URLs such as GitHub issues
+    /// 0.9 to 0.95, `.pdf` paths 0.7, and HTML 0.5 when nothing else matches
+    /// or 0.1 as a fallback. Local files and non-HTTP URIs have a single
+    /// candidate.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL string to analyze
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MarkdownError` if the URL is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::detection::UrlDetector;
+    /// use markdowndown::types::UrlType;
+    ///
+    /// let detector = UrlDetector::new();
+    /// let candidates = detector.detect_with_confidence("https://www.dropbox.com/s/abc/report.pdf")?;
+    /// let types: Vec<UrlType> = candidates.into_iter().map(|(url_type, _)| url_type).collect();
+    /// assert_eq!(types, vec![UrlType::Dropbox, UrlType::Pdf, UrlType::Html]);
+    /// # Ok::<(), markdowndown::types::MarkdownError>(())
+    /// ```
+    pub fn detect_with_confidence(&self, url: &str) -> Result<Vec<(UrlType, f32)>, MarkdownError> {
         let trimmed = url.trim();
 
+        // Inline documents carry their content in the URL itself
+        if crate::utils::is_data_uri(trimmed) {
+            return Ok(vec![(UrlType::DataUri, 1.0)]);
+        }
+
+        // Objects in cloud storage are read through the store's own API
+        if crate::utils::is_cloud_storage_uri(trimmed) {
+            return Ok(vec![(UrlType::CloudStorage, 1.0)]);
+        }
+
+        // Content-addressed documents are fetched through gateways
+        if crate::utils::is_ipfs_uri(trimmed) {
+            return Ok(vec![(UrlType::Ipfs, 1.0)]);
+        }
+
         // Check for local file paths first (before trying to parse as URL)
         if crate::utils::is_local_file_path(trimmed) {
-            return Ok(UrlType::LocalFile);
+            return Ok(vec![(UrlType::LocalFile, 1.0)]);
         }
 
         // Try to parse as URL for web-based sources
         let parsed_url = self.parse_url(url)?;
+        let mut candidates = Vec::new();
+
+        // Registered rules take precedence over every pattern
+        if let Some((_, url_type)) = self
+            .rules
+            .iter()
+            .find(|(rule, _)| rule.matches(&parsed_url))
+        {
+            add_candidate(&mut candidates, url_type.clone(), 1.0);
+        }
+
+        // Application-defined patterns take precedence over the built-in ones
+        if let Some(pattern) = self
+            .custom_patterns
+            .iter()
+            .find(|pattern| pattern.matches(&parsed_url))
+        {
+            add_candidate(&mut candidates, pattern.url_type.clone(), 0.95);
+        }
 
         // Special handling for GitHub issues (more complex pattern)
         if self.is_github_issue_url(&parsed_url) {
-            return Ok(UrlType::GitHubIssue);
+            add_candidate(&mut candidates, UrlType::GitHubIssue, 0.95);
+        }
+
+        // Azure DevOps work items are read through the work item API
+        if self.is_azure_devops_work_item_url(&parsed_url) {
+            add_candidate(&mut candidates, UrlType::AzureDevOps, 0.95);
+        }
+
+        // SharePoint and OneDrive documents are downloaded through Microsoft Graph
+        if self.is_office365_document_url(&parsed_url) {
+            add_candidate(&mut candidates, UrlType::Office365, 0.9);
+        }
+
+        // Dropbox Paper documents are exported as markdown and shared files downloaded
+        if self.is_dropbox_url(&parsed_url) {
+            add_candidate(&mut candidates, UrlType::Dropbox, 0.9);
         }
 
         // Check each pattern to find a match
-        for pattern in &self.patterns {
-            if pattern.matches(&parsed_url) {
-                return Ok(pattern.url_type.clone());
-            }
+        if let Some(pattern) = self
+            .patterns
+            .iter()
+            .find(|pattern| pattern.matches(&parsed_url))
+        {
+            add_candidate(&mut candidates, pattern.url_type.clone(), 0.9);
         }
 
-        // Default to HTML for any other HTTP/HTTPS URLs
-        Ok(UrlType::Html)
+        // Documents other than web pages are recognized by their file extension
+        let path = parsed_url.path().to_ascii_lowercase();
+        if path.ends_with(".pdf") {
+            add_candidate(&mut candidates, UrlType::Pdf, 0.7);
+        }
+        if path.ends_with(".ipynb") {
+            add_candidate(&mut candidates, UrlType::Notebook, 0.7);
+        }
+        if MarkupSyntax::from_path(&path).is_some() {
+            add_candidate(&mut candidates, UrlType::Markup, 0.7);
+        }
+        if path.ends_with(".eml") || path.ends_with(".mht") || path.ends_with(".mhtml") {
+            add_candidate(&mut candidates, UrlType::Email, 0.7);
+        }
+        if is_markdown_path(&path) {
+            add_candidate(&mut candidates, UrlType::Markdown, 0.7);
+        }
+
+        // Any other HTTP/HTTPS URL is an HTML page, and every page can be read as one
+        let html_confidence = if candidates.is_empty() { 0.5 } else { 0.1 };
+        add_candidate(&mut candidates, UrlType::Html, html_confidence);
+        Ok(candidates)
+    }
+
+    /// Checks if a URL is a link from a known URL shortener.
+    ///
+    /// Shortened links hide the document's real address, so they should be
+    /// expanded by following their redirect before the type is detected.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL string to check
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::detection::UrlDetector;
+    ///
+    /// let detector = UrlDetector::new();
+    /// assert!(detector.is_shortened_url("https://bit.ly/3xYzAbc"));
+    /// assert!(!detector.is_shortened_url("https://docs.google.com/document/d/123/edit"));
+    /// ```
+    pub fn is_shortened_url(&self, url: &str) -> bool {
+        let Ok(parsed_url) = ParsedUrl::parse(url.trim()) else {
+            return false;
+        };
+        let host = parsed_url.host_str().unwrap_or_default();
+        let host = host.strip_prefix("www.").unwrap_or(host);
+        matches!(parsed_url.scheme(), "http" | "https")
+            && parsed_url.path().len() > 1
+            && SHORTENER_HOSTS.contains(&host)
+    }
+
+    /// Refines a detected URL type with the content type the server reports.
+    ///
+    /// HTML and PDF documents are detected from the URL alone, which can be
+    /// wrong: a `.html` URL may serve a PDF, a notebook, or a saved email or
+    /// web archive, and a `.pdf` URL an HTML page.
+    /// Other types are returned unchanged, since their converters fetch
+    /// through service APIs rather than the URL itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `url_type` - The type detected from the URL
+    /// * `content_type` - The media type from the `Content-Type` header,
+    ///   lowercased, without parameters
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::detection::UrlDetector;
+    /// use markdowndown::types::UrlType;
+    ///
+    /// let detector = UrlDetector::new();
+    /// let url_type = detector.detect_type("https://example.com/report.html")?;
+    /// assert_eq!(detector.refine_type(url_type, "application/pdf"), UrlType::Pdf);
+    /// # Ok::<(), markdowndown::types::MarkdownError>(())
+    /// ```
+    pub fn refine_type(&self, url_type: UrlType, content_type: &str) -> UrlType {
+        match (&url_type, content_type) {
+            (UrlType::Html, "application/pdf") => UrlType::Pdf,
+            (UrlType::Html, "application/x-ipynb+json") => UrlType::Notebook,
+            (
+                UrlType::Html,
+                "message/rfc822" | "multipart/related" | "application/x-mimearchive",
+            ) => UrlType::Email,
+            (UrlType::Pdf, "text/html" | "application/xhtml+xml") => UrlType::Html,
+            _ => url_type,
+        }
     }
 
     /// Normalizes a URL by cleaning and validating it.
@@ -225,7 +628,7 @@ impl UrlDetector {
     /// This method:
     /// - Trims whitespace
     /// - Ensures HTTPS scheme where possible
-    /// - Removes tracking parameters
+    /// - Removes tracking parameters, except from pre-signed storage URLs
     /// - Validates URL structure
     ///
     /// # Arguments
@@ -238,14 +641,24 @@ impl UrlDetector {
     pub fn normalize_url(&self, url: &str) -> Result<String, MarkdownError> {
         let trimmed = url.trim();
 
-        // Handle local file paths separately (no URL parsing needed)
-        if crate::utils::is_local_file_path(trimmed) {
+        // Handle local file paths, inline documents, and stored objects
+        // separately (no URL parsing needed)
+        if crate::utils::is_local_file_path(trimmed)
+            || crate::utils::is_data_uri(trimmed)
+            || crate::utils::is_cloud_storage_uri(trimmed)
+            || crate::utils::is_ipfs_uri(trimmed)
+        {
             return Ok(trimmed.to_string());
         }
 
         // Parse as URL for web-based sources
         let mut parsed_url = self.parse_url(trimmed)?;
 
+        // Rebuilding the query would invalidate a signature
+        if is_presigned(&parsed_url) {
+            return Ok(parsed_url.to_string());
+        }
+
         // Remove tracking parameters
         let query_pairs: Vec<(String, String)> = parsed_url
             .query_pairs()
@@ -290,6 +703,53 @@ impl UrlDetector {
             return Ok(());
         }
 
+        // Allow cloud storage objects with a bucket or container
+        if crate::utils::is_cloud_storage_uri(trimmed) {
+            return ParsedUrl::parse(trimmed)
+                .ok()
+                .filter(|parsed| parsed.host_str().is_some())
+                .map(|_| ())
+                .ok_or_else(|| {
+                    let context =
+                        crate::types::ErrorContext::new(url, "URL parsing", "validate_url");
+                    MarkdownError::ValidationError {
+                        kind: crate::types::ValidationErrorKind::InvalidUrl,
+                        context,
+                    }
+                });
+        }
+
+        // Allow IPFS URIs with a well-formed CID
+        if crate::utils::is_ipfs_uri(trimmed) {
+            let cid = trimmed[7..]
+                .split(['/', '?', '#'])
+                .next()
+                .unwrap_or_default();
+            if !cid.chars().all(|c| c.is_ascii_alphanumeric()) {
+                let context =
+                    crate::types::ErrorContext::new(url, "URL validation", "validate_url")
+                        .with_info(format!("Invalid IPFS CID: {cid}"));
+                return Err(MarkdownError::ValidationError {
+                    kind: crate::types::ValidationErrorKind::InvalidUrl,
+                    context,
+                });
+            }
+            return Ok(());
+        }
+
+        // Allow data URIs that can be parsed
+        if crate::utils::is_data_uri(trimmed) {
+            if data_url::DataUrl::process(trimmed).is_err() {
+                let context =
+                    crate::types::ErrorContext::new(url, "URL validation", "validate_url");
+                return Err(MarkdownError::ValidationError {
+                    kind: crate::types::ValidationErrorKind::InvalidUrl,
+                    context,
+                });
+            }
+            return Ok(());
+        }
+
         // Basic validation - must be HTTP or HTTPS for web URLs
         if !(trimmed.starts_with("http://") || trimmed.starts_with("https://")) {
             let context = crate::types::ErrorContext::new(url, "URL validation", "validate_url");
@@ -335,8 +795,13 @@ impl UrlDetector {
     }
 
     /// Checks if a URL matches a GitHub issue or pull request pattern.
+    #[allow(clippy::collapsible_match)]
     fn is_github_issue_url(&self, parsed_url: &ParsedUrl) -> bool {
-        let host = parsed_url.host_str();
+        let host = match parsed_url.host_str() {
+            // Enterprise issue URLs have the same paths as github.com's
+            Some(host) if self.github_hosts.iter().any(|known| known == host) => Some("github.com"),
+            host => host,
+        };
         if host != Some("github.com") && host != Some("api.github.com") {
             return false;
         }
@@ -383,6 +848,77 @@ impl UrlDetector {
 
         false
     }
+
+    /// Checks if a URL is an Azure DevOps work item URL.
+    ///
+    /// Work items are at `dev.azure.com/{organization}/{project}/_workitems/edit/{id}`,
+    /// or at `{organization}.visualstudio.com/{project}/_workitems/edit/{id}` for
+    /// older organizations.
+    fn is_azure_devops_work_item_url(&self, parsed_url: &ParsedUrl) -> bool {
+        let path_segments: Vec<&str> = parsed_url
+            .path()
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect();
+        let work_item_segments = match parsed_url.host_str() {
+            Some("dev.azure.com") => path_segments.get(2..),
+            Some(host) if host.ends_with(".visualstudio.com") => path_segments.get(1..),
+            _ => None,
+        };
+        matches!(
+            work_item_segments,
+            Some(["_workitems", "edit", id, ..]) if id.parse::<u32>().is_ok()
+        )
+    }
+
+    /// Checks if a URL is a SharePoint or OneDrive document URL.
+    ///
+    /// Documents are SharePoint sharing links (`/:w:/...`, `/:p:/...`),
+    /// SharePoint paths to Office or PDF files, OneDrive short links
+    /// (`1drv.ms`), and `onedrive.live.com` links naming a file with `resid`.
+    /// Other SharePoint pages, such as site pages, are not documents.
+    fn is_office365_document_url(&self, parsed_url: &ParsedUrl) -> bool {
+        const DOCUMENT_EXTENSIONS: [&str; 9] = [
+            "docx", "doc", "pptx", "ppt", "xlsx", "xls", "odt", "rtf", "pdf",
+        ];
+
+        match parsed_url.host_str() {
+            Some(host) if host.ends_with(".sharepoint.com") => {
+                let path = parsed_url.path();
+                let is_sharing_link = path
+                    .strip_prefix("/:")
+                    .and_then(|rest| rest.split_once(":/"))
+                    .is_some_and(|(kind, _)| kind.len() == 1);
+                let extension = path
+                    .rsplit('/')
+                    .next()
+                    .and_then(|name| name.rsplit_once('.'))
+                    .map(|(_, extension)| extension.to_ascii_lowercase());
+                is_sharing_link
+                    || extension
+                        .is_some_and(|extension| DOCUMENT_EXTENSIONS.contains(&extension.as_str()))
+            }
+            Some("1drv.ms") => parsed_url.path().len() > 1,
+            Some("onedrive.live.com") => parsed_url.query_pairs().any(|(key, _)| key == "resid"),
+            _ => false,
+        }
+    }
+
+    /// Checks if a URL is a Dropbox Paper document or a Dropbox shared file.
+    ///
+    /// Paper documents live under `paper.dropbox.com/doc/`; shared files are
+    /// `dropbox.com/s/...` links and newer `dropbox.com/scl/fi/...` links.
+    /// Shared folders and other Dropbox pages are not matched.
+    fn is_dropbox_url(&self, parsed_url: &ParsedUrl) -> bool {
+        let path = parsed_url.path();
+        match parsed_url.host_str() {
+            Some("paper.dropbox.com") => path.starts_with("/doc/"),
+            Some("www.dropbox.com" | "dropbox.com") => {
+                path.starts_with("/s/") || path.starts_with("/scl/fi/")
+            }
+            _ => false,
+        }
+    }
 }
 
 impl Default for UrlDetector {
@@ -391,6 +927,24 @@ impl Default for UrlDetector {
     }
 }
 
+/// Adds a detection candidate unless the type was already found with a
+/// higher confidence.
+fn add_candidate(candidates: &mut Vec<(UrlType, f32)>, url_type: UrlType, confidence: f32) {
+    if !candidates.iter().any(|(known, _)| *known == url_type) {
+        candidates.push((url_type, confidence));
+    }
+}
+
+/// Checks if a URL is a pre-signed storage URL, such as an S3 presigned URL
+/// or an Azure SAS URL.
+fn is_presigned(url: &ParsedUrl) -> bool {
+    url.query_pairs().any(|(key, _)| {
+        SIGNATURE_PARAMS
+            .iter()
+            .any(|param| key.eq_ignore_ascii_case(param))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -417,6 +971,14 @@ mod tests {
         let url = "https://drive.google.com/file/d/1234567890/view";
         let result = detector.detect_type(url).unwrap();
         assert_eq!(result, UrlType::GoogleDocs);
+
+        // Shared links and download links name the file in the query
+        for url in [
+            "https://drive.google.com/open?id=1234567890",
+            "https://drive.google.com/uc?id=1234567890&export=download",
+        ] {
+            assert_eq!(detector.detect_type(url).unwrap(), UrlType::GoogleDocs);
+        }
     }
 
     #[test]
@@ -427,6 +989,196 @@ mod tests {
         assert_eq!(result, UrlType::GitHubIssue);
     }
 
+    #[test]
+    fn test_custom_patterns_take_precedence() {
+        let mut detector = UrlDetector::new();
+        detector.add_pattern("github.com", Some("/acme/"), UrlType::Custom("acme".into()));
+        detector.add_pattern("*.wiki.example", None, UrlType::Custom("wiki".into()));
+
+        let detect = |url: &str| detector.detect_type(url).unwrap();
+        assert_eq!(
+            detect("https://github.com/acme/repo/issues/1"),
+            UrlType::Custom("acme".into())
+        );
+        assert_eq!(
+            detect("https://github.com/other/repo/issues/1"),
+            UrlType::GitHubIssue
+        );
+        assert_eq!(detect("https://eng.wiki.example/page"), UrlType::Custom("wiki".into()));
+        assert_eq!(detect("https://example.com/page"), UrlType::Html);
+        assert_eq!(UrlType::Custom("wiki".into()).to_string(), "wiki");
+    }
+
+    #[test]
+    fn test_registered_rules_by_priority() {
+        let mut detector = UrlDetector::new();
+        detector.register_rule(
+            RulePattern::host("wiki.corp.example"),
+            UrlType::Custom("wiki".into()),
+        );
+        detector.register_rule(
+            RulePattern::regex(r"/exports/.*\.pdf$")
+                .unwrap()
+                .priority(5),
+            UrlType::Pdf,
+        );
+        detector.register_rule(RulePattern::host("*.corp.example"), UrlType::Html);
+        detector.register_rule(
+            RulePattern::host("docs-?.corp.example").priority(1),
+            UrlType::Custom("docs".into()),
+        );
+
+        let detect = |url: &str| detector.detect_type(url).unwrap();
+        assert_eq!(
+            detect("https://wiki.corp.example/page"),
+            UrlType::Custom("wiki".into())
+        );
+        assert_eq!(
+            detect("https://wiki.corp.example/exports/a.pdf"),
+            UrlType::Pdf
+        );
+        assert_eq!(detect("https://corp.example/docs/a.pdf"), UrlType::Html);
+        assert_eq!(detect("https://a.b.corp.example/"), UrlType::Html);
+        assert_eq!(
+            detect("https://docs-1.corp.example/"),
+            UrlType::Custom("docs".into())
+        );
+        assert_eq!(detect("https://docs-12.corp.example/"), UrlType::Html);
+        assert_eq!(detect("https://corp.example.com/page"), UrlType::Html);
+        assert_eq!(
+            detect("https://github.com/o/r/issues/1"),
+            UrlType::GitHubIssue
+        );
+        assert_eq!(detect("./notes.md"), UrlType::LocalFile);
+
+        assert!(matches!(
+            RulePattern::regex("(unclosed"),
+            Err(MarkdownError::ConfigurationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_detect_with_confidence() {
+        let detector = UrlDetector::new();
+        for url in [
+            "https://example.com/article",
+            "https://github.com/o/r/pull/7",
+            "https://example.com/paper.pdf",
+        ] {
+            let candidates = detector.detect_with_confidence(url).unwrap();
+            assert_eq!(candidates[0].0, detector.detect_type(url).unwrap());
+            assert!(candidates.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        }
+
+        let candidates = detector
+            .detect_with_confidence("https://www.dropbox.com/s/abc/report.pdf")
+            .unwrap();
+        assert_eq!(
+            candidates,
+            vec![
+                (UrlType::Dropbox, 0.9),
+                (UrlType::Pdf, 0.7),
+                (UrlType::Html, 0.1)
+            ]
+        );
+        assert_eq!(
+            detector
+                .detect_with_confidence("https://example.com/article")
+                .unwrap(),
+            vec![(UrlType::Html, 0.5)]
+        );
+        assert_eq!(
+            detector.detect_with_confidence("./notes.md").unwrap(),
+            vec![(UrlType::LocalFile, 1.0)]
+        );
+        assert_eq!(
+            detector
+                .detect_with_confidence("https://example.com/analysis.ipynb")
+                .unwrap(),
+            vec![(UrlType::Notebook, 0.7), (UrlType::Html, 0.1)]
+        );
+        assert_eq!(
+            detector
+                .detect_with_confidence("https://example.com/docs/install.rst")
+                .unwrap(),
+            vec![(UrlType::Markup, 0.7), (UrlType::Html, 0.1)]
+        );
+        assert_eq!(
+            detector
+                .detect_with_confidence("https://example.com/archive/saved.mhtml")
+                .unwrap(),
+            vec![(UrlType::Email, 0.7), (UrlType::Html, 0.1)]
+        );
+        assert_eq!(
+            detector
+                .detect_with_confidence("https://github.com/owner/repo/blob/main/README.md")
+                .unwrap(),
+            vec![(UrlType::Markdown, 0.7), (UrlType::Html, 0.1)]
+        );
+        assert!(detector.detect_with_confidence("not a url").is_err());
+    }
+
+    #[test]
+    fn test_is_shortened_url() {
+        let detector = UrlDetector::new();
+        for url in [
+            "https://bit.ly/3xYzAbc",
+            "http://t.co/AbC123",
+            "https://goo.gl/maps/xyz",
+            "https://www.tinyurl.com/y7abc",
+        ] {
+            assert!(detector.is_shortened_url(url), "{url}");
+        }
+        for url in [
+            "https://bit.ly/",
+            "https://bit.ly.example.com/abc",
+            "https://docs.google.com/document/d/123/edit",
+            "./bit.ly/notes.md",
+        ] {
+            assert!(!detector.is_shortened_url(url), "{url}");
+        }
+    }
+
+    #[test]
+    fn test_internationalized_domains() {
+        let mut detector = UrlDetector::new();
+        detector.add_pattern("*.例え.jp", None, UrlType::Custom("intranet".into()));
+
+        let unicode = "https://社内.例え.jp/wiki";
+        let punycode = "https://xn--v6q418g.xn--r8jz45g.jp/wiki";
+        assert_eq!(detector.normalize_url(unicode).unwrap(), punycode);
+        assert_eq!(
+            detector.detect_type(unicode).unwrap(),
+            UrlType::Custom("intranet".into())
+        );
+        assert_eq!(
+            detector.detect_type(punycode).unwrap(),
+            UrlType::Custom("intranet".into())
+        );
+    }
+
+    #[test]
+    fn test_detect_github_enterprise_host() {
+        let mut detector = UrlDetector::new();
+        let url = "https://GitHub.Example.com/platform/api/pull/17";
+        assert_eq!(detector.detect_type(url).unwrap(), UrlType::Html);
+
+        detector.add_github_host("github.example.com");
+        assert_eq!(detector.detect_type(url).unwrap(), UrlType::GitHubIssue);
+        assert_eq!(
+            detector
+                .detect_type("https://github.example.com/platform/api/wiki")
+                .unwrap(),
+            UrlType::Html
+        );
+        assert_eq!(
+            detector
+                .detect_type("https://github.com/owner/repo/issues/1")
+                .unwrap(),
+            UrlType::GitHubIssue
+        );
+    }
+
     #[test]
     fn test_detect_html_fallback() {
         let detector = UrlDetector::new();
@@ -435,6 +1187,133 @@ mod tests {
         assert_eq!(result, UrlType::Html);
     }
 
+    #[test]
+    fn test_detect_data_uri() {
+        let detector = UrlDetector::new();
+        let url = "  data:text/markdown,%23%20Notes?utm_source=chat  ";
+
+        assert_eq!(detector.detect_type(url).unwrap(), UrlType::DataUri);
+        assert_eq!(
+            detector.normalize_url(url).unwrap(),
+            "data:text/markdown,%23%20Notes?utm_source=chat"
+        );
+        assert!(detector.validate_url(url).is_ok());
+        assert!(detector.validate_url("data:").is_err());
+    }
+
+    #[test]
+    fn test_detect_cloud_storage() {
+        let detector = UrlDetector::new();
+
+        for url in [
+            "s3://reports/2024/q1.pdf",
+            "gs://team-docs/handbook.md",
+            " az://container/notes/meeting.docx ",
+        ] {
+            assert_eq!(detector.detect_type(url).unwrap(), UrlType::CloudStorage);
+            assert_eq!(detector.normalize_url(url).unwrap(), url.trim());
+            assert!(detector.validate_url(url).is_ok());
+        }
+        assert!(detector.validate_url("s3:///missing-bucket").is_err());
+    }
+
+    #[test]
+    fn test_detect_office365_document() {
+        let detector = UrlDetector::new();
+
+        for url in [
+            "https://contoso.sharepoint.com/:w:/s/team/EaBcDeFgHiJk?e=4x2Yz",
+            "https://contoso-my.sharepoint.com/:p:/g/personal/jamie/EbCdEf",
+            "https://contoso.sharepoint.com/sites/team/Shared%20Documents/Plan.DOCX",
+            "https://1drv.ms/w/s!AbCdEfGh",
+            "https://onedrive.live.com/redir?resid=1231244193912!12&authkey=!AbC",
+        ] {
+            assert_eq!(
+                detector.detect_type(url).unwrap(),
+                UrlType::Office365,
+                "{url}"
+            );
+        }
+        for url in [
+            "https://contoso.sharepoint.com/sites/team/SitePages/Home.aspx",
+            "https://contoso.sharepoint.com/sites/team",
+            "https://onedrive.live.com/",
+            "https://example.com/:w:/s/team/EaBcDeFgHiJk",
+        ] {
+            assert_eq!(detector.detect_type(url).unwrap(), UrlType::Html, "{url}");
+        }
+    }
+
+    #[test]
+    fn test_detect_dropbox() {
+        let detector = UrlDetector::new();
+
+        for url in [
+            "https://paper.dropbox.com/doc/Release-Notes--AbCdEfGhIjKlMnOp-qRsTuVwXyZ",
+            "https://www.dropbox.com/s/a1b2c3d4e5/report.pdf?dl=0",
+            "https://dropbox.com/s/a1b2c3d4e5/notes.docx",
+            "https://www.dropbox.com/scl/fi/a1b2c3/plan.docx?rlkey=xyz&dl=0",
+        ] {
+            assert_eq!(
+                detector.detect_type(url).unwrap(),
+                UrlType::Dropbox,
+                "{url}"
+            );
+        }
+        for url in [
+            "https://www.dropbox.com/home",
+            "https://www.dropbox.com/scl/fo/a1b2c3/folder?rlkey=xyz",
+            "https://paper.dropbox.com/",
+            "https://example.com/s/a1b2c3d4e5/report.html",
+        ] {
+            assert_eq!(detector.detect_type(url).unwrap(), UrlType::Html, "{url}");
+        }
+    }
+
+    #[test]
+    fn test_detect_azure_devops_work_item() {
+        let detector = UrlDetector::new();
+
+        for url in [
+            "https://dev.azure.com/contoso/Fabrikam%20Web/_workitems/edit/42",
+            "https://contoso.visualstudio.com/Fabrikam/_workitems/edit/42/",
+        ] {
+            assert_eq!(detector.detect_type(url).unwrap(), UrlType::AzureDevOps);
+        }
+        for url in [
+            "https://dev.azure.com/contoso/Fabrikam/_workitems/recentlyupdated",
+            "https://dev.azure.com/contoso/Fabrikam/_git/web",
+            "https://example.com/contoso/Fabrikam/_workitems/edit/42",
+        ] {
+            assert_eq!(detector.detect_type(url).unwrap(), UrlType::Html);
+        }
+    }
+
+    #[test]
+    fn test_detect_ipfs() {
+        let detector = UrlDetector::new();
+        let url =
+            " ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi/wiki/Rust.html ";
+
+        assert_eq!(detector.detect_type(url).unwrap(), UrlType::Ipfs);
+        assert_eq!(detector.normalize_url(url).unwrap(), url.trim());
+        assert!(detector.validate_url(url).is_ok());
+        assert!(detector.validate_url("ipfs://not a cid/").is_err());
+        assert!(detector.detect_type("ipfs://").is_err());
+    }
+
+    #[test]
+    fn test_normalize_presigned_url_keeps_query() {
+        let detector = UrlDetector::new();
+        let s3 = "https://reports.s3.amazonaws.com/q1.pdf?X-Amz-Algorithm=AWS4-HMAC-SHA256&X-Amz-Credential=AKIA%2F20240101%2Fus-east-1%2Fs3%2Faws4_request&utm_source=mail&X-Amz-Signature=abc123";
+        let sas = "https://acct.blob.core.windows.net/docs/spec.docx?sv=2022-11-02&sp=r&sig=a%2Bb%3D";
+
+        assert_eq!(detector.normalize_url(s3).unwrap(), s3);
+        assert_eq!(detector.normalize_url(sas).unwrap(), sas);
+        assert_eq!(detector.detect_type(s3).unwrap(), UrlType::Pdf);
+        assert_eq!(detector.detect_type(sas).unwrap(), UrlType::Html);
+    }
+
     #[test]
     fn test_normalize_url_removes_tracking() {
         let detector = UrlDetector::new();
@@ -0,0 +1,166 @@
+//! In-memory DNS cache shared by every clone of the HTTP client.
+//!
+//! Large batches fetch thousands of pages from the same few hosts. Without a
+//! cache, each new connection resolves its host again. [`DnsCache`] keeps the
+//! addresses of each host for a configurable time to live, and is installed
+//! as the resolver of [`crate::client::HttpClient`] when
+//! [`crate::config::HttpConfig::dns_cache_ttl`] is set.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::dns::DnsCache;
+//! use std::time::Duration;
+//!
+//! # async fn example() -> std::io::Result<()> {
+//! let cache = DnsCache::new(Duration::from_secs(60));
+//! let addrs = cache.lookup("localhost").await?;
+//! assert!(!addrs.is_empty());
+//! assert!(cache.contains("localhost"));
+//! # Ok(())
+//! # }
+//! ```
+
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Addresses resolved for a host, and when they stop being reused.
+#[derive(Debug, Clone)]
+struct CachedAddrs {
+    addrs: Vec<SocketAddr>,
+    expires_at: Instant,
+}
+
+/// Thread-safe cache of resolved host addresses with a fixed time to live.
+///
+/// Cloning a cache shares the underlying entries.
+#[derive(Debug, Clone)]
+pub struct DnsCache {
+    ttl: Duration,
+    hosts: Arc<Mutex<HashMap<String, CachedAddrs>>>,
+}
+
+impl DnsCache {
+    /// Creates an empty cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `ttl` - How long the addresses of a host are reused before it is resolved again
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            hosts: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Returns how long resolved addresses are reused.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the addresses of a host, resolving it if no unexpired entry exists.
+    ///
+    /// Ports in the returned addresses are 0; the caller sets the port it connects to.
+    ///
+    /// # Arguments
+    ///
+    /// * `host` - The host name to resolve
+    ///
+    /// # Errors
+    ///
+    /// Returns the resolver's error if the host cannot be resolved. Failures are not cached.
+    pub async fn lookup(&self, host: &str) -> io::Result<Vec<SocketAddr>> {
+        if let Some(addrs) = self.cached(host) {
+            return Ok(addrs);
+        }
+
+        let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, 0)).await?.collect();
+        debug!("Resolved {} to {} addresses", host, addrs.len());
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        hosts.insert(
+            host.to_string(),
+            CachedAddrs {
+                addrs: addrs.clone(),
+                expires_at: Instant::now() + self.ttl,
+            },
+        );
+        Ok(addrs)
+    }
+
+    /// Returns true if the cache holds unexpired addresses for a host.
+    pub fn contains(&self, host: &str) -> bool {
+        self.cached(host).is_some()
+    }
+
+    /// Removes every entry, so each host is resolved again on its next connection.
+    pub fn clear(&self) {
+        self.hosts.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// Returns the unexpired addresses of a host, dropping an expired entry.
+    fn cached(&self, host: &str) -> Option<Vec<SocketAddr>> {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        match hosts.get(host) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.addrs.clone()),
+            Some(_) => {
+                hosts.remove(host);
+                None
+            }
+            None => None,
+        }
+    }
+}
+
+impl Resolve for DnsCache {
+    fn resolve(&self, name: Name) -> Resolving {
+        let cache = self.clone();
+        Box::pin(async move {
+            let addrs = cache.lookup(name.as_str()).await?;
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_lookup_is_cached_until_ttl() {
+        let cache = DnsCache::new(Duration::from_millis(50));
+        assert!(!cache.contains("localhost"));
+
+        let addrs = cache.lookup("localhost").await.unwrap();
+        assert!(addrs.iter().all(|addr| addr.ip().is_loopback()));
+        assert!(cache.contains("localhost"));
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(!cache.contains("localhost"));
+    }
+
+    #[tokio::test]
+    async fn test_clones_share_entries() {
+        let cache = DnsCache::new(Duration::from_secs(60));
+        let clone = cache.clone();
+        clone.lookup("localhost").await.unwrap();
+        assert!(cache.contains("localhost"));
+
+        cache.clear();
+        assert!(!clone.contains("localhost"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_through_reqwest_trait() {
+        let cache = DnsCache::new(Duration::from_secs(60));
+        let name: Name = "localhost".parse().unwrap();
+        let addrs: Vec<SocketAddr> = cache.resolve(name).await.unwrap().collect();
+        assert!(!addrs.is_empty());
+        assert!(cache.contains("localhost"));
+    }
+}
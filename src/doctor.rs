@@ -0,0 +1,491 @@
+//! Environment diagnostics.
+//!
+//! [`crate::MarkdownDown::doctor`] checks what most failed conversions turn
+//! out to be caused by: no network access, a rejected or expired API token,
+//! a missing external tool, or a build without an optional feature. Each
+//! check becomes a [`DoctorCheck`] in a [`DoctorReport`], which prints as one
+//! line per check.
+//!
+//! # Usage Examples
+//!
+//! ```rust,no_run
+//! use markdowndown::MarkdownDown;
+//!
+//! # async fn example() {
+//! let report = MarkdownDown::new().doctor().await;
+//! println!("{report}");
+//! if !report.is_healthy() {
+//!     for check in report.failures() {
+//!         eprintln!("{}: {}", check.name, check.detail);
+//!     }
+//! }
+//! # }
+//! ```
+
+use crate::client::HttpClient;
+use crate::config::Config;
+use crate::features::{self, OptionalFeature};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Default URL fetched to check network connectivity
+const CONNECTIVITY_URL: &str = "https://example.com/";
+
+/// Default Google endpoint that describes an access token
+const GOOGLE_TOKENINFO_URL: &str = "https://oauth2.googleapis.com/tokeninfo";
+
+/// Default GitHub API base URL
+const GITHUB_API_BASE_URL: &str = "https://api.github.com";
+
+/// Outcome of a single diagnostic check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum CheckStatus {
+    /// Everything the check looked at works
+    Ok,
+    /// Works, but something is likely to cause problems
+    Warning,
+    /// Broken; conversions that depend on it will fail
+    Failed,
+    /// Not checked, because nothing is configured that needs it
+    Skipped,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warning => "warning",
+            CheckStatus::Failed => "failed",
+            CheckStatus::Skipped => "skipped",
+        };
+        f.write_str(label)
+    }
+}
+
+/// The result of one diagnostic check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DoctorCheck {
+    /// What was checked, such as `github_token` or `tool:git`
+    pub name: String,
+    /// The outcome
+    pub status: CheckStatus,
+    /// What was found, or what went wrong
+    pub detail: String,
+}
+
+impl DoctorCheck {
+    /// Creates a check result.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - What was checked
+    /// * `status` - The outcome
+    /// * `detail` - What was found, or what went wrong
+    pub fn new(name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Every check run by [`crate::MarkdownDown::doctor`], in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DoctorReport {
+    /// The check results
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorReport {
+    /// Returns true if no check failed; warnings do not count.
+    pub fn is_healthy(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// Returns the checks that failed.
+    pub fn failures(&self) -> impl Iterator<Item = &DoctorCheck> {
+        self.checks
+            .iter()
+            .filter(|check| check.status == CheckStatus::Failed)
+    }
+
+    /// Returns the check with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The check's name, such as `connectivity`
+    pub fn check(&self, name: &str) -> Option<&DoctorCheck> {
+        self.checks.iter().find(|check| check.name == name)
+    }
+}
+
+impl fmt::Display for DoctorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            writeln!(f, "[{}] {}: {}", check.status, check.name, check.detail)?;
+        }
+        Ok(())
+    }
+}
+
+/// Endpoints and tools checked by [`crate::MarkdownDown::doctor_with_options`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DoctorOptions {
+    /// URL fetched to check network connectivity
+    pub connectivity_url: String,
+    /// Google endpoint that describes an access token
+    pub google_tokeninfo_url: String,
+    /// External tools looked for on the `PATH`, with what each is used for
+    pub tools: Vec<(String, String)>,
+}
+
+impl DoctorOptions {
+    /// Creates options checking `https://example.com/`, Google's tokeninfo
+    /// endpoint, and the `git` and `pandoc` tools.
+    pub fn new() -> Self {
+        Self {
+            connectivity_url: CONNECTIVITY_URL.to_string(),
+            google_tokeninfo_url: GOOGLE_TOKENINFO_URL.to_string(),
+            tools: vec![
+                (
+                    "git".to_string(),
+                    "cloning repositories for documentation conversion".to_string(),
+                ),
+                (
+                    "pandoc".to_string(),
+                    "DOCX conversion with the `pandoc` feature".to_string(),
+                ),
+            ],
+        }
+    }
+
+    /// Sets the URL fetched to check network connectivity.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - A URL that should always be reachable
+    pub fn connectivity_url(mut self, url: impl Into<String>) -> Self {
+        self.connectivity_url = url.into();
+        self
+    }
+
+    /// Sets the Google endpoint that describes an access token.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The tokeninfo URL, without query
+    pub fn google_tokeninfo_url(mut self, url: impl Into<String>) -> Self {
+        self.google_tokeninfo_url = url.into();
+        self
+    }
+
+    /// Sets the external tools looked for.
+    ///
+    /// # Arguments
+    ///
+    /// * `tools` - Tool names, each with what it is used for
+    pub fn tools<I, S, P>(mut self, tools: I) -> Self
+    where
+        I: IntoIterator<Item = (S, P)>,
+        S: Into<String>,
+        P: Into<String>,
+    {
+        self.tools = tools
+            .into_iter()
+            .map(|(tool, purpose)| (tool.into(), purpose.into()))
+            .collect();
+        self
+    }
+}
+
+impl Default for DoctorOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Runs every check with the instance's client and configuration.
+pub(crate) async fn diagnose(
+    client: &HttpClient,
+    config: &Config,
+    options: &DoctorOptions,
+) -> DoctorReport {
+    let (connectivity, github, google, service_account) = tokio::join!(
+        check_connectivity(client, &options.connectivity_url),
+        check_github_token(client, config),
+        check_google_token(client, config, &options.google_tokeninfo_url),
+        check_google_service_account(client, config),
+    );
+
+    let mut checks = vec![connectivity, github, google, service_account];
+    for (tool, purpose) in &options.tools {
+        checks.push(check_tool(tool, purpose).await);
+    }
+    checks.extend(features::optional_features().iter().map(check_feature));
+    DoctorReport { checks }
+}
+
+/// Checks that a well-known URL can be fetched.
+async fn check_connectivity(client: &HttpClient, url: &str) -> DoctorCheck {
+    match client.get_text(url).await {
+        Ok(_) => DoctorCheck::new("connectivity", CheckStatus::Ok, format!("Fetched {url}")),
+        Err(e) => DoctorCheck::new(
+            "connectivity",
+            CheckStatus::Failed,
+            format!("Could not fetch {url}: {e}"),
+        ),
+    }
+}
+
+/// GitHub's rate limit response, of which only the overall limit is read.
+#[derive(Debug, Deserialize)]
+struct GitHubRateLimit {
+    rate: GitHubRate,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubRate {
+    limit: u64,
+    remaining: u64,
+}
+
+/// Checks the GitHub token against the rate limit endpoint, which costs no quota.
+async fn check_github_token(client: &HttpClient, config: &Config) -> DoctorCheck {
+    const NAME: &str = "github_token";
+    if config.auth.github_token.is_none() {
+        return DoctorCheck::new(
+            NAME,
+            CheckStatus::Skipped,
+            "No token configured; GitHub allows 60 unauthenticated API requests an hour",
+        );
+    }
+
+    let base_url = config
+        .auth
+        .github_api_base_url
+        .as_deref()
+        .unwrap_or(GITHUB_API_BASE_URL)
+        .trim_end_matches('/');
+    let url = format!("{base_url}/rate_limit");
+    let response = match client.get_text(&url).await {
+        Ok(response) => response,
+        Err(e) => {
+            return DoctorCheck::new(NAME, CheckStatus::Failed, format!("Token rejected: {e}"))
+        }
+    };
+
+    match serde_json::from_str::<GitHubRateLimit>(&response) {
+        Ok(limit) if limit.rate.remaining == 0 => DoctorCheck::new(
+            NAME,
+            CheckStatus::Warning,
+            format!(
+                "Token accepted, but all {} requests this hour are used",
+                limit.rate.limit
+            ),
+        ),
+        Ok(limit) if limit.rate.limit <= 60 => DoctorCheck::new(
+            NAME,
+            CheckStatus::Warning,
+            "Requests were not authenticated; check the token and API base URL",
+        ),
+        Ok(limit) => DoctorCheck::new(
+            NAME,
+            CheckStatus::Ok,
+            format!(
+                "Token accepted; {} of {} requests left this hour",
+                limit.rate.remaining, limit.rate.limit
+            ),
+        ),
+        Err(e) => DoctorCheck::new(
+            NAME,
+            CheckStatus::Failed,
+            format!("Unexpected rate limit response from {url}: {e}"),
+        ),
+    }
+}
+
+/// Google's description of an access token.
+#[derive(Debug, Deserialize)]
+struct GoogleTokenInfo {
+    #[serde(default)]
+    scope: Option<String>,
+    #[serde(default)]
+    expires_in: Option<serde_json::Value>,
+}
+
+/// Checks the Google API token with Google's tokeninfo endpoint.
+async fn check_google_token(
+    client: &HttpClient,
+    config: &Config,
+    tokeninfo_url: &str,
+) -> DoctorCheck {
+    const NAME: &str = "google_api_key";
+    let Some(token) = &config.auth.google_api_key else {
+        return DoctorCheck::new(
+            NAME,
+            CheckStatus::Skipped,
+            "No token configured; only public Google Docs and Drive files can be converted",
+        );
+    };
+
+    let url = format!("{tokeninfo_url}?access_token={token}");
+    let response = match client.get_text(&url).await {
+        Ok(response) => response,
+        Err(e) => {
+            return DoctorCheck::new(
+                NAME,
+                CheckStatus::Failed,
+                format!("Token is invalid or expired: {e}"),
+            )
+        }
+    };
+
+    match serde_json::from_str::<GoogleTokenInfo>(&response) {
+        Ok(info) => {
+            // tokeninfo returns expires_in as a string
+            let expires_in = info
+                .expires_in
+                .map(|value| value.as_str().map_or(value.to_string(), str::to_string));
+            let mut detail = "Token accepted".to_string();
+            if let Some(seconds) = expires_in {
+                detail.push_str(&format!("; expires in {seconds}s"));
+            }
+            if let Some(scope) = info.scope {
+                detail.push_str(&format!("; scopes: {scope}"));
+            }
+            DoctorCheck::new(NAME, CheckStatus::Ok, detail)
+        }
+        Err(e) => DoctorCheck::new(
+            NAME,
+            CheckStatus::Failed,
+            format!("Unexpected tokeninfo response: {e}"),
+        ),
+    }
+}
+
+/// Checks that the Google service account key can mint an access token.
+async fn check_google_service_account(client: &HttpClient, config: &Config) -> DoctorCheck {
+    const NAME: &str = "google_service_account";
+    if config.auth.google_service_account_key.is_none() {
+        return DoctorCheck::new(
+            NAME,
+            CheckStatus::Skipped,
+            "No service account key configured",
+        );
+    }
+    let Some(tokens) = client.google_service_account() else {
+        return DoctorCheck::new(
+            NAME,
+            CheckStatus::Failed,
+            "The configured key is not a service account JSON key",
+        );
+    };
+
+    match tokens.access_token(&reqwest::Client::new()).await {
+        Ok(_) => DoctorCheck::new(
+            NAME,
+            CheckStatus::Ok,
+            format!("Minted an access token for {}", tokens.client_email()),
+        ),
+        Err(e) => DoctorCheck::new(
+            NAME,
+            CheckStatus::Failed,
+            format!("Could not mint an access token: {e}"),
+        ),
+    }
+}
+
+/// Reports whether an optional feature was compiled in.
+fn check_feature(feature: &OptionalFeature) -> DoctorCheck {
+    let name = format!("feature:{}", feature.name);
+    if feature.enabled {
+        DoctorCheck::new(name, CheckStatus::Ok, "Compiled in")
+    } else {
+        DoctorCheck::new(name, CheckStatus::Skipped, feature.hint())
+    }
+}
+
+/// Checks that an external tool can be run, reporting its version.
+async fn check_tool(tool: &str, purpose: &str) -> DoctorCheck {
+    let name = format!("tool:{tool}");
+    if tool == "pandoc" && !cfg!(feature = "pandoc") {
+        return DoctorCheck::new(
+            name,
+            CheckStatus::Skipped,
+            "Not used; built without the `pandoc` feature",
+        );
+    }
+
+    match tokio::process::Command::new(tool)
+        .arg("--version")
+        .output()
+        .await
+    {
+        Ok(output) if output.status.success() => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let version = stdout.lines().next().unwrap_or_default().trim();
+            DoctorCheck::new(name, CheckStatus::Ok, version)
+        }
+        Ok(output) => DoctorCheck::new(
+            name,
+            CheckStatus::Warning,
+            format!("`{tool} --version` exited with {}", output.status),
+        ),
+        Err(e) => DoctorCheck::new(
+            name,
+            CheckStatus::Warning,
+            format!("Not available ({e}); needed for {purpose}"),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_health_and_display() {
+        let mut report = DoctorReport {
+            checks: vec![
+                DoctorCheck::new(
+                    "connectivity",
+                    CheckStatus::Ok,
+                    "Fetched https://example.com/",
+                ),
+                DoctorCheck::new("tool:git", CheckStatus::Warning, "Not available"),
+            ],
+        };
+        assert!(report.is_healthy());
+        assert_eq!(
+            report.to_string(),
+            "[ok] connectivity: Fetched https://example.com/\n[warning] tool:git: Not available\n"
+        );
+
+        report.checks.push(DoctorCheck::new(
+            "github_token",
+            CheckStatus::Failed,
+            "Token rejected",
+        ));
+        assert!(!report.is_healthy());
+        assert_eq!(report.failures().count(), 1);
+        assert_eq!(
+            report.check("github_token").map(|check| check.status),
+            Some(CheckStatus::Failed)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_tool() {
+        let missing = check_tool("markdowndown-no-such-tool", "testing").await;
+        assert_eq!(missing.name, "tool:markdowndown-no-such-tool");
+        assert_eq!(missing.status, CheckStatus::Warning);
+        assert!(missing.detail.contains("needed for testing"));
+
+        let cargo = check_tool("cargo", "building").await;
+        assert_eq!(cargo.status, CheckStatus::Ok);
+        assert!(cargo.detail.starts_with("cargo "));
+    }
+}
@@ -0,0 +1,273 @@
+//! Stable tracing events for auditing conversions.
+//!
+//! Log messages are written for people and change between versions. The
+//! events below are meant for log pipelines instead: each is emitted with the
+//! target [`TARGET`], an `event` field holding its name, and the fields listed
+//! here. Names and fields are stable; new fields may be added, but existing
+//! ones are not renamed or removed.
+//!
+//! `fetch_completed` is emitted for every body fetched by an
+//! [`HttpClient`](crate::client::HttpClient). The other events are emitted by
+//! [`MarkdownDown::convert_url`](crate::MarkdownDown::convert_url) and the
+//! functions built on it.
+//!
+//! | Event | Level | Fields |
+//! |-------|-------|--------|
+//! | `conversion_started` | INFO | `url`, `url_type` |
+//! | `fetch_completed` | INFO | `url`, `status`, `bytes` |
+//! | `fallback_used` | WARN | `url`, `from`, `to`, `reason` |
+//! | `conversion_completed` | INFO | `url`, `url_type`, `chars`, `warnings` |
+//! | `conversion_failed` | WARN | `url`, `error` |
+//!
+//! * `url` is the normalized URL, or the URL as given if it could not be normalized.
+//! * `url_type`, `from`, and `to` are URL types as displayed, such as `HTML` or `GitHub Issue`.
+//! * `status` is the HTTP status code and `bytes` the response body size. For
+//!   streamed bodies `bytes` is the `Content-Length`, and is absent when the
+//!   server did not send one.
+//! * `reason` is the primary converter's error, or `race` when the fallback
+//!   won a race between fetch strategies.
+//! * `chars` is the length of the markdown in bytes, and `warnings` the
+//!   number of lint warnings found (0 when linting is off).
+//! * `error` is the error message.
+//!
+//! [`MarkdownDown::convert_url_detailed`](crate::MarkdownDown::convert_url_detailed)
+//! also collects the first response's final URL and status, and the
+//! warnings raised during the conversion, for its result.
+//!
+//! # Examples
+//!
+//! Select the events with a target filter, for example
+//! `RUST_LOG=markdowndown::events=info`, or in code:
+//!
+//! ```rust
+//! use tracing_subscriber::filter::Targets;
+//!
+//! let filter = Targets::new().with_target(markdowndown::events::TARGET, tracing::Level::INFO);
+//! ```
+
+use crate::progress;
+use crate::types::{MarkdownError, UrlType};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+/// Target of every audit event.
+pub const TARGET: &str = "markdowndown::events";
+
+/// A conversion of a URL has started.
+pub const CONVERSION_STARTED: &str = "conversion_started";
+
+/// An HTTP response has been received.
+pub const FETCH_COMPLETED: &str = "fetch_completed";
+
+/// The HTML converter produced the result in place of the detected converter.
+pub const FALLBACK_USED: &str = "fallback_used";
+
+/// A conversion of a URL has finished successfully.
+pub const CONVERSION_COMPLETED: &str = "conversion_completed";
+
+/// A conversion of a URL has failed.
+pub const CONVERSION_FAILED: &str = "conversion_failed";
+
+/// What a conversion observed about its fetches and problems.
+#[derive(Debug, Default)]
+pub(crate) struct Observations {
+    /// The URL the first response came from, after redirects
+    pub final_url: Option<String>,
+    /// The status code of the first response
+    pub http_status: Option<u16>,
+    /// Problems that did not stop the conversion
+    pub warnings: Vec<String>,
+    /// The URL type the conversion was routed by
+    pub url_type: Option<UrlType>,
+}
+
+tokio::task_local! {
+    static OBSERVATIONS: Arc<Mutex<Observations>>;
+}
+
+/// Runs a conversion, collecting what it observes while it runs.
+pub(crate) async fn observe<T>(conversion: impl Future<Output = T>) -> (T, Observations) {
+    let observations = Arc::new(Mutex::new(Observations::default()));
+    let output = OBSERVATIONS.scope(observations.clone(), conversion).await;
+    let observations = std::mem::take(&mut *observations.lock().unwrap_or_else(|e| e.into_inner()));
+    (output, observations)
+}
+
+/// Records a problem that did not stop the conversion.
+///
+/// Does nothing unless the current task is collecting observations; callers
+/// log the problem themselves.
+pub(crate) fn warning(message: impl Into<String>) {
+    let _ = OBSERVATIONS.try_with(|observations| {
+        observations
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .warnings
+            .push(message.into());
+    });
+}
+
+/// Emits `conversion_started`.
+pub(crate) fn conversion_started(url: &str, url_type: &UrlType) {
+    info!(
+        target: TARGET,
+        event = CONVERSION_STARTED,
+        url,
+        url_type = %url_type,
+        "Conversion started"
+    );
+    let _ = OBSERVATIONS.try_with(|observations| {
+        observations
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .url_type
+            .get_or_insert_with(|| url_type.clone());
+    });
+}
+
+/// Emits `fetch_completed`.
+///
+/// `final_url` is where the response came from after redirects.
+pub(crate) fn fetch_completed(url: &str, final_url: &str, status: u16, bytes: Option<u64>) {
+    progress::add_bytes(bytes.unwrap_or_default());
+    let _ = OBSERVATIONS.try_with(|observations| {
+        let mut observations = observations.lock().unwrap_or_else(|e| e.into_inner());
+        if observations.http_status.is_none() {
+            observations.final_url = Some(final_url.to_string());
+            observations.http_status = Some(status);
+        }
+    });
+    info!(
+        target: TARGET,
+        event = FETCH_COMPLETED,
+        url,
+        status,
+        bytes,
+        "Fetch completed"
+    );
+}
+
+/// Emits `fallback_used`.
+pub(crate) fn fallback_used(url: &str, from: &UrlType, to: &UrlType, reason: &str) {
+    warning(format!(
+        "Converted with the {to} converter instead of {from}: {reason}"
+    ));
+    warn!(
+        target: TARGET,
+        event = FALLBACK_USED,
+        url,
+        from = %from,
+        to = %to,
+        reason,
+        "Fallback used"
+    );
+}
+
+/// Emits `conversion_completed`.
+pub(crate) fn conversion_completed(url: &str, url_type: &UrlType, chars: usize, warnings: usize) {
+    info!(
+        target: TARGET,
+        event = CONVERSION_COMPLETED,
+        url,
+        url_type = %url_type,
+        chars,
+        warnings,
+        "Conversion completed"
+    );
+}
+
+/// Emits `conversion_failed`.
+pub(crate) fn conversion_failed(url: &str, error: &MarkdownError) {
+    warn!(
+        target: TARGET,
+        event = CONVERSION_FAILED,
+        url,
+        error = %error,
+        "Conversion failed"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MarkdownDown;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::Layer;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Collects the fields of every audit event.
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<HashMap<String, String>>>>);
+
+    impl Recorder {
+        fn events(&self) -> Vec<HashMap<String, String>> {
+            self.0.lock().unwrap().clone()
+        }
+    }
+
+    impl<S: tracing::Subscriber> Layer<S> for Recorder {
+        fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+            if event.metadata().target() == TARGET {
+                let mut fields = FieldMap::default();
+                event.record(&mut fields);
+                self.0.lock().unwrap().push(fields.0);
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct FieldMap(HashMap<String, String>);
+
+    impl Visit for FieldMap {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{value:?}"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_conversion_emits_audit_events() {
+        let mock_server = MockServer::start().await;
+        let body = "<h1>Audited</h1><p>Logged for the pipeline.</p>";
+        Mock::given(method("GET"))
+            .and(path("/page"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&mock_server)
+            .await;
+        let url = format!("{}/page", mock_server.uri());
+
+        let recorder = Recorder::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(recorder.clone()));
+        let md = MarkdownDown::new();
+        let markdown = md.convert_url(&url).await.unwrap();
+        assert!(md.convert_url("not a url").await.is_err());
+
+        let events = recorder.events();
+        let names: Vec<&str> = events.iter().map(|e| e["event"].as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                CONVERSION_STARTED,
+                FETCH_COMPLETED,
+                CONVERSION_COMPLETED,
+                CONVERSION_FAILED
+            ]
+        );
+        assert_eq!(events[0]["url_type"], "HTML");
+        assert_eq!(events[1]["status"], "200");
+        assert_eq!(events[1]["bytes"], body.len().to_string());
+        assert_eq!(events[2]["chars"], markdown.as_str().len().to_string());
+        assert_eq!(events[2]["warnings"], "0");
+        assert_eq!(events[3]["url"], "not a url");
+    }
+}
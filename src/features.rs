@@ -0,0 +1,145 @@
+//! Optional Cargo features compiled into this build.
+//!
+//! Some converters depend on optional features. A build without one still
+//! detects the URLs the feature handles, but converting them either fails or
+//! falls back to a less capable converter. This module lists the optional
+//! features and whether each was compiled in, so errors, warnings, and
+//! [`crate::MarkdownDown::doctor`] can name the feature that is missing.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::features;
+//!
+//! for feature in features::disabled_features() {
+//!     println!("{}: {}", feature.name, feature.hint());
+//! }
+//!
+//! let cloud = features::feature("cloud").unwrap();
+//! assert_eq!(cloud.enabled, cfg!(feature = "cloud"));
+//! ```
+
+use crate::types::{ConfigErrorKind, ErrorContext, MarkdownError};
+
+/// An optional Cargo feature of this crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OptionalFeature {
+    /// The feature's name, as passed to `--features`
+    pub name: &'static str,
+    /// Whether the feature was compiled in
+    pub enabled: bool,
+    /// What the feature enables, completing "rebuild with the feature to ..."
+    pub purpose: &'static str,
+}
+
+impl OptionalFeature {
+    /// Returns a sentence telling the user how to enable the feature.
+    pub fn hint(&self) -> String {
+        format!(
+            "Rebuild markdowndown with `--features {}` to {}",
+            self.name, self.purpose
+        )
+    }
+}
+
+/// Every optional feature, in the order declared in `Cargo.toml`.
+const OPTIONAL_FEATURES: &[OptionalFeature] = &[
+    OptionalFeature {
+        name: "pandoc",
+        enabled: cfg!(feature = "pandoc"),
+        purpose: "convert DOCX documents with pandoc, when installed",
+    },
+    OptionalFeature {
+        name: "cloud",
+        enabled: cfg!(feature = "cloud"),
+        purpose: "read s3://, gs:// and az:// URLs",
+    },
+    OptionalFeature {
+        name: "blocking",
+        enabled: cfg!(feature = "blocking"),
+        purpose: "call markdowndown from synchronous code through `markdowndown::blocking`",
+    },
+];
+
+/// Returns every optional feature, whether compiled in or not.
+pub fn optional_features() -> &'static [OptionalFeature] {
+    OPTIONAL_FEATURES
+}
+
+/// Returns the optional features this build was compiled without.
+pub fn disabled_features() -> impl Iterator<Item = &'static OptionalFeature> {
+    OPTIONAL_FEATURES.iter().filter(|feature| !feature.enabled)
+}
+
+/// Returns the optional feature with the given name.
+///
+/// # Arguments
+///
+/// * `name` - The feature's name, such as `cloud`
+pub fn feature(name: &str) -> Option<&'static OptionalFeature> {
+    OPTIONAL_FEATURES
+        .iter()
+        .find(|feature| feature.name == name)
+}
+
+/// Builds the error returned when a conversion needs a feature that is not compiled in.
+///
+/// # Arguments
+///
+/// * `name` - The missing feature's name
+/// * `url` - The URL being converted
+/// * `operation` - What was being attempted
+/// * `converter` - The converter that needs the feature
+// Unused when every feature that can fail a conversion is enabled
+#[cfg_attr(feature = "cloud", allow(dead_code))]
+pub(crate) fn missing_feature_error(
+    name: &str,
+    url: &str,
+    operation: &str,
+    converter: &str,
+) -> MarkdownError {
+    let hint = feature(name).map_or_else(
+        || format!("Rebuild markdowndown with `--features {name}`"),
+        OptionalFeature::hint,
+    );
+    MarkdownError::ConfigurationError {
+        kind: ConfigErrorKind::MissingDependency,
+        context: ErrorContext::new(url, operation, converter)
+            .with_info(format!("Built without the `{name}` feature. {hint}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_features_match_build() {
+        assert_eq!(feature("pandoc").unwrap().enabled, cfg!(feature = "pandoc"));
+        assert_eq!(feature("cloud").unwrap().enabled, cfg!(feature = "cloud"));
+        assert_eq!(feature("blocking").unwrap().enabled, cfg!(feature = "blocking"));
+        assert!(feature("renderer").is_none());
+        assert!(disabled_features().all(|feature| !feature.enabled));
+        assert_eq!(
+            feature("cloud").unwrap().hint(),
+            "Rebuild markdowndown with `--features cloud` to read s3://, gs:// and az:// URLs"
+        );
+    }
+
+    #[test]
+    fn test_missing_feature_error_names_feature() {
+        let error =
+            missing_feature_error("cloud", "s3://bucket/key", "Object store setup", "Cloud");
+        match error {
+            MarkdownError::ConfigurationError {
+                kind: ConfigErrorKind::MissingDependency,
+                context,
+            } => {
+                let info = context.additional_info.unwrap();
+                assert!(info.contains("Built without the `cloud` feature"));
+                assert!(info.contains("--features cloud"));
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,558 @@
+//! Allow/deny content filters.
+//!
+//! This module compiles a [`FilterConfig`] into a [`ContentFilter`] that can
+//! decide whether a URL should be converted at all. URL rules (hosts and paths)
+//! are evaluated before anything is fetched; content rules (MIME types and
+//! size) and the date window are evaluated against the headers of the first
+//! response a conversion fetches, before its body is read.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::config::FilterConfig;
+//! use markdowndown::filter::ContentFilter;
+//!
+//! let config = FilterConfig {
+//!     denied_hosts: vec!["*.ads.example.com".to_string()],
+//!     ..Default::default()
+//! };
+//! let filter = ContentFilter::new(&config).unwrap();
+//!
+//! assert!(filter.check_url("https://example.com/page").is_ok());
+//! assert!(filter.check_url("https://cdn.ads.example.com/page").is_err());
+//! ```
+
+use crate::client::ResponseMetadata;
+use crate::config::{DateWindowAction, FilterConfig};
+use crate::types::{ConfigErrorKind, ErrorContext, MarkdownError, SkipDetails, SkipReason};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tracing::info;
+use url::Url;
+
+/// Compiled allow/deny rules ready for evaluation.
+#[derive(Debug, Clone)]
+pub struct ContentFilter {
+    allowed_hosts: Vec<String>,
+    denied_hosts: Vec<String>,
+    allowed_paths: Vec<Regex>,
+    denied_paths: Vec<Regex>,
+    allowed_mime_types: Vec<String>,
+    denied_mime_types: Vec<String>,
+    max_content_length: Option<u64>,
+    modified_after: Option<DateTime<Utc>>,
+    modified_before: Option<DateTime<Utc>>,
+    date_window_action: DateWindowAction,
+}
+
+impl ContentFilter {
+    /// Compiles the filter rules from configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MarkdownError::ConfigurationError` if a path pattern is not a valid regex.
+    pub fn new(config: &FilterConfig) -> Result<Self, MarkdownError> {
+        Ok(Self {
+            allowed_hosts: lowercase_all(&config.allowed_hosts),
+            denied_hosts: lowercase_all(&config.denied_hosts),
+            allowed_paths: compile_patterns(&config.allowed_paths)?,
+            denied_paths: compile_patterns(&config.denied_paths)?,
+            allowed_mime_types: lowercase_all(&config.allowed_mime_types),
+            denied_mime_types: lowercase_all(&config.denied_mime_types),
+            max_content_length: config.max_content_length,
+            modified_after: config.modified_after,
+            modified_before: config.modified_before,
+            date_window_action: config.date_window_action,
+        })
+    }
+
+    /// Checks a URL against the host and path rules.
+    ///
+    /// When an allowlist of hosts is set, a URL that cannot be parsed or has
+    /// no host (a local path, a `file:` or `data:` URL) is rejected, since it
+    /// cannot be on the list. Without one, such URLs are not filtered here;
+    /// they are rejected later by URL validation with a more specific error.
+    pub fn check_url(&self, url: &str) -> Result<(), SkipReason> {
+        let no_host = || {
+            if self.allowed_hosts.is_empty() {
+                Ok(())
+            } else {
+                Err(SkipReason::NoHost {
+                    url: url.to_string(),
+                })
+            }
+        };
+        let parsed = match Url::parse(url) {
+            Ok(parsed) => parsed,
+            Err(_) => return no_host(),
+        };
+
+        match parsed.host_str() {
+            Some(host) => {
+                let host = host.to_ascii_lowercase();
+                if let Some(pattern) = self
+                    .denied_hosts
+                    .iter()
+                    .find(|pattern| host_matches(&host, pattern))
+                {
+                    return Err(SkipReason::HostDenied {
+                        host,
+                        pattern: pattern.clone(),
+                    });
+                }
+                if !self.allowed_hosts.is_empty()
+                    && !self
+                        .allowed_hosts
+                        .iter()
+                        .any(|pattern| host_matches(&host, pattern))
+                {
+                    return Err(SkipReason::HostNotAllowed { host });
+                }
+            }
+            None => no_host()?,
+        }
+
+        let path = parsed.path();
+        if let Some(pattern) = self.denied_paths.iter().find(|re| re.is_match(path)) {
+            return Err(SkipReason::PathDenied {
+                path: path.to_string(),
+                pattern: pattern.as_str().to_string(),
+            });
+        }
+        if !self.allowed_paths.is_empty() && !self.allowed_paths.iter().any(|re| re.is_match(path))
+        {
+            return Err(SkipReason::PathNotAllowed {
+                path: path.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Checks response metadata against the MIME type and size rules.
+    ///
+    /// Missing metadata never causes a skip: a rule is only applied when the
+    /// corresponding value is known.
+    ///
+    /// # Arguments
+    ///
+    /// * `content_type` - MIME type without parameters, if known
+    /// * `content_length` - Content size in bytes, if known
+    pub fn check_content(
+        &self,
+        content_type: Option<&str>,
+        content_length: Option<u64>,
+    ) -> Result<(), SkipReason> {
+        if let Some(mime_type) = content_type {
+            let mime_type = mime_type.to_ascii_lowercase();
+            if let Some(pattern) = self
+                .denied_mime_types
+                .iter()
+                .find(|pattern| mime_matches(&mime_type, pattern))
+            {
+                return Err(SkipReason::MimeTypeDenied {
+                    mime_type,
+                    pattern: pattern.clone(),
+                });
+            }
+            if !self.allowed_mime_types.is_empty()
+                && !self
+                    .allowed_mime_types
+                    .iter()
+                    .any(|pattern| mime_matches(&mime_type, pattern))
+            {
+                return Err(SkipReason::MimeTypeNotAllowed { mime_type });
+            }
+        }
+
+        if let (Some(size), Some(limit)) = (content_length, self.max_content_length) {
+            if size > limit {
+                return Err(SkipReason::TooLarge { size, limit });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks a document date against the configured date window.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The document's last-modified or published date
+    pub fn check_date(&self, date: DateTime<Utc>) -> Result<(), SkipReason> {
+        if let Some(cutoff) = self.modified_after {
+            if date < cutoff {
+                return Err(SkipReason::TooOld { date, cutoff });
+            }
+        }
+        if let Some(cutoff) = self.modified_before {
+            if date > cutoff {
+                return Err(SkipReason::TooNew { date, cutoff });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if any rule needs response metadata to evaluate.
+    pub fn has_content_rules(&self) -> bool {
+        !self.allowed_mime_types.is_empty()
+            || !self.denied_mime_types.is_empty()
+            || self.max_content_length.is_some()
+    }
+
+    /// Returns true if a date window is configured.
+    pub fn has_date_rules(&self) -> bool {
+        self.modified_after.is_some() || self.modified_before.is_some()
+    }
+
+    /// Returns the action to take for documents outside the date window.
+    pub fn date_window_action(&self) -> DateWindowAction {
+        self.date_window_action
+    }
+}
+
+/// The filter applied to the first response of a conversion.
+struct ResponseCheck {
+    filter: Arc<ContentFilter>,
+    checked: bool,
+    last_modified: Option<DateTime<Utc>>,
+    published_date: Option<DateTime<Utc>>,
+}
+
+tokio::task_local! {
+    static RESPONSE_CHECK: Arc<Mutex<ResponseCheck>>;
+}
+
+/// Runs a conversion, checking the first response it fetches against the
+/// filter's content rules and date window.
+///
+/// Returns the conversion's output with the document's date: the first
+/// response's `Last-Modified` date or, failing that, the published date the
+/// converter recorded with [`record_published_date`].
+pub(crate) async fn check_first_response<T>(
+    filter: Arc<ContentFilter>,
+    conversion: impl Future<Output = T>,
+) -> (T, Option<DateTime<Utc>>) {
+    let check = Arc::new(Mutex::new(ResponseCheck {
+        filter,
+        checked: false,
+        last_modified: None,
+        published_date: None,
+    }));
+    let output = RESPONSE_CHECK.scope(check.clone(), conversion).await;
+    let check = check.lock().unwrap_or_else(|e| e.into_inner());
+    (output, check.last_modified.or(check.published_date))
+}
+
+/// Records the published date a converter found in the document, so the date
+/// window applies whether or not frontmatter is written.
+///
+/// The date is only extracted inside [`check_first_response`]; the first
+/// recorded date wins.
+pub(crate) fn record_published_date(extract: impl FnOnce() -> Option<DateTime<Utc>>) {
+    let _ = RESPONSE_CHECK.try_with(|check| {
+        let mut check = check.lock().unwrap_or_else(|e| e.into_inner());
+        if check.published_date.is_none() {
+            check.published_date = extract();
+        }
+    });
+}
+
+/// Checks a response's headers against the filter of the current conversion.
+///
+/// Only the first response of a conversion is checked; later requests, such
+/// as API pages or attachments, are not. Does nothing outside
+/// [`check_first_response`].
+///
+/// # Returns
+///
+/// Returns the filter's maximum content length when it applies to this
+/// response, so the caller can enforce it on the bytes actually read:
+/// `Content-Length` is missing from chunked responses and may understate
+/// the body.
+///
+/// # Errors
+///
+/// Returns `MarkdownError::Skipped` if the response's content type or
+/// `Content-Length` breaks a content rule, or its `Last-Modified` date is
+/// outside a date window whose action is [`DateWindowAction::Skip`].
+pub(crate) fn check_response(
+    url: &str,
+    metadata: &ResponseMetadata,
+) -> Result<Option<u64>, MarkdownError> {
+    let result = RESPONSE_CHECK.try_with(|check| {
+        let mut check = check.lock().unwrap_or_else(|e| e.into_inner());
+        if std::mem::replace(&mut check.checked, true) {
+            return Ok(None);
+        }
+        check.last_modified = metadata.last_modified();
+        check_metadata(&check.filter, metadata).map(|()| check.filter.max_content_length)
+    });
+    match result {
+        Ok(Err(reason)) => Err(skipped(url, "Content filtering", reason)),
+        Ok(Ok(limit)) => Ok(limit),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Checks response metadata against the content rules and, for skipping
+/// windows, the date window.
+pub(crate) fn check_metadata(
+    filter: &ContentFilter,
+    metadata: &ResponseMetadata,
+) -> Result<(), SkipReason> {
+    filter.check_content(
+        metadata.content_type().as_deref(),
+        metadata.content_length(),
+    )?;
+    match metadata.last_modified() {
+        Some(date) if filter.date_window_action() == DateWindowAction::Skip => {
+            filter.check_date(date)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Builds the error returned for a URL skipped by a filter rule.
+pub(crate) fn skipped(url: &str, operation: &str, reason: SkipReason) -> MarkdownError {
+    info!("Skipping URL: {}", reason);
+    MarkdownError::Skipped(Box::new(SkipDetails {
+        reason,
+        context: ErrorContext::new(url, operation, "MarkdownDown"),
+    }))
+}
+
+/// Matches a host against a pattern, supporting a leading `*.` wildcard.
+pub(crate) fn host_matches(host: &str, pattern: &str) -> bool {
+    match pattern.strip_prefix("*.") {
+        Some(base_domain) => host == base_domain || host.ends_with(&format!(".{base_domain}")),
+        None => host == pattern,
+    }
+}
+
+/// Matches a MIME type against a pattern, supporting a `type/*` wildcard.
+fn mime_matches(mime_type: &str, pattern: &str) -> bool {
+    match pattern.strip_suffix("/*") {
+        Some(family) => mime_type
+            .split_once('/')
+            .is_some_and(|(mime_family, _)| mime_family == family),
+        None => mime_type == pattern,
+    }
+}
+
+fn lowercase_all(patterns: &[String]) -> Vec<String> {
+    patterns.iter().map(|p| p.to_ascii_lowercase()).collect()
+}
+
+fn compile_patterns(patterns: &[String]) -> Result<Vec<Regex>, MarkdownError> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            Regex::new(pattern).map_err(|e| MarkdownError::ConfigurationError {
+                kind: ConfigErrorKind::InvalidValue,
+                context: ErrorContext::new(pattern, "Compile path filter", "ContentFilter")
+                    .with_info(format!("Invalid regex: {e}")),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(config: FilterConfig) -> ContentFilter {
+        ContentFilter::new(&config).unwrap()
+    }
+
+    #[test]
+    fn test_empty_filter_allows_everything() {
+        let filter = filter(FilterConfig::default());
+        assert!(filter.check_url("https://example.com/any/path").is_ok());
+        assert!(filter
+            .check_content(Some("application/pdf"), Some(u64::MAX))
+            .is_ok());
+        assert!(!filter.has_content_rules());
+    }
+
+    #[test]
+    fn test_denied_host_wildcard() {
+        let filter = filter(FilterConfig {
+            denied_hosts: vec!["*.tracker.com".to_string()],
+            ..Default::default()
+        });
+
+        assert_eq!(
+            filter.check_url("https://cdn.Tracker.com/x"),
+            Err(SkipReason::HostDenied {
+                host: "cdn.tracker.com".to_string(),
+                pattern: "*.tracker.com".to_string(),
+            })
+        );
+        assert!(filter.check_url("https://tracker.com/x").is_err());
+        assert!(filter.check_url("https://nottracker.com/x").is_ok());
+    }
+
+    #[test]
+    fn test_allowed_hosts() {
+        let filter = filter(FilterConfig {
+            allowed_hosts: vec!["docs.rs".to_string(), "*.github.com".to_string()],
+            denied_hosts: vec!["gist.github.com".to_string()],
+            ..Default::default()
+        });
+
+        assert!(filter.check_url("https://docs.rs/serde").is_ok());
+        assert!(filter.check_url("https://api.github.com/repos").is_ok());
+        assert_eq!(
+            filter.check_url("https://example.com/"),
+            Err(SkipReason::HostNotAllowed {
+                host: "example.com".to_string()
+            })
+        );
+        // Deny rules take precedence over allow rules
+        assert!(matches!(
+            filter.check_url("https://gist.github.com/abc"),
+            Err(SkipReason::HostDenied { .. })
+        ));
+    }
+
+    #[test]
+    fn test_allowed_hosts_reject_urls_without_a_host() {
+        let allowlist = filter(FilterConfig {
+            allowed_hosts: vec!["docs.rs".to_string()],
+            ..Default::default()
+        });
+        let unrestricted = filter(FilterConfig::default());
+
+        for url in ["/tmp/page.html", "file:///etc/passwd", "data:text/plain,hi"] {
+            assert_eq!(
+                allowlist.check_url(url),
+                Err(SkipReason::NoHost {
+                    url: url.to_string()
+                })
+            );
+            // Without an allowlist they are left to URL validation
+            assert!(unrestricted.check_url(url).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_path_rules() {
+        let filter = filter(FilterConfig {
+            allowed_paths: vec![r"^/docs/".to_string()],
+            denied_paths: vec![r"\.zip$".to_string()],
+            ..Default::default()
+        });
+
+        assert!(filter.check_url("https://example.com/docs/intro").is_ok());
+        assert!(matches!(
+            filter.check_url("https://example.com/docs/archive.zip"),
+            Err(SkipReason::PathDenied { .. })
+        ));
+        assert_eq!(
+            filter.check_url("https://example.com/blog/post"),
+            Err(SkipReason::PathNotAllowed {
+                path: "/blog/post".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_path_regex() {
+        let result = ContentFilter::new(&FilterConfig {
+            denied_paths: vec!["(unclosed".to_string()],
+            ..Default::default()
+        });
+
+        assert!(matches!(
+            result,
+            Err(MarkdownError::ConfigurationError {
+                kind: ConfigErrorKind::InvalidValue,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_mime_type_rules() {
+        let filter = filter(FilterConfig {
+            allowed_mime_types: vec!["text/*".to_string(), "application/json".to_string()],
+            denied_mime_types: vec!["text/csv".to_string()],
+            ..Default::default()
+        });
+
+        assert!(filter.check_content(Some("text/html"), None).is_ok());
+        assert!(filter.check_content(Some("application/json"), None).is_ok());
+        assert!(matches!(
+            filter.check_content(Some("text/csv"), None),
+            Err(SkipReason::MimeTypeDenied { .. })
+        ));
+        assert_eq!(
+            filter.check_content(Some("Application/PDF"), None),
+            Err(SkipReason::MimeTypeNotAllowed {
+                mime_type: "application/pdf".to_string()
+            })
+        );
+        // Unknown content type is not filtered
+        assert!(filter.check_content(None, None).is_ok());
+    }
+
+    #[test]
+    fn test_max_content_length() {
+        let filter = filter(FilterConfig {
+            max_content_length: Some(1000),
+            ..Default::default()
+        });
+
+        assert!(filter.has_content_rules());
+        assert!(filter.check_content(None, Some(1000)).is_ok());
+        assert!(filter.check_content(None, None).is_ok());
+        assert_eq!(
+            filter.check_content(None, Some(1001)),
+            Err(SkipReason::TooLarge {
+                size: 1001,
+                limit: 1000
+            })
+        );
+    }
+
+    #[test]
+    fn test_date_window() {
+        let date = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let filter = filter(FilterConfig {
+            modified_after: Some(date("2024-01-01T00:00:00Z")),
+            modified_before: Some(date("2024-12-31T00:00:00Z")),
+            ..Default::default()
+        });
+
+        assert!(filter.has_date_rules());
+        assert!(!filter.has_content_rules());
+        assert!(filter.check_date(date("2024-06-01T00:00:00Z")).is_ok());
+        assert_eq!(
+            filter.check_date(date("2023-06-01T00:00:00Z")),
+            Err(SkipReason::TooOld {
+                date: date("2023-06-01T00:00:00Z"),
+                cutoff: date("2024-01-01T00:00:00Z"),
+            })
+        );
+        assert!(matches!(
+            filter.check_date(date("2025-06-01T00:00:00Z")),
+            Err(SkipReason::TooNew { .. })
+        ));
+        assert_eq!(filter.date_window_action(), DateWindowAction::Skip);
+    }
+
+    #[test]
+    fn test_skip_reason_display() {
+        let reason = SkipReason::TooLarge {
+            size: 2048,
+            limit: 1024,
+        };
+        assert_eq!(
+            reason.to_string(),
+            "content size 2048 bytes exceeds limit of 1024 bytes"
+        );
+    }
+}
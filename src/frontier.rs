@@ -0,0 +1,379 @@
+//! Persistent crawl frontier with priority scoring.
+//!
+//! The frontier remembers every discovered URL across runs, together with the
+//! signals used to decide what to fetch next under a page budget:
+//!
+//! - **Depth**: clicks from the nearest seed; shallow pages matter more
+//! - **Inlinks**: how many crawled pages link here; popular pages matter more
+//! - **Change frequency**: how often the content changed between visits
+//! - **Staleness**: how long ago the page was last fetched
+//!
+//! Unvisited pages always score as likely to change, so new pages are fetched
+//! ahead of static archive pages that have never changed.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::frontier::CrawlFrontier;
+//! use chrono::Utc;
+//!
+//! let mut frontier = CrawlFrontier::new();
+//! frontier.add_seed("https://example.com");
+//! frontier.discover("https://example.com/about", 1);
+//!
+//! for url in frontier.next_batch(10, Utc::now()) {
+//!     // fetch and convert `url`, then:
+//!     frontier.record_visit(&url, "# converted markdown", Utc::now());
+//! }
+//! ```
+
+use crate::identity::{canonical_url, content_fingerprint};
+use crate::types::{ContentErrorKind, ConverterErrorKind, ErrorContext, MarkdownError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Days after a visit at which a page is treated as about two-thirds stale.
+const STALENESS_DAYS: f64 = 7.0;
+
+/// Crawl state for a single URL.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrontierEntry {
+    /// The canonical URL
+    pub url: String,
+    /// Fewest links followed from a seed to reach this URL
+    pub depth: u32,
+    /// Number of times the URL was discovered as a link
+    pub inlinks: u32,
+    /// Number of completed visits
+    pub visits: u32,
+    /// Number of visits whose content differed from the previous visit
+    pub changes: u32,
+    /// When the URL was last fetched
+    pub last_visited: Option<DateTime<Utc>>,
+    /// Fingerprint of the content at the last visit
+    pub fingerprint: Option<String>,
+}
+
+impl FrontierEntry {
+    fn new(url: String, depth: u32) -> Self {
+        Self {
+            url,
+            depth,
+            inlinks: 0,
+            visits: 0,
+            changes: 0,
+            last_visited: None,
+            fingerprint: None,
+        }
+    }
+
+    /// Returns the estimated probability that a visit finds changed content.
+    ///
+    /// Uses add-one smoothing so pages with few visits are neither assumed
+    /// static nor assumed to change every time.
+    pub fn change_rate(&self) -> f64 {
+        f64::from(self.changes + 1) / f64::from(self.visits + 2)
+    }
+
+    /// Returns the crawl priority of this URL at the given time; higher is sooner.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The time the next crawl runs
+    pub fn priority(&self, now: DateTime<Utc>) -> f64 {
+        let importance = (1.0 + f64::from(self.inlinks).ln_1p()) / f64::from(self.depth + 1);
+        let change_likelihood = match self.last_visited {
+            None => 1.0,
+            Some(visited) => {
+                let days = (now - visited).num_seconds().max(0) as f64 / 86_400.0;
+                self.change_rate() * (1.0 - (-days / STALENESS_DAYS).exp())
+            }
+        };
+        importance * change_likelihood
+    }
+}
+
+/// On-disk representation of the frontier.
+#[derive(Serialize, Deserialize)]
+struct FrontierFile {
+    version: u32,
+    entries: Vec<FrontierEntry>,
+}
+
+/// The set of known URLs for an incremental crawl.
+#[derive(Debug, Clone, Default)]
+pub struct CrawlFrontier {
+    entries: HashMap<String, FrontierEntry>,
+}
+
+impl CrawlFrontier {
+    /// Creates an empty frontier.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a frontier saved by [`CrawlFrontier::save`].
+    ///
+    /// A missing file yields an empty frontier, so the first crawl and later
+    /// incremental crawls share the same code path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The frontier file to read
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ContentError` - If the file cannot be read or parsed
+    pub async fn load(path: impl AsRef<Path>) -> Result<Self, MarkdownError> {
+        let path = path.as_ref();
+        let error_context = || {
+            ErrorContext::new(path.display().to_string(), "Load crawl frontier", "CrawlFrontier")
+        };
+
+        let json = match tokio::fs::read_to_string(path).await {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::new()),
+            Err(e) => {
+                return Err(MarkdownError::ContentError {
+                    kind: ContentErrorKind::ParsingFailed,
+                    context: error_context().with_info(format!("IO error: {e}")),
+                })
+            }
+        };
+
+        let file: FrontierFile =
+            serde_json::from_str(&json).map_err(|e| MarkdownError::ContentError {
+                kind: ContentErrorKind::ParsingFailed,
+                context: error_context().with_info(format!("Invalid frontier file: {e}")),
+            })?;
+
+        Ok(Self {
+            entries: file
+                .entries
+                .into_iter()
+                .map(|entry| (entry.url.clone(), entry))
+                .collect(),
+        })
+    }
+
+    /// Saves the frontier as JSON.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The frontier file to write
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ConverterError` - If the file cannot be written
+    pub async fn save(&self, path: impl AsRef<Path>) -> Result<(), MarkdownError> {
+        let path = path.as_ref();
+        let mut entries: Vec<FrontierEntry> = self.entries.values().cloned().collect();
+        entries.sort_by(|a, b| a.url.cmp(&b.url));
+
+        let json = serde_json::to_string_pretty(&FrontierFile {
+            version: 1,
+            entries,
+        })
+        .map_err(|e| MarkdownError::ParseError {
+            message: format!("Failed to serialize crawl frontier: {e}"),
+        })?;
+
+        tokio::fs::write(path, json)
+            .await
+            .map_err(|e| MarkdownError::ConverterError {
+                kind: ConverterErrorKind::ProcessingError,
+                context: ErrorContext::new(
+                    path.display().to_string(),
+                    "Save crawl frontier",
+                    "CrawlFrontier",
+                )
+                .with_info(format!("IO error: {e}")),
+            })
+    }
+
+    /// Adds a seed URL at depth zero.
+    pub fn add_seed(&mut self, url: &str) {
+        let url = canonical_url(url);
+        self.entries
+            .entry(url.clone())
+            .or_insert_with(|| FrontierEntry::new(url, 0))
+            .depth = 0;
+    }
+
+    /// Records that a crawled page links to `url`.
+    ///
+    /// New URLs are added at the given depth; known URLs gain an inlink and
+    /// keep the shallowest depth seen.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The discovered link target
+    /// * `depth` - The depth of the linking page plus one
+    pub fn discover(&mut self, url: &str, depth: u32) {
+        let url = canonical_url(url);
+        let entry = self
+            .entries
+            .entry(url.clone())
+            .or_insert_with(|| FrontierEntry::new(url, depth));
+        entry.inlinks += 1;
+        entry.depth = entry.depth.min(depth);
+    }
+
+    /// Records a completed visit and whether the content changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The visited URL
+    /// * `content` - The converted content, used to detect changes
+    /// * `at` - When the visit happened
+    ///
+    /// # Returns
+    ///
+    /// True if the content differs from the previous visit (or this is the first visit).
+    pub fn record_visit(&mut self, url: &str, content: &str, at: DateTime<Utc>) -> bool {
+        let url = canonical_url(url);
+        let entry = self
+            .entries
+            .entry(url.clone())
+            .or_insert_with(|| FrontierEntry::new(url, 0));
+
+        let fingerprint = content_fingerprint(content);
+        let changed = entry.fingerprint.as_deref() != Some(fingerprint.as_str());
+        if changed && entry.visits > 0 {
+            entry.changes += 1;
+        }
+        entry.visits += 1;
+        entry.last_visited = Some(at);
+        entry.fingerprint = Some(fingerprint);
+        changed
+    }
+
+    /// Returns up to `budget` URLs to fetch next, highest priority first.
+    ///
+    /// # Arguments
+    ///
+    /// * `budget` - Maximum number of pages to fetch
+    /// * `now` - The time the crawl runs, used for staleness
+    pub fn next_batch(&self, budget: usize, now: DateTime<Utc>) -> Vec<String> {
+        let mut scored: Vec<(f64, &str)> = self
+            .entries
+            .values()
+            .map(|entry| (entry.priority(now), entry.url.as_str()))
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+        scored
+            .into_iter()
+            .take(budget)
+            .map(|(_, url)| url.to_string())
+            .collect()
+    }
+
+    /// Returns the entry for a URL, if known.
+    pub fn get(&self, url: &str) -> Option<&FrontierEntry> {
+        self.entries.get(&canonical_url(url))
+    }
+
+    /// Returns the number of known URLs.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if no URLs are known.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_discover_tracks_depth_and_inlinks() {
+        let mut frontier = CrawlFrontier::new();
+        frontier.add_seed("https://example.com/");
+        frontier.discover("https://example.com/a", 2);
+        frontier.discover("https://example.com/a/", 1);
+
+        let entry = frontier.get("https://example.com/a").unwrap();
+        assert_eq!((entry.depth, entry.inlinks), (1, 2));
+        assert_eq!(frontier.len(), 2);
+    }
+
+    #[test]
+    fn test_record_visit_counts_changes() {
+        let mut frontier = CrawlFrontier::new();
+        let now = Utc::now();
+
+        assert!(frontier.record_visit("https://example.com", "v1", now));
+        assert!(!frontier.record_visit("https://example.com", "v1", now));
+        assert!(frontier.record_visit("https://example.com", "v2", now));
+
+        let entry = frontier.get("https://example.com").unwrap();
+        assert_eq!((entry.visits, entry.changes), (3, 1));
+        assert_eq!(entry.change_rate(), 2.0 / 5.0);
+    }
+
+    #[test]
+    fn test_next_batch_prefers_unvisited_changing_and_important_pages() {
+        let mut frontier = CrawlFrontier::new();
+        let now = Utc::now();
+        let last_month = now - Duration::days(30);
+
+        // A static archive page, visited often and never changed
+        for _ in 0..10 {
+            frontier.record_visit("https://example.com/archive/2010", "same", last_month);
+        }
+        // A frequently changing page, visited yesterday
+        for version in 0..5 {
+            frontier.record_visit(
+                "https://example.com/news",
+                &version.to_string(),
+                now - Duration::days(1),
+            );
+        }
+        // A new page found deep in the site, and a popular one near the top
+        frontier.discover("https://example.com/deep/page", 4);
+        frontier.discover("https://example.com/popular", 1);
+        frontier.discover("https://example.com/popular", 1);
+
+        assert_eq!(
+            frontier.next_batch(3, now),
+            vec![
+                "https://example.com/popular",
+                "https://example.com/deep/page",
+                "https://example.com/news",
+            ]
+        );
+        assert_eq!(
+            frontier.next_batch(10, now).last().map(String::as_str),
+            Some("https://example.com/archive/2010")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("frontier.json");
+
+        // Missing files start an empty frontier
+        assert!(CrawlFrontier::load(&path).await.unwrap().is_empty());
+
+        let mut frontier = CrawlFrontier::new();
+        frontier.add_seed("https://example.com");
+        frontier.record_visit("https://example.com", "content", Utc::now());
+        frontier.save(&path).await.unwrap();
+
+        let loaded = CrawlFrontier::load(&path).await.unwrap();
+        assert_eq!(loaded.get("https://example.com"), frontier.get("https://example.com"));
+
+        tokio::fs::write(&path, "not json").await.unwrap();
+        assert!(matches!(
+            CrawlFrontier::load(&path).await,
+            Err(MarkdownError::ContentError { .. })
+        ));
+    }
+}
@@ -4,6 +4,10 @@
 //! in markdown documents. It includes a builder pattern for constructing frontmatter
 //! and helper functions for combining frontmatter with content.
 //!
+//! Frontmatter is generated as YAML and can be converted to TOML or JSON with
+//! [`convert_frontmatter`], for static site generators such as Hugo. The
+//! functions that read and update frontmatter accept all three formats.
+//!
 //! # Usage Examples
 //!
 //! ## Basic Frontmatter Building
@@ -44,6 +48,73 @@ use crate::types::{Frontmatter, MarkdownError, Url};
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 
+/// The format of a document's frontmatter block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum FrontmatterFormat {
+    /// YAML between `---` lines
+    #[default]
+    Yaml,
+    /// TOML between `+++` lines
+    Toml,
+    /// A JSON object at the start of the document
+    Json,
+}
+
+/// Decides the frontmatter fields written for each converted document.
+///
+/// The provider is offered every field markdowndown generated, including
+/// custom fields and `document_id` and `slug`, along with the document body,
+/// and returns the fields to write instead. It can rename keys, nest values,
+/// drop fields, or return an empty mapping to remove the frontmatter block.
+/// Closures taking the generated fields and the body implement this trait.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdowndown::frontmatter::{apply_frontmatter_provider, FrontmatterProvider};
+/// use serde_yaml::{Mapping, Value};
+///
+/// struct HugoParams;
+///
+/// impl FrontmatterProvider for HugoParams {
+///     fn frontmatter(&self, generated: &Mapping, _content: &str) -> Mapping {
+///         let mut params = Mapping::new();
+///         params.insert("source".into(), generated["source_url"].clone());
+///
+///         let mut fields = Mapping::new();
+///         fields.insert("title".into(), generated["title"].clone());
+///         fields.insert("params".into(), Value::Mapping(params));
+///         fields
+///     }
+/// }
+///
+/// let markdown = "---\nsource_url: https://example.com/post\ntitle: Post\nexporter: markdowndown\n---\n\n# Post\n";
+/// let updated = apply_frontmatter_provider(markdown, &HugoParams);
+/// assert_eq!(
+///     updated,
+///     "---\ntitle: Post\nparams:\n  source: https://example.com/post\n---\n\n# Post\n"
+/// );
+/// ```
+pub trait FrontmatterProvider: Send + Sync {
+    /// Returns the frontmatter fields to write for a document.
+    ///
+    /// # Arguments
+    ///
+    /// * `generated` - The fields markdowndown generated
+    /// * `content` - The document body without the frontmatter
+    fn frontmatter(&self, generated: &serde_yaml::Mapping, content: &str) -> serde_yaml::Mapping;
+}
+
+impl<F> FrontmatterProvider for F
+where
+    F: Fn(&serde_yaml::Mapping, &str) -> serde_yaml::Mapping + Send + Sync,
+{
+    fn frontmatter(&self, generated: &serde_yaml::Mapping, content: &str) -> serde_yaml::Mapping {
+        self(generated, content)
+    }
+}
+
 /// Builder for constructing YAML frontmatter with validation and flexibility.
 ///
 /// This builder provides a fluent interface for creating frontmatter with required
@@ -54,6 +125,7 @@ pub struct FrontmatterBuilder {
     exporter: Option<String>,
     download_date: Option<DateTime<Utc>>,
     additional_fields: HashMap<String, String>,
+    list_fields: HashMap<String, Vec<String>>,
 }
 
 impl FrontmatterBuilder {
@@ -76,6 +148,7 @@ impl FrontmatterBuilder {
             exporter: None,
             download_date: None,
             additional_fields: HashMap::new(),
+            list_fields: HashMap::new(),
         }
     }
 
@@ -139,6 +212,30 @@ impl FrontmatterBuilder {
         self
     }
 
+    /// Adds a custom field whose value is a YAML sequence of strings.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The field name
+    /// * `values` - The items of the sequence
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::frontmatter::FrontmatterBuilder;
+    ///
+    /// let frontmatter = FrontmatterBuilder::new("https://example.com".to_string())
+    ///     .list_field("tags".to_string(), vec!["rust".to_string(), "async".to_string()])
+    ///     .build()?;
+    ///
+    /// assert!(frontmatter.contains("tags:\n- rust\n- async\n"));
+    /// # Ok::<(), markdowndown::types::MarkdownError>(())
+    /// ```
+    pub fn list_field(mut self, key: String, values: Vec<String>) -> Self {
+        self.list_fields.insert(key, values);
+        self
+    }
+
     /// Builds the YAML frontmatter string.
     ///
     /// This method validates the source URL, creates a Frontmatter struct, and serializes
@@ -171,7 +268,7 @@ impl FrontmatterBuilder {
     pub fn build(self) -> Result<String, MarkdownError> {
         // Store values for error messages before they get moved
         let source_url_str = self.source_url.clone();
-        let additional_fields_count = self.additional_fields.len();
+        let additional_fields_count = self.additional_fields.len() + self.list_fields.len();
 
         // Validate and create URL
         let url = Url::new(self.source_url)?;
@@ -192,7 +289,7 @@ impl FrontmatterBuilder {
             })?;
 
         // Add additional fields if any
-        if !self.additional_fields.is_empty() {
+        if additional_fields_count > 0 {
             // Parse the existing YAML to add additional fields
             let mut yaml_value: serde_yaml::Value =
                 serde_yaml::from_str(&yaml_content).map_err(|e| MarkdownError::ParseError {
@@ -210,6 +307,14 @@ impl FrontmatterBuilder {
                         serde_yaml::Value::String(value),
                     );
                 }
+                for (key, values) in self.list_fields {
+                    map.insert(
+                        serde_yaml::Value::String(key),
+                        serde_yaml::Value::Sequence(
+                            values.into_iter().map(serde_yaml::Value::String).collect(),
+                        ),
+                    );
+                }
             }
 
             yaml_content =
@@ -276,20 +381,333 @@ pub fn combine_frontmatter_and_content(frontmatter: &str, content: &str) -> Stri
 /// assert!(frontmatter.is_some());
 /// ```
 pub fn extract_frontmatter(markdown: &str) -> Option<Frontmatter> {
-    // Check if markdown starts with frontmatter delimiters
-    if !markdown.starts_with("---\n") {
-        return None;
+    let (format, fields, _) = split_frontmatter(markdown)?;
+    serde_yaml::from_value(parse_fields(format, fields)?).ok()
+}
+
+/// Reads a single string field from a markdown document's frontmatter.
+///
+/// # Arguments
+///
+/// * `markdown` - The complete markdown document potentially containing frontmatter
+/// * `key` - The top-level frontmatter key to look up
+///
+/// # Returns
+///
+/// The field value if the document has frontmatter and the field is a scalar,
+/// or `None` otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdowndown::frontmatter::frontmatter_field;
+///
+/// let markdown = "---\ntitle: Hello\n---\n\n# Content";
+/// assert_eq!(frontmatter_field(markdown, "title"), Some("Hello".to_string()));
+/// assert_eq!(frontmatter_field(markdown, "author"), None);
+/// ```
+pub fn frontmatter_field(markdown: &str, key: &str) -> Option<String> {
+    let (format, fields, _) = split_frontmatter(markdown)?;
+    let yaml_value = parse_fields(format, fields)?;
+
+    match yaml_value.get(key)? {
+        serde_yaml::Value::String(value) => Some(value.clone()),
+        serde_yaml::Value::Number(value) => Some(value.to_string()),
+        serde_yaml::Value::Bool(value) => Some(value.to_string()),
+        _ => None,
     }
+}
 
-    // Find the closing delimiter
-    let content_after_start = &markdown[4..]; // Skip "---\n"
-    if let Some(end_pos) = content_after_start.find("\n---\n") {
-        let yaml_content = &content_after_start[..end_pos];
+/// Reads a list of strings from a markdown document's frontmatter.
+///
+/// # Arguments
+///
+/// * `markdown` - The complete markdown document potentially containing frontmatter
+/// * `key` - The top-level frontmatter key to look up
+///
+/// # Returns
+///
+/// The string items of the field if it is a sequence, or `None` otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdowndown::frontmatter::frontmatter_list;
+///
+/// let markdown = "---\ntags:\n- rust\n- web\n---\n\n# Content";
+/// assert_eq!(
+///     frontmatter_list(markdown, "tags"),
+///     Some(vec!["rust".to_string(), "web".to_string()])
+/// );
+/// ```
+pub fn frontmatter_list(markdown: &str, key: &str) -> Option<Vec<String>> {
+    let (format, fields, _) = split_frontmatter(markdown)?;
+    let yaml_value = parse_fields(format, fields)?;
+
+    let items = yaml_value.get(key)?.as_sequence()?;
+    Some(
+        items
+            .iter()
+            .filter_map(|item| item.as_str().map(str::to_string))
+            .collect(),
+    )
+}
 
-        // Try to parse the YAML content
-        serde_yaml::from_str(yaml_content).ok()
+/// Sets a string field in a markdown document's frontmatter.
+///
+/// Existing fields with the same key are replaced. Documents without valid
+/// frontmatter are returned unchanged, since there is no metadata block to
+/// extend.
+///
+/// # Arguments
+///
+/// * `markdown` - The complete markdown document
+/// * `key` - The top-level frontmatter key to set
+/// * `value` - The value to store
+///
+/// # Returns
+///
+/// The markdown document with the updated frontmatter
+///
+/// # Examples
+///
+/// ```rust
+/// use markdowndown::frontmatter::{frontmatter_field, set_frontmatter_field};
+///
+/// let markdown = "---\ntitle: Hello\n---\n\n# Content";
+/// let updated = set_frontmatter_field(markdown, "status", "draft");
+/// assert_eq!(frontmatter_field(&updated, "status"), Some("draft".to_string()));
+/// assert!(updated.ends_with("\n# Content"));
+/// ```
+pub fn set_frontmatter_field(markdown: &str, key: &str, value: &str) -> String {
+    let Some((format, fields, content)) = split_frontmatter(markdown) else {
+        return markdown.to_string();
+    };
+
+    let Some(mut yaml_value) = parse_fields(format, fields) else {
+        return markdown.to_string();
+    };
+
+    if let serde_yaml::Value::Mapping(ref mut map) = yaml_value {
+        map.insert(
+            serde_yaml::Value::String(key.to_string()),
+            serde_yaml::Value::String(value.to_string()),
+        );
     } else {
-        None
+        return markdown.to_string();
+    }
+
+    match render_frontmatter(format, &yaml_value) {
+        Some(frontmatter) => format!("{frontmatter}{content}"),
+        None => markdown.to_string(),
+    }
+}
+
+/// Replaces a document's frontmatter with the fields a provider returns.
+///
+/// The frontmatter is written in the format it was read in. Documents
+/// without valid frontmatter are returned unchanged, so the provider is not
+/// consulted when frontmatter is disabled. See [`FrontmatterProvider`] for an
+/// example.
+///
+/// # Arguments
+///
+/// * `markdown` - The complete markdown document
+/// * `provider` - The provider deciding the fields
+pub fn apply_frontmatter_provider(markdown: &str, provider: &dyn FrontmatterProvider) -> String {
+    let Some((format, fields, content)) = split_frontmatter(markdown) else {
+        return markdown.to_string();
+    };
+    let generated = match parse_fields(format, fields) {
+        Some(serde_yaml::Value::Mapping(generated)) => generated,
+        _ => return markdown.to_string(),
+    };
+
+    let body = content.strip_prefix('\n').unwrap_or(content);
+    let provided = provider.frontmatter(&generated, body);
+    if provided.is_empty() {
+        return body.to_string();
+    }
+    match render_frontmatter(format, &serde_yaml::Value::Mapping(provided)) {
+        Some(frontmatter) => format!("{frontmatter}{content}"),
+        None => markdown.to_string(),
+    }
+}
+
+/// Rewrites a document's frontmatter in another format.
+///
+/// In TOML, nested tables follow the other fields, and since TOML has no
+/// null value, empty fields are dropped. Documents without valid frontmatter, or already in the
+/// requested format, are returned unchanged.
+///
+/// # Arguments
+///
+/// * `markdown` - The complete markdown document
+/// * `format` - The format to write the frontmatter in
+///
+/// # Examples
+///
+/// ```rust
+/// use markdowndown::frontmatter::{convert_frontmatter, frontmatter_field, FrontmatterFormat};
+///
+/// let markdown = "---\ntitle: Hello\ndraft: false\n---\n\n# Content";
+///
+/// let toml = convert_frontmatter(markdown, FrontmatterFormat::Toml);
+/// assert_eq!(toml, "+++\ntitle = \"Hello\"\ndraft = false\n+++\n\n# Content");
+///
+/// let json = convert_frontmatter(markdown, FrontmatterFormat::Json);
+/// assert!(json.starts_with("{\n  \"title\": \"Hello\","));
+/// assert_eq!(frontmatter_field(&json, "title"), Some("Hello".to_string()));
+/// ```
+pub fn convert_frontmatter(markdown: &str, format: FrontmatterFormat) -> String {
+    let Some((current, fields, content)) = split_frontmatter(markdown) else {
+        return markdown.to_string();
+    };
+    if current == format {
+        return markdown.to_string();
+    }
+
+    parse_fields(current, fields)
+        .and_then(|value| render_frontmatter(format, &value))
+        .map(|frontmatter| format!("{frontmatter}{content}"))
+        .unwrap_or_else(|| markdown.to_string())
+}
+
+/// Adds the frontmatter fields of a source document that a document lacks.
+///
+/// Markdown sources often carry their own frontmatter, such as a `title`,
+/// `tags`, or `author`. Merging keeps those fields after the generated ones,
+/// which win when both have a field. The frontmatter is written in the
+/// document's format. If either document has no valid frontmatter, the
+/// document is returned unchanged.
+///
+/// # Arguments
+///
+/// * `markdown` - The document with generated frontmatter
+/// * `source` - The source document whose frontmatter is merged in
+///
+/// # Examples
+///
+/// ```rust
+/// use markdowndown::frontmatter::{frontmatter_field, merge_frontmatter};
+///
+/// let markdown = "---\nurl: https://example.com/a.md\ntitle: A\n---\n\n# A";
+/// let source = "---\ntitle: Ignored\nauthor: Ann\n---\n\n# A";
+/// let merged = merge_frontmatter(markdown, source);
+/// assert_eq!(frontmatter_field(&merged, "title"), Some("A".to_string()));
+/// assert_eq!(frontmatter_field(&merged, "author"), Some("Ann".to_string()));
+/// assert!(merged.ends_with("---\n\n# A"));
+/// ```
+pub fn merge_frontmatter(markdown: &str, source: &str) -> String {
+    let (Some((format, fields, content)), Some((source_format, source_fields, _))) =
+        (split_frontmatter(markdown), split_frontmatter(source))
+    else {
+        return markdown.to_string();
+    };
+    let (Some(serde_yaml::Value::Mapping(mut merged)), Some(serde_yaml::Value::Mapping(extra))) = (
+        parse_fields(format, fields),
+        parse_fields(source_format, source_fields),
+    ) else {
+        return markdown.to_string();
+    };
+
+    for (key, value) in extra {
+        if !merged.contains_key(&key) {
+            merged.insert(key, value);
+        }
+    }
+    match render_frontmatter(format, &serde_yaml::Value::Mapping(merged)) {
+        Some(frontmatter) => format!("{frontmatter}{content}"),
+        None => markdown.to_string(),
+    }
+}
+
+/// Splits a markdown document into its frontmatter block, in any format, and
+/// the remaining text.
+///
+/// Returns `None` unless the block parses.
+pub(crate) fn frontmatter_block(markdown: &str) -> Option<(&str, &str)> {
+    let (format, fields, content) = split_frontmatter(markdown)?;
+    parse_fields(format, fields)?;
+    Some((&markdown[..markdown.len() - content.len()], content))
+}
+
+/// Splits a markdown document into its frontmatter format, the frontmatter
+/// fields, and the remaining text.
+///
+/// The fields are the text between the delimiters, or the whole object for
+/// JSON. The remaining text starts immediately after the closing delimiter line.
+fn split_frontmatter(markdown: &str) -> Option<(FrontmatterFormat, &str, &str)> {
+    for (format, delimiter) in [
+        (FrontmatterFormat::Yaml, "---"),
+        (FrontmatterFormat::Toml, "+++"),
+    ] {
+        if let Some(content_after_start) = markdown.strip_prefix(&format!("{delimiter}\n")) {
+            let end_pos = content_after_start.find(&format!("\n{delimiter}\n"))?;
+            return Some((
+                format,
+                &content_after_start[..end_pos],
+                &content_after_start[end_pos + 5..],
+            ));
+        }
+    }
+
+    if !markdown.starts_with('{') {
+        return None;
+    }
+    let mut values = serde_json::Deserializer::from_str(markdown).into_iter::<serde_json::Value>();
+    match values.next() {
+        Some(Ok(serde_json::Value::Object(_))) => {
+            let end_pos = values.byte_offset();
+            let content = markdown[end_pos..].strip_prefix('\n')?;
+            Some((FrontmatterFormat::Json, &markdown[..end_pos], content))
+        }
+        _ => None,
+    }
+}
+
+/// Parses frontmatter fields in any format into a YAML value.
+fn parse_fields(format: FrontmatterFormat, fields: &str) -> Option<serde_yaml::Value> {
+    match format {
+        FrontmatterFormat::Yaml => serde_yaml::from_str(fields).ok(),
+        FrontmatterFormat::Toml => {
+            serde_yaml::to_value(toml::from_str::<toml::Table>(fields).ok()?).ok()
+        }
+        FrontmatterFormat::Json => {
+            serde_yaml::to_value(serde_json::from_str::<serde_json::Value>(fields).ok()?).ok()
+        }
+    }
+}
+
+/// Renders frontmatter fields in a format, with delimiters and a final newline.
+fn render_frontmatter(format: FrontmatterFormat, value: &serde_yaml::Value) -> Option<String> {
+    match format {
+        FrontmatterFormat::Yaml => Some(format!("---\n{}---\n", serde_yaml::to_string(value).ok()?)),
+        FrontmatterFormat::Toml => {
+            let fields = toml::to_string(&without_nulls(value)).ok()?;
+            Some(format!("+++\n{fields}+++\n"))
+        }
+        FrontmatterFormat::Json => Some(format!("{}\n", serde_json::to_string_pretty(value).ok()?)),
+    }
+}
+
+/// Removes null values, which TOML cannot represent, from mappings and sequences.
+fn without_nulls(value: &serde_yaml::Value) -> serde_yaml::Value {
+    match value {
+        serde_yaml::Value::Mapping(map) => serde_yaml::Value::Mapping(
+            map.iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| (key.clone(), without_nulls(value)))
+                .collect(),
+        ),
+        serde_yaml::Value::Sequence(items) => serde_yaml::Value::Sequence(
+            items
+                .iter()
+                .filter(|item| !item.is_null())
+                .map(without_nulls)
+                .collect(),
+        ),
+        other => other.clone(),
     }
 }
 
@@ -313,29 +731,10 @@ pub fn extract_frontmatter(markdown: &str) -> Option<Frontmatter> {
 /// assert_eq!(content_only, "# My Title\n\nContent here.");
 /// ```
 pub fn strip_frontmatter(markdown: &str) -> String {
-    // Check if markdown starts with frontmatter delimiters
-    if !markdown.starts_with("---\n") {
-        return markdown.to_string();
-    }
-
-    // Find the closing delimiter
-    let content_after_start = &markdown[4..]; // Skip "---\n"
-    if let Some(end_pos) = content_after_start.find("\n---\n") {
-        let content_start = 4 + end_pos + 5; // Skip "---\n" + content + "\n---\n"
-        if content_start < markdown.len() {
-            // Skip any leading newlines from the combination
-            let remaining = &markdown[content_start..];
-            if let Some(stripped) = remaining.strip_prefix('\n') {
-                stripped.to_string()
-            } else {
-                remaining.to_string()
-            }
-        } else {
-            String::new()
-        }
-    } else {
-        // No closing delimiter found, return original
-        markdown.to_string()
+    match split_frontmatter(markdown) {
+        // Skip the blank line that separates the frontmatter from the content
+        Some((_, _, content)) => content.strip_prefix('\n').unwrap_or(content).to_string(),
+        None => markdown.to_string(),
     }
 }
 
@@ -507,4 +906,133 @@ mod tests {
         let extracted_content = strip_frontmatter(&complete_doc);
         assert_eq!(extracted_content, content);
     }
+
+    #[test]
+    fn test_frontmatter_field_lookup() {
+        let markdown = "---\ntitle: My Title\ncount: 3\n---\n\n# Content";
+        assert_eq!(
+            frontmatter_field(markdown, "title"),
+            Some("My Title".to_string())
+        );
+        assert_eq!(frontmatter_field(markdown, "count"), Some("3".to_string()));
+        assert_eq!(frontmatter_field(markdown, "missing"), None);
+        assert_eq!(frontmatter_field("# No frontmatter", "title"), None);
+    }
+
+    #[test]
+    fn test_list_field_round_trip() {
+        let frontmatter = FrontmatterBuilder::new("https://example.com".to_string())
+            .additional_field("title".to_string(), "Tagged".to_string())
+            .list_field("tags".to_string(), vec!["rust".to_string(), "yaml".to_string()])
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            frontmatter_list(&frontmatter, "tags"),
+            Some(vec!["rust".to_string(), "yaml".to_string()])
+        );
+        assert_eq!(frontmatter_list(&frontmatter, "title"), None);
+        assert_eq!(frontmatter_field(&frontmatter, "tags"), None);
+    }
+
+    #[test]
+    fn test_set_frontmatter_field() {
+        let markdown = "---\ntitle: My Title\n---\n\n# Content\n";
+
+        let updated = set_frontmatter_field(markdown, "status", "flagged");
+        assert_eq!(
+            frontmatter_field(&updated, "status"),
+            Some("flagged".to_string())
+        );
+        assert_eq!(
+            frontmatter_field(&updated, "title"),
+            Some("My Title".to_string())
+        );
+        assert_eq!(strip_frontmatter(&updated), "# Content\n");
+
+        // Replaces existing values
+        let replaced = set_frontmatter_field(&updated, "title", "New Title");
+        assert_eq!(
+            frontmatter_field(&replaced, "title"),
+            Some("New Title".to_string())
+        );
+
+        // Documents without frontmatter are left alone
+        assert_eq!(
+            set_frontmatter_field("# Content", "status", "flagged"),
+            "# Content"
+        );
+    }
+
+    #[test]
+    fn test_convert_frontmatter_formats() {
+        let markdown = "---\ntitle: My Title\nauthor: null\ntags:\n- rust\n- docs\n---\n\n# Content\n";
+
+        let toml = convert_frontmatter(markdown, FrontmatterFormat::Toml);
+        assert_eq!(
+            toml,
+            "+++\ntitle = \"My Title\"\ntags = [\"rust\", \"docs\"]\n+++\n\n# Content\n"
+        );
+        assert_eq!(strip_frontmatter(&toml), "# Content\n");
+        assert_eq!(
+            frontmatter_list(&toml, "tags"),
+            Some(vec!["rust".to_string(), "docs".to_string()])
+        );
+
+        let json = convert_frontmatter(markdown, FrontmatterFormat::Json);
+        assert!(json.starts_with("{\n  \"title\": \"My Title\",\n  \"author\": null,"));
+        assert!(json.ends_with("}\n\n# Content\n"));
+        let updated = set_frontmatter_field(&json, "status", "flagged");
+        assert!(updated.starts_with('{'));
+        assert_eq!(
+            frontmatter_field(&updated, "status"),
+            Some("flagged".to_string())
+        );
+
+        // Converting back keeps the fields
+        let yaml = convert_frontmatter(&toml, FrontmatterFormat::Yaml);
+        assert!(yaml.starts_with("---\n"));
+        assert_eq!(
+            frontmatter_field(&yaml, "title"),
+            Some("My Title".to_string())
+        );
+        assert_eq!(strip_frontmatter(&yaml), "# Content\n");
+
+        // Documents without frontmatter are left alone
+        assert_eq!(
+            convert_frontmatter("{not json}\n# Content", FrontmatterFormat::Toml),
+            "{not json}\n# Content"
+        );
+    }
+
+    #[test]
+    fn test_apply_frontmatter_provider() {
+        let markdown = "+++\nsource_url = \"https://example.com\"\nexporter = \"markdowndown\"\n+++\n\n# Content\n";
+
+        let renamed = apply_frontmatter_provider(
+            markdown,
+            &|generated: &serde_yaml::Mapping, content: &str| {
+                let mut fields = serde_yaml::Mapping::new();
+                fields.insert("link".into(), generated["source_url"].clone());
+                fields.insert("words".into(), content.split_whitespace().count().into());
+                fields
+            },
+        );
+        assert_eq!(
+            renamed,
+            "+++\nlink = \"https://example.com\"\nwords = 2\n+++\n\n# Content\n"
+        );
+
+        // An empty mapping removes the frontmatter
+        let removed = apply_frontmatter_provider(markdown, &|_: &serde_yaml::Mapping, _: &str| {
+            serde_yaml::Mapping::new()
+        });
+        assert_eq!(removed, "# Content\n");
+
+        // Documents without frontmatter are left alone
+        let untouched = apply_frontmatter_provider("# Content", &|_: &serde_yaml::Mapping, _: &str| {
+            serde_yaml::Mapping::new()
+        });
+        assert_eq!(untouched, "# Content");
+    }
 }
@@ -0,0 +1,226 @@
+//! Link graphs of converted documents.
+//!
+//! A batch conversion produces documents that link to each other. A
+//! [`GraphBuilder`] collects each converted document with its outbound
+//! links, resolved against the document's source URL, and keeps the links
+//! whose target is another document of the batch. The resulting
+//! [`DocumentGraph`] serializes to JSON for import into graph tools.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::graph::GraphBuilder;
+//!
+//! let mut builder = GraphBuilder::new();
+//! builder.add_document(
+//!     "https://example.com/docs/intro",
+//!     "001.md",
+//!     "# Intro\n\nNext, read the [setup guide](setup#install).\n",
+//! );
+//! builder.add_document("https://example.com/docs/setup", "002.md", "# Setup\n");
+//!
+//! let graph = builder.build();
+//! assert_eq!(graph.nodes.len(), 2);
+//! assert_eq!(graph.edges[0].source, graph.nodes[0].id);
+//! assert_eq!(graph.edges[0].target, graph.nodes[1].id);
+//! assert_eq!(graph.edges[0].text, "setup guide");
+//! ```
+
+use crate::identity::{canonical_url, document_id};
+use crate::index::document_title;
+use crate::links::extract_links;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use url::Url;
+
+/// A converted document in a link graph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphNode {
+    /// The stable document ID, as stamped into the frontmatter
+    pub id: String,
+    /// The URL the document was converted from
+    pub url: String,
+    /// Where the converted output was written
+    pub path: String,
+    /// The document title, when known
+    pub title: Option<String>,
+}
+
+/// Links from one converted document to another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphEdge {
+    /// ID of the linking document
+    pub source: String,
+    /// ID of the linked document
+    pub target: String,
+    /// Anchor text of the first link
+    pub text: String,
+    /// Number of links from the source to the target
+    pub count: usize,
+}
+
+/// Converted documents and the links between them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocumentGraph {
+    /// The documents, sorted by output path
+    pub nodes: Vec<GraphNode>,
+    /// The links between documents, grouped per source and target pair
+    pub edges: Vec<GraphEdge>,
+}
+
+/// Collects converted documents and builds the graph of links between them.
+#[derive(Debug, Default)]
+pub struct GraphBuilder {
+    nodes: Vec<GraphNode>,
+    /// Resolved outbound links of each node, with their anchor text
+    links: Vec<Vec<(String, String)>>,
+}
+
+impl GraphBuilder {
+    /// Creates an empty graph builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a converted document.
+    ///
+    /// Only the document's title and outbound links are kept, so the
+    /// builder does not hold whole documents.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL or local path the document was converted from
+    /// * `path` - Where the converted output was written
+    /// * `markdown` - The converted output
+    pub fn add_document(&mut self, url: &str, path: &str, markdown: &str) {
+        let base = link_key(url);
+        let links = extract_links(markdown, &base)
+            .into_iter()
+            .map(|link| (link_key(&link.url), link.text))
+            .collect();
+        self.nodes.push(GraphNode {
+            id: document_id(url),
+            url: url.to_string(),
+            path: path.to_string(),
+            title: document_title(markdown),
+        });
+        self.links.push(links);
+    }
+
+    /// Builds the graph, keeping links whose target is an added document.
+    ///
+    /// Documents are sorted by output path, so batches that convert
+    /// concurrently still produce the same graph. Links from a document to
+    /// itself are dropped.
+    pub fn build(self) -> DocumentGraph {
+        let mut documents: Vec<(GraphNode, Vec<(String, String)>)> =
+            self.nodes.into_iter().zip(self.links).collect();
+        documents.sort_by(|a, b| a.0.path.cmp(&b.0.path));
+
+        let ids: HashMap<String, &str> = documents
+            .iter()
+            .map(|(node, _)| (link_key(&node.url), node.id.as_str()))
+            .collect();
+
+        let mut edges: Vec<GraphEdge> = Vec::new();
+        for (node, links) in &documents {
+            let first_edge = edges.len();
+            for (target, text) in links {
+                let Some(&target) = ids.get(target) else {
+                    continue;
+                };
+                if target == node.id {
+                    continue;
+                }
+                match edges[first_edge..]
+                    .iter_mut()
+                    .find(|edge| edge.target == target)
+                {
+                    Some(edge) => edge.count += 1,
+                    None => edges.push(GraphEdge {
+                        source: node.id.clone(),
+                        target: target.to_string(),
+                        text: text.clone(),
+                        count: 1,
+                    }),
+                }
+            }
+        }
+
+        DocumentGraph {
+            nodes: documents.into_iter().map(|(node, _)| node).collect(),
+            edges,
+        }
+    }
+}
+
+/// Returns the form of a URL or local path that links are matched on.
+///
+/// URLs are canonicalized, so fragments and trailing slashes do not matter.
+/// Local paths become `file://` URLs, so relative links between local files
+/// resolve.
+fn link_key(url: &str) -> String {
+    if Url::parse(url).is_ok() {
+        return canonical_url(url);
+    }
+    std::path::absolute(url)
+        .ok()
+        .and_then(|path| Url::from_file_path(path).ok())
+        .map(|file_url| file_url.to_string())
+        .unwrap_or_else(|| url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graph_counts_links_between_batch_documents() {
+        let mut builder = GraphBuilder::new();
+        builder.add_document(
+            "https://example.com/a",
+            "a.md",
+            "---\ntitle: Page A\n---\n\n[B](/b/) and [B again](https://example.com/b#top), \
+             [self](/a), [outside](https://other.org/), ![img](/b)\n\n```\n[C](/c)\n```\n",
+        );
+        builder.add_document("https://example.com/b/", "b.md", "[A](/a)\n");
+        builder.add_document("https://example.com/c", "c.md", "# Page C\n");
+
+        let graph = builder.build();
+        let ids: Vec<&str> = graph.nodes.iter().map(|node| node.id.as_str()).collect();
+        assert_eq!(graph.nodes[0].title.as_deref(), Some("Page A"));
+        assert_eq!(graph.nodes[2].title.as_deref(), Some("Page C"));
+        assert_eq!(
+            graph.edges,
+            vec![
+                GraphEdge {
+                    source: ids[0].to_string(),
+                    target: ids[1].to_string(),
+                    text: "B".to_string(),
+                    count: 2,
+                },
+                GraphEdge {
+                    source: ids[1].to_string(),
+                    target: ids[0].to_string(),
+                    text: "A".to_string(),
+                    count: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_graph_resolves_relative_links_between_local_files() {
+        let mut builder = GraphBuilder::new();
+        builder.add_document(
+            "/srv/docs/guide/intro.md",
+            "001.md",
+            "[Setup](../setup.md)\n",
+        );
+        builder.add_document("/srv/docs/setup.md", "002.md", "# Setup\n");
+
+        let graph = builder.build();
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!(graph.edges[0].target, graph.nodes[1].id);
+    }
+}
@@ -0,0 +1,651 @@
+//! Response cache with ETag and Last-Modified revalidation.
+//!
+//! Converting the same URL again, in a later batch run or a long-lived
+//! service, usually fetches a page that has not changed. When
+//! [`crate::config::HttpConfig::cache`] is set, [`crate::client::HttpClient`]
+//! keeps successful responses in an [`HttpCache`]: an in-memory LRU, backed
+//! by an optional directory so the cache survives restarts. Repeated requests
+//! send `If-None-Match` and `If-Modified-Since` with the stored validators,
+//! and a `304 Not Modified` answer is served from the cache.
+//!
+//! The markdown converted from the responses is cached too, keyed by the URL
+//! and the settings that shape the output, with the requests the conversion
+//! made. Converting the URL again revalidates those requests, and when every
+//! one is answered `304 Not Modified` the cached markdown is returned without
+//! converting anything. Conversions whose requests carried an
+//! `Authorization` header are kept in memory only, so credentials never
+//! reach the directory. Unlike [`crate::MarkdownDown::with_cache`], which
+//! never expires documents, a document changed at its source is always
+//! converted again.
+//!
+//! Responses younger than the configured time to live are served without
+//! revalidating at all. Responses marked `Cache-Control: no-store`, and
+//! responses with neither validators nor a time to live to reuse them for,
+//! are not stored. A conversion can skip cached responses with
+//! [`crate::config::ConversionOptions::bypass_cache`].
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::config::HttpCacheConfig;
+//! use markdowndown::Config;
+//! use std::time::Duration;
+//!
+//! let config = Config::builder()
+//!     .http_cache(
+//!         HttpCacheConfig::new()
+//!             .max_entries(1000)
+//!             .ttl(Duration::from_secs(300))
+//!             .disk_dir(std::env::temp_dir().join("markdowndown-http-cache")),
+//!     )
+//!     .build();
+//! ```
+
+use crate::config::HttpCacheConfig;
+use crate::identity::fnv1a_hex;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, HeaderValue, CACHE_CONTROL, ETAG, LAST_MODIFIED};
+use reqwest::{Response, ResponseBuilderExt, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, warn};
+use url::Url;
+
+/// A stored response.
+#[derive(Debug, Clone)]
+pub(crate) struct CachedResponse {
+    meta: CachedMeta,
+    body: Bytes,
+}
+
+/// The parts of a stored response other than its body, as written to disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedMeta {
+    url: String,
+    status: u16,
+    headers: Vec<(String, String)>,
+    stored_at: DateTime<Utc>,
+}
+
+impl CachedResponse {
+    /// Creates the response to store from a received response's parts.
+    pub(crate) fn new(url: &Url, status: StatusCode, headers: &HeaderMap, body: Bytes) -> Self {
+        let meta = CachedMeta {
+            url: url.to_string(),
+            status: status.as_u16(),
+            headers: headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    value
+                        .to_str()
+                        .ok()
+                        .map(|value| (name.to_string(), value.to_string()))
+                })
+                .collect(),
+            stored_at: Utc::now(),
+        };
+        Self { meta, body }
+    }
+
+    /// Returns true if the response can be served without revalidating.
+    pub(crate) fn is_fresh(&self, ttl: Duration, now: DateTime<Utc>) -> bool {
+        chrono::Duration::from_std(ttl)
+            .is_ok_and(|ttl| !ttl.is_zero() && self.meta.stored_at + ttl > now)
+    }
+
+    /// Returns the conditional request headers built from the stored validators.
+    pub(crate) fn validators(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.meta.headers {
+            let conditional = match name.as_str() {
+                "etag" => reqwest::header::IF_NONE_MATCH,
+                "last-modified" => reqwest::header::IF_MODIFIED_SINCE,
+                _ => continue,
+            };
+            if let Ok(value) = HeaderValue::from_str(value) {
+                headers.insert(conditional, value);
+            }
+        }
+        headers
+    }
+
+    /// Returns a copy stored now, after the server confirmed it is unchanged.
+    pub(crate) fn revalidated(&self) -> Self {
+        let mut entry = self.clone();
+        entry.meta.stored_at = Utc::now();
+        entry
+    }
+
+    /// Returns the size of the stored body in bytes.
+    pub(crate) fn body_len(&self) -> usize {
+        self.body.len()
+    }
+
+    /// Rebuilds the stored response.
+    pub(crate) fn to_response(&self) -> Response {
+        let mut builder = http::Response::builder().status(self.meta.status);
+        for (name, value) in &self.meta.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        if let Ok(url) = Url::parse(&self.meta.url) {
+            builder = builder.url(url);
+        }
+        let response = builder
+            .body(self.body.clone())
+            .expect("stored response headers were valid when received");
+        Response::from(response)
+    }
+}
+
+/// Returns true if a response may be stored.
+///
+/// Only complete `200 OK` responses not marked `no-store` are stored, and
+/// only when they can be reused: they carry a validator, or the cache has a
+/// time to live. A `Content-Length` over `max_body_bytes` is rejected here;
+/// bodies without one must be checked after they are read.
+pub(crate) fn is_storable(response: &Response, config: &HttpCacheConfig) -> bool {
+    let headers = response.headers();
+    let no_store = headers
+        .get_all(CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .any(|value| value.to_ascii_lowercase().contains("no-store"));
+    let reusable =
+        headers.contains_key(ETAG) || headers.contains_key(LAST_MODIFIED) || !config.ttl.is_zero();
+    let small_enough = response
+        .content_length()
+        .is_none_or(|length| length <= config.max_body_bytes as u64);
+    response.status() == reqwest::StatusCode::OK && !no_store && reusable && small_enough
+}
+
+/// A request made by a conversion, kept so its response can be revalidated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct CachedRequest {
+    url: String,
+    headers: Vec<(String, String)>,
+}
+
+impl CachedRequest {
+    /// Creates the record of a request, with its custom headers if any.
+    pub(crate) fn new(url: &str, headers: Option<&HashMap<String, String>>) -> Self {
+        let mut headers: Vec<_> = headers
+            .into_iter()
+            .flatten()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        headers.sort();
+        Self {
+            url: url.to_string(),
+            headers,
+        }
+    }
+
+    /// Returns the URL the request was sent to.
+    pub(crate) fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the request's custom headers, or `None` if it had none.
+    pub(crate) fn headers(&self) -> Option<HashMap<String, String>> {
+        (!self.headers.is_empty()).then(|| self.headers.iter().cloned().collect())
+    }
+
+    fn has_credentials(&self) -> bool {
+        self.headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("authorization"))
+    }
+}
+
+/// Markdown converted from cached responses, with the requests that fetched them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CachedConversion {
+    requests: Vec<CachedRequest>,
+    markdown: String,
+}
+
+impl CachedConversion {
+    /// Creates the record of a conversion.
+    pub(crate) fn new(requests: Vec<CachedRequest>, markdown: String) -> Self {
+        Self { requests, markdown }
+    }
+
+    /// Returns the requests to revalidate before the markdown is reused.
+    pub(crate) fn requests(&self) -> &[CachedRequest] {
+        &self.requests
+    }
+
+    /// Returns the converted markdown.
+    pub(crate) fn markdown(&self) -> &str {
+        &self.markdown
+    }
+}
+
+/// The requests made by a conversion so far.
+#[derive(Debug)]
+struct ConversionRequests {
+    requests: Vec<CachedRequest>,
+    /// Whether every response so far was stored in the cache
+    cached: bool,
+}
+
+tokio::task_local! {
+    static CONVERSION_REQUESTS: Arc<Mutex<ConversionRequests>>;
+}
+
+/// Runs a conversion, recording the requests it makes.
+///
+/// Returns the conversion's output with its requests, or with `None` if a
+/// response was not stored in the cache, since the output could not be
+/// revalidated.
+pub(crate) async fn record_requests<T>(
+    conversion: impl Future<Output = T>,
+) -> (T, Option<Vec<CachedRequest>>) {
+    let recorded = Arc::new(Mutex::new(ConversionRequests {
+        requests: Vec::new(),
+        cached: true,
+    }));
+    let output = CONVERSION_REQUESTS
+        .scope(recorded.clone(), conversion)
+        .await;
+    let mut recorded = recorded.lock().unwrap_or_else(|e| e.into_inner());
+    let requests = recorded
+        .cached
+        .then(|| std::mem::take(&mut recorded.requests));
+    (output, requests)
+}
+
+/// Records a request made inside [`record_requests`].
+///
+/// # Arguments
+///
+/// * `request` - The request
+/// * `cached` - Whether its response is stored in the cache
+pub(crate) fn record_request(request: CachedRequest, cached: bool) {
+    let _ = CONVERSION_REQUESTS.try_with(|recorded| {
+        let mut recorded = recorded.lock().unwrap_or_else(|e| e.into_inner());
+        recorded.cached &= cached;
+        if !recorded.requests.contains(&request) {
+            recorded.requests.push(request);
+        }
+    });
+}
+
+/// Stored values with the order they were used in, so the least recently
+/// used is found without a scan.
+#[derive(Debug)]
+struct Entries<T> {
+    values: HashMap<String, (Arc<T>, u64)>,
+    by_use: BTreeMap<u64, String>,
+    clock: u64,
+}
+
+impl<T> Default for Entries<T> {
+    fn default() -> Self {
+        Self {
+            values: HashMap::new(),
+            by_use: BTreeMap::new(),
+            clock: 0,
+        }
+    }
+}
+
+impl<T> Entries<T> {
+    fn get(&mut self, key: &str) -> Option<Arc<T>> {
+        let (entry, used) = self.values.get_mut(key)?;
+        self.clock += 1;
+        self.by_use.remove(used);
+        *used = self.clock;
+        self.by_use.insert(self.clock, key.to_string());
+        Some(entry.clone())
+    }
+
+    fn insert(&mut self, key: &str, entry: Arc<T>, max_entries: usize) {
+        self.clock += 1;
+        if let Some((_, used)) = self.values.insert(key.to_string(), (entry, self.clock)) {
+            self.by_use.remove(&used);
+        }
+        self.by_use.insert(self.clock, key.to_string());
+        while self.values.len() > max_entries {
+            let Some((_, oldest)) = self.by_use.pop_first() else {
+                break;
+            };
+            self.values.remove(&oldest);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.values.clear();
+        self.by_use.clear();
+    }
+}
+
+/// Stored responses and the conversions made from them.
+#[derive(Debug, Default)]
+struct Stores {
+    responses: Entries<CachedResponse>,
+    conversions: Entries<CachedConversion>,
+}
+
+/// Successful responses kept for revalidation, shared by clones.
+#[derive(Debug, Clone)]
+pub struct HttpCache {
+    config: HttpCacheConfig,
+    entries: Arc<Mutex<Stores>>,
+}
+
+impl HttpCache {
+    /// Creates an empty cache.
+    ///
+    /// Responses in the configured directory, if any, are loaded as they
+    /// are requested.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The cache's size, time to live, and directory
+    pub fn new(config: HttpCacheConfig) -> Self {
+        Self {
+            config,
+            entries: Arc::new(Mutex::new(Stores::default())),
+        }
+    }
+
+    /// Returns the cache's configuration.
+    pub fn config(&self) -> &HttpCacheConfig {
+        &self.config
+    }
+
+    /// Returns the number of responses held in memory.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().responses.values.len()
+    }
+
+    /// Returns true if no responses are held in memory.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes every response and conversion held in memory; the directory
+    /// is left as is.
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.responses.clear();
+        entries.conversions.clear();
+    }
+
+    /// Returns the stored response for a request, loading it from disk if needed.
+    pub(crate) async fn get(&self, key: &str) -> Option<Arc<CachedResponse>> {
+        if let Some(entry) = self.entries.lock().unwrap().responses.get(key) {
+            return Some(entry);
+        }
+        let entry = Arc::new(self.load(key).await?);
+        self.entries
+            .lock()
+            .unwrap()
+            .responses
+            .insert(key, entry.clone(), self.config.max_entries);
+        Some(entry)
+    }
+
+    /// Stores a response for a request, in memory and on disk.
+    ///
+    /// Callers check the body against `max_body_bytes` first.
+    pub(crate) async fn put(&self, key: &str, entry: CachedResponse) -> Arc<CachedResponse> {
+        self.save(key, &entry).await;
+        let entry = Arc::new(entry);
+        self.entries
+            .lock()
+            .unwrap()
+            .responses
+            .insert(key, entry.clone(), self.config.max_entries);
+        entry
+    }
+
+    /// Returns the stored conversion for a URL and settings fingerprint,
+    /// loading it from disk if needed.
+    pub(crate) async fn get_conversion(&self, key: &str) -> Option<Arc<CachedConversion>> {
+        if let Some(entry) = self.entries.lock().unwrap().conversions.get(key) {
+            return Some(entry);
+        }
+        let entry = Arc::new(self.load_conversion(key).await?);
+        self.entries.lock().unwrap().conversions.insert(
+            key,
+            entry.clone(),
+            self.config.max_entries,
+        );
+        Some(entry)
+    }
+
+    /// Stores a conversion for a URL and settings fingerprint.
+    ///
+    /// Conversions whose requests carried credentials are kept in memory only.
+    pub(crate) async fn put_conversion(&self, key: &str, entry: CachedConversion) {
+        if !entry.requests.iter().any(CachedRequest::has_credentials) {
+            self.save_conversion(key, &entry).await;
+        }
+        self.entries.lock().unwrap().conversions.insert(
+            key,
+            Arc::new(entry),
+            self.config.max_entries,
+        );
+    }
+
+    /// Returns the files a request's response is kept in, if the cache has a directory.
+    fn paths(&self, key: &str) -> Option<(PathBuf, PathBuf)> {
+        let dir = self.config.disk_dir.as_ref()?;
+        let stem = fnv1a_hex(key.as_bytes());
+        Some((
+            dir.join(format!("{stem}.json")),
+            dir.join(format!("{stem}.body")),
+        ))
+    }
+
+    /// Returns the file a conversion is kept in, if the cache has a directory.
+    fn conversion_path(&self, key: &str) -> Option<PathBuf> {
+        let dir = self.config.disk_dir.as_ref()?;
+        Some(dir.join(format!("{}.conversion.json", fnv1a_hex(key.as_bytes()))))
+    }
+
+    async fn load_conversion(&self, key: &str) -> Option<CachedConversion> {
+        let path = self.conversion_path(key)?;
+        let stored: StoredConversion =
+            serde_json::from_slice(&tokio::fs::read(&path).await.ok()?).ok()?;
+        // Hashes can collide, so the key is stored and checked
+        (stored.key == key).then_some(stored.conversion)
+    }
+
+    async fn save_conversion(&self, key: &str, entry: &CachedConversion) {
+        let Some(path) = self.conversion_path(key) else {
+            return;
+        };
+        let stored = StoredConversion {
+            key: key.to_string(),
+            conversion: entry.clone(),
+        };
+        let result = async {
+            if let Some(dir) = path.parent() {
+                tokio::fs::create_dir_all(dir).await?;
+            }
+            let json = serde_json::to_vec(&stored).map_err(std::io::Error::other)?;
+            tokio::fs::write(&path, json).await
+        }
+        .await;
+        if let Err(e) = result {
+            warn!(
+                "Failed to write cached conversion to {}: {e}",
+                path.display()
+            );
+        }
+    }
+
+    async fn load(&self, key: &str) -> Option<CachedResponse> {
+        let (meta_path, body_path) = self.paths(key)?;
+        let meta = tokio::fs::read(&meta_path).await.ok()?;
+        let stored: StoredMeta = serde_json::from_slice(&meta).ok()?;
+        // Hashes can collide, so the key is stored and checked
+        if stored.key != key {
+            return None;
+        }
+        let body = tokio::fs::read(&body_path).await.ok()?;
+        debug!("Loaded cached response for {}", stored.meta.url);
+        Some(CachedResponse {
+            meta: stored.meta,
+            body: Bytes::from(body),
+        })
+    }
+
+    async fn save(&self, key: &str, entry: &CachedResponse) {
+        let Some((meta_path, body_path)) = self.paths(key) else {
+            return;
+        };
+        let stored = StoredMeta {
+            key: key.to_string(),
+            meta: entry.meta.clone(),
+        };
+        let result = async {
+            if let Some(dir) = meta_path.parent() {
+                tokio::fs::create_dir_all(dir).await?;
+            }
+            tokio::fs::write(&body_path, &entry.body).await?;
+            let json = serde_json::to_vec(&stored).map_err(std::io::Error::other)?;
+            tokio::fs::write(&meta_path, json).await
+        }
+        .await;
+        if let Err(e) = result {
+            warn!(
+                "Failed to write cached response to {}: {e}",
+                meta_path.display()
+            );
+        }
+    }
+}
+
+/// A response's metadata as written to disk, with the request it answers.
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredMeta {
+    key: String,
+    #[serde(flatten)]
+    meta: CachedMeta,
+}
+
+/// A conversion as written to disk, with the URL and settings it answers.
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredConversion {
+    key: String,
+    #[serde(flatten)]
+    conversion: CachedConversion,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(url: &str, headers: &[(&str, &str)]) -> CachedResponse {
+        CachedResponse {
+            meta: CachedMeta {
+                url: url.to_string(),
+                status: 200,
+                headers: headers
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect(),
+                stored_at: Utc::now(),
+            },
+            body: Bytes::from_static(b"<p>cached</p>"),
+        }
+    }
+
+    #[test]
+    fn test_validators_and_freshness() {
+        let entry = entry(
+            "https://example.com/page",
+            &[
+                ("etag", "\"v1\""),
+                ("last-modified", "Wed, 21 Oct 2015 07:28:00 GMT"),
+                ("content-type", "text/html"),
+            ],
+        );
+        let validators = entry.validators();
+        assert_eq!(validators["if-none-match"], "\"v1\"");
+        assert_eq!(
+            validators["if-modified-since"],
+            "Wed, 21 Oct 2015 07:28:00 GMT"
+        );
+        assert_eq!(validators.len(), 2);
+
+        let now = Utc::now();
+        assert!(!entry.is_fresh(Duration::ZERO, now));
+        assert!(entry.is_fresh(Duration::from_secs(60), now));
+        assert!(!entry.is_fresh(Duration::from_secs(60), now + chrono::Duration::minutes(2)));
+    }
+
+    #[tokio::test]
+    async fn test_least_recently_used_evicted() {
+        let cache = HttpCache::new(HttpCacheConfig::new().max_entries(2));
+        cache.put("a", entry("https://example.com/a", &[])).await;
+        cache.put("b", entry("https://example.com/b", &[])).await;
+        assert!(cache.get("a").await.is_some());
+        cache.put("c", entry("https://example.com/c", &[])).await;
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("a").await.is_some());
+        assert!(cache.get("b").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_disk_cache_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = HttpCacheConfig::new().disk_dir(dir.path());
+        let stored = HttpCache::new(config.clone())
+            .put(
+                "page",
+                entry("https://example.com/page", &[("etag", "\"v1\"")]),
+            )
+            .await;
+
+        let restarted = HttpCache::new(config);
+        let loaded = restarted.get("page").await.unwrap();
+        assert_eq!(loaded.body, stored.body);
+        assert_eq!(loaded.validators()["if-none-match"], "\"v1\"");
+        assert!(restarted.get("other").await.is_none());
+
+        let response = loaded.to_response();
+        assert_eq!(response.url().as_str(), "https://example.com/page");
+        assert_eq!(response.text().await.unwrap(), "<p>cached</p>");
+    }
+
+    #[tokio::test]
+    async fn test_conversions_with_credentials_kept_off_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = HttpCacheConfig::new().disk_dir(dir.path());
+        let cache = HttpCache::new(config.clone());
+        let public = CachedRequest::new("https://example.com/page", None);
+        let credentials = HashMap::from([("Authorization".to_string(), "Basic abc".to_string())]);
+        let private = CachedRequest::new("https://example.com/private", Some(&credentials));
+        assert_eq!(private.headers(), Some(credentials));
+        cache
+            .put_conversion(
+                "page",
+                CachedConversion::new(vec![public.clone()], "# Page".into()),
+            )
+            .await;
+        cache
+            .put_conversion(
+                "private",
+                CachedConversion::new(vec![private], "# Private".into()),
+            )
+            .await;
+        assert!(cache.get_conversion("private").await.is_some());
+
+        let restarted = HttpCache::new(config);
+        let loaded = restarted.get_conversion("page").await.unwrap();
+        assert_eq!(loaded.markdown(), "# Page");
+        assert_eq!(loaded.requests(), [public]);
+        assert!(restarted.get_conversion("private").await.is_none());
+    }
+}
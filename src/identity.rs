@@ -0,0 +1,280 @@
+//! Stable document identity: canonical URLs, document IDs, and slugs.
+//!
+//! Every conversion gets a `document_id` derived from its canonical URL and a
+//! filesystem-safe `slug`, so consumers can deduplicate and name output files
+//! consistently without inventing their own schemes.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::identity::{canonical_url, document_id, document_slug};
+//!
+//! let url = "HTTPS://Example.com:443/Blog/Post/?utm_source=feed&b=2&a=1#comments";
+//! assert_eq!(canonical_url(url), "https://example.com/Blog/Post?a=1&b=2");
+//!
+//! // Equivalent URLs share an ID
+//! assert_eq!(document_id(url), document_id("https://example.com/Blog/Post?b=2&a=1"));
+//! assert_eq!(document_slug("https://example.com/Blog/Post"), "example-com-blog-post");
+//!
+//! // Slugs of URLs with a query end in part of the document ID
+//! assert!(document_slug(url).starts_with("example-com-blog-post-"));
+//! ```
+
+use url::Url;
+
+/// Query parameters that only track where a visitor came from.
+const TRACKING_PARAMS: &[&str] = &["fbclid", "gclid", "mc_cid", "mc_eid", "ref_src"];
+
+/// Maximum length of a generated slug.
+const MAX_SLUG_LENGTH: usize = 80;
+
+/// Number of document ID characters appended to slugs that lose information.
+const SLUG_HASH_LENGTH: usize = 8;
+
+/// Returns the canonical form of a URL.
+///
+/// Canonicalization lowercases the scheme and host, drops default ports,
+/// fragments, trailing slashes, and tracking parameters (`utm_*`, `fbclid`,
+/// ...), and sorts the remaining query parameters. Inputs that are not
+/// absolute URLs (such as local file paths) are returned trimmed but otherwise
+/// unchanged.
+///
+/// # Arguments
+///
+/// * `url` - The URL to canonicalize
+pub fn canonical_url(url: &str) -> String {
+    let trimmed = url.trim();
+    let mut parsed = match Url::parse(trimmed) {
+        Ok(parsed) if parsed.has_host() => parsed,
+        _ => return trimmed.to_string(),
+    };
+
+    parsed.set_fragment(None);
+
+    let mut params: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(key, _)| !is_tracking_param(key))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    params.sort();
+    if params.is_empty() {
+        parsed.set_query(None);
+    } else {
+        parsed.query_pairs_mut().clear().extend_pairs(params);
+    }
+
+    let path = parsed.path().trim_end_matches('/').to_string();
+    parsed.set_path(if path.is_empty() { "/" } else { &path });
+
+    let canonical = parsed.to_string();
+    // The url crate always renders an empty path as "/"; drop it for bare hosts
+    match canonical.strip_suffix('/') {
+        Some(stripped) if parsed.path() == "/" && parsed.query().is_none() => stripped.to_string(),
+        _ => canonical,
+    }
+}
+
+/// Returns a stable identifier for a document.
+///
+/// The ID is the 64-bit FNV-1a hash of the canonical URL, rendered as 16
+/// lowercase hex characters. FNV-1a is used because its output is fixed by
+/// specification, unlike the standard library hasher.
+///
+/// # Arguments
+///
+/// * `url` - The document URL
+pub fn document_id(url: &str) -> String {
+    fnv1a_hex(canonical_url(url).as_bytes())
+}
+
+/// Returns a stable identifier for one part of a document.
+///
+/// Parts of the same document get distinct IDs, none of them equal to the
+/// [`document_id`] of the whole.
+///
+/// # Arguments
+///
+/// * `url` - The document URL
+/// * `index` - Position of the part, starting at 1
+pub fn part_id(url: &str, index: usize) -> String {
+    fnv1a_hex(format!("{}#part-{index}", canonical_url(url)).as_bytes())
+}
+
+/// Returns a stable fingerprint of document content.
+///
+/// Like [`document_id`], this is a 64-bit FNV-1a hash rendered as 16 hex
+/// characters, so fingerprints can be persisted and compared across runs to
+/// detect changed content.
+///
+/// # Arguments
+///
+/// * `content` - The document content to fingerprint
+pub fn content_fingerprint(content: &str) -> String {
+    fnv1a_hex(content.as_bytes())
+}
+
+/// Returns a filesystem-safe slug for a document.
+///
+/// The slug is built from the host and path of the canonical URL, so it is
+/// stable across conversions and readable in directory listings. When the
+/// query is dropped or the slug is truncated, the first 8 characters of the
+/// [`document_id`] are appended so that distinct URLs keep distinct slugs.
+///
+/// # Arguments
+///
+/// * `url` - The document URL
+pub fn document_slug(url: &str) -> String {
+    let canonical = canonical_url(url);
+    let (source, has_query) = match Url::parse(&canonical) {
+        Ok(parsed) => (
+            format!("{}{}", parsed.host_str().unwrap_or_default(), parsed.path()),
+            parsed.query().is_some(),
+        ),
+        Err(_) => (canonical, false),
+    };
+
+    let slug = slugify(&source);
+    if slug.is_empty() {
+        return document_id(url);
+    }
+    if !has_query && slug.len() < MAX_SLUG_LENGTH {
+        return slug;
+    }
+
+    let id = document_id(url);
+    let suffix = &id[..SLUG_HASH_LENGTH];
+    let mut base = slug;
+    base.truncate(MAX_SLUG_LENGTH - SLUG_HASH_LENGTH - 1);
+    format!("{}-{suffix}", base.trim_end_matches('-'))
+}
+
+/// Converts text into a lowercase, hyphen-separated, filesystem-safe slug.
+///
+/// Runs of characters other than ASCII letters and digits become a single
+/// hyphen, and the result is capped at 80 characters without a trailing hyphen.
+///
+/// # Arguments
+///
+/// * `text` - The text to slugify
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    slug.truncate(MAX_SLUG_LENGTH);
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Hashes bytes with 64-bit FNV-1a and renders the hash as 16 hex characters.
+pub(crate) fn fnv1a_hex(bytes: &[u8]) -> String {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+    let hash = bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    });
+    format!("{hash:016x}")
+}
+
+/// Returns true if a query parameter only tracks where a visitor came from.
+pub(crate) fn is_tracking_param(key: &str) -> bool {
+    key.starts_with("utm_") || TRACKING_PARAMS.contains(&key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_url_normalization() {
+        assert_eq!(
+            canonical_url("HTTPS://WWW.Example.COM:443/path/"),
+            "https://www.example.com/path"
+        );
+        assert_eq!(
+            canonical_url("http://example.com:80/a?z=1&utm_medium=x&a=2#top"),
+            "http://example.com/a?a=2&z=1"
+        );
+        assert_eq!(
+            canonical_url("https://example.com/post?ref=nav&fbclid=abc"),
+            "https://example.com/post?ref=nav"
+        );
+        assert_eq!(canonical_url("https://example.com/"), "https://example.com");
+        assert_eq!(
+            canonical_url("https://example.com:8443/"),
+            "https://example.com:8443"
+        );
+    }
+
+    #[test]
+    fn test_canonical_url_passes_through_local_paths() {
+        assert_eq!(canonical_url(" ./docs/readme.md "), "./docs/readme.md");
+    }
+
+    #[test]
+    fn test_document_id_is_stable() {
+        let id = document_id("https://example.com/page");
+        assert_eq!(id.len(), 16);
+        assert!(id.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(id, document_id("https://EXAMPLE.com/page/#section"));
+        assert_ne!(id, document_id("https://example.com/other"));
+        // Pinned so IDs never change between releases
+        assert_eq!(document_id(""), "cbf29ce484222325");
+    }
+
+    #[test]
+    fn test_part_id_is_distinct_per_part() {
+        let url = "https://example.com/book.pdf";
+        assert_eq!(
+            part_id(url, 1),
+            part_id("https://EXAMPLE.com/book.pdf#intro", 1)
+        );
+        assert_ne!(part_id(url, 1), part_id(url, 2));
+        assert_ne!(part_id(url, 1), document_id(url));
+    }
+
+    #[test]
+    fn test_content_fingerprint() {
+        assert_eq!(content_fingerprint(""), "cbf29ce484222325");
+        assert_eq!(content_fingerprint("# Page"), content_fingerprint("# Page"));
+        assert_ne!(content_fingerprint("# Page"), content_fingerprint("# Page 2"));
+    }
+
+    #[test]
+    fn test_document_slug() {
+        assert_eq!(
+            document_slug("https://github.com/rust-lang/rust/issues/1"),
+            "github-com-rust-lang-rust-issues-1"
+        );
+        assert_eq!(document_slug("https://example.com"), "example-com");
+
+        // Dropped queries and truncation add a hash so slugs stay distinct
+        let first = document_slug("https://example.com/view?id=1");
+        let second = document_slug("https://example.com/view?id=2");
+        assert!(first.starts_with("example-com-view-"));
+        assert_eq!(first.len(), "example-com-view-".len() + 8);
+        assert_ne!(first, second);
+        assert_eq!(
+            first,
+            document_slug("https://example.com/view?id=1&utm_source=feed")
+        );
+
+        let long =
+            |tail: &str| document_slug(&format!("https://example.com/{}{tail}", "a".repeat(200)));
+        assert!(long("b").len() <= 80);
+        assert_ne!(long("b"), long("c"));
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Hello, World!"), "hello-world");
+        assert_eq!(slugify("  --Already--Sluggy--  "), "already-sluggy");
+        assert_eq!(slugify("Café au lait"), "caf-au-lait");
+        assert_eq!(slugify("!!!"), "");
+    }
+}
@@ -0,0 +1,494 @@
+//! Include and snippet resolution for templated markdown sources.
+//!
+//! Markdown written for static site generators pulls shared text in with
+//! template directives, which are left in the output as noise when the source
+//! file is converted directly. The resolver replaces each directive with the
+//! file it names, so the converted page is self-contained:
+//!
+//! - **Jekyll**: `{% include note.html %}` from the site's `_includes` folder,
+//!   with `{{ include.param }}` values filled in, and
+//!   `{% include_relative part.md %}` from next to the page
+//! - **Hugo**: `{{< readfile file="part.md" >}}` and `{{% include "part.md" %}}`
+//! - **MkDocs snippets**: `--8<-- "part.md"` lines, and `--8<--` blocks that
+//!   list one file per line
+//!
+//! Files are looked up next to the page and then in each parent folder. Local
+//! pages only include local files, and fetched pages only include files from
+//! the same origin. Included files are resolved in turn, to a limited depth.
+//! Directives inside code blocks and code spans are left alone; directives
+//! whose file cannot be found are removed.
+//!
+//! # Usage Examples
+//!
+//! ```rust,no_run
+//! use markdowndown::client::HttpClient;
+//! use markdowndown::includes::IncludeResolver;
+//!
+//! # async fn example() {
+//! let client = HttpClient::new();
+//! let page = "# Setup\n\n{% include_relative install.md %}\n";
+//! let resolved = IncludeResolver::new(&client)
+//!     .resolve(page, "docs/setup.md")
+//!     .await;
+//! # }
+//! ```
+
+use crate::client::HttpClient;
+use crate::events;
+use futures::future::BoxFuture;
+use regex::{Captures, Regex};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+use url::Url;
+
+/// How many levels of included files are themselves resolved.
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// Marker of MkDocs snippet lines and blocks.
+const SNIPPET_MARKER: &str = "--8<--";
+
+/// Jekyll `include` and `include_relative` tags, with their parameters.
+const JEKYLL_PATTERN: &str = r"\{%-?\s*include(_relative)?\s+([^\s%]+)([^%]*?)-?%\}";
+
+/// Hugo `readfile` and `include` shortcodes, in either delimiter style.
+const HUGO_PATTERN: &str =
+    r#"\{\{[<%]-?\s*(?:readfile|include)\s+(?:file\s*=\s*)?["']([^"']+)["'][^}]*?[%>]\}\}"#;
+
+/// File extensions of markdown sources.
+const MARKDOWN_EXTENSIONS: [&str; 5] = ["md", "markdown", "mdx", "mkd", "mdown"];
+
+/// Where the file named by a directive is looked up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lookup {
+    /// Next to the including page only (Jekyll `include_relative`)
+    Relative,
+    /// In the `_includes` folder next to the page or in a parent folder (Jekyll `include`)
+    JekyllIncludes,
+    /// Next to the page, then in each parent folder (Hugo and MkDocs)
+    Ancestors,
+}
+
+/// A directive naming a file to include.
+#[derive(Debug, Clone, PartialEq)]
+struct Directive {
+    path: String,
+    lookup: Lookup,
+    /// Jekyll include parameters, substituted for `{{ include.name }}`
+    params: Vec<(String, String)>,
+}
+
+impl Directive {
+    fn new(path: &str, lookup: Lookup) -> Self {
+        Self {
+            path: path.trim_matches(|c| c == '"' || c == '\'').to_string(),
+            lookup,
+            params: Vec::new(),
+        }
+    }
+}
+
+/// A run of page text, or a directive to replace with an included file.
+#[derive(Debug, Clone, PartialEq)]
+enum Piece {
+    Text(String),
+    Include(Directive),
+}
+
+/// Where a page was read from, which is where its includes are read from.
+#[derive(Debug, Clone)]
+enum Source {
+    Local(PathBuf),
+    Remote(Url),
+}
+
+impl Source {
+    /// Parses a local path, `file://` URL, or HTTP(S) URL.
+    fn parse(location: &str) -> Option<Self> {
+        if let Some(path) = location.strip_prefix("file://") {
+            return Some(Self::Local(PathBuf::from(path)));
+        }
+        match Url::parse(location) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => Some(Self::Remote(url)),
+            // Other schemes have no folders to look files up in
+            Ok(url) if url.scheme().len() > 1 => None,
+            // Windows drive letters parse as one-letter schemes
+            _ => Some(Self::Local(PathBuf::from(location))),
+        }
+    }
+
+    /// Returns the locations to try for a directive's file, in order.
+    fn candidates(&self, directive: &Directive) -> Vec<Source> {
+        let path = directive.path.as_str();
+        match self {
+            Self::Local(page) => {
+                let dir = page.parent().unwrap_or(Path::new(""));
+                let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+                let bases: Vec<&Path> = match directive.lookup {
+                    Lookup::Relative => vec![dir.as_path()],
+                    _ => dir.ancestors().collect(),
+                };
+                bases
+                    .into_iter()
+                    .map(|base| match directive.lookup {
+                        Lookup::JekyllIncludes => base.join("_includes").join(path),
+                        _ => base.join(path),
+                    })
+                    .map(Self::Local)
+                    .collect()
+            }
+            Self::Remote(page) => {
+                let mut bases = Vec::new();
+                let mut base = page.join(".").ok();
+                while let Some(dir) = base {
+                    let parent = dir.join("..").ok().filter(|parent| *parent != dir);
+                    bases.push(dir);
+                    if directive.lookup == Lookup::Relative {
+                        break;
+                    }
+                    base = parent;
+                }
+                bases
+                    .into_iter()
+                    .filter_map(|base| match directive.lookup {
+                        Lookup::JekyllIncludes => base.join("_includes/").ok()?.join(path).ok(),
+                        _ => base.join(path).ok(),
+                    })
+                    // An absolute URL in a directive must not reach another site
+                    .filter(|candidate| candidate.origin() == page.origin())
+                    .map(Self::Remote)
+                    .collect()
+            }
+        }
+    }
+
+    /// Reads the file at this location, if it exists.
+    async fn read(&self, client: &HttpClient) -> Option<String> {
+        match self {
+            Self::Local(path) => tokio::fs::read_to_string(path).await.ok(),
+            Self::Remote(url) => client.get_text(url.as_str()).await.ok(),
+        }
+    }
+}
+
+/// Resolves include directives in markdown sources.
+#[derive(Debug, Clone)]
+pub struct IncludeResolver<'a> {
+    client: &'a HttpClient,
+}
+
+impl<'a> IncludeResolver<'a> {
+    /// Creates a resolver that fetches remote includes with the given client.
+    pub fn new(client: &'a HttpClient) -> Self {
+        Self { client }
+    }
+
+    /// Replaces the include directives in a page with the files they name.
+    ///
+    /// # Arguments
+    ///
+    /// * `markdown` - The page content
+    /// * `source` - The path or URL the page was read from
+    ///
+    /// # Returns
+    ///
+    /// The page with each directive replaced by its file, or removed if the
+    /// file cannot be found.
+    pub async fn resolve(&self, markdown: &str, source: &str) -> String {
+        self.expand(markdown, Source::parse(source).as_ref(), 0)
+            .await
+    }
+
+    /// Resolves the directives in `text`, read from `source`.
+    fn expand<'b>(
+        &'b self,
+        text: &'b str,
+        source: Option<&'b Source>,
+        depth: usize,
+    ) -> BoxFuture<'b, String> {
+        Box::pin(async move {
+            let mut output = String::with_capacity(text.len());
+            for piece in parse(text) {
+                match piece {
+                    Piece::Text(text) => output.push_str(&text),
+                    Piece::Include(directive) => {
+                        match self.include(&directive, source, depth).await {
+                            Some(included) => output.push_str(&included),
+                            None => {
+                                warn!("Removing unresolved include of {}", directive.path);
+                                events::warning(format!(
+                                    "Removing unresolved include of {}",
+                                    directive.path
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+            output
+        })
+    }
+
+    /// Reads and resolves the file a directive names.
+    async fn include(
+        &self,
+        directive: &Directive,
+        source: Option<&Source>,
+        depth: usize,
+    ) -> Option<String> {
+        if depth >= MAX_INCLUDE_DEPTH {
+            warn!(
+                "Not including {}, which is nested too deeply",
+                directive.path
+            );
+            return None;
+        }
+        for candidate in source?.candidates(directive) {
+            if let Some(text) = candidate.read(self.client).await {
+                let text = match directive.lookup {
+                    Lookup::Ancestors => text,
+                    _ => substitute_params(&text, &directive.params),
+                };
+                let resolved = self.expand(&text, Some(&candidate), depth + 1).await;
+                return Some(resolved.trim_end_matches('\n').to_string());
+            }
+        }
+        None
+    }
+}
+
+/// Returns whether a path or URL names a markdown source file.
+pub fn is_markdown_source(location: &str) -> bool {
+    let path = location.split(['?', '#']).next().unwrap_or_default();
+    Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            MARKDOWN_EXTENSIONS
+                .iter()
+                .any(|known| extension.eq_ignore_ascii_case(known))
+        })
+}
+
+/// Splits a page into text and include directives, skipping code.
+fn parse(text: &str) -> Vec<Piece> {
+    let jekyll = Regex::new(JEKYLL_PATTERN).expect("valid Jekyll include pattern");
+    let hugo = Regex::new(HUGO_PATTERN).expect("valid Hugo include pattern");
+    let mut pieces = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut in_snippet_block = false;
+
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim();
+        let ending = &line[line.trim_end().len()..];
+
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            pieces.push(Piece::Text(line.to_string()));
+            continue;
+        }
+        if in_snippet_block {
+            if trimmed == SNIPPET_MARKER {
+                in_snippet_block = false;
+            } else if !trimmed.is_empty() && !trimmed.starts_with(';') {
+                pieces.push(Piece::Include(Directive::new(trimmed, Lookup::Ancestors)));
+                pieces.push(Piece::Text(ending.to_string()));
+            }
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            fence = Some(marker);
+            pieces.push(Piece::Text(line.to_string()));
+            continue;
+        }
+        if trimmed == SNIPPET_MARKER {
+            in_snippet_block = true;
+            continue;
+        }
+        if let Some(path) = trimmed.strip_prefix(SNIPPET_MARKER) {
+            let path = path.trim();
+            if path.len() > 2 && (path.starts_with('"') || path.starts_with('\'')) {
+                pieces.push(Piece::Include(Directive::new(path, Lookup::Ancestors)));
+                pieces.push(Piece::Text(ending.to_string()));
+                continue;
+            }
+        }
+
+        // Odd segments between backticks are code spans
+        for (i, segment) in line.split('`').enumerate() {
+            if i > 0 {
+                pieces.push(Piece::Text("`".to_string()));
+            }
+            if i % 2 == 1 {
+                pieces.push(Piece::Text(segment.to_string()));
+            } else {
+                parse_inline(segment, &jekyll, &hugo, &mut pieces);
+            }
+        }
+    }
+    pieces
+}
+
+/// Splits text outside code into text and Jekyll or Hugo directives.
+fn parse_inline(text: &str, jekyll: &Regex, hugo: &Regex, pieces: &mut Vec<Piece>) {
+    let mut directives: Vec<(usize, usize, Directive)> = jekyll
+        .captures_iter(text)
+        .map(|caps| {
+            let lookup = if caps.get(1).is_some() {
+                Lookup::Relative
+            } else {
+                Lookup::JekyllIncludes
+            };
+            let mut directive = Directive::new(&caps[2], lookup);
+            directive.params = parse_params(&caps[3]);
+            let span = caps.get(0).expect("whole match");
+            (span.start(), span.end(), directive)
+        })
+        .chain(hugo.captures_iter(text).map(|caps| {
+            let span = caps.get(0).expect("whole match");
+            (
+                span.start(),
+                span.end(),
+                Directive::new(&caps[1], Lookup::Ancestors),
+            )
+        }))
+        .collect();
+    directives.sort_by_key(|(start, _, _)| *start);
+
+    let mut last = 0;
+    for (start, end, directive) in directives {
+        if start < last {
+            continue;
+        }
+        pieces.push(Piece::Text(text[last..start].to_string()));
+        pieces.push(Piece::Include(directive));
+        last = end;
+    }
+    pieces.push(Piece::Text(text[last..].to_string()));
+}
+
+/// Parses Jekyll include parameters written as `name="value"`, `name='value'`, or `name=value`.
+fn parse_params(text: &str) -> Vec<(String, String)> {
+    let param = Regex::new(r#"(\w+)\s*=\s*(?:"([^"]*)"|'([^']*)'|(\S+))"#)
+        .expect("valid include parameter pattern");
+    param
+        .captures_iter(text)
+        .map(|caps| {
+            let value = caps
+                .get(2)
+                .or_else(|| caps.get(3))
+                .or_else(|| caps.get(4))
+                .map_or("", |m| m.as_str());
+            (caps[1].to_string(), value.to_string())
+        })
+        .collect()
+}
+
+/// Fills in `{{ include.name }}` references from Jekyll include parameters.
+fn substitute_params(text: &str, params: &[(String, String)]) -> String {
+    let reference =
+        Regex::new(r"\{\{-?\s*include\.(\w+)\s*-?\}\}").expect("valid include reference pattern");
+    reference
+        .replace_all(text, |caps: &Captures| {
+            params
+                .iter()
+                .find(|(name, _)| *name == caps[1])
+                .map(|(_, value)| value.clone())
+                .unwrap_or_default()
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_resolve_local_includes() {
+        let site = TempDir::new().unwrap();
+        fs::create_dir_all(site.path().join("_includes")).unwrap();
+        fs::create_dir_all(site.path().join("docs/guide")).unwrap();
+        fs::write(
+            site.path().join("_includes/note.html"),
+            "> **{{ include.title }}**: {% include_relative missing.md %}\n",
+        )
+        .unwrap();
+        fs::write(site.path().join("docs/guide/steps.md"), "1. Install\n").unwrap();
+        fs::write(site.path().join("docs/shared.md"), "Shared text.\n").unwrap();
+        let page = site.path().join("docs/guide/setup.md");
+
+        let markdown = "# Setup\n\
+            {% include note.html title=\"Heads up\" %}\n\
+            {% include_relative steps.md %}\n\
+            {{< readfile file=\"shared.md\" >}}\n\
+            --8<-- \"shared.md\"\n\
+            Use `{% include note.html %}` in templates.\n\
+            ```liquid\n{% include note.html %}\n```\n\
+            {{% include \"nowhere.md\" %}}\n";
+
+        let client = HttpClient::new();
+        let resolved = IncludeResolver::new(&client)
+            .resolve(markdown, page.to_str().unwrap())
+            .await;
+        assert_eq!(
+            resolved,
+            "# Setup\n\
+            > **Heads up**: \n\
+            1. Install\n\
+            Shared text.\n\
+            Shared text.\n\
+            Use `{% include note.html %}` in templates.\n\
+            ```liquid\n{% include note.html %}\n```\n\
+            \n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_remote_snippet_block() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/docs/abbreviations.md"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("*[API]: Application Programming Interface\n"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/links.md"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string("[home]: https://example.com\n"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let markdown = "Call the API.\n\n--8<--\nabbreviations.md\n; commented.md\nlinks.md\nhttps://example.org/secret.md\n--8<--\n";
+        let config = crate::Config::builder().max_retries(0).build();
+        let client = HttpClient::with_config(&config.http, &config.auth);
+        let resolved = IncludeResolver::new(&client)
+            .resolve(markdown, &format!("{}/docs/page.md", server.uri()))
+            .await;
+        assert_eq!(
+            resolved,
+            "Call the API.\n\n*[API]: Application Programming Interface\n[home]: https://example.com\n\n"
+        );
+    }
+
+    #[test]
+    fn test_is_markdown_source() {
+        assert!(is_markdown_source("docs/index.md"));
+        assert!(is_markdown_source("https://example.com/README.MD?plain=1"));
+        assert!(is_markdown_source("file:///site/_posts/intro.markdown"));
+        assert!(!is_markdown_source("https://example.com/page.html"));
+        assert!(!is_markdown_source("https://example.com/"));
+    }
+}
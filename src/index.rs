@@ -0,0 +1,235 @@
+//! Index documents linking to the outputs of a batch conversion.
+//!
+//! An archive of converted documents needs an entry point a person can
+//! browse. [`render_index`] builds a markdown document that links to every
+//! converted output, with its title and date, grouped by host or by the
+//! path of the source URL.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::index::{render_index, IndexEntry, IndexGrouping};
+//!
+//! let document = "---\ntitle: Install Guide\npublished_date: 2024-03-01T00:00:00Z\n---\n\n# Install\n";
+//! let entries = vec![IndexEntry::from_markdown(
+//!     "https://example.com/docs/install",
+//!     "001.md",
+//!     document,
+//! )];
+//!
+//! let index = render_index(&entries, IndexGrouping::Host);
+//! assert!(index.contains("## example.com"));
+//! assert!(index.contains("- [Install Guide](001.md) (2024-03-01)"));
+//! ```
+
+use crate::frontmatter::frontmatter_field;
+use crate::sections::extract_sections;
+use crate::utils::parse_date;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// How entries in an index document are grouped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum IndexGrouping {
+    /// One section per host
+    #[default]
+    Host,
+    /// One section per host and folder path, like a breadcrumb trail
+    Path,
+}
+
+impl FromStr for IndexGrouping {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "host" => Ok(IndexGrouping::Host),
+            "path" | "breadcrumb" => Ok(IndexGrouping::Path),
+            other => Err(format!(
+                "unknown index grouping '{other}' (expected host or path)"
+            )),
+        }
+    }
+}
+
+/// A converted document listed in an index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexEntry {
+    /// The URL the document was converted from
+    pub url: String,
+    /// Where the converted output is, relative to the index document
+    pub link: String,
+    /// The document title, when known
+    pub title: Option<String>,
+    /// The publication date as `YYYY-MM-DD`, when known
+    pub date: Option<String>,
+}
+
+impl IndexEntry {
+    /// Creates an entry, reading the title and date from the output's frontmatter.
+    ///
+    /// Documents without a frontmatter title are titled by their first
+    /// top-level heading.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL the document was converted from
+    /// * `link` - Where the converted output is, relative to the index document
+    /// * `markdown` - The converted output
+    pub fn from_markdown(url: &str, link: &str, markdown: &str) -> Self {
+        let date = frontmatter_field(markdown, "published_date").map(|date| {
+            parse_date(&date)
+                .map(|parsed| parsed.format("%Y-%m-%d").to_string())
+                .unwrap_or(date)
+        });
+        Self {
+            url: url.to_string(),
+            link: link.to_string(),
+            title: document_title(markdown),
+            date,
+        }
+    }
+
+    /// Returns the heading of the section this entry is listed under.
+    ///
+    /// Local files are grouped under "Local files", by folder for
+    /// [`IndexGrouping::Path`].
+    fn group(&self, grouping: IndexGrouping) -> String {
+        let (root, path) = match url::Url::parse(&self.url) {
+            Ok(parsed) if parsed.scheme() != "file" => match parsed.host_str() {
+                Some(host) => (host.to_string(), parsed.path().to_string()),
+                None => ("Other".to_string(), String::new()),
+            },
+            Ok(parsed) => ("Local files".to_string(), parsed.path().to_string()),
+            Err(_) => ("Local files".to_string(), self.url.clone()),
+        };
+        match grouping {
+            IndexGrouping::Host => root,
+            IndexGrouping::Path => {
+                let segments: Vec<&str> = path.split(['/', '\\']).collect();
+                let folders = &segments[..segments.len().saturating_sub(1)];
+                let mut crumbs = vec![root.as_str()];
+                crumbs.extend(folders.iter().filter(|segment| !segment.is_empty()));
+                crumbs.join(" / ")
+            }
+        }
+    }
+}
+
+/// Returns the frontmatter title of a document, or its first top-level heading.
+pub(crate) fn document_title(markdown: &str) -> Option<String> {
+    frontmatter_field(markdown, "title")
+        .filter(|title| !title.trim().is_empty())
+        .or_else(|| {
+            extract_sections(markdown)
+                .into_iter()
+                .find(|section| section.level == 1)
+                .map(|section| section.text)
+        })
+}
+
+/// Renders an index document linking to converted outputs.
+///
+/// Sections are sorted by name and entries within a section by URL. Each
+/// entry links to its output with the document title, or the URL when there
+/// is no title, followed by the date when known.
+///
+/// # Arguments
+///
+/// * `entries` - The converted documents to list
+/// * `grouping` - Whether sections are per host or per folder path
+///
+/// # Returns
+///
+/// The index as a markdown document.
+pub fn render_index(entries: &[IndexEntry], grouping: IndexGrouping) -> String {
+    let mut groups: BTreeMap<String, Vec<&IndexEntry>> = BTreeMap::new();
+    for entry in entries {
+        groups.entry(entry.group(grouping)).or_default().push(entry);
+    }
+
+    let count = match entries.len() {
+        1 => "1 document".to_string(),
+        n => format!("{n} documents"),
+    };
+    let mut index = format!("# Index\n\n{count}.\n");
+    for (group, mut members) in groups {
+        members.sort_by(|a, b| a.url.cmp(&b.url));
+        index.push_str(&format!("\n## {group}\n\n"));
+        for entry in members {
+            let title = entry.title.as_deref().unwrap_or(&entry.url);
+            let text = title.replace('[', "\\[").replace(']', "\\]");
+            let link = if entry.link.contains([' ', '(', ')']) {
+                format!("<{}>", entry.link)
+            } else {
+                entry.link.clone()
+            };
+            match &entry.date {
+                Some(date) => index.push_str(&format!("- [{text}]({link}) ({date})\n")),
+                None => index.push_str(&format!("- [{text}]({link})\n")),
+            }
+        }
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(url: &str, link: &str, title: Option<&str>) -> IndexEntry {
+        IndexEntry {
+            url: url.to_string(),
+            link: link.to_string(),
+            title: title.map(str::to_string),
+            date: None,
+        }
+    }
+
+    #[test]
+    fn test_render_index_by_path() {
+        let entries = vec![
+            entry(
+                "https://b.example.com/docs/guide/setup",
+                "002.md",
+                Some("Setup"),
+            ),
+            entry("https://a.example.com/about", "003.md", None),
+            entry("/srv/notes/todo.md", "004.md", Some("Todo")),
+            entry(
+                "https://b.example.com/docs/guide/intro",
+                "001 intro.md",
+                Some("[Draft] Intro"),
+            ),
+        ];
+
+        let index = render_index(&entries, IndexGrouping::Path);
+        assert_eq!(
+            index,
+            "# Index\n\n4 documents.\n\n\
+             ## Local files / srv / notes\n\n\
+             - [Todo](004.md)\n\n\
+             ## a.example.com\n\n\
+             - [https://a.example.com/about](003.md)\n\n\
+             ## b.example.com / docs / guide\n\n\
+             - [\\[Draft\\] Intro](<001 intro.md>)\n\
+             - [Setup](002.md)\n"
+        );
+    }
+
+    #[test]
+    fn test_index_entry_reads_frontmatter() {
+        let markdown =
+            "---\ntitle: Notes\npublished_date: 2023-11-05T10:00:00+00:00\n---\n\nBody\n";
+        let entry = IndexEntry::from_markdown("https://example.com/notes", "notes.md", markdown);
+        assert_eq!(entry.title.as_deref(), Some("Notes"));
+        assert_eq!(entry.date.as_deref(), Some("2023-11-05"));
+
+        let bare =
+            IndexEntry::from_markdown("https://example.com/raw", "raw.md", "## Raw\n\n# Top\n");
+        assert_eq!(bare.title.as_deref(), Some("Top"));
+        assert_eq!(bare.date, None);
+        assert_eq!("breadcrumb".parse(), Ok(IndexGrouping::Path));
+    }
+}
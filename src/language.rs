@@ -0,0 +1,626 @@
+//! Heuristic language detection for multilingual documents.
+//!
+//! Bilingual pages such as policy documents often print each paragraph
+//! twice, once per language. Converted as-is, the markdown alternates
+//! between the two. This module guesses the language of each block from
+//! its script and common short words, recognizes documents that interleave
+//! two languages, and keeps only the blocks of a requested language.
+//!
+//! Right-to-left text (Arabic, Hebrew, and related scripts) is displayed
+//! left-to-right by most markdown viewers unless it is marked. The module
+//! also detects right-to-left blocks and marks them, either with invisible
+//! Unicode right-to-left marks or with HTML `dir` wrappers.
+//!
+//! Detection is deliberately simple: blocks too short to judge, code
+//! blocks, and the frontmatter are never dropped or marked.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::language::{detect_bilingual, extract_language};
+//!
+//! let markdown = "The policy applies to all of the staff.\n\n\
+//!                 La politique s'applique à tout le personnel.\n\n\
+//!                 It is reviewed every year by the board.\n\n\
+//!                 Elle est revue chaque année par le conseil.\n";
+//!
+//! assert_eq!(detect_bilingual(markdown), Some(("en", "fr")));
+//!
+//! let english = extract_language(markdown, "en").unwrap();
+//! assert!(english.contains("reviewed every year"));
+//! assert!(!english.contains("personnel"));
+//! ```
+
+use crate::config::DirectionMarkers;
+use std::collections::HashMap;
+
+/// Minimum words in a Latin-script block before its language is guessed.
+const MIN_WORDS: usize = 4;
+
+/// Minimum stopword hits before a Latin-script language is chosen.
+const MIN_STOPWORD_HITS: usize = 2;
+
+/// Share of detected blocks the less common language needs for a bilingual document.
+const MIN_SECONDARY_SHARE: f64 = 0.3;
+
+/// Common short words for each Latin-script language, by ISO 639-1 code.
+const STOPWORDS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "the", "and", "of", "to", "is", "in", "that", "it", "for", "with", "are", "this", "be",
+            "on", "you", "we", "our", "not", "or", "by", "will", "all", "was", "have",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "le", "la", "les", "des", "et", "est", "un", "une", "du", "que", "pour", "dans", "qui",
+            "sur", "pas", "nous", "vous", "avec", "au", "ce", "tout", "elle", "sont", "par",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "el", "la", "los", "las", "y", "es", "un", "una", "del", "que", "para", "en", "por",
+            "con", "no", "se", "su", "al", "como", "lo", "todo", "todos", "son", "está",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "der", "die", "das", "und", "ist", "nicht", "ein", "eine", "zu", "den", "mit", "von",
+            "für", "auf", "sich", "dem", "wir", "sie", "auch", "werden", "wird", "alle", "jedes",
+            "im",
+        ],
+    ),
+    (
+        "it",
+        &[
+            "il", "la", "di", "e", "che", "è", "un", "una", "per", "non", "del", "della", "gli",
+            "con", "sono", "le", "si", "da", "nel", "al", "tutto", "tutti", "ogni", "viene",
+        ],
+    ),
+    (
+        "pt",
+        &[
+            "o", "a", "os", "as", "de", "e", "que", "não", "um", "uma", "para", "com", "do", "da",
+            "em", "é", "no", "na", "se", "por", "todo", "todos", "são", "cada",
+        ],
+    ),
+    (
+        "nl",
+        &[
+            "de", "het", "een", "en", "van", "is", "dat", "niet", "op", "te", "voor", "met",
+            "zijn", "wij", "ze", "ook", "aan", "er", "bij", "worden", "wordt", "alle", "elk",
+            "jaar",
+        ],
+    ),
+];
+
+/// Guesses the language of a block of text.
+///
+/// Text in a non-Latin script is identified by its script alone. Latin-script
+/// text is matched against common short words in English, French, Spanish,
+/// German, Italian, Portuguese, and Dutch.
+///
+/// # Arguments
+///
+/// * `text` - The text to inspect
+///
+/// # Returns
+///
+/// The ISO 639-1 code of the most likely language, or `None` when the text
+/// is too short or too ambiguous to judge.
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let mut scripts: HashMap<&'static str, usize> = HashMap::new();
+    let mut letters = 0;
+    for c in text.chars().filter(|c| c.is_alphabetic()) {
+        letters += 1;
+        if let Some(script) = script_language(c) {
+            *scripts.entry(script).or_default() += 1;
+        }
+    }
+    if letters == 0 {
+        return None;
+    }
+
+    // Japanese mixes kana with Han characters, so any kana decides it
+    if scripts.get("ja").is_some_and(|kana| kana * 10 >= letters) {
+        return Some("ja");
+    }
+    if let Some((&script, &count)) = scripts.iter().max_by_key(|(_, count)| **count) {
+        if count * 2 > letters {
+            if script == "ru" && text.chars().any(|c| "іїєґІЇЄҐ".contains(c)) {
+                return Some("uk");
+            }
+            return Some(script);
+        }
+    }
+
+    detect_latin_language(text)
+}
+
+/// Maps a character in a script used by a single common language to that language.
+fn script_language(c: char) -> Option<&'static str> {
+    match c as u32 {
+        0x0370..=0x03FF => Some("el"),
+        0x0400..=0x04FF => Some("ru"),
+        0x0590..=0x05FF => Some("he"),
+        0x0600..=0x06FF => Some("ar"),
+        0x0900..=0x097F => Some("hi"),
+        0x0E00..=0x0E7F => Some("th"),
+        0x3040..=0x30FF => Some("ja"),
+        0x4E00..=0x9FFF => Some("zh"),
+        0xAC00..=0xD7AF => Some("ko"),
+        _ => None,
+    }
+}
+
+/// Guesses the language of Latin-script text from its common short words.
+fn detect_latin_language(text: &str) -> Option<&'static str> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphabetic() && c != '\'')
+        .flat_map(|word| word.split('\''))
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    if words.len() < MIN_WORDS {
+        return None;
+    }
+
+    let mut scores: Vec<(&'static str, usize)> = STOPWORDS
+        .iter()
+        .map(|(language, stopwords)| {
+            let hits = words
+                .iter()
+                .filter(|word| stopwords.contains(&word.as_str()))
+                .count();
+            (*language, hits)
+        })
+        .collect();
+    scores.sort_by_key(|&(_, hits)| std::cmp::Reverse(hits));
+
+    match scores.as_slice() {
+        [(language, best), (_, second), ..] if *best >= MIN_STOPWORD_HITS && best > second => {
+            Some(language)
+        }
+        _ => None,
+    }
+}
+
+/// A block of a markdown document, with the language guessed for it.
+struct Block<'a> {
+    text: &'a str,
+    /// Whether the block holds the frontmatter or a fenced code block
+    code: bool,
+    language: Option<&'static str>,
+}
+
+/// Splits a markdown document into blank-line separated blocks.
+///
+/// The frontmatter and fenced code blocks are kept whole and never get a
+/// language, so they are always kept.
+fn split_blocks(markdown: &str) -> Vec<Block<'_>> {
+    let mut blocks = Vec::new();
+    let mut start = 0;
+    let mut offset = 0;
+    let mut in_frontmatter = markdown.starts_with("---\n");
+    let mut in_code_block = false;
+    let mut protected = in_frontmatter;
+    let mut after_frontmatter = false;
+
+    for (index, line) in markdown.split_inclusive('\n').enumerate() {
+        offset += line.len();
+        let trimmed = line.trim();
+
+        if in_frontmatter {
+            if index > 0 && trimmed == "---" {
+                in_frontmatter = false;
+                after_frontmatter = true;
+            }
+            continue;
+        }
+        // Text right after the frontmatter starts a block of its own
+        if std::mem::take(&mut after_frontmatter) && !trimmed.is_empty() {
+            let end = offset - line.len();
+            blocks.push(Block {
+                text: &markdown[start..end],
+                code: true,
+                language: None,
+            });
+            start = end;
+            protected = false;
+        }
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            protected = true;
+            continue;
+        }
+        if in_code_block || !trimmed.is_empty() {
+            continue;
+        }
+
+        // A blank line ends the current block; blank lines belong to the block before them
+        if markdown[start..offset - line.len()].trim().is_empty() {
+            continue;
+        }
+        let text = &markdown[start..offset];
+        blocks.push(Block {
+            text,
+            code: protected,
+            language: if protected {
+                None
+            } else {
+                detect_language(text)
+            },
+        });
+        start = offset;
+        protected = false;
+    }
+    if start < markdown.len() {
+        let text = &markdown[start..];
+        blocks.push(Block {
+            text,
+            code: protected || in_frontmatter,
+            language: if protected || in_frontmatter {
+                None
+            } else {
+                detect_language(text)
+            },
+        });
+    }
+    blocks
+}
+
+/// Detects a document whose blocks alternate between two languages.
+///
+/// A document is bilingual when its two most common block languages each
+/// cover a fair share of the blocks and the text switches between them at
+/// least as often as the less common language appears, as with
+/// side-by-side translations.
+///
+/// # Arguments
+///
+/// * `markdown` - The markdown document, with or without frontmatter
+///
+/// # Returns
+///
+/// The two languages, most common first, or `None` for a document in one
+/// language.
+pub fn detect_bilingual(markdown: &str) -> Option<(&'static str, &'static str)> {
+    let languages: Vec<&'static str> = split_blocks(markdown)
+        .iter()
+        .filter_map(|block| block.language)
+        .collect();
+
+    let mut counts: HashMap<&'static str, usize> = HashMap::new();
+    for language in &languages {
+        *counts.entry(language).or_default() += 1;
+    }
+    let mut ranked: Vec<(&'static str, usize)> = counts.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    let (primary, primary_count) = *ranked.first()?;
+    let (secondary, secondary_count) = *ranked.get(1)?;
+    let share = secondary_count as f64 / (primary_count + secondary_count) as f64;
+    if secondary_count < 2 || share < MIN_SECONDARY_SHARE {
+        return None;
+    }
+
+    let pair: Vec<&'static str> = languages
+        .into_iter()
+        .filter(|language| *language == primary || *language == secondary)
+        .collect();
+    let switches = pair
+        .windows(2)
+        .filter(|window| window[0] != window[1])
+        .count();
+    (switches >= secondary_count).then_some((primary, secondary))
+}
+
+/// Keeps only the blocks of one language in a bilingual document.
+///
+/// Blocks detected as another language are dropped. Blocks whose language
+/// could not be judged, such as short headings, code blocks, and the
+/// frontmatter, are kept.
+///
+/// # Arguments
+///
+/// * `markdown` - The markdown document, with or without frontmatter
+/// * `language` - The language to keep, as an ISO 639-1 code; region
+///   subtags such as `en-GB` are ignored
+///
+/// # Returns
+///
+/// The filtered document, or `None` when the document does not interleave
+/// the requested language with another one.
+pub fn extract_language(markdown: &str, language: &str) -> Option<String> {
+    let wanted = language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let (primary, secondary) = detect_bilingual(markdown)?;
+    if wanted != primary && wanted != secondary {
+        return None;
+    }
+
+    Some(
+        split_blocks(markdown)
+            .into_iter()
+            .filter(|block| block.language.is_none_or(|detected| detected == wanted))
+            .map(|block| block.text)
+            .collect(),
+    )
+}
+
+/// Right-to-left mark, which gives a line a right-to-left base direction.
+const RIGHT_TO_LEFT_MARK: char = '\u{200F}';
+
+/// Whether a character belongs to a right-to-left script.
+fn is_rtl_char(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x0590..=0x08FF | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFF
+    )
+}
+
+/// Judges whether a block of text reads right-to-left.
+///
+/// Letters in link targets and HTML tags are ignored, so a right-to-left
+/// link text is not outweighed by its URL.
+///
+/// Returns `None` when the text has no letters.
+fn is_rtl(text: &str) -> Option<bool> {
+    let mut rtl = 0;
+    let mut ltr = 0;
+    let mut previous = ' ';
+    let mut skip_until = None;
+    for c in text.chars() {
+        match skip_until {
+            Some(end) if c == end => skip_until = None,
+            Some(_) => {}
+            None if c == '(' && previous == ']' => skip_until = Some(')'),
+            None if c == '<' => skip_until = Some('>'),
+            None if is_rtl_char(c) => rtl += 1,
+            None if c.is_alphabetic() => ltr += 1,
+            None => {}
+        }
+        previous = c;
+    }
+    (rtl + ltr > 0).then_some(rtl > ltr)
+}
+
+/// Detects the base direction of a markdown document.
+///
+/// Each block outside code blocks and the frontmatter is judged by whether
+/// most of its letters are in a right-to-left script.
+///
+/// # Arguments
+///
+/// * `markdown` - The markdown document, with or without frontmatter
+///
+/// # Returns
+///
+/// `Some("rtl")` when most blocks read right-to-left, `Some("mixed")` when
+/// only some do, and `None` for a document without right-to-left text.
+pub fn detect_direction(markdown: &str) -> Option<&'static str> {
+    let directions: Vec<bool> = split_blocks(markdown)
+        .iter()
+        .filter(|block| !block.code)
+        .filter_map(|block| is_rtl(block.text))
+        .collect();
+    let rtl = directions.iter().filter(|rtl| **rtl).count();
+    match rtl {
+        0 => None,
+        _ if rtl * 2 > directions.len() => Some("rtl"),
+        _ => Some("mixed"),
+    }
+}
+
+/// Marks the right-to-left blocks of a markdown document.
+///
+/// With [`DirectionMarkers::Unicode`] a right-to-left mark is placed at the
+/// start of each line's text, after any heading, list, or quote marker, so
+/// the markdown structure is unchanged. Table rows are left alone. With
+/// [`DirectionMarkers::Html`] each run of right-to-left blocks is wrapped in
+/// a `<div dir="rtl">`, with blank lines so the markdown inside is still
+/// rendered.
+///
+/// # Arguments
+///
+/// * `markdown` - The markdown document, with or without frontmatter
+/// * `markers` - How right-to-left blocks are marked
+///
+/// # Returns
+///
+/// The marked document; unchanged when `markers` is
+/// [`DirectionMarkers::Off`] or nothing reads right-to-left.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdowndown::config::DirectionMarkers;
+/// use markdowndown::language::mark_direction;
+///
+/// let markdown = "# عنوان\n\nمرحبا بالعالم\n";
+/// let marked = mark_direction(markdown, DirectionMarkers::Html);
+/// assert_eq!(marked, "<div dir=\"rtl\">\n\n# عنوان\n\nمرحبا بالعالم\n\n</div>\n");
+/// ```
+pub fn mark_direction(markdown: &str, markers: DirectionMarkers) -> String {
+    if markers == DirectionMarkers::Off {
+        return markdown.to_string();
+    }
+
+    let mut output = String::with_capacity(markdown.len());
+    let mut run = String::new();
+    for block in split_blocks(markdown) {
+        if !block.code && is_rtl(block.text) == Some(true) {
+            run.push_str(block.text);
+            continue;
+        }
+        push_rtl_run(&mut output, &run, markers);
+        run.clear();
+        output.push_str(block.text);
+    }
+    push_rtl_run(&mut output, &run, markers);
+    output
+}
+
+/// Appends a run of consecutive right-to-left blocks, marked as configured.
+fn push_rtl_run(output: &mut String, run: &str, markers: DirectionMarkers) {
+    if run.is_empty() {
+        return;
+    }
+    match markers {
+        DirectionMarkers::Off => output.push_str(run),
+        DirectionMarkers::Unicode => {
+            for line in run.split_inclusive('\n') {
+                let text = line.trim_start();
+                if text.trim().is_empty() || text.starts_with('|') {
+                    output.push_str(line);
+                    continue;
+                }
+                let prefix = line.len() - text.len() + block_marker_len(text);
+                output.push_str(&line[..prefix]);
+                output.push(RIGHT_TO_LEFT_MARK);
+                output.push_str(&line[prefix..]);
+            }
+        }
+        DirectionMarkers::Html => {
+            let body = run.trim_end_matches('\n');
+            output.push_str("<div dir=\"rtl\">\n\n");
+            output.push_str(body);
+            output.push_str("\n\n</div>\n");
+            output.push_str(&run[body.len()..].replacen('\n', "", 1));
+        }
+    }
+}
+
+/// Measures the quote, heading, list, and task markers at the start of a line.
+fn block_marker_len(line: &str) -> usize {
+    let mut rest = line;
+    while let Some(quoted) = rest.strip_prefix('>') {
+        rest = quoted.trim_start_matches(' ');
+    }
+    let hashes = rest.len() - rest.trim_start_matches('#').len();
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if (1..=6).contains(&hashes) && rest[hashes..].starts_with(' ') {
+        rest = &rest[hashes + 1..];
+    } else if ["- ", "* ", "+ "]
+        .iter()
+        .any(|bullet| rest.starts_with(bullet))
+    {
+        rest = &rest[2..];
+    } else if digits > 0 && (rest[digits..].starts_with(". ") || rest[digits..].starts_with(") ")) {
+        rest = &rest[digits + 2..];
+    }
+    for task in ["[ ] ", "[x] ", "[X] "] {
+        if let Some(unchecked) = rest.strip_prefix(task) {
+            rest = unchecked;
+        }
+    }
+    line.len() - rest.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(
+            detect_language("The data is stored for the length of the contract."),
+            Some("en")
+        );
+        assert_eq!(
+            detect_language("Les données sont conservées pendant toute la durée du contrat."),
+            Some("fr")
+        );
+        assert_eq!(
+            detect_language("Die Daten werden für die Dauer des Vertrags gespeichert."),
+            Some("de")
+        );
+        assert_eq!(
+            detect_language("Данные хранятся в течение срока договора."),
+            Some("ru")
+        );
+        assert_eq!(
+            detect_language("データは契約期間中保存されます。"),
+            Some("ja")
+        );
+        assert_eq!(detect_language("Privacy"), None);
+    }
+
+    #[test]
+    fn test_extract_language_keeps_frontmatter_code_and_headings() {
+        let markdown = "---\ntitle: Policy\n---\n\n# Policy / Politique\n\n\
+                        The policy applies to all of the staff.\n\n\
+                        La politique s'applique à tout le personnel.\n\n\
+                        ```\nle code\n\nthe code\n```\n\n\
+                        It is reviewed every year by the board.\n\n\
+                        Elle est revue chaque année par le conseil.\n";
+
+        let french = extract_language(markdown, "fr-CA").unwrap();
+        assert_eq!(
+            french,
+            "---\ntitle: Policy\n---\n\n# Policy / Politique\n\n\
+             La politique s'applique à tout le personnel.\n\n\
+             ```\nle code\n\nthe code\n```\n\n\
+             Elle est revue chaque année par le conseil.\n"
+        );
+        assert!(extract_language(markdown, "de").is_none());
+    }
+
+    #[test]
+    fn test_detect_bilingual_ignores_single_language_and_halves() {
+        let english = "The policy applies to all of the staff.\n\n\
+                       It is reviewed every year by the board.\n";
+        assert_eq!(detect_bilingual(english), None);
+
+        let halves = "The policy applies to all of the staff.\n\n\
+                      It is reviewed every year by the board.\n\n\
+                      The board is elected by all of the members.\n\n\
+                      La politique s'applique à tout le personnel.\n\n\
+                      Elle est revue chaque année par le conseil.\n";
+        assert_eq!(detect_bilingual(halves), None);
+    }
+
+    #[test]
+    fn test_detect_direction() {
+        assert_eq!(detect_direction("# Title\n\nHello world.\n"), None);
+        assert_eq!(
+            detect_direction(
+                "# שלום\n\nזהו מסמך בעברית.\n\nSee [הקישור](https://example.com/page).\n"
+            ),
+            Some("rtl")
+        );
+        assert_eq!(
+            detect_direction("# Policy\n\nThe English text.\n\nالنص العربي.\n"),
+            Some("mixed")
+        );
+    }
+
+    #[test]
+    fn test_mark_direction_unicode() {
+        let markdown = "---\ntitle: x\n---\n# Policy\n\n## سياسة\n\n- أولا\n- ثانيا\n\n\
+                        > نص مقتبس\n\n```\nكود\n```\n";
+        let marked = mark_direction(markdown, DirectionMarkers::Unicode);
+        assert_eq!(
+            marked,
+            "---\ntitle: x\n---\n# Policy\n\n## \u{200F}سياسة\n\n- \u{200F}أولا\n- \u{200F}ثانيا\n\n\
+             > \u{200F}نص مقتبس\n\n```\nكود\n```\n"
+        );
+        assert_eq!(mark_direction(markdown, DirectionMarkers::Off), markdown);
+    }
+
+    #[test]
+    fn test_mark_direction_html_wraps_runs() {
+        let markdown = "# Policy\n\nمرحبا\n\nبالعالم\n\nEnglish again.\n";
+        assert_eq!(
+            mark_direction(markdown, DirectionMarkers::Html),
+            "# Policy\n\n<div dir=\"rtl\">\n\nمرحبا\n\nبالعالم\n\n</div>\n\nEnglish again.\n"
+        );
+    }
+}
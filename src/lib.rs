@@ -14,6 +14,16 @@
 //! - URL type detection for automatic handler selection
 //! - Specific handlers for each supported URL type
 //! - Unified public API for simple integration
+//!
+//! ## API Stability
+//!
+//! The [`prelude`] holds the stable API: the [`MarkdownDown`] entry point,
+//! its configuration, the markdown and error types, and the [`Converter`]
+//! trait for custom converters. Other modules are public so applications can
+//! reach further in, but their contents may change in a minor release.
+
+/// The stable public API, for glob import
+pub mod prelude;
 
 /// Core types, traits, and error definitions
 pub mod types;
@@ -36,10 +46,147 @@ pub mod config;
 /// Utility functions shared across the codebase
 pub mod utils;
 
-use crate::client::HttpClient;
-use crate::converters::ConverterRegistry;
+/// Allow/deny content filters
+pub mod filter;
+
+/// Stable document IDs and slugs
+pub mod identity;
+
+/// Outbound link inventory
+pub mod links;
+
+/// Document heading tree
+pub mod sections;
+
+/// Persistent crawl frontier with priority scoring
+pub mod frontier;
+
+/// Per-host request statistics
+pub mod stats;
+
+/// Adaptive per-host concurrency limits for batches
+pub mod concurrency;
+
+/// Per-stage timing of conversions
+pub mod profiling;
+
+/// Markdown linting of converted output
+pub mod lint;
+
+/// Include resolution for templated markdown sources
+pub mod includes;
+
+/// Heuristic language detection for multilingual documents
+pub mod language;
+
+/// Index documents linking to the outputs of a batch conversion
+pub mod index;
+
+/// Link graphs of converted documents
+pub mod graph;
+
+/// Search-index documents for converted output
+pub mod search;
+
+/// Overlapping text chunks for embedding
+pub mod chunks;
+
+/// Site conversion driven by sitemap.xml files
+pub mod sitemap;
+
+/// Recursive crawling from a seed URL, respecting robots.txt
+pub mod crawl;
+
+/// Optional gzip and zstd compression of stored output
+pub mod compression;
+
+/// Stable tracing events for auditing conversions
+pub mod events;
+
+/// Documentation conversion from git repositories
+pub mod repository;
+
+/// Environment diagnostics: connectivity, API tokens, and external tools
+pub mod doctor;
+
+/// Optional Cargo features compiled into this build
+pub mod features;
+
+/// In-memory DNS cache shared across a batch run
+pub mod dns;
+
+/// OAuth access tokens minted from Google service account keys
+pub mod google_auth;
+
+/// HTTP response caching with ETag and Last-Modified revalidation
+pub mod http_cache;
+
+/// Per-host request rate limits using token buckets
+pub mod rate_limit;
+
+/// Delay policies for retrying failed requests
+pub mod retry;
+
+/// Persistent cache of converted markdown, keyed by URL and configuration
+pub mod markdown_cache;
+
+/// OAuth 2.0 device sign-in and token refresh for Google and Microsoft
+pub mod oauth;
+
+/// Image downloading and link rewriting for converted documents
+pub mod assets;
+
+/// Progress reporting for slow conversions and batches
+pub mod progress;
+
+/// Wayback Machine snapshots of pages that are gone or unreachable
+pub mod wayback;
+
+/// A synchronous API for callers without an async runtime
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
+use crate::assets::{Asset, AssetDownloader};
+use crate::client::{HttpClient, ResponseMetadata};
+use crate::compression::CompressedWriter;
+use crate::concurrency::AdaptiveConcurrency;
+use crate::converters::excerpt::ExcerptExtractor;
+use crate::converters::plugin::install_plugins;
+use crate::converters::{ConverterRegistry, MarkdownStream};
+use crate::crawl::{CrawlOptions, CrawledPage, RobotsRules};
 use crate::detection::UrlDetector;
-use crate::types::{Markdown, MarkdownError, UrlType};
+use crate::doctor::{DoctorOptions, DoctorReport};
+use crate::frontier::CrawlFrontier;
+use crate::config::{
+    ConversionOptions, DateWindowAction, DirectionMarkers, FilterConfig, ImageDownloads, LintMode,
+    SubDocumentMode,
+};
+use crate::filter::ContentFilter;
+use crate::includes::IncludeResolver;
+use crate::http_cache::CachedConversion;
+use crate::links::LinkRewriter;
+use crate::lint::MarkdownLinter;
+use crate::markdown_cache::MarkdownCache;
+use crate::profiling::Stage;
+use crate::progress::{ProgressEvent, ProgressObserver};
+use crate::repository::{RepositoryFile, RepositoryOptions};
+use crate::sitemap::SitemapOptions;
+use crate::stats::HostStatsRecorder;
+use crate::frontmatter::{
+    apply_frontmatter_provider, convert_frontmatter, frontmatter_block, frontmatter_field,
+    set_frontmatter_field, strip_frontmatter, FrontmatterFormat, FrontmatterProvider,
+};
+use crate::types::{
+    ConfigErrorKind, ContentErrorKind, ConversionPlan, ConversionResult, ConverterErrorKind,
+    ErrorContext, Markdown, MarkdownError, NetworkErrorKind, PlanStep, SkipDetails, SkipReason,
+    SubDocument, UrlType, ValidationErrorKind,
+};
+use crate::utils::parse_date;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 use tracing::{debug, error, info, instrument, warn};
 
 /// Main library struct providing unified URL to markdown conversion.
@@ -82,11 +229,21 @@ pub struct MarkdownDown {
     config: crate::config::Config,
     detector: UrlDetector,
     registry: ConverterRegistry,
+    client: HttpClient,
+    link_rewriters: Vec<Box<dyn LinkRewriter>>,
+    frontmatter_provider: Option<Arc<dyn FrontmatterProvider>>,
+    progress_observer: Option<Arc<dyn ProgressObserver>>,
+    markdown_cache: Option<MarkdownCache>,
+    /// Content filter compiled once from the configuration, or the context of
+    /// the pattern that failed to compile
+    filter: Result<Option<Arc<ContentFilter>>, ErrorContext>,
 }
 
 impl MarkdownDown {
     /// Creates a new MarkdownDown instance with default configuration.
     ///
+    /// Converters registered with [`register_converter!`] are installed too.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -95,10 +252,20 @@ impl MarkdownDown {
     /// let md = MarkdownDown::new();
     /// ```
     pub fn new() -> Self {
+        let config = crate::config::Config::default();
+        let mut registry = ConverterRegistry::new();
+        let mut detector = UrlDetector::new();
+        install_plugins(&config, &mut registry, &mut detector);
         Self {
-            config: crate::config::Config::default(),
-            detector: UrlDetector::new(),
-            registry: ConverterRegistry::new(),
+            config,
+            detector,
+            registry,
+            client: HttpClient::new(),
+            link_rewriters: Vec::new(),
+            frontmatter_provider: None,
+            progress_observer: None,
+            markdown_cache: None,
+            filter: Ok(None),
         }
     }
 
@@ -119,19 +286,340 @@ impl MarkdownDown {
     ///
     /// let md = MarkdownDown::with_config(config);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the proxy or TLS settings are invalid. Use
+    /// [`MarkdownDown::try_with_config`] to handle them as errors.
     pub fn with_config(config: crate::config::Config) -> Self {
+        Self::with_host_stats(config, HostStatsRecorder::new())
+    }
+
+    /// Creates a new MarkdownDown instance that records into a shared statistics recorder.
+    ///
+    /// Instances sharing a recorder accumulate per-host statistics together,
+    /// so a batch run that creates one instance per task can still report on
+    /// every host it touched.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration to use
+    /// * `stats` - The per-host statistics recorder to share
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::stats::HostStatsRecorder;
+    /// use markdowndown::{Config, MarkdownDown};
+    ///
+    /// let stats = HostStatsRecorder::new();
+    /// let first = MarkdownDown::with_host_stats(Config::default(), stats.clone());
+    /// let second = MarkdownDown::with_host_stats(Config::default(), stats.clone());
+    ///
+    /// // Both instances report into `stats.snapshot()`
+    /// assert!(stats.snapshot().is_empty());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the proxy or TLS settings are invalid, like
+    /// [`MarkdownDown::with_config`].
+    pub fn with_host_stats(config: crate::config::Config, stats: HostStatsRecorder) -> Self {
+        Self::try_with_host_stats(config, stats)
+            .unwrap_or_else(|e| panic!("Invalid HTTP client configuration: {e}"))
+    }
+
+    /// Creates a new MarkdownDown instance, checking the proxy and TLS settings.
+    ///
+    /// Unlike [`MarkdownDown::with_config`], which panics, a proxy URL or
+    /// certificate that cannot be parsed is returned as an error, so
+    /// requests never bypass the proxy or go out without the configured
+    /// certificates.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration to use
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ConfigurationError` - If the proxy URL, a root
+    ///   certificate, or the client certificate and key cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::{Config, MarkdownDown};
+    ///
+    /// let config = Config::builder().proxy("http://[proxy").build();
+    /// assert!(MarkdownDown::try_with_config(config).is_err());
+    /// ```
+    pub fn try_with_config(config: crate::config::Config) -> Result<Self, MarkdownError> {
+        Self::try_with_host_stats(config, HostStatsRecorder::new())
+    }
+
+    /// Creates a new MarkdownDown instance that records into a shared
+    /// statistics recorder, checking the proxy and TLS settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration to use
+    /// * `stats` - The per-host statistics recorder to share
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ConfigurationError` - As for [`MarkdownDown::try_with_config`]
+    pub fn try_with_host_stats(
+        config: crate::config::Config,
+        stats: HostStatsRecorder,
+    ) -> Result<Self, MarkdownError> {
         // Create configured HTTP client
-        let http_client = HttpClient::with_config(&config.http, &config.auth);
+        let http_client =
+            HttpClient::try_with_config(&config.http, &config.auth)?.with_stats(stats);
 
         // Create registry with configured HTTP client, HTML config, and output config
-        let registry =
-            ConverterRegistry::with_config(http_client, config.html.clone(), &config.output);
+        let mut registry = ConverterRegistry::with_config(
+            http_client.clone(),
+            config.html.clone(),
+            &config.output,
+        );
 
-        Self {
+        // Issue URLs on a GitHub Enterprise Server go to the GitHub converter
+        let mut detector = UrlDetector::new();
+        if let Some(host) = config
+            .auth
+            .github_api_base_url
+            .as_deref()
+            .and_then(crate::utils::github_enterprise_host)
+        {
+            detector.add_github_host(&host);
+        }
+        // Deployment rules route internal domains to specific converters
+        for (pattern, url_type) in &config.detection.rules {
+            detector.register_rule(pattern.clone(), url_type.clone());
+        }
+        install_plugins(&config, &mut registry, &mut detector);
+        let filter = compile_filter(&config.filters);
+        if !config.output.include_frontmatter
+            && config.filters.date_window_action == DateWindowAction::Flag
+            && matches!(&filter, Ok(Some(filter)) if filter.has_date_rules())
+        {
+            warn!("Documents outside the date window cannot be flagged without frontmatter; they will be passed through unflagged");
+        }
+
+        Ok(Self {
             config,
-            detector: UrlDetector::new(),
+            detector,
             registry,
-        }
+            client: http_client,
+            link_rewriters: Vec::new(),
+            frontmatter_provider: None,
+            progress_observer: None,
+            markdown_cache: None,
+            filter,
+        })
+    }
+
+    /// Registers a converter for a URL type, replacing any existing converter.
+    ///
+    /// This lets applications add their own converters, or swap out a
+    /// built-in one, without changing the crate.
+    ///
+    /// # Arguments
+    ///
+    /// * `url_type` - The URL type the converter handles
+    /// * `converter` - The converter implementation
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_trait::async_trait;
+    /// use markdowndown::converters::Converter;
+    /// use markdowndown::types::{Markdown, MarkdownError, UrlType};
+    /// use markdowndown::MarkdownDown;
+    ///
+    /// struct IntranetConverter;
+    ///
+    /// #[async_trait]
+    /// impl Converter for IntranetConverter {
+    ///     async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+    ///         Markdown::new(format!("# Intranet page\n\nFetched from {url}"))
+    ///     }
+    ///
+    ///     fn name(&self) -> &'static str {
+    ///         "Intranet"
+    ///     }
+    /// }
+    ///
+    /// let md = MarkdownDown::new().with_converter(UrlType::Html, Box::new(IntranetConverter));
+    /// assert_eq!(
+    ///     md.registry().get_converter(&UrlType::Html).map(|c| c.name()),
+    ///     Some("Intranet")
+    /// );
+    /// ```
+    pub fn with_converter(
+        mut self,
+        url_type: crate::types::UrlType,
+        converter: Box<dyn crate::converters::Converter>,
+    ) -> Self {
+        self.registry.register(url_type, converter);
+        self
+    }
+
+    /// Adds a detection pattern that assigns a URL type to matching URLs.
+    ///
+    /// Combined with [`MarkdownDown::with_converter`], this routes URLs to an
+    /// application-defined `UrlType::Custom` converter. Custom types without a
+    /// registered converter are converted as HTML.
+    ///
+    /// # Arguments
+    ///
+    /// * `domain_pattern` - Host name, or `*.example.com` to match any subdomain
+    /// * `path_pattern` - Optional path prefix; `*` matches any prefix or suffix
+    /// * `url_type` - The URL type to assign
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::types::UrlType;
+    /// use markdowndown::MarkdownDown;
+    ///
+    /// let md = MarkdownDown::new().with_url_pattern(
+    ///     "*.atlassian.net",
+    ///     Some("/wiki/"),
+    ///     UrlType::Custom("confluence".into()),
+    /// );
+    /// assert_eq!(
+    ///     md.detector().detect_type("https://acme.atlassian.net/wiki/x").unwrap(),
+    ///     UrlType::Custom("confluence".into())
+    /// );
+    /// ```
+    pub fn with_url_pattern(
+        mut self,
+        domain_pattern: &str,
+        path_pattern: Option<&str>,
+        url_type: crate::types::UrlType,
+    ) -> Self {
+        self.detector
+            .add_pattern(domain_pattern, path_pattern, url_type);
+        self
+    }
+
+    /// Adds a rewriter that maps link targets during postprocessing.
+    ///
+    /// Each link in the converted document is offered to the rewriters in
+    /// the order they were added, and the first new target wins. See
+    /// [`links::rewrite_links`] for which links are rewritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `rewriter` - The rewriter to add
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::types::LinkKind;
+    /// use markdowndown::MarkdownDown;
+    ///
+    /// let md = MarkdownDown::new().with_link_rewriter(Box::new(|url: &str, kind: LinkKind| {
+    ///     let page = url.strip_prefix("https://wiki.example.com/wiki/")?;
+    ///     (kind == LinkKind::Internal).then(|| format!("{page}.md"))
+    /// }));
+    ///
+    /// let html = "<p>See <a href=\"/wiki/Setup\">Setup</a>.</p>";
+    /// let markdown = md.convert_html(html, Some("https://wiki.example.com/wiki/Home"))?;
+    /// assert!(markdown.content_only().contains("[Setup](Setup.md)"));
+    /// # Ok::<(), markdowndown::types::MarkdownError>(())
+    /// ```
+    pub fn with_link_rewriter(mut self, rewriter: Box<dyn LinkRewriter>) -> Self {
+        self.link_rewriters.push(rewriter);
+        self
+    }
+
+    /// Sets the provider that decides the frontmatter fields of each document.
+    ///
+    /// The provider replaces the generated fields after `document_id` and
+    /// `slug` are stamped and before linting, and the result is written in
+    /// the configured frontmatter format. A later call replaces the provider.
+    /// See [`FrontmatterProvider`] for what the provider is given.
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - The provider to use
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::MarkdownDown;
+    /// use serde_yaml::Mapping;
+    ///
+    /// let md = MarkdownDown::new().with_frontmatter_provider(Box::new(
+    ///     |generated: &Mapping, _content: &str| {
+    ///         let mut fields = Mapping::new();
+    ///         fields.insert("canonical".into(), generated["source_url"].clone());
+    ///         fields
+    ///     },
+    /// ));
+    ///
+    /// let markdown = md.convert_html("<p>Hello</p>", Some("https://example.com/hello"))?;
+    /// assert_eq!(
+    ///     markdown.frontmatter().as_deref(),
+    ///     Some("---\ncanonical: https://example.com/hello\n---\n")
+    /// );
+    /// # Ok::<(), markdowndown::types::MarkdownError>(())
+    /// ```
+    pub fn with_frontmatter_provider(mut self, provider: Box<dyn FrontmatterProvider>) -> Self {
+        self.frontmatter_provider = Some(Arc::from(provider));
+        self
+    }
+
+    /// Sets the observer told about the progress of each conversion.
+    ///
+    /// Every conversion the instance runs through [`MarkdownDown::convert_url`]
+    /// reports to the observer, including those of batches, crawls, and
+    /// sitemaps. See [`progress`] for the events reported. A later call
+    /// replaces the observer.
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - The observer to report to
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::progress::ProgressEvent;
+    /// use markdowndown::MarkdownDown;
+    ///
+    /// let md = MarkdownDown::new().with_progress_observer(Box::new(
+    ///     |url: &str, event: &ProgressEvent| eprintln!("{url}: {event:?}"),
+    /// ));
+    /// ```
+    pub fn with_progress_observer(mut self, observer: Box<dyn ProgressObserver>) -> Self {
+        self.progress_observer = Some(Arc::from(observer));
+        self
+    }
+
+    /// Keeps converted markdown in a directory, reusing it for later conversions.
+    ///
+    /// Conversions of a URL with the same configuration and per-call options
+    /// return the stored markdown without fetching or converting anything, so
+    /// re-running a batch job is near-instant and an interrupted one resumes
+    /// where it stopped. See [`markdown_cache`] for when to clear the directory.
+    /// Conversions that return downloaded images in memory are not cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory converted documents are kept in
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::MarkdownDown;
+    ///
+    /// let md = MarkdownDown::new().with_cache(std::env::temp_dir().join("markdowndown"));
+    /// ```
+    pub fn with_cache<P: Into<std::path::PathBuf>>(mut self, dir: P) -> Self {
+        self.markdown_cache = Some(MarkdownCache::new(dir));
+        self
     }
 
     /// Converts content from a URL to markdown.
@@ -154,6 +642,14 @@ impl MarkdownDown {
     /// * `MarkdownError::ParseError` - If content conversion fails
     /// * `MarkdownError::AuthError` - For authentication failures
     /// * `MarkdownError::ConfigurationError` - If no converter is available for the URL type
+    /// * `MarkdownError::Skipped` - If the URL is rejected by the configured content filters
+    /// * `MarkdownError::EnhancedNetworkError` - With `NetworkErrorKind::Timeout` if the
+    ///   configured `total_timeout` runs out before any converter finishes
+    ///
+    /// When `total_timeout` is set, filtering, fetching, retries, and the HTML
+    /// fallback all share that one budget. If it runs out during the fallback,
+    /// the primary converter's error is returned, since it says more about what
+    /// went wrong than the timeout does.
     ///
     /// # Examples
     ///
@@ -167,591 +663,4285 @@ impl MarkdownDown {
     /// # Ok(())
     /// # }
     /// ```
-    #[instrument(skip(self), fields(url_type))]
     pub async fn convert_url(&self, url: &str) -> Result<Markdown, MarkdownError> {
-        info!("Starting URL conversion for: {}", url);
-
-        // Step 1: Normalize the URL
-        debug!("Normalizing URL");
-        let normalized_url = self.detector.normalize_url(url)?;
-        debug!("Normalized URL: {}", normalized_url);
+        self.convert_url_with_options(url, &ConversionOptions::default()).await
+    }
 
-        // Step 2: Detect URL type
-        debug!("Detecting URL type");
-        let url_type = self.detector.detect_type(&normalized_url)?;
-        tracing::Span::current().record("url_type", format!("{url_type}"));
-        info!("Detected URL type: {}", url_type);
+    /// Converts content from a URL to markdown, overriding settings for this call.
+    ///
+    /// This runs the [`MarkdownDown::convert_url`] pipeline with the settings
+    /// in `options` in place of the configured ones, reusing this instance's
+    /// HTTP client and converters.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to fetch and convert
+    /// * `options` - The settings to override
+    ///
+    /// # Returns
+    ///
+    /// Returns the converted markdown content or an error.
+    ///
+    /// # Errors
+    ///
+    /// The same errors as [`MarkdownDown::convert_url`], with the timeout
+    /// from `options` in place of `total_timeout` when it is set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::config::ConversionOptions;
+    /// use markdowndown::MarkdownDown;
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
+    /// let md = MarkdownDown::new();
+    /// let options = ConversionOptions::new()
+    ///     .strip_frontmatter(true)
+    ///     .timeout(Duration::from_secs(5));
+    /// let body = md.convert_url_with_options("https://example.com/page.html", &options).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn convert_url_with_options(
+        &self,
+        url: &str,
+        options: &ConversionOptions,
+    ) -> Result<Markdown, MarkdownError> {
+        self.convert_url_with_assets(url, options)
+            .await
+            .map(|(markdown, _assets)| markdown)
+    }
 
-        // Step 3: Get appropriate converter
-        debug!("Looking up converter for type: {}", url_type);
-        let converter = self.registry.get_converter(&url_type).ok_or_else(|| {
-            error!("No converter available for URL type: {}", url_type);
-            MarkdownError::LegacyConfigurationError {
-                message: format!("No converter available for URL type: {url_type}"),
+    /// Converts a URL, returning the markdown and any images downloaded for it.
+    #[instrument(name = "convert_url", skip(self, options), fields(url_type))]
+    async fn convert_url_with_assets(
+        &self,
+        url: &str,
+        options: &ConversionOptions,
+    ) -> Result<(Markdown, Vec<Asset>), MarkdownError> {
+        info!("Starting URL conversion for: {}", url);
+        // Images returned in memory cannot be recovered from the cache
+        let cache = self
+            .markdown_cache
+            .as_ref()
+            .filter(|_| !matches!(self.config.output.download_images, ImageDownloads::InMemory));
+        let cache_key = cache.map(|_| {
+            let cache_url = self
+                .detector
+                .normalize_url(url)
+                .unwrap_or_else(|_| url.to_string());
+            (cache_url, self.cache_fingerprint(options))
+        });
+        if let (Some(cache), Some((cache_url, fingerprint))) = (cache, &cache_key) {
+            if !options.bypass_cache {
+                if let Some(markdown) = cache.get(cache_url, fingerprint).await {
+                    return Ok((markdown, Vec::new()));
+                }
             }
-        })?;
-        debug!("Found converter for type: {}", url_type);
-
-        // Step 4: Convert using the selected converter
-        info!("Starting conversion with {} converter", url_type);
-        match converter.convert(&normalized_url).await {
-            Ok(result) => {
-                info!(
-                    "Successfully converted URL to markdown ({} chars)",
-                    result.as_str().len()
-                );
-                Ok(result)
+        }
+        let conversion =
+            client::with_call_options(options, self.convert_revalidating(url, options));
+        let result = match &self.progress_observer {
+            Some(observer) => {
+                let progress_url = self
+                    .detector
+                    .normalize_url(url)
+                    .unwrap_or_else(|_| url.to_string());
+                progress::observe(observer.clone(), &progress_url, conversion).await
             }
-            Err(e) => {
-                error!("Primary converter failed: {}", e);
+            None => conversion.await,
+        };
+        if let Err(e) = &result {
+            let audit_url = self
+                .detector
+                .normalize_url(url)
+                .unwrap_or_else(|_| url.to_string());
+            events::conversion_failed(&audit_url, e);
+        }
+        if let (Some(cache), Some((cache_url, fingerprint)), Ok((markdown, _))) =
+            (cache, &cache_key, &result)
+        {
+            if let Err(e) = cache.put(cache_url, fingerprint, markdown).await {
+                warn!("Failed to cache markdown for {}: {e}", cache_url);
+            }
+        }
+        result
+    }
 
-                // Step 5: Attempt fallback strategies for recoverable errors
-                if e.is_recoverable() && url_type != UrlType::Html {
-                    warn!("Attempting HTML fallback conversion for recoverable error");
-
-                    // Try HTML converter as fallback
-                    if let Some(html_converter) = self.registry.get_converter(&UrlType::Html) {
-                        match html_converter.convert(&normalized_url).await {
-                            Ok(fallback_result) => {
-                                warn!(
-                                    "Fallback HTML conversion succeeded ({} chars)",
-                                    fallback_result.as_str().len()
-                                );
-                                return Ok(fallback_result);
-                            }
-                            Err(fallback_error) => {
-                                error!("Fallback HTML conversion also failed: {}", fallback_error);
-                            }
-                        }
+    /// Runs the conversion pipeline through the response cache's stored
+    /// conversions, if the client caches responses.
+    ///
+    /// A stored conversion is returned when every request it made is still
+    /// current; otherwise the URL is converted again and the result stored.
+    async fn convert_revalidating(
+        &self,
+        url: &str,
+        options: &ConversionOptions,
+    ) -> Result<(Markdown, Vec<Asset>), MarkdownError> {
+        // Images returned in memory cannot be recovered from the cache
+        let Some(cache) = self
+            .client
+            .http_cache()
+            .filter(|_| !matches!(self.config.output.download_images, ImageDownloads::InMemory))
+        else {
+            return Box::pin(self.run_conversion(url, options)).await;
+        };
+        let cache_url = self
+            .detector
+            .normalize_url(url)
+            .unwrap_or_else(|_| url.to_string());
+        let key = format!("{cache_url}\n{}", self.cache_fingerprint(options));
+        if !options.bypass_cache {
+            if let Some(stored) = cache.get_conversion(&key).await {
+                let mut unchanged = true;
+                for request in stored.requests() {
+                    if !self.client.revalidate(request).await {
+                        unchanged = false;
+                        break;
                     }
                 }
-
-                Err(e)
+                if unchanged {
+                    debug!("Serving cached conversion for {}", cache_url);
+                    return Ok((Markdown::from(stored.markdown().to_string()), Vec::new()));
+                }
             }
         }
+        let (result, requests) =
+            http_cache::record_requests(Box::pin(self.run_conversion(url, options))).await;
+        if let (Ok((markdown, _)), Some(requests)) = (&result, requests) {
+            if !requests.is_empty() {
+                let stored = CachedConversion::new(requests, markdown.as_str().to_string());
+                cache.put_conversion(&key, stored).await;
+            }
+        }
+        result
     }
 
-    /// Returns the configuration being used by this instance.
-    pub fn config(&self) -> &crate::config::Config {
-        &self.config
+    /// Returns a fingerprint of the settings that shape a conversion's output.
+    ///
+    /// The crate version is included, so upgrades convert documents again.
+    fn cache_fingerprint(&self, options: &ConversionOptions) -> String {
+        let settings = format!(
+            "{}\n{:?}\n{:?}\n{:?}\n{:?}\n{:?}",
+            env!("CARGO_PKG_VERSION"),
+            self.config,
+            options.strip_frontmatter,
+            options.frontmatter_format,
+            options.lint_mode,
+            options.headers
+        );
+        identity::fnv1a_hex(settings.as_bytes())
     }
 
-    /// Returns the URL detector being used by this instance.
-    pub fn detector(&self) -> &UrlDetector {
-        &self.detector
-    }
+    /// Runs the [`MarkdownDown::convert_url`] pipeline.
+    async fn run_conversion(
+        &self,
+        url: &str,
+        options: &ConversionOptions,
+    ) -> Result<(Markdown, Vec<Asset>), MarkdownError> {
+        let deadline = options
+            .timeout
+            .or(self.config.http.total_timeout)
+            .map(|budget| tokio::time::Instant::now() + budget);
 
-    /// Returns the converter registry being used by this instance.
-    pub fn registry(&self) -> &ConverterRegistry {
-        &self.registry
-    }
+        // Step 1: Normalize the URL, following shortened links and redirects
+        debug!("Normalizing URL");
+        let requested_url = self.detector.normalize_url(url)?;
+        let (normalized_url, preflight) = within_budget(
+            deadline,
+            &requested_url,
+            "Redirect resolution",
+            self.resolve_url(requested_url.clone()),
+        )
+        .await?;
+        debug!("Normalized URL: {}", normalized_url);
 
-    /// Lists all supported URL types.
-    pub fn supported_types(&self) -> Vec<crate::types::UrlType> {
-        self.registry.supported_types()
+        // Step 2: Detect URL type
+        debug!("Detecting URL type");
+        let (url_type, preflight) = within_budget(
+            deadline,
+            &normalized_url,
+            "Content sniffing",
+            self.detect_served_type(&normalized_url, preflight),
+        )
+        .await?;
+        tracing::Span::current().record("url_type", format!("{url_type}"));
+        info!("Detected URL type: {}", url_type);
+        events::conversion_started(&normalized_url, &url_type);
+
+        // Step 3: Apply allow/deny filters before fetching anything
+        let filter = self.content_filter()?;
+        if let Some(filter) = filter {
+            self.apply_filters(filter, &normalized_url, preflight)?;
+        }
+
+        // Steps 4-6: Convert, falling back to HTML and then to an archived copy where possible.
+        // Content rules are checked against the first response the conversion fetches.
+        progress::report(ProgressEvent::Converting);
+        let convert_started = std::time::Instant::now();
+        let recorded_before = profiling::recorded_time();
+        let (converted, document_date) = self
+            .check_first_response(
+                filter,
+                self.convert_with_fallback(&normalized_url, &url_type, deadline),
+            )
+            .await;
+        let result = match converted {
+            Ok(result) => result,
+            // Boxed so the normal path does not carry the second conversion's future
+            Err(e) => Box::pin(self.convert_from_archive(&normalized_url, e, deadline)).await?,
+        };
+        let (normalized_url, url_type, result) = self
+            .follow_canonical(&requested_url, normalized_url, url_type, result, deadline)
+            .await;
+        profiling::record_excluding_nested(
+            Stage::Convert,
+            convert_started,
+            recorded_before,
+            result.len(),
+        );
+        progress::report(ProgressEvent::Postprocessing);
+        let postprocess_started = std::time::Instant::now();
+        let recorded_before = profiling::recorded_time();
+
+        // Step 7: Resolve include directives in markdown sources
+        let result = if self.config.output.resolve_includes
+            && (url_type == UrlType::LocalFile || includes::is_markdown_source(&normalized_url))
+        {
+            let resolver = IncludeResolver::new(&self.client);
+            Markdown::from(resolver.resolve(result.as_str(), &normalized_url).await)
+        } else {
+            result
+        };
+
+        // Step 8: Download referenced images and link to the copies
+        let (result, assets) = if self.config.output.download_images.is_enabled() {
+            let downloader =
+                AssetDownloader::new(&self.client, &self.config.output.download_images);
+            let (localized, assets) = within_budget(
+                deadline,
+                &normalized_url,
+                "Image download",
+                downloader.localize(result.as_str(), &normalized_url),
+            )
+            .await?;
+            (Markdown::from(localized), assets)
+        } else {
+            (result, Vec::new())
+        };
+
+        // Step 9: Strip tracking parameters and apply the registered link rewriters
+        let result = Markdown::from(self.apply_link_rules(result.as_str(), &normalized_url));
+
+        // Step 10: Keep only the requested language of a bilingual document
+        let result = Markdown::from(self.apply_language(&normalized_url, String::from(result)));
+
+        // Step 11: Mark right-to-left paragraphs and record the text direction
+        let markers = self.config.output.direction_markers;
+        let result = Markdown::from(apply_direction(result.as_str(), markers));
+
+        // Step 12: Apply the date window now that the published date is known
+        let result = match &filter {
+            Some(filter) if filter.has_date_rules() => {
+                self.apply_date_window(filter, &normalized_url, result, document_date)?
+            }
+            _ => result,
+        };
+
+        // Step 13: Stamp stable identity metadata, and an excerpt where the converter
+        // wrote none, into the frontmatter
+        let with_identity = stamp_identity(result.as_str(), &normalized_url);
+        let with_identity = match self.config.output.excerpt_sentences {
+            Some(sentences) => stamp_excerpt(&with_identity, sentences),
+            None => with_identity,
+        };
+
+        // Step 14: Let the frontmatter provider decide the fields
+        let with_identity = self.apply_frontmatter_provider(with_identity);
+
+        // Step 15: Lint the final output
+        let lint_mode = options.lint_mode.unwrap_or(self.config.output.lint_mode);
+        let (markdown, warnings) = self.apply_lint(&normalized_url, with_identity, lint_mode);
+
+        // Step 16: Write the frontmatter in the configured format, or drop it
+        let markdown = if options.strip_frontmatter {
+            strip_frontmatter(&markdown)
+        } else {
+            let format = options
+                .frontmatter_format
+                .unwrap_or(self.config.output.frontmatter_format);
+            convert_frontmatter(&markdown, format)
+        };
+        profiling::record_excluding_nested(
+            Stage::Postprocess,
+            postprocess_started,
+            recorded_before,
+            markdown.len(),
+        );
+        events::conversion_completed(&normalized_url, &url_type, markdown.len(), warnings);
+        progress::done(markdown.len());
+        Ok((Markdown::from(markdown), assets))
     }
-}
 
-impl Default for MarkdownDown {
-    fn default() -> Self {
-        Self::new()
+    /// Converts HTML the caller already has, without fetching anything.
+    ///
+    /// This runs the HTML pipeline (preprocessing, conversion, postprocessing,
+    /// and frontmatter) and the final link, language, direction, identity, frontmatter, and lint steps of
+    /// [`MarkdownDown::convert_url`]. Frontmatter, including `document_id`
+    /// and `slug`, records where the document came from, so it is only added
+    /// when `base_url` is given. Images are never downloaded; with image
+    /// downloading enabled they are kept as links to their original location.
+    ///
+    /// # Arguments
+    ///
+    /// * `html` - The HTML document
+    /// * `base_url` - The URL the document was fetched from, if known
+    ///
+    /// # Returns
+    ///
+    /// Returns the converted markdown content or an error.
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ValidationError` - If `base_url` is not a valid URL
+    /// * `MarkdownError::ParseError` - If the HTML is empty or cannot be converted
+    /// * `MarkdownError::ContentError` - If the page looks like a "not found" template
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::MarkdownDown;
+    ///
+    /// let md = MarkdownDown::new();
+    /// let html = "<html><head><title>Saved</title></head><body><h1>Hello</h1></body></html>";
+    ///
+    /// let markdown = md.convert_html(html, Some("https://example.com/saved"))?;
+    /// assert!(markdown.as_str().contains("# Hello"));
+    /// assert!(markdown.frontmatter().is_some());
+    ///
+    /// let body_only = md.convert_html(html, None)?;
+    /// assert!(body_only.frontmatter().is_none());
+    /// # Ok::<(), markdowndown::types::MarkdownError>(())
+    /// ```
+    pub fn convert_html(
+        &self,
+        html: &str,
+        base_url: Option<&str>,
+    ) -> Result<Markdown, MarkdownError> {
+        let normalized_url = base_url
+            .map(|url| self.detector.normalize_url(url))
+            .transpose()?;
+
+        let converter = HtmlConverter::with_config(
+            self.client.clone(),
+            self.config.html.clone(),
+            self.config.output.clone(),
+        );
+        let result = converter.convert_document(html, normalized_url.as_deref())?;
+
+        match normalized_url {
+            Some(url) => {
+                let result = self.apply_link_rules(result.as_str(), &url);
+                let result = self.apply_language(&url, result);
+                let result = apply_direction(&result, self.config.output.direction_markers);
+                let with_identity = stamp_identity(&result, &url);
+                let with_identity = self.apply_frontmatter_provider(with_identity);
+                let (markdown, _) =
+                    self.apply_lint(&url, with_identity, self.config.output.lint_mode);
+                Ok(Markdown::from(convert_frontmatter(
+                    &markdown,
+                    self.config.output.frontmatter_format,
+                )))
+            }
+            None => {
+                let result = self.apply_link_rules(result.as_str(), "");
+                let result = self.apply_language("inline HTML", result);
+                let result = apply_direction(&result, self.config.output.direction_markers);
+                let (markdown, _) =
+                    self.apply_lint("inline HTML", result, self.config.output.lint_mode);
+                Ok(Markdown::from(markdown))
+            }
+        }
     }
-}
 
-/// Convenience function for converting a URL to markdown with default configuration.
-///
-/// This is equivalent to calling `MarkdownDown::new().convert_url(url).await`.
-///
-/// # Arguments
-///
-/// * `url` - The URL to fetch and convert
-///
-/// # Returns
-///
-/// Returns the converted markdown content or an error.
-///
-/// # Examples
-///
-/// ```rust
-/// use markdowndown::convert_url;
-///
-/// # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
-/// let result = convert_url("https://example.com/article.html").await?;
-/// println!("{}", result);
-/// # Ok(())
-/// # }
-/// ```
-pub async fn convert_url(url: &str) -> Result<Markdown, MarkdownError> {
-    MarkdownDown::new().convert_url(url).await
-}
+    /// Converts content from a URL to markdown, one document per part.
+    ///
+    /// With [`SubDocumentMode::Split`], sources made of several logical
+    /// parts, such as PDFs whose outline has several chapters, are returned
+    /// as one document per part. Each part's frontmatter gets its own
+    /// `document_id` and `slug`, the `parent_id` of the whole document, and
+    /// `part_index`, `part_count`, and `part_title` fields. With
+    /// [`SubDocumentMode::Concatenate`], the default, the document is
+    /// converted by [`MarkdownDown::convert_url`] and returned as one part.
+    ///
+    /// In split mode, URL filters are applied before fetching, and each part
+    /// goes through link cleanup and rewriting, language extraction,
+    /// direction marking, the frontmatter provider, and linting. Steps that
+    /// need the whole document are skipped: HTML fallback, image
+    /// downloading, the date window, and soft-404 detection. Parts are not
+    /// kept in the cache added with [`MarkdownDown::with_cache`]. Sources
+    /// with a single part are returned as one part with the whole document's
+    /// identity.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to fetch and convert
+    ///
+    /// # Returns
+    ///
+    /// Returns the parts in document order, or a `MarkdownError` on failure.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::config::{Config, SubDocumentMode};
+    /// use markdowndown::MarkdownDown;
+    ///
+    /// # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
+    /// let config = Config::builder().sub_documents(SubDocumentMode::Split).build();
+    /// let md = MarkdownDown::with_config(config);
+    /// for part in md.convert_url_parts("https://example.com/handbook.pdf").await? {
+    ///     println!("{} {:?}", part.index, part.title);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn convert_url_parts(&self, url: &str) -> Result<Vec<SubDocument>, MarkdownError> {
+        if self.config.output.sub_documents == SubDocumentMode::Concatenate {
+            let markdown = self.convert_url(url).await?;
+            return Ok(vec![SubDocument {
+                index: 1,
+                title: frontmatter_field(markdown.as_str(), "title"),
+                markdown,
+            }]);
+        }
 
-/// Convenience function for converting a URL to markdown with custom configuration.
-///
-/// # Arguments
-///
-/// * `url` - The URL to fetch and convert
-/// * `config` - The configuration to use
-///
-/// # Returns
-///
-/// Returns the converted markdown content or an error.
-///
-/// # Examples
-///
-/// ```rust
-/// use markdowndown::{convert_url_with_config, Config};
-///
-/// # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
-/// let config = Config::builder()
-///     .timeout_seconds(60)
-///     .build();
-///
-/// let result = convert_url_with_config("https://example.com/article.html", config).await?;
-/// println!("{}", result);
-/// # Ok(())
-/// # }
-/// ```
-pub async fn convert_url_with_config(
-    url: &str,
-    config: crate::config::Config,
-) -> Result<Markdown, MarkdownError> {
-    MarkdownDown::with_config(config).convert_url(url).await
-}
+        info!("Starting split URL conversion for: {}", url);
+        let normalized_url = self.detector.normalize_url(url)?;
+        let (normalized_url, preflight) = self.resolve_url(normalized_url).await?;
+        let (url_type, preflight) = self.detect_served_type(&normalized_url, preflight).await?;
+
+        let filter = self.content_filter()?;
+        if let Some(filter) = filter {
+            self.apply_filters(filter, &normalized_url, preflight)?;
+        }
+
+        let converter = self.converter_for(&url_type)?;
+        let (parts, _) = self
+            .check_first_response(filter, converter.convert_parts(&normalized_url))
+            .await;
+        let parts = parts?;
+
+        let part_count = parts.len();
+        let output = &self.config.output;
+        let parts = parts
+            .into_iter()
+            .map(|part| {
+                let part_url = format!("{normalized_url}#part-{}", part.index);
+                let result = self.apply_link_rules(part.markdown.as_str(), &normalized_url);
+                let result = self.apply_language(&part_url, result);
+                let result = apply_direction(&result, output.direction_markers);
+                let with_identity = if part_count > 1 {
+                    stamp_part(&result, &normalized_url, &part, part_count)
+                } else {
+                    stamp_identity(&result, &normalized_url)
+                };
+                let with_identity = self.apply_frontmatter_provider(with_identity);
+                let (markdown, _) = self.apply_lint(&part_url, with_identity, output.lint_mode);
+                SubDocument {
+                    markdown: Markdown::from(convert_frontmatter(
+                        &markdown,
+                        output.frontmatter_format,
+                    )),
+                    ..part
+                }
+            })
+            .collect();
+        Ok(parts)
+    }
+
+    /// Converts content from a URL to markdown, yielding it in chunks as it is converted.
+    ///
+    /// Web pages are fetched and converted a segment at a time, so large
+    /// documents can be written out without being held in memory whole; other
+    /// converters yield the document as a single chunk. The chunks concatenate
+    /// to the complete document, and the first chunk holds the frontmatter.
+    ///
+    /// URL filters are applied before fetching and the `document_id` and
+    /// `slug` fields are stamped into the frontmatter. Steps that need the
+    /// whole document are skipped: HTML fallback after a failed conversion,
+    /// image downloading, link cleanup and rewriting, language extraction,
+    /// direction marking, the date window, soft-404 detection, and linting.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to fetch and convert
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of markdown chunks. Errors, including errors before
+    /// anything is fetched, are yielded as items and end the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use futures::StreamExt;
+    /// use markdowndown::MarkdownDown;
+    /// use tokio::io::AsyncWriteExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let md = MarkdownDown::new();
+    /// let mut file = tokio::fs::File::create("article.md").await?;
+    /// let mut chunks = md.convert_url_stream("https://example.com/article.html");
+    /// while let Some(chunk) = chunks.next().await {
+    ///     file.write_all(chunk?.as_bytes()).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn convert_url_stream(&self, url: &str) -> MarkdownStream<'_> {
+        let url = url.to_string();
+        stream::once(async move { self.open_url_stream(&url).await })
+            .try_flatten()
+            .boxed()
+    }
+
+    /// Converts content from a URL to markdown, writing it to `writer` as it is converted.
+    ///
+    /// This drives [`MarkdownDown::convert_url_stream`] and writes each chunk
+    /// as soon as it is produced, so a server can pass a socket, file, or
+    /// compression encoder and never hold the whole document. The writer is
+    /// flushed once the document is complete; on error, anything written so
+    /// far is left in place.
+    ///
+    /// When the output configuration sets a compression, chunks are
+    /// compressed as they are written and the writer is shut down to finish
+    /// the compressed stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to fetch and convert
+    /// * `writer` - Where to write the markdown
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of markdown bytes written, before compression.
+    ///
+    /// # Errors
+    ///
+    /// The same errors as [`MarkdownDown::convert_url_stream`], or
+    /// `MarkdownError::ConverterError` if writing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::MarkdownDown;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let md = MarkdownDown::new();
+    /// let mut file = tokio::fs::File::create("article.md").await?;
+    /// let written = md
+    ///     .convert_url_to_writer("https://example.com/article.html", &mut file)
+    ///     .await?;
+    /// println!("Wrote {written} bytes");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn convert_url_to_writer<W>(
+        &self,
+        url: &str,
+        writer: &mut W,
+    ) -> Result<u64, MarkdownError>
+    where
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        let write_error = |e: std::io::Error| MarkdownError::ConverterError {
+            kind: ConverterErrorKind::ProcessingError,
+            context: ErrorContext::new(url, "Write output", "MarkdownDown")
+                .with_info(format!("IO error: {e}")),
+        };
+
+        let mut sink = CompressedWriter::new(writer, self.config.output.compression);
+        let mut chunks = self.convert_url_stream(url);
+        let mut written = 0;
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk?;
+            sink.write_all(chunk.as_bytes())
+                .await
+                .map_err(write_error)?;
+            written += chunk.len() as u64;
+        }
+        sink.finish().await.map_err(write_error)?;
+        Ok(written)
+    }
+
+    /// Runs the pre-fetch pipeline steps and starts a streaming conversion.
+    async fn open_url_stream(&self, url: &str) -> Result<MarkdownStream<'_>, MarkdownError> {
+        info!("Starting streaming URL conversion for: {}", url);
+        let normalized_url = self.detector.normalize_url(url)?;
+        let (normalized_url, preflight) = self.resolve_url(normalized_url).await?;
+        let (url_type, preflight) = self.detect_served_type(&normalized_url, preflight).await?;
+
+        let filter = self.content_filter()?;
+        if let Some(filter) = filter {
+            self.apply_filters(filter, &normalized_url, preflight)?;
+        }
+
+        let converter = self.converter_for(&url_type)?;
+        let chunks = if self.config.output.frontmatter_only {
+            let (frontmatter, _) = self
+                .check_first_response(filter, converter.convert_frontmatter(&normalized_url))
+                .await;
+            let frontmatter = frontmatter?;
+            stream::once(async move { Ok(String::from(frontmatter)) }).boxed()
+        } else {
+            let (chunks, _) = self
+                .check_first_response(filter, converter.convert_stream(&normalized_url))
+                .await;
+            chunks?
+        };
+
+        // The frontmatter is always in the first chunk
+        let mut first = true;
+        let frontmatter_format = self.config.output.frontmatter_format;
+        let frontmatter_provider = self.frontmatter_provider.clone();
+        let chunks = chunks.map_ok(move |chunk| {
+            if !std::mem::take(&mut first) {
+                return chunk;
+            }
+            let mut with_identity = stamp_identity(&chunk, &normalized_url);
+            if let Some(provider) = &frontmatter_provider {
+                with_identity = apply_frontmatter_provider(&with_identity, provider.as_ref());
+            }
+            convert_frontmatter(&with_identity, frontmatter_format)
+        });
+        Ok(chunks.boxed())
+    }
+
+    /// Keeps only the configured language when the output is bilingual.
+    ///
+    /// Output that is not detected as interleaving the configured language
+    /// with another one is returned unchanged.
+    fn apply_language(&self, url: &str, markdown: String) -> String {
+        let Some(wanted) = &self.config.output.extract_language else {
+            return markdown;
+        };
+        match language::extract_language(&markdown, wanted) {
+            Some(extracted) => {
+                debug!("Kept only '{}' blocks of {}", wanted, url);
+                extracted
+            }
+            None => {
+                debug!("{} is not bilingual in '{}'", url, wanted);
+                markdown
+            }
+        }
+    }
+
+    /// Strips tracking parameters from links, if enabled, and applies the
+    /// registered link rewriters.
+    fn apply_link_rules(&self, markdown: &str, url: &str) -> String {
+        let output = &self.config.output;
+        let markdown = if output.strip_tracking_params {
+            links::strip_tracking_params(markdown, &output.tracking_params)
+        } else {
+            markdown.to_string()
+        };
+        links::rewrite_links(&markdown, url, &self.link_rewriters)
+    }
+
+    /// Replaces the frontmatter fields with the provider's, if one is set.
+    fn apply_frontmatter_provider(&self, markdown: String) -> String {
+        match &self.frontmatter_provider {
+            Some(provider) => apply_frontmatter_provider(&markdown, provider.as_ref()),
+            None => markdown,
+        }
+    }
+
+    /// Runs the configured lint rules over converted output.
+    ///
+    /// In warn mode each problem is logged and the output is unchanged; in
+    /// fix mode the output is rewritten. Returns the output with the number
+    /// of problems found.
+    fn apply_lint(&self, url: &str, markdown: String, mode: LintMode) -> (String, usize) {
+        let linter = MarkdownLinter::new(&self.config.output.lint_rules);
+        match mode {
+            LintMode::Off => (markdown, 0),
+            LintMode::Warn => {
+                let warnings = linter.check(&markdown);
+                for warning in &warnings {
+                    warn!("Lint warning for {}: {}", url, warning);
+                }
+                (markdown, warnings.len())
+            }
+            LintMode::Fix => {
+                let warnings = linter.check(&markdown).len();
+                (linter.fix(&markdown), warnings)
+            }
+        }
+    }
+
+    /// Converts content from a URL and returns it with document metadata.
+    ///
+    /// This runs the same pipeline as [`MarkdownDown::convert_url`] and also
+    /// reports what happened: the detected URL type, where the first response
+    /// came from and its status, the title and word count, how long the
+    /// conversion took, and any warnings. It also inventories the outbound
+    /// links and heading tree of the converted document. With profiling enabled in the configuration, the time and
+    /// bytes of each pipeline stage are recorded too, and with image
+    /// downloading enabled, the downloaded images are returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to fetch and convert
+    ///
+    /// # Returns
+    ///
+    /// Returns the converted markdown with its links and sections, or an error.
+    ///
+    /// # Errors
+    ///
+    /// The same errors as [`MarkdownDown::convert_url`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::MarkdownDown;
+    ///
+    /// # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
+    /// let md = MarkdownDown::new();
+    /// let result = md.convert_url_detailed("https://example.com/page.html").await?;
+    /// println!(
+    ///     "{} ({} words) from {:?} in {:?}",
+    ///     result.title.as_deref().unwrap_or("Untitled"),
+    ///     result.word_count,
+    ///     result.final_url,
+    ///     result.duration
+    /// );
+    /// for warning in &result.warnings {
+    ///     println!("warning: {warning}");
+    /// }
+    /// for link in &result.links {
+    ///     println!("{:?} {} -> {}", link.kind, link.text, link.url);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn convert_url_detailed(&self, url: &str) -> Result<ConversionResult, MarkdownError> {
+        let started = std::time::Instant::now();
+        let options = ConversionOptions::default();
+        let (converted, observations) = events::observe(async {
+            if self.config.output.profiling {
+                let (converted, profile) =
+                    profiling::profile(self.convert_url_with_assets(url, &options)).await;
+                converted.map(|converted| (converted, Some(profile)))
+            } else {
+                self.convert_url_with_assets(url, &options)
+                    .await
+                    .map(|converted| (converted, None))
+            }
+        })
+        .await;
+        let ((markdown, assets), profile) = converted?;
+        let normalized_url = self.detector.normalize_url(url)?;
+        // Content sniffing can route a URL differently than its address suggests
+        let url_type = match observations.url_type {
+            Some(url_type) => url_type,
+            None => self.detector.detect_type(&normalized_url)?,
+        };
+        let content = markdown.content_only();
+        let links = links::extract_links(&content, &normalized_url);
+        let sections = sections::extract_sections(markdown.as_str());
+        let lint_warnings =
+            MarkdownLinter::new(&self.config.output.lint_rules).check(markdown.as_str());
+        let word_count = content
+            .split_whitespace()
+            .filter(|word| word.chars().any(char::is_alphanumeric))
+            .count();
+
+        Ok(ConversionResult {
+            title: frontmatter_field(markdown.as_str(), "title"),
+            markdown,
+            url_type,
+            final_url: observations.final_url,
+            http_status: observations.http_status,
+            word_count,
+            duration: started.elapsed(),
+            warnings: observations.warnings,
+            links,
+            sections,
+            lint_warnings,
+            profile,
+            assets,
+        })
+    }
+
+    /// Converts the documentation files in a git repository.
+    ///
+    /// The repository is shallow-cloned with the `git` command into a
+    /// temporary directory, and every file matching `options.docs_path` is
+    /// converted through [`MarkdownDown::convert_url`]. Each file gets its own
+    /// result, so one unconvertible page does not fail the rest.
+    ///
+    /// Unless disabled with [`RepositoryOptions::project_aware`], mdBook and
+    /// Docusaurus projects are recognized: their pages are returned in table
+    /// of contents order, with mdBook includes resolved and MDX syntax removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The repository, as a `git+https://` URL or anything `git clone` accepts
+    /// * `options` - Which files to convert and which branch or tag to check out
+    ///
+    /// # Returns
+    ///
+    /// Returns the matching files, in reading order for recognized projects and by
+    /// path otherwise, with a result for each.
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ConfigurationError` - If git is not installed
+    /// * `MarkdownError::ConverterError` - If the repository could not be cloned
+    /// * `MarkdownError::ContentError` - If no files match the docs path
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use markdowndown::repository::RepositoryOptions;
+    /// use markdowndown::MarkdownDown;
+    ///
+    /// # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
+    /// let md = MarkdownDown::new();
+    /// let options = RepositoryOptions::new().reference("main");
+    /// let files = md
+    ///     .convert_repository("https://github.com/owner/project", &options)
+    ///     .await?;
+    /// println!("Converted {} pages", files.iter().filter(|f| f.result.is_ok()).count());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn convert_repository(
+        &self,
+        url: &str,
+        options: &RepositoryOptions,
+    ) -> Result<Vec<RepositoryFile>, MarkdownError> {
+        let checkout = repository::shallow_clone(url, options.reference.as_deref()).await?;
+        let paths = repository::prepare_files(checkout.path(), options);
+        if paths.is_empty() {
+            let context = ErrorContext::new(url, "File selection", "convert_repository")
+                .with_info(format!("No files match {}", options.docs_path));
+            return Err(MarkdownError::ContentError {
+                kind: ContentErrorKind::EmptyContent,
+                context,
+            });
+        }
+        info!("Converting {} files from {}", paths.len(), url);
+
+        let mut files = Vec::with_capacity(paths.len());
+        for path in paths {
+            let local_path = checkout.path().join(&path);
+            let result = self.convert_url(&local_path.to_string_lossy()).await;
+            files.push(RepositoryFile { path, result });
+        }
+        Ok(files)
+    }
+
+    /// Converts the pages listed in a sitemap.
+    ///
+    /// The sitemap is fetched (or read from a local path), sitemap indexes
+    /// and gzipped sitemaps are followed, and the pages selected by the
+    /// include and exclude patterns are converted with
+    /// [`MarkdownDown::convert_url`]. Pages are converted
+    /// [`SitemapOptions::concurrency`] at a time, and conversions start at
+    /// least [`SitemapOptions::delay`] apart so the site is not overloaded.
+    /// Each page has its own result, so one failing page does not fail the
+    /// rest. With [`SitemapOptions::output_dir`], converted pages are also
+    /// written to files named after their URL slug.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The sitemap URL, such as `https://example.com/sitemap.xml`
+    /// * `options` - Which pages to convert, how fast, and where to write them
+    ///
+    /// # Returns
+    ///
+    /// Returns a map from page URL to the converted markdown or the error
+    /// that page failed with.
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::NetworkError` - If the sitemap could not be fetched
+    /// * `MarkdownError::ContentError` - If the sitemap could not be read or no pages match
+    /// * `MarkdownError::ConverterError` - If the output directory could not be created
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use markdowndown::sitemap::SitemapOptions;
+    /// use markdowndown::MarkdownDown;
+    ///
+    /// # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
+    /// let md = MarkdownDown::new();
+    /// let options = SitemapOptions::new().include("/docs/**").output_dir("site");
+    /// let pages = md
+    ///     .convert_sitemap("https://example.com/sitemap.xml", &options)
+    ///     .await?;
+    /// println!("Converted {} pages", pages.values().filter(|page| page.is_ok()).count());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn convert_sitemap(
+        &self,
+        url: &str,
+        options: &SitemapOptions,
+    ) -> Result<BTreeMap<String, Result<Markdown, MarkdownError>>, MarkdownError> {
+        let pages = sitemap::page_urls(&self.client, url, options).await?;
+        info!("Converting {} pages from sitemap {}", pages.len(), url);
+
+        if let Some(dir) = &options.output_dir {
+            tokio::fs::create_dir_all(dir)
+                .await
+                .map_err(|e| MarkdownError::ConverterError {
+                    kind: ConverterErrorKind::ProcessingError,
+                    context: ErrorContext::new(url, "Create output directory", "convert_sitemap")
+                        .with_info(format!("IO error: {e}")),
+                })?;
+        }
+
+        let next_start = tokio::sync::Mutex::new(tokio::time::Instant::now());
+        let adaptive = options
+            .adaptive_concurrency
+            .then(|| AdaptiveConcurrency::new(1, options.concurrency));
+        let results = self
+            .convert_paced(
+                pages,
+                options.concurrency,
+                options.delay,
+                &next_start,
+                adaptive.as_ref(),
+            )
+            .await;
+        let mut converted = BTreeMap::new();
+        for (page, result) in results {
+            let result = match (result, &options.output_dir) {
+                (Ok(markdown), Some(dir)) => {
+                    self.write_site_page(dir, &page, markdown, "convert_sitemap")
+                        .await
+                }
+                (result, _) => result,
+            };
+            converted.insert(page, result);
+        }
+        Ok(converted)
+    }
+
+    /// Converts pages from a seed URL, following links in the converted pages.
+    ///
+    /// The seed is converted first, then the pages it links to, then the
+    /// pages those link to, up to [`CrawlOptions::max_depth`] links from the
+    /// seed. Pages are taken from a [`CrawlFrontier`] in priority order and
+    /// each visit is recorded there; with [`CrawlOptions::frontier`] the
+    /// frontier is loaded before the crawl and saved after it, so a later
+    /// crawl fetches new and changing pages first. Each URL is converted once
+    /// per crawl, whatever the number of pages that link to it, and with
+    /// [`CrawlOptions::same_domain`] only links to the seed's domain are
+    /// followed. Unless [`CrawlOptions::respect_robots`] is
+    /// turned off, each host's `robots.txt` is fetched once and pages it
+    /// disallows for the configured user agent are skipped; a longer
+    /// `Crawl-delay` for the seed's host replaces [`CrawlOptions::delay`].
+    /// Pages are converted like [`MarkdownDown::convert_sitemap`] converts
+    /// them, concurrently and spaced by the politeness delay.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The URL the crawl starts from
+    /// * `options` - How far to follow links, how fast, and where to write pages
+    ///
+    /// # Returns
+    ///
+    /// Returns the converted pages, shallowest first and by URL within a
+    /// depth, each with the markdown or the error that page failed with.
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ValidationError` - If the seed is not a valid http(s) URL
+    /// * `MarkdownError::ContentError` - If the frontier file could not be read
+    /// * `MarkdownError::ConverterError` - If the output directory could not be
+    ///   created or the frontier file could not be written
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use markdowndown::crawl::CrawlOptions;
+    /// use markdowndown::MarkdownDown;
+    ///
+    /// # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
+    /// let md = MarkdownDown::new();
+    /// let options = CrawlOptions::new().max_depth(1).max_pages(50);
+    /// for page in md.crawl("https://example.com/docs/", &options).await? {
+    ///     println!("{} (depth {}): {}", page.url, page.depth, page.result.is_ok());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn crawl(
+        &self,
+        seed: &str,
+        options: &CrawlOptions,
+    ) -> Result<Vec<CrawledPage>, MarkdownError> {
+        let seed_url = url::Url::parse(seed)
+            .ok()
+            .filter(|parsed| matches!(parsed.scheme(), "http" | "https"))
+            .filter(|parsed| parsed.host_str().is_some())
+            .ok_or_else(|| MarkdownError::ValidationError {
+                kind: ValidationErrorKind::InvalidUrl,
+                context: ErrorContext::new(seed, "Seed validation", "crawl")
+                    .with_info("Crawls start from an http or https URL"),
+            })?;
+
+        if let Some(dir) = &options.output_dir {
+            tokio::fs::create_dir_all(dir)
+                .await
+                .map_err(|e| MarkdownError::ConverterError {
+                    kind: ConverterErrorKind::ProcessingError,
+                    context: ErrorContext::new(seed, "Create output directory", "crawl")
+                        .with_info(format!("IO error: {e}")),
+                })?;
+        }
+
+        let mut robots: HashMap<String, RobotsRules> = HashMap::new();
+        let mut delay = options.delay;
+        if options.respect_robots {
+            let rules = self.robots_rules(&seed_url, &mut robots).await;
+            if let Some(crawl_delay) = rules.crawl_delay.filter(|d| *d > delay) {
+                debug!("Using robots.txt crawl delay of {:?}", crawl_delay);
+                delay = crawl_delay;
+            }
+        }
+
+        let mut frontier = match &options.frontier {
+            Some(path) => CrawlFrontier::load(path).await?,
+            None => CrawlFrontier::new(),
+        };
+        frontier.add_seed(seed_url.as_str());
+        // Pages are fetched by the URL they were found under, not their canonical form
+        let mut page_urls =
+            HashMap::from([(identity::canonical_url(seed_url.as_str()), seed_url.to_string())]);
+        let mut attempted = HashSet::new();
+        let mut crawled = Vec::new();
+        let next_start = tokio::sync::Mutex::new(tokio::time::Instant::now());
+        let adaptive = options
+            .adaptive_concurrency
+            .then(|| AdaptiveConcurrency::new(1, options.concurrency));
+        loop {
+            let budget = options
+                .max_pages
+                .map_or(usize::MAX, |max| max.saturating_sub(crawled.len()));
+            let mut depths = HashMap::new();
+            for canonical in frontier.next_batch(frontier.len(), Utc::now()) {
+                if depths.len() >= budget {
+                    break;
+                }
+                let depth = frontier.get(&canonical).map_or(0, |entry| entry.depth);
+                if depth > options.max_depth || !attempted.insert(canonical.clone()) {
+                    continue;
+                }
+                let page = page_urls.get(&canonical).cloned().unwrap_or(canonical);
+                let Ok(page_url) = url::Url::parse(&page) else {
+                    continue;
+                };
+                // Frontiers loaded from a file may hold pages outside this crawl
+                if !crawl::in_scope(&page_url, &seed_url, options) {
+                    continue;
+                }
+                if options.respect_robots {
+                    let rules = self.robots_rules(&page_url, &mut robots).await;
+                    let path = &page_url[url::Position::BeforePath..url::Position::AfterQuery];
+                    if !rules.is_allowed(path) {
+                        debug!("Skipping {} disallowed by robots.txt", page);
+                        continue;
+                    }
+                }
+                depths.insert(page, depth);
+            }
+            if depths.is_empty() {
+                break;
+            }
+            info!("Crawling {} pages", depths.len());
+
+            let results = self
+                .convert_paced(
+                    depths.keys().cloned().collect(),
+                    options.concurrency,
+                    delay,
+                    &next_start,
+                    adaptive.as_ref(),
+                )
+                .await;
+
+            for (page, result) in results {
+                let depth = depths[&page];
+                if let Ok(markdown) = &result {
+                    frontier.record_visit(&page, markdown.as_str(), Utc::now());
+                    if depth < options.max_depth {
+                        for link in
+                            crawl::links_to_follow(markdown.as_str(), &page, &seed_url, options)
+                        {
+                            frontier.discover(&link, depth + 1);
+                            page_urls
+                                .entry(identity::canonical_url(&link))
+                                .or_insert(link);
+                        }
+                    }
+                }
+                let result = match (result, &options.output_dir) {
+                    (Ok(markdown), Some(dir)) => {
+                        self.write_site_page(dir, &page, markdown, "crawl").await
+                    }
+                    (result, _) => result,
+                };
+                crawled.push(CrawledPage {
+                    url: page,
+                    depth,
+                    result,
+                });
+            }
+        }
+
+        if let Some(path) = &options.frontier {
+            frontier.save(path).await?;
+        }
+        crawled.sort_by(|a, b| (a.depth, &a.url).cmp(&(b.depth, &b.url)));
+        Ok(crawled)
+    }
+
+    /// Returns the `robots.txt` rules for a page's host, fetching them once per host.
+    ///
+    /// A missing or unreadable `robots.txt` allows every page.
+    async fn robots_rules<'a>(
+        &self,
+        page: &url::Url,
+        cache: &'a mut HashMap<String, RobotsRules>,
+    ) -> &'a RobotsRules {
+        let origin = page.origin().ascii_serialization();
+        if !cache.contains_key(&origin) {
+            let robots_url = format!("{origin}/robots.txt");
+            let rules = match self.client.get_text(&robots_url).await {
+                Ok(robots) => RobotsRules::parse(&robots, &self.config.http.user_agent),
+                Err(e) => {
+                    debug!("No robots.txt rules for {}: {}", origin, e);
+                    RobotsRules::default()
+                }
+            };
+            cache.insert(origin.clone(), rules);
+        }
+        &cache[&origin]
+    }
+
+    /// Converts pages concurrently, starting conversions at least `delay` apart.
+    ///
+    /// `next_start` is the earliest time the next conversion may start, so
+    /// successive calls sharing it keep the spacing. With an `adaptive`
+    /// controller, each conversion also waits for a slot in its host's limit.
+    async fn convert_paced(
+        &self,
+        pages: Vec<String>,
+        concurrency: usize,
+        delay: std::time::Duration,
+        next_start: &tokio::sync::Mutex<tokio::time::Instant>,
+        adaptive: Option<&AdaptiveConcurrency>,
+    ) -> Vec<(String, Result<Markdown, MarkdownError>)> {
+        stream::iter(pages)
+            .map(|page| async move {
+                let permit = match adaptive {
+                    Some(adaptive) => Some(adaptive.acquire(&page).await),
+                    None => None,
+                };
+                let start = {
+                    let mut next = next_start.lock().await;
+                    let start = (*next).max(tokio::time::Instant::now());
+                    *next = start + delay;
+                    start
+                };
+                tokio::time::sleep_until(start).await;
+                let result = self.convert_url(&page).await;
+                if let Some(permit) = permit {
+                    permit.record(&result);
+                }
+                (page, result)
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Writes a page converted from a site to the output directory.
+    async fn write_site_page(
+        &self,
+        dir: &std::path::Path,
+        url: &str,
+        markdown: Markdown,
+        operation: &str,
+    ) -> Result<Markdown, MarkdownError> {
+        let mut filename = format!("{}.md", identity::document_slug(url));
+        if let Some(extension) = self.config.output.compression.extension() {
+            filename = format!("{filename}.{extension}");
+        }
+        compression::write_file_atomic(dir.join(filename), markdown.as_str())
+            .await
+            .map_err(|e| MarkdownError::ConverterError {
+                kind: ConverterErrorKind::ProcessingError,
+                context: ErrorContext::new(url, "Write output", operation)
+                    .with_info(format!("IO error: {e}")),
+            })?;
+        Ok(markdown)
+    }
+
+    /// Works out what converting a URL will do, without fetching anything.
+    ///
+    /// The plan lists the pipeline steps [`MarkdownDown::convert_url`] runs
+    /// for the URL, the converter and fallback that handle it, and how many
+    /// HTTP requests to expect, so a batch can be reviewed before it runs.
+    /// Host and path filters are applied: a URL they exclude is reported as
+    /// skipped, with no steps after the filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to plan
+    ///
+    /// # Returns
+    ///
+    /// Returns the conversion plan, or an error if the URL cannot be converted.
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ValidationError` - If the URL is invalid
+    /// * `MarkdownError::ConfigurationError` - If a filter pattern is invalid
+    /// * `MarkdownError::LegacyConfigurationError` - If no converter handles the URL type
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use markdowndown::MarkdownDown;
+    ///
+    /// let md = MarkdownDown::new();
+    /// let plan = md.plan("https://github.com/rust-lang/rust/pull/1")?;
+    /// assert_eq!(plan.converter, "GitHub Issue");
+    /// assert_eq!(plan.fallback.as_deref(), Some("HTML"));
+    /// assert_eq!(plan.estimated_requests, 4);
+    /// println!("{plan}");
+    /// # Ok::<(), markdowndown::types::MarkdownError>(())
+    /// ```
+    pub fn plan(&self, url: &str) -> Result<ConversionPlan, MarkdownError> {
+        let normalized_url = self.detector.normalize_url(url)?;
+        let url_type = self.detector.detect_type(&normalized_url)?;
+        let step = |name: &str, detail: String, requests: usize| PlanStep {
+            name: name.to_string(),
+            detail,
+            requests,
+        };
+        let mut steps = vec![step("detect", format!("Detected as {url_type}"), 0)];
+
+        let filter = self.content_filter()?;
+        let mut skipped = None;
+        if let Some(filter) = filter {
+            skipped = filter.check_url(&normalized_url).err();
+            let detail = if filter.has_content_rules() || filter.has_date_rules() {
+                "Check host and path rules, then content rules against the first response"
+            } else {
+                "Check host and path rules"
+            };
+            steps.push(step("filter", detail.to_string(), 0));
+        }
+
+        let converter = self.converter_for(&url_type)?;
+        let fallbacks = self.fallbacks_for(&normalized_url, &url_type);
+        let races_fallback = !fallbacks.is_empty() && self.config.http.race_strategies;
+        if skipped.is_none() {
+            let output = &self.config.output;
+            let scope = if output.frontmatter_only {
+                " (frontmatter only)"
+            } else {
+                ""
+            };
+            steps.push(step(
+                "convert",
+                format!("Convert with {}{scope}", converter.name()),
+                converter.estimated_requests(&normalized_url),
+            ));
+            if let Some((_, fallback)) = fallbacks.first() {
+                steps.push(if races_fallback {
+                    step(
+                        "fallback",
+                        format!("Race {} against the converter", fallback.name()),
+                        fallback.estimated_requests(&normalized_url),
+                    )
+                } else {
+                    let names: Vec<&str> = fallbacks.iter().map(|(_, c)| c.name()).collect();
+                    step(
+                        "fallback",
+                        format!(
+                            "Retry with {} after a recoverable failure",
+                            names.join(", then ")
+                        ),
+                        0,
+                    )
+                });
+            }
+            if output.resolve_includes
+                && (url_type == UrlType::LocalFile || includes::is_markdown_source(&normalized_url))
+            {
+                steps.push(step(
+                    "resolve-includes",
+                    "Replace include directives with the files they name".to_string(),
+                    0,
+                ));
+            }
+            if output.download_images.is_enabled() {
+                steps.push(step(
+                    "download-images",
+                    "Download referenced images and link to the copies".to_string(),
+                    0,
+                ));
+            }
+            if output.strip_tracking_params {
+                steps.push(step(
+                    "strip-tracking",
+                    "Remove tracking parameters from links".to_string(),
+                    0,
+                ));
+            }
+            if !self.link_rewriters.is_empty() {
+                steps.push(step(
+                    "rewrite-links",
+                    format!("Map link targets with {} rewriters", self.link_rewriters.len()),
+                    0,
+                ));
+            }
+            if let Some(wanted) = &output.extract_language {
+                steps.push(step(
+                    "language",
+                    format!("Keep only '{wanted}' blocks if the document is bilingual"),
+                    0,
+                ));
+            }
+            match output.direction_markers {
+                DirectionMarkers::Off => {}
+                DirectionMarkers::Unicode => steps.push(step(
+                    "direction",
+                    "Mark right-to-left paragraphs with right-to-left marks".to_string(),
+                    0,
+                )),
+                DirectionMarkers::Html => steps.push(step(
+                    "direction",
+                    "Wrap right-to-left paragraphs in dir=\"rtl\" elements".to_string(),
+                    0,
+                )),
+            }
+            if filter.is_some_and(|filter| filter.has_date_rules()) {
+                steps.push(step(
+                    "date-window",
+                    "Check the document date against the date window".to_string(),
+                    0,
+                ));
+            }
+            steps.push(step(
+                "identity",
+                "Stamp document_id and slug into the frontmatter".to_string(),
+                0,
+            ));
+            if self.frontmatter_provider.is_some() {
+                steps.push(step(
+                    "frontmatter-provider",
+                    "Replace the frontmatter fields with the provider's".to_string(),
+                    0,
+                ));
+            }
+            match output.lint_mode {
+                LintMode::Off => {}
+                LintMode::Warn => steps.push(step(
+                    "lint",
+                    "Lint the output and log problems".to_string(),
+                    0,
+                )),
+                LintMode::Fix => steps.push(step(
+                    "lint",
+                    "Lint the output and fix problems".to_string(),
+                    0,
+                )),
+            }
+            match output.frontmatter_format {
+                FrontmatterFormat::Yaml => {}
+                FrontmatterFormat::Toml => steps.push(step(
+                    "frontmatter-format",
+                    "Write the frontmatter as TOML".to_string(),
+                    0,
+                )),
+                FrontmatterFormat::Json => steps.push(step(
+                    "frontmatter-format",
+                    "Write the frontmatter as JSON".to_string(),
+                    0,
+                )),
+            }
+        }
+
+        let estimated_requests = steps.iter().map(|step| step.requests).sum();
+        Ok(ConversionPlan {
+            url: normalized_url,
+            url_type,
+            converter: converter.name().to_string(),
+            fallback: fallbacks
+                .first()
+                .map(|(_, fallback)| fallback.name().to_string()),
+            races_fallback,
+            skipped,
+            steps,
+            estimated_requests,
+        })
+    }
+
+    /// Looks up the converter for a URL type.
+    ///
+    /// Custom types without their own converter are treated as web pages.
+    fn converter_for(&self, url_type: &UrlType) -> Result<&dyn Converter, MarkdownError> {
+        debug!("Looking up converter for type: {}", url_type);
+        let converter = match self.registry.get_converter(url_type) {
+            Some(converter) => Some(converter),
+            None if matches!(url_type, UrlType::Custom(_)) => {
+                warn!("No converter registered for {}, using HTML converter", url_type);
+                self.registry.get_converter(&UrlType::Html)
+            }
+            None => None,
+        }
+        .ok_or_else(|| {
+            error!("No converter available for URL type: {}", url_type);
+            MarkdownError::LegacyConfigurationError {
+                message: format!("No converter available for URL type: {url_type}"),
+            }
+        })?;
+        debug!("Found converter for type: {}", url_type);
+        Ok(converter)
+    }
+
+    /// Looks up the converters tried, in order, when a specialized converter fails.
+    ///
+    /// The URL's other candidate types are tried from most to least likely,
+    /// as ranked by [`UrlDetector::detect_with_confidence`], ending with the
+    /// HTML converter.
+    fn fallbacks_for(&self, url: &str, url_type: &UrlType) -> Vec<(UrlType, &dyn Converter)> {
+        // Custom types without a converter already use the HTML converter
+        if self.registry.get_converter(url_type).is_none() {
+            return Vec::new();
+        }
+        let candidates = self
+            .detector
+            .detect_with_confidence(url)
+            .unwrap_or_default();
+        let mut fallbacks: Vec<(UrlType, &dyn Converter)> = Vec::new();
+        for candidate in candidates
+            .into_iter()
+            .map(|(candidate, _)| candidate)
+            .chain(std::iter::once(UrlType::Html))
+        {
+            if candidate == *url_type || fallbacks.iter().any(|(known, _)| *known == candidate) {
+                continue;
+            }
+            if let Some(converter) = self.registry.get_converter(&candidate) {
+                fallbacks.push((candidate, converter));
+            }
+        }
+        fallbacks
+    }
+
+    /// Converts a normalized URL with its detected converter.
+    ///
+    /// Recoverable failures of specialized converters are retried with the
+    /// converters of the URL's other candidate types, ending with HTML, before
+    /// giving up. Every attempt stops at `deadline`. When strategies race, the
+    /// converter runs at once with the first fallback instead.
+    async fn convert_with_fallback(
+        &self,
+        normalized_url: &str,
+        url_type: &UrlType,
+        deadline: Option<tokio::time::Instant>,
+    ) -> Result<Markdown, MarkdownError> {
+        // Step 4: Get appropriate converter
+        let converter = self.converter_for(url_type)?;
+        let fallbacks = self.fallbacks_for(normalized_url, url_type);
+
+        if let Some((fallback_type, fallback_converter)) = fallbacks.first() {
+            if self.config.http.race_strategies {
+                info!(
+                    "Racing {} converter against {} fallback",
+                    url_type, fallback_type
+                );
+                return self
+                    .race_with_fallback(
+                        converter,
+                        (fallback_type, *fallback_converter),
+                        normalized_url,
+                        url_type,
+                        deadline,
+                    )
+                    .await;
+            }
+        }
+
+        // Step 5: Convert using the selected converter
+        info!("Starting conversion with {} converter", url_type);
+        let primary = within_budget(
+            deadline,
+            normalized_url,
+            "Conversion",
+            self.run_converter(converter, normalized_url),
+        )
+        .await;
+        match primary {
+            Ok(result) => {
+                info!(
+                    "Successfully converted URL to markdown ({} chars)",
+                    result.as_str().len()
+                );
+                Ok(result)
+            }
+            Err(e) => {
+                error!("Primary converter failed: {}", e);
+
+                // Step 6: Attempt fallback strategies for recoverable errors
+                if !e.is_recoverable() {
+                    return Err(e);
+                }
+                for (fallback_type, fallback_converter) in fallbacks {
+                    let out_of_time =
+                        deadline.is_some_and(|deadline| tokio::time::Instant::now() >= deadline);
+                    if out_of_time {
+                        warn!(
+                            "Total time budget exhausted, skipping {} fallback",
+                            fallback_type
+                        );
+                        break;
+                    }
+                    warn!(
+                        "Attempting {} fallback conversion for recoverable error",
+                        fallback_type
+                    );
+                    let fallback = within_budget(
+                        deadline,
+                        normalized_url,
+                        "Fallback conversion",
+                        self.run_converter(fallback_converter, normalized_url),
+                    )
+                    .await;
+                    match fallback {
+                        Ok(fallback_result) => {
+                            warn!(
+                                "Fallback {} conversion succeeded ({} chars)",
+                                fallback_type,
+                                fallback_result.as_str().len()
+                            );
+                            events::fallback_used(
+                                normalized_url,
+                                url_type,
+                                &fallback_type,
+                                &e.to_string(),
+                            );
+                            return Ok(fallback_result);
+                        }
+                        Err(fallback_error) => {
+                            error!(
+                                "Fallback {} conversion also failed: {}",
+                                fallback_type, fallback_error
+                            );
+                        }
+                    }
+                }
+
+                Err(e)
+            }
+        }
+    }
+
+    /// Runs a converter and its first fallback at the same time.
+    ///
+    /// The first successful result is used and the other conversion is
+    /// canceled. An unrecoverable primary error is returned straight away, as
+    /// it would be without racing. If both fail, the primary error is returned.
+    async fn race_with_fallback(
+        &self,
+        converter: &dyn Converter,
+        (fallback_type, fallback_converter): (&UrlType, &dyn Converter),
+        normalized_url: &str,
+        url_type: &UrlType,
+        deadline: Option<tokio::time::Instant>,
+    ) -> Result<Markdown, MarkdownError> {
+        let primary = within_budget(
+            deadline,
+            normalized_url,
+            "Conversion",
+            self.run_converter(converter, normalized_url),
+        );
+        let fallback = within_budget(
+            deadline,
+            normalized_url,
+            "Fallback conversion",
+            self.run_converter(fallback_converter, normalized_url),
+        );
+        tokio::pin!(primary, fallback);
+
+        tokio::select! {
+            // Prefer the primary result when both are ready
+            biased;
+            result = &mut primary => match result {
+                Ok(result) => {
+                    info!("Primary converter won the race ({} chars)", result.as_str().len());
+                    Ok(result)
+                }
+                Err(e) if !e.is_recoverable() => {
+                    error!("Primary converter failed: {}", e);
+                    Err(e)
+                }
+                Err(e) => {
+                    error!(
+                        "Primary converter failed, waiting for {} fallback: {}",
+                        fallback_type, e
+                    );
+                    match fallback.await {
+                        Ok(result) => {
+                            let reason = e.to_string();
+                            events::fallback_used(normalized_url, url_type, fallback_type, &reason);
+                            Ok(result)
+                        }
+                        Err(fallback_error) => {
+                            error!(
+                                "Fallback {} conversion also failed: {}",
+                                fallback_type, fallback_error
+                            );
+                            Err(e)
+                        }
+                    }
+                }
+            },
+            result = &mut fallback => match result {
+                Ok(result) => {
+                    info!(
+                        "{} fallback won the race ({} chars)",
+                        fallback_type,
+                        result.as_str().len()
+                    );
+                    events::fallback_used(normalized_url, url_type, fallback_type, "race");
+                    Ok(result)
+                }
+                Err(fallback_error) => {
+                    warn!(
+                        "Fallback {} conversion failed, waiting for primary: {}",
+                        fallback_type, fallback_error
+                    );
+                    primary.await
+                }
+            },
+        }
+    }
+
+    /// Runs a converter, producing only frontmatter when so configured.
+    async fn run_converter(
+        &self,
+        converter: &dyn Converter,
+        normalized_url: &str,
+    ) -> Result<Markdown, MarkdownError> {
+        if self.config.output.frontmatter_only {
+            converter.convert_frontmatter(normalized_url).await
+        } else {
+            converter.convert(normalized_url).await
+        }
+    }
+
+    /// Resolves the URL a document is converted from.
+    ///
+    /// Links from known URL shorteners are always expanded, and with
+    /// `resolve_redirects` HTML and PDF URLs are followed through their
+    /// redirects too, so a shortened or shared link to a Google Doc or a
+    /// GitHub issue goes to its dedicated converter. Returns the response
+    /// metadata fetched for the final URL, if any, so sniffing and content
+    /// filters can reuse it. Failures are logged and ignored, leaving the
+    /// URL in place.
+    async fn resolve_url(
+        &self,
+        url: String,
+    ) -> Result<(String, Option<ResponseMetadata>), MarkdownError> {
+        let follow = self.detector.is_shortened_url(&url)
+            || (self.config.http.resolve_redirects
+                && matches!(
+                    self.detector.detect_type(&url)?,
+                    UrlType::Html | UrlType::Pdf
+                ));
+        if !follow {
+            return Ok((url, None));
+        }
+
+        let metadata = match self.client.sniff(&url).await {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                warn!("Failed to resolve redirects for {}: {}", url, e);
+                return Ok((url, None));
+            }
+        };
+        if metadata.url == url {
+            return Ok((url, Some(metadata)));
+        }
+        info!("Resolved {} to {}", url, metadata.url);
+        let final_url = self.detector.normalize_url(&metadata.url)?;
+        // Normalizing can drop query parameters, making it a different request
+        let metadata = (final_url == metadata.url).then_some(metadata);
+        Ok((final_url, metadata))
+    }
+
+    /// Detects a normalized URL's type, checking the served content type
+    /// when sniffing is enabled.
+    ///
+    /// Returns the response metadata fetched for sniffing, if any, so content
+    /// filters can reuse it. Metadata already fetched while resolving the URL
+    /// is used instead of a new request. Sniffing failures are logged and
+    /// ignored, leaving the URL routed by its address.
+    async fn detect_served_type(
+        &self,
+        url: &str,
+        preflight: Option<ResponseMetadata>,
+    ) -> Result<(UrlType, Option<ResponseMetadata>), MarkdownError> {
+        let url_type = self.detector.detect_type(url)?;
+        if !(self.config.http.sniff_content_type
+            && matches!(url_type, UrlType::Html | UrlType::Pdf))
+        {
+            return Ok((url_type, preflight));
+        }
+
+        let metadata = match preflight {
+            Some(metadata) => metadata,
+            None => match self.client.sniff(url).await {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    debug!("Content sniffing failed, routing by URL: {}", e);
+                    return Ok((url_type, None));
+                }
+            },
+        };
+        let url_type = match metadata.content_type() {
+            Some(content_type) => self.detector.refine_type(url_type, &content_type),
+            None => url_type,
+        };
+        Ok((url_type, Some(metadata)))
+    }
+
+    /// Converts the latest Wayback Machine snapshot of a URL that could not be
+    /// converted, with `wayback_api_url` set.
+    ///
+    /// Only dead links and timeouts are retried from the archive; see
+    /// [`wayback::should_fall_back`]. The snapshot is converted by the
+    /// converter its own URL is detected as, and the document records
+    /// `archived_from`, `snapshot_timestamp`, and `snapshot_url` in its
+    /// frontmatter. If the fallback is disabled, no snapshot exists, or the
+    /// snapshot cannot be converted either, the original error is returned.
+    async fn convert_from_archive(
+        &self,
+        url: &str,
+        error: MarkdownError,
+        deadline: Option<tokio::time::Instant>,
+    ) -> Result<Markdown, MarkdownError> {
+        let Some(api_url) = &self.config.http.wayback_api_url else {
+            return Err(error);
+        };
+        if !wayback::should_fall_back(&error) {
+            return Err(error);
+        }
+
+        // The lookup runs in its own observation scope, so the document's
+        // final URL and status come from the snapshot fetch, not the API
+        info!("Looking up a Wayback Machine snapshot of {}", url);
+        let (lookup, _) = events::observe(within_budget(
+            deadline,
+            url,
+            "Wayback lookup",
+            wayback::latest_snapshot(&self.client, api_url, url),
+        ))
+        .await;
+        let snapshot = match lookup {
+            Ok(Some(snapshot)) => snapshot,
+            Ok(None) => {
+                info!("No Wayback Machine snapshot of {}", url);
+                return Err(error);
+            }
+            Err(e) => {
+                warn!("Wayback Machine lookup failed for {}: {}", url, e);
+                return Err(error);
+            }
+        };
+
+        warn!(
+            "Converting {} from its Wayback Machine snapshot {}",
+            url, snapshot.url
+        );
+        let snapshot_type = self
+            .detector
+            .detect_type(&snapshot.url)
+            .unwrap_or(UrlType::Html);
+        // The snapshot's own response is held to the content filter too
+        let filter = self.content_filter().ok().flatten();
+        let (converted, _) = self
+            .check_first_response(
+                filter,
+                self.convert_with_fallback(&snapshot.url, &snapshot_type, deadline),
+            )
+            .await;
+        match converted {
+            Ok(converted) => {
+                let converted = set_frontmatter_field(converted.as_str(), "archived_from", url);
+                let converted = set_frontmatter_field(
+                    &converted,
+                    "snapshot_timestamp",
+                    &snapshot.timestamp.to_rfc3339(),
+                );
+                let converted = set_frontmatter_field(&converted, "snapshot_url", &snapshot.url);
+                Ok(Markdown::from(converted))
+            }
+            Err(e) if e.is_skip() => Err(e),
+            Err(e) => {
+                warn!("Failed to convert snapshot {}: {}", snapshot.url, e);
+                Err(error)
+            }
+        }
+    }
+
+    /// Converts an HTML page again from its canonical URL when that URL has a
+    /// dedicated converter, with `resolve_redirects` enabled.
+    ///
+    /// The HTML converter records a page's `<link rel="canonical">` in the
+    /// `canonical_url` frontmatter field; only URLs of service types, such as
+    /// GitHub issues, are followed. Documents reached through a redirect
+    /// record the URL they were converted from instead. If converting the
+    /// canonical URL fails, the page's own conversion is kept.
+    async fn follow_canonical(
+        &self,
+        requested_url: &str,
+        url: String,
+        url_type: UrlType,
+        result: Markdown,
+        deadline: Option<tokio::time::Instant>,
+    ) -> (String, UrlType, Markdown) {
+        let Some(canonical) = frontmatter_field(result.as_str(), "canonical_url") else {
+            if requested_url == url {
+                return (url, url_type, result);
+            }
+            let result = set_frontmatter_field(result.as_str(), "canonical_url", &url);
+            return (url, url_type, Markdown::from(result));
+        };
+        if !(self.config.http.resolve_redirects && url_type == UrlType::Html) {
+            return (url, url_type, result);
+        }
+        let canonical_type = match self.detector.detect_type(&canonical) {
+            Ok(
+                canonical_type @ (UrlType::GoogleDocs
+                | UrlType::GitHubIssue
+                | UrlType::AzureDevOps
+                | UrlType::Office365
+                | UrlType::Dropbox
+                | UrlType::Custom(_)),
+            ) => canonical_type,
+            _ => return (url, url_type, result),
+        };
+
+        info!("Converting {} from its canonical URL {}", url, canonical);
+        match self
+            .convert_with_fallback(&canonical, &canonical_type, deadline)
+            .await
+        {
+            Ok(converted) => {
+                let converted =
+                    set_frontmatter_field(converted.as_str(), "canonical_url", &canonical);
+                (canonical, canonical_type, Markdown::from(converted))
+            }
+            Err(e) => {
+                warn!("Failed to convert canonical URL {}: {}", canonical, e);
+                (url, url_type, result)
+            }
+        }
+    }
+
+    /// Returns the content filter compiled from the configuration, if any rules are set.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MarkdownError::ConfigurationError` if a path pattern is not a valid regex.
+    fn content_filter(&self) -> Result<Option<&Arc<ContentFilter>>, MarkdownError> {
+        match &self.filter {
+            Ok(filter) => Ok(filter.as_ref()),
+            Err(context) => Err(MarkdownError::ConfigurationError {
+                kind: ConfigErrorKind::InvalidValue,
+                context: context.clone(),
+            }),
+        }
+    }
+
+    /// Evaluates the host and path rules for a URL before anything is fetched.
+    ///
+    /// Response metadata already fetched by content sniffing is checked against
+    /// the content rules too, so a rejected document is not downloaded. The
+    /// conversion's own first response is checked again in
+    /// [`MarkdownDown::check_first_response`].
+    fn apply_filters(
+        &self,
+        filter: &ContentFilter,
+        url: &str,
+        preflight: Option<ResponseMetadata>,
+    ) -> Result<(), MarkdownError> {
+        filter
+            .check_url(url)
+            .map_err(|reason| filter::skipped(url, "Content filtering", reason))?;
+        if let Some(metadata) = preflight {
+            filter::check_metadata(filter, &metadata)
+                .map_err(|reason| filter::skipped(url, "Content filtering", reason))?;
+        }
+        Ok(())
+    }
+
+    /// Runs a conversion step, checking the first response it fetches against
+    /// the content filter's MIME type, size, and date rules.
+    ///
+    /// Returns the step's output with the document's date, either the response's
+    /// `Last-Modified` date or the published date the converter extracted, so
+    /// the date window can be applied after conversion without a second request.
+    async fn check_first_response<T>(
+        &self,
+        filter: Option<&Arc<ContentFilter>>,
+        conversion: impl std::future::Future<Output = T>,
+    ) -> (T, Option<DateTime<Utc>>) {
+        match filter {
+            Some(filter) if filter.has_content_rules() || filter.has_date_rules() => {
+                filter::check_first_response(filter.clone(), conversion).await
+            }
+            _ => (conversion.await, None),
+        }
+    }
+
+    /// Applies the configured date window to a converted document.
+    ///
+    /// The `Last-Modified` header takes precedence, then the published date
+    /// the converter extracted, then a `published_date` frontmatter field
+    /// carried over from the source. Documents with no known date are passed
+    /// through unchanged.
+    ///
+    /// Flagged documents are marked with a `date_window` frontmatter field;
+    /// without frontmatter there is nowhere to put it, so a warning is
+    /// recorded instead.
+    fn apply_date_window(
+        &self,
+        filter: &ContentFilter,
+        url: &str,
+        markdown: Markdown,
+        document_date: Option<DateTime<Utc>>,
+    ) -> Result<Markdown, MarkdownError> {
+        let date = document_date.or_else(|| {
+            frontmatter_field(markdown.as_str(), "published_date")
+                .and_then(|value| parse_date(&value))
+        });
+
+        let Some(date) = date else {
+            debug!("No document date available, skipping date window");
+            return Ok(markdown);
+        };
+
+        match filter.check_date(date) {
+            Ok(()) => Ok(markdown),
+            Err(reason) => match filter.date_window_action() {
+                DateWindowAction::Skip => {
+                    info!("Skipping URL: {}", reason);
+                    Err(MarkdownError::Skipped(Box::new(SkipDetails {
+                        reason,
+                        context: ErrorContext::new(url, "Date window", "MarkdownDown"),
+                    })))
+                }
+                DateWindowAction::Flag if !self.config.output.include_frontmatter => {
+                    warn!("Cannot flag {} without frontmatter: {}", url, reason);
+                    events::warning(format!(
+                        "document is outside the date window ({reason}) but frontmatter is disabled, so it was not flagged"
+                    ));
+                    Ok(markdown)
+                }
+                DateWindowAction::Flag => {
+                    info!("Flagging document outside date window: {}", reason);
+                    let status = match reason {
+                        SkipReason::TooNew { .. } => "too_new",
+                        _ => "too_old",
+                    };
+                    Ok(Markdown::from(set_frontmatter_field(
+                        markdown.as_str(),
+                        "date_window",
+                        status,
+                    )))
+                }
+            },
+        }
+    }
+
+    /// Diagnoses the environment conversions run in.
+    ///
+    /// Checks that the network is reachable, validates the configured GitHub
+    /// and Google tokens against their APIs, mints a token with the Google
+    /// service account key, if any, and looks for the optional
+    /// `git` and `pandoc` tools. Checks never return an error; problems are
+    /// reported in the returned [`DoctorReport`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use markdowndown::MarkdownDown;
+    ///
+    /// # async fn example() {
+    /// let report = MarkdownDown::new().doctor().await;
+    /// print!("{report}");
+    /// # }
+    /// ```
+    pub async fn doctor(&self) -> DoctorReport {
+        self.doctor_with_options(&DoctorOptions::new()).await
+    }
+
+    /// Diagnoses the environment, checking the given endpoints and tools.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The connectivity URL, token endpoint, and tools to check
+    pub async fn doctor_with_options(&self, options: &DoctorOptions) -> DoctorReport {
+        crate::doctor::diagnose(&self.client, &self.config, options).await
+    }
+
+    /// Returns the configuration being used by this instance.
+    pub fn config(&self) -> &crate::config::Config {
+        &self.config
+    }
+
+    /// Returns the URL detector being used by this instance.
+    pub fn detector(&self) -> &UrlDetector {
+        &self.detector
+    }
+
+    /// Returns the converter registry being used by this instance.
+    pub fn registry(&self) -> &ConverterRegistry {
+        &self.registry
+    }
+
+    /// Lists all supported URL types.
+    pub fn supported_types(&self) -> Vec<crate::types::UrlType> {
+        self.registry.supported_types()
+    }
+
+    /// Returns the per-host request statistics recorded by this instance.
+    ///
+    /// Use [`HostStatsRecorder::snapshot`] or [`HostStatsRecorder::worst_hosts`]
+    /// to see success rates, latency, retries, and throttling for each host.
+    pub fn host_stats(&self) -> &HostStatsRecorder {
+        self.client.stats()
+    }
+}
+
+impl Default for MarkdownDown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convenience function for converting a URL to markdown with default configuration.
+///
+/// This is equivalent to calling `MarkdownDown::new().convert_url(url).await`.
+///
+/// # Arguments
+///
+/// * `url` - The URL to fetch and convert
+///
+/// # Returns
+///
+/// Returns the converted markdown content or an error.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdowndown::convert_url;
+///
+/// # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
+/// let result = convert_url("https://example.com/article.html").await?;
+/// println!("{}", result);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn convert_url(url: &str) -> Result<Markdown, MarkdownError> {
+    MarkdownDown::new().convert_url(url).await
+}
+
+/// Compiles the configured content filter once per instance.
+///
+/// An invalid pattern is logged here and returned by every conversion, since
+/// constructing an instance cannot fail.
+fn compile_filter(filters: &FilterConfig) -> Result<Option<Arc<ContentFilter>>, ErrorContext> {
+    if filters.is_empty() {
+        return Ok(None);
+    }
+    match ContentFilter::new(filters) {
+        Ok(filter) => Ok(Some(Arc::new(filter))),
+        Err(e) => {
+            error!("Invalid content filter: {}", e);
+            Err(e.context().cloned().unwrap_or_else(|| {
+                ErrorContext::new("", "Compile path filter", "ContentFilter")
+            }))
+        }
+    }
+}
+
+/// Stamps the stable `document_id` and `slug` fields for a URL into the frontmatter.
+///
+/// URLs with an internationalized host also get `host_unicode`, the readable
+/// form of the host, and `idn_mixed_script: true` when the host mixes
+/// scripts in a way typical of homograph attacks.
+fn stamp_identity(markdown: &str, url: &str) -> String {
+    let with_id = set_frontmatter_field(markdown, "document_id", &identity::document_id(url));
+    let with_slug = set_frontmatter_field(&with_id, "slug", &identity::document_slug(url));
+
+    let idn_host = url::Url::parse(url)
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(str::to_string))
+        .filter(|host| host.split('.').any(|label| label.starts_with("xn--")));
+    let Some(host) = idn_host else {
+        return with_slug;
+    };
+    let with_host = set_frontmatter_field(&with_slug, "host_unicode", &utils::host_to_unicode(&host));
+    if utils::is_mixed_script_host(&host) {
+        set_frontmatter_field(&with_host, "idn_mixed_script", "true")
+    } else {
+        with_host
+    }
+}
+
+/// Stamps the identity of one part of a split document into its frontmatter.
+///
+/// The part gets its own `document_id` and `slug`, and `parent_id` is the
+/// `document_id` the whole document would have.
+fn stamp_part(markdown: &str, url: &str, part: &SubDocument, part_count: usize) -> String {
+    let with_identity = stamp_identity(markdown, url);
+    let fields = [
+        ("document_id", identity::part_id(url, part.index)),
+        (
+            "slug",
+            format!("{}-{}", identity::document_slug(url), part.index),
+        ),
+        ("parent_id", identity::document_id(url)),
+        ("part_index", part.index.to_string()),
+        ("part_count", part_count.to_string()),
+    ];
+    let with_fields = fields.iter().fold(with_identity, |markdown, (key, value)| {
+        set_frontmatter_field(&markdown, key, value)
+    });
+    match &part.title {
+        Some(title) => set_frontmatter_field(&with_fields, "part_title", title),
+        None => with_fields,
+    }
+}
+
+/// Adds an `excerpt` frontmatter field taken from the converted markdown.
+///
+/// Converters that read the original HTML write a more exact excerpt
+/// themselves; documents that already have one are returned unchanged.
+fn stamp_excerpt(markdown: &str, sentences: usize) -> String {
+    if frontmatter_field(markdown, "excerpt").is_some() {
+        return markdown.to_string();
+    }
+    let excerpt = frontmatter_block(markdown)
+        .and_then(|(_, body)| ExcerptExtractor::new().extract(body, sentences));
+    match excerpt {
+        Some(excerpt) => set_frontmatter_field(markdown, "excerpt", &excerpt),
+        None => markdown.to_string(),
+    }
+}
+
+/// Marks right-to-left paragraphs and records a `direction` frontmatter field.
+///
+/// Documents without right-to-left text are returned unchanged.
+fn apply_direction(markdown: &str, markers: DirectionMarkers) -> String {
+    let Some(direction) = language::detect_direction(markdown) else {
+        return markdown.to_string();
+    };
+    let marked = language::mark_direction(markdown, markers);
+    set_frontmatter_field(&marked, "direction", direction)
+}
+
+/// Runs one step of a conversion, failing with a timeout once `deadline` passes.
+async fn within_budget<T>(
+    deadline: Option<tokio::time::Instant>,
+    url: &str,
+    operation: &str,
+    step: impl std::future::Future<Output = Result<T, MarkdownError>>,
+) -> Result<T, MarkdownError> {
+    let Some(deadline) = deadline else {
+        return step.await;
+    };
+    tokio::time::timeout_at(deadline, step)
+        .await
+        .unwrap_or_else(|_| {
+            warn!(
+                "Total time budget exhausted during {} for {}",
+                operation, url
+            );
+            Err(MarkdownError::EnhancedNetworkError {
+                kind: NetworkErrorKind::Timeout,
+                context: ErrorContext::new(url, operation, "MarkdownDown")
+                    .with_info("Total conversion time budget exceeded"),
+            })
+        })
+}
+
+/// Convenience function for converting a URL to markdown with custom configuration.
+///
+/// # Arguments
+///
+/// * `url` - The URL to fetch and convert
+/// * `config` - The configuration to use
+///
+/// # Returns
+///
+/// Returns the converted markdown content or an error.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdowndown::{convert_url_with_config, Config};
+///
+/// # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
+/// let config = Config::builder()
+///     .timeout_seconds(60)
+///     .build();
+///
+/// let result = convert_url_with_config("https://example.com/article.html", config).await?;
+/// println!("{}", result);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn convert_url_with_config(
+    url: &str,
+    config: crate::config::Config,
+) -> Result<Markdown, MarkdownError> {
+    MarkdownDown::with_config(config).convert_url(url).await
+}
+
+/// Utility function to detect the type of a URL without converting it.
+///
+/// # Arguments
+///
+/// * `url` - The URL to analyze
+///
+/// # Returns
+///
+/// Returns the detected URL type or an error.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdowndown::{detect_url_type, types::UrlType};
+///
+/// # fn example() -> Result<(), markdowndown::types::MarkdownError> {
+/// let url_type = detect_url_type("https://docs.google.com/document/d/123/edit")?;
+/// assert_eq!(url_type, UrlType::GoogleDocs);
+/// # Ok(())
+/// # }
+/// ```
+pub fn detect_url_type(url: &str) -> Result<crate::types::UrlType, MarkdownError> {
+    let detector = UrlDetector::new();
+    detector.detect_type(url)
+}
+
+// Re-export main API items for convenience
+pub use config::Config;
+// Used by `register_converter!`, so plugin crates need not depend on inventory
+#[doc(hidden)]
+pub use inventory;
+pub use converters::{Converter, HtmlConverter};
+pub use types::{Frontmatter, Url};
+
+/// Library version information
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::converters::GitHubConverter;
+    use crate::detection::UrlDetector;
+    use crate::types::UrlType;
+    use std::time::Duration;
+
+    #[test]
+    fn test_version_available() {
+        // Verify version follows semantic versioning pattern (major.minor.patch)
+        assert!(VERSION.chars().any(|c| c.is_ascii_digit()));
+        assert!(VERSION.contains('.'));
+        // Basic format validation - should have at least one dot for major.minor
+        let parts: Vec<&str> = VERSION.split('.').collect();
+        assert!(
+            parts.len() >= 2,
+            "Version should have at least major.minor format"
+        );
+    }
+
+    #[test]
+    fn test_markdowndown_with_default_config() {
+        // Test that MarkdownDown can be created with default configuration
+        let md = MarkdownDown::new();
+
+        // Verify config is stored and accessible
+        let config = md.config();
+        assert_eq!(config.http.timeout, Duration::from_secs(30));
+        assert_eq!(config.http.max_retries, 3);
+        assert_eq!(config.http.retry_delay, Duration::from_secs(1));
+        assert_eq!(config.http.max_redirects, 10);
+        assert!(config.auth.github_token.is_none());
+        assert!(config.auth.office365_token.is_none());
+        assert!(config.auth.google_api_key.is_none());
+        assert!(config.output.include_frontmatter);
+        assert_eq!(config.output.max_consecutive_blank_lines, 2);
+    }
+
+    #[test]
+    fn test_markdowndown_with_custom_config() {
+        // Test that MarkdownDown respects custom configuration
+        let config = Config::builder()
+            .timeout_seconds(60)
+            .user_agent("TestApp/1.0")
+            .max_retries(5)
+            .github_token("test_token")
+            .include_frontmatter(false)
+            .max_consecutive_blank_lines(1)
+            .build();
+
+        let md = MarkdownDown::with_config(config);
+
+        // Verify custom config is stored
+        let stored_config = md.config();
+        assert_eq!(stored_config.http.timeout, Duration::from_secs(60));
+        assert_eq!(stored_config.http.user_agent, "TestApp/1.0");
+        assert_eq!(stored_config.http.max_retries, 5);
+        assert_eq!(
+            stored_config.auth.github_token,
+            Some("test_token".to_string())
+        );
+        assert!(!stored_config.output.include_frontmatter);
+        assert_eq!(stored_config.output.max_consecutive_blank_lines, 1);
+    }
+
+    #[test]
+    fn test_config_builder_fluent_interface() {
+        // Test that the config builder's fluent interface works correctly
+        let config = Config::builder()
+            .github_token("ghp_test_token")
+            .office365_token("office_token")
+            .google_api_key("google_key")
+            .timeout_seconds(45)
+            .user_agent("IntegrationTest/2.0")
+            .max_retries(3)
+            .include_frontmatter(true)
+            .custom_frontmatter_field("project", "markdowndown")
+            .custom_frontmatter_field("version", "test")
+            .normalize_whitespace(false)
+            .max_consecutive_blank_lines(3)
+            .build();
+
+        // Verify all custom settings
+        assert_eq!(config.auth.github_token, Some("ghp_test_token".to_string()));
+        assert_eq!(
+            config.auth.office365_token,
+            Some("office_token".to_string())
+        );
+        assert_eq!(config.auth.google_api_key, Some("google_key".to_string()));
+        assert_eq!(config.http.timeout, Duration::from_secs(45));
+        assert_eq!(config.http.user_agent, "IntegrationTest/2.0");
+        assert_eq!(config.http.max_retries, 3);
+        assert!(config.output.include_frontmatter);
+        assert_eq!(config.output.custom_frontmatter_fields.len(), 2);
+        assert_eq!(
+            config.output.custom_frontmatter_fields[0],
+            ("project".to_string(), "markdowndown".to_string())
+        );
+        assert_eq!(
+            config.output.custom_frontmatter_fields[1],
+            ("version".to_string(), "test".to_string())
+        );
+        assert!(!config.output.normalize_whitespace);
+        assert_eq!(config.output.max_consecutive_blank_lines, 3);
+    }
+
+    #[test]
+    fn test_config_from_default() {
+        // Test that Config::default() produces expected defaults
+        let config = Config::default();
+
+        // HTTP config defaults
+        assert_eq!(config.http.timeout, Duration::from_secs(30));
+        assert!(config.http.user_agent.starts_with("markdowndown/"));
+        assert_eq!(config.http.max_retries, 3);
+        assert_eq!(config.http.retry_delay, Duration::from_secs(1));
+        assert_eq!(config.http.max_redirects, 10);
+
+        // Auth config defaults
+        assert!(config.auth.github_token.is_none());
+        assert!(config.auth.office365_token.is_none());
+        assert!(config.auth.google_api_key.is_none());
+
+        // Output config defaults
+        assert!(config.output.include_frontmatter);
+        assert!(config.output.custom_frontmatter_fields.is_empty());
+        assert!(config.output.normalize_whitespace);
+        assert_eq!(config.output.max_consecutive_blank_lines, 2);
+    }
+
+    #[test]
+    fn test_supported_url_types() {
+        // Test that MarkdownDown reports supported URL types correctly
+        let md = MarkdownDown::new();
+        let supported_types = md.supported_types();
+
+        // Should support at least these URL types
+        assert!(supported_types.contains(&crate::types::UrlType::Html));
+        assert!(supported_types.contains(&crate::types::UrlType::GoogleDocs));
+        assert!(supported_types.contains(&crate::types::UrlType::GitHubIssue));
+        assert!(supported_types.contains(&crate::types::UrlType::LocalFile));
+        assert!(supported_types.contains(&crate::types::UrlType::DataUri));
+        assert!(supported_types.contains(&crate::types::UrlType::CloudStorage));
+        assert!(supported_types.contains(&crate::types::UrlType::Ipfs));
+        assert!(supported_types.contains(&crate::types::UrlType::AzureDevOps));
+        assert!(supported_types.contains(&crate::types::UrlType::Office365));
+        assert!(supported_types.contains(&crate::types::UrlType::Dropbox));
+        assert!(supported_types.contains(&crate::types::UrlType::Pdf));
+        assert!(supported_types.contains(&crate::types::UrlType::Notebook));
+        assert!(supported_types.contains(&crate::types::UrlType::Markup));
+        assert!(supported_types.contains(&crate::types::UrlType::Email));
+        assert!(supported_types.contains(&crate::types::UrlType::Markdown));
+
+        // Should have exactly 15 supported types
+        assert_eq!(supported_types.len(), 15);
+    }
+
+    #[test]
+    fn test_detect_url_type_integration() {
+        // Test that URL type detection works through the main API
+
+        // Test HTML URL
+        let html_result = detect_url_type("https://example.com/article.html");
+        assert!(html_result.is_ok());
+        assert_eq!(html_result.unwrap(), crate::types::UrlType::Html);
+
+        // Test Google Docs URL
+        let gdocs_result = detect_url_type("https://docs.google.com/document/d/abc123/edit");
+        assert!(gdocs_result.is_ok());
+        assert_eq!(gdocs_result.unwrap(), crate::types::UrlType::GoogleDocs);
+
+        // Test GitHub Issue URL
+        let github_result = detect_url_type("https://github.com/owner/repo/issues/123");
+        assert!(github_result.is_ok());
+        assert_eq!(github_result.unwrap(), crate::types::UrlType::GitHubIssue);
+
+        // Test invalid URL
+        let invalid_result = detect_url_type("not-a-url");
+        assert!(invalid_result.is_err());
+    }
+
+    #[test]
+    fn test_github_integration_issue_and_pr() {
+        // Test integration between URL detection and GitHub converter
+        let detector = UrlDetector::new();
+        let converter = GitHubConverter::new();
+
+        // Test GitHub issue URL
+        let issue_url = "https://github.com/microsoft/vscode/issues/12345";
+        let detected_type = detector.detect_type(issue_url).unwrap();
+        assert_eq!(detected_type, UrlType::GitHubIssue);
+
+        // Verify GitHub converter can parse the issue URL
+        let parsed_issue = converter.parse_github_url(issue_url).unwrap();
+        assert_eq!(parsed_issue.owner, "microsoft");
+        assert_eq!(parsed_issue.repo, "vscode");
+        assert_eq!(parsed_issue.number, 12345);
+
+        // Test GitHub pull request URL
+        let pr_url = "https://github.com/rust-lang/rust/pull/98765";
+        let detected_type = detector.detect_type(pr_url).unwrap();
+        assert_eq!(detected_type, UrlType::GitHubIssue);
+
+        // Verify GitHub converter can parse the PR URL
+        let parsed_pr = converter.parse_github_url(pr_url).unwrap();
+        assert_eq!(parsed_pr.owner, "rust-lang");
+        assert_eq!(parsed_pr.repo, "rust");
+        assert_eq!(parsed_pr.number, 98765);
+    }
+
+    #[test]
+    fn test_plan_steps_and_requests() {
+        let config = Config::builder()
+            .deny_host("*.ads.example.com")
+            .allow_mime_type("text/*")
+            .race_strategies(true)
+            .lint_mode(LintMode::Fix)
+            .build();
+        let md = MarkdownDown::with_config(config);
+
+        let plan = md.plan("https://example.com/article").unwrap();
+        assert_eq!(plan.url_type, UrlType::Html);
+        assert_eq!(plan.fallback, None);
+        let names: Vec<&str> = plan.steps.iter().map(|step| step.name.as_str()).collect();
+        assert_eq!(names, ["detect", "filter", "convert", "identity", "lint"]);
+        // Content rules are checked against the page's own response
+        assert_eq!(plan.estimated_requests, 1);
+
+        let plan = md
+            .plan("https://github.com/rust-lang/rust/issues/1")
+            .unwrap();
+        assert_eq!(plan.fallback.as_deref(), Some("HTML"));
+        assert!(plan.races_fallback);
+        assert_eq!(plan.estimated_requests, 3);
+        assert!(plan
+            .to_string()
+            .contains("fallback: Race HTML against the converter"));
+
+        let plan = md.plan("https://tracker.ads.example.com/page").unwrap();
+        assert!(matches!(plan.skipped, Some(SkipReason::HostDenied { .. })));
+        assert_eq!(plan.steps.len(), 2);
+        assert_eq!(plan.estimated_requests, 0);
+    }
+
+    /// Comprehensive tests for improved coverage
+    mod comprehensive_coverage_tests {
+        use super::*;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        #[test]
+        fn test_detector_getter() {
+            // Test the detector() getter method
+            let md = MarkdownDown::new();
+            let detector = md.detector();
+            
+            // Should return a valid detector that can detect URL types
+            let result = detector.detect_type("https://example.com/page.html");
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap(), UrlType::Html);
+        }
+
+        #[test]
+        fn test_registry_getter() {
+            // Test the registry() getter method
+            let md = MarkdownDown::new();
+            let registry = md.registry();
+            
+            // Should return a valid registry with converters
+            let supported_types = registry.supported_types();
+            assert!(!supported_types.is_empty());
+            assert!(supported_types.contains(&UrlType::Html));
+        }
+
+        #[test]
+        fn test_default_trait_implementation() {
+            // Test that Default trait is properly implemented
+            let md1 = MarkdownDown::new();
+            let md2 = MarkdownDown::default();
+            
+            // Both should have identical configurations
+            assert_eq!(md1.config().http.timeout, md2.config().http.timeout);
+            assert_eq!(md1.config().http.max_retries, md2.config().http.max_retries);
+            assert_eq!(md1.config().auth.github_token, md2.config().auth.github_token);
+            assert_eq!(md1.config().output.include_frontmatter, md2.config().output.include_frontmatter);
+        }
+
+        #[tokio::test]
+        async fn test_total_timeout_bounds_conversion() {
+            let mock_server = MockServer::start().await;
+
+            Mock::given(method("GET"))
+                .and(path("/slow"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string("<p>Too late</p>")
+                        .set_delay(Duration::from_secs(5)),
+                )
+                .mount(&mock_server)
+                .await;
+
+            let config = Config::builder()
+                .total_timeout(Duration::from_millis(200))
+                .build();
+            let md = MarkdownDown::with_config(config);
+            let url = format!("{}/slow", mock_server.uri());
+
+            let started = std::time::Instant::now();
+            let result = md.convert_url(&url).await;
+
+            assert!(started.elapsed() < Duration::from_secs(2));
+            match result {
+                Err(MarkdownError::EnhancedNetworkError { kind, context }) => {
+                    assert_eq!(kind, NetworkErrorKind::Timeout);
+                    assert_eq!(context.converter_type, "MarkdownDown");
+                }
+                other => panic!("Expected budget timeout, got {other:?}"),
+            }
+        }
+
+        #[tokio::test]
+        async fn test_convert_url_with_options_overrides_call() {
+            let mock_server = MockServer::start().await;
+
+            Mock::given(method("GET"))
+                .and(path("/page"))
+                .respond_with(ResponseTemplate::new(200).set_body_string("<h1>Page</h1>"))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/slow"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string("<p>Too late</p>")
+                        .set_delay(Duration::from_secs(5)),
+                )
+                .mount(&mock_server)
+                .await;
+
+            let md = MarkdownDown::new();
+            let url = format!("{}/page", mock_server.uri());
+
+            let body = md
+                .convert_url_with_options(&url, &ConversionOptions::new().strip_frontmatter(true))
+                .await
+                .unwrap();
+            assert!(body.frontmatter().is_none());
+            assert!(body.as_str().starts_with("# Page"));
+
+            let toml = md
+                .convert_url_with_options(
+                    &url,
+                    &ConversionOptions::new().frontmatter_format(FrontmatterFormat::Toml),
+                )
+                .await
+                .unwrap();
+            assert!(toml.as_str().starts_with("+++\n"));
+
+            // The instance's own settings are unchanged
+            let default = md.convert_url(&url).await.unwrap();
+            assert!(default.as_str().starts_with("---\n"));
+
+            let started = std::time::Instant::now();
+            let result = md
+                .convert_url_with_options(
+                    &format!("{}/slow", mock_server.uri()),
+                    &ConversionOptions::new().timeout(Duration::from_millis(200)),
+                )
+                .await;
+            assert!(started.elapsed() < Duration::from_secs(2));
+            assert!(matches!(
+                result,
+                Err(MarkdownError::EnhancedNetworkError {
+                    kind: NetworkErrorKind::Timeout,
+                    ..
+                })
+            ));
+        }
+
+        #[tokio::test]
+        async fn test_convert_url_sends_custom_headers() {
+            use wiremock::matchers::header;
+
+            let mock_server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/intranet"))
+                .and(header("X-Org-Token", "per-call"))
+                .and(header("X-Team", "docs"))
+                .respond_with(ResponseTemplate::new(200).set_body_string("<h1>Call</h1>"))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/intranet"))
+                .and(header("X-Org-Token", "default"))
+                .respond_with(ResponseTemplate::new(200).set_body_string("<h1>Default</h1>"))
+                .mount(&mock_server)
+                .await;
+
+            let config = Config::builder()
+                .default_header("X-Org-Token", "default")
+                .max_retries(0)
+                .build();
+            let md = MarkdownDown::with_config(config);
+            let url = format!("{}/intranet", mock_server.uri());
+
+            let markdown = md.convert_url(&url).await.unwrap();
+            assert!(markdown.content_only().contains("# Default"));
+
+            // Per-call headers replace default headers of the same name
+            let options = ConversionOptions::new()
+                .header("X-Org-Token", "per-call")
+                .header("X-Team", "docs");
+            let markdown = md.convert_url_with_options(&url, &options).await.unwrap();
+            assert!(markdown.content_only().contains("# Call"));
+        }
+
+        #[tokio::test]
+        async fn test_converted_markdown_cached_on_disk() {
+            let mock_server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/article"))
+                .respond_with(ResponseTemplate::new(200).set_body_string("<h1>Article</h1>"))
+                .expect(3)
+                .mount(&mock_server)
+                .await;
+            let dir = tempfile::tempdir().unwrap();
+            let url = format!("{}/article", mock_server.uri());
+
+            let md = MarkdownDown::new().with_cache(dir.path());
+            let first = md.convert_url(&url).await.unwrap();
+            assert!(first.content_only().contains("# Article"));
+
+            // A new instance, as in a re-run batch job, reads the cache
+            let md = MarkdownDown::new().with_cache(dir.path());
+            let second = md.convert_url(&url).await.unwrap();
+            assert_eq!(second.as_str(), first.as_str());
+
+            // Different options and a bypass both convert again
+            let options = ConversionOptions::new().strip_frontmatter(true);
+            let stripped = md.convert_url_with_options(&url, &options).await.unwrap();
+            assert!(stripped.frontmatter().is_none());
+            let options = ConversionOptions::new().bypass_cache(true);
+            md.convert_url_with_options(&url, &options).await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn test_converted_markdown_reused_when_not_modified() {
+            use crate::config::HttpCacheConfig;
+            use crate::progress::ProgressEvent;
+            use std::sync::Mutex;
+
+            let mock_server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/article"))
+                .and(wiremock::matchers::header("If-None-Match", "\"v1\""))
+                .respond_with(ResponseTemplate::new(304))
+                .up_to_n_times(1)
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/article"))
+                .and(wiremock::matchers::header("If-None-Match", "\"v1\""))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("ETag", "\"v2\"")
+                        .set_body_string("<h1>Revised</h1>"),
+                )
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/article"))
+                .and(wiremock::matchers::header("If-None-Match", "\"v2\""))
+                .respond_with(ResponseTemplate::new(304))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/article"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("ETag", "\"v1\"")
+                        .set_body_string("<h1>Article</h1>"),
+                )
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+            let url = format!("{}/article", mock_server.uri());
+
+            let converted = Arc::new(Mutex::new(0));
+            let counter = converted.clone();
+            let md = MarkdownDown::with_config(
+                Config::builder()
+                    .http_cache(HttpCacheConfig::new())
+                    .build(),
+            )
+            .with_progress_observer(Box::new(move |_: &str, event: &ProgressEvent| {
+                if *event == ProgressEvent::Converting {
+                    *counter.lock().unwrap() += 1;
+                }
+            }));
+            let first = md.convert_url(&url).await.unwrap();
+            assert!(first.content_only().contains("# Article"));
+
+            // A 304 returns the stored markdown without converting again
+            let second = md.convert_url(&url).await.unwrap();
+            assert_eq!(second.as_str(), first.as_str());
+            assert_eq!(*converted.lock().unwrap(), 1);
+
+            // A changed page is converted again
+            let third = md.convert_url(&url).await.unwrap();
+            assert!(third.content_only().contains("# Revised"));
+            assert_eq!(*converted.lock().unwrap(), 2);
+        }
+
+        #[tokio::test]
+        async fn test_progress_observer_reports_steps() {
+            use crate::progress::ProgressEvent;
+            use std::sync::Mutex;
+
+            let mock_server = MockServer::start().await;
+
+            Mock::given(method("GET"))
+                .and(path("/flaky"))
+                .respond_with(ResponseTemplate::new(503))
+                .up_to_n_times(1)
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/flaky"))
+                .respond_with(ResponseTemplate::new(200).set_body_string("<h1>Back</h1>"))
+                .mount(&mock_server)
+                .await;
+            let url = format!("{}/flaky", mock_server.uri());
+
+            let events = Arc::new(Mutex::new(Vec::new()));
+            let recorded = events.clone();
+            let md = MarkdownDown::with_config(
+                Config::builder()
+                    .retry_delay(Duration::from_millis(10))
+                    .build(),
+            )
+            .with_progress_observer(Box::new(move |url: &str, event: &ProgressEvent| {
+                recorded.lock().unwrap().push((url.to_string(), event.clone()));
+            }));
+            let markdown = md.convert_url(&url).await.unwrap();
+
+            let events = events.lock().unwrap();
+            assert!(events.iter().all(|(event_url, _)| *event_url == url));
+            let events: Vec<_> = events.iter().map(|(_, event)| event.clone()).collect();
+            assert_eq!(
+                events,
+                [
+                    ProgressEvent::Converting,
+                    ProgressEvent::Fetching { url: url.clone() },
+                    ProgressEvent::Retrying {
+                        url: url.clone(),
+                        attempt: 1
+                    },
+                    ProgressEvent::Postprocessing,
+                    ProgressEvent::Done {
+                        bytes: "<h1>Back</h1>".len() as u64,
+                        chars: markdown.as_str().len()
+                    },
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn test_race_strategies_uses_first_result() {
+            struct SlowConverter;
+
+            #[async_trait::async_trait]
+            impl Converter for SlowConverter {
+                async fn convert(&self, _url: &str) -> Result<Markdown, MarkdownError> {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    Markdown::new("# Slow".to_string())
+                }
+
+                fn name(&self) -> &'static str {
+                    "Slow"
+                }
+            }
+
+            let mock_server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/doc"))
+                .respond_with(ResponseTemplate::new(200).set_body_string("<h1>Fast</h1>"))
+                .mount(&mock_server)
+                .await;
+
+            let slow = UrlType::Custom("slow".into());
+            let md = MarkdownDown::with_config(Config::builder().race_strategies(true).build())
+                .with_url_pattern("127.0.0.1", Some("/doc"), slow.clone())
+                .with_converter(slow, Box::new(SlowConverter));
+            let url = format!("{}/doc", mock_server.uri());
+
+            let started = std::time::Instant::now();
+            let markdown = md.convert_url(&url).await.unwrap();
+
+            assert!(started.elapsed() < Duration::from_secs(2));
+            assert!(markdown.as_str().contains("Fast"));
+        }
+
+        #[tokio::test]
+        async fn test_convert_url_convenience_function() {
+            // Test the standalone convert_url function
+            let mock_server = MockServer::start().await;
+
+            let html_content = "<h1>Test Content</h1><p>This is a test.</p>";
+
+            Mock::given(method("GET"))
+                .and(path("/test-page"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(html_content))
+                .mount(&mock_server)
+                .await;
+
+            let url = format!("{}/test-page", mock_server.uri());
+            let result = convert_url(&url).await;
+
+            assert!(result.is_ok());
+            let markdown = result.unwrap();
+            assert!(markdown.as_str().contains("# Test Content"));
+            assert!(markdown.as_str().contains("This is a test"));
+        }
+
+        #[tokio::test]
+        async fn test_convert_url_with_config_convenience_function() {
+            // Test the standalone convert_url_with_config function
+            let mock_server = MockServer::start().await;
+
+            let html_content = "<h1>Custom Config Test</h1><p>Testing with custom configuration.</p>";
+
+            Mock::given(method("GET"))
+                .and(path("/custom-config-page"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(html_content))
+                .mount(&mock_server)
+                .await;
+
+            // Create custom configuration
+            let config = Config::builder()
+                .timeout_seconds(45)
+                .user_agent("TestConvenience/1.0")
+                .include_frontmatter(false)
+                .build();
+
+            let url = format!("{}/custom-config-page", mock_server.uri());
+            let result = convert_url_with_config(&url, config).await;
+
+            assert!(result.is_ok());
+            let markdown = result.unwrap();
+            assert!(markdown.as_str().contains("# Custom Config Test"));
+            assert!(markdown.as_str().contains("Testing with custom configuration"));
+            // Should not have frontmatter since we disabled it
+            assert!(!markdown.as_str().starts_with("---"));
+        }
+
+        #[tokio::test]
+        async fn test_convert_url_error_no_converter_available() {
+            // Test error path when no converter is available for URL type
+            // This is tricky to test directly, but we can test with a custom registry
+            // that has been modified to not have converters for certain types
+            
+            // For this test, we'll create a scenario where the fallback would be attempted
+            // by using a URL that should work but simulating a failure
+            let mock_server = MockServer::start().await;
+
+            // Return an error status to trigger the error handling path
+            Mock::given(method("GET"))
+                .and(path("/error-test"))
+                .respond_with(ResponseTemplate::new(500))
+                .mount(&mock_server)
+                .await;
+
+            let md = MarkdownDown::new();
+            let url = format!("{}/error-test", mock_server.uri());
+            let result = md.convert_url(&url).await;
+
+            // Should result in an error due to server error
+            assert!(result.is_err());
+        }
+
+        #[tokio::test]
+        async fn test_fallback_conversion_logic() {
+            // Test the fallback logic when primary converter fails but error is recoverable
+            let mock_server = MockServer::start().await;
+
+            // Set up a server that returns success
+            let html_content = "<h1>Fallback Test</h1><p>This should work via fallback.</p>";
+
+            Mock::given(method("GET"))
+                .and(path("/fallback-test"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(html_content))
+                .mount(&mock_server)
+                .await;
+
+            let md = MarkdownDown::new();
+            let url = format!("{}/fallback-test", mock_server.uri());
+            let result = md.convert_url(&url).await;
+
+            // Should succeed with HTML conversion
+            assert!(result.is_ok());
+            let markdown = result.unwrap();
+            assert!(markdown.as_str().contains("# Fallback Test"));
+            assert!(markdown.as_str().contains("This should work via fallback"));
+        }
+
+        #[tokio::test]
+        async fn test_convert_url_invalid_url_error() {
+            // Test convert_url with an invalid URL to trigger validation error
+            let md = MarkdownDown::new();
+            let result = md.convert_url("not-a-valid-url").await;
+
+            assert!(result.is_err());
+            match result.unwrap_err() {
+                MarkdownError::ValidationError { kind, context } => {
+                    assert_eq!(kind, crate::types::ValidationErrorKind::InvalidUrl);
+                    assert_eq!(context.url, "not-a-valid-url");
+                }
+                _ => panic!("Expected ValidationError for invalid URL"),
+            }
+        }
+
+        #[tokio::test]
+        async fn test_convert_url_malformed_url_error() {
+            // Test convert_url with a malformed URL
+            let md = MarkdownDown::new();
+            let result = md.convert_url("http://[invalid-host").await;
+
+            assert!(result.is_err());
+            // Should get a validation error for malformed URL
+            match result.unwrap_err() {
+                MarkdownError::ValidationError { kind, context } => {
+                    assert_eq!(kind, crate::types::ValidationErrorKind::InvalidUrl);
+                    assert_eq!(context.url, "http://[invalid-host");
+                }
+                _ => panic!("Expected ValidationError for malformed URL"),
+            }
+        }
+
+        #[tokio::test]
+        async fn test_successful_conversion_with_instrumentation() {
+            // Test successful conversion to ensure instrumentation line is covered
+            let mock_server = MockServer::start().await;
+
+            let html_content = "<h1>Instrumentation Test</h1><p>Testing the instrumentation decorator.</p>";
+
+            Mock::given(method("GET"))
+                .and(path("/instrumentation-test"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(html_content))
+                .mount(&mock_server)
+                .await;
+
+            let md = MarkdownDown::new();
+            let url = format!("{}/instrumentation-test", mock_server.uri());
+            let result = md.convert_url(&url).await;
+
+            assert!(result.is_ok());
+            let markdown = result.unwrap();
+            assert!(markdown.as_str().contains("# Instrumentation Test"));
+            assert!(markdown.as_str().contains("Testing the instrumentation decorator"));
+        }
+
+        #[tokio::test]
+        async fn test_conversion_includes_document_identity() {
+            let mock_server = MockServer::start().await;
+
+            Mock::given(method("GET"))
+                .and(path("/identity-test"))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_string("<h1>Identity</h1><p>Stable IDs.</p>"),
+                )
+                .mount(&mock_server)
+                .await;
+
+            let md = MarkdownDown::new();
+            let url = format!("{}/identity-test", mock_server.uri());
+            let markdown = md.convert_url(&url).await.unwrap();
+
+            assert_eq!(
+                crate::frontmatter::frontmatter_field(markdown.as_str(), "document_id"),
+                Some(identity::document_id(&url))
+            );
+            assert_eq!(
+                crate::frontmatter::frontmatter_field(markdown.as_str(), "slug"),
+                Some(identity::document_slug(&url))
+            );
+            assert!(markdown.as_str().contains("# Identity"));
+        }
+
+        #[tokio::test]
+        async fn test_convert_url_detailed_lists_links() {
+            let mock_server = MockServer::start().await;
+
+            Mock::given(method("GET"))
+                .and(path("/links-test"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    r#"<h1>Links</h1><p><a href="/about">About us</a> and <a href="https://other.org/">Other</a>.</p>"#,
+                ))
+                .mount(&mock_server)
+                .await;
+
+            let md = MarkdownDown::new();
+            let url = format!("{}/links-test", mock_server.uri());
+            let result = md.convert_url_detailed(&url).await.unwrap();
+
+            assert!(result.markdown.as_str().contains("# Links"));
+            assert_eq!(result.links.len(), 2);
+            assert_eq!(result.links[0].url, format!("{}/about", mock_server.uri()));
+            assert_eq!(result.links[0].text, "About us");
+            assert_eq!(result.links[0].kind, crate::types::LinkKind::Internal);
+            assert_eq!(result.links[1].kind, crate::types::LinkKind::External);
+
+            assert_eq!(result.sections.len(), 1);
+            let section = &result.sections[0];
+            assert_eq!((section.level, section.anchor.as_str()), (1, "links"));
+            assert!(result.markdown.as_str()[section.start..].starts_with("# Links"));
+        }
+
+        #[tokio::test]
+        async fn test_convert_url_detailed_reports_outcome() {
+            let mock_server = MockServer::start().await;
+
+            Mock::given(method("GET"))
+                .and(path("/old"))
+                .respond_with(
+                    ResponseTemplate::new(301)
+                        .insert_header("Location", format!("{}/new", mock_server.uri()).as_str()),
+                )
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/new"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    "<html><head><title>Moved Page</title></head><body>\
+                     <p>Four words live here.</p><img src=\"/missing.png\" alt=\"gone\"></body></html>",
+                ))
+                .mount(&mock_server)
+                .await;
+
+            let md = MarkdownDown::with_config(
+                Config::builder()
+                    .download_images(crate::config::ImageDownloads::InMemory)
+                    .build(),
+            );
+            let url = format!("{}/old", mock_server.uri());
+            let result = md.convert_url_detailed(&url).await.unwrap();
+
+            assert_eq!(result.url_type, crate::types::UrlType::Html);
+            assert_eq!(result.final_url, Some(format!("{}/new", mock_server.uri())));
+            assert_eq!(result.http_status, Some(200));
+            assert_eq!(result.title.as_deref(), Some("Moved Page"));
+            assert!(result.word_count >= 4);
+            assert!(result.duration > std::time::Duration::ZERO);
+            assert_eq!(result.warnings.len(), 1);
+            assert!(result.warnings[0].contains("/missing.png"));
+        }
+
+        #[tokio::test]
+        async fn test_sniffed_content_type_routes_conversion() {
+            let mock_server = MockServer::start().await;
+
+            // A ".pdf" URL that serves an HTML landing page
+            Mock::given(method("HEAD"))
+                .and(path("/paper.pdf"))
+                .respond_with(ResponseTemplate::new(200).insert_header("Content-Type", "text/html"))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/paper.pdf"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_raw("<h1>Paper</h1><p>Download below.</p>", "text/html"),
+                )
+                .mount(&mock_server)
+                .await;
+
+            let url = format!("{}/paper.pdf", mock_server.uri());
+            // Without sniffing, the page only converts through the HTML fallback
+            let result = MarkdownDown::new()
+                .convert_url_detailed(&url)
+                .await
+                .unwrap();
+            assert_eq!(result.url_type, crate::types::UrlType::Pdf);
+
+            let md = MarkdownDown::with_config(Config::builder().sniff_content_type(true).build());
+            let result = md.convert_url_detailed(&url).await.unwrap();
+            assert_eq!(result.url_type, crate::types::UrlType::Html);
+            assert!(result.markdown.as_str().contains("# Paper"));
+        }
+
+        #[tokio::test]
+        async fn test_shortened_url_expanded_before_detection() {
+            let mock_server = MockServer::start().await;
+
+            // The proxy receives the requests for both hosts
+            Mock::given(method("HEAD"))
+                .and(path("/3xYzAbc"))
+                .respond_with(ResponseTemplate::new(301).insert_header(
+                    "Location",
+                    "http://docs.google.com/document/d/abc123/edit?utm_source=share",
+                ))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("HEAD"))
+                .and(path("/document/d/abc123/edit"))
+                .respond_with(ResponseTemplate::new(200))
+                .mount(&mock_server)
+                .await;
+
+            let config = Config::builder()
+                .proxy(&mock_server.uri())
+                .proxy_from_env(false)
+                .build();
+            let md = MarkdownDown::with_config(config);
+            let (url, _) = md
+                .resolve_url("http://bit.ly/3xYzAbc".to_string())
+                .await
+                .unwrap();
+            assert_eq!(url, "http://docs.google.com/document/d/abc123/edit");
+            assert_eq!(
+                md.detector().detect_type(&url).unwrap(),
+                crate::types::UrlType::GoogleDocs
+            );
+
+            // Other URLs are left alone without a request
+            let url = "http://example.com/page".to_string();
+            assert_eq!(md.resolve_url(url.clone()).await.unwrap(), (url, None));
+        }
+
+        #[tokio::test]
+        async fn test_convert_url_parts_splits_only_in_split_mode() {
+            struct BookConverter;
+
+            #[async_trait::async_trait]
+            impl Converter for BookConverter {
+                async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+                    Markdown::new(format!("---\nsource_url: {url}\n---\n\n# One\n\n# Two\n"))
+                }
+
+                async fn convert_parts(
+                    &self,
+                    url: &str,
+                ) -> Result<Vec<SubDocument>, MarkdownError> {
+                    ["One", "Two"]
+                        .iter()
+                        .enumerate()
+                        .map(|(index, title)| {
+                            Ok(SubDocument {
+                                index: index + 1,
+                                title: Some(title.to_string()),
+                                markdown: Markdown::new(format!(
+                                    "---\nsource_url: {url}\n---\n\n# {title}\n"
+                                ))?,
+                            })
+                        })
+                        .collect()
+                }
+
+                fn name(&self) -> &'static str {
+                    "Book"
+                }
+            }
+
+            let url = "https://books.example.com/book/1";
+            let book = UrlType::Custom("book".into());
+            let md = |mode| {
+                MarkdownDown::with_config(Config::builder().sub_documents(mode).build())
+                    .with_url_pattern("books.example.com", None, book.clone())
+                    .with_converter(book.clone(), Box::new(BookConverter))
+            };
+
+            let whole = md(SubDocumentMode::Concatenate)
+                .convert_url_parts(url)
+                .await
+                .unwrap();
+            assert_eq!(whole.len(), 1);
+            assert!(whole[0].markdown.as_str().contains("# One\n\n# Two"));
+
+            let parts = md(SubDocumentMode::Split)
+                .convert_url_parts(url)
+                .await
+                .unwrap();
+            assert_eq!(parts.len(), 2);
+            let field = |index: usize, key| frontmatter_field(parts[index].markdown.as_str(), key);
+            assert!(parts[1].markdown.as_str().contains("# Two"));
+            assert_eq!(field(1, "parent_id"), Some(identity::document_id(url)));
+            assert_eq!(field(1, "document_id"), Some(identity::part_id(url, 2)));
+            assert_eq!(field(1, "part_index").as_deref(), Some("2"));
+            assert_eq!(field(1, "part_count").as_deref(), Some("2"));
+            assert_eq!(field(1, "part_title").as_deref(), Some("Two"));
+            assert_ne!(field(0, "slug"), field(1, "slug"));
+        }
+
+        #[tokio::test]
+        async fn test_redirects_and_canonical_links_route_conversion() {
+            struct WikiConverter;
+
+            #[async_trait::async_trait]
+            impl Converter for WikiConverter {
+                async fn convert(&self, url: &str) -> Result<Markdown, MarkdownError> {
+                    Markdown::new(format!("---\nsource_url: {url}\n---\n\n# Wiki page\n"))
+                }
+
+                fn name(&self) -> &'static str {
+                    "Wiki"
+                }
+            }
+
+            let mock_server = MockServer::start().await;
+            let wiki_url = format!("{}/wiki/page", mock_server.uri());
+            Mock::given(method("HEAD"))
+                .and(path("/share/xyz"))
+                .respond_with(ResponseTemplate::new(302).insert_header("Location", "/wiki/page"))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("HEAD"))
+                .and(path("/wiki/page"))
+                .respond_with(ResponseTemplate::new(200))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/mirror"))
+                .respond_with(ResponseTemplate::new(200).set_body_raw(
+                    "<html><head><title>Mirror</title>\
+                     <link rel=\"canonical\" href=\"/wiki/page\"></head>\
+                     <body><h1>Mirrored copy</h1></body></html>",
+                    "text/html",
+                ))
+                .mount(&mock_server)
+                .await;
 
-/// Utility function to detect the type of a URL without converting it.
-///
-/// # Arguments
-///
-/// * `url` - The URL to analyze
-///
-/// # Returns
-///
-/// Returns the detected URL type or an error.
-///
-/// # Examples
-///
-/// ```rust
-/// use markdowndown::{detect_url_type, types::UrlType};
-///
-/// # fn example() -> Result<(), markdowndown::types::MarkdownError> {
-/// let url_type = detect_url_type("https://docs.google.com/document/d/123/edit")?;
-/// assert_eq!(url_type, UrlType::GoogleDocs);
-/// # Ok(())
-/// # }
-/// ```
-pub fn detect_url_type(url: &str) -> Result<crate::types::UrlType, MarkdownError> {
-    let detector = UrlDetector::new();
-    detector.detect_type(url)
-}
+            let wiki = UrlType::Custom("wiki".into());
+            let config = Config::builder().resolve_redirects(true).build();
+            let md = MarkdownDown::with_config(config)
+                .with_url_pattern("127.0.0.1", Some("/wiki/"), wiki.clone())
+                .with_converter(wiki.clone(), Box::new(WikiConverter));
 
-// Re-export main API items for convenience
-pub use config::Config;
-pub use converters::{Converter, HtmlConverter};
-pub use types::{Frontmatter, Url};
+            // A shared link that redirects to the wiki
+            let url = format!("{}/share/xyz", mock_server.uri());
+            let result = md.convert_url_detailed(&url).await.unwrap();
+            assert!(result.markdown.as_str().contains("# Wiki page"));
+            assert_eq!(
+                frontmatter_field(result.markdown.as_str(), "canonical_url"),
+                Some(wiki_url.clone())
+            );
 
-/// Library version information
-pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+            // A mirror whose canonical link points to the wiki
+            let url = format!("{}/mirror", mock_server.uri());
+            let markdown = md.convert_url(&url).await.unwrap();
+            assert!(markdown.as_str().contains("# Wiki page"));
+            assert_eq!(
+                frontmatter_field(markdown.as_str(), "canonical_url"),
+                Some(wiki_url.clone())
+            );
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::converters::GitHubConverter;
-    use crate::detection::UrlDetector;
-    use crate::types::UrlType;
-    use std::time::Duration;
+            // Without resolving, the mirror is converted as HTML
+            let markdown = MarkdownDown::new().convert_url(&url).await.unwrap();
+            assert!(markdown.as_str().contains("Mirrored copy"));
+            assert_eq!(
+                frontmatter_field(markdown.as_str(), "canonical_url"),
+                Some(wiki_url)
+            );
+        }
 
-    #[test]
-    fn test_version_available() {
-        // Verify version follows semantic versioning pattern (major.minor.patch)
-        assert!(VERSION.chars().any(|c| c.is_ascii_digit()));
-        assert!(VERSION.contains('.'));
-        // Basic format validation - should have at least one dot for major.minor
-        let parts: Vec<&str> = VERSION.split('.').collect();
-        assert!(
-            parts.len() >= 2,
-            "Version should have at least major.minor format"
-        );
-    }
+        #[tokio::test]
+        async fn test_convert_url_checks_content_rules_against_response() {
+            let mock_server = MockServer::start().await;
+            // No HEAD mocks: the rules must hold without a HEAD request
+            Mock::given(method("GET"))
+                .and(path("/report.pdf"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_raw(b"%PDF-1.4".to_vec(), "application/pdf"),
+                )
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/page"))
+                .respond_with(ResponseTemplate::new(200).set_body_raw(
+                    "<html><body><p>A page longer than the limit.</p></body></html>",
+                    "text/html",
+                ))
+                .mount(&mock_server)
+                .await;
 
-    #[test]
-    fn test_markdowndown_with_default_config() {
-        // Test that MarkdownDown can be created with default configuration
-        let md = MarkdownDown::new();
+            let pdf_url = format!("{}/report.pdf", mock_server.uri());
+            let md = MarkdownDown::with_config(
+                Config::builder().deny_mime_type("application/pdf").build(),
+            );
+            let error = md.convert_url(&pdf_url).await.unwrap_err();
+            assert!(error.is_skip());
+            assert_eq!(error.context().unwrap().url, pdf_url);
+            assert!(error.to_string().contains("application/pdf"));
 
-        // Verify config is stored and accessible
-        let config = md.config();
-        assert_eq!(config.http.timeout, Duration::from_secs(30));
-        assert_eq!(config.http.max_retries, 3);
-        assert_eq!(config.http.retry_delay, Duration::from_secs(1));
-        assert_eq!(config.http.max_redirects, 10);
-        assert!(config.auth.github_token.is_none());
-        assert!(config.auth.office365_token.is_none());
-        assert!(config.auth.google_api_key.is_none());
-        assert!(config.output.include_frontmatter);
-        assert_eq!(config.output.max_consecutive_blank_lines, 2);
-    }
+            let page_url = format!("{}/page", mock_server.uri());
+            let md = MarkdownDown::with_config(Config::builder().max_content_length(10).build());
+            let error = md.convert_url(&page_url).await.unwrap_err();
+            assert!(matches!(
+                &error,
+                MarkdownError::Skipped(skip)
+                    if matches!(skip.reason, SkipReason::TooLarge { limit: 10, .. })
+            ));
 
-    #[test]
-    fn test_markdowndown_with_custom_config() {
-        // Test that MarkdownDown respects custom configuration
-        let config = Config::builder()
-            .timeout_seconds(60)
-            .user_agent("TestApp/1.0")
-            .max_retries(5)
-            .github_token("test_token")
-            .include_frontmatter(false)
-            .max_consecutive_blank_lines(1)
-            .build();
+            // Invalid patterns are compiled once and reported by every conversion
+            let md = MarkdownDown::with_config(Config::builder().deny_path("(unclosed").build());
+            assert!(matches!(
+                md.convert_url(&page_url).await,
+                Err(MarkdownError::ConfigurationError { .. })
+            ));
+        }
 
-        let md = MarkdownDown::with_config(config);
+        #[tokio::test]
+        async fn test_convert_url_adds_excerpt_for_non_html_documents() {
+            let mock_server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/notes.md"))
+                .respond_with(ResponseTemplate::new(200).set_body_raw(
+                    "# Notes\n\nFirst line here. Second line follows. Third.\n",
+                    "text/markdown",
+                ))
+                .mount(&mock_server)
+                .await;
+            let url = format!("{}/notes.md", mock_server.uri());
 
-        // Verify custom config is stored
-        let stored_config = md.config();
-        assert_eq!(stored_config.http.timeout, Duration::from_secs(60));
-        assert_eq!(stored_config.http.user_agent, "TestApp/1.0");
-        assert_eq!(stored_config.http.max_retries, 5);
-        assert_eq!(
-            stored_config.auth.github_token,
-            Some("test_token".to_string())
-        );
-        assert!(!stored_config.output.include_frontmatter);
-        assert_eq!(stored_config.output.max_consecutive_blank_lines, 1);
-    }
+            let md = MarkdownDown::with_config(Config::builder().excerpt_sentences(2).build());
+            let markdown = md.convert_url(&url).await.unwrap();
+            assert_eq!(
+                frontmatter_field(markdown.as_str(), "excerpt"),
+                Some("First line here. Second line follows.".to_string())
+            );
+        }
 
-    #[test]
-    fn test_config_builder_fluent_interface() {
-        // Test that the config builder's fluent interface works correctly
-        let config = Config::builder()
-            .github_token("ghp_test_token")
-            .office365_token("office_token")
-            .google_api_key("google_key")
-            .timeout_seconds(45)
-            .user_agent("IntegrationTest/2.0")
-            .max_retries(3)
-            .include_frontmatter(true)
-            .custom_frontmatter_field("project", "markdowndown")
-            .custom_frontmatter_field("version", "test")
-            .normalize_whitespace(false)
-            .max_consecutive_blank_lines(3)
-            .build();
+        #[tokio::test]
+        async fn test_date_window_applies_without_frontmatter() {
+            let mock_server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/old"))
+                .respond_with(ResponseTemplate::new(200).set_body_raw(
+                    r#"<html><head><meta property="article:published_time" content="2019-03-01T00:00:00Z"></head><body><p>An old article.</p></body></html>"#,
+                    "text/html",
+                ))
+                .mount(&mock_server)
+                .await;
+            let url = format!("{}/old", mock_server.uri());
+            let after = "2023-01-01T00:00:00Z".parse().unwrap();
 
-        // Verify all custom settings
-        assert_eq!(config.auth.github_token, Some("ghp_test_token".to_string()));
-        assert_eq!(
-            config.auth.office365_token,
-            Some("office_token".to_string())
-        );
-        assert_eq!(config.auth.google_api_key, Some("google_key".to_string()));
-        assert_eq!(config.http.timeout, Duration::from_secs(45));
-        assert_eq!(config.http.user_agent, "IntegrationTest/2.0");
-        assert_eq!(config.http.max_retries, 3);
-        assert!(config.output.include_frontmatter);
-        assert_eq!(config.output.custom_frontmatter_fields.len(), 2);
-        assert_eq!(
-            config.output.custom_frontmatter_fields[0],
-            ("project".to_string(), "markdowndown".to_string())
-        );
-        assert_eq!(
-            config.output.custom_frontmatter_fields[1],
-            ("version".to_string(), "test".to_string())
-        );
-        assert!(!config.output.normalize_whitespace);
-        assert_eq!(config.output.max_consecutive_blank_lines, 3);
-    }
+            let md = MarkdownDown::with_config(
+                Config::builder()
+                    .modified_after(after)
+                    .include_frontmatter(false)
+                    .build(),
+            );
+            let error = md.convert_url(&url).await.unwrap_err();
+            assert!(matches!(
+                &error,
+                MarkdownError::Skipped(skip) if matches!(skip.reason, SkipReason::TooOld { .. })
+            ));
 
-    #[test]
-    fn test_config_from_default() {
-        // Test that Config::default() produces expected defaults
-        let config = Config::default();
+            // Flagged documents cannot be marked without frontmatter, so they warn instead
+            let md = MarkdownDown::with_config(
+                Config::builder()
+                    .modified_after(after)
+                    .date_window_action(DateWindowAction::Flag)
+                    .include_frontmatter(false)
+                    .build(),
+            );
+            let result = md.convert_url_detailed(&url).await.unwrap();
+            assert!(!result.markdown.as_str().contains("date_window"));
+            assert!(result
+                .warnings
+                .iter()
+                .any(|warning| warning.contains("date window")));
+        }
 
-        // HTTP config defaults
-        assert_eq!(config.http.timeout, Duration::from_secs(30));
-        assert!(config.http.user_agent.starts_with("markdowndown/"));
-        assert_eq!(config.http.max_retries, 3);
-        assert_eq!(config.http.retry_delay, Duration::from_secs(1));
-        assert_eq!(config.http.max_redirects, 10);
+        #[tokio::test]
+        async fn test_convert_url_falls_back_to_wayback_snapshot() {
+            let mock_server = MockServer::start().await;
+            let gone_url = format!("{}/gone", mock_server.uri());
+            Mock::given(method("GET"))
+                .and(path("/gone"))
+                .respond_with(ResponseTemplate::new(404))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/wayback/available"))
+                .and(wiremock::matchers::query_param("url", gone_url.as_str()))
+                .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                    r#"{{"archived_snapshots": {{"closest": {{"available": true, "status": "200",
+                    "url": "{}/web/20230506070809/{gone_url}", "timestamp": "20230506070809"}}}}}}"#,
+                    mock_server.uri()
+                )))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path(format!("/web/20230506070809id_/{gone_url}")))
+                .respond_with(ResponseTemplate::new(200).set_body_raw(
+                    "<html><body><h1>Archived page</h1><p>Kept by the archive.</p></body></html>",
+                    "text/html",
+                ))
+                .mount(&mock_server)
+                .await;
 
-        // Auth config defaults
-        assert!(config.auth.github_token.is_none());
-        assert!(config.auth.office365_token.is_none());
-        assert!(config.auth.google_api_key.is_none());
+            // Without the fallback, the dead link fails
+            let error = MarkdownDown::new()
+                .convert_url(&gone_url)
+                .await
+                .unwrap_err();
+            assert!(error.is_dead_link());
 
-        // Output config defaults
-        assert!(config.output.include_frontmatter);
-        assert!(config.output.custom_frontmatter_fields.is_empty());
-        assert!(config.output.normalize_whitespace);
-        assert_eq!(config.output.max_consecutive_blank_lines, 2);
-    }
+            let wayback_config = Config::builder()
+                .wayback_api_url(format!("{}/wayback/available", mock_server.uri()))
+                .build();
+            let markdown = MarkdownDown::with_config(wayback_config.clone())
+                .convert_url(&gone_url)
+                .await
+                .unwrap();
+            assert!(markdown.as_str().contains("# Archived page"));
+            assert_eq!(
+                frontmatter_field(markdown.as_str(), "archived_from"),
+                Some(gone_url.clone())
+            );
+            assert_eq!(
+                frontmatter_field(markdown.as_str(), "snapshot_timestamp").as_deref(),
+                Some("2023-05-06T07:08:09+00:00")
+            );
+            let result = MarkdownDown::with_config(wayback_config.clone())
+                .convert_url_detailed(&gone_url)
+                .await
+                .unwrap();
+            assert_eq!(
+                result.final_url,
+                Some(format!(
+                    "{}/web/20230506070809id_/{gone_url}",
+                    mock_server.uri()
+                ))
+            );
+            assert_eq!(result.http_status, Some(200));
 
-    #[test]
-    fn test_supported_url_types() {
-        // Test that MarkdownDown reports supported URL types correctly
-        let md = MarkdownDown::new();
-        let supported_types = md.supported_types();
+            // Pages that were never archived keep their original error
+            let config = Config::builder()
+                .wayback_api_url(format!("{}/missing/available", mock_server.uri()))
+                .build();
+            let error = MarkdownDown::with_config(config)
+                .convert_url(&gone_url)
+                .await
+                .unwrap_err();
+            assert!(error.is_dead_link());
+        }
 
-        // Should support at least these URL types
-        assert!(supported_types.contains(&crate::types::UrlType::Html));
-        assert!(supported_types.contains(&crate::types::UrlType::GoogleDocs));
-        assert!(supported_types.contains(&crate::types::UrlType::GitHubIssue));
-        assert!(supported_types.contains(&crate::types::UrlType::LocalFile));
+        #[tokio::test]
+        async fn test_convert_url_detailed_profiles_stages() {
+            let mock_server = MockServer::start().await;
+            let body = "<h1>Profiled</h1><p>Timing each stage.</p>";
 
-        // Should have exactly 4 supported types
-        assert_eq!(supported_types.len(), 4);
-    }
+            Mock::given(method("GET"))
+                .and(path("/profile-test"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string(body)
+                        .set_delay(std::time::Duration::from_millis(50)),
+                )
+                .mount(&mock_server)
+                .await;
+            let url = format!("{}/profile-test", mock_server.uri());
 
-    #[test]
-    fn test_detect_url_type_integration() {
-        // Test that URL type detection works through the main API
+            let unprofiled = MarkdownDown::new().convert_url_detailed(&url).await.unwrap();
+            assert!(unprofiled.profile.is_none());
 
-        // Test HTML URL
-        let html_result = detect_url_type("https://example.com/article.html");
-        assert!(html_result.is_ok());
-        assert_eq!(html_result.unwrap(), crate::types::UrlType::Html);
+            let config = Config::builder().profiling(true).build();
+            let result = MarkdownDown::with_config(config)
+                .convert_url_detailed(&url)
+                .await
+                .unwrap();
+            let profile = result.profile.unwrap();
+            assert_eq!(profile.fetch.calls, 1);
+            assert_eq!(profile.fetch.bytes, body.len() as u64);
+            assert!(profile.fetch.duration >= std::time::Duration::from_millis(50));
+            assert_eq!(profile.preprocess.calls, 1);
+            assert_eq!(profile.convert.calls, 1);
+            // The HTML converter's markdown cleanup and the pipeline steps after it
+            assert_eq!(profile.postprocess.calls, 2);
+            // Which stage is slowest depends on the machine, so check it against the measurements
+            let slowest = profile.stage(profile.slowest_stage()).duration;
+            let stages = [
+                &profile.fetch,
+                &profile.preprocess,
+                &profile.convert,
+                &profile.postprocess,
+            ];
+            assert!(stages.iter().all(|stage| stage.duration <= slowest));
+            assert!(profile.total >= profile.fetch.duration);
+        }
 
-        // Test Google Docs URL
-        let gdocs_result = detect_url_type("https://docs.google.com/document/d/abc123/edit");
-        assert!(gdocs_result.is_ok());
-        assert_eq!(gdocs_result.unwrap(), crate::types::UrlType::GoogleDocs);
+        #[tokio::test]
+        async fn test_convert_url_detailed_downloads_images() {
+            let mock_server = MockServer::start().await;
+            let png = b"\x89PNG\r\n\x1a\nimage data".to_vec();
 
-        // Test GitHub Issue URL
-        let github_result = detect_url_type("https://github.com/owner/repo/issues/123");
-        assert!(github_result.is_ok());
-        assert_eq!(github_result.unwrap(), crate::types::UrlType::GitHubIssue);
+            Mock::given(method("GET"))
+                .and(path("/gallery"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    "<h1>Gallery</h1><p>Our logo: <img src=\"/logo.png\" alt=\"Logo\"></p>",
+                ))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/logo.png"))
+                .respond_with(ResponseTemplate::new(200).set_body_bytes(png.clone()))
+                .mount(&mock_server)
+                .await;
+            let url = format!("{}/gallery", mock_server.uri());
 
-        // Test invalid URL
-        let invalid_result = detect_url_type("not-a-url");
-        assert!(invalid_result.is_err());
-    }
+            let config = Config::builder()
+                .download_images(crate::config::ImageDownloads::InMemory)
+                .build();
+            let md = MarkdownDown::with_config(config);
+            let plan = md.plan(&url).unwrap();
+            assert!(plan.steps.iter().any(|step| step.name == "download-images"));
+            let result = md.convert_url_detailed(&url).await.unwrap();
 
-    #[test]
-    fn test_github_integration_issue_and_pr() {
-        // Test integration between URL detection and GitHub converter
-        let detector = UrlDetector::new();
-        let converter = GitHubConverter::new();
+            assert_eq!(result.assets.len(), 1);
+            let asset = &result.assets[0];
+            assert_eq!(asset.source_url, format!("{}/logo.png", mock_server.uri()));
+            assert_eq!(asset.bytes.as_ref(), png.as_slice());
+            assert!(result
+                .markdown
+                .as_str()
+                .contains(&format!("![Logo]({})", asset.path)));
+        }
+
+        #[tokio::test]
+        async fn test_convert_url_lint_modes() {
+            let mock_server = MockServer::start().await;
+
+            Mock::given(method("GET"))
+                .and(path("/lint-test"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    "<h1>Title</h1><p>Docs live at https://example.com/docs today.</p>",
+                ))
+                .mount(&mock_server)
+                .await;
+            let url = format!("{}/lint-test", mock_server.uri());
+
+            let warn = MarkdownDown::with_config(
+                Config::builder().lint_mode(crate::config::LintMode::Warn).build(),
+            );
+            let result = warn.convert_url_detailed(&url).await.unwrap();
+            assert!(result.markdown.as_str().contains("at https://example.com/docs today"));
+            assert_eq!(result.lint_warnings.len(), 1);
+            assert_eq!(
+                result.lint_warnings[0].rule,
+                crate::config::LintRule::NoBareUrls
+            );
+
+            let fix = MarkdownDown::with_config(
+                Config::builder().lint_mode(crate::config::LintMode::Fix).build(),
+            );
+            let result = fix.convert_url_detailed(&url).await.unwrap();
+            assert!(result.markdown.as_str().contains("at <https://example.com/docs> today"));
+            assert!(result.lint_warnings.is_empty());
+        }
 
-        // Test GitHub issue URL
-        let issue_url = "https://github.com/microsoft/vscode/issues/12345";
-        let detected_type = detector.detect_type(issue_url).unwrap();
-        assert_eq!(detected_type, UrlType::GitHubIssue);
+        #[test]
+        fn test_convert_html_without_fetching() {
+            let md = MarkdownDown::with_config(Config::builder().summarize_forms(true).build());
+            let html = "<html><head><title>Saved Page</title></head><body><h1>Saved</h1><p>Offline copy.</p><form><input name=\"q\"></form></body></html>";
 
-        // Verify GitHub converter can parse the issue URL
-        let parsed_issue = converter.parse_github_url(issue_url).unwrap();
-        assert_eq!(parsed_issue.owner, "microsoft");
-        assert_eq!(parsed_issue.repo, "vscode");
-        assert_eq!(parsed_issue.number, 12345);
+            let markdown = md
+                .convert_html(html, Some("https://example.com/saved"))
+                .unwrap();
+            assert_eq!(
+                frontmatter_field(markdown.as_str(), "title"),
+                Some("Saved Page".to_string())
+            );
+            assert!(frontmatter_field(markdown.as_str(), "document_id").is_some());
+            assert!(markdown.as_str().contains("Offline copy."));
+            assert!(markdown.as_str().contains("q (text)"));
 
-        // Test GitHub pull request URL
-        let pr_url = "https://github.com/rust-lang/rust/pull/98765";
-        let detected_type = detector.detect_type(pr_url).unwrap();
-        assert_eq!(detected_type, UrlType::GitHubIssue);
+            let body_only = md.convert_html(html, None).unwrap();
+            assert!(body_only.frontmatter().is_none());
+            assert!(body_only.as_str().contains("Offline copy."));
 
-        // Verify GitHub converter can parse the PR URL
-        let parsed_pr = converter.parse_github_url(pr_url).unwrap();
-        assert_eq!(parsed_pr.owner, "rust-lang");
-        assert_eq!(parsed_pr.repo, "rust");
-        assert_eq!(parsed_pr.number, 98765);
-    }
+            assert!(md.convert_html(html, Some("not a url")).is_err());
+            assert!(md.convert_html("  ", None).is_err());
+        }
 
-    /// Comprehensive tests for improved coverage
-    mod comprehensive_coverage_tests {
-        use super::*;
-        use wiremock::matchers::{method, path};
-        use wiremock::{Mock, MockServer, ResponseTemplate};
+        #[test]
+        fn test_convert_html_extracts_language() {
+            let md = MarkdownDown::with_config(Config::builder().extract_language("en").build());
+            let html = "<html><body>\
+                <p>The policy applies to all of the staff.</p>\
+                <p>La politique s'applique à tout le personnel.</p>\
+                <p>It is reviewed every year by the board.</p>\
+                <p>Elle est revue chaque année par le conseil.</p>\
+                </body></html>";
+
+            let markdown = md.convert_html(html, None).unwrap();
+            assert!(markdown.as_str().contains("reviewed every year"));
+            assert!(!markdown.as_str().contains("personnel"));
+            assert!(!markdown.as_str().contains("conseil"));
+        }
 
         #[test]
-        fn test_detector_getter() {
-            // Test the detector() getter method
-            let md = MarkdownDown::new();
-            let detector = md.detector();
-            
-            // Should return a valid detector that can detect URL types
-            let result = detector.detect_type("https://example.com/page.html");
-            assert!(result.is_ok());
-            assert_eq!(result.unwrap(), UrlType::Html);
+        fn test_convert_html_marks_right_to_left_text() {
+            let md = MarkdownDown::with_config(
+                Config::builder()
+                    .direction_markers(DirectionMarkers::Unicode)
+                    .build(),
+            );
+            let html = "<html><body><h1>מדיניות</h1><p>זהו מסמך בעברית.</p></body></html>";
+
+            let markdown = md
+                .convert_html(html, Some("https://example.com/he"))
+                .unwrap();
+            assert_eq!(
+                frontmatter_field(markdown.as_str(), "direction"),
+                Some("rtl".to_string())
+            );
+            assert!(markdown.as_str().contains("# \u{200F}מדיניות"));
+            assert!(markdown.as_str().contains("\u{200F}זהו מסמך בעברית."));
         }
 
         #[test]
-        fn test_registry_getter() {
-            // Test the registry() getter method
-            let md = MarkdownDown::new();
-            let registry = md.registry();
-            
-            // Should return a valid registry with converters
-            let supported_types = registry.supported_types();
-            assert!(!supported_types.is_empty());
-            assert!(supported_types.contains(&UrlType::Html));
+        fn test_convert_html_strips_tracking_params() {
+            let html = "<p><a href=\"https://example.com/post?id=7&amp;utm_source=feed\">Post</a></p>";
+
+            let kept = MarkdownDown::new().convert_html(html, None).unwrap();
+            assert!(kept.as_str().contains("utm_source=feed"));
+
+            let md = MarkdownDown::with_config(Config::builder().strip_tracking_params(true).build());
+            let markdown = md.convert_html(html, None).unwrap();
+            assert!(markdown.as_str().contains("[Post](https://example.com/post?id=7)"));
         }
 
         #[test]
-        fn test_default_trait_implementation() {
-            // Test that Default trait is properly implemented
-            let md1 = MarkdownDown::new();
-            let md2 = MarkdownDown::default();
-            
-            // Both should have identical configurations
-            assert_eq!(md1.config().http.timeout, md2.config().http.timeout);
-            assert_eq!(md1.config().http.max_retries, md2.config().http.max_retries);
-            assert_eq!(md1.config().auth.github_token, md2.config().auth.github_token);
-            assert_eq!(md1.config().output.include_frontmatter, md2.config().output.include_frontmatter);
+        fn test_convert_html_writes_toml_frontmatter() {
+            let html = "<html><head><title>Release Notes</title></head><body><p>Shipped.</p></body></html>";
+            let md = MarkdownDown::with_config(
+                Config::builder()
+                    .frontmatter_format(FrontmatterFormat::Toml)
+                    .build(),
+            );
+
+            let markdown = md
+                .convert_html(html, Some("https://example.com/notes"))
+                .unwrap();
+
+            assert!(markdown.as_str().starts_with("+++\n"));
+            assert!(markdown.as_str().contains("title = \"Release Notes\""));
+            assert!(markdown.as_str().contains("slug = \"example-com-notes\""));
+            assert!(markdown.frontmatter().unwrap().ends_with("+++\n"));
+            assert!(markdown.content_only().contains("Shipped."));
         }
 
         #[tokio::test]
-        async fn test_convert_url_convenience_function() {
-            // Test the standalone convert_url function
+        async fn test_convert_url_applies_frontmatter_provider() {
             let mock_server = MockServer::start().await;
 
-            let html_content = "<h1>Test Content</h1><p>This is a test.</p>";
-
             Mock::given(method("GET"))
-                .and(path("/test-page"))
-                .respond_with(ResponseTemplate::new(200).set_body_string(html_content))
+                .and(path("/posts/hello"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    "<html><head><title>Hello</title></head><body><p>Hi there.</p></body></html>",
+                ))
                 .mount(&mock_server)
                 .await;
+            let url = format!("{}/posts/hello", mock_server.uri());
 
-            let url = format!("{}/test-page", mock_server.uri());
-            let result = convert_url(&url).await;
+            let md = MarkdownDown::with_config(
+                Config::builder()
+                    .frontmatter_format(FrontmatterFormat::Json)
+                    .build(),
+            )
+            .with_frontmatter_provider(Box::new(
+                |generated: &serde_yaml::Mapping, _: &str| {
+                    let mut meta = serde_yaml::Mapping::new();
+                    meta.insert("id".into(), generated["document_id"].clone());
+                    let mut fields = serde_yaml::Mapping::new();
+                    fields.insert("heading".into(), generated["title"].clone());
+                    fields.insert("meta".into(), serde_yaml::Value::Mapping(meta));
+                    fields
+                },
+            ));
+            let markdown = md.convert_url(&url).await.unwrap();
 
-            assert!(result.is_ok());
-            let markdown = result.unwrap();
-            assert!(markdown.as_str().contains("# Test Content"));
-            assert!(markdown.as_str().contains("This is a test"));
+            assert!(markdown.as_str().starts_with("{\n  \"heading\": \"Hello\",\n  \"meta\": {"));
+            assert!(frontmatter_field(markdown.as_str(), "source_url").is_none());
+            assert!(markdown.content_only().contains("Hi there."));
+            let plan = md.plan(&url).unwrap();
+            assert!(plan.steps.iter().any(|step| step.name == "frontmatter-provider"));
         }
 
         #[tokio::test]
-        async fn test_convert_url_with_config_convenience_function() {
-            // Test the standalone convert_url_with_config function
+        async fn test_convert_url_applies_link_rewriters() {
+            use crate::types::LinkKind;
+
             let mock_server = MockServer::start().await;
 
-            let html_content = "<h1>Custom Config Test</h1><p>Testing with custom configuration.</p>";
+            Mock::given(method("GET"))
+                .and(path("/wiki/Home"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    "<h1>Home</h1><p>Read <a href=\"/wiki/Setup\">Setup</a> and \
+                     <a href=\"https://other.org/\">elsewhere</a>.</p>",
+                ))
+                .mount(&mock_server)
+                .await;
+            let url = format!("{}/wiki/Home", mock_server.uri());
+
+            let md = MarkdownDown::new()
+                .with_link_rewriter(Box::new(|url: &str, kind: LinkKind| {
+                    let page = url.rsplit_once("/wiki/")?.1;
+                    (kind == LinkKind::Internal).then(|| format!("{page}.md"))
+                }))
+                .with_link_rewriter(Box::new(|_: &str, kind: LinkKind| {
+                    (kind == LinkKind::External).then(|| "external".to_string())
+                }));
+            let markdown = md.convert_url(&url).await.unwrap();
+
+            assert!(markdown.as_str().contains("[Setup](Setup.md)"));
+            assert!(markdown.as_str().contains("[elsewhere](external)"));
+            let plan = md.plan(&url).unwrap();
+            assert!(plan.steps.iter().any(|step| step.name == "rewrite-links"));
+        }
+
+        #[tokio::test]
+        async fn test_convert_sitemap_converts_selected_pages() {
+            let mock_server = MockServer::start().await;
+            let base = mock_server.uri();
 
             Mock::given(method("GET"))
-                .and(path("/custom-config-page"))
-                .respond_with(ResponseTemplate::new(200).set_body_string(html_content))
+                .and(path("/sitemap.xml"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                    "<urlset><url><loc>{base}/docs/a</loc></url>\
+                     <url><loc>{base}/docs/missing</loc></url>\
+                     <url><loc>{base}/blog/b</loc></url></urlset>"
+                )))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/docs/a"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    "<html><head><title>Page A</title></head><body><p>Alpha page.</p></body></html>",
+                ))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/docs/missing"))
+                .respond_with(ResponseTemplate::new(404))
                 .mount(&mock_server)
                 .await;
 
-            // Create custom configuration
-            let config = Config::builder()
-                .timeout_seconds(45)
-                .user_agent("TestConvenience/1.0")
-                .include_frontmatter(false)
-                .build();
+            let output_dir = tempfile::TempDir::new().unwrap();
+            let options = SitemapOptions::new()
+                .include("/docs/*")
+                .delay(std::time::Duration::ZERO)
+                .output_dir(output_dir.path());
+            let config = Config::builder().max_retries(0).build();
+            let pages = MarkdownDown::with_config(config)
+                .convert_sitemap(&format!("{base}/sitemap.xml"), &options)
+                .await
+                .unwrap();
 
-            let url = format!("{}/custom-config-page", mock_server.uri());
-            let result = convert_url_with_config(&url, config).await;
+            assert_eq!(pages.len(), 2);
+            let page = pages[&format!("{base}/docs/a")].as_ref().unwrap();
+            assert!(page.as_str().contains("Alpha page."));
+            assert!(pages[&format!("{base}/docs/missing")].is_err());
 
-            assert!(result.is_ok());
-            let markdown = result.unwrap();
-            assert!(markdown.as_str().contains("# Custom Config Test"));
-            assert!(markdown.as_str().contains("Testing with custom configuration"));
-            // Should not have frontmatter since we disabled it
-            assert!(!markdown.as_str().starts_with("---"));
+            let slug = identity::document_slug(&format!("{base}/docs/a"));
+            let written = output_dir.path().join(format!("{slug}.md"));
+            assert!(std::fs::read_to_string(written)
+                .unwrap()
+                .contains("Alpha page."));
         }
 
         #[tokio::test]
-        async fn test_convert_url_error_no_converter_available() {
-            // Test error path when no converter is available for URL type
-            // This is tricky to test directly, but we can test with a custom registry
-            // that has been modified to not have converters for certain types
-            
-            // For this test, we'll create a scenario where the fallback would be attempted
-            // by using a URL that should work but simulating a failure
+        async fn test_convert_sitemap_with_adaptive_concurrency() {
             let mock_server = MockServer::start().await;
+            let base = mock_server.uri();
 
-            // Return an error status to trigger the error handling path
             Mock::given(method("GET"))
-                .and(path("/error-test"))
-                .respond_with(ResponseTemplate::new(500))
+                .and(path("/sitemap.xml"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                    "<urlset><url><loc>{base}/a</loc></url>\
+                     <url><loc>{base}/b</loc></url>\
+                     <url><loc>{base}/busy</loc></url></urlset>"
+                )))
+                .mount(&mock_server)
+                .await;
+            for page in ["/a", "/b"] {
+                Mock::given(method("GET"))
+                    .and(path(page))
+                    .respond_with(ResponseTemplate::new(200).set_body_string(
+                        "<html><body><p>Page body.</p></body></html>",
+                    ))
+                    .mount(&mock_server)
+                    .await;
+            }
+            Mock::given(method("GET"))
+                .and(path("/busy"))
+                .respond_with(ResponseTemplate::new(503))
                 .mount(&mock_server)
                 .await;
 
-            let md = MarkdownDown::new();
-            let url = format!("{}/error-test", mock_server.uri());
-            let result = md.convert_url(&url).await;
+            let options = SitemapOptions::new()
+                .concurrency(3)
+                .adaptive_concurrency(true)
+                .delay(std::time::Duration::ZERO);
+            let config = Config::builder().max_retries(0).build();
+            let pages = MarkdownDown::with_config(config)
+                .convert_sitemap(&format!("{base}/sitemap.xml"), &options)
+                .await
+                .unwrap();
 
-            // Should result in an error due to server error
-            assert!(result.is_err());
+            assert_eq!(pages.len(), 3);
+            assert!(pages[&format!("{base}/a")].is_ok());
+            assert!(pages[&format!("{base}/b")].is_ok());
+            assert!(pages[&format!("{base}/busy")].is_err());
         }
 
         #[tokio::test]
-        async fn test_fallback_conversion_logic() {
-            // Test the fallback logic when primary converter fails but error is recoverable
+        async fn test_crawl_follows_links_within_depth_and_robots() {
             let mock_server = MockServer::start().await;
+            let base = mock_server.uri();
+            let page = |body: &str| {
+                ResponseTemplate::new(200)
+                    .set_body_string(format!("<html><body><p>{body}</p></body></html>"))
+            };
 
-            // Set up a server that returns success
-            let html_content = "<h1>Fallback Test</h1><p>This should work via fallback.</p>";
+            Mock::given(method("GET"))
+                .and(path("/robots.txt"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string("User-agent: *\nDisallow: /private/\n"),
+                )
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/"))
+                .respond_with(page(
+                    r#"Home <a href="/a">A</a> <a href="/a#top">A again</a>
+                       <a href="/b">B</a> <a href="/private/x">Private</a>"#,
+                ))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/a"))
+                .respond_with(page(r#"Alpha <a href="/c">C</a> <a href="/">Home</a>"#))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/b"))
+                .respond_with(ResponseTemplate::new(404))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/private/x"))
+                .respond_with(page("Secret"))
+                .expect(0)
+                .mount(&mock_server)
+                .await;
 
+            let options = CrawlOptions::new()
+                .max_depth(1)
+                .delay(std::time::Duration::ZERO);
+            let config = Config::builder().max_retries(0).build();
+            let pages = MarkdownDown::with_config(config)
+                .crawl(&format!("{base}/"), &options)
+                .await
+                .unwrap();
+
+            let visited: Vec<(&str, u32)> = pages
+                .iter()
+                .map(|page| (page.url.trim_start_matches(&base), page.depth))
+                .collect();
+            assert_eq!(visited, vec![("/", 0), ("/a", 1), ("/b", 1)]);
+            assert!(pages[1].result.as_ref().unwrap().as_str().contains("Alpha"));
+            assert!(pages[2].result.is_err());
+        }
+
+        #[tokio::test]
+        async fn test_crawl_resumes_from_saved_frontier() {
+            let mock_server = MockServer::start().await;
+            let base = mock_server.uri();
             Mock::given(method("GET"))
-                .and(path("/fallback-test"))
-                .respond_with(ResponseTemplate::new(200).set_body_string(html_content))
+                .and(path("/"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    r#"<html><body><p>Home <a href="/a">A</a> <a href="/b">B</a></p></body></html>"#,
+                ))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/a"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string("<html><body><p>Alpha</p></body></html>"),
+                )
+                .expect(1)
                 .mount(&mock_server)
                 .await;
 
+            let dir = tempfile::tempdir().unwrap();
+            let frontier_path = dir.path().join("frontier.json");
+            let options = CrawlOptions::new()
+                .max_depth(1)
+                .max_pages(1)
+                .respect_robots(false)
+                .delay(std::time::Duration::ZERO)
+                .frontier(&frontier_path);
             let md = MarkdownDown::new();
-            let url = format!("{}/fallback-test", mock_server.uri());
-            let result = md.convert_url(&url).await;
 
-            // Should succeed with HTML conversion
-            assert!(result.is_ok());
-            let markdown = result.unwrap();
-            assert!(markdown.as_str().contains("# Fallback Test"));
-            assert!(markdown.as_str().contains("This should work via fallback"));
+            // The budget only covers the seed, but its links are remembered
+            let first = md.crawl(&format!("{base}/"), &options).await.unwrap();
+            assert_eq!(first.len(), 1);
+            let frontier = CrawlFrontier::load(&frontier_path).await.unwrap();
+            assert_eq!(frontier.len(), 3);
+            assert_eq!(frontier.get(&format!("{base}/")).unwrap().visits, 1);
+
+            // The next crawl spends its budget on a page never fetched
+            let second = md.crawl(&format!("{base}/"), &options).await.unwrap();
+            assert_eq!(second.len(), 1);
+            assert_eq!(second[0].url, format!("{base}/a"));
+            assert_eq!(second[0].depth, 1);
         }
 
         #[tokio::test]
-        async fn test_convert_url_invalid_url_error() {
-            // Test convert_url with an invalid URL to trigger validation error
+        async fn test_convert_url_stream_yields_segments() {
+            let mock_server = MockServer::start().await;
+
+            let paragraphs: String = (0..400)
+                .map(|i| format!("<p>Paragraph {i} of a long article about streaming.</p>"))
+                .collect();
+            Mock::given(method("GET"))
+                .and(path("/long"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                    "<html><head><title>Long Article</title></head><body>{paragraphs}</body></html>"
+                )))
+                .mount(&mock_server)
+                .await;
+            let url = format!("{}/long", mock_server.uri());
+
             let md = MarkdownDown::new();
-            let result = md.convert_url("not-a-valid-url").await;
+            let chunks: Vec<String> = md
+                .convert_url_stream(&url)
+                .map(|chunk| chunk.unwrap())
+                .collect()
+                .await;
 
-            assert!(result.is_err());
-            match result.unwrap_err() {
-                MarkdownError::ValidationError { kind, context } => {
-                    assert_eq!(kind, crate::types::ValidationErrorKind::InvalidUrl);
-                    assert_eq!(context.url, "not-a-valid-url");
-                }
-                _ => panic!("Expected ValidationError for invalid URL"),
-            }
+            assert!(chunks.len() > 1);
+            assert!(chunks[0].starts_with("---\n"));
+            assert!(chunks[0].contains("title: Long Article"));
+            assert!(chunks[0].contains("document_id:"));
+            assert!(!chunks[1].contains("document_id:"));
+
+            let document = chunks.concat();
+            assert!(document.contains("Paragraph 0 of"));
+            assert!(document.contains("Paragraph 399 of"));
+            assert!(!document.contains("<p>"));
         }
 
-        #[tokio::test]
-        async fn test_convert_url_malformed_url_error() {
-            // Test convert_url with a malformed URL
+        #[test]
+        fn test_internationalized_host_metadata() {
             let md = MarkdownDown::new();
-            let result = md.convert_url("http://[invalid-host").await;
+            let html = "<h1>Wiki</h1><p>Internal page.</p>";
 
-            assert!(result.is_err());
-            // Should get a validation error for malformed URL
-            match result.unwrap_err() {
-                MarkdownError::ValidationError { kind, context } => {
-                    assert_eq!(kind, crate::types::ValidationErrorKind::InvalidUrl);
-                    assert_eq!(context.url, "http://[invalid-host");
-                }
-                _ => panic!("Expected ValidationError for malformed URL"),
-            }
+            let markdown = md.convert_html(html, Some("https://社内.例え.jp/wiki")).unwrap();
+            assert_eq!(
+                frontmatter_field(markdown.as_str(), "source_url").as_deref(),
+                Some("https://xn--v6q418g.xn--r8jz45g.jp/wiki")
+            );
+            assert_eq!(
+                frontmatter_field(markdown.as_str(), "host_unicode").as_deref(),
+                Some("社内.例え.jp")
+            );
+            assert!(frontmatter_field(markdown.as_str(), "idn_mixed_script").is_none());
+
+            let spoofed = md
+                .convert_html(html, Some("https://p\u{0430}ypal.com/login"))
+                .unwrap();
+            assert_eq!(
+                frontmatter_field(spoofed.as_str(), "idn_mixed_script").as_deref(),
+                Some("true")
+            );
+
+            let plain = md.convert_html(html, Some("https://example.com/")).unwrap();
+            assert!(frontmatter_field(plain.as_str(), "host_unicode").is_none());
         }
 
         #[tokio::test]
-        async fn test_successful_conversion_with_instrumentation() {
-            // Test successful conversion to ensure instrumentation line is covered
+        async fn test_convert_github_enterprise_issue() {
             let mock_server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/api/v3/repos/platform/api/issues/5"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "id": 1, "number": 5, "title": "Rotate signing keys", "body": "Due Friday.",
+                    "state": "open", "user": {"login": "alice", "id": 1},
+                    "created_at": "2023-01-15T10:00:00Z", "updated_at": "2023-01-15T10:00:00Z",
+                    "labels": [], "pull_request": null
+                })))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/api/v3/repos/platform/api/issues/5/comments"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+                .mount(&mock_server)
+                .await;
 
-            let html_content = "<h1>Instrumentation Test</h1><p>Testing the instrumentation decorator.</p>";
+            let config = crate::config::Config::builder()
+                .github_api_base_url(format!("{}/api/v3", mock_server.uri()))
+                .build();
+            let md = MarkdownDown::with_config(config);
+            let url = format!("{}/platform/api/issues/5", mock_server.uri());
+            assert_eq!(
+                md.detector().detect_type(&url).unwrap(),
+                UrlType::GitHubIssue
+            );
+
+            let markdown = md.convert_url(&url).await.unwrap();
+            assert!(markdown.as_str().contains("Rotate signing keys"));
+            assert!(markdown.as_str().contains("Due Friday."));
+        }
+
+        #[tokio::test]
+        async fn test_convert_data_uri() {
+            let md = MarkdownDown::new();
+            assert!(md.supported_types().contains(&UrlType::DataUri));
+            assert_eq!(
+                detect_url_type("data:text/html,<p>Hi</p>").unwrap(),
+                UrlType::DataUri
+            );
+
+            // "<h1>Inline</h1><p>No temp file needed.</p>"
+            let url =
+                "data:text/html;base64,PGgxPklubGluZTwvaDE+PHA+Tm8gdGVtcCBmaWxlIG5lZWRlZC48L3A+";
+            let markdown = md.convert_url(url).await.unwrap();
+            assert!(markdown.content_only().contains("# Inline"));
+            assert!(frontmatter_field(markdown.as_str(), "document_id").is_some());
+
+            let markdown = md
+                .convert_url("data:text/markdown;charset=utf-8,%23%20Notes%0A%0A-%20item")
+                .await
+                .unwrap();
+            assert_eq!(markdown.as_str(), "# Notes\n\n- item");
+        }
 
+        #[tokio::test]
+        async fn test_convert_url_to_writer() {
+            let mock_server = MockServer::start().await;
             Mock::given(method("GET"))
-                .and(path("/instrumentation-test"))
-                .respond_with(ResponseTemplate::new(200).set_body_string(html_content))
+                .and(path("/article"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string("<h1>Written</h1><p>Straight to the sink.</p>"),
+                )
                 .mount(&mock_server)
                 .await;
+            let url = format!("{}/article", mock_server.uri());
 
             let md = MarkdownDown::new();
-            let url = format!("{}/instrumentation-test", mock_server.uri());
-            let result = md.convert_url(&url).await;
+            let mut sink = Vec::new();
+            let written = md.convert_url_to_writer(&url, &mut sink).await.unwrap();
 
-            assert!(result.is_ok());
-            let markdown = result.unwrap();
-            assert!(markdown.as_str().contains("# Instrumentation Test"));
-            assert!(markdown.as_str().contains("Testing the instrumentation decorator"));
+            let document = String::from_utf8(sink).unwrap();
+            assert_eq!(written, document.len() as u64);
+            assert!(document.starts_with("---\n"));
+            assert!(document.contains("Straight to the sink."));
+
+            let mut sink = Vec::new();
+            assert!(md
+                .convert_url_to_writer("not a url", &mut sink)
+                .await
+                .is_err());
+            assert!(sink.is_empty());
+
+            let md = MarkdownDown::with_config(
+                Config::builder()
+                    .compression(crate::compression::Compression::Gzip)
+                    .build(),
+            );
+            let mut sink = Vec::new();
+            md.convert_url_to_writer(&url, &mut sink).await.unwrap();
+            let document = crate::compression::read_to_string(sink.as_slice())
+                .await
+                .unwrap();
+            assert!(document.contains("Straight to the sink."));
+        }
+
+        #[tokio::test]
+        async fn test_convert_url_stream_reports_errors_as_items() {
+            let md = MarkdownDown::new();
+            let chunks: Vec<_> = md.convert_url_stream("not a url").collect().await;
+
+            assert_eq!(chunks.len(), 1);
+            assert!(chunks[0].is_err());
+        }
+
+        #[tokio::test]
+        async fn test_doctor_checks_connectivity_and_tokens() {
+            use crate::doctor::CheckStatus;
+            use wiremock::matchers::query_param;
+
+            let mock_server = MockServer::start().await;
+            let base = mock_server.uri();
+            Mock::given(method("GET"))
+                .and(path("/"))
+                .respond_with(ResponseTemplate::new(200).set_body_string("up"))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/rate_limit"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    r#"{"resources": {}, "rate": {"limit": 5000, "remaining": 4990, "reset": 0}}"#,
+                ))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/tokeninfo"))
+                .and(query_param("access_token", "expired"))
+                .respond_with(
+                    ResponseTemplate::new(400).set_body_string(r#"{"error": "invalid_token"}"#),
+                )
+                .mount(&mock_server)
+                .await;
+
+            let md = MarkdownDown::with_config(
+                Config::builder()
+                    .max_retries(0)
+                    .github_token("ghp_test")
+                    .github_api_base_url(&base)
+                    .google_api_key("expired")
+                    .build(),
+            );
+            let options = DoctorOptions::new()
+                .connectivity_url(format!("{base}/"))
+                .google_tokeninfo_url(format!("{base}/tokeninfo"))
+                .tools([("markdowndown-no-such-tool", "testing")]);
+            let report = md.doctor_with_options(&options).await;
+
+            assert_eq!(
+                report.checks.len(),
+                5 + crate::features::optional_features().len()
+            );
+            assert_eq!(report.check("connectivity").unwrap().status, CheckStatus::Ok);
+            let github = report.check("github_token").unwrap();
+            assert_eq!(github.status, CheckStatus::Ok);
+            assert!(github.detail.contains("4990 of 5000"));
+            assert_eq!(
+                report.check("google_api_key").unwrap().status,
+                CheckStatus::Failed
+            );
+            assert_eq!(
+                report.check("tool:markdowndown-no-such-tool").unwrap().status,
+                CheckStatus::Warning
+            );
+            let cloud = report.check("feature:cloud").unwrap();
+            if cfg!(feature = "cloud") {
+                assert_eq!(cloud.status, CheckStatus::Ok);
+            } else {
+                assert_eq!(cloud.status, CheckStatus::Skipped);
+                assert!(cloud.detail.contains("--features cloud"));
+            }
+            assert!(!report.is_healthy());
+
+            // Nothing configured means nothing to validate
+            let report = MarkdownDown::new()
+                .doctor_with_options(&options.connectivity_url(format!("{base}/")).tools(
+                    Vec::<(String, String)>::new(),
+                ))
+                .await;
+            assert_eq!(report.check("github_token").unwrap().status, CheckStatus::Skipped);
+            assert_eq!(
+                report.check("google_api_key").unwrap().status,
+                CheckStatus::Skipped
+            );
+            assert_eq!(
+                report.check("google_service_account").unwrap().status,
+                CheckStatus::Skipped
+            );
+            assert!(report.is_healthy());
         }
 
         #[test]
@@ -0,0 +1,429 @@
+//! Outbound link inventory and link rewriting for converted documents.
+//!
+//! Links are collected from the converted markdown, resolved against the
+//! source URL, and classified as internal (same host) or external. Images,
+//! same-page anchors, and links inside code blocks are not included.
+//!
+//! The same links can be mapped to new targets with a [`LinkRewriter`],
+//! such as one that points internal wiki links at local files. Rewriters
+//! registered with [`crate::MarkdownDown::with_link_rewriter`] run during
+//! postprocessing, after tracking parameters are stripped when
+//! [`crate::config::OutputConfig::strip_tracking_params`] is enabled.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::links::extract_links;
+//! use markdowndown::types::LinkKind;
+//!
+//! let markdown = "See [the guide](/guide) and [Rust](https://www.rust-lang.org).";
+//! let links = extract_links(markdown, "https://example.com/blog/post");
+//!
+//! assert_eq!(links[0].url, "https://example.com/guide");
+//! assert_eq!(links[0].kind, LinkKind::Internal);
+//! assert_eq!(links[1].kind, LinkKind::External);
+//! ```
+
+use crate::identity;
+use crate::types::{LinkKind, OutboundLink};
+use regex::{Captures, Regex};
+use url::Url;
+
+/// Click and campaign IDs that ad and mail platforms add to outbound links,
+/// beyond the ones canonical URLs already drop.
+const LINK_TRACKING_PARAMS: &[&str] = &[
+    "msclkid", "dclid", "gbraid", "wbraid", "yclid", "twclid", "igshid", "_hsenc", "_hsmi",
+    "mkt_tok",
+];
+
+/// Matches inline markdown links and images: `[text](target "title")`.
+const LINK_PATTERN: &str = r#"(!?)\[([^\]]*)\]\(\s*<?([^)\s>]+)>?(?:\s+"[^"]*")?\s*\)"#;
+
+/// Maps the targets of links in converted markdown to new targets.
+///
+/// Closures taking the link target and kind implement this trait.
+///
+/// # Examples
+///
+/// ```rust
+/// use markdowndown::links::{rewrite_links, LinkRewriter};
+/// use markdowndown::types::LinkKind;
+///
+/// struct LocalWiki;
+///
+/// impl LinkRewriter for LocalWiki {
+///     fn rewrite(&self, url: &str, kind: LinkKind) -> Option<String> {
+///         let page = url.strip_prefix("https://wiki.example.com/wiki/")?;
+///         (kind == LinkKind::Internal).then(|| format!("{page}.md"))
+///     }
+/// }
+///
+/// let rewriters: Vec<Box<dyn LinkRewriter>> = vec![Box::new(LocalWiki)];
+/// let markdown = rewrite_links(
+///     "See [Setup](/wiki/Setup) and [Rust](https://www.rust-lang.org).",
+///     "https://wiki.example.com/wiki/Home",
+///     &rewriters,
+/// );
+/// assert_eq!(markdown, "See [Setup](Setup.md) and [Rust](https://www.rust-lang.org).");
+/// ```
+pub trait LinkRewriter: Send + Sync {
+    /// Returns the new target for a link, or `None` to leave it unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The link target, resolved against the source URL
+    /// * `kind` - Whether the link stays on the source document's host
+    fn rewrite(&self, url: &str, kind: LinkKind) -> Option<String>;
+}
+
+impl<F> LinkRewriter for F
+where
+    F: Fn(&str, LinkKind) -> Option<String> + Send + Sync,
+{
+    fn rewrite(&self, url: &str, kind: LinkKind) -> Option<String> {
+        self(url, kind)
+    }
+}
+
+/// Collects the outbound links of a markdown document.
+///
+/// # Arguments
+///
+/// * `markdown` - The converted markdown, with or without frontmatter
+/// * `source_url` - The URL the document was converted from, used to resolve
+///   relative links and classify them
+///
+/// # Returns
+///
+/// The links in document order. Duplicate targets are kept, since the same
+/// URL may appear with different anchor text.
+pub fn extract_links(markdown: &str, source_url: &str) -> Vec<OutboundLink> {
+    let pattern = Regex::new(LINK_PATTERN).expect("valid link pattern");
+    let base = Url::parse(source_url).ok();
+    let mut links = Vec::new();
+
+    for line in lines_outside_code(markdown) {
+        for captures in pattern.captures_iter(line) {
+            let Some((url, kind)) = classify(&captures, base.as_ref()) else {
+                continue;
+            };
+            links.push(OutboundLink {
+                url: url.to_string(),
+                text: captures[2].trim().to_string(),
+                kind,
+            });
+        }
+    }
+
+    links
+}
+
+/// Replaces link targets with the targets the rewriters map them to.
+///
+/// Each link is offered to the rewriters in order, and the first new target
+/// wins. Links are matched like [`extract_links`] matches them, so images,
+/// same-page anchors, and code blocks are left alone, as are link titles.
+///
+/// # Arguments
+///
+/// * `markdown` - The converted markdown, with or without frontmatter
+/// * `source_url` - The URL the document was converted from, used to resolve
+///   relative links and classify them
+/// * `rewriters` - The rewriters to apply
+///
+/// # Returns
+///
+/// The markdown with rewritten link targets.
+pub fn rewrite_links(
+    markdown: &str,
+    source_url: &str,
+    rewriters: &[Box<dyn LinkRewriter>],
+) -> String {
+    if rewriters.is_empty() {
+        return markdown.to_string();
+    }
+    let base = Url::parse(source_url).ok();
+    replace_targets(markdown, |captures| {
+        let (url, kind) = classify(captures, base.as_ref())?;
+        rewriters
+            .iter()
+            .find_map(|rewriter| rewriter.rewrite(url.as_str(), kind))
+    })
+}
+
+/// Makes relative link and image targets absolute.
+///
+/// Markdown written for a repository or site links to its neighbours with
+/// relative paths, which break once the document is read somewhere else.
+/// Same-page anchors, targets that already have a scheme, and code blocks
+/// are left alone, as is everything when the source URL is not absolute.
+///
+/// # Arguments
+///
+/// * `markdown` - The markdown document
+/// * `source_url` - The URL the document was read from
+///
+/// # Examples
+///
+/// ```rust
+/// use markdowndown::links::resolve_links;
+///
+/// let markdown = "[Setup](setup.md) ![Logo](../logo.png) [Top](#top) [Rust](https://www.rust-lang.org)";
+/// assert_eq!(
+///     resolve_links(markdown, "https://example.com/docs/index.md"),
+///     "[Setup](https://example.com/docs/setup.md) ![Logo](https://example.com/logo.png) \
+///      [Top](#top) [Rust](https://www.rust-lang.org)"
+/// );
+/// ```
+pub fn resolve_links(markdown: &str, source_url: &str) -> String {
+    let Ok(base) = Url::parse(source_url) else {
+        return markdown.to_string();
+    };
+    replace_targets(markdown, |captures| {
+        let target = &captures[3];
+        if target.starts_with('#') || Url::parse(target).is_ok() {
+            return None;
+        }
+        base.join(target).ok().map(String::from)
+    })
+}
+
+/// Removes tracking parameters from the query strings of link targets.
+///
+/// `utm_*` parameters, `fbclid`, `gclid`, and the other parameters that
+/// [`crate::identity::canonical_url`] ignores are removed, along with
+/// `extra_params`. Targets keep their form otherwise: relative links stay
+/// relative, and the remaining parameters keep their order. Images,
+/// same-page anchors, and code blocks are left alone.
+///
+/// # Arguments
+///
+/// * `markdown` - The converted markdown, with or without frontmatter
+/// * `extra_params` - More parameter names to remove; a trailing `*` matches
+///   any parameter starting with the rest of the name
+///
+/// # Examples
+///
+/// ```rust
+/// use markdowndown::links::strip_tracking_params;
+///
+/// let markdown = "[Post](https://example.com/post?id=7&utm_source=feed&fbclid=abc#top)";
+/// assert_eq!(
+///     strip_tracking_params(markdown, &[]),
+///     "[Post](https://example.com/post?id=7#top)"
+/// );
+/// assert_eq!(
+///     strip_tracking_params("[Docs](/docs?page=2&campaign=x)", &["campaign".to_string()]),
+///     "[Docs](/docs?page=2)"
+/// );
+/// ```
+pub fn strip_tracking_params(markdown: &str, extra_params: &[String]) -> String {
+    replace_targets(markdown, |captures| {
+        if !captures[1].is_empty() {
+            return None;
+        }
+        let target = &captures[3];
+        let (before_fragment, fragment) = match target.find('#') {
+            Some(index) => target.split_at(index),
+            None => (target, ""),
+        };
+        let (path, query) = before_fragment.split_once('?')?;
+        let kept: Vec<&str> = query
+            .split('&')
+            .filter(|pair| {
+                let key = pair.split('=').next().unwrap_or_default();
+                !pair.is_empty() && !is_tracking_param(key, extra_params)
+            })
+            .collect();
+        if kept.len() == query.split('&').count() {
+            return None;
+        }
+        let query = if kept.is_empty() {
+            String::new()
+        } else {
+            format!("?{}", kept.join("&"))
+        };
+        Some(format!("{path}{query}{fragment}"))
+    })
+}
+
+/// Returns true if a query parameter only tracks where a visitor came from.
+fn is_tracking_param(key: &str, extra_params: &[String]) -> bool {
+    identity::is_tracking_param(key)
+        || LINK_TRACKING_PARAMS.contains(&key)
+        || extra_params
+            .iter()
+            .any(|param| match param.strip_suffix('*') {
+                Some(prefix) => key.starts_with(prefix),
+                None => key == param,
+            })
+}
+
+/// Replaces link targets outside code blocks with the targets `replace`
+/// returns, keeping the rest of each link.
+fn replace_targets(markdown: &str, mut replace: impl FnMut(&Captures) -> Option<String>) -> String {
+    let pattern = Regex::new(LINK_PATTERN).expect("valid link pattern");
+    let mut output = String::with_capacity(markdown.len());
+    let mut in_code_block = false;
+
+    for line in markdown.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let is_fence = trimmed.starts_with("```") || trimmed.starts_with("~~~");
+        if is_fence {
+            in_code_block = !in_code_block;
+        }
+        if is_fence || in_code_block {
+            output.push_str(line);
+            continue;
+        }
+
+        let rewritten = pattern.replace_all(line, |captures: &Captures| {
+            let whole = captures.get(0).expect("match has a whole group");
+            let target = captures.get(3).expect("link pattern has a target group");
+            match replace(captures) {
+                Some(new_target) => format!(
+                    "{}{new_target}{}",
+                    &line[whole.start()..target.start()],
+                    &line[target.end()..whole.end()]
+                ),
+                None => whole.as_str().to_string(),
+            }
+        });
+        output.push_str(&rewritten);
+    }
+    output
+}
+
+/// Returns the lines of a markdown document that are outside fenced code blocks.
+fn lines_outside_code(markdown: &str) -> impl Iterator<Item = &str> {
+    let mut in_code_block = false;
+    markdown.lines().filter(move |line| {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            return false;
+        }
+        !in_code_block
+    })
+}
+
+/// Resolves a matched link and classifies it, skipping images and same-page anchors.
+fn classify(captures: &Captures, base: Option<&Url>) -> Option<(Url, LinkKind)> {
+    let target = &captures[3];
+    if !captures[1].is_empty() || target.starts_with('#') {
+        return None;
+    }
+
+    let resolved = match base {
+        Some(base) => base.join(target).ok()?,
+        None => Url::parse(target).ok()?,
+    };
+    let is_internal = matches!(resolved.scheme(), "http" | "https")
+        && base.is_some_and(|base| base.host_str() == resolved.host_str());
+    let kind = if is_internal {
+        LinkKind::Internal
+    } else {
+        LinkKind::External
+    };
+    Some((resolved, kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_links_resolves_and_classifies() {
+        let markdown = "[Home](/) and [Sibling](other.html \"Title\")\n[Mail](mailto:a@example.com) [Out](https://other.org/x)";
+        let links = extract_links(markdown, "https://example.com/docs/page.html");
+
+        let summary: Vec<(&str, &str, LinkKind)> = links
+            .iter()
+            .map(|link| (link.url.as_str(), link.text.as_str(), link.kind))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("https://example.com/", "Home", LinkKind::Internal),
+                ("https://example.com/docs/other.html", "Sibling", LinkKind::Internal),
+                ("mailto:a@example.com", "Mail", LinkKind::External),
+                ("https://other.org/x", "Out", LinkKind::External),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_links_skips_images_anchors_and_code() {
+        let markdown = "![Logo](/logo.png)\n[Top](#top)\n```\n[Code](https://example.com/code)\n```\n[Kept](https://example.com/kept)";
+        let links = extract_links(markdown, "https://example.com/");
+
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].text, "Kept");
+    }
+
+    #[test]
+    fn test_extract_links_without_base_keeps_absolute_links() {
+        let markdown = "[Relative](./readme.md) [Absolute](https://example.com)";
+        let links = extract_links(markdown, "./docs/index.md");
+
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "https://example.com/");
+        assert_eq!(links[0].kind, LinkKind::External);
+    }
+
+    #[test]
+    fn test_resolve_links() {
+        let markdown = "[Guide](guide/ \"Guide\") [Mail](mailto:a@example.com)\n```\n[Code](code.md)\n```\n";
+        assert_eq!(
+            resolve_links(markdown, "https://example.com/docs/README.md"),
+            "[Guide](https://example.com/docs/guide/ \"Guide\") [Mail](mailto:a@example.com)\n```\n[Code](code.md)\n```\n"
+        );
+        assert_eq!(resolve_links("[Up](../a.md)", "./notes.md"), "[Up](../a.md)");
+    }
+
+    #[test]
+    fn test_rewrite_links_maps_targets() {
+        let rewriters: Vec<Box<dyn LinkRewriter>> = vec![
+            Box::new(|url: &str, kind: LinkKind| {
+                if kind != LinkKind::Internal {
+                    return None;
+                }
+                Some(format!("local{}", Url::parse(url).ok()?.path()))
+            }),
+            Box::new(|_: &str, _: LinkKind| Some("unused".to_string())),
+        ];
+        let markdown = "[Guide](/guide \"Title\") ![Logo](/logo.png) [Top](#top)\n\
+                        ```\n[Code](/code)\n```\n[Out](https://other.org/)\n";
+
+        let rewritten = rewrite_links(markdown, "https://example.com/docs/", &rewriters);
+        assert_eq!(
+            rewritten,
+            "[Guide](local/guide \"Title\") ![Logo](/logo.png) [Top](#top)\n```\n[Code](/code)\n```\n[Out](unused)\n"
+        );
+        assert_eq!(
+            rewrite_links(markdown, "https://example.com/", &[]),
+            markdown
+        );
+    }
+
+    #[test]
+    fn test_strip_tracking_params_keeps_other_parameters() {
+        let markdown = "[A](https://example.com/a?utm_source=x&id=1&gclid=2 \"Title\") \
+                        ![Img](/i.png?utm_source=x) [B](/b?a=1&b=2) [C](/c?utm_medium=y#s)\n\
+                        ```\n[Code](/code?utm_source=x)\n```\n[D](/d?ref_src=tw&mkt_tok=z)\n";
+
+        let stripped = strip_tracking_params(markdown, &["mkt_*".to_string()]);
+        assert_eq!(
+            stripped,
+            "[A](https://example.com/a?id=1 \"Title\") \
+             ![Img](/i.png?utm_source=x) [B](/b?a=1&b=2) [C](/c#s)\n\
+             ```\n[Code](/code?utm_source=x)\n```\n[D](/d)\n"
+        );
+
+        // Only known tracking parameters are removed by default
+        assert_eq!(
+            strip_tracking_params("[E](/e?ref=main&msclkid=1&_hsenc=p2)", &[]),
+            "[E](/e?ref=main)"
+        );
+    }
+}
@@ -0,0 +1,336 @@
+//! Markdown linting for converted output.
+//!
+//! The lint pass runs as the final pipeline step so converted files pass the
+//! same markdownlint checks as hand-written ones. Each rule can report a
+//! problem or rewrite the line to fix it:
+//!
+//! - **Heading increment**: a heading more than one level deeper than the
+//!   previous heading is raised, together with its subheadings
+//! - **No bare URLs**: bare URLs are wrapped in `<...>` autolinks
+//! - **Fenced code language**: fences without a language are marked `text`
+//!
+//! The frontmatter block and the contents of code blocks are never changed.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::config::LintRule;
+//! use markdowndown::lint::MarkdownLinter;
+//!
+//! let linter = MarkdownLinter::new(&LintRule::all());
+//! let markdown = "# Title\n\n### Details\n\nSee https://example.com\n";
+//!
+//! assert_eq!(linter.check(markdown).len(), 2);
+//! assert_eq!(
+//!     linter.fix(markdown),
+//!     "# Title\n\n## Details\n\nSee <https://example.com>\n"
+//! );
+//! ```
+
+use crate::config::LintRule;
+use regex::Regex;
+use std::fmt;
+
+/// Language added to fenced code blocks that do not declare one.
+const DEFAULT_CODE_LANGUAGE: &str = "text";
+
+/// Spans in which URLs are already links: code spans, inline links and
+/// images, autolinks and inline HTML, and reference definitions.
+const LINKED_SPAN_PATTERN: &str =
+    r"`[^`]*`|!?\[[^\]]*\]\([^)]*\)|<[^>]*>|^\s*\[[^\]]+\]:\s*\S+";
+
+/// A bare `http` or `https` URL.
+const BARE_URL_PATTERN: &str = r"https?://[^\s<>()\[\]`]+";
+
+/// A problem found by the lint pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintWarning {
+    /// The rule that found the problem
+    pub rule: LintRule,
+    /// The 1-based line number of the problem
+    pub line: usize,
+    /// A description of the problem
+    pub message: String,
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {} ({})", self.line, self.message, self.rule.name())
+    }
+}
+
+/// Checks and fixes markdown against a set of lint rules.
+pub struct MarkdownLinter {
+    rules: Vec<LintRule>,
+    linked_span: Regex,
+    bare_url: Regex,
+}
+
+impl MarkdownLinter {
+    /// Creates a linter that applies the given rules.
+    ///
+    /// # Arguments
+    ///
+    /// * `rules` - The rules to apply
+    pub fn new(rules: &[LintRule]) -> Self {
+        Self {
+            rules: rules.to_vec(),
+            linked_span: Regex::new(LINKED_SPAN_PATTERN).expect("valid linked span pattern"),
+            bare_url: Regex::new(BARE_URL_PATTERN).expect("valid bare URL pattern"),
+        }
+    }
+
+    /// Returns the problems found in a markdown document.
+    ///
+    /// # Arguments
+    ///
+    /// * `markdown` - The markdown document, with or without frontmatter
+    pub fn check(&self, markdown: &str) -> Vec<LintWarning> {
+        self.run(markdown).1
+    }
+
+    /// Returns the markdown document with every problem fixed.
+    ///
+    /// # Arguments
+    ///
+    /// * `markdown` - The markdown document, with or without frontmatter
+    pub fn fix(&self, markdown: &str) -> String {
+        self.run(markdown).0
+    }
+
+    fn enabled(&self, rule: LintRule) -> bool {
+        self.rules.contains(&rule)
+    }
+
+    /// Lints the document line by line, returning the fixed text and the problems found.
+    fn run(&self, markdown: &str) -> (String, Vec<LintWarning>) {
+        let mut output = String::with_capacity(markdown.len());
+        let mut warnings = Vec::new();
+        let mut in_frontmatter = markdown.starts_with("---\n");
+        // The character and length of the open code fence, if inside one
+        let mut fence: Option<(char, usize)> = None;
+        let mut previous_level: Option<usize> = None;
+        // Original and fixed levels of the enclosing headings, outermost first
+        let mut heading_stack: Vec<(usize, usize)> = Vec::new();
+
+        for (index, line) in markdown.split_inclusive('\n').enumerate() {
+            let number = index + 1;
+            let text = line.trim_end_matches(['\n', '\r']);
+            let ending = &line[text.len()..];
+
+            if in_frontmatter {
+                if index > 0 && text.trim_end() == "---" {
+                    in_frontmatter = false;
+                }
+                output.push_str(line);
+                continue;
+            }
+
+            if let Some((fence_char, fence_len)) = fence {
+                if is_fence_close(text, fence_char, fence_len) {
+                    fence = None;
+                }
+                output.push_str(line);
+                continue;
+            }
+
+            let mut fixed = text.to_string();
+            if let Some((fence_char, fence_len, info)) = parse_fence_open(text) {
+                fence = Some((fence_char, fence_len));
+                if info.is_empty() && self.enabled(LintRule::FencedCodeLanguage) {
+                    warnings.push(LintWarning {
+                        rule: LintRule::FencedCodeLanguage,
+                        line: number,
+                        message: "Fenced code block has no language".to_string(),
+                    });
+                    fixed = format!("{}{DEFAULT_CODE_LANGUAGE}", text.trim_end());
+                }
+                output.push_str(&fixed);
+                output.push_str(ending);
+                continue;
+            }
+
+            if let Some(level) = heading_level(text) {
+                if self.enabled(LintRule::HeadingIncrement) {
+                    if let Some(previous) = previous_level.filter(|p| level > p + 1) {
+                        warnings.push(LintWarning {
+                            rule: LintRule::HeadingIncrement,
+                            line: number,
+                            message: format!(
+                                "Heading level {level} follows level {previous}; expected {}",
+                                previous + 1
+                            ),
+                        });
+                    }
+
+                    // Raise the heading to one below its parent, so a skipped
+                    // level moves the whole subtree up and siblings stay siblings
+                    while heading_stack.last().is_some_and(|(orig, _)| *orig >= level) {
+                        heading_stack.pop();
+                    }
+                    let new_level = heading_stack
+                        .last()
+                        .map_or(level, |(_, parent)| level.min(parent + 1));
+                    if new_level != level {
+                        fixed = set_heading_level(text, level, new_level);
+                    }
+                    heading_stack.push((level, new_level));
+                }
+                previous_level = Some(level);
+            }
+
+            // Indented code blocks keep their URLs as written
+            let indented = text.starts_with('\t') || text.starts_with("    ");
+            if !indented && self.enabled(LintRule::NoBareUrls) {
+                fixed = self.link_bare_urls(&fixed, number, &mut warnings);
+            }
+
+            output.push_str(&fixed);
+            output.push_str(ending);
+        }
+
+        (output, warnings)
+    }
+
+    /// Wraps bare URLs in a line as autolinks, recording a warning for each.
+    fn link_bare_urls(&self, text: &str, number: usize, warnings: &mut Vec<LintWarning>) -> String {
+        // Blank out spans that are already links so their URLs are not matched
+        let mut masked = text.as_bytes().to_vec();
+        for span in self.linked_span.find_iter(text) {
+            masked[span.range()].fill(b' ');
+        }
+        let masked = String::from_utf8(masked).expect("masking whole characters keeps UTF-8");
+
+        let mut ranges = Vec::new();
+        for url in self.bare_url.find_iter(&masked) {
+            let trimmed = url.as_str().trim_end_matches(['.', ',', ';', ':', '!', '?', '\'', '"']);
+            warnings.push(LintWarning {
+                rule: LintRule::NoBareUrls,
+                line: number,
+                message: format!("Bare URL: {trimmed}"),
+            });
+            ranges.push((url.start(), url.start() + trimmed.len()));
+        }
+
+        let mut fixed = text.to_string();
+        for (start, end) in ranges.into_iter().rev() {
+            fixed.insert(end, '>');
+            fixed.insert(start, '<');
+        }
+        fixed
+    }
+}
+
+/// Splits a line into its indentation (up to three spaces) and the rest.
+fn split_indent(text: &str) -> Option<(&str, &str)> {
+    let indent = text.len() - text.trim_start_matches(' ').len();
+    (indent <= 3).then(|| text.split_at(indent))
+}
+
+/// Parses a code fence opening line into its character, length, and info string.
+fn parse_fence_open(text: &str) -> Option<(char, usize, &str)> {
+    let (_, rest) = split_indent(text)?;
+    let fence_char = rest.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let fence_len = rest.chars().take_while(|c| *c == fence_char).count();
+    if fence_len < 3 {
+        return None;
+    }
+
+    let info = rest[fence_len..].trim();
+    // Backtick fences cannot have backticks in the info string (that is inline code)
+    if fence_char == '`' && info.contains('`') {
+        return None;
+    }
+    Some((fence_char, fence_len, info))
+}
+
+/// Returns true if the line closes a fence opened with the given character and length.
+fn is_fence_close(text: &str, fence_char: char, fence_len: usize) -> bool {
+    let Some((_, rest)) = split_indent(text) else {
+        return false;
+    };
+    let run = rest.chars().take_while(|c| *c == fence_char).count();
+    run >= fence_len && rest[run..].trim().is_empty()
+}
+
+/// Returns the level of an ATX heading line.
+fn heading_level(text: &str) -> Option<usize> {
+    let (_, rest) = split_indent(text)?;
+    let level = rest.chars().take_while(|c| *c == '#').count();
+    let after = &rest[level..];
+    ((1..=6).contains(&level) && (after.is_empty() || after.starts_with([' ', '\t'])))
+        .then_some(level)
+}
+
+/// Rewrites an ATX heading line to a different level.
+fn set_heading_level(text: &str, level: usize, new_level: usize) -> String {
+    let (indent, rest) = split_indent(text).unwrap_or(("", text));
+    format!("{indent}{}{}", "#".repeat(new_level), &rest[level..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(rule: LintRule) -> MarkdownLinter {
+        MarkdownLinter::new(&[rule])
+    }
+
+    #[test]
+    fn test_heading_increment() {
+        let linter = rules(LintRule::HeadingIncrement);
+        let markdown = "## Start\n\n#### Too deep\n\n#### Sibling\n\n# Back to top\n";
+
+        let warnings = linter.check(markdown);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].line, 3);
+        assert_eq!(
+            warnings[0].to_string(),
+            "line 3: Heading level 4 follows level 2; expected 3 (heading-increment)"
+        );
+        assert_eq!(
+            linter.fix(markdown),
+            "## Start\n\n### Too deep\n\n### Sibling\n\n# Back to top\n"
+        );
+    }
+
+    #[test]
+    fn test_no_bare_urls() {
+        let linter = rules(LintRule::NoBareUrls);
+        let markdown = "Visit https://example.com/docs.\n\
+                        [Link](https://example.com) <https://example.com> `https://example.com`\n\
+                        [ref]: https://example.com/ref\n\
+                        \x20   indented https://example.com/code\n";
+
+        let warnings = linter.check(markdown);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].message, "Bare URL: https://example.com/docs");
+        assert!(linter
+            .fix(markdown)
+            .starts_with("Visit <https://example.com/docs>.\n[Link]"));
+    }
+
+    #[test]
+    fn test_fenced_code_language() {
+        let linter = rules(LintRule::FencedCodeLanguage);
+        let markdown = "```\nlet url = \"https://example.com\";\n```\n\n~~~rust\nfn main() {}\n~~~\n";
+
+        assert_eq!(linter.check(markdown).len(), 1);
+        assert_eq!(
+            linter.fix(markdown),
+            "```text\nlet url = \"https://example.com\";\n```\n\n~~~rust\nfn main() {}\n~~~\n"
+        );
+    }
+
+    #[test]
+    fn test_fix_preserves_frontmatter_and_code() {
+        let linter = MarkdownLinter::new(&LintRule::all());
+        let markdown = "---\nsource_url: https://example.com\n---\n\n# Title\n\n```bash\n### not a heading https://example.com\n```\n\n### Deep\n";
+
+        let fixed = linter.fix(markdown);
+        assert!(fixed.starts_with("---\nsource_url: https://example.com\n---\n"));
+        assert!(fixed.contains("### not a heading https://example.com\n"));
+        assert!(fixed.ends_with("## Deep\n"));
+        assert!(linter.check(&fixed).is_empty());
+    }
+}
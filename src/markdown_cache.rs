@@ -0,0 +1,129 @@
+//! Persistent cache of converted markdown.
+//!
+//! The HTTP response cache saves downloads, but every conversion still runs
+//! the converters and post-processing again. A [`MarkdownCache`] keeps the
+//! final markdown of each successful conversion in a directory, keyed by the
+//! URL and a fingerprint of the configuration and per-call options that
+//! produced it. Re-running a batch job returns unchanged documents from disk,
+//! and a job interrupted part way resumes where it stopped.
+//!
+//! Entries are never expired: clear the directory to convert everything
+//! again, or set [`crate::config::ConversionOptions::bypass_cache`] to refresh
+//! individual documents. Converters, link rewriters, and frontmatter
+//! providers registered on a [`crate::MarkdownDown`] are not part of the
+//! fingerprint, so clear the directory after changing them too.
+//!
+//! # Usage Examples
+//!
+//! ```rust,no_run
+//! use markdowndown::MarkdownDown;
+//!
+//! # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
+//! let md = MarkdownDown::new().with_cache("/var/cache/markdowndown/documents");
+//! // Converted once; later calls, in this process or the next, read the cache
+//! let markdown = md.convert_url("https://example.com/article.html").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::identity::fnv1a_hex;
+use crate::types::Markdown;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// A directory of converted documents.
+#[derive(Debug, Clone)]
+pub struct MarkdownCache {
+    dir: PathBuf,
+}
+
+/// A cached document as written to disk, with the key it was stored under.
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    url: String,
+    fingerprint: String,
+    markdown: String,
+}
+
+impl MarkdownCache {
+    /// Creates a cache in a directory, which is created when the first
+    /// document is stored.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory converted documents are kept in
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Returns the directory converted documents are kept in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the file a document is kept in.
+    fn path(&self, url: &str, fingerprint: &str) -> PathBuf {
+        let hash = fnv1a_hex(format!("{url}\n{fingerprint}").as_bytes());
+        self.dir.join(format!("{hash}.json"))
+    }
+
+    /// Returns the cached markdown for a URL converted with the fingerprinted settings.
+    pub(crate) async fn get(&self, url: &str, fingerprint: &str) -> Option<Markdown> {
+        let json = tokio::fs::read(self.path(url, fingerprint)).await.ok()?;
+        let entry: Entry = serde_json::from_slice(&json).ok()?;
+        // Hashes can collide, so the key is stored and checked
+        if entry.url != url || entry.fingerprint != fingerprint {
+            return None;
+        }
+        debug!("Using cached markdown for {}", url);
+        Some(Markdown::from(entry.markdown))
+    }
+
+    /// Stores the markdown converted from a URL with the fingerprinted settings.
+    ///
+    /// The document is written to a temporary file and renamed into place,
+    /// so an interrupted run never leaves a truncated entry behind.
+    pub(crate) async fn put(
+        &self,
+        url: &str,
+        fingerprint: &str,
+        markdown: &Markdown,
+    ) -> std::io::Result<()> {
+        let entry = Entry {
+            url: url.to_string(),
+            fingerprint: fingerprint.to_string(),
+            markdown: markdown.as_str().to_string(),
+        };
+        let json = serde_json::to_vec(&entry).map_err(std::io::Error::other)?;
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let path = self.path(url, fingerprint);
+        let partial = path.with_extension(format!("{}.partial", std::process::id()));
+        tokio::fs::write(&partial, json).await?;
+        tokio::fs::rename(&partial, &path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cached_markdown_keyed_by_url_and_fingerprint() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = MarkdownCache::new(dir.path().join("documents"));
+        let markdown = Markdown::from("# Cached".to_string());
+
+        assert!(cache.get("https://example.com/a", "f1").await.is_none());
+        cache
+            .put("https://example.com/a", "f1", &markdown)
+            .await
+            .unwrap();
+
+        let cached = cache.get("https://example.com/a", "f1").await.unwrap();
+        assert_eq!(cached.as_str(), "# Cached");
+        assert!(cache.get("https://example.com/a", "f2").await.is_none());
+        assert!(cache.get("https://example.com/b", "f1").await.is_none());
+        assert_eq!(std::fs::read_dir(cache.dir()).unwrap().count(), 1);
+    }
+}
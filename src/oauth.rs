@@ -0,0 +1,961 @@
+//! OAuth 2.0 user sign-in for Google and Microsoft.
+//!
+//! A service account or a pasted access token is not always an option:
+//! personal Google Drive files and OneDrive documents are only readable by
+//! the user who owns them, and pasted tokens expire within an hour. An
+//! [`OAuthSession`] signs a user in once with the device authorization flow,
+//! keeps the resulting tokens in a [`TokenStore`], and refreshes the access
+//! token whenever it expires.
+//!
+//! Sessions added with [`crate::config::ConfigBuilder::oauth_session`] are
+//! used by the HTTP client for the hosts of their provider. When a server
+//! rejects an access token, the request fails with
+//! [`AuthErrorKind::TokenExpired`] internally; the client then refreshes the
+//! token and sends the request once more, so callers only see the error if
+//! the refresh token itself has expired or been revoked.
+//!
+//! # Usage Examples
+//!
+//! ```rust,no_run
+//! use markdowndown::oauth::{FileTokenStore, OAuthClient, OAuthSession};
+//! use markdowndown::{Config, MarkdownDown};
+//!
+//! # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
+//! let session = OAuthSession::new(
+//!     OAuthClient::microsoft("common", "00000000-0000-0000-0000-000000000000"),
+//!     FileTokenStore::new("microsoft-token.json"),
+//! );
+//!
+//! // Sign in on first use; later runs reuse and refresh the stored tokens
+//! if !session.is_signed_in()? {
+//!     let http = reqwest::Client::new();
+//!     session
+//!         .sign_in_with_device_code(&http, |authorization| {
+//!             println!(
+//!                 "Visit {} and enter the code {}",
+//!                 authorization.verification_uri, authorization.user_code
+//!             );
+//!         })
+//!         .await?;
+//! }
+//!
+//! let md = MarkdownDown::with_config(Config::builder().oauth_session(session).build());
+//! let markdown = md
+//!     .convert_url("https://contoso.sharepoint.com/:w:/s/team/EaBcDeFgHiJk")
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::filter::host_matches;
+use crate::google_auth::DRIVE_READONLY_SCOPE;
+use crate::types::{AuthErrorKind, ConverterErrorKind, ErrorContext, MarkdownError};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Mutex;
+
+/// Google's device authorization endpoint
+const GOOGLE_DEVICE_AUTHORIZATION_URL: &str = "https://oauth2.googleapis.com/device/code";
+
+/// Google's token endpoint
+const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+
+/// Microsoft identity platform endpoints, under `/{tenant}/oauth2/v2.0/`
+const MICROSOFT_LOGIN_BASE_URL: &str = "https://login.microsoftonline.com";
+
+/// Grant type of device code token requests
+const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// Access tokens are refreshed this long before they expire
+const EXPIRY_MARGIN_SECONDS: i64 = 60;
+
+/// Polling interval used when the provider does not suggest one
+const DEFAULT_POLL_INTERVAL_SECONDS: u64 = 5;
+
+/// Seconds added to the polling interval when asked to slow down
+const SLOW_DOWN_SECONDS: u64 = 5;
+
+/// An OAuth application registered with Google or Microsoft.
+///
+/// Holds the application's client credentials, the scopes it requests, the
+/// provider's endpoints, and the hosts its access tokens are sent to.
+#[derive(Clone, PartialEq, Eq)]
+pub struct OAuthClient {
+    /// The application's client ID
+    pub client_id: String,
+    /// The application's client secret; Google requires one even for
+    /// installed applications, Microsoft public clients have none
+    pub client_secret: Option<String>,
+    /// Scopes requested at sign-in
+    pub scopes: Vec<String>,
+    /// Endpoint the device flow is started at
+    pub device_authorization_url: String,
+    /// Endpoint tokens are requested and refreshed at
+    pub token_url: String,
+    /// Host patterns access tokens are sent to; a leading `*.` also matches subdomains
+    pub hosts: Vec<String>,
+}
+
+impl OAuthClient {
+    /// Creates a client for a Google OAuth application of the "TVs and
+    /// Limited Input devices" type, requesting read access to Drive files.
+    ///
+    /// Tokens are sent to Google Docs, Google Drive, and `googleapis.com`.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_id` - The application's client ID
+    /// * `client_secret` - The application's client secret
+    pub fn google<I: Into<String>, S: Into<String>>(client_id: I, client_secret: S) -> Self {
+        Self {
+            client_id: client_id.into(),
+            client_secret: Some(client_secret.into()),
+            scopes: vec![DRIVE_READONLY_SCOPE.to_string()],
+            device_authorization_url: GOOGLE_DEVICE_AUTHORIZATION_URL.to_string(),
+            token_url: GOOGLE_TOKEN_URL.to_string(),
+            hosts: vec![
+                "docs.google.com".to_string(),
+                "drive.google.com".to_string(),
+                "*.googleapis.com".to_string(),
+            ],
+        }
+    }
+
+    /// Creates a client for a Microsoft Entra public client application,
+    /// requesting read access to OneDrive and SharePoint files.
+    ///
+    /// Tokens are sent to Microsoft Graph and SharePoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `tenant` - The directory tenant ID or domain, or `common`,
+    ///   `organizations`, or `consumers`
+    /// * `client_id` - The application's client ID
+    pub fn microsoft<T: AsRef<str>, I: Into<String>>(tenant: T, client_id: I) -> Self {
+        let base = format!("{MICROSOFT_LOGIN_BASE_URL}/{}/oauth2/v2.0", tenant.as_ref());
+        Self {
+            client_id: client_id.into(),
+            client_secret: None,
+            scopes: ["offline_access", "Files.Read.All", "Sites.Read.All"]
+                .map(String::from)
+                .to_vec(),
+            device_authorization_url: format!("{base}/devicecode"),
+            token_url: format!("{base}/token"),
+            hosts: vec![
+                "graph.microsoft.com".to_string(),
+                "*.sharepoint.com".to_string(),
+            ],
+        }
+    }
+
+    /// Replaces the scopes requested at sign-in.
+    pub fn scopes<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Replaces the hosts access tokens are sent to.
+    pub fn hosts<I, S>(mut self, hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.hosts = hosts
+            .into_iter()
+            .map(|host| host.into().to_ascii_lowercase())
+            .collect();
+        self
+    }
+
+    /// Uses different provider endpoints (primarily for testing).
+    ///
+    /// # Arguments
+    ///
+    /// * `device_authorization_url` - The endpoint the device flow is started at
+    /// * `token_url` - The endpoint tokens are requested at
+    pub fn endpoints<D: Into<String>, T: Into<String>>(
+        mut self,
+        device_authorization_url: D,
+        token_url: T,
+    ) -> Self {
+        self.device_authorization_url = device_authorization_url.into();
+        self.token_url = token_url.into();
+        self
+    }
+
+    /// Returns true if access tokens are sent to the host.
+    pub fn applies_to(&self, host: &str) -> bool {
+        let host = host.to_ascii_lowercase();
+        self.hosts
+            .iter()
+            .any(|pattern| host_matches(&host, pattern))
+    }
+
+    /// Starts the device authorization flow.
+    ///
+    /// Show the returned user code and verification URI to the user, then
+    /// call [`OAuthClient::poll_device_authorization`] to wait for them to
+    /// sign in.
+    ///
+    /// # Arguments
+    ///
+    /// * `http` - HTTP client the request is sent with
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::NetworkError` - If the endpoint cannot be reached
+    /// * `MarkdownError::AuthenticationError` - If the provider rejects the application
+    pub async fn start_device_authorization(
+        &self,
+        http: &reqwest::Client,
+    ) -> Result<DeviceAuthorization, MarkdownError> {
+        let scope = self.scopes.join(" ");
+        let mut form = vec![("client_id", self.client_id.as_str())];
+        if !scope.is_empty() {
+            form.push(("scope", scope.as_str()));
+        }
+
+        let body = self
+            .post_form(
+                http,
+                &self.device_authorization_url,
+                &form,
+                "Start device authorization",
+            )
+            .await?
+            .map_err(|error| {
+                error.into_markdown_error(
+                    &self.device_authorization_url,
+                    "Start device authorization",
+                )
+            })?;
+        serde_json::from_str(&body).map_err(|e| {
+            unexpected_response(
+                &self.device_authorization_url,
+                "Start device authorization",
+                e,
+            )
+        })
+    }
+
+    /// Waits for the user to complete a device authorization, returning their tokens.
+    ///
+    /// Polls the token endpoint at the interval the provider asked for,
+    /// slowing down when told to.
+    ///
+    /// # Arguments
+    ///
+    /// * `http` - HTTP client the requests are sent with
+    /// * `authorization` - The authorization started with
+    ///   [`OAuthClient::start_device_authorization`]
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::AuthenticationError` - If the user declines
+    ///   (`PermissionDenied`) or the code expires first (`TokenExpired`)
+    /// * `MarkdownError::NetworkError` - If the endpoint cannot be reached
+    pub async fn poll_device_authorization(
+        &self,
+        http: &reqwest::Client,
+        authorization: &DeviceAuthorization,
+    ) -> Result<OAuthToken, MarkdownError> {
+        let operation = "Poll device authorization";
+        let deadline = Instant::now() + std::time::Duration::from_secs(authorization.expires_in);
+        let mut interval = authorization.interval;
+        let mut form = vec![
+            ("grant_type", DEVICE_CODE_GRANT_TYPE),
+            ("device_code", authorization.device_code.as_str()),
+            ("client_id", self.client_id.as_str()),
+        ];
+        if let Some(secret) = &self.client_secret {
+            form.push(("client_secret", secret.as_str()));
+        }
+
+        loop {
+            match self
+                .post_form(http, &self.token_url, &form, operation)
+                .await?
+            {
+                Ok(body) => return self.parse_token(&body, None, operation),
+                Err(error) if error.error == "authorization_pending" => {}
+                Err(error) if error.error == "slow_down" => interval += SLOW_DOWN_SECONDS,
+                Err(error) => return Err(error.into_markdown_error(&self.token_url, operation)),
+            }
+            if Instant::now() >= deadline {
+                return Err(auth_error(
+                    AuthErrorKind::TokenExpired,
+                    &self.token_url,
+                    operation,
+                    "The device code expired before the user signed in".to_string(),
+                ));
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+        }
+    }
+
+    /// Exchanges a refresh token for a new access token.
+    ///
+    /// The returned token keeps `refresh_token` when the provider does not
+    /// issue a new one.
+    ///
+    /// # Arguments
+    ///
+    /// * `http` - HTTP client the request is sent with
+    /// * `refresh_token` - The refresh token from an earlier sign-in
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::AuthenticationError` - `TokenExpired` if the refresh
+    ///   token has expired or been revoked
+    /// * `MarkdownError::NetworkError` - If the endpoint cannot be reached
+    pub async fn refresh(
+        &self,
+        http: &reqwest::Client,
+        refresh_token: &str,
+    ) -> Result<OAuthToken, MarkdownError> {
+        let operation = "Refresh access token";
+        let scope = self.scopes.join(" ");
+        let mut form = vec![
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+            ("client_id", self.client_id.as_str()),
+        ];
+        if let Some(secret) = &self.client_secret {
+            form.push(("client_secret", secret.as_str()));
+        }
+        if !scope.is_empty() {
+            form.push(("scope", scope.as_str()));
+        }
+
+        match self
+            .post_form(http, &self.token_url, &form, operation)
+            .await?
+        {
+            Ok(body) => self.parse_token(&body, Some(refresh_token), operation),
+            Err(error) if error.error == "invalid_grant" => Err(auth_error(
+                AuthErrorKind::TokenExpired,
+                &self.token_url,
+                operation,
+                format!("The refresh token is no longer valid: {error}"),
+            )),
+            Err(error) => Err(error.into_markdown_error(&self.token_url, operation)),
+        }
+    }
+
+    /// Posts a form, returning the body of a successful response or the
+    /// OAuth error of a failed one.
+    async fn post_form(
+        &self,
+        http: &reqwest::Client,
+        url: &str,
+        form: &[(&str, &str)],
+        operation: &str,
+    ) -> Result<Result<String, ErrorResponse>, MarkdownError> {
+        let response = http
+            .post(url)
+            .header("Accept", "application/json")
+            .form(form)
+            .send()
+            .await
+            .map_err(|e| MarkdownError::NetworkError {
+                message: format!("{operation} failed: {e}"),
+            })?;
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if status.is_success() {
+            return Ok(Ok(body));
+        }
+        Ok(Err(serde_json::from_str(&body).unwrap_or_else(|_| {
+            ErrorResponse {
+                error: format!("http_{}", status.as_u16()),
+                error_description: Some(body.trim().to_string()),
+            }
+        })))
+    }
+
+    /// Parses a token endpoint response.
+    fn parse_token(
+        &self,
+        body: &str,
+        refresh_token: Option<&str>,
+        operation: &str,
+    ) -> Result<OAuthToken, MarkdownError> {
+        let response: TokenResponse = serde_json::from_str(body)
+            .map_err(|e| unexpected_response(&self.token_url, operation, e))?;
+        Ok(OAuthToken {
+            access_token: response.access_token,
+            refresh_token: response
+                .refresh_token
+                .or_else(|| refresh_token.map(str::to_string)),
+            expires_at: response
+                .expires_in
+                .map(|seconds| Utc::now() + Duration::seconds(seconds)),
+        })
+    }
+}
+
+// The client secret is never printed
+impl fmt::Debug for OAuthClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OAuthClient")
+            .field("client_id", &self.client_id)
+            .field("scopes", &self.scopes)
+            .field("token_url", &self.token_url)
+            .field("hosts", &self.hosts)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A device authorization in progress, as returned by the provider.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DeviceAuthorization {
+    /// Code the token endpoint is polled with
+    pub device_code: String,
+    /// Code the user enters at the verification URI
+    pub user_code: String,
+    /// Page the user signs in at
+    #[serde(alias = "verification_url")]
+    pub verification_uri: String,
+    /// Page the user signs in at with the code filled in, if the provider offers one
+    #[serde(default)]
+    pub verification_uri_complete: Option<String>,
+    /// Seconds until the codes expire
+    pub expires_in: u64,
+    /// Seconds to wait between polls
+    #[serde(default = "default_poll_interval")]
+    pub interval: u64,
+}
+
+fn default_poll_interval() -> u64 {
+    DEFAULT_POLL_INTERVAL_SECONDS
+}
+
+/// Tokens issued to a signed-in user.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OAuthToken {
+    /// Token sent with requests
+    pub access_token: String,
+    /// Token exchanged for new access tokens, if the provider issued one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    /// When the access token expires (None if the provider did not say)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl OAuthToken {
+    /// Returns true if the access token can still be used at `now`.
+    pub fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at
+            .is_none_or(|expires_at| expires_at - Duration::seconds(EXPIRY_MARGIN_SECONDS) > now)
+    }
+}
+
+// Tokens are never printed
+impl fmt::Debug for OAuthToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OAuthToken")
+            .field("has_refresh_token", &self.refresh_token.is_some())
+            .field("expires_at", &self.expires_at)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Token endpoint success response.
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<i64>,
+}
+
+/// OAuth error response, as defined by RFC 6749.
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    error: String,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+impl fmt::Display for ErrorResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.error_description {
+            Some(description) => write!(f, "{} ({})", self.error, description),
+            None => write!(f, "{}", self.error),
+        }
+    }
+}
+
+impl ErrorResponse {
+    /// Converts the error to the authentication error it stands for.
+    fn into_markdown_error(self, url: &str, operation: &str) -> MarkdownError {
+        let kind = match self.error.as_str() {
+            "access_denied" => AuthErrorKind::PermissionDenied,
+            "expired_token" | "invalid_grant" => AuthErrorKind::TokenExpired,
+            _ => AuthErrorKind::InvalidToken,
+        };
+        auth_error(kind, url, operation, format!("OAuth error: {self}"))
+    }
+}
+
+fn auth_error(kind: AuthErrorKind, url: &str, operation: &str, info: String) -> MarkdownError {
+    MarkdownError::AuthenticationError {
+        kind,
+        context: ErrorContext::new(url, operation, "OAuth").with_info(info),
+    }
+}
+
+fn unexpected_response(url: &str, operation: &str, error: serde_json::Error) -> MarkdownError {
+    auth_error(
+        AuthErrorKind::InvalidToken,
+        url,
+        operation,
+        format!("Unexpected response: {error}"),
+    )
+}
+
+/// Storage that keeps a session's tokens between runs.
+///
+/// Implement this to keep tokens in a keychain or secrets manager.
+/// [`FileTokenStore`] and [`MemoryTokenStore`] are provided.
+pub trait TokenStore: Send + Sync {
+    /// Returns the stored tokens, if any.
+    fn load(&self) -> Result<Option<OAuthToken>, MarkdownError>;
+
+    /// Replaces the stored tokens.
+    fn save(&self, token: &OAuthToken) -> Result<(), MarkdownError>;
+}
+
+/// Keeps tokens in memory only, so users sign in again in each process.
+#[derive(Debug, Default)]
+pub struct MemoryTokenStore {
+    token: std::sync::Mutex<Option<OAuthToken>>,
+}
+
+impl MemoryTokenStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a store holding tokens obtained elsewhere.
+    pub fn with_token(token: OAuthToken) -> Self {
+        Self {
+            token: std::sync::Mutex::new(Some(token)),
+        }
+    }
+}
+
+impl TokenStore for MemoryTokenStore {
+    fn load(&self) -> Result<Option<OAuthToken>, MarkdownError> {
+        Ok(self.token.lock().unwrap().clone())
+    }
+
+    fn save(&self, token: &OAuthToken) -> Result<(), MarkdownError> {
+        *self.token.lock().unwrap() = Some(token.clone());
+        Ok(())
+    }
+}
+
+/// Keeps tokens in a JSON file, readable only by its owner on Unix.
+#[derive(Debug, Clone)]
+pub struct FileTokenStore {
+    path: PathBuf,
+}
+
+impl FileTokenStore {
+    /// Creates a store for a file, which need not exist yet.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Returns the file tokens are kept in.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn io_error(&self, operation: &str, e: std::io::Error) -> MarkdownError {
+        MarkdownError::ConverterError {
+            kind: ConverterErrorKind::ProcessingError,
+            context: ErrorContext::new(
+                self.path.display().to_string(),
+                operation,
+                "FileTokenStore",
+            )
+            .with_info(format!("IO error: {e}")),
+        }
+    }
+}
+
+impl TokenStore for FileTokenStore {
+    fn load(&self) -> Result<Option<OAuthToken>, MarkdownError> {
+        let json = match std::fs::read_to_string(&self.path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(self.io_error("Load OAuth token", e)),
+        };
+        serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| MarkdownError::ParseError {
+                message: format!("Invalid OAuth token file {}: {e}", self.path.display()),
+            })
+    }
+
+    fn save(&self, token: &OAuthToken) -> Result<(), MarkdownError> {
+        use std::io::Write;
+
+        let json = serde_json::to_string_pretty(token).map_err(|e| MarkdownError::ParseError {
+            message: format!("Failed to serialize OAuth token: {e}"),
+        })?;
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| self.io_error("Save OAuth token", e))?;
+        }
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(json.as_bytes()))
+            .map_err(|e| self.io_error("Save OAuth token", e))
+    }
+}
+
+/// The tokens a session is using, and when they were obtained.
+struct CurrentToken {
+    token: OAuthToken,
+    /// When the token was issued to this process (None if loaded from the store)
+    obtained: Option<Instant>,
+}
+
+/// A signed-in user's tokens for one OAuth application.
+///
+/// Loads tokens from its store on first use, refreshes the access token
+/// when it expires, and saves refreshed tokens back to the store. Clones
+/// share the same tokens.
+#[derive(Clone)]
+pub struct OAuthSession {
+    client: OAuthClient,
+    store: Arc<dyn TokenStore>,
+    current: Arc<Mutex<Option<CurrentToken>>>,
+}
+
+impl OAuthSession {
+    /// Creates a session for an application, keeping tokens in a store.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The OAuth application
+    /// * `store` - Where tokens are loaded from and saved to
+    pub fn new<S: TokenStore + 'static>(client: OAuthClient, store: S) -> Self {
+        Self {
+            client,
+            store: Arc::new(store),
+            current: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Returns the session's OAuth application.
+    pub fn client(&self) -> &OAuthClient {
+        &self.client
+    }
+
+    /// Returns true if the store holds tokens for the session.
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::ConverterError` or `MarkdownError::ParseError` - If
+    ///   the store cannot be read
+    pub fn is_signed_in(&self) -> Result<bool, MarkdownError> {
+        Ok(self.store.load()?.is_some())
+    }
+
+    /// Signs the user in with the device authorization flow and stores their tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `http` - HTTP client the requests are sent with
+    /// * `prompt` - Called once with the code to show the user
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::AuthenticationError` - If the user declines or the code expires
+    /// * `MarkdownError::NetworkError` - If the provider cannot be reached
+    pub async fn sign_in_with_device_code(
+        &self,
+        http: &reqwest::Client,
+        prompt: impl FnOnce(&DeviceAuthorization),
+    ) -> Result<(), MarkdownError> {
+        let authorization = self.client.start_device_authorization(http).await?;
+        prompt(&authorization);
+        let token = self
+            .client
+            .poll_device_authorization(http, &authorization)
+            .await?;
+        self.replace(&mut *self.current.lock().await, token)
+    }
+
+    /// Returns an access token, refreshing the stored one if it has expired.
+    ///
+    /// # Arguments
+    ///
+    /// * `http` - HTTP client refresh requests are sent with
+    ///
+    /// # Errors
+    ///
+    /// * `MarkdownError::AuthenticationError` - `MissingToken` if the user
+    ///   has not signed in, `TokenExpired` if the token has expired and
+    ///   cannot be refreshed
+    pub async fn access_token(&self, http: &reqwest::Client) -> Result<String, MarkdownError> {
+        let mut current = self.current.lock().await;
+        if current.is_none() {
+            *current = self.store.load()?.map(|token| CurrentToken {
+                token,
+                obtained: None,
+            });
+        }
+        match current.as_ref() {
+            Some(entry) if entry.token.is_fresh(Utc::now()) => Ok(entry.token.access_token.clone()),
+            Some(_) => self.refresh_locked(http, &mut current).await,
+            None => Err(auth_error(
+                AuthErrorKind::MissingToken,
+                &self.client.token_url,
+                "Load OAuth token",
+                "No OAuth token is stored; sign in with the device flow first".to_string(),
+            )),
+        }
+    }
+
+    /// Refreshes the access token after a server rejected one obtained
+    /// before `rejected_at`.
+    ///
+    /// Does nothing if the token was already replaced since then, so
+    /// concurrent requests rejected together refresh only once.
+    pub(crate) async fn refresh_rejected(
+        &self,
+        http: &reqwest::Client,
+        rejected_at: Instant,
+    ) -> Result<(), MarkdownError> {
+        let mut current = self.current.lock().await;
+        if current
+            .as_ref()
+            .and_then(|entry| entry.obtained)
+            .is_some_and(|obtained| obtained > rejected_at)
+        {
+            return Ok(());
+        }
+        self.refresh_locked(http, &mut current).await.map(|_| ())
+    }
+
+    /// Refreshes the current token, returning the new access token.
+    async fn refresh_locked(
+        &self,
+        http: &reqwest::Client,
+        current: &mut Option<CurrentToken>,
+    ) -> Result<String, MarkdownError> {
+        let refresh_token = current
+            .as_ref()
+            .and_then(|entry| entry.token.refresh_token.clone())
+            .ok_or_else(|| {
+                auth_error(
+                    AuthErrorKind::TokenExpired,
+                    &self.client.token_url,
+                    "Refresh access token",
+                    "The access token expired and no refresh token is stored; sign in again"
+                        .to_string(),
+                )
+            })?;
+        let token = self.client.refresh(http, &refresh_token).await?;
+        let access_token = token.access_token.clone();
+        self.replace(current, token)?;
+        Ok(access_token)
+    }
+
+    /// Saves new tokens and makes them current.
+    fn replace(
+        &self,
+        current: &mut Option<CurrentToken>,
+        token: OAuthToken,
+    ) -> Result<(), MarkdownError> {
+        self.store.save(&token)?;
+        *current = Some(CurrentToken {
+            token,
+            obtained: Some(Instant::now()),
+        });
+        Ok(())
+    }
+}
+
+impl fmt::Debug for OAuthSession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OAuthSession")
+            .field("client", &self.client)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_client(server: &MockServer) -> OAuthClient {
+        OAuthClient::microsoft("common", "app-id").endpoints(
+            format!("{}/devicecode", server.uri()),
+            format!("{}/token", server.uri()),
+        )
+    }
+
+    #[test]
+    fn test_provider_hosts() {
+        let google = OAuthClient::google("id", "secret");
+        assert!(google.applies_to("docs.google.com"));
+        assert!(google.applies_to("www.googleapis.com"));
+        assert!(!google.applies_to("graph.microsoft.com"));
+
+        let microsoft = OAuthClient::microsoft("contoso.onmicrosoft.com", "id");
+        assert!(microsoft.applies_to("contoso.sharepoint.com"));
+        assert!(microsoft.applies_to("graph.microsoft.com"));
+        assert_eq!(
+            microsoft.token_url,
+            "https://login.microsoftonline.com/contoso.onmicrosoft.com/oauth2/v2.0/token"
+        );
+        assert!(!format!("{google:?}").contains("secret"));
+    }
+
+    #[tokio::test]
+    async fn test_device_code_sign_in() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/devicecode"))
+            .and(body_string_contains("client_id=app-id"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"device_code": "dev-123", "user_code": "ABCD-EFGH",
+                    "verification_url": "https://example.com/device",
+                    "expires_in": 900, "interval": 0}"#,
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(body_string_contains("device_code=dev-123"))
+            .respond_with(
+                ResponseTemplate::new(400).set_body_string(r#"{"error": "authorization_pending"}"#),
+            )
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"access_token": "at-1", "refresh_token": "rt-1", "expires_in": 3600}"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let store = Arc::new(MemoryTokenStore::new());
+        let session = OAuthSession {
+            client: test_client(&server),
+            store: store.clone(),
+            current: Arc::new(Mutex::new(None)),
+        };
+        let mut prompted = None;
+        session
+            .sign_in_with_device_code(&reqwest::Client::new(), |authorization| {
+                prompted = Some(authorization.user_code.clone());
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(prompted.as_deref(), Some("ABCD-EFGH"));
+        let stored = store.load().unwrap().unwrap();
+        assert_eq!(stored.access_token, "at-1");
+        assert_eq!(stored.refresh_token.as_deref(), Some("rt-1"));
+    }
+
+    #[tokio::test]
+    async fn test_expired_token_is_refreshed_and_saved() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(body_string_contains("grant_type=refresh_token"))
+            .and(body_string_contains("refresh_token=rt-1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"{"access_token": "at-2", "expires_in": 3600}"#),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileTokenStore::new(dir.path().join("tokens").join("microsoft.json"));
+        store
+            .save(&OAuthToken {
+                access_token: "at-1".to_string(),
+                refresh_token: Some("rt-1".to_string()),
+                expires_at: Some(Utc::now() - Duration::minutes(5)),
+            })
+            .unwrap();
+
+        let session = OAuthSession::new(test_client(&server), store.clone());
+        let http = reqwest::Client::new();
+        assert_eq!(session.access_token(&http).await.unwrap(), "at-2");
+        assert_eq!(session.access_token(&http).await.unwrap(), "at-2");
+
+        // The provider did not rotate the refresh token, so it is kept
+        let saved = store.load().unwrap().unwrap();
+        assert_eq!(saved.access_token, "at-2");
+        assert_eq!(saved.refresh_token.as_deref(), Some("rt-1"));
+    }
+
+    #[tokio::test]
+    async fn test_revoked_refresh_token_is_token_expired() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .respond_with(ResponseTemplate::new(400).set_body_string(
+                r#"{"error": "invalid_grant", "error_description": "Token has been revoked"}"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let expired = OAuthToken {
+            access_token: "at-1".to_string(),
+            refresh_token: Some("rt-1".to_string()),
+            expires_at: Some(Utc::now()),
+        };
+        let session =
+            OAuthSession::new(test_client(&server), MemoryTokenStore::with_token(expired));
+        match session.access_token(&reqwest::Client::new()).await {
+            Err(MarkdownError::AuthenticationError { kind, context }) => {
+                assert_eq!(kind, AuthErrorKind::TokenExpired);
+                assert!(context.additional_info.unwrap().contains("revoked"));
+            }
+            other => panic!("Expected TokenExpired, got {other:?}"),
+        }
+
+        let signed_out = OAuthSession::new(test_client(&server), MemoryTokenStore::new());
+        assert!(!signed_out.is_signed_in().unwrap());
+        assert!(matches!(
+            signed_out.access_token(&reqwest::Client::new()).await,
+            Err(MarkdownError::AuthenticationError {
+                kind: AuthErrorKind::MissingToken,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,35 @@
+//! The stable public API.
+//!
+//! Everything re-exported here follows semantic versioning: it is only
+//! changed incompatibly in a major release. Glob-import it to get the types
+//! most applications need:
+//!
+//! ```rust
+//! use markdowndown::prelude::*;
+//!
+//! # async fn example() -> Result<(), MarkdownError> {
+//! let md = MarkdownDown::with_config(Config::builder().timeout_seconds(60).build());
+//! let markdown: Markdown = md.convert_url("https://example.com/article").await?;
+//! match detect_url_type("https://github.com/owner/repo/issues/1")? {
+//!     UrlType::GitHubIssue => println!("issue: {} chars", markdown.as_str().len()),
+//!     _ => {}
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Items outside the prelude, such as the individual converters in
+//! [`crate::converters`] and the HTML pre- and postprocessing helpers, are
+//! experimental and may change in a minor release. Enums that will grow, like
+//! [`UrlType`] and the error kinds, are `#[non_exhaustive]`, so matches on
+//! them need a wildcard arm.
+
+pub use crate::config::Config;
+pub use crate::converters::Converter;
+pub use crate::register_converter;
+pub use crate::types::{
+    AuthErrorKind, ConfigErrorKind, ContentErrorKind, ConversionResult, ConverterErrorKind,
+    ErrorContext, Markdown, MarkdownError, NetworkErrorKind, SkipReason, UrlType,
+    ValidationErrorKind,
+};
+pub use crate::{convert_url, convert_url_with_config, detect_url_type, MarkdownDown};
@@ -0,0 +1,273 @@
+//! Per-stage timing and buffer sizes of a single conversion.
+//!
+//! With `Config::builder().profiling(true)`,
+//! [`crate::MarkdownDown::convert_url_detailed`] returns a
+//! [`ConversionProfile`] alongside the markdown. It splits the conversion's
+//! wall-clock time into four stages:
+//!
+//! - **fetch**: HTTP requests, including retries and reading the body
+//! - **preprocess**: cleaning HTML before it is converted
+//! - **convert**: the converter's remaining work, such as html2text or PDF text extraction
+//! - **postprocess**: markdown cleanup, includes, language and direction
+//!   handling, identity stamping, and linting
+//!
+//! Each stage also records the bytes it produced: response bodies, cleaned
+//! HTML, and markdown. These are the buffers a conversion allocates, so
+//! they approximate its memory use without an instrumented allocator.
+//!
+//! Measurements are collected for the task running the conversion. Work a
+//! converter hands to another task is counted in the stage that awaits it.
+//!
+//! # Usage Examples
+//!
+//! ```rust,no_run
+//! use markdowndown::{Config, MarkdownDown};
+//!
+//! # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
+//! let md = MarkdownDown::with_config(Config::builder().profiling(true).build());
+//! let result = md.convert_url_detailed("https://example.com/slow-page").await?;
+//! if let Some(profile) = &result.profile {
+//!     println!("{profile}");
+//!     println!("Slowest stage: {}", profile.slowest_stage());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A stage of the conversion pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Stage {
+    /// HTTP requests and response bodies
+    Fetch,
+    /// HTML cleanup before conversion
+    Preprocess,
+    /// Converting the source document to markdown
+    Convert,
+    /// Markdown cleanup and the pipeline steps after conversion
+    Postprocess,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Fetch => write!(f, "fetch"),
+            Stage::Preprocess => write!(f, "preprocess"),
+            Stage::Convert => write!(f, "convert"),
+            Stage::Postprocess => write!(f, "postprocess"),
+        }
+    }
+}
+
+/// Time spent in one stage, and the bytes it produced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StageProfile {
+    /// Wall-clock time spent in the stage
+    pub duration: Duration,
+    /// Times the stage ran, such as the number of requests for fetch
+    pub calls: usize,
+    /// Bytes the stage produced
+    pub bytes: u64,
+}
+
+impl StageProfile {
+    fn add(&mut self, duration: Duration, bytes: u64) {
+        self.duration += duration;
+        self.calls += 1;
+        self.bytes += bytes;
+    }
+}
+
+/// Wall-clock time and approximate allocations of one conversion, by stage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConversionProfile {
+    /// HTTP requests and response bodies
+    pub fetch: StageProfile,
+    /// HTML cleanup before conversion
+    pub preprocess: StageProfile,
+    /// The converter's work other than fetching and HTML cleanup
+    pub convert: StageProfile,
+    /// Markdown cleanup and the pipeline steps after conversion
+    pub postprocess: StageProfile,
+    /// Wall-clock time of the whole conversion
+    pub total: Duration,
+}
+
+impl ConversionProfile {
+    /// Returns the profile of a stage.
+    pub fn stage(&self, stage: Stage) -> &StageProfile {
+        match stage {
+            Stage::Fetch => &self.fetch,
+            Stage::Preprocess => &self.preprocess,
+            Stage::Convert => &self.convert,
+            Stage::Postprocess => &self.postprocess,
+        }
+    }
+
+    /// Returns the stage that took the longest.
+    pub fn slowest_stage(&self) -> Stage {
+        [
+            Stage::Fetch,
+            Stage::Preprocess,
+            Stage::Convert,
+            Stage::Postprocess,
+        ]
+        .into_iter()
+        .max_by_key(|stage| self.stage(*stage).duration)
+        .unwrap_or(Stage::Fetch)
+    }
+
+    /// Returns the bytes produced across every stage, an approximation of
+    /// what the conversion allocated.
+    pub fn allocated_bytes(&self) -> u64 {
+        self.fetch.bytes + self.preprocess.bytes + self.convert.bytes + self.postprocess.bytes
+    }
+
+    fn stage_mut(&mut self, stage: Stage) -> &mut StageProfile {
+        match stage {
+            Stage::Fetch => &mut self.fetch,
+            Stage::Preprocess => &mut self.preprocess,
+            Stage::Convert => &mut self.convert,
+            Stage::Postprocess => &mut self.postprocess,
+        }
+    }
+}
+
+impl fmt::Display for ConversionProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "total {:?}", self.total)?;
+        for stage in [
+            Stage::Fetch,
+            Stage::Preprocess,
+            Stage::Convert,
+            Stage::Postprocess,
+        ] {
+            let profile = self.stage(stage);
+            write!(
+                f,
+                ", {stage} {:?} ({} bytes)",
+                profile.duration, profile.bytes
+            )?;
+        }
+        Ok(())
+    }
+}
+
+tokio::task_local! {
+    static PROFILE: Arc<Mutex<ConversionProfile>>;
+}
+
+/// Runs a conversion, collecting the stages recorded while it runs.
+pub(crate) async fn profile<T>(conversion: impl Future<Output = T>) -> (T, ConversionProfile) {
+    let profile = Arc::new(Mutex::new(ConversionProfile::default()));
+    let started = Instant::now();
+    let output = PROFILE.scope(profile.clone(), conversion).await;
+    let mut profile = *profile.lock().unwrap_or_else(|e| e.into_inner());
+    profile.total = started.elapsed();
+    (output, profile)
+}
+
+/// Records time spent in a stage since `started`, and the bytes it produced.
+///
+/// Does nothing unless the current task is collecting a profile.
+pub(crate) fn record(stage: Stage, started: Instant, bytes: usize) {
+    add(stage, started.elapsed(), bytes);
+}
+
+/// Returns the time recorded so far across every stage.
+///
+/// Taken before a region that records its own stage with
+/// [`record_excluding_nested`].
+pub(crate) fn recorded_time() -> Duration {
+    PROFILE
+        .try_with(|profile| {
+            let profile = profile.lock().unwrap_or_else(|e| e.into_inner());
+            profile.fetch.duration
+                + profile.preprocess.duration
+                + profile.convert.duration
+                + profile.postprocess.duration
+        })
+        .unwrap_or_default()
+}
+
+/// Records time spent in a stage since `started`, less the time other
+/// stages recorded in the meantime.
+///
+/// A converter fetches and cleans HTML while it runs; those stages record
+/// themselves, and only the rest is counted as convert time.
+///
+/// # Arguments
+///
+/// * `stage` - The stage the region belongs to
+/// * `started` - When the region started
+/// * `recorded_before` - [`recorded_time`] when the region started
+/// * `bytes` - The bytes the region produced
+pub(crate) fn record_excluding_nested(
+    stage: Stage,
+    started: Instant,
+    recorded_before: Duration,
+    bytes: usize,
+) {
+    let nested = recorded_time().saturating_sub(recorded_before);
+    add(stage, started.elapsed().saturating_sub(nested), bytes);
+}
+
+/// Adds a measurement to the current task's profile, if it is collecting one.
+fn add(stage: Stage, duration: Duration, bytes: usize) {
+    let _ = PROFILE.try_with(|profile| {
+        profile
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .stage_mut(stage)
+            .add(duration, bytes as u64);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_profile_collects_stages() {
+        let ((), profile) = profile(async {
+            let recorded_before = recorded_time();
+            let convert_started = Instant::now();
+
+            let started = Instant::now();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            record(Stage::Fetch, started, 1000);
+            record(Stage::Preprocess, Instant::now(), 800);
+
+            record_excluding_nested(Stage::Convert, convert_started, recorded_before, 300);
+            record(Stage::Postprocess, Instant::now(), 320);
+        })
+        .await;
+
+        assert_eq!(profile.fetch.calls, 1);
+        assert!(profile.fetch.duration >= Duration::from_millis(20));
+        // Fetching inside the converter is not counted as convert time
+        assert!(profile.convert.duration < Duration::from_millis(20));
+        assert_eq!(profile.slowest_stage(), Stage::Fetch);
+        assert_eq!(profile.allocated_bytes(), 2420);
+        assert!(profile.total >= profile.fetch.duration);
+        assert!(profile.to_string().contains("fetch"));
+    }
+
+    #[tokio::test]
+    async fn test_recording_outside_profile_is_ignored() {
+        record(Stage::Fetch, Instant::now(), 10);
+        assert_eq!(recorded_time(), Duration::ZERO);
+
+        let (recorded, _) = profile(async {
+            record(Stage::Fetch, Instant::now(), 10);
+            recorded_time()
+        })
+        .await;
+        assert!(recorded < Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,151 @@
+//! Progress reporting for slow conversions and batches.
+//!
+//! A [`ProgressObserver`] set with
+//! [`crate::MarkdownDown::with_progress_observer`] is told what each
+//! conversion is doing as it happens, so command line and GUI front ends can
+//! show progress. Every event names the URL being converted, so one observer
+//! can follow the conversions of a batch, crawl, or sitemap at once.
+//!
+//! A conversion reports [`ProgressEvent::Converting`] when its converter
+//! starts. Converters fetch their source while they run, so
+//! [`ProgressEvent::Fetching`] and [`ProgressEvent::Retrying`] follow for
+//! each request they make, and downloads to a file report
+//! [`ProgressEvent::Downloading`] as the body arrives.
+//! [`ProgressEvent::Postprocessing`] and [`ProgressEvent::Done`] come last.
+//! A conversion that fails stops without reporting `Done`.
+//!
+//! # Usage Examples
+//!
+//! ```rust,no_run
+//! use markdowndown::progress::ProgressEvent;
+//! use markdowndown::MarkdownDown;
+//!
+//! # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
+//! let md = MarkdownDown::new().with_progress_observer(Box::new(
+//!     |url: &str, event: &ProgressEvent| match event {
+//!         ProgressEvent::Retrying { attempt, .. } => eprintln!("{url}: retry {attempt}"),
+//!         ProgressEvent::Done { bytes, chars } => {
+//!             eprintln!("{url}: {bytes} bytes fetched, {chars} characters written")
+//!         }
+//!         _ => {}
+//!     },
+//! ));
+//! md.convert_url("https://example.com/slow-page").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// A step of a conversion, reported to a [`ProgressObserver`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProgressEvent {
+    /// The converter has started
+    Converting,
+    /// A request is being sent
+    Fetching {
+        /// The URL requested, which may be an API URL rather than the one converted
+        url: String,
+    },
+    /// A request is being sent again after a failure
+    Retrying {
+        /// The URL requested
+        url: String,
+        /// The retry number, starting at 1
+        attempt: u32,
+    },
+    /// Part of a response body has been written to a file
+    Downloading {
+        /// The URL being downloaded
+        url: String,
+        /// Bytes written so far
+        bytes: u64,
+        /// Size of the whole body, when the server sent it
+        total: Option<u64>,
+    },
+    /// The converted markdown is being cleaned up and annotated
+    Postprocessing,
+    /// The conversion has finished
+    Done {
+        /// Response bytes fetched, not counting streamed bodies of unknown size
+        bytes: u64,
+        /// Length of the markdown in bytes
+        chars: usize,
+    },
+}
+
+/// Receives progress events from conversions.
+///
+/// Observers are called on the task running the conversion, so they should
+/// return quickly. Closures taking the converted URL and the event implement
+/// this trait.
+pub trait ProgressObserver: Send + Sync {
+    /// Called for each step of a conversion.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The normalized URL being converted
+    /// * `event` - What the conversion is doing
+    fn on_progress(&self, url: &str, event: &ProgressEvent);
+}
+
+impl<F> ProgressObserver for F
+where
+    F: Fn(&str, &ProgressEvent) + Send + Sync,
+{
+    fn on_progress(&self, url: &str, event: &ProgressEvent) {
+        self(url, event)
+    }
+}
+
+/// The observer and byte count of the conversion running on a task.
+struct Progress {
+    observer: Arc<dyn ProgressObserver>,
+    url: String,
+    bytes: AtomicU64,
+}
+
+tokio::task_local! {
+    static PROGRESS: Arc<Progress>;
+}
+
+/// Runs a conversion, reporting its progress to `observer`.
+pub(crate) async fn observe<T>(
+    observer: Arc<dyn ProgressObserver>,
+    url: &str,
+    conversion: impl Future<Output = T>,
+) -> T {
+    let progress = Arc::new(Progress {
+        observer,
+        url: url.to_string(),
+        bytes: AtomicU64::new(0),
+    });
+    PROGRESS.scope(progress, conversion).await
+}
+
+/// Reports an event for the conversion running on the current task.
+///
+/// Does nothing unless the current task is observing progress.
+pub(crate) fn report(event: ProgressEvent) {
+    let _ = PROGRESS.try_with(|progress| progress.observer.on_progress(&progress.url, &event));
+}
+
+/// Reports that the conversion on the current task is done.
+pub(crate) fn done(chars: usize) {
+    let _ = PROGRESS.try_with(|progress| {
+        let bytes = progress.bytes.load(Ordering::Relaxed);
+        progress
+            .observer
+            .on_progress(&progress.url, &ProgressEvent::Done { bytes, chars });
+    });
+}
+
+/// Adds fetched response bytes to the conversion on the current task.
+pub(crate) fn add_bytes(bytes: u64) {
+    let _ = PROGRESS.try_with(|progress| {
+        progress.bytes.fetch_add(bytes, Ordering::Relaxed);
+    });
+}
@@ -0,0 +1,170 @@
+//! Per-host request rate limits shared by every clone of the HTTP client.
+//!
+//! Retries back off after a server answers `429 Too Many Requests`, but by
+//! then the request has already been rejected, and batch and crawl runs with
+//! many tasks keep tripping the limit. A [`RateLimiter`] spaces requests out
+//! before they are sent: each [`RateLimit`] is a token bucket that refills at
+//! the configured rate, and a request to a host whose bucket is empty waits
+//! for its turn. [`crate::client::HttpClient`] applies the limits in
+//! [`crate::config::HttpConfig::rate_limits`] to every request it sends.
+//!
+//! A limit's bucket is shared by its clones, so every client created from
+//! one configuration, such as the instance each batch task creates, draws
+//! from the same budget.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::config::RateLimit;
+//! use markdowndown::rate_limit::RateLimiter;
+//!
+//! # async fn example() {
+//! let limiter = RateLimiter::new(vec![RateLimit::new("api.github.com", 1.0)]);
+//! limiter.acquire("api.github.com").await; // sent at once
+//! limiter.acquire("api.github.com").await; // about a second later
+//! limiter.acquire("example.com").await; // not limited
+//! # }
+//! ```
+
+use crate::config::RateLimit;
+use crate::filter::host_matches;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+use tracing::debug;
+
+/// Requests available now, as of a point in time.
+///
+/// Tokens go negative when requests are waiting, so each waiting request
+/// takes the next free slot.
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// The state of a [`RateLimit`]'s token bucket, shared by clones of the limit.
+///
+/// Sharing the state through the configuration lets every client built
+/// from it, such as one per task of a batch run, draw from one budget.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TokenBucket {
+    state: Arc<Mutex<Option<Bucket>>>,
+}
+
+impl TokenBucket {
+    /// Takes a token, returning how long to wait for it.
+    ///
+    /// The bucket starts full.
+    fn reserve(&self, rate: f64, burst: u32, now: Instant) -> Duration {
+        if !(rate > 0.0 && rate.is_finite()) {
+            return Duration::ZERO;
+        }
+        let burst = f64::from(burst.max(1));
+        let mut state = self.state.lock().unwrap();
+        let bucket = state.get_or_insert(Bucket {
+            tokens: burst,
+            updated_at: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * rate).min(burst);
+        bucket.updated_at = now;
+        bucket.tokens -= 1.0;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / rate)
+        }
+    }
+}
+
+/// The rate limits that apply to requests.
+///
+/// Cloning a limiter, or the limits it was created from, shares the buckets.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    limits: Arc<Vec<RateLimit>>,
+}
+
+impl RateLimiter {
+    /// Creates a limiter for a set of limits.
+    ///
+    /// # Arguments
+    ///
+    /// * `limits` - The limits to enforce; the first matching a host applies
+    pub fn new(limits: Vec<RateLimit>) -> Self {
+        Self {
+            limits: Arc::new(limits),
+        }
+    }
+
+    /// Returns the limit that applies to a host, if any.
+    pub fn limit_for(&self, host: &str) -> Option<&RateLimit> {
+        let host = host.to_ascii_lowercase();
+        self.limits
+            .iter()
+            .find(|limit| host_matches(&host, &limit.host))
+    }
+
+    /// Waits until a request to a host may be sent.
+    ///
+    /// Returns at once for hosts without a limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `host` - The host the request is sent to
+    pub async fn acquire(&self, host: &str) {
+        let Some(limit) = self.limit_for(host) else {
+            return;
+        };
+        let wait = reserve(limit, Instant::now());
+        if !wait.is_zero() {
+            debug!("Waiting {:?} for the {} rate limit", wait, limit.host);
+            sleep(wait).await;
+        }
+    }
+}
+
+/// Takes a token from a limit's bucket, returning how long to wait for it.
+fn reserve(limit: &RateLimit, now: Instant) -> Duration {
+    limit
+        .bucket
+        .reserve(limit.requests_per_second, limit.burst, now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requests_beyond_burst_wait_their_turn() {
+        let limiter = RateLimiter::new(vec![RateLimit::new("*.example.com", 2.0).burst(2)]);
+        let limit = limiter.limit_for("docs.example.com").unwrap();
+        // Clones, as in each task's copy of the configuration, share the bucket
+        let copy = limit.clone();
+        let now = Instant::now();
+
+        assert_eq!(reserve(limit, now), Duration::ZERO);
+        assert_eq!(reserve(&copy, now), Duration::ZERO);
+        assert_eq!(reserve(limit, now), Duration::from_millis(500));
+        assert_eq!(reserve(&copy, now), Duration::from_secs(1));
+
+        // Once the waiting requests have gone, tokens accumulate again
+        let later = now + Duration::from_millis(1500);
+        assert_eq!(reserve(limit, later), Duration::ZERO);
+        assert_eq!(reserve(limit, later), Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_unlimited_hosts_not_delayed() {
+        let limiter = RateLimiter::new(vec![RateLimit::new("api.github.com", 0.001)]);
+        assert!(limiter.limit_for("example.com").is_none());
+
+        let started = std::time::Instant::now();
+        for _ in 0..3 {
+            limiter.acquire("example.com").await;
+        }
+        limiter.acquire("API.github.com").await;
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
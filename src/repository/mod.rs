@@ -0,0 +1,404 @@
+//! Documentation conversion from git repositories.
+//!
+//! Documentation sites are built from markdown sources kept in the project's
+//! repository, such as the `docs/` folder of an MkDocs site or the `src/`
+//! folder of an mdBook. Converting those sources is more faithful than
+//! scraping the rendered site, so [`MarkdownDown::convert_repository`]
+//! shallow-clones a repository with the `git` command and converts every file
+//! matching a path pattern.
+//!
+//! Repositories are given as `git+https://` URLs or as anything `git clone`
+//! accepts, such as `https://github.com/owner/repo`, `git@host:owner/repo.git`,
+//! or a local path.
+//!
+//! mdBook and Docusaurus projects are recognized, so their pages come back in
+//! reading order with includes resolved and MDX syntax removed; see
+//! [`project`].
+//!
+//! [`MarkdownDown::convert_repository`]: crate::MarkdownDown::convert_repository
+//!
+//! # Usage Examples
+//!
+//! ```rust,no_run
+//! use markdowndown::repository::RepositoryOptions;
+//! use markdowndown::MarkdownDown;
+//!
+//! # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
+//! let md = MarkdownDown::new();
+//! let options = RepositoryOptions::new().docs_path("src/**/*.md");
+//! let files = md
+//!     .convert_repository("git+https://github.com/rust-lang/book.git", &options)
+//!     .await?;
+//! for file in &files {
+//!     match &file.result {
+//!         Ok(markdown) => println!("{}: {} chars", file.path, markdown.as_str().len()),
+//!         Err(e) => println!("{}: {}", file.path, e),
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+/// mdBook and Docusaurus project handling
+pub mod project;
+
+use crate::types::{
+    ConfigErrorKind, ConverterErrorKind, ErrorContext, Markdown, MarkdownError, ValidationErrorKind,
+};
+use project::Project;
+use std::path::Path;
+use tempfile::TempDir;
+use tracing::{debug, info};
+
+/// Files converted when no docs path is given.
+pub const DEFAULT_DOCS_PATH: &str = "docs/**/*.md";
+
+/// Options for converting the documentation in a repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepositoryOptions {
+    /// Pattern selecting the files to convert, relative to the repository root
+    pub docs_path: String,
+    /// Branch or tag to check out (None uses the default branch)
+    pub reference: Option<String>,
+    /// Whether to order and rewrite the pages of mdBook and Docusaurus projects
+    pub project_aware: bool,
+}
+
+impl RepositoryOptions {
+    /// Creates options converting `docs/**/*.md` on the default branch.
+    pub fn new() -> Self {
+        Self {
+            docs_path: DEFAULT_DOCS_PATH.to_string(),
+            reference: None,
+            project_aware: true,
+        }
+    }
+
+    /// Sets the pattern selecting the files to convert.
+    ///
+    /// Patterns are matched against paths relative to the repository root.
+    /// `*` matches within a path segment, `?` matches one character, and `**`
+    /// matches any number of directories.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The pattern, such as `docs/**/*.md` or `src/*.md`
+    pub fn docs_path<T: Into<String>>(mut self, pattern: T) -> Self {
+        self.docs_path = pattern.into();
+        self
+    }
+
+    /// Sets the branch or tag to check out.
+    ///
+    /// # Arguments
+    ///
+    /// * `reference` - The branch or tag name, such as `v2.0`
+    pub fn reference<T: Into<String>>(mut self, reference: T) -> Self {
+        self.reference = Some(reference.into());
+        self
+    }
+
+    /// Sets whether mdBook and Docusaurus projects are handled as such.
+    ///
+    /// When enabled (the default), a project found in the repository decides
+    /// the order of the results, mdBook includes are resolved, and Docusaurus
+    /// MDX syntax is removed. If the docs path is left at its default, the
+    /// project's own pages are converted instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_aware` - Whether to recognize documentation projects
+    pub fn project_aware(mut self, project_aware: bool) -> Self {
+        self.project_aware = project_aware;
+        self
+    }
+}
+
+impl Default for RepositoryOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The conversion of one file from a repository.
+#[derive(Debug)]
+pub struct RepositoryFile {
+    /// Path of the file relative to the repository root, with `/` separators
+    pub path: String,
+    /// The converted markdown, or why the file could not be converted
+    pub result: Result<Markdown, MarkdownError>,
+}
+
+/// Returns the URL to pass to `git clone`, removing a `git+` prefix.
+pub(crate) fn clone_url(url: &str) -> Result<String, MarkdownError> {
+    let trimmed = url.trim();
+    let clone_url = match trimmed.get(..4) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("git+") => &trimmed[4..],
+        _ => trimmed,
+    };
+
+    // A leading dash would be read as an option by git
+    if clone_url.is_empty() || clone_url.starts_with('-') {
+        let context = ErrorContext::new(url, "Repository URL validation", "convert_repository")
+            .with_info("Expected a git repository URL or path");
+        return Err(MarkdownError::ValidationError {
+            kind: ValidationErrorKind::InvalidUrl,
+            context,
+        });
+    }
+    Ok(clone_url.to_string())
+}
+
+/// Shallow-clones a repository into a temporary directory.
+///
+/// The directory is removed when the returned handle is dropped.
+pub(crate) async fn shallow_clone(
+    url: &str,
+    reference: Option<&str>,
+) -> Result<TempDir, MarkdownError> {
+    let clone_url = clone_url(url)?;
+    let tool_error = |info: String| MarkdownError::ConverterError {
+        kind: ConverterErrorKind::ExternalToolFailed,
+        context: ErrorContext::new(url, "git clone", "convert_repository").with_info(info),
+    };
+
+    let checkout = TempDir::new()
+        .map_err(|e| tool_error(format!("Failed to create checkout directory: {e}")))?;
+    let mut command = tokio::process::Command::new("git");
+    command
+        .args(["clone", "--depth", "1", "--quiet"])
+        // Fail instead of waiting for credentials on a terminal
+        .env("GIT_TERMINAL_PROMPT", "0");
+    if let Some(reference) = reference {
+        command.args(["--branch", reference]);
+    }
+    command.arg("--").arg(&clone_url).arg(checkout.path());
+
+    info!("Cloning {}", clone_url);
+    let output = match command.output().await {
+        Ok(output) => output,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let context = ErrorContext::new(url, "git clone", "convert_repository")
+                .with_info("Converting repositories requires git to be installed");
+            return Err(MarkdownError::ConfigurationError {
+                kind: ConfigErrorKind::MissingDependency,
+                context,
+            });
+        }
+        Err(e) => return Err(tool_error(format!("Failed to start git: {e}"))),
+    };
+    if !output.status.success() {
+        return Err(tool_error(format!(
+            "git exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(checkout)
+}
+
+/// Lists the files of a checkout to convert, in order, and prepares them for conversion.
+///
+/// Pages of a recognized documentation project are rewritten in place.
+pub(crate) fn prepare_files(checkout: &Path, options: &RepositoryOptions) -> Vec<String> {
+    let project = if options.project_aware {
+        Project::detect(checkout)
+    } else {
+        None
+    };
+    let Some(project) = project else {
+        return matching_files(checkout, &options.docs_path);
+    };
+
+    let mut files = if options.docs_path == DEFAULT_DOCS_PATH {
+        project.pages(checkout)
+    } else {
+        matching_files(checkout, &options.docs_path)
+    };
+    project.sort(&mut files);
+    for file in &files {
+        project.prepare(checkout, file);
+    }
+    files
+}
+
+/// Lists the files below `root` whose relative paths match `pattern`, sorted.
+///
+/// The `.git` directory is skipped and symbolic links are not followed.
+pub(crate) fn matching_files(root: &Path, pattern: &str) -> Vec<String> {
+    let pattern: Vec<&str> = pattern
+        .trim()
+        .trim_start_matches("./")
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let mut files = Vec::new();
+    collect_files(root, root, &pattern, &mut files);
+    files.sort();
+    files
+}
+
+fn collect_files(root: &Path, dir: &Path, pattern: &[&str], files: &mut Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        if file_type.is_dir() {
+            if entry.file_name() != ".git" {
+                collect_files(root, &path, pattern, files);
+            }
+        } else if file_type.is_file() {
+            let Ok(relative) = path.strip_prefix(root) else {
+                continue;
+            };
+            let segments: Vec<String> = relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+            if segments_match(pattern, &segments) {
+                debug!("Matched {}", segments.join("/"));
+                files.push(segments.join("/"));
+            }
+        }
+    }
+}
+
+/// Matches a `/`-separated path against a pattern with `*`, `?`, and `**` wildcards.
+pub(crate) fn glob_match(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split('/').collect();
+    let path: Vec<&str> = path.split('/').collect();
+    segments_match(&pattern, &path)
+}
+
+/// Matches path segments against pattern segments, where `**` spans directories.
+fn segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| segments_match(rest, &path[skip..])),
+        Some((segment, rest)) => path.split_first().is_some_and(|(name, path_rest)| {
+            let segment: Vec<char> = segment.chars().collect();
+            let name: Vec<char> = name.chars().collect();
+            wildcard_match(&segment, &name) && segments_match(rest, path_rest)
+        }),
+    }
+}
+
+/// Matches a file name against a pattern with `*` and `?` wildcards.
+fn wildcard_match(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skip| wildcard_match(rest, &name[skip..])),
+        Some(('?', rest)) => !name.is_empty() && wildcard_match(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && wildcard_match(rest, &name[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MarkdownDown;
+    use std::process::Command;
+
+    #[test]
+    fn test_matching_files() {
+        let dir = TempDir::new().unwrap();
+        for file in [
+            "README.md",
+            "docs/index.md",
+            "docs/guide/install.md",
+            "docs/guide/logo.png",
+            "src/SUMMARY.md",
+            ".git/description.md",
+        ] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "# Page\n").unwrap();
+        }
+
+        assert_eq!(
+            matching_files(dir.path(), DEFAULT_DOCS_PATH),
+            vec!["docs/guide/install.md", "docs/index.md"]
+        );
+        assert_eq!(
+            matching_files(dir.path(), "docs/*.md"),
+            vec!["docs/index.md"]
+        );
+        assert_eq!(
+            matching_files(dir.path(), "**/*.md"),
+            vec![
+                "README.md",
+                "docs/guide/install.md",
+                "docs/index.md",
+                "src/SUMMARY.md"
+            ]
+        );
+        assert_eq!(
+            matching_files(dir.path(), "./docs/guide/????.png"),
+            vec!["docs/guide/logo.png"]
+        );
+    }
+
+    #[test]
+    fn test_clone_url() {
+        assert_eq!(
+            clone_url(" git+https://example.com/team/handbook.git ").unwrap(),
+            "https://example.com/team/handbook.git"
+        );
+        assert_eq!(
+            clone_url("git@example.com:team/handbook.git").unwrap(),
+            "git@example.com:team/handbook.git"
+        );
+        assert!(clone_url("--upload-pack=touch /tmp/x").is_err());
+        assert!(clone_url("git+").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_convert_repository() {
+        let origin = TempDir::new().unwrap();
+        std::fs::create_dir_all(origin.path().join("docs/guide")).unwrap();
+        std::fs::write(origin.path().join("README.md"), "# Project\n").unwrap();
+        std::fs::write(
+            origin.path().join("docs/index.md"),
+            "# Handbook\n\nWelcome.\n",
+        )
+        .unwrap();
+        std::fs::write(origin.path().join("docs/guide/empty.md"), "\n").unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=Docs", "-c", "user.email=docs@example.com"])
+                .args(args)
+                .current_dir(origin.path())
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {args:?} failed");
+        };
+        git(&["init", "--quiet"]);
+        git(&["add", "."]);
+        git(&["commit", "--quiet", "-m", "Add docs"]);
+
+        let url = format!("file://{}", origin.path().display());
+        let files = MarkdownDown::new()
+            .convert_repository(&url, &RepositoryOptions::new())
+            .await
+            .unwrap();
+
+        let paths: Vec<&str> = files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(paths, vec!["docs/guide/empty.md", "docs/index.md"]);
+        assert!(files[0].result.is_err());
+        let index = files[1].result.as_ref().unwrap();
+        assert!(index.as_str().contains("# Handbook"));
+        assert!(index.as_str().contains("Welcome."));
+
+        let missing = MarkdownDown::new()
+            .convert_repository(&url, &RepositoryOptions::new().docs_path("book/**/*.md"))
+            .await;
+        assert!(missing.is_err());
+    }
+}
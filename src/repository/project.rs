@@ -0,0 +1,556 @@
+//! Documentation project layouts.
+//!
+//! Converting each markdown file on its own loses what the documentation
+//! site adds around it: the reading order from an mdBook `SUMMARY.md` or
+//! Docusaurus sidebars, files pulled in by mdBook `{{#include}}` directives,
+//! and Docusaurus MDX syntax that is not markdown. Repositories holding one of
+//! these projects, at their root or in a top-level folder such as `website/`,
+//! are recognized by their configuration file, and their pages are ordered
+//! and rewritten to plain markdown before conversion.
+
+use super::matching_files;
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+/// Configuration files that mark a Docusaurus site.
+const DOCUSAURUS_CONFIGS: [&str; 4] = [
+    "docusaurus.config.js",
+    "docusaurus.config.ts",
+    "docusaurus.config.mjs",
+    "docusaurus.config.cjs",
+];
+
+/// Sidebar definitions of a Docusaurus site, in the order Docusaurus looks for them.
+const DOCUSAURUS_SIDEBARS: [&str; 4] = [
+    "sidebars.js",
+    "sidebars.ts",
+    "sidebars.json",
+    "sidebars.cjs",
+];
+
+/// The documentation tool a repository's pages are written for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectKind {
+    /// An mdBook book, marked by `book.toml`
+    MdBook,
+    /// A Docusaurus site, marked by `docusaurus.config.js` or `.ts`
+    Docusaurus,
+}
+
+/// A documentation project found in a checkout.
+#[derive(Debug, Clone)]
+pub(crate) struct Project {
+    pub(crate) kind: ProjectKind,
+    /// Folder holding the pages, relative to the checkout
+    source_dir: String,
+    /// Pages in table of contents or sidebar order, relative to the checkout
+    order: Vec<String>,
+}
+
+impl Project {
+    /// Finds a documentation project at the root of `checkout` or in one of its top-level folders.
+    pub(crate) fn detect(checkout: &Path) -> Option<Self> {
+        let mut candidates = vec![String::new()];
+        if let Ok(entries) = std::fs::read_dir(checkout) {
+            let mut folders: Vec<String> = entries
+                .flatten()
+                .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .filter(|name| name != ".git")
+                .collect();
+            folders.sort();
+            candidates.extend(folders);
+        }
+
+        candidates.iter().find_map(|folder| {
+            let root = checkout.join(folder);
+            if root.join("book.toml").is_file() {
+                Some(Self::mdbook(checkout, &root, folder))
+            } else if DOCUSAURUS_CONFIGS
+                .iter()
+                .any(|config| root.join(config).is_file())
+            {
+                Some(Self::docusaurus(checkout, &root, folder))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Reads an mdBook's source folder from `book.toml` and its order from `SUMMARY.md`.
+    fn mdbook(checkout: &Path, root: &Path, folder: &str) -> Self {
+        let src = std::fs::read_to_string(root.join("book.toml"))
+            .ok()
+            .and_then(|book| book.parse::<toml::Table>().ok())
+            .and_then(|book| {
+                book.get("book")
+                    .and_then(|section| section.get("src"))
+                    .and_then(|src| src.as_str())
+                    .map(str::to_string)
+            })
+            .unwrap_or_else(|| "src".to_string());
+        let source_dir = join(folder, &src);
+
+        let link = Regex::new(r"\[[^\]]*\]\(([^)]*)\)").expect("valid link pattern");
+        let summary = std::fs::read_to_string(checkout.join(&source_dir).join("SUMMARY.md"))
+            .unwrap_or_default();
+        let order = link
+            .captures_iter(&summary)
+            .filter_map(|caps| {
+                let target = caps[1].split('#').next().unwrap_or_default().trim();
+                // Draft chapters have an empty link
+                (!target.is_empty()).then(|| join(&source_dir, target))
+            })
+            .collect();
+
+        info!("Found mdBook in {}", display_folder(folder));
+        Self {
+            kind: ProjectKind::MdBook,
+            source_dir,
+            order,
+        }
+    }
+
+    /// Orders a Docusaurus site's docs by the doc IDs its sidebars mention.
+    fn docusaurus(checkout: &Path, root: &Path, folder: &str) -> Self {
+        let source_dir = join(folder, "docs");
+        let mut ids = HashMap::new();
+        for path in matching_files(checkout, &join(&source_dir, "**/*.md*")) {
+            let relative = &path[source_dir.len() + 1..];
+            let content = std::fs::read_to_string(checkout.join(&path)).unwrap_or_default();
+            ids.insert(doc_id(relative, &content), path);
+        }
+
+        let literal =
+            Regex::new(r#"'([^'\\]*)'|"([^"\\]*)"|`([^`\\]*)`"#).expect("valid string pattern");
+        let sidebars = DOCUSAURUS_SIDEBARS
+            .iter()
+            .find_map(|name| std::fs::read_to_string(root.join(name)).ok())
+            .unwrap_or_default();
+        let mut order: Vec<String> = Vec::new();
+        for caps in literal.captures_iter(&sidebars) {
+            let value = caps
+                .iter()
+                .skip(1)
+                .flatten()
+                .next()
+                .map_or("", |m| m.as_str());
+            if let Some(path) = ids.get(value) {
+                if !order.contains(path) {
+                    order.push(path.clone());
+                }
+            }
+        }
+
+        info!("Found Docusaurus site in {}", display_folder(folder));
+        Self {
+            kind: ProjectKind::Docusaurus,
+            source_dir,
+            order,
+        }
+    }
+
+    /// Lists the project's pages, excluding files that configure the project.
+    pub(crate) fn pages(&self, checkout: &Path) -> Vec<String> {
+        let pattern = match self.kind {
+            ProjectKind::MdBook => join(&self.source_dir, "**/*.md"),
+            ProjectKind::Docusaurus => join(&self.source_dir, "**/*.md*"),
+        };
+        let summary = join(&self.source_dir, "SUMMARY.md");
+        matching_files(checkout, &pattern)
+            .into_iter()
+            .filter(|path| self.kind != ProjectKind::MdBook || *path != summary)
+            .collect()
+    }
+
+    /// Sorts pages into reading order; pages missing from the table of contents go last.
+    pub(crate) fn sort(&self, paths: &mut [String]) {
+        paths.sort_by_cached_key(|path| {
+            let position = self.order.iter().position(|page| page == path);
+            (position.unwrap_or(usize::MAX), path.clone())
+        });
+    }
+
+    /// Rewrites a page in the checkout to plain markdown.
+    pub(crate) fn prepare(&self, checkout: &Path, path: &str) {
+        let file = checkout.join(path);
+        let Ok(content) = std::fs::read_to_string(&file) else {
+            return;
+        };
+        let prepared = match self.kind {
+            ProjectKind::MdBook => resolve_includes(checkout, &file, &content),
+            ProjectKind::Docusaurus => strip_mdx(&content),
+        };
+        if prepared != content {
+            debug!("Rewrote {} for conversion", path);
+            if let Err(e) = std::fs::write(&file, prepared) {
+                warn!("Failed to rewrite {}: {}", path, e);
+            }
+        }
+    }
+}
+
+/// Joins repository-relative paths, where an empty folder is the root.
+fn join(folder: &str, path: &str) -> String {
+    let path = path.trim_start_matches("./").trim_end_matches('/');
+    match (folder.trim_end_matches('/'), path) {
+        ("" | ".", path) => path.to_string(),
+        (folder, "" | ".") => folder.to_string(),
+        (folder, path) => format!("{folder}/{path}"),
+    }
+}
+
+fn display_folder(folder: &str) -> &str {
+    if folder.is_empty() {
+        "repository root"
+    } else {
+        folder
+    }
+}
+
+/// Returns the Docusaurus ID of a doc, from its front matter `id` or its path.
+///
+/// Number prefixes such as `01-` are left out of path-based IDs, as Docusaurus does.
+fn doc_id(relative_path: &str, content: &str) -> String {
+    let without_extension = relative_path
+        .rsplit_once('.')
+        .map_or(relative_path, |(stem, _)| stem);
+    let mut segments: Vec<String> = without_extension
+        .split('/')
+        .map(|segment| {
+            let unnumbered = segment.trim_start_matches(|c: char| c.is_ascii_digit());
+            match unnumbered.strip_prefix(['-', '_', '.']) {
+                Some(rest) if unnumbered.len() < segment.len() && !rest.is_empty() => {
+                    rest.to_string()
+                }
+                _ => segment.to_string(),
+            }
+        })
+        .collect();
+
+    let front_matter_id = content
+        .strip_prefix("---\n")
+        .and_then(|rest| rest.split("\n---").next())
+        .and_then(|front_matter| {
+            front_matter
+                .lines()
+                .find_map(|line| line.strip_prefix("id:"))
+                .map(|id| id.trim().trim_matches(['"', '\'']).to_string())
+        });
+    if let (Some(id), Some(last)) = (front_matter_id, segments.last_mut()) {
+        *last = id;
+    }
+    segments.join("/")
+}
+
+/// Expands mdBook `{{#include}}`, `{{#rustdoc_include}}`, and `{{#playground}}`
+/// directives and removes `{{#title}}`.
+///
+/// Included files must be inside the checkout; directives pointing elsewhere
+/// are left as they are.
+fn resolve_includes(checkout: &Path, file: &Path, content: &str) -> String {
+    let directive = Regex::new(r"\{\{#(?:include|rustdoc_include|playground)\s+([^}\s]+)[^}]*\}\}")
+        .expect("valid include pattern");
+    let title = Regex::new(r"\{\{#title\s[^}]*\}\}\n?").expect("valid title pattern");
+    let base = file.parent().unwrap_or(checkout);
+
+    let content = directive.replace_all(content, |caps: &Captures| {
+        included_text(checkout, base, &caps[1]).unwrap_or_else(|| caps[0].to_string())
+    });
+    title.replace_all(&content, "").into_owned()
+}
+
+/// Reads the lines an include directive selects, as `path`, `path:line`,
+/// `path:start:end`, or `path:anchor`.
+fn included_text(checkout: &Path, base: &Path, spec: &str) -> Option<String> {
+    let (path, range) = match spec.split_once(':') {
+        Some((path, range)) => (path, Some(range)),
+        None => (spec, None),
+    };
+    let target: PathBuf = base.join(path).canonicalize().ok()?;
+    if !target.starts_with(checkout.canonicalize().ok()?) {
+        warn!("Not including {}, which is outside the repository", path);
+        return None;
+    }
+    let text = std::fs::read_to_string(&target).ok()?;
+    let lines: Vec<&str> = text.lines().collect();
+    let selected = match range {
+        None => lines,
+        Some(range) => select_lines(&lines, range)?,
+    };
+
+    let selected: Vec<&str> = selected
+        .into_iter()
+        .filter(|line| !line.contains("ANCHOR:") && !line.contains("ANCHOR_END:"))
+        .collect();
+    Some(selected.join("\n"))
+}
+
+/// Selects 1-based lines by `line`, `start:end` (either side may be empty), or anchor name.
+fn select_lines<'a>(lines: &[&'a str], range: &str) -> Option<Vec<&'a str>> {
+    let (start, end) = match range.split_once(':') {
+        Some((start, end)) => (
+            if start.is_empty() {
+                1
+            } else {
+                start.parse().ok()?
+            },
+            if end.is_empty() {
+                lines.len()
+            } else {
+                end.parse().ok()?
+            },
+        ),
+        None => match range.parse::<usize>() {
+            Ok(line) => (line, line),
+            Err(_) => return anchor_lines(lines, range),
+        },
+    };
+    let start = start.max(1);
+    Some(
+        lines
+            .iter()
+            .skip(start - 1)
+            .take((end + 1).saturating_sub(start))
+            .copied()
+            .collect(),
+    )
+}
+
+/// Selects the lines between `ANCHOR: name` and `ANCHOR_END: name` markers.
+fn anchor_lines<'a>(lines: &[&'a str], name: &str) -> Option<Vec<&'a str>> {
+    let marker_name = |line: &str, marker: &str| {
+        line.split_once(marker)
+            .and_then(|(_, rest)| rest.split_whitespace().next())
+            .is_some_and(|marked| marked == name)
+    };
+    let start = lines.iter().position(|line| marker_name(line, "ANCHOR:"))?;
+    Some(
+        lines[start + 1..]
+            .iter()
+            .take_while(|line| !marker_name(line, "ANCHOR_END:"))
+            .copied()
+            .collect(),
+    )
+}
+
+/// Rewrites Docusaurus MDX to plain markdown.
+///
+/// `import` and `export` statements, `{/* */}` comments, and JSX component
+/// tags such as `<Tabs>` are removed, keeping the text between tags.
+/// Admonitions (`:::note ... :::`) become block quotes headed by their title.
+/// Code blocks are left unchanged.
+fn strip_mdx(content: &str) -> String {
+    let component =
+        Regex::new(r"</?[A-Z][A-Za-z0-9.]*(?:\s[^<>]*)?/?>").expect("valid component pattern");
+    let comment = Regex::new(r"\{/\*.*?\*/\}").expect("valid comment pattern");
+
+    let mut output: Vec<String> = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut in_statement = false;
+    let mut admonitions = 0;
+    let mut after_title = false;
+    for line in content.lines() {
+        let quote = "> ".repeat(admonitions);
+        let push = |output: &mut Vec<String>, line: &str| {
+            output.push(format!("{quote}{line}").trim_end().to_string());
+        };
+        let trimmed = line.trim_start();
+        // The title is already followed by a blank quoted line
+        if std::mem::take(&mut after_title) && trimmed.is_empty() {
+            continue;
+        }
+
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            push(&mut output, line);
+            continue;
+        }
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence = Some(&trimmed[..3]);
+            push(&mut output, line);
+            continue;
+        }
+
+        if in_statement {
+            in_statement = !statement_ends(line);
+            continue;
+        }
+        if line.starts_with("import ") || line.starts_with("export ") {
+            in_statement = !statement_ends(line);
+            continue;
+        }
+
+        if let Some(admonition) = trimmed.strip_prefix(":::") {
+            let admonition = admonition.trim_start_matches(':').trim();
+            if admonition.is_empty() {
+                admonitions = admonitions.saturating_sub(1);
+            } else {
+                push(
+                    &mut output,
+                    &format!("> **{}**", admonition_title(admonition)),
+                );
+                push(&mut output, ">");
+                admonitions += 1;
+                after_title = true;
+            }
+            continue;
+        }
+
+        let cleaned = comment.replace_all(line, "");
+        let cleaned = component.replace_all(&cleaned, "");
+        // Lines holding only tags or comments are dropped rather than left blank
+        if cleaned.trim().is_empty() && !line.trim().is_empty() {
+            continue;
+        }
+        push(&mut output, &cleaned);
+    }
+
+    let mut stripped = output.join("\n");
+    if content.ends_with('\n') {
+        stripped.push('\n');
+    }
+    stripped
+}
+
+/// Whether an `import` or `export` statement is complete after `line`.
+fn statement_ends(line: &str) -> bool {
+    let line = line.trim_end();
+    line.is_empty() || line.ends_with(';') || line.contains(" from ")
+}
+
+/// Returns an admonition's title: its own title if given, or its capitalized type.
+fn admonition_title(admonition: &str) -> String {
+    let (kind, title) = match admonition.split_once(['[', ' ']) {
+        Some((kind, title)) => (kind, title.trim_end_matches(']').trim()),
+        None => (admonition, ""),
+    };
+    if !title.is_empty() {
+        return title.to_string();
+    }
+    let mut chars = kind.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_mdbook_order_and_includes() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "book/book.toml",
+            "[book]\ntitle = \"Guide\"\nsrc = \"pages\"\n",
+        );
+        write(
+            root,
+            "book/pages/SUMMARY.md",
+            "# Summary\n\n[Introduction](intro.md)\n\n- [Setup](chapter/setup.md)\n- [Draft]()\n",
+        );
+        write(
+            root,
+            "book/pages/intro.md",
+            "{{#title Welcome}}\n# Introduction\n",
+        );
+        write(
+            root,
+            "book/pages/chapter/setup.md",
+            "# Setup\n\n```rust\n{{#include ../../listings/main.rs:2:3}}\n```\n\n```rust\n{{#rustdoc_include ../../listings/main.rs:body}}\n```\n\n{{#include ../../../../etc/hostname}}\n",
+        );
+        write(root, "book/pages/appendix.md", "# Appendix\n");
+        write(
+            root,
+            "book/listings/main.rs",
+            "fn main() {\n    // ANCHOR: body\n    println!(\"hi\");\n    // ANCHOR_END: body\n}\n",
+        );
+
+        let project = Project::detect(root).unwrap();
+        assert_eq!(project.kind, ProjectKind::MdBook);
+        let mut pages = project.pages(root);
+        project.sort(&mut pages);
+        assert_eq!(
+            pages,
+            vec![
+                "book/pages/intro.md",
+                "book/pages/chapter/setup.md",
+                "book/pages/appendix.md"
+            ]
+        );
+
+        for page in &pages {
+            project.prepare(root, page);
+        }
+        let intro = std::fs::read_to_string(root.join("book/pages/intro.md")).unwrap();
+        assert_eq!(intro, "# Introduction\n");
+        let setup = std::fs::read_to_string(root.join("book/pages/chapter/setup.md")).unwrap();
+        assert!(setup.contains(
+            "```rust\n    println!(\"hi\");\n```\n\n```rust\n    println!(\"hi\");\n```"
+        ));
+        // Includes outside the repository are left alone
+        assert!(setup.contains("{{#include ../../../../etc/hostname}}"));
+    }
+
+    #[test]
+    fn test_docusaurus_order_and_mdx() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "website/docusaurus.config.js",
+            "module.exports = {};\n",
+        );
+        write(
+            root,
+            "website/sidebars.js",
+            "module.exports = {\n  docs: [\n    'intro',\n    {type: 'category', label: 'Guides', items: [\"guides/install\", 'guides/setup']},\n  ],\n};\n",
+        );
+        write(root, "website/docs/intro.mdx", "# Intro\n");
+        write(root, "website/docs/guides/01-setup.md", "# Setup\n");
+        write(
+            root,
+            "website/docs/guides/install.md",
+            "---\nid: install\n---\n# Install\n",
+        );
+        write(root, "website/docs/faq.md", "# FAQ\n");
+
+        let project = Project::detect(root).unwrap();
+        assert_eq!(project.kind, ProjectKind::Docusaurus);
+        let mut pages = project.pages(root);
+        project.sort(&mut pages);
+        assert_eq!(
+            pages,
+            vec![
+                "website/docs/intro.mdx",
+                "website/docs/guides/install.md",
+                "website/docs/guides/01-setup.md",
+                "website/docs/faq.md"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strip_mdx() {
+        let mdx = "import Tabs from '@theme/Tabs';\nimport {\n  TabItem,\n} from '@theme/TabItem';\n\n# Install\n\n{/* Shown on the landing page */}\n<Tabs>\n  <TabItem value=\"npm\" label=\"npm\">\n\nRun <Highlight color=\"#25c2a0\">npm install</Highlight>.\n\n  </TabItem>\n</Tabs>\n\n:::tip[Faster builds]\n\nUse a cache.\n\n:::\n\n:::warning\nOld versions break.\n:::\n\n```jsx\nimport Tabs from '@theme/Tabs';\n<Tabs />\n```\n";
+        assert_eq!(
+            strip_mdx(mdx),
+            "\n# Install\n\n\nRun npm install.\n\n\n> **Faster builds**\n>\n> Use a cache.\n>\n\n> **Warning**\n>\n> Old versions break.\n\n```jsx\nimport Tabs from '@theme/Tabs';\n<Tabs />\n```\n"
+        );
+    }
+}
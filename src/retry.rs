@@ -0,0 +1,228 @@
+//! Delays between retries of failed requests.
+//!
+//! [`crate::client::HttpClient`] retries network errors, server errors, and
+//! rate-limited requests up to [`crate::config::HttpConfig::max_retries`]
+//! times. A [`RetryPolicy`] decides how long to wait before each retry, or
+//! to stop retrying early. Without one, the delay doubles from
+//! [`crate::config::HttpConfig::retry_delay`] on every retry.
+//!
+//! Many clients retrying a failing service in lockstep can keep it down
+//! when it comes back. [`ExponentialBackoff::jitter`] randomizes each delay
+//! to spread their retries out.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::retry::{ExponentialBackoff, FibonacciBackoff, FixedDelay, NoRetry};
+//! use markdowndown::Config;
+//! use std::time::Duration;
+//!
+//! let config = Config::builder()
+//!     .max_retries(5)
+//!     .retry_policy(
+//!         ExponentialBackoff::new(Duration::from_millis(200))
+//!             .max_delay(Duration::from_secs(10))
+//!             .jitter(true),
+//!     )
+//!     .build();
+//!
+//! // Other policies
+//! let fixed = FixedDelay::new(Duration::from_secs(2));
+//! let fibonacci = FibonacciBackoff::new(Duration::from_millis(500));
+//! let never = NoRetry;
+//! ```
+
+use std::fmt;
+use std::time::Duration;
+
+/// Decides how long to wait before retrying a failed request.
+///
+/// Implement this to tune retries for an environment, such as a service
+/// with known recovery times.
+pub trait RetryPolicy: fmt::Debug + Send + Sync {
+    /// Returns the delay before a retry, or None to stop retrying.
+    ///
+    /// # Arguments
+    ///
+    /// * `retry` - The number of retries already made, starting at 0
+    fn delay(&self, retry: u32) -> Option<Duration>;
+}
+
+/// Doubles the delay on every retry, optionally with random jitter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExponentialBackoff {
+    base: Duration,
+    max_delay: Duration,
+    jitter: bool,
+}
+
+impl ExponentialBackoff {
+    /// Creates a policy waiting `base` before the first retry, without a
+    /// maximum or jitter.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The delay before the first retry
+    pub fn new(base: Duration) -> Self {
+        Self {
+            base,
+            max_delay: Duration::MAX,
+            jitter: false,
+        }
+    }
+
+    /// Sets the longest delay between retries.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_delay` - The delay the doubling stops at
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Sets whether delays are randomized.
+    ///
+    /// With jitter, each delay is chosen uniformly between zero and the
+    /// exponential delay ("full jitter"), so clients that failed together
+    /// do not retry together.
+    ///
+    /// # Arguments
+    ///
+    /// * `jitter` - Whether to randomize delays
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+}
+
+impl RetryPolicy for ExponentialBackoff {
+    fn delay(&self, retry: u32) -> Option<Duration> {
+        let delay = self
+            .base
+            .saturating_mul(2_u32.saturating_pow(retry))
+            .min(self.max_delay);
+        if self.jitter {
+            Some(delay.mul_f64(fastrand::f64()))
+        } else {
+            Some(delay)
+        }
+    }
+}
+
+/// Waits the same time before every retry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedDelay {
+    delay: Duration,
+}
+
+impl FixedDelay {
+    /// Creates a policy waiting `delay` before every retry.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - The delay between retries
+    pub fn new(delay: Duration) -> Self {
+        Self { delay }
+    }
+}
+
+impl RetryPolicy for FixedDelay {
+    fn delay(&self, _retry: u32) -> Option<Duration> {
+        Some(self.delay)
+    }
+}
+
+/// Grows the delay along the Fibonacci sequence: 1, 1, 2, 3, 5, 8 times the base.
+///
+/// Delays grow more slowly than with [`ExponentialBackoff`], so later
+/// retries come sooner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FibonacciBackoff {
+    base: Duration,
+    max_delay: Duration,
+}
+
+impl FibonacciBackoff {
+    /// Creates a policy waiting `base` before the first two retries, without a maximum.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The delay before the first retry
+    pub fn new(base: Duration) -> Self {
+        Self {
+            base,
+            max_delay: Duration::MAX,
+        }
+    }
+
+    /// Sets the longest delay between retries.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_delay` - The delay the growth stops at
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+}
+
+impl RetryPolicy for FibonacciBackoff {
+    fn delay(&self, retry: u32) -> Option<Duration> {
+        let (mut current, mut next) = (1_u32, 1_u32);
+        for _ in 0..retry {
+            (current, next) = (next, current.saturating_add(next));
+        }
+        Some(self.base.saturating_mul(current).min(self.max_delay))
+    }
+}
+
+/// Never retries; the first failure is returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NoRetry;
+
+impl RetryPolicy for NoRetry {
+    fn delay(&self, _retry: u32) -> Option<Duration> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delays(policy: &dyn RetryPolicy, count: u32) -> Vec<Option<Duration>> {
+        (0..count).map(|retry| policy.delay(retry)).collect()
+    }
+
+    #[test]
+    fn test_policy_delays() {
+        let ms = |ms| Some(Duration::from_millis(ms));
+        let exponential = ExponentialBackoff::new(Duration::from_millis(100))
+            .max_delay(Duration::from_millis(500));
+        assert_eq!(
+            delays(&exponential, 5),
+            [ms(100), ms(200), ms(400), ms(500), ms(500)]
+        );
+        assert_eq!(exponential.delay(40), ms(500));
+
+        let fibonacci = FibonacciBackoff::new(Duration::from_millis(100));
+        assert_eq!(
+            delays(&fibonacci, 6),
+            [ms(100), ms(100), ms(200), ms(300), ms(500), ms(800)]
+        );
+
+        let fixed = FixedDelay::new(Duration::from_millis(250));
+        assert_eq!(delays(&fixed, 3), [ms(250), ms(250), ms(250)]);
+        assert_eq!(delays(&NoRetry, 2), [None, None]);
+    }
+
+    #[test]
+    fn test_jitter_stays_within_exponential_delay() {
+        let policy = ExponentialBackoff::new(Duration::from_secs(1)).jitter(true);
+        for retry in 0..4 {
+            let delay = policy.delay(retry).unwrap();
+            assert!(delay <= Duration::from_secs(1 << retry));
+        }
+    }
+}
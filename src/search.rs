@@ -0,0 +1,220 @@
+//! Search-index documents for converted output.
+//!
+//! Search engines such as Meilisearch and Elasticsearch index flat JSON
+//! documents, not markdown. A [`SearchDocument`] holds what they need from a
+//! converted document: a stable `id` to use as the primary key, the title,
+//! the heading texts, the frontmatter tags and other metadata, and the body
+//! as plain text without markdown syntax.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::search::SearchDocument;
+//!
+//! let markdown = "---\ntitle: Install Guide\ntags:\n- setup\n---\n\n# Install\n\n\
+//!                 Run **cargo install** from the [crate page](https://crates.io).\n";
+//! let document = SearchDocument::from_markdown("https://example.com/install", markdown);
+//!
+//! assert_eq!(document.title.as_deref(), Some("Install Guide"));
+//! assert_eq!(document.headings, vec!["Install"]);
+//! assert_eq!(document.tags, vec!["setup"]);
+//! assert_eq!(document.text, "Install\n\nRun cargo install from the crate page.");
+//! ```
+
+use crate::frontmatter::{frontmatter_list, strip_frontmatter};
+use crate::identity::document_id;
+use crate::index::document_title;
+use crate::sections::extract_sections;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Frontmatter fields stored in their own [`SearchDocument`] fields.
+const PROMOTED_FIELDS: &[&str] = &["title", "tags", "source_url"];
+
+/// A converted document, flattened for a search engine.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchDocument {
+    /// The stable document ID, as stamped into the frontmatter
+    pub id: String,
+    /// The URL the document was converted from
+    pub url: String,
+    /// The document title, when known
+    pub title: Option<String>,
+    /// The heading texts, in document order
+    pub headings: Vec<String>,
+    /// The frontmatter tags
+    pub tags: Vec<String>,
+    /// The document body as plain text, paragraphs separated by blank lines
+    pub text: String,
+    /// The remaining frontmatter fields, such as `exporter` and `language`
+    pub metadata: BTreeMap<String, serde_json::Value>,
+}
+
+impl SearchDocument {
+    /// Builds the search document for a converted document.
+    ///
+    /// Documents without a frontmatter title are titled by their first
+    /// top-level heading.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL the document was converted from
+    /// * `markdown` - The converted output, with or without frontmatter
+    pub fn from_markdown(url: &str, markdown: &str) -> Self {
+        Self {
+            id: document_id(url),
+            url: url.to_string(),
+            title: document_title(markdown),
+            headings: extract_sections(markdown)
+                .into_iter()
+                .map(|section| section.text)
+                .collect(),
+            tags: frontmatter_list(markdown, "tags").unwrap_or_default(),
+            text: plain_text(markdown),
+            metadata: frontmatter_metadata(markdown),
+        }
+    }
+}
+
+/// Returns the frontmatter fields that have no [`SearchDocument`] field of their own.
+fn frontmatter_metadata(markdown: &str) -> BTreeMap<String, serde_json::Value> {
+    let Some(yaml) = markdown
+        .strip_prefix("---\n")
+        .and_then(|rest| rest.split_once("\n---\n"))
+        .map(|(yaml, _)| yaml)
+    else {
+        return BTreeMap::new();
+    };
+    let Ok(serde_yaml::Value::Mapping(fields)) = serde_yaml::from_str(yaml) else {
+        return BTreeMap::new();
+    };
+    fields
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let key = key.as_str()?.to_string();
+            if PROMOTED_FIELDS.contains(&key.as_str()) {
+                return None;
+            }
+            Some((key, serde_json::to_value(value).ok()?))
+        })
+        .collect()
+}
+
+/// Converts a markdown document to plain text.
+///
+/// The frontmatter is dropped. Heading, list, quote and emphasis markers
+/// are removed, links and images are replaced by their text, HTML tags are
+/// stripped, and table cells are separated by spaces. Code blocks keep
+/// their contents without the fences. Lines of a paragraph are joined with
+/// spaces and paragraphs are separated by blank lines.
+///
+/// # Arguments
+///
+/// * `markdown` - The markdown document, with or without frontmatter
+pub fn plain_text(markdown: &str) -> String {
+    let image = Regex::new(r"!\[([^\]]*)\]\([^)]*\)").expect("valid image pattern");
+    let link = Regex::new(r"\[([^\]]*)\](?:\([^)]*\)|\[[^\]]*\])").expect("valid link pattern");
+    let html = Regex::new(r"</?[A-Za-z][^<>]*>").expect("valid tag pattern");
+    let marker = Regex::new(r"^(?:#{1,6}\s+|(?:>\s?)+|[-*+]\s+(?:\[[ xX]\]\s+)?|\d+[.)]\s+)+")
+        .expect("valid marker pattern");
+    let emphasis = Regex::new(r"\*{1,3}|_{2,3}|~~|`").expect("valid emphasis pattern");
+    let rule = Regex::new(r"^(?:[-*_]\s*){3,}$").expect("valid rule pattern");
+    let table_separator = Regex::new(r"^\|?\s*:?-+:?\s*(?:\|\s*:?-+:?\s*)*\|?$")
+        .expect("valid table separator pattern");
+
+    let mut paragraphs: Vec<String> = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+    let mut in_code_block = false;
+    for line in strip_frontmatter(markdown).lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            flush_paragraph(&mut current, &mut paragraphs);
+            continue;
+        }
+        if in_code_block {
+            if !trimmed.is_empty() {
+                current.push(trimmed.to_string());
+            }
+            continue;
+        }
+        if trimmed.is_empty() || rule.is_match(trimmed) {
+            flush_paragraph(&mut current, &mut paragraphs);
+            continue;
+        }
+        if table_separator.is_match(trimmed) {
+            continue;
+        }
+
+        let text = marker.replace(trimmed, "");
+        let text = image.replace_all(&text, "$1");
+        let text = link.replace_all(&text, "$1");
+        let text = html.replace_all(&text, "");
+        let text = emphasis.replace_all(&text, "");
+        let text = if text.contains('|') {
+            text.split('|')
+                .map(str::trim)
+                .filter(|cell| !cell.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        } else {
+            text.trim().to_string()
+        };
+        if !text.is_empty() {
+            current.push(text);
+        }
+        // Headings are paragraphs of their own
+        if trimmed.starts_with('#') {
+            flush_paragraph(&mut current, &mut paragraphs);
+        }
+    }
+    flush_paragraph(&mut current, &mut paragraphs);
+    paragraphs.join("\n\n")
+}
+
+/// Moves the collected lines into a new paragraph.
+fn flush_paragraph(current: &mut Vec<String>, paragraphs: &mut Vec<String>) {
+    if !current.is_empty() {
+        paragraphs.push(current.join(" "));
+        current.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text_strips_markdown_syntax() {
+        let markdown = "# Title\n## Usage\n\n> Quoted *text*\n> continues\n\n\
+                        - [x] Done item\n1. First `step`\n\n\
+                        | Name | Value |\n|------|:-----:|\n| a | ![logo](x.png) |\n\n\
+                        ---\n\n```rust\nlet x = 1;\n```\n\n<div dir=\"rtl\">Hello</div>\n";
+
+        assert_eq!(
+            plain_text(markdown),
+            "Title\n\nUsage\n\nQuoted text continues\n\nDone item First step\n\n\
+             Name Value a logo\n\nlet x = 1;\n\nHello"
+        );
+    }
+
+    #[test]
+    fn test_search_document_metadata_and_fallback_title() {
+        let markdown = "---\nsource_url: https://example.com/a\nexporter: markdowndown\n\
+                        word_count: 3\n---\n\n# Heading One\n\nSome words here.\n";
+        let document = SearchDocument::from_markdown("https://example.com/a", markdown);
+
+        assert_eq!(document.id, document_id("https://example.com/a"));
+        assert_eq!(document.title.as_deref(), Some("Heading One"));
+        assert!(document.tags.is_empty());
+        assert_eq!(
+            document.metadata.keys().collect::<Vec<_>>(),
+            vec!["exporter", "word_count"]
+        );
+        assert_eq!(document.metadata["word_count"], serde_json::json!(3));
+
+        let json = serde_json::to_value(&document).unwrap();
+        assert_eq!(json["headings"], serde_json::json!(["Heading One"]));
+    }
+}
@@ -0,0 +1,191 @@
+//! Heading tree extraction for converted documents.
+//!
+//! Each ATX heading (`#` to `######`) opens a section that runs until the next
+//! heading of the same or a higher level. Sections record their parent, so the
+//! flat list doubles as a tree for navigation, chunking, and deep links.
+//! Headings inside code blocks and the frontmatter block are ignored.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::sections::extract_sections;
+//!
+//! let markdown = "# Guide\n\nIntro.\n\n## Install\n\nSteps.\n";
+//! let sections = extract_sections(markdown);
+//!
+//! assert_eq!(sections[1].text, "Install");
+//! assert_eq!(sections[1].anchor, "install");
+//! assert_eq!(sections[1].parent, Some(0));
+//! assert_eq!(&markdown[sections[1].start..sections[1].end], "## Install\n\nSteps.\n");
+//! ```
+
+use crate::types::Section;
+use std::collections::HashMap;
+
+/// Extracts the heading tree of a markdown document.
+///
+/// # Arguments
+///
+/// * `markdown` - The markdown document, with or without frontmatter
+///
+/// # Returns
+///
+/// The sections in document order, with byte offsets into `markdown`.
+pub fn extract_sections(markdown: &str) -> Vec<Section> {
+    let mut sections: Vec<Section> = Vec::new();
+    // Indexes of sections that have not been closed yet, outermost first
+    let mut open: Vec<usize> = Vec::new();
+    let mut anchor_counts: HashMap<String, usize> = HashMap::new();
+    let mut in_frontmatter = markdown.starts_with("---\n");
+    let mut in_code_block = false;
+    let mut offset = 0;
+
+    for (index, line) in markdown.split_inclusive('\n').enumerate() {
+        let start = offset;
+        offset += line.len();
+        let trimmed = line.trim_end();
+
+        if in_frontmatter {
+            if index > 0 && trimmed == "---" {
+                in_frontmatter = false;
+            }
+            continue;
+        }
+        if trimmed.trim_start().starts_with("```") || trimmed.trim_start().starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        let Some((level, text)) = parse_heading(trimmed) else {
+            continue;
+        };
+
+        // Close every open section at this level or deeper
+        while let Some(&open_index) = open.last() {
+            if sections[open_index].level < level {
+                break;
+            }
+            sections[open_index].end = start;
+            open.pop();
+        }
+
+        open.push(sections.len());
+        sections.push(Section {
+            level,
+            anchor: unique_anchor(&text, &mut anchor_counts),
+            text,
+            parent: open.iter().rev().nth(1).copied(),
+            start,
+            end: markdown.len(),
+        });
+    }
+
+    sections
+}
+
+/// Parses an ATX heading line into its level and text.
+fn parse_heading(line: &str) -> Option<(u8, String)> {
+    // Up to three spaces of indentation are allowed before the markers
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+
+    let rest = &line[indent..];
+    let level = rest.chars().take_while(|&c| c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+
+    let after = &rest[level..];
+    if !after.is_empty() && !after.starts_with([' ', '\t']) {
+        return None;
+    }
+
+    // Drop an optional closing sequence of '#' characters
+    let text = after.trim();
+    let text = match text.trim_end_matches('#') {
+        stripped if stripped.is_empty() || stripped.ends_with([' ', '\t']) => stripped.trim_end(),
+        _ => text,
+    };
+    Some((level as u8, text.to_string()))
+}
+
+/// Builds a GitHub-style anchor, adding `-1`, `-2`, ... for repeated headings.
+fn unique_anchor(text: &str, counts: &mut HashMap<String, usize>) -> String {
+    let base: String = text
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect();
+
+    let count = counts.entry(base.clone()).or_insert(0);
+    let anchor = if *count == 0 {
+        base
+    } else {
+        format!("{base}-{count}")
+    };
+    *count += 1;
+    anchor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_sections_builds_tree() {
+        let markdown = "# Title\n\n## One\n\ntext\n\n### One.A\n\n## Two\n\nend\n";
+        let sections = extract_sections(markdown);
+
+        let outline: Vec<(u8, &str, Option<usize>)> = sections
+            .iter()
+            .map(|s| (s.level, s.text.as_str(), s.parent))
+            .collect();
+        assert_eq!(
+            outline,
+            vec![
+                (1, "Title", None),
+                (2, "One", Some(0)),
+                (3, "One.A", Some(1)),
+                (2, "Two", Some(0)),
+            ]
+        );
+
+        assert_eq!(sections[0].start, 0);
+        assert_eq!(sections[0].end, markdown.len());
+        assert_eq!(
+            &markdown[sections[1].start..sections[1].end],
+            "## One\n\ntext\n\n### One.A\n\n"
+        );
+        assert_eq!(&markdown[sections[3].start..sections[3].end], "## Two\n\nend\n");
+    }
+
+    #[test]
+    fn test_extract_sections_anchors() {
+        let markdown = "# Getting Started!\n# FAQ\n# FAQ\n## Closing ##\n";
+        let anchors: Vec<String> = extract_sections(markdown)
+            .into_iter()
+            .map(|s| s.anchor)
+            .collect();
+
+        assert_eq!(anchors, vec!["getting-started", "faq", "faq-1", "closing"]);
+    }
+
+    #[test]
+    fn test_extract_sections_skips_frontmatter_and_code() {
+        let markdown = "---\ntitle: x\n# not: heading\n---\n\n```\n# comment\n```\n#hashtag\n    # indented\n# Real\n";
+        let sections = extract_sections(markdown);
+
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].text, "Real");
+        assert_eq!(&markdown[sections[0].start..sections[0].end], "# Real\n");
+    }
+}
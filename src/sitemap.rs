@@ -0,0 +1,340 @@
+//! Site conversion driven by `sitemap.xml` files.
+//!
+//! A sitemap lists the pages of a site in `<loc>` elements, and a sitemap
+//! index lists further sitemaps. [`crate::MarkdownDown::convert_sitemap`]
+//! reads a sitemap (following sitemap indexes and gzipped sitemaps), keeps
+//! the pages that [`SitemapOptions`] select, and converts them concurrently
+//! with a politeness delay between requests.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::sitemap::SitemapOptions;
+//! use std::time::Duration;
+//!
+//! let options = SitemapOptions::new()
+//!     .include("/docs/**")
+//!     .exclude("/docs/archive/**")
+//!     .concurrency(2)
+//!     .delay(Duration::from_millis(250));
+//!
+//! assert!(options.matches("https://example.com/docs/guide/install"));
+//! assert!(!options.matches("https://example.com/docs/archive/v1"));
+//! assert!(!options.matches("https://example.com/blog/post"));
+//! ```
+
+use crate::client::HttpClient;
+use crate::compression;
+use crate::converters::docx::decode_xml_entities;
+use crate::repository::glob_match;
+use crate::types::{ContentErrorKind, ErrorContext, MarkdownError};
+use regex::Regex;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Deepest chain of sitemap indexes that is followed.
+const MAX_SITEMAP_DEPTH: usize = 3;
+
+/// Options for converting the pages listed in a sitemap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SitemapOptions {
+    /// Path patterns selecting pages to convert (empty selects every page)
+    pub include: Vec<String>,
+    /// Path patterns excluding pages, applied after `include`
+    pub exclude: Vec<String>,
+    /// Number of pages converted at the same time, or the most per host when adaptive
+    pub concurrency: usize,
+    /// Whether concurrency adapts to each host's errors and latency
+    pub adaptive_concurrency: bool,
+    /// Minimum time between the start of two page conversions
+    pub delay: Duration,
+    /// Maximum number of pages to convert (None converts every selected page)
+    pub max_pages: Option<usize>,
+    /// Directory each converted page is written to (None only returns them)
+    pub output_dir: Option<PathBuf>,
+}
+
+impl SitemapOptions {
+    /// Creates options converting every page, four at a time, 500ms apart.
+    pub fn new() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+            concurrency: 4,
+            adaptive_concurrency: false,
+            delay: Duration::from_millis(500),
+            max_pages: None,
+            output_dir: None,
+        }
+    }
+
+    /// Adds a pattern selecting pages to convert.
+    ///
+    /// Patterns are matched against the path of each page URL. `*` matches
+    /// within a path segment, `?` matches one character, and `**` matches any
+    /// number of segments. Once any include pattern is given, pages matching
+    /// none of them are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The pattern, such as `/docs/**` or `/blog/*/index.html`
+    pub fn include<T: Into<String>>(mut self, pattern: T) -> Self {
+        self.include.push(pattern.into());
+        self
+    }
+
+    /// Adds a pattern excluding pages, even when an include pattern matches.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The pattern, in the same form as [`SitemapOptions::include`]
+    pub fn exclude<T: Into<String>>(mut self, pattern: T) -> Self {
+        self.exclude.push(pattern.into());
+        self
+    }
+
+    /// Sets how many pages are converted at the same time.
+    ///
+    /// # Arguments
+    ///
+    /// * `concurrency` - Number of concurrent conversions, at least 1
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets whether concurrency adapts to each host instead of staying fixed.
+    ///
+    /// Each host starts at one conversion at a time. Healthy responses
+    /// raise its limit up to [`Self::concurrency`], and throttling, timeouts,
+    /// server errors, or slowing responses halve it. See
+    /// [`crate::concurrency::AdaptiveConcurrency`].
+    ///
+    /// # Arguments
+    ///
+    /// * `adaptive` - Whether per-host limits adapt to the host's responses
+    pub fn adaptive_concurrency(mut self, adaptive: bool) -> Self {
+        self.adaptive_concurrency = adaptive;
+        self
+    }
+
+    /// Sets the politeness delay between the start of two page conversions.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - Minimum time between conversions; zero disables the delay
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Limits how many pages are converted.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - Maximum number of pages, taken in sitemap order
+    pub fn max_pages(mut self, max: usize) -> Self {
+        self.max_pages = Some(max);
+        self
+    }
+
+    /// Writes each converted page to a directory.
+    ///
+    /// Pages are named after their URL slug, such as
+    /// `example-com-docs-install.md`, with the configured output compression
+    /// extension added.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The output directory, created if missing
+    pub fn output_dir<T: Into<PathBuf>>(mut self, dir: T) -> Self {
+        self.output_dir = Some(dir.into());
+        self
+    }
+
+    /// Checks whether a page URL is selected by the include and exclude patterns.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The page URL
+    pub fn matches(&self, url: &str) -> bool {
+        let path = match url::Url::parse(url) {
+            Ok(parsed) => parsed.path().to_string(),
+            Err(_) => url.to_string(),
+        };
+        let path = path.trim_start_matches('/');
+        let matches = |pattern: &String| glob_match(pattern.trim_start_matches('/'), path);
+        (self.include.is_empty() || self.include.iter().any(matches))
+            && !self.exclude.iter().any(matches)
+    }
+}
+
+impl Default for SitemapOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads a sitemap and returns the page URLs the options select.
+///
+/// Sitemap indexes are followed up to [`MAX_SITEMAP_DEPTH`] levels. Child
+/// sitemaps that cannot be read are skipped with a warning; the top-level
+/// sitemap must be readable.
+pub(crate) async fn page_urls(
+    client: &HttpClient,
+    url: &str,
+    options: &SitemapOptions,
+) -> Result<Vec<String>, MarkdownError> {
+    let mut pages = Vec::new();
+    let mut seen = HashSet::new();
+    let mut pending = vec![(url.to_string(), 0)];
+    let mut visited = HashSet::new();
+
+    while let Some((sitemap, depth)) = pending.pop() {
+        if !visited.insert(sitemap.clone()) {
+            continue;
+        }
+        let xml = match read_sitemap(client, &sitemap).await {
+            Ok(xml) => xml,
+            Err(e) if depth > 0 => {
+                warn!("Skipping sitemap {}: {}", sitemap, e);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+
+        let locations = parse_locations(&xml);
+        if xml.contains("<sitemapindex") {
+            if depth + 1 >= MAX_SITEMAP_DEPTH {
+                warn!("Not following sitemap index {} any deeper", sitemap);
+                continue;
+            }
+            debug!(
+                "Sitemap index {} lists {} sitemaps",
+                sitemap,
+                locations.len()
+            );
+            // Reversed so child sitemaps are read in the order they are listed
+            pending.extend(locations.into_iter().rev().map(|child| (child, depth + 1)));
+            continue;
+        }
+        for page in locations {
+            if options.matches(&page) && seen.insert(page.clone()) {
+                pages.push(page);
+            }
+        }
+    }
+
+    if let Some(max) = options.max_pages {
+        pages.truncate(max);
+    }
+    if pages.is_empty() {
+        let context = ErrorContext::new(url, "Page selection", "convert_sitemap")
+            .with_info("No pages in the sitemap match the include and exclude patterns");
+        return Err(MarkdownError::ContentError {
+            kind: ContentErrorKind::EmptyContent,
+            context,
+        });
+    }
+    Ok(pages)
+}
+
+/// Fetches a sitemap, or reads it from a local path, decompressing gzip.
+async fn read_sitemap(client: &HttpClient, location: &str) -> Result<String, MarkdownError> {
+    let bytes = if location.starts_with("http://") || location.starts_with("https://") {
+        client.get_bytes(location).await?.to_vec()
+    } else {
+        tokio::fs::read(location)
+            .await
+            .map_err(|e| MarkdownError::ContentError {
+                kind: ContentErrorKind::ParsingFailed,
+                context: ErrorContext::new(location, "Read sitemap", "convert_sitemap")
+                    .with_info(format!("IO error: {e}")),
+            })?
+    };
+    compression::read_to_string(bytes.as_slice())
+        .await
+        .map_err(|e| MarkdownError::ContentError {
+            kind: ContentErrorKind::ParsingFailed,
+            context: ErrorContext::new(location, "Decode sitemap", "convert_sitemap")
+                .with_info(format!("Sitemap is not valid text: {e}")),
+        })
+}
+
+/// Extracts the `<loc>` values of a sitemap or sitemap index.
+fn parse_locations(xml: &str) -> Vec<String> {
+    let pattern = Regex::new(r"(?s)<loc>\s*(?:<!\[CDATA\[)?(.*?)(?:\]\]>)?\s*</loc>")
+        .expect("valid loc pattern");
+    pattern
+        .captures_iter(xml)
+        .map(|captures| decode_xml_entities(captures[1].trim()))
+        .filter(|location| !location.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_locations() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url><loc>https://example.com/</loc><lastmod>2024-01-01</lastmod></url>
+  <url><loc>
+    https://example.com/search?q=a&amp;page=2
+  </loc></url>
+  <url><loc><![CDATA[https://example.com/docs/intro]]></loc></url>
+</urlset>"#;
+
+        assert_eq!(
+            parse_locations(xml),
+            vec![
+                "https://example.com/",
+                "https://example.com/search?q=a&page=2",
+                "https://example.com/docs/intro",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_page_urls_follows_indexes_and_filters() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let child = dir.path().join("pages.xml");
+        std::fs::write(
+            &child,
+            "<urlset><url><loc>https://example.com/docs/a</loc></url>\
+             <url><loc>https://example.com/docs/old/b</loc></url>\
+             <url><loc>https://example.com/blog/c</loc></url>\
+             <url><loc>https://example.com/docs/a</loc></url></urlset>",
+        )
+        .unwrap();
+        let index = dir.path().join("sitemap.xml");
+        std::fs::write(
+            &index,
+            format!(
+                "<sitemapindex><sitemap><loc>{}</loc></sitemap>\
+                 <sitemap><loc>{}</loc></sitemap></sitemapindex>",
+                child.display(),
+                dir.path().join("missing.xml").display()
+            ),
+        )
+        .unwrap();
+
+        let client = HttpClient::new();
+        let options = SitemapOptions::new()
+            .include("docs/**")
+            .exclude("/docs/old/*");
+        let pages = page_urls(&client, &index.to_string_lossy(), &options)
+            .await
+            .unwrap();
+        assert_eq!(pages, vec!["https://example.com/docs/a"]);
+
+        let none = SitemapOptions::new().include("/nothing/*");
+        assert!(page_urls(&client, &index.to_string_lossy(), &none)
+            .await
+            .is_err());
+    }
+}
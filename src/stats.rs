@@ -0,0 +1,246 @@
+//! Per-host request statistics.
+//!
+//! The HTTP client records every request it sends, grouped by host: whether it
+//! succeeded, how long it took including retries, how many retries it needed,
+//! and how many times the server throttled it (HTTP 429). A recorder is shared
+//! by every clone of the client, so a batch run accumulates one set of
+//! statistics across all of its conversions.
+//!
+//! # Usage Examples
+//!
+//! ```rust
+//! use markdowndown::stats::HostStatsRecorder;
+//! use std::time::Duration;
+//!
+//! let recorder = HostStatsRecorder::new();
+//! recorder.record("https://example.com/a", Duration::from_millis(120), 0, 0, true);
+//! recorder.record("https://example.com/b", Duration::from_millis(900), 2, 2, false);
+//!
+//! let stats = recorder.host("example.com").unwrap();
+//! assert_eq!(stats.requests, 2);
+//! assert_eq!(stats.success_rate(), 0.5);
+//! assert_eq!(stats.throttled, 2);
+//! ```
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use url::Url;
+
+/// Aggregated request statistics for a single host.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HostStats {
+    /// The host name, e.g. `example.com`
+    pub host: String,
+    /// Number of requests sent, not counting retries
+    pub requests: u64,
+    /// Number of requests that ended in a successful response
+    pub successes: u64,
+    /// Number of requests that failed after all retries
+    pub failures: u64,
+    /// Total retries across all requests
+    pub retries: u64,
+    /// Number of throttled (HTTP 429) responses
+    pub throttled: u64,
+    /// Median time per request, including retries and backoff
+    pub median_latency: Duration,
+    /// Slowest request, including retries and backoff
+    pub max_latency: Duration,
+}
+
+impl HostStats {
+    /// Returns the fraction of requests that succeeded, from 0.0 to 1.0.
+    pub fn success_rate(&self) -> f64 {
+        if self.requests == 0 {
+            return 0.0;
+        }
+        self.successes as f64 / self.requests as f64
+    }
+}
+
+/// Raw measurements for a host, kept until a snapshot is taken.
+#[derive(Debug, Default)]
+struct HostRecord {
+    successes: u64,
+    failures: u64,
+    retries: u64,
+    throttled: u64,
+    latencies: Vec<Duration>,
+}
+
+impl HostRecord {
+    fn summarize(&self, host: &str) -> HostStats {
+        let mut latencies = self.latencies.clone();
+        latencies.sort();
+        let median_latency = match latencies.len() {
+            0 => Duration::ZERO,
+            len if len % 2 == 1 => latencies[len / 2],
+            len => (latencies[len / 2 - 1] + latencies[len / 2]) / 2,
+        };
+
+        HostStats {
+            host: host.to_string(),
+            requests: self.successes + self.failures,
+            successes: self.successes,
+            failures: self.failures,
+            retries: self.retries,
+            throttled: self.throttled,
+            median_latency,
+            max_latency: latencies.last().copied().unwrap_or_default(),
+        }
+    }
+}
+
+/// Thread-safe collector of per-host request statistics.
+///
+/// Cloning a recorder shares the underlying statistics.
+#[derive(Debug, Clone, Default)]
+pub struct HostStatsRecorder {
+    hosts: Arc<Mutex<HashMap<String, HostRecord>>>,
+}
+
+impl HostStatsRecorder {
+    /// Creates an empty recorder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the outcome of a request.
+    ///
+    /// URLs without a host are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The requested URL
+    /// * `latency` - Time from the first attempt to the final outcome
+    /// * `retries` - Number of attempts after the first
+    /// * `throttled` - Number of throttled (HTTP 429) responses
+    /// * `success` - Whether the request ended in a successful response
+    pub fn record(&self, url: &str, latency: Duration, retries: u32, throttled: u32, success: bool) {
+        let Some(host) = Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+        else {
+            return;
+        };
+
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        let record = hosts.entry(host).or_default();
+        if success {
+            record.successes += 1;
+        } else {
+            record.failures += 1;
+        }
+        record.retries += u64::from(retries);
+        record.throttled += u64::from(throttled);
+        record.latencies.push(latency);
+    }
+
+    /// Returns the statistics for one host, if any requests were sent to it.
+    pub fn host(&self, host: &str) -> Option<HostStats> {
+        let hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        hosts.get(host).map(|record| record.summarize(host))
+    }
+
+    /// Returns the statistics for every host, sorted by host name.
+    pub fn snapshot(&self) -> Vec<HostStats> {
+        let hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        let mut stats: Vec<HostStats> = hosts
+            .iter()
+            .map(|(host, record)| record.summarize(host))
+            .collect();
+        stats.sort_by(|a, b| a.host.cmp(&b.host));
+        stats
+    }
+
+    /// Returns the statistics for every host, worst success rate first.
+    ///
+    /// Ties are broken by throttling events and then by median latency, so the
+    /// origins causing the most trouble come first.
+    pub fn worst_hosts(&self) -> Vec<HostStats> {
+        let mut stats = self.snapshot();
+        stats.sort_by(|a, b| {
+            a.success_rate()
+                .total_cmp(&b.success_rate())
+                .then_with(|| b.throttled.cmp(&a.throttled))
+                .then_with(|| b.median_latency.cmp(&a.median_latency))
+        });
+        stats
+    }
+
+    /// Discards all recorded statistics.
+    pub fn clear(&self) {
+        self.hosts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_record_aggregates_by_host() {
+        let recorder = HostStatsRecorder::new();
+        recorder.record("https://a.example/1", ms(100), 0, 0, true);
+        recorder.record("https://a.example/2", ms(300), 1, 0, true);
+        recorder.record("https://a.example/3", ms(200), 3, 2, false);
+        recorder.record("https://b.example/", ms(50), 0, 0, true);
+        recorder.record("not a url", ms(50), 0, 0, false);
+
+        let stats = recorder.snapshot();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(
+            stats[0],
+            HostStats {
+                host: "a.example".to_string(),
+                requests: 3,
+                successes: 2,
+                failures: 1,
+                retries: 4,
+                throttled: 2,
+                median_latency: ms(200),
+                max_latency: ms(300),
+            }
+        );
+        assert_eq!(stats[1].host, "b.example");
+    }
+
+    #[test]
+    fn test_median_of_even_count_and_success_rate() {
+        let recorder = HostStatsRecorder::new();
+        recorder.record("https://example.com", ms(100), 0, 0, true);
+        recorder.record("https://example.com", ms(400), 0, 0, false);
+
+        let stats = recorder.host("example.com").unwrap();
+        assert_eq!(stats.median_latency, ms(250));
+        assert_eq!(stats.success_rate(), 0.5);
+        assert!(recorder.host("missing.example").is_none());
+    }
+
+    #[test]
+    fn test_worst_hosts_and_shared_clones() {
+        let recorder = HostStatsRecorder::new();
+        let clone = recorder.clone();
+        clone.record("https://good.example", ms(10), 0, 0, true);
+        clone.record("https://slow.example", ms(900), 0, 0, true);
+        clone.record("https://bad.example", ms(10), 3, 3, false);
+
+        let order: Vec<String> = recorder
+            .worst_hosts()
+            .into_iter()
+            .map(|stats| stats.host)
+            .collect();
+        assert_eq!(order, vec!["bad.example", "slow.example", "good.example"]);
+
+        recorder.clear();
+        assert!(clone.snapshot().is_empty());
+    }
+}
@@ -190,7 +190,7 @@ impl Markdown {
     ///
     /// # Returns
     ///
-    /// An `Option<String>` containing the YAML frontmatter (including delimiters)
+    /// An `Option<String>` containing the frontmatter (including delimiters)
     /// if found, or `None` if no frontmatter is present.
     ///
     /// # Examples
@@ -206,9 +206,9 @@ impl Markdown {
     /// assert!(frontmatter.unwrap().contains("source_url"));
     /// ```
     pub fn frontmatter(&self) -> Option<String> {
-        // Check if content starts with frontmatter delimiters
+        // TOML and JSON frontmatter
         if !self.0.starts_with("---\n") {
-            return None;
+            return crate::frontmatter::frontmatter_block(&self.0).map(|(block, _)| block.to_string());
         }
 
         // Find the closing delimiter
@@ -254,9 +254,12 @@ impl Markdown {
     /// assert_eq!(content_only, "# Hello World\n\nContent here.");
     /// ```
     pub fn content_only(&self) -> String {
-        // Check if content starts with frontmatter delimiters
+        // TOML and JSON frontmatter
         if !self.0.starts_with("---\n") {
-            return self.0.clone();
+            return match crate::frontmatter::frontmatter_block(&self.0) {
+                Some((_, content)) => content.strip_prefix('\n').unwrap_or(content).to_string(),
+                None => self.0.clone(),
+            };
         }
 
         // If frontmatter parsing fails, treat the whole thing as content
@@ -364,6 +367,9 @@ pub struct Url(String);
 impl Url {
     /// Creates a new URL instance with basic validation.
     ///
+    /// Internationalized host names are stored punycode-encoded, so
+    /// `https://例え.jp/` becomes `https://xn--r8jz45g.jp/`.
+    ///
     /// # Errors
     ///
     /// Returns a `MarkdownError::InvalidUrl` if the URL format is invalid.
@@ -372,11 +378,15 @@ impl Url {
         if (url.starts_with("http://") && url.len() > 7)
             || (url.starts_with("https://") && url.len() > 8)
         {
-            return Ok(Url(url));
+            return Self::with_ascii_host(url).map(Url);
         }
 
-        // Check for local file paths
-        if crate::utils::is_local_file_path(&url) {
+        // Check for local file paths, inline documents, and stored objects
+        if crate::utils::is_local_file_path(&url)
+            || crate::utils::is_data_uri(&url)
+            || crate::utils::is_cloud_storage_uri(&url)
+            || crate::utils::is_ipfs_uri(&url)
+        {
             return Ok(Url(url));
         }
 
@@ -392,6 +402,48 @@ impl Url {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Returns the host name in Unicode form, decoding punycode labels.
+    ///
+    /// Returns `None` for local file paths.
+    pub fn host_unicode(&self) -> Option<String> {
+        url::Url::parse(&self.0)
+            .ok()
+            .and_then(|parsed| parsed.host_str().map(crate::utils::host_to_unicode))
+    }
+
+    /// Punycode-encodes a non-ASCII host in an HTTP(S) URL.
+    fn with_ascii_host(url: String) -> Result<String, MarkdownError> {
+        let authority_start = url.find("://").map_or(0, |i| i + 3);
+        let rest = &url[authority_start..];
+        let authority_len = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+        let authority = &rest[..authority_len];
+        if authority.is_ascii() {
+            return Ok(url);
+        }
+
+        // Skip any user info and keep any port
+        let host_start = authority_start + authority.rfind('@').map_or(0, |i| i + 1);
+        let host_and_port = &url[host_start..authority_start + authority_len];
+        let host_len = host_and_port.rfind(':').unwrap_or(host_and_port.len());
+        let host = &host_and_port[..host_len];
+
+        match crate::utils::host_to_ascii(host) {
+            Some(ascii) => Ok(format!(
+                "{}{ascii}{}",
+                &url[..host_start],
+                &url[host_start + host_len..]
+            )),
+            None => {
+                let context = ErrorContext::new(&url, "URL validation", "Url::new")
+                    .with_info(format!("Invalid internationalized domain name: {host}"));
+                Err(MarkdownError::ValidationError {
+                    kind: ValidationErrorKind::InvalidUrl,
+                    context,
+                })
+            }
+        }
+    }
 }
 
 impl fmt::Display for Url {
@@ -418,6 +470,7 @@ impl<'de> Deserialize<'de> for Url {
 
 /// Enumeration of supported URL types for content extraction.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum UrlType {
     /// Generic HTML pages
     Html,
@@ -425,8 +478,36 @@ pub enum UrlType {
     GoogleDocs,
     /// GitHub issues
     GitHubIssue,
+    /// Azure DevOps work items
+    AzureDevOps,
+    /// SharePoint and OneDrive documents
+    Office365,
+    /// Dropbox Paper documents and Dropbox shared files
+    Dropbox,
     /// Local file paths
     LocalFile,
+    /// Documents passed inline as `data:` URIs
+    DataUri,
+    /// Objects in S3, Google Cloud Storage, or Azure Blob Storage (`s3://`, `gs://`, `az://`)
+    CloudStorage,
+    /// Content on IPFS (`ipfs://`), fetched through HTTP gateways
+    Ipfs,
+    /// PDF documents
+    Pdf,
+    /// Jupyter notebooks (`.ipynb`)
+    Notebook,
+    /// AsciiDoc and reStructuredText sources (`.adoc`, `.rst`)
+    Markup,
+    /// Email messages and web archives (`.eml`, `.mhtml`)
+    Email,
+    /// Markdown sources (`.md`), passed through with normalization
+    Markdown,
+    /// An application-defined URL type, identified by name
+    ///
+    /// Custom types are assigned by patterns added with
+    /// `UrlDetector::add_pattern` and routed to converters registered with
+    /// `ConverterRegistry::register`.
+    Custom(String),
 }
 
 impl fmt::Display for UrlType {
@@ -435,7 +516,19 @@ impl fmt::Display for UrlType {
             UrlType::Html => write!(f, "HTML"),
             UrlType::GoogleDocs => write!(f, "Google Docs"),
             UrlType::GitHubIssue => write!(f, "GitHub Issue"),
+            UrlType::AzureDevOps => write!(f, "Azure DevOps Work Item"),
+            UrlType::Office365 => write!(f, "Office 365 Document"),
+            UrlType::Dropbox => write!(f, "Dropbox"),
             UrlType::LocalFile => write!(f, "Local File"),
+            UrlType::DataUri => write!(f, "Data URI"),
+            UrlType::CloudStorage => write!(f, "Cloud Storage"),
+            UrlType::Ipfs => write!(f, "IPFS"),
+            UrlType::Pdf => write!(f, "PDF"),
+            UrlType::Notebook => write!(f, "Jupyter Notebook"),
+            UrlType::Markup => write!(f, "Markup Document"),
+            UrlType::Email => write!(f, "Email Message"),
+            UrlType::Markdown => write!(f, "Markdown Source"),
+            UrlType::Custom(name) => write!(f, "{name}"),
         }
     }
 }
@@ -480,6 +573,7 @@ impl ErrorContext {
 
 /// Validation error kinds for input validation failures.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum ValidationErrorKind {
     InvalidUrl,
     InvalidFormat,
@@ -488,6 +582,7 @@ pub enum ValidationErrorKind {
 
 /// Network error kinds for connection and communication failures.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum NetworkErrorKind {
     Timeout,
     ConnectionFailed,
@@ -498,6 +593,7 @@ pub enum NetworkErrorKind {
 
 /// Authentication error kinds for authorization failures.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum AuthErrorKind {
     MissingToken,
     InvalidToken,
@@ -507,14 +603,19 @@ pub enum AuthErrorKind {
 
 /// Content error kinds for data processing failures.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum ContentErrorKind {
     EmptyContent,
     UnsupportedFormat,
     ParsingFailed,
+    SoftNotFound,
+    TooLarge,
+    AccessWall,
 }
 
 /// Converter error kinds for external tool and processing failures.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum ConverterErrorKind {
     ExternalToolFailed,
     ProcessingError,
@@ -523,14 +624,137 @@ pub enum ConverterErrorKind {
 
 /// Configuration error kinds for setup and configuration failures.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum ConfigErrorKind {
     InvalidConfig,
     MissingDependency,
     InvalidValue,
 }
 
+/// Reasons a URL was deliberately skipped by a configured content policy.
+///
+/// Skips are not failures: they record that a policy rule matched so batch
+/// reports can tell "filtered out" apart from "broken".
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum SkipReason {
+    /// The host matched a deny pattern
+    HostDenied { host: String, pattern: String },
+    /// An allowlist of hosts is configured and the host is not on it
+    HostNotAllowed { host: String },
+    /// An allowlist of hosts is configured and the URL has no host to check,
+    /// such as a local path or a `file:` or `data:` URL
+    NoHost { url: String },
+    /// The path matched a deny pattern
+    PathDenied { path: String, pattern: String },
+    /// An allowlist of paths is configured and the path matches none of them
+    PathNotAllowed { path: String },
+    /// The content type matched a deny pattern
+    MimeTypeDenied { mime_type: String, pattern: String },
+    /// An allowlist of content types is configured and the type is not on it
+    MimeTypeNotAllowed { mime_type: String },
+    /// The content is larger than the configured maximum
+    TooLarge { size: u64, limit: u64 },
+    /// The document date is before the start of the configured date window
+    TooOld {
+        date: DateTime<Utc>,
+        cutoff: DateTime<Utc>,
+    },
+    /// The document date is after the end of the configured date window
+    TooNew {
+        date: DateTime<Utc>,
+        cutoff: DateTime<Utc>,
+    },
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::HostDenied { host, pattern } => {
+                write!(f, "host '{host}' matches deny pattern '{pattern}'")
+            }
+            SkipReason::HostNotAllowed { host } => {
+                write!(f, "host '{host}' is not in the allowed host list")
+            }
+            SkipReason::NoHost { url } => {
+                write!(
+                    f,
+                    "'{url}' has no host to check against the allowed host list"
+                )
+            }
+            SkipReason::PathDenied { path, pattern } => {
+                write!(f, "path '{path}' matches deny pattern '{pattern}'")
+            }
+            SkipReason::PathNotAllowed { path } => {
+                write!(f, "path '{path}' matches no allowed path pattern")
+            }
+            SkipReason::MimeTypeDenied { mime_type, pattern } => {
+                write!(
+                    f,
+                    "content type '{mime_type}' matches deny pattern '{pattern}'"
+                )
+            }
+            SkipReason::MimeTypeNotAllowed { mime_type } => {
+                write!(f, "content type '{mime_type}' is not in the allowed type list")
+            }
+            SkipReason::TooLarge { size, limit } => {
+                write!(f, "content size {size} bytes exceeds limit of {limit} bytes")
+            }
+            SkipReason::TooOld { date, cutoff } => write!(
+                f,
+                "document date {} is before {}",
+                date.to_rfc3339(),
+                cutoff.to_rfc3339()
+            ),
+            SkipReason::TooNew { date, cutoff } => write!(
+                f,
+                "document date {} is after {}",
+                date.to_rfc3339(),
+                cutoff.to_rfc3339()
+            ),
+        }
+    }
+}
+
+/// A URL skipped by a configured content policy, carried by
+/// [`MarkdownError::Skipped`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkipDetails {
+    /// Which rule the URL broke
+    pub reason: SkipReason,
+    /// Where the URL was skipped
+    pub context: ErrorContext,
+}
+
+/// A record of why a URL could not be converted, for storing alongside output.
+///
+/// Batch conversions can write one in place of each document that fails, so
+/// downstream jobs that expect a file per URL can tell why one is missing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorReport {
+    /// The URL that failed
+    pub url: String,
+    /// The error category, such as `network` or `authentication`
+    pub category: String,
+    /// The error kind within the category, such as `Timeout`
+    pub kind: Option<String>,
+    /// The operation that failed, when known
+    pub operation: Option<String>,
+    /// What went wrong
+    pub message: String,
+    /// Whether converting the URL again later may succeed
+    pub retryable: bool,
+    /// Whether the URL points at missing content
+    pub dead_link: bool,
+    /// Suggestions for resolving the error
+    pub suggestions: Vec<String>,
+    /// When the conversion failed
+    pub failed_at: DateTime<Utc>,
+}
+
 /// Error types for the markdowndown library.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum MarkdownError {
     /// Validation errors for invalid input
     #[error("Validation error: {kind:?} - {context:?}")]
@@ -574,6 +798,12 @@ pub enum MarkdownError {
         context: ErrorContext,
     },
 
+    /// The URL was skipped by a configured content policy
+    ///
+    /// The details are boxed to keep `MarkdownError` small.
+    #[error("Skipped: {}", .0.reason)]
+    Skipped(Box<SkipDetails>),
+
     // Legacy error types for backward compatibility - keep the exact same names and structures
     /// Network-related errors
     #[error("Network error: {message}")]
@@ -606,10 +836,83 @@ impl MarkdownError {
             MarkdownError::ContentError { context, .. } => Some(context),
             MarkdownError::ConverterError { context, .. } => Some(context),
             MarkdownError::ConfigurationError { context, .. } => Some(context),
+            MarkdownError::Skipped(skip) => Some(&skip.context),
             _ => None,
         }
     }
 
+    /// Returns a serializable record of this error for a URL.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL whose conversion failed
+    pub fn report(&self, url: &str) -> ErrorReport {
+        let (category, kind) = match self {
+            MarkdownError::ValidationError { kind, .. } => {
+                ("validation", Some(format!("{kind:?}")))
+            }
+            MarkdownError::EnhancedNetworkError { kind, .. } => {
+                ("network", Some(format!("{kind:?}")))
+            }
+            MarkdownError::AuthenticationError { kind, .. } => {
+                ("authentication", Some(format!("{kind:?}")))
+            }
+            MarkdownError::ContentError { kind, .. } => ("content", Some(format!("{kind:?}"))),
+            MarkdownError::ConverterError { kind, .. } => ("converter", Some(format!("{kind:?}"))),
+            MarkdownError::ConfigurationError { kind, .. } => {
+                ("configuration", Some(format!("{kind:?}")))
+            }
+            MarkdownError::Skipped(_) => ("skipped", None),
+            MarkdownError::NetworkError { .. } => ("network", None),
+            MarkdownError::ParseError { .. } => ("parse", None),
+            MarkdownError::InvalidUrl { .. } => ("validation", Some("InvalidUrl".to_string())),
+            MarkdownError::AuthError { .. } => ("authentication", None),
+            MarkdownError::LegacyConfigurationError { .. } => ("configuration", None),
+        };
+        // Contextual errors display their context for debugging, so report its parts instead
+        let message = match self.context() {
+            Some(context) => context
+                .additional_info
+                .clone()
+                .unwrap_or_else(|| format!("{} failed", context.operation)),
+            None => self.to_string(),
+        };
+
+        ErrorReport {
+            url: url.to_string(),
+            category: category.to_string(),
+            kind,
+            operation: self.context().map(|context| context.operation.clone()),
+            message,
+            retryable: self.is_retryable(),
+            dead_link: self.is_dead_link(),
+            suggestions: self.suggestions(),
+            failed_at: Utc::now(),
+        }
+    }
+
+    /// Returns true if this error records a deliberate policy skip rather than a failure.
+    pub fn is_skip(&self) -> bool {
+        matches!(self, MarkdownError::Skipped(_))
+    }
+
+    /// Returns true if this error means the URL points at missing content.
+    ///
+    /// Covers hard 404/410 responses as well as soft 404s: pages that return
+    /// success but render a "not found" template.
+    pub fn is_dead_link(&self) -> bool {
+        matches!(
+            self,
+            MarkdownError::EnhancedNetworkError {
+                kind: NetworkErrorKind::ServerError(404 | 410),
+                ..
+            } | MarkdownError::ContentError {
+                kind: ContentErrorKind::SoftNotFound,
+                ..
+            }
+        )
+    }
+
     /// Returns true if this error is potentially retryable.
     pub fn is_retryable(&self) -> bool {
         match self {
@@ -726,10 +1029,25 @@ impl MarkdownError {
                     "Try using a different converter for this content type".to_string(),
                     "Check if the content format is supported".to_string(),
                 ],
+                ContentErrorKind::SoftNotFound => vec![
+                    "The page returned success but shows a \"not found\" message".to_string(),
+                    "Check whether the content has moved or been removed".to_string(),
+                ],
                 ContentErrorKind::ParsingFailed => vec![
                     "The content format may be corrupted or unsupported".to_string(),
                     "Try accessing the content directly to verify it's valid".to_string(),
                 ],
+                ContentErrorKind::TooLarge => vec![
+                    "The response is larger than the configured maximum".to_string(),
+                    "Raise max_response_bytes to download larger content".to_string(),
+                ],
+                ContentErrorKind::AccessWall => vec![
+                    "The page shows a paywall or sign-in prompt instead of its content"
+                        .to_string(),
+                    "Send your session cookie or token with default_headers or a request template"
+                        .to_string(),
+                    "Disable detect_access_wall to convert the visible teaser anyway".to_string(),
+                ],
             },
             MarkdownError::ConverterError { kind, .. } => match kind {
                 ConverterErrorKind::ExternalToolFailed => vec![
@@ -759,6 +1077,10 @@ impl MarkdownError {
                     "Refer to documentation for valid configuration options".to_string(),
                 ],
             },
+            MarkdownError::Skipped(_) => vec![
+                "The URL was filtered out by the configured allow/deny rules".to_string(),
+                "Adjust the filter configuration if this document should be converted".to_string(),
+            ],
             // Legacy error suggestions
             MarkdownError::NetworkError { .. } => vec![
                 "Check your internet connection".to_string(),
@@ -798,6 +1120,147 @@ pub struct Frontmatter {
     pub date_downloaded: DateTime<Utc>,
 }
 
+/// Whether a link points within the source document's site or away from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum LinkKind {
+    /// The link targets the same host as the source document
+    Internal,
+    /// The link targets another host or a non-HTTP scheme
+    External,
+}
+
+/// A link found in converted markdown.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutboundLink {
+    /// The absolute link target, resolved against the source URL
+    pub url: String,
+    /// The anchor text of the link
+    pub text: String,
+    /// Internal/external classification relative to the source URL
+    pub kind: LinkKind,
+}
+
+/// A heading of a converted document and the span of the section it opens.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Section {
+    /// Heading level, from 1 (`#`) to 6 (`######`)
+    pub level: u8,
+    /// The heading text without the leading `#` markers
+    pub text: String,
+    /// A unique, GitHub-style anchor for deep links (e.g. `getting-started`)
+    pub anchor: String,
+    /// Index of the enclosing section in the section list, if any
+    pub parent: Option<usize>,
+    /// Byte offset of the start of the heading line in the markdown
+    pub start: usize,
+    /// Byte offset where the section ends (the next heading of the same or a
+    /// higher level, or the end of the document)
+    pub end: usize,
+}
+
+/// A conversion result with the metadata gathered while converting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ConversionResult {
+    /// The converted markdown, including frontmatter when enabled
+    pub markdown: Markdown,
+    /// The detected URL type
+    pub url_type: UrlType,
+    /// The URL the first response came from, after redirects; `None` when
+    /// nothing was fetched over HTTP
+    pub final_url: Option<String>,
+    /// The status code of the first response; `None` when nothing was
+    /// fetched over HTTP
+    pub http_status: Option<u16>,
+    /// The document title from the frontmatter, if any
+    pub title: Option<String>,
+    /// Number of words in the document body, excluding frontmatter
+    pub word_count: usize,
+    /// Wall-clock time the conversion took
+    pub duration: std::time::Duration,
+    /// Problems that did not stop the conversion, such as a fallback to the
+    /// HTML converter or an image that could not be downloaded
+    pub warnings: Vec<String>,
+    /// Links found in the converted document, in document order
+    pub links: Vec<OutboundLink>,
+    /// Headings of the converted document, in document order; byte offsets
+    /// index into `markdown`
+    pub sections: Vec<Section>,
+    /// Problems the configured lint rules find in `markdown`
+    pub lint_warnings: Vec<crate::lint::LintWarning>,
+    /// Time and bytes per pipeline stage, when profiling is enabled
+    pub profile: Option<crate::profiling::ConversionProfile>,
+    /// Images downloaded for the document, when image downloading is enabled
+    pub assets: Vec<crate::assets::Asset>,
+}
+
+/// One part of a document made of several, such as a chapter of a PDF.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubDocument {
+    /// Position of the part in the document, starting at 1
+    pub index: usize,
+    /// The part's title, such as its outline entry, if it has one
+    pub title: Option<String>,
+    /// The part converted to markdown, including frontmatter when enabled
+    pub markdown: Markdown,
+}
+
+/// A pipeline step in a [`ConversionPlan`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlanStep {
+    /// Short name of the step, such as `convert` or `lint`
+    pub name: String,
+    /// What the step does for this URL
+    pub detail: String,
+    /// HTTP requests the step is expected to make
+    pub requests: usize,
+}
+
+/// What converting a URL will do, worked out without fetching anything.
+///
+/// Request counts are estimates for a conversion that succeeds first time:
+/// retries, result pages after the first, and fallbacks after a failure are
+/// not counted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConversionPlan {
+    /// The normalized URL
+    pub url: String,
+    /// The detected URL type
+    pub url_type: UrlType,
+    /// Name of the converter that handles the URL
+    pub converter: String,
+    /// Name of the converter used when the first one fails recoverably
+    pub fallback: Option<String>,
+    /// Whether the fallback runs at the same time as the converter
+    pub races_fallback: bool,
+    /// The filter rule that skips the URL before anything is fetched, if any
+    pub skipped: Option<SkipReason>,
+    /// The pipeline steps that run, in order
+    pub steps: Vec<PlanStep>,
+    /// Total HTTP requests the steps are expected to make
+    pub estimated_requests: usize,
+}
+
+impl fmt::Display for ConversionPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} ({})", self.url, self.url_type)?;
+        for (i, step) in self.steps.iter().enumerate() {
+            write!(f, "  {}. {}: {}", i + 1, step.name, step.detail)?;
+            match step.requests {
+                0 => writeln!(f)?,
+                1 => writeln!(f, " [1 request]")?,
+                n => writeln!(f, " [{n} requests]")?,
+            }
+        }
+        if let Some(reason) = &self.skipped {
+            writeln!(f, "Skipped: {reason}")?;
+        }
+        write!(f, "Estimated requests: {}", self.estimated_requests)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -979,6 +1442,18 @@ mod tests {
         assert_eq!(url.as_str(), "http://example.com");
     }
 
+    #[test]
+    fn test_url_new_internationalized_host() {
+        let url = Url::new("https://社内.例え.jp:8443/wiki?page=1".to_string()).unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://xn--v6q418g.xn--r8jz45g.jp:8443/wiki?page=1"
+        );
+        assert_eq!(url.host_unicode().as_deref(), Some("社内.例え.jp"));
+
+        assert!(Url::new("https://例え jp/".to_string()).is_err());
+    }
+
     #[test]
     fn test_url_new_invalid() {
         let result = Url::new("not-a-url".to_string());
@@ -1620,6 +2095,36 @@ mod tests {
                 assert_eq!(context.additional_info, deserialized.additional_info);
             }
 
+            #[test]
+            fn test_error_report() {
+                let url = "https://example.com/gone";
+                let error = MarkdownError::EnhancedNetworkError {
+                    kind: NetworkErrorKind::ServerError(404),
+                    context: ErrorContext::new(url, "HTTP request", "HttpClient")
+                        .with_info("HTTP 404 Not Found"),
+                };
+
+                let report = error.report(url);
+                assert_eq!(report.category, "network");
+                assert_eq!(report.kind.as_deref(), Some("ServerError(404)"));
+                assert_eq!(report.operation.as_deref(), Some("HTTP request"));
+                assert_eq!(report.message, "HTTP 404 Not Found");
+                assert!(!report.retryable);
+                assert!(report.dead_link);
+                assert_eq!(report.suggestions, error.suggestions());
+
+                let yaml = serde_yaml::to_string(&report).unwrap();
+                let deserialized: ErrorReport = serde_yaml::from_str(&yaml).unwrap();
+                assert_eq!(deserialized, report);
+
+                let legacy = MarkdownError::ParseError {
+                    message: "bad markup".to_string(),
+                };
+                let report = legacy.report(url);
+                assert_eq!((report.category.as_str(), report.kind), ("parse", None));
+                assert_eq!(report.message, "Parse error: bad markup");
+            }
+
             #[test]
             fn test_error_kind_serialization() {
                 // Test that all error kinds can be serialized/deserialized
@@ -2007,6 +2512,7 @@ mod tests {
                     ContentErrorKind::EmptyContent,
                     ContentErrorKind::UnsupportedFormat,
                     ContentErrorKind::ParsingFailed,
+                    ContentErrorKind::AccessWall,
                 ];
 
                 for kind in content_kinds {
@@ -2097,6 +2603,12 @@ mod tests {
                         kind: ConfigErrorKind::InvalidConfig, 
                         context: context.clone() 
                     },
+                    MarkdownError::Skipped(Box::new(SkipDetails {
+                        reason: SkipReason::HostNotAllowed {
+                            host: "example.com".to_string(),
+                        },
+                        context: context.clone(),
+                    })),
                 ];
 
                 for error in enhanced_errors {
@@ -2119,7 +2631,7 @@ mod tests {
                 assert_eq!(as_ref, content);
 
                 // Test Deref to str
-                let deref_str: &str = &*markdown;
+                let deref_str: &str = &markdown;
                 assert_eq!(deref_str, content);
 
                 // Test that we can use string methods directly on Markdown
@@ -2161,6 +2673,17 @@ mod tests {
                     (UrlType::GoogleDocs, "Google Docs"),
                     (UrlType::GitHubIssue, "GitHub Issue"),
                     (UrlType::LocalFile, "Local File"),
+                    (UrlType::DataUri, "Data URI"),
+                    (UrlType::CloudStorage, "Cloud Storage"),
+                    (UrlType::Ipfs, "IPFS"),
+                    (UrlType::AzureDevOps, "Azure DevOps Work Item"),
+                    (UrlType::Office365, "Office 365 Document"),
+                    (UrlType::Dropbox, "Dropbox"),
+                    (UrlType::Pdf, "PDF"),
+                    (UrlType::Notebook, "Jupyter Notebook"),
+                    (UrlType::Markup, "Markup Document"),
+                    (UrlType::Email, "Email Message"),
+                    (UrlType::Markdown, "Markdown Source"),
                 ];
 
                 for (variant, expected_display) in variants {
@@ -2168,7 +2691,7 @@ mod tests {
                     
                     // Test Debug as well
                     let debug_str = format!("{variant:?}");
-                    assert!(debug_str.contains(&variant.to_string()) || debug_str.contains("LocalFile") || debug_str.contains("Html") || debug_str.contains("GoogleDocs") || debug_str.contains("GitHubIssue"));
+                    assert!(debug_str.contains(&variant.to_string()) || debug_str.contains("LocalFile") || debug_str.contains("Html") || debug_str.contains("GoogleDocs") || debug_str.contains("GitHubIssue") || debug_str.contains("DataUri") || debug_str.contains("CloudStorage") || debug_str.contains("Ipfs") || debug_str.contains("AzureDevOps") || debug_str.contains("Office365") || debug_str.contains("Dropbox") || debug_str.contains("Pdf") || debug_str.contains("Notebook") || debug_str.contains("Markup") || debug_str.contains("Email") || debug_str.contains("Markdown"));
                 }
             }
 
@@ -2180,6 +2703,17 @@ mod tests {
                     UrlType::GoogleDocs,
                     UrlType::GitHubIssue,
                     UrlType::LocalFile,
+                    UrlType::DataUri,
+                    UrlType::CloudStorage,
+                    UrlType::Ipfs,
+                    UrlType::AzureDevOps,
+                    UrlType::Office365,
+                    UrlType::Dropbox,
+                    UrlType::Pdf,
+                    UrlType::Notebook,
+                    UrlType::Markup,
+                    UrlType::Email,
+                    UrlType::Markdown,
                 ];
 
                 for variant in variants {
@@ -2401,9 +2935,9 @@ Final paragraph with émojis 🚀 and Unicode characters: café, naïve, résum
                     // Note: The actual validation depends on the utils::is_local_file_path implementation
                     // This test covers the integration point even if some cases might fail
                     // depending on the utils implementation
-                    if url_result.is_err() {
+                    if let Err(error) = url_result {
                         // If it fails, it should be a ValidationError with InvalidUrl kind
-                        match url_result.unwrap_err() {
+                        match error {
                             MarkdownError::ValidationError { kind, context } => {
                                 assert_eq!(kind, ValidationErrorKind::InvalidUrl);
                                 assert_eq!(context.url, file_path);
@@ -1,5 +1,7 @@
 //! Utility functions shared across the codebase.
 
+use chrono::{DateTime, NaiveDate, Utc};
+
 /// Checks if a string represents a local file path or file:// URL.
 ///
 /// This function identifies various forms of local file paths:
@@ -152,10 +154,215 @@ pub fn is_local_file_path(input: &str) -> bool {
     false
 }
 
+/// Checks if a string is a `data:` URI carrying an inline document.
+///
+/// The scheme is matched case-insensitively; the rest of the URI is not validated.
+///
+/// # Arguments
+///
+/// * `input` - The string to check
+pub fn is_data_uri(input: &str) -> bool {
+    input
+        .trim()
+        .get(..5)
+        .is_some_and(|scheme| scheme.eq_ignore_ascii_case("data:"))
+}
+
+/// Checks if a string is an `ipfs://` URI naming content by its CID.
+///
+/// The scheme is matched case-insensitively, and a CID must follow it.
+///
+/// # Arguments
+///
+/// * `input` - The string to check
+pub fn is_ipfs_uri(input: &str) -> bool {
+    let trimmed = input.trim();
+    trimmed
+        .get(..7)
+        .is_some_and(|scheme| scheme.eq_ignore_ascii_case("ipfs://"))
+        && trimmed[7..]
+            .split(['/', '?', '#'])
+            .next()
+            .is_some_and(|cid| !cid.is_empty())
+}
+
+/// URI schemes of the cloud object stores that can be read directly.
+pub const CLOUD_STORAGE_SCHEMES: [&str; 3] = ["s3", "gs", "az"];
+
+/// Checks if a string is an object URI for Amazon S3 (`s3://`), Google Cloud
+/// Storage (`gs://`), or Azure Blob Storage (`az://`).
+///
+/// The scheme is matched case-insensitively, and a bucket or container name
+/// must follow it.
+///
+/// # Arguments
+///
+/// * `input` - The string to check
+pub fn is_cloud_storage_uri(input: &str) -> bool {
+    input
+        .trim()
+        .split_once("://")
+        .is_some_and(|(scheme, rest)| {
+            CLOUD_STORAGE_SCHEMES
+                .iter()
+                .any(|known| scheme.eq_ignore_ascii_case(known))
+                && !rest.is_empty()
+                && !rest.starts_with('/')
+        })
+}
+
+/// Parses a date as found in HTML metadata, HTTP headers, or user input.
+///
+/// Accepts RFC 3339 timestamps (`2024-01-15T10:30:00Z`), RFC 2822 / HTTP dates
+/// (`Mon, 15 Jan 2024 10:30:00 GMT`), and plain calendar dates (`2024-01-15`),
+/// which are taken as midnight UTC.
+///
+/// # Arguments
+///
+/// * `input` - The date string to parse
+///
+/// # Returns
+///
+/// The parsed date in UTC, or `None` if the input matches none of the formats.
+pub fn parse_date(input: &str) -> Option<DateTime<Utc>> {
+    let trimmed = input.trim();
+
+    if let Ok(date) = DateTime::parse_from_rfc3339(trimmed) {
+        return Some(date.with_timezone(&Utc));
+    }
+
+    if let Ok(date) = DateTime::parse_from_rfc2822(trimmed) {
+        return Some(date.with_timezone(&Utc));
+    }
+
+    // Plain dates, optionally followed by a time component we can't parse
+    let date_part = trimmed.get(..10).unwrap_or(trimmed);
+    NaiveDate::parse_from_str(date_part, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date| date.and_utc())
+}
+
+/// Returns the web host of a GitHub Enterprise Server from its API base URL.
+///
+/// GitHub Enterprise Server serves its API below the web host, at
+/// `https://<host>/api/v3`, so issue URLs share the API URL's host.
+///
+/// # Arguments
+///
+/// * `api_base_url` - The API base URL, such as `https://github.example.com/api/v3`
+///
+/// # Returns
+///
+/// The host, such as `github.example.com`, or `None` for github.com's own
+/// API or a URL without a host.
+pub fn github_enterprise_host(api_base_url: &str) -> Option<String> {
+    let parsed = url::Url::parse(api_base_url.trim()).ok()?;
+    let host = parsed.host_str()?.to_ascii_lowercase();
+    (host != "api.github.com" && host != "github.com").then_some(host)
+}
+
+/// Converts a host name to its ASCII form, punycode-encoding internationalized labels.
+///
+/// ASCII hosts are lowercased and otherwise returned unchanged.
+///
+/// # Arguments
+///
+/// * `host` - The host name, such as `例え.jp`
+///
+/// # Returns
+///
+/// The ASCII host, such as `xn--r8jz45g.jp`, or `None` if the host is not a valid domain name.
+pub fn host_to_ascii(host: &str) -> Option<String> {
+    idna::domain_to_ascii_cow(host.as_bytes(), idna::AsciiDenyList::URL)
+        .ok()
+        .map(|host| host.into_owned())
+}
+
+/// Converts a host name to its Unicode form, decoding punycode labels.
+///
+/// Labels that are not valid punycode are left as they are.
+///
+/// # Arguments
+///
+/// * `host` - The host name, such as `xn--r8jz45g.jp`
+pub fn host_to_unicode(host: &str) -> String {
+    idna::domain_to_unicode(host).0
+}
+
+/// Scripts whose letters are commonly confused with each other in host names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfusableScript {
+    Latin,
+    Greek,
+    Cyrillic,
+}
+
+impl ConfusableScript {
+    fn of(c: char) -> Option<Self> {
+        match c {
+            'a'..='z' | 'A'..='Z' | '\u{00C0}'..='\u{024F}' => Some(Self::Latin),
+            '\u{0370}'..='\u{03FF}' => Some(Self::Greek),
+            '\u{0400}'..='\u{052F}' => Some(Self::Cyrillic),
+            _ => None,
+        }
+    }
+}
+
+/// Checks whether a label of a host mixes Latin, Greek, or Cyrillic letters.
+///
+/// Mixing these scripts within one label, as in `pаypal.com` with a Cyrillic
+/// `а`, is the usual form of an IDN homograph attack. Other combinations,
+/// such as Japanese labels with Latin letters, are not flagged.
+///
+/// # Arguments
+///
+/// * `host` - The host name, in ASCII or Unicode form
+pub fn is_mixed_script_host(host: &str) -> bool {
+    host_to_unicode(host).split('.').any(|label| {
+        let mut scripts = label.chars().filter_map(ConfusableScript::of);
+        match scripts.next() {
+            Some(first) => scripts.any(|script| script != first),
+            None => false,
+        }
+    })
+}
+
+/// Finds an executable in the directories of the `PATH` environment variable.
+///
+/// # Arguments
+///
+/// * `program` - The executable's name, without the `.exe` suffix on Windows
+pub fn find_in_path(program: &str) -> Option<std::path::PathBuf> {
+    let file_name = format!("{program}{}", std::env::consts::EXE_SUFFIX);
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(&file_name))
+        .find(|candidate| candidate.is_file())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_host_idn_conversion() {
+        assert_eq!(host_to_ascii("例え.jp").as_deref(), Some("xn--r8jz45g.jp"));
+        assert_eq!(host_to_ascii("Example.COM").as_deref(), Some("example.com"));
+        assert_eq!(host_to_unicode("xn--r8jz45g.jp"), "例え.jp");
+        assert!(host_to_ascii("bad host.com").is_none());
+    }
+
+    #[test]
+    fn test_mixed_script_host() {
+        assert!(is_mixed_script_host("p\u{0430}ypal.com"));
+        assert!(is_mixed_script_host(
+            &host_to_ascii("p\u{0430}ypal.com").unwrap()
+        ));
+        assert!(!is_mixed_script_host("社内wiki.example.jp"));
+        assert!(!is_mixed_script_host("example.com"));
+    }
+
     #[test]
     fn test_unix_absolute_paths() {
         assert!(is_local_file_path("/path/to/file"));
@@ -243,4 +450,25 @@ mod tests {
         assert!(!is_local_file_path("simple"));
         assert!(!is_local_file_path("word"));
     }
+
+    #[test]
+    fn test_parse_date_formats() {
+        let expected = DateTime::parse_from_rfc3339("2024-01-15T10:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(parse_date("2024-01-15T10:30:00Z"), Some(expected));
+        assert_eq!(parse_date("2024-01-15T12:30:00+02:00"), Some(expected));
+        assert_eq!(parse_date("Mon, 15 Jan 2024 10:30:00 GMT"), Some(expected));
+        assert_eq!(
+            parse_date("2024-01-15"),
+            Some(
+                DateTime::parse_from_rfc3339("2024-01-15T00:00:00Z")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            )
+        );
+        assert_eq!(parse_date("not a date"), None);
+        assert_eq!(parse_date(""), None);
+    }
 }
@@ -0,0 +1,225 @@
+//! Wayback Machine snapshots of pages that are gone or unreachable.
+//!
+//! With [`crate::config::ConfigBuilder::wayback_fallback`] enabled, a URL
+//! that fails with a 404 or 410 response, a soft 404 page, or a timeout is
+//! looked up in the Internet Archive's
+//! [availability API](https://archive.org/help/wayback_api.php). When a
+//! snapshot exists, the most recent one is converted in its place and the
+//! document records where it came from in its frontmatter:
+//!
+//! - `archived_from`: the URL that could not be converted
+//! - `snapshot_timestamp`: when the snapshot was taken, in RFC 3339
+//! - `snapshot_url`: the archived copy that was converted
+//!
+//! Snapshots are fetched in their original form (the `id_` variant), without
+//! the Wayback Machine's toolbar and rewritten links.
+//!
+//! # Usage Examples
+//!
+//! ```rust,no_run
+//! use markdowndown::client::HttpClient;
+//! use markdowndown::config::DEFAULT_WAYBACK_API_URL;
+//! use markdowndown::wayback::latest_snapshot;
+//!
+//! # async fn example() -> Result<(), markdowndown::types::MarkdownError> {
+//! let client = HttpClient::new();
+//! if let Some(snapshot) =
+//!     latest_snapshot(&client, DEFAULT_WAYBACK_API_URL, "https://example.com/gone").await?
+//! {
+//!     println!("{} from {}", snapshot.url, snapshot.timestamp);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::HttpClient;
+use crate::types::{
+    ContentErrorKind, ErrorContext, MarkdownError, NetworkErrorKind, ValidationErrorKind,
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use url::Url;
+
+/// An archived copy of a page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Address of the snapshot, serving the page as originally archived
+    pub url: String,
+    /// When the snapshot was taken
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Returns true if a failed conversion may be served from an archived copy.
+///
+/// Dead links, both hard 404/410 responses and soft 404 pages, and timeouts
+/// qualify; other failures, such as authentication errors, do not.
+pub fn should_fall_back(error: &MarkdownError) -> bool {
+    error.is_dead_link()
+        || matches!(
+            error,
+            MarkdownError::EnhancedNetworkError {
+                kind: NetworkErrorKind::Timeout,
+                ..
+            }
+        )
+}
+
+/// Looks up the most recent snapshot of a URL.
+///
+/// # Arguments
+///
+/// * `client` - HTTP client used to query the availability API
+/// * `api_url` - The availability API endpoint, such as
+///   [`crate::config::DEFAULT_WAYBACK_API_URL`]
+/// * `url` - The URL to find a snapshot of
+///
+/// # Returns
+///
+/// The latest successful snapshot, or `None` if the page was never archived.
+///
+/// # Errors
+///
+/// * `MarkdownError::ValidationError` - If `api_url` is not a valid URL
+/// * `MarkdownError::ContentError` - If the API response is not JSON
+///   (`ParsingFailed`)
+/// * Any error of [`HttpClient::get_text`] from the API request
+pub async fn latest_snapshot(
+    client: &HttpClient,
+    api_url: &str,
+    url: &str,
+) -> Result<Option<Snapshot>, MarkdownError> {
+    let query = Url::parse_with_params(api_url, [("url", url)]).map_err(|e| {
+        MarkdownError::ValidationError {
+            kind: ValidationErrorKind::InvalidUrl,
+            context: ErrorContext::new(api_url, "Wayback lookup", "Wayback Machine")
+                .with_info(format!("Invalid availability API URL: {e}")),
+        }
+    })?;
+    let response = client.get_text(query.as_str()).await?;
+    parse_availability(&response).map_err(|e| MarkdownError::ContentError {
+        kind: ContentErrorKind::ParsingFailed,
+        context: ErrorContext::new(url, "Wayback lookup", "Wayback Machine")
+            .with_info(format!("Invalid availability API response: {e}")),
+    })
+}
+
+/// Reads the closest snapshot from an availability API response.
+///
+/// Snapshots that are unavailable, or that archived an error page, are
+/// ignored.
+fn parse_availability(response: &str) -> Result<Option<Snapshot>, serde_json::Error> {
+    let json: serde_json::Value = serde_json::from_str(response)?;
+    let closest = &json["archived_snapshots"]["closest"];
+    if closest["available"].as_bool() != Some(true) {
+        return Ok(None);
+    }
+    let archived_error = closest["status"]
+        .as_str()
+        .is_some_and(|status| !status.starts_with('2'));
+    if archived_error {
+        return Ok(None);
+    }
+    let (Some(url), Some(timestamp)) = (closest["url"].as_str(), closest["timestamp"].as_str())
+    else {
+        return Ok(None);
+    };
+    let Ok(timestamp) = NaiveDateTime::parse_from_str(timestamp, "%Y%m%d%H%M%S") else {
+        return Ok(None);
+    };
+    Ok(Some(Snapshot {
+        url: original_snapshot_url(url),
+        timestamp: timestamp.and_utc(),
+    }))
+}
+
+/// Rewrites a snapshot URL to serve the page as archived.
+///
+/// `/web/20240102030405/https://...` becomes `/web/20240102030405id_/https://...`,
+/// and Wayback Machine addresses are upgraded to HTTPS.
+fn original_snapshot_url(snapshot_url: &str) -> String {
+    let Ok(mut url) = Url::parse(snapshot_url) else {
+        return snapshot_url.to_string();
+    };
+    if url.scheme() == "http" && url.host_str() == Some("web.archive.org") {
+        let _ = url.set_scheme("https");
+    }
+    let path = url.path().to_string();
+    let Some(rest) = path.strip_prefix("/web/") else {
+        return url.to_string();
+    };
+    let (timestamp, archived) = rest.split_once('/').unwrap_or((rest, ""));
+    if timestamp.is_empty() || !timestamp.bytes().all(|b| b.is_ascii_digit()) {
+        return url.to_string();
+    }
+    url.set_path(&format!("/web/{timestamp}id_/{archived}"));
+    url.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_parse_availability() {
+        let response = r#"{"url": "example.com/gone", "archived_snapshots": {"closest": {
+            "status": "200", "available": true,
+            "url": "http://web.archive.org/web/20240102030405/https://example.com/gone?a=1",
+            "timestamp": "20240102030405"}}}"#;
+        let snapshot = parse_availability(response).unwrap().unwrap();
+        assert_eq!(
+            snapshot.url,
+            "https://web.archive.org/web/20240102030405id_/https://example.com/gone?a=1"
+        );
+        assert_eq!(snapshot.timestamp.to_rfc3339(), "2024-01-02T03:04:05+00:00");
+
+        let missing = r#"{"url": "example.com/new", "archived_snapshots": {}}"#;
+        assert_eq!(parse_availability(missing).unwrap(), None);
+        let archived_error = response.replace(r#""status": "200""#, r#""status": "404""#);
+        assert_eq!(parse_availability(&archived_error).unwrap(), None);
+        assert!(parse_availability("<html>").is_err());
+    }
+
+    #[test]
+    fn test_should_fall_back() {
+        let error = |kind| MarkdownError::EnhancedNetworkError {
+            kind,
+            context: ErrorContext::new("https://example.com", "Fetch", "test"),
+        };
+        assert!(should_fall_back(&error(NetworkErrorKind::ServerError(410))));
+        assert!(should_fall_back(&error(NetworkErrorKind::Timeout)));
+        assert!(!should_fall_back(&error(NetworkErrorKind::ServerError(
+            500
+        ))));
+        assert!(!should_fall_back(&error(NetworkErrorKind::DnsResolution)));
+    }
+
+    #[tokio::test]
+    async fn test_latest_snapshot() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wayback/available"))
+            .and(query_param("url", "https://example.com/gone"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                r#"{{"archived_snapshots": {{"closest": {{"available": true,
+                "url": "{}/web/20230506070809/https://example.com/gone",
+                "timestamp": "20230506070809", "status": "200"}}}}}}"#,
+                server.uri()
+            )))
+            .mount(&server)
+            .await;
+
+        let api_url = format!("{}/wayback/available", server.uri());
+        let snapshot = latest_snapshot(&HttpClient::new(), &api_url, "https://example.com/gone")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            snapshot.url,
+            format!(
+                "{}/web/20230506070809id_/https://example.com/gone",
+                server.uri()
+            )
+        );
+    }
+}
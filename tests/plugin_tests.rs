@@ -0,0 +1,91 @@
+//! Converter plugin registration.
+//!
+//! Plugins registered with `register_converter!` are installed into every
+//! `MarkdownDown` of the binary, so these tests live in their own test binary.
+
+use async_trait::async_trait;
+use markdowndown::converters::plugin::plugins;
+use markdowndown::converters::Converter;
+use markdowndown::types::{Markdown, MarkdownError, UrlType};
+use markdowndown::{register_converter, Config, MarkdownDown};
+
+struct TimeoutConverter(u64);
+
+#[async_trait]
+impl Converter for TimeoutConverter {
+    async fn convert(&self, _url: &str) -> Result<Markdown, MarkdownError> {
+        Markdown::new(format!("Timeout {}", self.0))
+    }
+
+    fn name(&self) -> &'static str {
+        "Timeout"
+    }
+}
+
+struct FixedConverter;
+
+#[async_trait]
+impl Converter for FixedConverter {
+    async fn convert(&self, _url: &str) -> Result<Markdown, MarkdownError> {
+        Markdown::new("Fixed".to_string())
+    }
+
+    fn name(&self) -> &'static str {
+        "FixedConverter"
+    }
+}
+
+register_converter! {
+    url_type: UrlType::Custom("plugin-test".into()),
+    converter: |config| TimeoutConverter(config.http.timeout.as_secs()),
+    patterns: [("plugins.example.com", Some("/docs/")), ("*.plugins.example.org", None)],
+}
+
+register_converter! {
+    url_type: UrlType::Custom("plugin-fixed".into()),
+    converter: FixedConverter,
+}
+
+/// Test that a registered plugin is installed with the instance's configuration
+#[tokio::test]
+async fn test_registered_plugin_is_installed_with_config() {
+    let config = Config::builder().timeout_seconds(42).build();
+    let md = MarkdownDown::with_config(config);
+    let url_type = UrlType::Custom("plugin-test".into());
+
+    for url in [
+        "https://plugins.example.com/docs/intro",
+        "https://a.plugins.example.org/page",
+    ] {
+        assert_eq!(md.detector().detect_type(url).unwrap(), url_type);
+    }
+    assert_eq!(
+        md.detector()
+            .detect_type("https://plugins.example.com/blog")
+            .unwrap(),
+        UrlType::Html
+    );
+
+    let markdown = md
+        .convert_url("https://plugins.example.com/docs/intro")
+        .await
+        .unwrap();
+    assert!(markdown.as_str().contains("Timeout 42"));
+}
+
+/// Test that default instances pick up plugins too, including those without patterns
+#[test]
+fn test_default_instance_lists_plugins() {
+    let md = MarkdownDown::new();
+    let supported = md.supported_types();
+
+    assert!(supported.contains(&UrlType::Custom("plugin-test".into())));
+    assert!(supported.contains(&UrlType::Custom("plugin-fixed".into())));
+    assert_eq!(
+        md.registry()
+            .get_converter(&UrlType::Custom("plugin-fixed".into()))
+            .map(|c| c.name()),
+        Some("FixedConverter")
+    );
+    assert_eq!(plugins().count(), 2);
+}
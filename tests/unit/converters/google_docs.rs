@@ -4,9 +4,9 @@
 //! export API integration, error handling, and document format processing.
 
 use markdowndown::client::HttpClient;
-use markdowndown::config::Config;
+use markdowndown::config::{CommentMode, Config, GoogleDocsExportFormat};
 use markdowndown::converters::{Converter, GoogleDocsConverter};
-use markdowndown::types::{MarkdownError, NetworkErrorKind};
+use markdowndown::types::{ContentErrorKind, MarkdownError, NetworkErrorKind};
 use mockito::Server;
 
 mod helpers {
@@ -386,6 +386,145 @@ mod google_docs_conversion_tests {
         assert!(content.contains("<strong>Budget Review</strong>"));
     }
 
+    #[tokio::test]
+    async fn test_convert_google_docs_with_html_export_format() {
+        let mut server = Server::new_async().await;
+        let html_content = helpers::sample_google_docs_html();
+
+        let html_mock = server
+            .mock(
+                "GET",
+                "/document/d/1BxiMVs0XRA5nFMdKvBdBZjgmUUqptlbs74OgvHTML/export",
+            )
+            .match_query(mockito::Matcher::UrlEncoded("format".into(), "html".into()))
+            .with_status(200)
+            .with_header("content-type", "text/html; charset=utf-8")
+            .with_body(html_content)
+            .create_async()
+            .await;
+
+        let converter = GoogleDocsConverter::with_client(HttpClient::new())
+            .with_export_format(GoogleDocsExportFormat::Html);
+        let export_url = format!(
+            "{}/document/d/1BxiMVs0XRA5nFMdKvBdBZjgmUUqptlbs74OgvHTML/export?format=html",
+            server.url()
+        );
+        let markdown = converter.convert(&export_url).await.unwrap();
+
+        html_mock.assert_async().await;
+        let content = markdown.content_only();
+
+        // The HTML export goes through the HTML pipeline, keeping its structure
+        assert!(content.contains("# Meeting Notes - Q4 Planning"));
+        assert!(content.contains("## Agenda Items"));
+        assert!(content.contains("**Budget Review**"));
+        assert!(!content.contains("<h1>"));
+    }
+
+    #[tokio::test]
+    async fn test_convert_drive_link_to_text_file() {
+        let mut server = Server::new_async().await;
+        let file_id = "1BxiMVs0XRA5nFMdKvBdBZjgmUUqptlbs74OgvTEXT";
+
+        let metadata_mock = server
+            .mock("GET", format!("/files/{file_id}").as_str())
+            .match_query(mockito::Matcher::UrlEncoded(
+                "fields".into(),
+                "id,name,mimeType".into(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(
+                r#"{{"id": "{file_id}", "name": "notes.txt", "mimeType": "text/plain"}}"#
+            ))
+            .create_async()
+            .await;
+        let download_mock = server
+            .mock("GET", format!("/files/{file_id}").as_str())
+            .match_query(mockito::Matcher::UrlEncoded("alt".into(), "media".into()))
+            .with_status(200)
+            .with_body("Plain notes from Drive")
+            .create_async()
+            .await;
+
+        let converter = GoogleDocsConverter::with_client(HttpClient::new())
+            .with_drive_api_base_url(server.url());
+        for url in [
+            format!("https://drive.google.com/file/d/{file_id}/view?usp=sharing"),
+            format!("https://drive.google.com/open?id={file_id}"),
+        ] {
+            let markdown = converter.convert(&url).await.unwrap();
+            assert_eq!(markdown.content_only(), "Plain notes from Drive");
+
+            let frontmatter = markdown.frontmatter().unwrap();
+            assert!(frontmatter.contains("conversion_type: google_drive"));
+            assert!(frontmatter.contains("title: notes.txt"));
+            assert!(frontmatter.contains("mime_type: text/plain"));
+        }
+
+        metadata_mock.expect(2).assert_async().await;
+        download_mock.expect(2).assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_convert_drive_link_with_unsupported_type() {
+        let mut server = Server::new_async().await;
+        let file_id = "1BxiMVs0XRA5nFMdKvBdBZjgmUUqptlbs74OgvZIPS";
+
+        let _metadata_mock = server
+            .mock("GET", format!("/files/{file_id}").as_str())
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(format!(
+                r#"{{"id": "{file_id}", "name": "photos.zip", "mimeType": "application/zip"}}"#
+            ))
+            .create_async()
+            .await;
+
+        let converter = GoogleDocsConverter::with_client(HttpClient::new())
+            .with_drive_api_base_url(server.url());
+        let url = format!("https://drive.google.com/file/d/{file_id}/view");
+        match converter.convert(&url).await.unwrap_err() {
+            MarkdownError::ContentError { kind, context } => {
+                assert_eq!(kind, ContentErrorKind::UnsupportedFormat);
+                assert!(context.additional_info.unwrap().contains("application/zip"));
+            }
+            other => panic!("Expected ContentError, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_convert_google_docs_with_comments_as_footnotes() {
+        let mut server = Server::new_async().await;
+        let html_content = r##"<html><body><p><span>Ship in March.</span><sup><a href="#cmnt1" id="cmnt_ref1">[a]</a></sup></p><div><p><a href="#cmnt_ref1" id="cmnt1">[a]</a><span>Is March realistic?</span></p></div></body></html>"##;
+
+        let html_mock = server
+            .mock(
+                "GET",
+                "/document/d/1BxiMVs0XRA5nFMdKvBdBZjgmUUqptlbs74OgvCMNT/export",
+            )
+            .match_query(mockito::Matcher::UrlEncoded("format".into(), "html".into()))
+            .with_status(200)
+            .with_header("content-type", "text/html; charset=utf-8")
+            .with_body(html_content)
+            .create_async()
+            .await;
+
+        let converter = GoogleDocsConverter::with_client(HttpClient::new())
+            .with_comment_mode(CommentMode::Footnotes);
+        let export_url = format!(
+            "{}/document/d/1BxiMVs0XRA5nFMdKvBdBZjgmUUqptlbs74OgvCMNT/export?format=html",
+            server.url()
+        );
+        let markdown = converter.convert(&export_url).await.unwrap();
+
+        html_mock.assert_async().await;
+        let content = markdown.content_only();
+        assert!(content.contains("Ship in March.[^c1]"));
+        assert!(content.contains("[^c1]: Is March realistic?"));
+        assert!(!content.contains("<p>"));
+    }
+
     #[tokio::test]
     async fn test_convert_google_docs_with_special_characters() {
         let mut server = Server::new_async().await;
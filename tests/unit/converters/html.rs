@@ -125,6 +125,14 @@ mod converter_creation_tests {
             remove_sidebars: true,
             remove_ads: true,
             max_blank_lines: 1,
+            detect_soft_404: true,
+            detect_access_wall: true,
+            detect_login_walls: false,
+            escape_mode: markdowndown::converters::EscapeMode::Minimal,
+            passthrough_unconvertible: false,
+            summarize_forms: false,
+            detection_strategy: markdowndown::converters::DetectionStrategy::Url,
+            plain_text: markdowndown::converters::PlainTextHeuristics::default(),
         };
         let output_config = markdowndown::config::OutputConfig::default();
         let converter = HtmlConverter::with_config(client, config, output_config);
@@ -555,6 +563,14 @@ mod configuration_tests {
             remove_sidebars: false,
             remove_ads: false,
             max_blank_lines: 5,
+            detect_soft_404: true,
+            detect_access_wall: true,
+            detect_login_walls: false,
+            escape_mode: markdowndown::converters::EscapeMode::Minimal,
+            passthrough_unconvertible: false,
+            summarize_forms: false,
+            detection_strategy: markdowndown::converters::DetectionStrategy::Url,
+            plain_text: markdowndown::converters::PlainTextHeuristics::default(),
         };
 
         assert_eq!(config.max_line_width, 100);
@@ -588,6 +604,14 @@ mod configuration_tests {
             remove_sidebars: false,
             remove_ads: false,
             max_blank_lines: 10,
+            detect_soft_404: true,
+            detect_access_wall: true,
+            detect_login_walls: false,
+            escape_mode: markdowndown::converters::EscapeMode::Minimal,
+            passthrough_unconvertible: false,
+            summarize_forms: false,
+            detection_strategy: markdowndown::converters::DetectionStrategy::Url,
+            plain_text: markdowndown::converters::PlainTextHeuristics::default(),
         };
         let output_config = markdowndown::config::OutputConfig::default();
         let converter = HtmlConverter::with_config(client, html_config, output_config);
@@ -638,10 +662,14 @@ mod frontmatter_tests {
         
         // Enable frontmatter and add custom fields
         let html_config = HtmlConverterConfig::default();
-        let mut output_config = markdowndown::config::OutputConfig::default();
-        output_config.include_frontmatter = true;
-        output_config.custom_frontmatter_fields.push(("custom_field".to_string(), "custom_value".to_string()));
-        output_config.custom_frontmatter_fields.push(("author".to_string(), "test_author".to_string()));
+        let output_config = markdowndown::config::OutputConfig {
+            include_frontmatter: true,
+            custom_frontmatter_fields: vec![
+                ("custom_field".to_string(), "custom_value".to_string()),
+                ("author".to_string(), "test_author".to_string()),
+            ],
+            ..Default::default()
+        };
         
         let converter = HtmlConverter::with_config(client, html_config, output_config);
 
@@ -696,8 +724,10 @@ mod frontmatter_tests {
         
         // Disable frontmatter
         let html_config = HtmlConverterConfig::default();
-        let mut output_config = markdowndown::config::OutputConfig::default();
-        output_config.include_frontmatter = false;
+        let output_config = markdowndown::config::OutputConfig {
+            include_frontmatter: false,
+            ..Default::default()
+        };
         
         let converter = HtmlConverter::with_config(client, html_config, output_config);
 
@@ -740,8 +770,10 @@ mod frontmatter_tests {
         let client = HttpClient::with_config(&config.http, &config.auth);
         
         let html_config = HtmlConverterConfig::default();
-        let mut output_config = markdowndown::config::OutputConfig::default();
-        output_config.include_frontmatter = true;
+        let output_config = markdowndown::config::OutputConfig {
+            include_frontmatter: true,
+            ..Default::default()
+        };
         
         let converter = HtmlConverter::with_config(client, html_config, output_config);
 
@@ -787,10 +819,14 @@ mod frontmatter_tests {
         
         // Enable frontmatter and add custom fields
         let html_config = HtmlConverterConfig::default();
-        let mut output_config = markdowndown::config::OutputConfig::default();
-        output_config.include_frontmatter = true;
-        output_config.custom_frontmatter_fields.push(("custom_field".to_string(), "custom_value".to_string()));
-        output_config.custom_frontmatter_fields.push(("author".to_string(), "test_author".to_string()));
+        let output_config = markdowndown::config::OutputConfig {
+            include_frontmatter: true,
+            custom_frontmatter_fields: vec![
+                ("custom_field".to_string(), "custom_value".to_string()),
+                ("author".to_string(), "test_author".to_string()),
+            ],
+            ..Default::default()
+        };
         
         let converter = HtmlConverter::with_config(client, html_config, output_config);
 
@@ -843,8 +879,10 @@ mod frontmatter_tests {
         let client = HttpClient::with_config(&config.http, &config.auth);
         
         let html_config = HtmlConverterConfig::default();
-        let mut output_config = markdowndown::config::OutputConfig::default();
-        output_config.include_frontmatter = true;
+        let output_config = markdowndown::config::OutputConfig {
+            include_frontmatter: true,
+            ..Default::default()
+        };
         
         let converter = HtmlConverter::with_config(client, html_config, output_config);
 
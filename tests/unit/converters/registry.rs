@@ -43,7 +43,25 @@ mod helpers {
                 UrlType::GitHubIssue,
                 "https://github.com/owner/repo/issues/123",
             ),
+            (
+                UrlType::AzureDevOps,
+                "https://dev.azure.com/org/project/_workitems/edit/123",
+            ),
+            (
+                UrlType::Office365,
+                "https://contoso.sharepoint.com/:w:/s/team/EaBcDeFgHiJk",
+            ),
+            (
+                UrlType::Dropbox,
+                "https://www.dropbox.com/s/a1b2c3d4e5/report.pdf?dl=0",
+            ),
             (UrlType::LocalFile, "/path/to/test.md"),
+            (UrlType::DataUri, "data:text/markdown,%23%20Inline"),
+            (UrlType::CloudStorage, "s3://bucket/notes.md"),
+            (
+                UrlType::Ipfs,
+                "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+            ),
         ]
     }
 }
@@ -62,7 +80,18 @@ mod registry_creation_tests {
         assert!(supported_types.contains(&UrlType::GoogleDocs));
         assert!(supported_types.contains(&UrlType::GitHubIssue));
         assert!(supported_types.contains(&UrlType::LocalFile));
-        assert_eq!(supported_types.len(), 4);
+        assert!(supported_types.contains(&UrlType::DataUri));
+        assert!(supported_types.contains(&UrlType::CloudStorage));
+        assert!(supported_types.contains(&UrlType::Ipfs));
+        assert!(supported_types.contains(&UrlType::AzureDevOps));
+        assert!(supported_types.contains(&UrlType::Office365));
+        assert!(supported_types.contains(&UrlType::Dropbox));
+        assert!(supported_types.contains(&UrlType::Pdf));
+        assert!(supported_types.contains(&UrlType::Notebook));
+        assert!(supported_types.contains(&UrlType::Markup));
+        assert!(supported_types.contains(&UrlType::Email));
+        assert!(supported_types.contains(&UrlType::Markdown));
+        assert_eq!(supported_types.len(), 15);
     }
 
     #[test]
@@ -75,7 +104,7 @@ mod registry_creation_tests {
         assert!(supported_types.contains(&UrlType::GoogleDocs));
         assert!(supported_types.contains(&UrlType::GitHubIssue));
         assert!(supported_types.contains(&UrlType::LocalFile));
-        assert_eq!(supported_types.len(), 4);
+        assert_eq!(supported_types.len(), 15);
     }
 
     #[test]
@@ -93,6 +122,14 @@ mod registry_creation_tests {
             remove_sidebars: true,
             remove_ads: true,
             max_blank_lines: 1,
+            detect_soft_404: true,
+            detect_access_wall: true,
+            detect_login_walls: false,
+            escape_mode: markdowndown::converters::EscapeMode::Minimal,
+            passthrough_unconvertible: false,
+            summarize_forms: false,
+            detection_strategy: markdowndown::converters::DetectionStrategy::Url,
+            plain_text: markdowndown::converters::PlainTextHeuristics::default(),
         };
         let output_config = markdowndown::config::OutputConfig::default();
 
@@ -100,7 +137,7 @@ mod registry_creation_tests {
         let supported_types = registry.supported_types();
 
         // Should support all URL types with custom configuration
-        assert_eq!(supported_types.len(), 4);
+        assert_eq!(supported_types.len(), 15);
         assert!(supported_types.contains(&UrlType::Html));
         assert!(supported_types.contains(&UrlType::GoogleDocs));
         assert!(supported_types.contains(&UrlType::GitHubIssue));
@@ -126,7 +163,21 @@ mod converter_management_tests {
                 UrlType::Html => assert_eq!(converter.name(), "HTML"),
                 UrlType::GoogleDocs => assert_eq!(converter.name(), "Google Docs"),
                 UrlType::GitHubIssue => assert_eq!(converter.name(), "GitHub Issue"),
+                UrlType::AzureDevOps => assert_eq!(converter.name(), "Azure DevOps Work Item"),
+                UrlType::Office365 => assert_eq!(converter.name(), "Office 365"),
+                UrlType::Dropbox => assert_eq!(converter.name(), "Dropbox"),
                 UrlType::LocalFile => assert_eq!(converter.name(), "Local File Converter"),
+                UrlType::DataUri => assert_eq!(converter.name(), "Data URI Converter"),
+                UrlType::CloudStorage => {
+                    assert_eq!(converter.name(), "Cloud Storage Converter")
+                }
+                UrlType::Ipfs => assert_eq!(converter.name(), "IPFS Converter"),
+                UrlType::Pdf => assert_eq!(converter.name(), "PDF"),
+                UrlType::Notebook => assert_eq!(converter.name(), "Jupyter Notebook"),
+                UrlType::Markup => assert_eq!(converter.name(), "Markup Document"),
+                UrlType::Email => assert_eq!(converter.name(), "Email Message"),
+                UrlType::Markdown => assert_eq!(converter.name(), "Markdown Source"),
+                other => panic!("Unexpected URL type: {other}"),
             }
         }
     }
@@ -135,8 +186,11 @@ mod converter_management_tests {
     fn test_get_converter_nonexistent_type() {
         let registry = helpers::create_test_registry();
 
-        // Create a mock URL type that doesn't exist in the registry
-        // Since all UrlType variants are covered, we'll test retrieval logic
+        // Custom URL types have no converter until one is registered
+        assert!(registry
+            .get_converter(&UrlType::Custom("unregistered".to_string()))
+            .is_none());
+
         let supported_types = registry.supported_types();
         assert!(!supported_types.is_empty());
 
@@ -238,7 +292,7 @@ mod configuration_propagation_tests {
 
         // All converters should be present
         let supported_types = registry.supported_types();
-        assert_eq!(supported_types.len(), 4);
+        assert_eq!(supported_types.len(), 15);
 
         // Verify each converter is accessible
         for url_type in supported_types {
@@ -326,7 +380,7 @@ mod error_handling_tests {
 
         // Registry should still have same number of converters
         let supported_types = registry.supported_types();
-        assert_eq!(supported_types.len(), 4);
+        assert_eq!(supported_types.len(), 15);
     }
 }
 
@@ -439,6 +493,14 @@ mod integration_tests {
             remove_sidebars: false,
             remove_ads: false,
             max_blank_lines: 5,
+            detect_soft_404: true,
+            detect_access_wall: true,
+            detect_login_walls: false,
+            escape_mode: markdowndown::converters::EscapeMode::Minimal,
+            passthrough_unconvertible: false,
+            summarize_forms: false,
+            detection_strategy: markdowndown::converters::DetectionStrategy::Url,
+            plain_text: markdowndown::converters::PlainTextHeuristics::default(),
         };
         let output_config = markdowndown::config::OutputConfig::default();
 
@@ -446,7 +508,7 @@ mod integration_tests {
 
         // Verify all converters are properly configured
         let supported_types = registry.supported_types();
-        assert_eq!(supported_types.len(), 4);
+        assert_eq!(supported_types.len(), 15);
 
         for url_type in supported_types {
             let converter = registry.get_converter(&url_type);
@@ -458,7 +520,21 @@ mod integration_tests {
                 UrlType::Html => assert_eq!(converter.name(), "HTML"),
                 UrlType::GoogleDocs => assert_eq!(converter.name(), "Google Docs"),
                 UrlType::GitHubIssue => assert_eq!(converter.name(), "GitHub Issue"),
+                UrlType::AzureDevOps => assert_eq!(converter.name(), "Azure DevOps Work Item"),
+                UrlType::Office365 => assert_eq!(converter.name(), "Office 365"),
+                UrlType::Dropbox => assert_eq!(converter.name(), "Dropbox"),
                 UrlType::LocalFile => assert_eq!(converter.name(), "Local File Converter"),
+                UrlType::DataUri => assert_eq!(converter.name(), "Data URI Converter"),
+                UrlType::CloudStorage => {
+                    assert_eq!(converter.name(), "Cloud Storage Converter")
+                }
+                UrlType::Ipfs => assert_eq!(converter.name(), "IPFS Converter"),
+                UrlType::Pdf => assert_eq!(converter.name(), "PDF"),
+                UrlType::Notebook => assert_eq!(converter.name(), "Jupyter Notebook"),
+                UrlType::Markup => assert_eq!(converter.name(), "Markup Document"),
+                UrlType::Email => assert_eq!(converter.name(), "Email Message"),
+                UrlType::Markdown => assert_eq!(converter.name(), "Markdown Source"),
+                other => panic!("Unexpected URL type: {other}"),
             }
         }
     }
@@ -539,6 +615,60 @@ mod extensibility_tests {
             "GitHub Issue"
         );
     }
+
+    #[test]
+    fn test_register_returns_replaced_and_unregister_removes() {
+        let mut registry = ConverterRegistry::new();
+
+        let replaced = registry.register(UrlType::Html, Box::new(MockConverter::new("First")));
+        assert_eq!(replaced.map(|c| c.name()), Some("HTML"));
+
+        let removed = registry.unregister(&UrlType::Html);
+        assert_eq!(removed.map(|c| c.name()), Some("First"));
+        assert!(registry.get_converter(&UrlType::Html).is_none());
+        assert!(registry.unregister(&UrlType::Html).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_markdowndown_with_converter_routes_to_custom_converter() {
+        let md = markdowndown::MarkdownDown::new()
+            .with_converter(UrlType::Html, Box::new(MockConverter::new("Plugin")));
+
+        let result = md.convert_url("https://example.com/page").await.unwrap();
+        assert!(result.content_only().contains("Mock conversion by Plugin"));
+    }
+
+    #[tokio::test]
+    async fn test_custom_url_type_routing_and_html_fallback() {
+        let wiki = UrlType::Custom("wiki".to_string());
+        let md = markdowndown::MarkdownDown::new()
+            .with_url_pattern("wiki.example.com", None, wiki.clone())
+            .with_converter(wiki.clone(), Box::new(MockConverter::new("Wiki")));
+
+        let result = md.convert_url("https://wiki.example.com/page").await.unwrap();
+        assert!(result.content_only().contains("Mock conversion by Wiki"));
+
+        // A custom type without a converter is converted as HTML
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/page")
+            .with_status(200)
+            .with_body("<h1>Plain page</h1>")
+            .create_async()
+            .await;
+        let md = markdowndown::MarkdownDown::new().with_url_pattern(
+            "127.0.0.1",
+            None,
+            UrlType::Custom("unhandled".to_string()),
+        );
+
+        let result = md
+            .convert_url(&format!("{}/page", server.url()))
+            .await
+            .unwrap();
+        assert!(result.content_only().contains("Plain page"));
+        mock.assert_async().await;
+    }
 }
 
 /// Performance and stress tests
@@ -181,7 +181,7 @@ mod markdowndown_creation_tests {
         let _registry = md.registry();
         let types = md.supported_types();
 
-        assert_eq!(types.len(), 4); // HTML, GoogleDocs, GitHubIssue, LocalFile
+        assert_eq!(types.len(), 15); // HTML, GoogleDocs, GitHubIssue, AzureDevOps, Office365, Dropbox, LocalFile, DataUri, CloudStorage, IPFS, PDF, Notebook, Markup, Email, Markdown
     }
 }
 
@@ -283,6 +283,7 @@ mod end_to_end_conversion_tests {
         // Mock GitHub API response for comments
         let comments_mock = server
             .mock("GET", "/repos/owner/repo/issues/1234/comments")
+            .match_query(mockito::Matcher::UrlEncoded("per_page".into(), "100".into()))
             .match_header("Accept", "application/vnd.github.v3+json")
             .with_status(200)
             .with_header("content-type", "application/json")
@@ -610,7 +611,7 @@ mod convenience_function_tests {
 
         let office_result = detect_url_type("https://company.sharepoint.com/doc.docx");
         assert!(office_result.is_ok());
-        assert_eq!(office_result.unwrap(), UrlType::Html);
+        assert_eq!(office_result.unwrap(), UrlType::Office365);
 
         let github_result = detect_url_type("https://github.com/owner/repo/issues/123");
         assert!(github_result.is_ok());
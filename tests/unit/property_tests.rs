@@ -495,6 +495,14 @@ mod html_converter_properties {
                 remove_sidebars,
                 remove_ads,
                 max_blank_lines,
+                detect_soft_404: true,
+                detect_access_wall: true,
+                detect_login_walls: false,
+                escape_mode: markdowndown::converters::EscapeMode::Minimal,
+                passthrough_unconvertible: false,
+                summarize_forms: false,
+                detection_strategy: markdowndown::converters::DetectionStrategy::Url,
+                plain_text: markdowndown::converters::PlainTextHeuristics::default(),
             };
 
             // Configuration should be stored correctly
@@ -524,6 +532,14 @@ mod html_converter_properties {
                 remove_sidebars: true,
                 remove_ads: false,
                 max_blank_lines,
+                detect_soft_404: true,
+                detect_access_wall: true,
+                detect_login_walls: false,
+                escape_mode: markdowndown::converters::EscapeMode::Minimal,
+                passthrough_unconvertible: false,
+                summarize_forms: false,
+                detection_strategy: markdowndown::converters::DetectionStrategy::Url,
+                plain_text: markdowndown::converters::PlainTextHeuristics::default(),
             };
 
             let cloned_config = original_config.clone();
@@ -576,8 +592,8 @@ mod markdowndown_api_properties {
         let result2 = detect_url_type(url);
 
         assert_eq!(result1.is_ok(), result2.is_ok());
-        if result1.is_ok() && result2.is_ok() {
-            assert_eq!(result1.unwrap(), result2.unwrap());
+        if let (Ok(type1), Ok(type2)) = (result1, result2) {
+            assert_eq!(type1, type2);
         }
     }
 }